@@ -15,7 +15,7 @@ for entry in adj.entries {
 # Ok(()) }
 ```
  */
-use crate::mesh_data::{MeshObjectData, VectorData};
+use crate::mesh_data::{MeshData, MeshObjectData, VectorData};
 use itertools::Itertools;
 use ssbh_lib::formats::adj::{Adj, AdjEntry};
 use std::convert::TryFrom;
@@ -30,7 +30,8 @@ const MAX_ADJACENT_VERTICES: usize = 18;
 pub mod error {
     use thiserror::Error;
 
-    /// Errors while creating an [Adj](super::Adj) from [AdjData](super::AdjData).
+    /// Errors while creating an [Adj](super::Adj) from [AdjData](super::AdjData)
+    /// or validating [AdjData](super::AdjData).
     #[derive(Debug, Error)]
     pub enum Error {
         /// An error occurred while writing data to a buffer.
@@ -48,6 +49,115 @@ pub mod error {
             end: usize,
             buffer_size: usize,
         },
+
+        /// No mesh object has the given name.
+        #[error("no mesh object named {:?} found in the mesh", name)]
+        MissingMeshObject { name: String },
+
+        /// The entry's mesh object index is out of range for the mesh.
+        #[error(
+            "entry {} has mesh object index {} but the mesh only has {} objects",
+            entry_index,
+            mesh_object_index,
+            object_count
+        )]
+        InvalidMeshObjectIndex {
+            entry_index: usize,
+            mesh_object_index: usize,
+            object_count: usize,
+        },
+
+        /// The entry contains an adjacent vertex index that is out of range for its mesh object.
+        #[error(
+            "entry {} has adjacent vertex index {} but the mesh object only has {} vertices",
+            entry_index,
+            vertex_index,
+            vertex_count
+        )]
+        InvalidVertexIndex {
+            entry_index: usize,
+            vertex_index: i16,
+            vertex_count: usize,
+        },
+
+        /// The entry's byte offset is not aligned to the size of an index.
+        #[error(
+            "entry {} has byte offset {} that is not a multiple of 2",
+            entry_index,
+            offset
+        )]
+        MisalignedOffset { entry_index: usize, offset: usize },
+
+        /// The entry's indices do not contain the same number of elements for each vertex.
+        #[error(
+            "entry {} has {} indices, which is not a multiple of {} indices per vertex",
+            entry_index,
+            length,
+            indices_per_vertex
+        )]
+        InvalidEntryLength {
+            entry_index: usize,
+            length: usize,
+            indices_per_vertex: usize,
+        },
+
+        /// The entry offsets and buffer length do not match any known [AdjFormat](super::AdjFormat).
+        #[error("the adjacency entries do not match a known adjb format")]
+        UnrecognizedFormat,
+    }
+}
+
+/// The layout of the adjacency indices for each vertex in an [Adj] file.
+///
+/// Games can use a different number of indices for each vertex.
+/// Reading a file with the wrong format produces incorrect adjacency for every vertex,
+/// so [AdjData::from_adj_with_format] and [AdjData::to_adj_with_format] check that the entries match the format.
+///
+/// The [SsbhData](crate::SsbhData) methods like [AdjData::from_file] don't check the format.
+/// Use [AdjData::from_file_with_format] and [AdjData::write_to_file_with_format] to check the entries when reading or writing files.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum AdjFormat {
+    /// 18 indices for each vertex used by Smash Ultimate.
+    #[default]
+    SmashUltimate,
+    /// A fixed number of indices for each vertex.
+    Custom { indices_per_vertex: usize },
+}
+
+impl AdjFormat {
+    /// The number of elements in the index buffer for each vertex.
+    pub fn indices_per_vertex(&self) -> usize {
+        match self {
+            AdjFormat::SmashUltimate => MAX_ADJACENT_VERTICES,
+            AdjFormat::Custom { indices_per_vertex } => *indices_per_vertex,
+        }
+    }
+
+    /// Detects the format from the entry offsets and length of the index buffer.
+    ///
+    /// This only recognizes [AdjFormat::SmashUltimate] and never returns [AdjFormat::Custom].
+    /// Adjb files don't store the number of indices for each vertex,
+    /// and the entry lengths alone can't reliably distinguish between other layouts.
+    /// Files that don't match a known format return [error::Error::UnrecognizedFormat].
+    /// Use [AdjFormat::Custom] with [AdjData::from_adj_with_format] to read these files.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_data::adj_data::{AdjData, AdjFormat};
+    use ssbh_lib::formats::adj::Adj;
+
+    let adj = Adj::from_file("model.adjb")?;
+    let format = AdjFormat::detect(&adj)?;
+    let data = AdjData::from_adj_with_format(&adj, format)?;
+    # Ok(()) }
+    ```
+     */
+    pub fn detect(adj: &Adj) -> Result<Self, error::Error> {
+        let ranges = entry_ranges(adj)?;
+        check_entry_lengths(ranges.iter().map(|r| r.len()), MAX_ADJACENT_VERTICES)
+            .map(|_| AdjFormat::SmashUltimate)
+            .map_err(|_| error::Error::UnrecognizedFormat)
     }
 }
 
@@ -73,6 +183,224 @@ pub struct AdjEntryData {
     pub vertex_adjacency: Vec<i16>,
 }
 
+impl AdjData {
+    /// Computes adjacency for the objects in `mesh` with a name in `object_names` and merges the new entries.
+    ///
+    /// The existing entries are assumed to refer to `mesh` without the named objects.
+    /// The [mesh_object_index](struct.AdjEntryData.html#structfield.mesh_object_index) for existing entries
+    /// is updated to account for the inserted objects, so all entries remain valid for `mesh`.
+    /// Entries are sorted by mesh object index after merging.
+    /// Objects sharing a name with different subindices are all treated as new objects.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_data::prelude::*;
+
+    let mesh = MeshData::from_file("model.numshb")?;
+    let mut adj = AdjData::from_file("model.adjb")?;
+    adj.add_entries_for(&mesh, &["newMeshShape"])?;
+    # Ok(()) }
+    ```
+     */
+    pub fn add_entries_for(
+        &mut self,
+        mesh: &MeshData,
+        object_names: &[&str],
+    ) -> Result<(), error::Error> {
+        if let Some(name) = object_names
+            .iter()
+            .find(|name| !mesh.objects.iter().any(|o| o.name == **name))
+        {
+            return Err(error::Error::MissingMeshObject {
+                name: name.to_string(),
+            });
+        }
+
+        let is_new = |o: &MeshObjectData| object_names.contains(&o.name.as_str());
+
+        // Map indices for the mesh before insertion to indices in the new mesh.
+        let old_to_new = mesh
+            .objects
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| !is_new(o))
+            .map(|(i, _)| i)
+            .collect_vec();
+        let inserted_count = mesh.objects.len() - old_to_new.len();
+
+        for entry in &mut self.entries {
+            // Preserve the relative order for indices past the end of the original mesh.
+            entry.mesh_object_index = old_to_new
+                .get(entry.mesh_object_index)
+                .copied()
+                .unwrap_or(entry.mesh_object_index + inserted_count);
+        }
+
+        self.entries.extend(
+            mesh.objects
+                .iter()
+                .enumerate()
+                .filter(|(_, o)| is_new(o))
+                .map(|(i, o)| AdjEntryData::from_mesh_object(i, o)),
+        );
+        self.entries.sort_by_key(|e| e.mesh_object_index);
+
+        Ok(())
+    }
+
+    /// Checks that every entry refers to an object in `mesh`
+    /// and that every adjacent vertex index is `-1` or a valid vertex for that object.
+    /// The vertex count for an object is the length of its first position attribute.
+    ///
+    /// Invalid entries load without errors but may cause crashes or rendering issues in game.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_data::prelude::*;
+
+    let mesh = MeshData::from_file("model.numshb")?;
+    let adj = AdjData::from_file("model.adjb")?;
+    adj.validate(&mesh)?;
+    # Ok(()) }
+    ```
+     */
+    pub fn validate(&self, mesh: &MeshData) -> Result<(), error::Error> {
+        for (entry_index, entry) in self.entries.iter().enumerate() {
+            let object = mesh.objects.get(entry.mesh_object_index).ok_or(
+                error::Error::InvalidMeshObjectIndex {
+                    entry_index,
+                    mesh_object_index: entry.mesh_object_index,
+                    object_count: mesh.objects.len(),
+                },
+            )?;
+
+            let vertex_count = object.positions.first().map(|p| p.data.len()).unwrap_or(0);
+            if let Some(vertex_index) = entry
+                .vertex_adjacency
+                .iter()
+                .find(|i| **i != -1 && (**i < 0 || **i as usize >= vertex_count))
+            {
+                return Err(error::Error::InvalidVertexIndex {
+                    entry_index,
+                    vertex_index: *vertex_index,
+                    vertex_count,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AdjData {
+    /// Converts `adj` after checking that the entries use the layout for `format`.
+    /// Unlike [AdjData::try_from], this returns an error instead of silently splitting the buffer
+    /// at the wrong vertex boundaries for files from other games.
+    pub fn from_adj_with_format(adj: &Adj, format: AdjFormat) -> Result<Self, error::Error> {
+        let ranges = entry_ranges(adj)?;
+        check_entry_lengths(ranges.iter().map(|r| r.len()), format.indices_per_vertex())?;
+
+        Ok(AdjData {
+            entries: adj
+                .entries
+                .iter()
+                .zip(ranges)
+                .map(|(entry, range)| AdjEntryData {
+                    mesh_object_index: entry.mesh_object_index as usize,
+                    vertex_adjacency: adj.index_buffer[range].to_vec(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Converts the entries to an [Adj] after checking that they use the layout for `format`.
+    pub fn to_adj_with_format(&self, format: AdjFormat) -> Result<Adj, error::Error> {
+        check_entry_lengths(
+            self.entries.iter().map(|e| e.vertex_adjacency.len()),
+            format.indices_per_vertex(),
+        )?;
+        Ok(Adj::try_from(self)?)
+    }
+
+    /// Reads the file at `path` using [AdjData::from_adj_with_format].
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_data::adj_data::{AdjData, AdjFormat};
+
+    let adj = AdjData::from_file_with_format("model.adjb", AdjFormat::SmashUltimate)?;
+    adj.write_to_file_with_format("model_new.adjb", AdjFormat::SmashUltimate)?;
+    # Ok(()) }
+    ```
+     */
+    pub fn from_file_with_format<P: AsRef<std::path::Path>>(
+        path: P,
+        format: AdjFormat,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::from_adj_with_format(&Adj::from_file(path)?, format)?)
+    }
+
+    /// Writes the data to `path` using [AdjData::to_adj_with_format].
+    pub fn write_to_file_with_format<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        format: AdjFormat,
+    ) -> Result<(), error::Error> {
+        self.to_adj_with_format(format)?.write_to_file(path)?;
+        Ok(())
+    }
+}
+
+// The element range in the index buffer for each entry.
+// The next entry's offset marks the end of the current entry's data.
+fn entry_ranges(adj: &Adj) -> Result<Vec<std::ops::Range<usize>>, error::Error> {
+    let buffer_size = adj.index_buffer.len() * std::mem::size_of::<i16>();
+    let offsets: Vec<_> = adj
+        .entries
+        .iter()
+        .map(|e| e.index_buffer_offset as usize)
+        .chain(std::iter::once(buffer_size))
+        .collect();
+
+    offsets
+        .windows(2)
+        .enumerate()
+        .map(|(entry_index, w)| {
+            let (start, end) = (w[0], w[1]);
+            if start % std::mem::size_of::<i16>() != 0 {
+                Err(error::Error::MisalignedOffset {
+                    entry_index,
+                    offset: start,
+                })
+            } else if start > end || end > buffer_size {
+                Err(error::Error::BufferOffsetOutOfRange {
+                    start,
+                    end,
+                    buffer_size,
+                })
+            } else {
+                Ok(start / std::mem::size_of::<i16>()..end / std::mem::size_of::<i16>())
+            }
+        })
+        .collect()
+}
+
+fn check_entry_lengths(
+    lengths: impl Iterator<Item = usize>,
+    indices_per_vertex: usize,
+) -> Result<(), error::Error> {
+    for (entry_index, length) in lengths.enumerate() {
+        if length.checked_rem(indices_per_vertex).unwrap_or(length) != 0 {
+            return Err(error::Error::InvalidEntryLength {
+                entry_index,
+                length,
+                indices_per_vertex,
+            });
+        }
+    }
+    Ok(())
+}
+
 impl AdjEntryData {
     /// Computes the vertex adjacency information from triangle faces.
     /// `vertex_indices.len()` should be a multiple of 3.
@@ -356,6 +684,174 @@ mod tests {
         assert_eq!(adj, Adj::try_from(&data).unwrap());
     }
 
+    fn object(name: &str) -> MeshObjectData {
+        MeshObjectData {
+            name: name.to_string(),
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![crate::mesh_data::AttributeData {
+                name: String::new(),
+                data: VectorData::Vector3(vec![[0.0; 3], [1.0; 3], [2.0; 3]]),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn add_entries_for_inserted_objects() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("new"), object("a"), object("new"), object("b")],
+        };
+
+        let mut data = AdjData {
+            entries: vec![
+                AdjEntryData {
+                    mesh_object_index: 1,
+                    vertex_adjacency: vec![1],
+                },
+                AdjEntryData {
+                    mesh_object_index: 0,
+                    vertex_adjacency: vec![0],
+                },
+            ],
+        };
+        data.add_entries_for(&mesh, &["new"]).unwrap();
+
+        let adjacency = AdjEntryData::from_mesh_object(0, &object("new")).vertex_adjacency;
+        assert_eq!(
+            AdjData {
+                entries: vec![
+                    AdjEntryData {
+                        mesh_object_index: 0,
+                        vertex_adjacency: adjacency.clone(),
+                    },
+                    AdjEntryData {
+                        mesh_object_index: 1,
+                        vertex_adjacency: vec![0],
+                    },
+                    AdjEntryData {
+                        mesh_object_index: 2,
+                        vertex_adjacency: adjacency,
+                    },
+                    AdjEntryData {
+                        mesh_object_index: 3,
+                        vertex_adjacency: vec![1],
+                    },
+                ]
+            },
+            data
+        );
+    }
+
+    #[test]
+    fn add_entries_for_missing_object() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a")],
+        };
+
+        let mut data = AdjData {
+            entries: Vec::new(),
+        };
+        let result = data.add_entries_for(&mesh, &["b"]);
+        assert!(matches!(
+            result,
+            Err(error::Error::MissingMeshObject { name }) if name == "b"
+        ));
+        assert!(data.entries.is_empty());
+    }
+
+    #[test]
+    fn validate_valid_entries() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a"), object("b")],
+        };
+
+        let data = AdjData {
+            entries: vec![
+                AdjEntryData::from_mesh_object(1, &mesh.objects[1]),
+                AdjEntryData {
+                    mesh_object_index: 0,
+                    vertex_adjacency: vec![0, 2, -1],
+                },
+            ],
+        };
+        assert!(data.validate(&mesh).is_ok());
+    }
+
+    #[test]
+    fn validate_invalid_mesh_object_index() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a")],
+        };
+
+        let data = AdjData {
+            entries: vec![
+                AdjEntryData {
+                    mesh_object_index: 0,
+                    vertex_adjacency: Vec::new(),
+                },
+                AdjEntryData {
+                    mesh_object_index: 1,
+                    vertex_adjacency: Vec::new(),
+                },
+            ],
+        };
+        assert!(matches!(
+            data.validate(&mesh),
+            Err(error::Error::InvalidMeshObjectIndex {
+                entry_index: 1,
+                mesh_object_index: 1,
+                object_count: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_invalid_vertex_index() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a")],
+        };
+
+        let data = AdjData {
+            entries: vec![AdjEntryData {
+                mesh_object_index: 0,
+                vertex_adjacency: vec![0, 1, -1, 3],
+            }],
+        };
+        assert!(matches!(
+            data.validate(&mesh),
+            Err(error::Error::InvalidVertexIndex {
+                entry_index: 0,
+                vertex_index: 3,
+                vertex_count: 3
+            })
+        ));
+
+        let data = AdjData {
+            entries: vec![AdjEntryData {
+                mesh_object_index: 0,
+                vertex_adjacency: vec![-2],
+            }],
+        };
+        assert!(matches!(
+            data.validate(&mesh),
+            Err(error::Error::InvalidVertexIndex {
+                entry_index: 0,
+                vertex_index: -2,
+                vertex_count: 3
+            })
+        ));
+    }
+
     #[test]
     fn create_adj_data_invalid_offset_first_entry() {
         let adj = Adj {
@@ -463,4 +959,97 @@ mod tests {
             triangle_adjacency(&[0, 1, 2, 3, 4, 5], &[0.0, 0.5, 1.0, 0.0, 1.5, 2.0], 5)
         );
     }
+
+    fn adj(offsets: &[u32], index_count: usize) -> Adj {
+        Adj {
+            entries: offsets
+                .iter()
+                .enumerate()
+                .map(|(i, offset)| AdjEntry {
+                    mesh_object_index: i as u32,
+                    index_buffer_offset: *offset,
+                })
+                .collect(),
+            index_buffer: vec![-1; index_count],
+        }
+    }
+
+    #[test]
+    fn detect_format_smash_ultimate() {
+        // Two entries with 2 and 1 vertices.
+        let adj = adj(&[0, 72], 54);
+        assert_eq!(AdjFormat::SmashUltimate, AdjFormat::detect(&adj).unwrap());
+
+        let data = AdjData::from_adj_with_format(&adj, AdjFormat::SmashUltimate).unwrap();
+        assert_eq!(36, data.entries[0].vertex_adjacency.len());
+        assert_eq!(18, data.entries[1].vertex_adjacency.len());
+    }
+
+    #[test]
+    fn detect_format_unrecognized() {
+        // Entries with 12 indices per vertex don't split evenly into 18 indices per vertex.
+        let adj = adj(&[0, 24], 24);
+        assert!(matches!(
+            AdjFormat::detect(&adj),
+            Err(error::Error::UnrecognizedFormat)
+        ));
+        assert!(matches!(
+            AdjData::from_adj_with_format(&adj, AdjFormat::SmashUltimate),
+            Err(error::Error::InvalidEntryLength {
+                entry_index: 0,
+                length: 12,
+                indices_per_vertex: 18
+            })
+        ));
+
+        let format = AdjFormat::Custom {
+            indices_per_vertex: 12,
+        };
+        let data = AdjData::from_adj_with_format(&adj, format).unwrap();
+        assert_eq!(2, data.entries.len());
+        assert_eq!(adj, data.to_adj_with_format(format).unwrap());
+    }
+
+    #[test]
+    fn from_adj_with_format_misaligned_offset() {
+        let adj = adj(&[0, 3], 36);
+        assert!(matches!(
+            AdjData::from_adj_with_format(&adj, AdjFormat::SmashUltimate),
+            Err(error::Error::MisalignedOffset {
+                entry_index: 1,
+                offset: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn from_adj_with_format_decreasing_offsets() {
+        let adj = adj(&[36, 0], 36);
+        assert!(matches!(
+            AdjData::from_adj_with_format(&adj, AdjFormat::SmashUltimate),
+            Err(error::Error::BufferOffsetOutOfRange {
+                start: 36,
+                end: 0,
+                buffer_size: 72
+            })
+        ));
+    }
+
+    #[test]
+    fn to_adj_with_format_invalid_length() {
+        let data = AdjData {
+            entries: vec![AdjEntryData {
+                mesh_object_index: 0,
+                vertex_adjacency: vec![-1; 20],
+            }],
+        };
+        assert!(matches!(
+            data.to_adj_with_format(AdjFormat::SmashUltimate),
+            Err(error::Error::InvalidEntryLength {
+                entry_index: 0,
+                length: 20,
+                indices_per_vertex: 18
+            })
+        ));
+    }
 }
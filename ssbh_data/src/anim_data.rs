@@ -50,16 +50,27 @@ use ssbh_lib::{
     SsbhArray, Vector3, Vector4, Version,
 };
 use ssbh_write::SsbhWrite;
-use std::collections::HashMap;
+
+use crate::context::{ErrorContext, ResultExt};
+use crate::skel_data::SkelData;
 use std::{
     convert::{TryFrom, TryInto},
     error::Error,
 };
 
-mod buffers;
+pub mod buffers;
 use buffers::*;
 mod bitutils;
+#[cfg(feature = "serde")]
+pub mod compact;
 mod compression;
+mod constant;
+mod looping;
+pub(crate) use looping::{lerp_f32, lerp_vector4};
+pub mod material_tracks;
+mod remove;
+mod root_motion;
+pub mod stats;
 
 /// Data associated with an [Anim] file.
 /// Supported versions are 2.0 and 2.1.
@@ -81,6 +92,420 @@ pub struct AnimData {
     pub groups: Vec<GroupData>,
 }
 
+impl AnimData {
+    /// Reduces the size of the [Anim] created from this data without changing the animation.
+    ///
+    /// Tracks where every frame has the same value are collapsed to a single frame.
+    /// These tracks are saved as constant tracks and repeat their value for every frame.
+    /// Use [AnimData::to_anim_compressed] to also compress tracks when it reduces the size.
+    ///
+    /// Tracks that only contain default values are kept since the defaults aren't stored in the anim.
+    /// Use [AnimData::remove_default_tracks] to also remove these tracks.
+    ///
+    /// # Examples
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags};
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 2.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Visibility,
+            nodes: vec![NodeData {
+                name: "Visibility".to_string(),
+                tracks: vec![TrackData {
+                    name: "Visibility".to_string(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Boolean(vec![true, true, true]),
+                }],
+            }],
+        }],
+    };
+
+    anim.optimize();
+    assert_eq!(
+        TrackValues::Boolean(vec![true]),
+        anim.groups[0].nodes[0].tracks[0].values
+    );
+    ```
+    */
+    pub fn optimize(&mut self) {
+        for track in self
+            .groups
+            .iter_mut()
+            .flat_map(|g| g.nodes.iter_mut())
+            .flat_map(|n| n.tracks.iter_mut())
+        {
+            track.values.collapse_constant_frames();
+        }
+    }
+
+    /// Converts the data to an [Anim] using compressed data for any track with multiple frames
+    /// where compression has a smaller encoded size than direct data.
+    ///
+    /// Compressed values are quantized, so reading the [Anim] may not return the exact original values.
+    /// [Anim::try_from] only compresses tracks with enough frames to offset the compression header.
+    pub fn to_anim_compressed(&self) -> Result<Anim, error::Error> {
+        create_anim(self, true)
+    }
+
+    /// Adds `track` to the node `node_name` in the group with `group_type`.
+    /// The group and node are created if they don't already exist.
+    ///
+    /// The [compensate_scale](struct.TrackData.html#structfield.compensate_scale)
+    /// and [transform_flags](struct.TrackData.html#structfield.transform_flags) are copied from a reference track.
+    /// An existing track in the node with the same name is replaced and used as the reference.
+    /// Otherwise, the reference is the first track in the group with the same name.
+    /// If the reference track is constant, `track` is also collapsed to a constant track when possible.
+    ///
+    /// # Examples
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupType, TrackData, TrackValues, Transform, TransformFlags};
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 0.0,
+        groups: Vec::new(),
+    };
+
+    anim.insert_track(
+        GroupType::Transform,
+        "Hip",
+        TrackData {
+            name: "Transform".to_string(),
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values: TrackValues::Transform(vec![Transform::IDENTITY]),
+        },
+    );
+    assert_eq!("Hip", anim.groups[0].nodes[0].name);
+    ```
+    */
+    pub fn insert_track(&mut self, group_type: GroupType, node_name: &str, mut track: TrackData) {
+        let group = match self.groups.iter().position(|g| g.group_type == group_type) {
+            Some(index) => &mut self.groups[index],
+            None => {
+                self.groups.push(GroupData {
+                    group_type,
+                    nodes: Vec::new(),
+                });
+                self.groups.last_mut().unwrap()
+            }
+        };
+
+        let reference = group
+            .nodes
+            .iter()
+            .find(|n| n.name == node_name)
+            .and_then(|n| n.tracks.iter().find(|t| t.name == track.name))
+            .or_else(|| {
+                group
+                    .nodes
+                    .iter()
+                    .flat_map(|n| n.tracks.iter())
+                    .find(|t| t.name == track.name)
+            });
+        if let Some(reference) = reference {
+            track.copy_flags_from(reference);
+            if reference.values.len() == 1 {
+                track.values.collapse_constant_frames();
+            }
+        }
+
+        let node = match group.nodes.iter().position(|n| n.name == node_name) {
+            Some(index) => &mut group.nodes[index],
+            None => {
+                group.nodes.push(NodeData {
+                    name: node_name.to_string(),
+                    tracks: Vec::new(),
+                });
+                group.nodes.last_mut().unwrap()
+            }
+        };
+
+        match node.tracks.iter_mut().find(|t| t.name == track.name) {
+            Some(existing) => *existing = track,
+            None => node.tracks.push(track),
+        }
+    }
+}
+
+impl AnimData {
+    /// Sorts the [groups](#structfield.groups) by [GroupType] value
+    /// and the nodes and tracks for each group by name.
+    pub fn sort(&mut self) {
+        self.groups.sort_by_key(|g| u64::from(g.group_type));
+        for group in &mut self.groups {
+            group.nodes.sort_by(|a, b| a.name.cmp(&b.name));
+            for node in &mut group.nodes {
+                node.tracks.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+        }
+    }
+}
+
+impl AnimData {
+    /// Creates a [GroupType::Transform] group with a node for each bone in `skel`.
+    /// Each node has a constant `"Transform"` track with the bone's rest pose transform.
+    ///
+    /// The rest pose transform is decomposed from the bone's
+    /// [transform](crate::skel_data::BoneData::transform) relative to its parent.
+    /// Nodes are in the same order as the bones.
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupType};
+    use ssbh_data::skel_data::{BillboardType, BoneData, SkelData};
+
+    let skel = SkelData {
+        major_version: 1,
+        minor_version: 0,
+        bones: vec![BoneData {
+            name: "Trans".to_string(),
+            transform: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            parent_index: None,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        }],
+    };
+
+    let anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 0.0,
+        groups: vec![AnimData::create_transform_group_from_skel(&skel)],
+    };
+    assert_eq!(GroupType::Transform, anim.groups[0].group_type);
+    assert_eq!("Trans", anim.groups[0].nodes[0].name);
+    ```
+     */
+    pub fn create_transform_group_from_skel(skel: &SkelData) -> GroupData {
+        GroupData {
+            group_type: GroupType::Transform,
+            nodes: skel
+                .bones
+                .iter()
+                .map(|b| {
+                    let (scale, rotation, translation) =
+                        glam::Mat4::from_cols_array_2d(&b.transform)
+                            .to_scale_rotation_translation();
+
+                    NodeData {
+                        name: b.name.clone(),
+                        tracks: vec![TrackData {
+                            name: "Transform".to_string(),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Transform(vec![Transform {
+                                scale: scale.to_array().into(),
+                                rotation: rotation.to_array().into(),
+                                translation: translation.to_array().into(),
+                            }]),
+                        }],
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A track with a different number of frames than expected from the
+/// [final_frame_index](struct.AnimData.html#structfield.final_frame_index).
+/// See [AnimData::frame_count_mismatches].
+#[derive(Debug, PartialEq, Clone)]
+pub struct FrameCountMismatch {
+    pub group_type: GroupType,
+    pub node_name: String,
+    pub track_name: String,
+    /// The number of frames in the track.
+    pub frame_count: usize,
+    /// The number of frames expected from the final frame index.
+    pub expected_frame_count: usize,
+}
+
+/// The method for making tracks consistent with the final frame index in [AnimData::fix_frame_counts].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FrameCountFix {
+    /// Increase the final frame index to include the longest track and pad shorter tracks.
+    Pad,
+    /// Keep the final frame index and trim longer tracks or pad shorter tracks.
+    Trim,
+}
+
+impl AnimData {
+    /// The number of frames in the longest track or `0` if there are no tracks.
+    pub fn max_frame_count(&self) -> usize {
+        self.tracks()
+            .map(|(_, _, t)| t.frame_count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The number of frames for non constant tracks implied by the
+    /// [final_frame_index](#structfield.final_frame_index).
+    /// Fractional final frame indices are rounded down.
+    pub fn expected_frame_count(&self) -> usize {
+        self.final_frame_index.max(0.0).floor() as usize + 1
+    }
+
+    /// Finds the non constant tracks with a frame count that doesn't match [AnimData::expected_frame_count].
+    ///
+    /// Tracks that are shorter than the animation cause the animation to freeze early in game.
+    /// Tracks that are longer than the animation will fail to save.
+    /// Constant tracks with a single frame are repeated for every frame and are not included.
+    pub fn frame_count_mismatches(&self) -> Vec<FrameCountMismatch> {
+        let expected_frame_count = self.expected_frame_count();
+        self.tracks()
+            .filter(|(_, _, t)| t.frame_count() > 1 && t.frame_count() != expected_frame_count)
+            .map(|(group_type, node_name, t)| FrameCountMismatch {
+                group_type,
+                node_name: node_name.to_string(),
+                track_name: t.name.clone(),
+                frame_count: t.frame_count(),
+                expected_frame_count,
+            })
+            .collect()
+    }
+
+    /// Updates the [final_frame_index](#structfield.final_frame_index) and the frames of non constant tracks
+    /// so that [AnimData::frame_count_mismatches] is empty.
+    /// Tracks are padded by repeating their final frame.
+    ///
+    /// # Examples
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, FrameCountFix, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags};
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 1.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Visibility,
+            nodes: vec![NodeData {
+                name: "Visibility".to_string(),
+                tracks: vec![TrackData {
+                    name: "Visibility".to_string(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Boolean(vec![true, false, true]),
+                }],
+            }],
+        }],
+    };
+    assert_eq!(1, anim.frame_count_mismatches().len());
+
+    anim.fix_frame_counts(FrameCountFix::Pad);
+    assert_eq!(2.0, anim.final_frame_index);
+    assert!(anim.frame_count_mismatches().is_empty());
+    ```
+    */
+    pub fn fix_frame_counts(&mut self, fix: FrameCountFix) {
+        if fix == FrameCountFix::Pad {
+            let max_frame_count = self.max_frame_count();
+            if max_frame_count > self.expected_frame_count() {
+                self.final_frame_index = (max_frame_count - 1) as f32;
+            }
+        }
+
+        let frame_count = self.expected_frame_count();
+        for track in self
+            .groups
+            .iter_mut()
+            .flat_map(|g| g.nodes.iter_mut())
+            .flat_map(|n| n.tracks.iter_mut())
+        {
+            if track.frame_count() > 1 {
+                track.values.resize(frame_count);
+            }
+        }
+    }
+
+    fn tracks(&self) -> impl Iterator<Item = (GroupType, &str, &TrackData)> {
+        self.groups.iter().flat_map(|g| {
+            g.nodes.iter().flat_map(move |n| {
+                n.tracks
+                    .iter()
+                    .map(move |t| (g.group_type, n.name.as_str(), t))
+            })
+        })
+    }
+}
+
+/// A recoverable error for a track when reading with [AnimData::from_anim_lenient].
+#[derive(Debug)]
+pub struct TrackWarning {
+    pub group_type: GroupType,
+    pub node_name: String,
+    pub track_name: String,
+    /// The number of frames read for the track or [None] if the track was skipped.
+    pub frames_read: Option<usize>,
+    /// The error that would have caused reading the file to fail.
+    pub error: error::Error,
+}
+
+impl AnimData {
+    /// Converts `anim` while recovering as much track data as possible.
+    ///
+    /// Tracks with an out of range data size or truncated buffers return the frames that can be read.
+    /// Tracks where no frames can be read are skipped.
+    /// Each recovered or skipped track produces a [TrackWarning].
+    /// Edited files with slightly incorrect buffer sizes often still work in game,
+    /// so this can be used to open files that fail to convert with [TryFrom].
+    pub fn from_anim_lenient(anim: &Anim) -> Result<(Self, Vec<TrackWarning>), Box<dyn Error>> {
+        let mut warnings = Vec::new();
+        let groups = match anim {
+            Anim::V12 {
+                tracks, buffers, ..
+            } => read_groups_v12(&tracks.elements, &buffers.elements)?,
+            Anim::V20 { groups, buffer, .. } | Anim::V21 { groups, buffer, .. } => {
+                read_groups_v20(&groups.elements, &buffer.elements, Some(&mut warnings))?
+            }
+        };
+
+        let (major_version, minor_version) = anim.major_minor_version();
+        Ok((
+            Self {
+                major_version,
+                minor_version,
+                final_frame_index: final_frame_index(anim),
+                groups,
+            },
+            warnings,
+        ))
+    }
+
+    /// Reads the file at `path` using [AnimData::from_anim_lenient].
+    pub fn from_file_lenient<P: AsRef<std::path::Path>>(
+        path: P,
+    ) -> Result<(Self, Vec<TrackWarning>), Box<dyn Error>> {
+        Self::from_anim_lenient(&Anim::from_file(path)?)
+    }
+}
+
+fn final_frame_index(anim: &Anim) -> f32 {
+    match anim {
+        Anim::V12 {
+            final_frame_index, ..
+        } => *final_frame_index,
+        Anim::V20 {
+            final_frame_index, ..
+        } => *final_frame_index,
+        Anim::V21 {
+            final_frame_index, ..
+        } => *final_frame_index,
+    }
+}
+
 // TODO: Test these conversions.
 impl TryFrom<Anim> for AnimData {
     type Error = Box<dyn Error>;
@@ -98,17 +523,7 @@ impl TryFrom<&Anim> for AnimData {
         Ok(Self {
             major_version,
             minor_version,
-            final_frame_index: match &anim {
-                Anim::V12 {
-                    final_frame_index, ..
-                } => *final_frame_index,
-                Anim::V20 {
-                    final_frame_index, ..
-                } => *final_frame_index,
-                Anim::V21 {
-                    final_frame_index, ..
-                } => *final_frame_index,
-            },
+            final_frame_index: final_frame_index(anim),
             groups: read_anim_groups(anim)?,
         })
     }
@@ -118,7 +533,7 @@ impl TryFrom<AnimData> for Anim {
     type Error = error::Error;
 
     fn try_from(data: AnimData) -> Result<Self, Self::Error> {
-        create_anim(&data)
+        create_anim(&data, false)
     }
 }
 
@@ -126,7 +541,7 @@ impl TryFrom<&AnimData> for Anim {
     type Error = error::Error;
 
     fn try_from(data: &AnimData) -> Result<Self, Self::Error> {
-        create_anim(data)
+        create_anim(data, false)
     }
 }
 
@@ -134,7 +549,7 @@ pub mod error {
     use super::*;
     use thiserror::Error;
 
-    /// Errors while creating an [Anim] from [AnimData].
+    /// Errors while creating an [Anim] from [AnimData] or editing [AnimData].
     #[derive(Debug, Error)]
     pub enum Error {
         /// Creating an [Anim] file for the given version is not supported.
@@ -176,6 +591,20 @@ pub mod error {
         )]
         UnexpectedBitCount { expected: usize, actual: usize },
 
+        /// The compressed buffer does not contain all the bits for the frame at index `frame`.
+        /// This usually indicates a truncated or corrupted file.
+        #[error(
+            "compressed frame {} with {} bits per entry is out of range for a buffer of {} bits",
+            frame,
+            bits_per_entry,
+            buffer_bits
+        )]
+        CompressedFrameOutOfRange {
+            frame: usize,
+            bits_per_entry: usize,
+            buffer_bits: usize,
+        },
+
         #[error(
             "track data range {0}..{0}+{1} is out of range for a buffer of size {2}",
             start,
@@ -202,6 +631,21 @@ pub mod error {
         /// An error occurred while reading the compressed header for version 2.0 or later.
         #[error("the track data compression header is malformed and cannot be read")]
         MalformedCompressionHeader,
+
+        /// No node in a [GroupType::Transform] group has the given name and a transform track.
+        #[error("node {:?} does not have a transform track", node_name)]
+        MissingTransformTrack { node_name: String },
+
+        /// The root motion displacement does not have a value for each frame in the track.
+        #[error(
+            "root motion with {} frames cannot be applied to a track with {} frames",
+            displacement_count,
+            frame_count
+        )]
+        RootMotionFrameCountMismatch {
+            frame_count: usize,
+            displacement_count: usize,
+        },
     }
 }
 
@@ -211,7 +655,7 @@ enum AnimVersion {
 }
 
 // TODO: Test this for a small example?
-fn create_anim(data: &AnimData) -> Result<Anim, error::Error> {
+fn create_anim(data: &AnimData, compress_smaller_tracks: bool) -> Result<Anim, error::Error> {
     let version = match (data.major_version, data.minor_version) {
         (2, 0) => Ok(AnimVersion::Version20),
         (2, 1) => Ok(AnimVersion::Version21),
@@ -226,7 +670,7 @@ fn create_anim(data: &AnimData) -> Result<Anim, error::Error> {
     let animations = data
         .groups
         .iter()
-        .map(|g| create_anim_group(g, &mut buffer))
+        .map(|g| create_anim_group(g, &mut buffer, compress_smaller_tracks))
         .collect::<Result<Vec<_>, _>>()?;
 
     let max_frame_count = animations
@@ -277,25 +721,33 @@ fn create_anim(data: &AnimData) -> Result<Anim, error::Error> {
     }
 }
 
-fn create_anim_group(g: &GroupData, buffer: &mut Cursor<Vec<u8>>) -> Result<Group, error::Error> {
+fn create_anim_group(
+    g: &GroupData,
+    buffer: &mut Cursor<Vec<u8>>,
+    compress_smaller_tracks: bool,
+) -> Result<Group, error::Error> {
     Ok(Group {
         group_type: g.group_type,
         nodes: g
             .nodes
             .iter()
-            .map(|n| create_anim_node(n, buffer))
+            .map(|n| create_anim_node(n, buffer, compress_smaller_tracks))
             .collect::<Result<Vec<_>, _>>()?
             .into(),
     })
 }
 
-fn create_anim_node(n: &NodeData, buffer: &mut Cursor<Vec<u8>>) -> Result<Node, error::Error> {
+fn create_anim_node(
+    n: &NodeData,
+    buffer: &mut Cursor<Vec<u8>>,
+    compress_smaller_tracks: bool,
+) -> Result<Node, error::Error> {
     Ok(Node {
         name: n.name.as_str().into(), // TODO: Make a convenience method for this?
         tracks: n
             .tracks
             .iter()
-            .map(|t| create_anim_track_v2(buffer, t))
+            .map(|t| create_anim_track_v2(buffer, t, compress_smaller_tracks))
             .collect::<Result<Vec<_>, _>>()?
             .into(),
     })
@@ -304,22 +756,15 @@ fn create_anim_node(n: &NodeData, buffer: &mut Cursor<Vec<u8>>) -> Result<Node,
 fn create_anim_track_v2(
     buffer: &mut Cursor<Vec<u8>>,
     t: &TrackData,
+    compress_smaller_tracks: bool,
 ) -> Result<TrackV2, error::Error> {
-    let compression_type = infer_optimal_compression_type(&t.values);
+    let (compression_type, track_data) =
+        create_track_buffer(&t.values, t.compensate_scale, compress_smaller_tracks)?;
 
     // The current stream position matches the offsets used for Smash Ultimate's anim files.
     // This assumes we traverse the hierarchy (group -> node -> track) in DFS order.
     let pos_before = buffer.stream_position()?;
-
-    // Pointers for compressed data are relative to the start of the track's data.
-    // This requires using a second writer due to how SsbhWrite is implemented.
-    let mut track_data = Cursor::new(Vec::new());
-
-    // TODO: Add tests for preserving scale compensation?.
-    t.values
-        .write(&mut track_data, compression_type, t.compensate_scale)?;
-
-    buffer.write_all(&track_data.into_inner())?;
+    buffer.write_all(&track_data)?;
     let pos_after = buffer.stream_position()?;
 
     Ok(TrackV2 {
@@ -335,8 +780,51 @@ fn create_anim_track_v2(
     })
 }
 
+fn create_track_buffer(
+    values: &TrackValues,
+    compensate_scale: bool,
+    compress_smaller_tracks: bool,
+) -> Result<(CompressionType, Vec<u8>), error::Error> {
+    if let TrackValues::Raw(v) = values {
+        return Ok((v.flags.compression_type, v.data.clone()));
+    }
+
+    let compression_type = infer_optimal_compression_type(values);
+    let data = write_track_buffer(values, compression_type, compensate_scale)?;
+
+    // The inferred compression assumes every component uses the default bit count.
+    // Components that never change don't take up any space in the compressed buffer,
+    // so compression may still be smaller even with just a few frames.
+    // Compression is lossy, so only do this when requested.
+    if compress_smaller_tracks && compression_type == CompressionType::Direct {
+        let compressed = write_track_buffer(values, CompressionType::Compressed, compensate_scale)?;
+        if compressed.len() < data.len() {
+            return Ok((CompressionType::Compressed, compressed));
+        }
+    }
+
+    Ok((compression_type, data))
+}
+
+fn write_track_buffer(
+    values: &TrackValues,
+    compression_type: CompressionType,
+    compensate_scale: bool,
+) -> Result<Vec<u8>, error::Error> {
+    // Pointers for compressed data are relative to the start of the track's data.
+    // This requires using a second writer due to how SsbhWrite is implemented.
+    let mut track_data = Cursor::new(Vec::new());
+
+    // TODO: Add tests for preserving scale compensation?.
+    values.write(&mut track_data, compression_type, compensate_scale)?;
+
+    Ok(track_data.into_inner())
+}
+
 fn infer_optimal_compression_type(values: &TrackValues) -> CompressionType {
     match (values, values.len()) {
+        // Raw data can only be written with its original compression.
+        (TrackValues::Raw(v), _) => v.flags.compression_type,
         // Single frame animations use a special compression type.
         (TrackValues::Transform(_), 0..=1) => CompressionType::ConstTransform,
         (_, 0..=1) => CompressionType::Constant,
@@ -360,7 +848,7 @@ fn infer_optimal_compression_type(values: &TrackValues) -> CompressionType {
 }
 
 // TODO: Test conversions from anim?
-fn read_anim_groups(anim: &Anim) -> Result<Vec<GroupData>, error::Error> {
+fn read_anim_groups(anim: &Anim) -> Result<Vec<GroupData>, Box<dyn Error>> {
     match anim {
         // TODO: Create fake groups for version 1.0?
         ssbh_lib::prelude::Anim::V12 {
@@ -369,13 +857,13 @@ fn read_anim_groups(anim: &Anim) -> Result<Vec<GroupData>, error::Error> {
             // TODO: Group by type?
             // TODO: Assign a single node to each track with the track name as the name?
             // TODO: Use the track type as the track name like "Transform"?
-            read_groups_v12(&tracks.elements, &buffers.elements)
+            read_groups_v12(&tracks.elements, &buffers.elements).map_err(Into::into)
         }
         ssbh_lib::formats::anim::Anim::V20 { groups, buffer, .. } => {
-            read_groups_v20(&groups.elements, &buffer.elements)
+            read_groups_v20(&groups.elements, &buffer.elements, None)
         }
         ssbh_lib::formats::anim::Anim::V21 { groups, buffer, .. } => {
-            read_groups_v20(&groups.elements, &buffer.elements)
+            read_groups_v20(&groups.elements, &buffer.elements, None)
         }
     }
 }
@@ -393,7 +881,8 @@ fn read_groups_v12(
     buffers: &[ssbh_lib::SsbhByteBuffer],
 ) -> Result<Vec<GroupData>, error::Error> {
     // Group by the track type.
-    let mut tracks_by_type = HashMap::new();
+    // Groups are in order of first appearance to make the output deterministic.
+    let mut tracks_by_type: Vec<(GroupType, Vec<_>)> = Vec::new();
 
     // TODO: Avoid unwrap.
     // Node names like bones names are set at the track level for anim 1.2.
@@ -401,10 +890,11 @@ fn read_groups_v12(
     for track in tracks {
         let group_type = group_type_v12(track.track_type);
         let track_data = create_track_data_v12(track, buffers).unwrap();
-        tracks_by_type
-            .entry(group_type)
-            .or_insert(Vec::new())
-            .push((track.name.to_string_lossy(), track_data));
+        let node = (track.name.to_string_lossy(), track_data);
+        match tracks_by_type.iter_mut().find(|(t, _)| *t == group_type) {
+            Some((_, nodes)) => nodes.push(node),
+            None => tracks_by_type.push((group_type, vec![node])),
+        }
     }
 
     // Use the grouping conventions for version 2.0+ anims.
@@ -502,7 +992,8 @@ fn create_track_data_v12(
 fn read_groups_v20(
     anim_groups: &[ssbh_lib::formats::anim::Group],
     anim_buffer: &[u8],
-) -> Result<Vec<GroupData>, error::Error> {
+    mut warnings: Option<&mut Vec<TrackWarning>>,
+) -> Result<Vec<GroupData>, Box<dyn Error>> {
     let mut groups = Vec::new();
 
     for anim_group in anim_groups {
@@ -512,8 +1003,40 @@ fn read_groups_v20(
             let mut tracks = Vec::new();
             for anim_track in &anim_node.tracks.elements {
                 // Find and read the track data.
-                let track = create_track_data_v20(anim_track, anim_buffer)?;
-                tracks.push(track);
+                if let GroupType::Unknown(_) = anim_group.group_type {
+                    // The track layout may depend on the group type, so don't decode the data.
+                    tracks.push(create_raw_track_data_v20(anim_track, anim_buffer).context(
+                        || ErrorContext::Track {
+                            group_type: anim_group.group_type,
+                            node_name: anim_node.name.to_string_lossy(),
+                            track_name: anim_track.name.to_string_lossy(),
+                        },
+                    )?);
+                    continue;
+                }
+
+                match warnings.as_deref_mut() {
+                    Some(warnings) => {
+                        let (track, error) = create_track_data_v20_lenient(anim_track, anim_buffer);
+                        if let Some(error) = error {
+                            warnings.push(TrackWarning {
+                                group_type: anim_group.group_type,
+                                node_name: anim_node.name.to_string_lossy(),
+                                track_name: anim_track.name.to_string_lossy(),
+                                frames_read: track.as_ref().map(|t| t.values.len()),
+                                error,
+                            });
+                        }
+                        tracks.extend(track);
+                    }
+                    None => tracks.push(create_track_data_v20(anim_track, anim_buffer).context(
+                        || ErrorContext::Track {
+                            group_type: anim_group.group_type,
+                            node_name: anim_node.name.to_string_lossy(),
+                            track_name: anim_track.name.to_string_lossy(),
+                        },
+                    )?),
+                }
             }
 
             let node = NodeData {
@@ -533,10 +1056,10 @@ fn read_groups_v20(
     Ok(groups)
 }
 
-fn create_track_data_v20(
+fn track_data_v20<'a>(
     track: &ssbh_lib::formats::anim::TrackV2,
-    buffer: &[u8],
-) -> Result<TrackData, error::Error> {
+    buffer: &'a [u8],
+) -> Result<&'a [u8], error::Error> {
     let start = track.data_offset as usize;
     let end =
         start
@@ -546,13 +1069,20 @@ fn create_track_data_v20(
                 size: track.data_size as usize,
                 buffer_size: buffer.len(),
             })?;
-    let buffer = buffer
+    buffer
         .get(start..end)
         .ok_or(error::Error::InvalidTrackDataRange {
             start: track.data_offset as usize,
             size: track.data_size as usize,
             buffer_size: buffer.len(),
-        })?;
+        })
+}
+
+fn create_track_data_v20(
+    track: &ssbh_lib::formats::anim::TrackV2,
+    buffer: &[u8],
+) -> Result<TrackData, error::Error> {
+    let buffer = track_data_v20(track, buffer)?;
 
     let (values, compensate_scale) =
         read_track_values(buffer, track.flags, track.frame_count as usize)?;
@@ -566,6 +1096,53 @@ fn create_track_data_v20(
     })
 }
 
+fn create_raw_track_data_v20(
+    track: &ssbh_lib::formats::anim::TrackV2,
+    buffer: &[u8],
+) -> Result<TrackData, error::Error> {
+    let data = track_data_v20(track, buffer)?;
+
+    // Any scale compensation is already part of the data.
+    Ok(TrackData {
+        name: track.name.to_string_lossy(),
+        values: TrackValues::Raw(RawTrackValues {
+            flags: track.flags,
+            frame_count: track.frame_count as usize,
+            data: data.to_vec(),
+        }),
+        compensate_scale: false,
+        transform_flags: track.transform_flags.into(),
+    })
+}
+
+fn create_track_data_v20_lenient(
+    track: &ssbh_lib::formats::anim::TrackV2,
+    buffer: &[u8],
+) -> (Option<TrackData>, Option<error::Error>) {
+    // Use the remaining data for out of range sizes.
+    let start = (track.data_offset as usize).min(buffer.len());
+    let end = start.saturating_add(track.data_size as usize);
+    let range_error = (end > buffer.len()).then_some(error::Error::InvalidTrackDataRange {
+        start: track.data_offset as usize,
+        size: track.data_size as usize,
+        buffer_size: buffer.len(),
+    });
+    let data = &buffer[start..end.min(buffer.len())];
+
+    match read_track_values_partial(data, track.flags, track.frame_count as usize) {
+        Ok((values, compensate_scale, read_error)) => (
+            Some(TrackData {
+                name: track.name.to_string_lossy(),
+                values,
+                compensate_scale,
+                transform_flags: track.transform_flags.into(),
+            }),
+            read_error.or(range_error),
+        ),
+        Err(e) => (None, Some(e)),
+    }
+}
+
 /// Data associated with a [Group].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -631,6 +1208,44 @@ pub struct TrackData {
     pub values: TrackValues,
 }
 
+impl TrackData {
+    /// Creates a track named `name` with default flags.
+    /// The [TrackValues] variant is determined by the element type of `values`.
+    /**
+    ```rust
+    use ssbh_data::anim_data::{TrackData, TrackValues};
+
+    let track = TrackData::new("nfTexture0[0].PatternIndex", vec![1u32, 2, 3]);
+    assert_eq!(TrackValues::PatternIndex(vec![1, 2, 3]), track.values);
+
+    let track = TrackData::new("CustomBoolean1", vec![true, false]);
+    assert_eq!(TrackValues::Boolean(vec![true, false]), track.values);
+    ```
+     */
+    pub fn new<V: Into<TrackValues>>(name: &str, values: V) -> Self {
+        Self {
+            name: name.to_string(),
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values: values.into(),
+        }
+    }
+
+    /// Copies the [compensate_scale](#structfield.compensate_scale)
+    /// and [transform_flags](#structfield.transform_flags) from `reference`.
+    /// This keeps the settings for a new track consistent with existing tracks.
+    pub fn copy_flags_from(&mut self, reference: &TrackData) {
+        self.compensate_scale = reference.compensate_scale;
+        self.transform_flags = reference.transform_flags;
+    }
+
+    /// The number of frames in [values](#structfield.values).
+    /// Constant tracks have a single frame.
+    pub fn frame_count(&self) -> usize {
+        self.values.len()
+    }
+}
+
 /// See [ssbh_lib::formats::anim::TransformFlags].
 // Including compensate scale would be redundant with ScaleOptions.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -717,6 +1332,7 @@ impl Transform {
 // TODO: Create runtime errors when saving tracks with incompatible data?
 /// A value collection with an element for each frame of the animation.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "compact::TrackValuesRepr"))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug, PartialEq, Clone)]
 pub enum TrackValues {
@@ -732,6 +1348,21 @@ pub enum TrackValues {
     Boolean(Vec<bool>),
     /// Material animations or animated vector parameters.
     Vector4(Vec<Vector4>),
+    /// The undecoded data for tracks in groups with [GroupType::Unknown].
+    Raw(RawTrackValues),
+}
+
+/// The track data for a group type not recognized by ssbh_data.
+///
+/// The bytes are written back unmodified using the original [TrackFlags].
+/// Editing operations like [AnimData::collapse_constant_tracks] leave these tracks unchanged.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RawTrackValues {
+    pub flags: TrackFlags,
+    pub frame_count: usize,
+    pub data: Vec<u8>,
 }
 
 impl TrackValues {
@@ -751,6 +1382,7 @@ impl TrackValues {
             TrackValues::PatternIndex(v) => v.len(),
             TrackValues::Boolean(v) => v.len(),
             TrackValues::Vector4(v) => v.len(),
+            TrackValues::Raw(v) => v.frame_count,
         }
     }
 
@@ -769,10 +1401,44 @@ impl TrackValues {
             TrackValues::PatternIndex(v) => v.is_empty(),
             TrackValues::Boolean(v) => v.is_empty(),
             TrackValues::Vector4(v) => v.is_empty(),
+            TrackValues::Raw(v) => v.frame_count == 0,
+        }
+    }
+
+    fn collapse_constant_frames(&mut self) {
+        match self {
+            TrackValues::Transform(v) => collapse_constant_frames(v),
+            TrackValues::UvTransform(v) => collapse_constant_frames(v),
+            TrackValues::Float(v) => collapse_constant_frames(v),
+            TrackValues::PatternIndex(v) => collapse_constant_frames(v),
+            TrackValues::Boolean(v) => collapse_constant_frames(v),
+            TrackValues::Vector4(v) => collapse_constant_frames(v),
+            TrackValues::Raw(_) => (),
+        }
+    }
+
+    fn resize(&mut self, frame_count: usize) {
+        match self {
+            TrackValues::Transform(v) => resize_frames(v, frame_count),
+            TrackValues::UvTransform(v) => resize_frames(v, frame_count),
+            TrackValues::Float(v) => resize_frames(v, frame_count),
+            TrackValues::PatternIndex(v) => resize_frames(v, frame_count),
+            TrackValues::Boolean(v) => resize_frames(v, frame_count),
+            TrackValues::Vector4(v) => resize_frames(v, frame_count),
+            TrackValues::Raw(_) => (),
         }
     }
 
-    fn track_type(&self) -> TrackTypeV2 {
+    /// The track type used when saving these values.
+    /**
+    ```rust
+    # use ssbh_data::anim_data::TrackValues;
+    use ssbh_lib::formats::anim::TrackTypeV2;
+
+    assert_eq!(TrackTypeV2::PatternIndex, TrackValues::from(vec![1u32, 2]).track_type());
+    ```
+     */
+    pub fn track_type(&self) -> TrackTypeV2 {
         match self {
             TrackValues::Transform(_) => TrackTypeV2::Transform,
             TrackValues::UvTransform(_) => TrackTypeV2::UvTransform,
@@ -780,6 +1446,44 @@ impl TrackValues {
             TrackValues::PatternIndex(_) => TrackTypeV2::PatternIndex,
             TrackValues::Boolean(_) => TrackTypeV2::Boolean,
             TrackValues::Vector4(_) => TrackTypeV2::Vector4,
+            TrackValues::Raw(v) => v.flags.track_type,
+        }
+    }
+}
+
+macro_rules! track_values_from_vec {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<Vec<$ty>> for TrackValues {
+                fn from(values: Vec<$ty>) -> Self {
+                    TrackValues::$variant(values)
+                }
+            }
+        )*
+    };
+}
+
+track_values_from_vec!(
+    Transform => Transform,
+    UvTransform => UvTransform,
+    f32 => Float,
+    u32 => PatternIndex,
+    bool => Boolean,
+    Vector4 => Vector4
+);
+
+fn resize_frames<T: Clone>(values: &mut Vec<T>, frame_count: usize) {
+    // Repeat the final frame to avoid introducing new values.
+    if let Some(last) = values.last().cloned() {
+        values.resize(frame_count, last);
+    }
+}
+
+fn collapse_constant_frames<T: PartialEq>(values: &mut Vec<T>) {
+    // A single frame is repeated for the entire animation.
+    if let Some((first, rest)) = values.split_first() {
+        if rest.iter().all(|v| v == first) {
+            values.truncate(1);
         }
     }
 }
@@ -825,36 +1529,200 @@ struct V12Test3 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_hex_eq;
+    use crate::skel_data::{BillboardType, BoneData};
+    use crate::test_fixtures;
+    use approx::assert_relative_eq;
+    use hexlit::hex;
+    use ssbh_lib::formats::anim::CompressionType;
 
     // TODO: Test the conversions more thoroughly.
 
     #[test]
-    fn create_empty_anim_v_2_0() {
-        let anim = create_anim(&AnimData {
-            major_version: 2,
+    fn transform_group_from_skel() {
+        let bone = |name: &str, transform, parent_index| BoneData {
+            name: name.to_string(),
+            transform,
+            parent_index,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        };
+        let skel = SkelData {
+            major_version: 1,
             minor_version: 0,
-            final_frame_index: 1.5,
-            groups: Vec::new(),
-        })
-        .unwrap();
+            bones: vec![
+                bone(
+                    "A",
+                    glam::Mat4::from_translation(glam::vec3(1.0, 2.0, 3.0)).to_cols_array_2d(),
+                    None,
+                ),
+                bone(
+                    "B",
+                    glam::Mat4::from_scale_rotation_translation(
+                        glam::vec3(2.0, 2.0, 2.0),
+                        glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+                        glam::Vec3::ZERO,
+                    )
+                    .to_cols_array_2d(),
+                    Some(0),
+                ),
+            ],
+        };
 
-        assert!(matches!(
-            anim,
-            Anim::V20 {
-                final_frame_index,
-                ..
-            } if final_frame_index == 1.5
-        ));
+        let group = AnimData::create_transform_group_from_skel(&skel);
+        assert_eq!(GroupType::Transform, group.group_type);
+        assert_eq!(
+            vec!["A", "B"],
+            group
+                .nodes
+                .iter()
+                .map(|n| n.name.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        match &group.nodes[0].tracks[0].values {
+            TrackValues::Transform(values) => assert_eq!(
+                vec![Transform {
+                    translation: Vector3::new(1.0, 2.0, 3.0),
+                    ..Transform::IDENTITY
+                }],
+                *values
+            ),
+            _ => panic!("unexpected track type"),
+        }
+
+        match &group.nodes[1].tracks[0].values {
+            TrackValues::Transform(values) => {
+                assert_eq!(1, values.len());
+                assert_relative_eq!(2.0, values[0].scale.x, epsilon = 1e-6);
+                assert_relative_eq!(0.5f32.sqrt(), values[0].rotation.z, epsilon = 1e-6);
+                assert_relative_eq!(0.5f32.sqrt(), values[0].rotation.w, epsilon = 1e-6);
+            }
+            _ => panic!("unexpected track type"),
+        }
+        assert_eq!("Transform", group.nodes[1].tracks[0].name);
     }
 
-    #[test]
-    fn create_empty_anim_v_2_1() {
-        let anim = create_anim(&AnimData {
+    fn frame_count_anim(final_frame_index: f32, frame_counts: &[usize]) -> AnimData {
+        AnimData {
             major_version: 2,
-            minor_version: 1,
-            final_frame_index: 2.5,
-            groups: Vec::new(),
-        })
+            minor_version: 0,
+            final_frame_index,
+            groups: vec![GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "mat".to_string(),
+                    tracks: frame_counts
+                        .iter()
+                        .enumerate()
+                        .map(|(i, count)| TrackData {
+                            name: format!("CustomFloat{i}"),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                            values: TrackValues::Float((0..*count).map(|f| f as f32).collect()),
+                        })
+                        .collect(),
+                }],
+            }],
+        }
+    }
+
+    fn frame_counts(data: &AnimData) -> Vec<usize> {
+        data.groups[0].nodes[0]
+            .tracks
+            .iter()
+            .map(|t| t.frame_count())
+            .collect()
+    }
+
+    #[test]
+    fn frame_count_mismatches() {
+        let data = frame_count_anim(2.5, &[1, 3, 2, 5]);
+        assert_eq!(5, data.max_frame_count());
+        assert_eq!(3, data.expected_frame_count());
+        assert_eq!(
+            vec![
+                FrameCountMismatch {
+                    group_type: GroupType::Material,
+                    node_name: "mat".to_string(),
+                    track_name: "CustomFloat2".to_string(),
+                    frame_count: 2,
+                    expected_frame_count: 3
+                },
+                FrameCountMismatch {
+                    group_type: GroupType::Material,
+                    node_name: "mat".to_string(),
+                    track_name: "CustomFloat3".to_string(),
+                    frame_count: 5,
+                    expected_frame_count: 3
+                }
+            ],
+            data.frame_count_mismatches()
+        );
+    }
+
+    #[test]
+    fn fix_frame_counts_pad() {
+        let mut data = frame_count_anim(2.0, &[1, 3, 2, 5]);
+        data.fix_frame_counts(FrameCountFix::Pad);
+
+        assert_eq!(4.0, data.final_frame_index);
+        assert_eq!(vec![1, 5, 5, 5], frame_counts(&data));
+        assert_eq!(
+            TrackValues::Float(vec![0.0, 1.0, 1.0, 1.0, 1.0]),
+            data.groups[0].nodes[0].tracks[2].values
+        );
+        assert!(data.frame_count_mismatches().is_empty());
+        assert!(create_anim(&data, false).is_ok());
+    }
+
+    #[test]
+    fn fix_frame_counts_trim() {
+        let mut data = frame_count_anim(2.0, &[1, 3, 2, 5]);
+        data.fix_frame_counts(FrameCountFix::Trim);
+
+        assert_eq!(2.0, data.final_frame_index);
+        assert_eq!(vec![1, 3, 3, 3], frame_counts(&data));
+        assert_eq!(
+            TrackValues::Float(vec![0.0, 1.0, 2.0]),
+            data.groups[0].nodes[0].tracks[3].values
+        );
+        assert!(data.frame_count_mismatches().is_empty());
+    }
+
+    #[test]
+    fn create_empty_anim_v_2_0() {
+        let anim = create_anim(
+            &AnimData {
+                major_version: 2,
+                minor_version: 0,
+                final_frame_index: 1.5,
+                groups: Vec::new(),
+            },
+            false,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            anim,
+            Anim::V20 {
+                final_frame_index,
+                ..
+            } if final_frame_index == 1.5
+        ));
+    }
+
+    #[test]
+    fn create_empty_anim_v_2_1() {
+        let anim = create_anim(
+            &AnimData {
+                major_version: 2,
+                minor_version: 1,
+                final_frame_index: 2.5,
+                groups: Vec::new(),
+            },
+            false,
+        )
         .unwrap();
 
         assert!(matches!(anim, Anim::V21 {
@@ -865,12 +1733,15 @@ mod tests {
 
     #[test]
     fn create_anim_negative_frame_index() {
-        let result = create_anim(&AnimData {
-            major_version: 2,
-            minor_version: 1,
-            final_frame_index: -1.0,
-            groups: Vec::new(),
-        });
+        let result = create_anim(
+            &AnimData {
+                major_version: 2,
+                minor_version: 1,
+                final_frame_index: -1.0,
+                groups: Vec::new(),
+            },
+            false,
+        );
 
         assert!(matches!(
             result,
@@ -882,23 +1753,26 @@ mod tests {
 
     #[test]
     fn create_anim_insufficient_frame_index() {
-        let result = create_anim(&AnimData {
-            major_version: 2,
-            minor_version: 1,
-            final_frame_index: 2.0,
-            groups: vec![GroupData {
-                group_type: GroupType::Visibility,
-                nodes: vec![NodeData {
-                    name: String::new(),
-                    tracks: vec![TrackData {
+        let result = create_anim(
+            &AnimData {
+                major_version: 2,
+                minor_version: 1,
+                final_frame_index: 2.0,
+                groups: vec![GroupData {
+                    group_type: GroupType::Visibility,
+                    nodes: vec![NodeData {
                         name: String::new(),
-                        values: TrackValues::Boolean(vec![true; 4]),
-                        compensate_scale: false,
-                        transform_flags: TransformFlags::default(),
+                        tracks: vec![TrackData {
+                            name: String::new(),
+                            values: TrackValues::Boolean(vec![true; 4]),
+                            compensate_scale: false,
+                            transform_flags: TransformFlags::default(),
+                        }],
                     }],
                 }],
-            }],
-        });
+            },
+            false,
+        );
 
         // A value of at least 3.0 is expected.
         assert!(matches!(
@@ -911,12 +1785,15 @@ mod tests {
 
     #[test]
     fn create_anim_zero_frame_index() {
-        let anim = create_anim(&AnimData {
-            major_version: 2,
-            minor_version: 1,
-            final_frame_index: 0.0,
-            groups: Vec::new(),
-        })
+        let anim = create_anim(
+            &AnimData {
+                major_version: 2,
+                minor_version: 1,
+                final_frame_index: 0.0,
+                groups: Vec::new(),
+            },
+            false,
+        )
         .unwrap();
 
         assert!(matches!(anim, Anim::V21 {
@@ -927,12 +1804,15 @@ mod tests {
 
     #[test]
     fn create_empty_anim_invalid_version() {
-        let result = create_anim(&AnimData {
-            major_version: 1,
-            minor_version: 2,
-            final_frame_index: 0.0,
-            groups: Vec::new(),
-        });
+        let result = create_anim(
+            &AnimData {
+                major_version: 1,
+                minor_version: 2,
+                final_frame_index: 0.0,
+                groups: Vec::new(),
+            },
+            false,
+        );
 
         assert!(matches!(
             result,
@@ -952,7 +1832,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        let anim_node = create_anim_node(&node, &mut buffer).unwrap();
+        let anim_node = create_anim_node(&node, &mut buffer, false).unwrap();
         assert_eq!("empty", anim_node.name.to_str().unwrap());
         assert!(anim_node.tracks.elements.is_empty());
     }
@@ -979,7 +1859,7 @@ mod tests {
 
         let mut buffer = Cursor::new(Vec::new());
 
-        let anim_node = create_anim_node(&node, &mut buffer).unwrap();
+        let anim_node = create_anim_node(&node, &mut buffer, false).unwrap();
         assert_eq!("empty", anim_node.name.to_str().unwrap());
         assert_eq!(2, anim_node.tracks.elements.len());
 
@@ -1191,6 +2071,252 @@ mod tests {
         );
     }
 
+    #[test]
+    fn track_buffer_float_multiple_frames() {
+        // Use direct data since compression doesn't save space.
+        let (compression_type, data) =
+            create_track_buffer(&TrackValues::Float(vec![1.0, 2.0, 3.0]), false, true).unwrap();
+        assert_eq!(CompressionType::Direct, compression_type);
+        assert_eq!(12, data.len());
+    }
+
+    #[test]
+    fn track_buffer_float_all_equal() {
+        // Compression is lossy, so the default should still use direct data.
+        let (compression_type, data) =
+            create_track_buffer(&TrackValues::Float(vec![1.0; 10]), false, false).unwrap();
+        assert_eq!(CompressionType::Direct, compression_type);
+        assert_eq!(40, data.len());
+    }
+
+    #[test]
+    fn track_buffer_float_all_equal_compress_smaller_tracks() {
+        // Compressing uses 0 bits per frame, which is smaller than direct data.
+        let (compression_type, data) =
+            create_track_buffer(&TrackValues::Float(vec![1.0; 10]), false, true).unwrap();
+        assert_eq!(CompressionType::Compressed, compression_type);
+        assert!(data.len() < 40);
+    }
+
+    #[test]
+    fn anim_compressed_tracks() {
+        let data = test_fixtures::transform_track_anim_data(
+            "a",
+            9.0,
+            vec![TrackValues::Float(vec![1.0; 10])],
+        );
+
+        let compression_type = |anim: &Anim| match anim {
+            Anim::V20 { groups, .. } => {
+                groups.elements[0].nodes.elements[0].tracks.elements[0]
+                    .flags
+                    .compression_type
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            CompressionType::Direct,
+            compression_type(&Anim::try_from(&data).unwrap())
+        );
+        assert_eq!(
+            CompressionType::Compressed,
+            compression_type(&data.to_anim_compressed().unwrap())
+        );
+    }
+
+    #[test]
+    fn track_buffer_transform_single_frame() {
+        let (compression_type, _) = create_track_buffer(
+            &TrackValues::Transform(vec![Transform::IDENTITY]),
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(CompressionType::ConstTransform, compression_type);
+    }
+
+    fn track(values: TrackValues) -> TrackData {
+        TrackData {
+            name: String::new(),
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values,
+        }
+    }
+
+    #[test]
+    fn optimize_anim_tracks() {
+        let mut anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 2.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "material".to_string(),
+                    tracks: vec![
+                        track(TrackValues::Float(vec![0.5; 3])),
+                        track(TrackValues::Float(vec![0.5, 0.5, 1.0])),
+                        track(TrackValues::Vector4(vec![
+                            Vector4::new(1.0, 2.0, 3.0, 4.0);
+                            3
+                        ])),
+                        track(TrackValues::PatternIndex(Vec::new())),
+                    ],
+                }],
+            }],
+        };
+
+        anim.optimize();
+
+        let tracks = &anim.groups[0].nodes[0].tracks;
+        assert_eq!(TrackValues::Float(vec![0.5]), tracks[0].values);
+        assert_eq!(TrackValues::Float(vec![0.5, 0.5, 1.0]), tracks[1].values);
+        assert_eq!(
+            TrackValues::Vector4(vec![Vector4::new(1.0, 2.0, 3.0, 4.0)]),
+            tracks[2].values
+        );
+        assert_eq!(TrackValues::PatternIndex(Vec::new()), tracks[3].values);
+    }
+
+    #[test]
+    fn optimize_anim_transform_tracks() {
+        let mut values = vec![Transform::IDENTITY; 8];
+        let mut anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 7.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "Trans".to_string(),
+                    tracks: vec![track(TrackValues::Transform(values.clone()))],
+                }],
+            }],
+        };
+
+        anim.optimize();
+        assert_eq!(
+            TrackValues::Transform(vec![Transform::IDENTITY]),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+
+        // Any changed frame should preserve the original values.
+        values[7].translation.x = 1.0;
+        anim.groups[0].nodes[0].tracks[0].values = TrackValues::Transform(values.clone());
+        anim.optimize();
+        assert_eq!(
+            TrackValues::Transform(values),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    fn named_track(name: &str, values: TrackValues) -> TrackData {
+        TrackData {
+            name: name.to_string(),
+            ..track(values)
+        }
+    }
+
+    #[test]
+    fn insert_track_new_group_and_node() {
+        let mut anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 1.0,
+            groups: Vec::new(),
+        };
+
+        let new_track = named_track("Visibility", TrackValues::Boolean(vec![true, false]));
+        anim.insert_track(GroupType::Visibility, "a", new_track.clone());
+
+        assert_eq!(
+            vec![GroupData {
+                group_type: GroupType::Visibility,
+                nodes: vec![NodeData {
+                    name: "a".to_string(),
+                    tracks: vec![new_track]
+                }]
+            }],
+            anim.groups
+        );
+    }
+
+    #[test]
+    fn insert_track_copy_reference_flags() {
+        let reference = TrackData {
+            name: "Transform".to_string(),
+            compensate_scale: true,
+            transform_flags: TransformFlags {
+                override_scale: true,
+                ..Default::default()
+            },
+            values: TrackValues::Transform(vec![Transform::IDENTITY]),
+        };
+        let mut anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 2.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "a".to_string(),
+                    tracks: vec![reference.clone()],
+                }],
+            }],
+        };
+
+        // The reference track is constant, so the new track should also be constant.
+        anim.insert_track(
+            GroupType::Transform,
+            "b",
+            named_track(
+                "Transform",
+                TrackValues::Transform(vec![Transform::IDENTITY; 3]),
+            ),
+        );
+
+        assert_eq!(reference, anim.groups[0].nodes[1].tracks[0]);
+    }
+
+    #[test]
+    fn insert_track_replace_existing() {
+        let mut anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 1.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "mat".to_string(),
+                    tracks: vec![
+                        named_track("CustomFloat0", TrackValues::Float(vec![0.0, 1.0])),
+                        TrackData {
+                            compensate_scale: true,
+                            ..named_track("CustomFloat1", TrackValues::Float(vec![0.0, 1.0]))
+                        },
+                    ],
+                }],
+            }],
+        };
+
+        anim.insert_track(
+            GroupType::Material,
+            "mat",
+            named_track("CustomFloat1", TrackValues::Float(vec![2.0, 3.0])),
+        );
+
+        let tracks = &anim.groups[0].nodes[0].tracks;
+        assert_eq!(2, tracks.len());
+        assert_eq!(
+            TrackData {
+                compensate_scale: true,
+                ..named_track("CustomFloat1", TrackValues::Float(vec![2.0, 3.0]))
+            },
+            tracks[1]
+        );
+    }
+
     #[test]
     fn read_v20_track_invalid_offset() {
         let result = create_track_data_v20(
@@ -1271,4 +2397,343 @@ mod tests {
             })
         ));
     }
+
+    fn float_track(frame_count: u32, data_size: u64) -> TrackV2 {
+        TrackV2 {
+            name: "abc".into(),
+            flags: TrackFlags {
+                track_type: TrackTypeV2::Float,
+                compression_type: CompressionType::Direct,
+            },
+            frame_count,
+            transform_flags: AnimTransformFlags::new(),
+            data_offset: 0,
+            data_size,
+        }
+    }
+
+    #[test]
+    fn read_v20_track_lenient_truncated_buffer() {
+        // The buffer only contains 2 of the 3 frames.
+        let buffer = [0u8, 0u8, 128u8, 63u8, 0u8, 0u8, 0u8, 64u8, 0u8, 0u8];
+        let (track, error) = create_track_data_v20_lenient(&float_track(3, 12), &buffer);
+
+        assert_eq!(TrackValues::Float(vec![1.0, 2.0]), track.unwrap().values);
+        assert!(matches!(error, Some(error::Error::BinRead(_))));
+    }
+
+    #[test]
+    fn read_v20_track_lenient_invalid_size() {
+        // All frames can still be read from the remaining data.
+        let buffer = [0u8, 0u8, 128u8, 63u8, 0u8, 0u8, 0u8, 64u8];
+        let (track, error) = create_track_data_v20_lenient(&float_track(2, 12), &buffer);
+
+        assert_eq!(TrackValues::Float(vec![1.0, 2.0]), track.unwrap().values);
+        assert!(matches!(
+            error,
+            Some(error::Error::InvalidTrackDataRange {
+                start: 0,
+                size: 12,
+                buffer_size: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn read_anim_lenient_skip_empty_track() {
+        let anim = Anim::V20 {
+            final_frame_index: 1.0,
+            unk1: 1,
+            unk2: 3,
+            name: "".into(),
+            groups: vec![Group {
+                group_type: GroupType::Material,
+                nodes: vec![Node {
+                    name: "mat".into(),
+                    tracks: vec![
+                        TrackV2 {
+                            name: "CustomFloat0".into(),
+                            ..float_track(2, 8)
+                        },
+                        TrackV2 {
+                            name: "CustomFloat1".into(),
+                            data_offset: 8,
+                            ..float_track(2, 8)
+                        },
+                    ]
+                    .into(),
+                }]
+                .into(),
+            }]
+            .into(),
+            buffer: vec![0u8, 0u8, 128u8, 63u8, 0u8, 0u8, 0u8, 64u8].into(),
+        };
+
+        // The second track has no data, so the strict conversion fails.
+        let e = AnimData::try_from(&anim).unwrap_err();
+        assert_eq!(
+            r#"Material group node "mat" track "CustomFloat1""#,
+            e.to_string()
+        );
+        assert_eq!(
+            "track data range 8..8+8 is out of range for a buffer of size 8",
+            crate::context::root_cause(e.as_ref()).to_string()
+        );
+
+        let (data, warnings) = AnimData::from_anim_lenient(&anim).unwrap();
+        let tracks = &data.groups[0].nodes[0].tracks;
+        assert_eq!(1, tracks.len());
+        assert_eq!("CustomFloat0", tracks[0].name);
+
+        assert_eq!(1, warnings.len());
+        assert_eq!(GroupType::Material, warnings[0].group_type);
+        assert_eq!("mat", warnings[0].node_name);
+        assert_eq!("CustomFloat1", warnings[0].track_name);
+        assert_eq!(None, warnings[0].frames_read);
+    }
+
+    fn node_with_tracks(name: &str, track_names: &[&str]) -> NodeData {
+        NodeData {
+            name: name.to_string(),
+            tracks: track_names
+                .iter()
+                .map(|t| named_track(t, TrackValues::Float(vec![0.0])))
+                .collect(),
+        }
+    }
+
+    fn unsorted_anim() -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![
+                GroupData {
+                    group_type: GroupType::Material,
+                    nodes: vec![
+                        node_with_tracks("b", &["CustomFloat1", "CustomFloat0"]),
+                        node_with_tracks("a", &["CustomFloat0"]),
+                    ],
+                },
+                GroupData {
+                    group_type: GroupType::Visibility,
+                    nodes: vec![NodeData {
+                        name: "c".to_string(),
+                        tracks: vec![named_track("Visibility", TrackValues::Boolean(vec![true]))],
+                    }],
+                },
+            ],
+        }
+    }
+
+    fn names(data: &AnimData) -> Vec<(GroupType, &str, Vec<&str>)> {
+        data.groups
+            .iter()
+            .flat_map(|g| {
+                g.nodes.iter().map(move |n| {
+                    (
+                        g.group_type,
+                        n.name.as_str(),
+                        n.tracks.iter().map(|t| t.name.as_str()).collect(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn convert_anim_preserves_order() {
+        let data = unsorted_anim();
+        let new_data = AnimData::try_from(Anim::try_from(&data).unwrap()).unwrap();
+        assert_eq!(names(&data), names(&new_data));
+    }
+
+    #[test]
+    fn convert_anim_unknown_group_type() {
+        let data = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![
+                GroupData {
+                    group_type: GroupType::from(7),
+                    nodes: vec![NodeData {
+                        name: "a".to_string(),
+                        tracks: vec![named_track("Unk", TrackValues::Float(vec![0.5]))],
+                    }],
+                },
+                GroupData {
+                    group_type: GroupType::Visibility,
+                    nodes: vec![NodeData {
+                        name: "b".to_string(),
+                        tracks: vec![named_track("Visibility", TrackValues::Boolean(vec![true]))],
+                    }],
+                },
+            ],
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        Anim::try_from(&data).unwrap().write(&mut writer).unwrap();
+        writer.set_position(0);
+
+        // Tracks for unknown groups aren't decoded.
+        let new_data = AnimData::try_from(Anim::read(&mut writer).unwrap()).unwrap();
+        assert_eq!(GroupType::from(7), new_data.groups[0].group_type);
+        assert_eq!(
+            TrackValues::Raw(RawTrackValues {
+                flags: TrackFlags {
+                    track_type: TrackTypeV2::Float,
+                    compression_type: CompressionType::Constant,
+                },
+                frame_count: 1,
+                data: 0.5f32.to_le_bytes().to_vec(),
+            }),
+            new_data.groups[0].nodes[0].tracks[0].values
+        );
+        assert_eq!(data.groups[1], new_data.groups[1]);
+
+        // Saving again should produce the same file.
+        let mut new_writer = Cursor::new(Vec::new());
+        Anim::try_from(&new_data)
+            .unwrap()
+            .write(&mut new_writer)
+            .unwrap();
+        assert_eq!(writer.into_inner(), new_writer.into_inner());
+    }
+
+    #[test]
+    fn convert_anim_unknown_group_type_raw_data() {
+        // The data doesn't need to be a valid compressed buffer.
+        let raw = TrackValues::Raw(RawTrackValues {
+            flags: TrackFlags {
+                track_type: TrackTypeV2::Transform,
+                compression_type: CompressionType::Compressed,
+            },
+            frame_count: 3,
+            data: vec![1, 2, 3, 4, 5],
+        });
+        let data = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 2.0,
+            groups: vec![GroupData {
+                group_type: GroupType::from(3),
+                nodes: vec![NodeData {
+                    name: "a".to_string(),
+                    tracks: vec![named_track("Unk", raw.clone())],
+                }],
+            }],
+        };
+
+        let anim = Anim::try_from(&data).unwrap();
+        match &anim {
+            Anim::V20 { groups, buffer, .. } => {
+                assert_eq!(
+                    3,
+                    groups.elements[0].nodes.elements[0].tracks.elements[0].frame_count
+                );
+                assert_eq!(vec![1, 2, 3, 4, 5], buffer.elements);
+            }
+            _ => panic!("unexpected version"),
+        }
+
+        assert_eq!(data, AnimData::try_from(anim).unwrap());
+    }
+
+    #[test]
+    fn sort_anim() {
+        let mut data = unsorted_anim();
+        data.sort();
+        assert_eq!(
+            vec![
+                (GroupType::Visibility, "c", vec!["Visibility"]),
+                (GroupType::Material, "a", vec!["CustomFloat0"]),
+                (
+                    GroupType::Material,
+                    "b",
+                    vec!["CustomFloat0", "CustomFloat1"]
+                ),
+            ],
+            names(&data)
+        );
+    }
+
+    fn anim_bytes(data: &AnimData) -> Vec<u8> {
+        let mut writer = Cursor::new(Vec::new());
+        Anim::try_from(data).unwrap().write(&mut writer).unwrap();
+        writer.into_inner()
+    }
+
+    #[test]
+    fn write_compressed_float_expected_bytes() {
+        // Values without an exact float representation should always round to the same bits.
+        let values = TrackValues::Float((0..10).map(|i| i as f32 * 0.1 - 0.35).collect());
+        assert_hex_eq!(
+            &write_track_values(&values, CompressionType::Compressed, false).unwrap(),
+            &hex!("04000000 20001800 24000000 0A000000 3333B3BE CECC0C3F 18000000 00000000 3333B3BE 000000C6 711C8DE3 38545555 1BC771E2 388EA9AA AA6F1CC7 378EE3FF FFFF")
+        );
+    }
+
+    #[test]
+    fn write_compressed_vector4_expected_bytes() {
+        let values = TrackValues::Vector4(
+            (0..10)
+                .map(|i| {
+                    let x = i as f32 * 0.1 - 0.35;
+                    Vector4::new(x, 0.3, x * 2.0, 1.0)
+                })
+                .collect(),
+        );
+        assert_hex_eq!(
+            &write_track_values(&values, CompressionType::Compressed, false).unwrap(),
+            &hex!("04000000 50003000 60000000 0A000000 3333B3BE CECC0C3F 18000000 00000000 9A99993E 9A99993E 00000000 00000000 333333BF CECC8C3F 18000000 00000000 0000803F 0000803F 00000000 00000000 3333B3BE 9A99993E 333333BF 0000803F 00000000 0000C671 1CC6711C 8DE3388D E3385455 55545555 1BC7711B C771E238 8EE2388E A9AAAAA9 AAAA6F1C C76F1CC7 378EE337 8EE3FFFF FFFFFFFF")
+        );
+    }
+
+    #[test]
+    fn write_compressed_float_out_of_range_expected_bytes() {
+        // Out of range and NaN values shouldn't depend on platform specific conversions.
+        let values = TrackValues::Float(vec![f32::NAN, 0.0, f32::INFINITY]);
+        assert_hex_eq!(
+            &write_track_values(&values, CompressionType::Compressed, false).unwrap(),
+            &hex!("04000000 20001800 24000000 03000000 00000000 0000807F 18000000 00000000 00000000 00000000 00000000 00")
+        );
+
+        let values = TrackValues::Float(vec![-1.0, 0.0, 2.0, f32::INFINITY]);
+        assert_hex_eq!(
+            &write_track_values(&values, CompressionType::Compressed, false).unwrap(),
+            &hex!("04000000 20001800 24000000 04000000 000080BF 0000807F 18000000 00000000 000080BF 00000000 00000000 00000000")
+        );
+    }
+
+    #[test]
+    fn anim_round_trip_track_types() {
+        for values in [
+            // Compressed values with a range larger than the default float bit count.
+            TrackValues::PatternIndex((0..40).map(|i| i * 1000000).collect()),
+            TrackValues::PatternIndex(vec![u32::MAX, 0, 1, 2]),
+            TrackValues::Boolean((0..40).map(|i| i % 3 == 0).collect()),
+            TrackValues::Boolean(vec![true, false]),
+            TrackValues::Vector4(vec![
+                Vector4::new(0.1, 0.2, 0.3, 0.4),
+                Vector4::new(-1.0, 2.0, -3.0, 4.0),
+            ]),
+        ] {
+            let data = AnimData {
+                major_version: 2,
+                minor_version: 0,
+                final_frame_index: values.len() as f32 - 1.0,
+                groups: vec![GroupData {
+                    group_type: GroupType::Material,
+                    nodes: vec![NodeData {
+                        name: "a".to_string(),
+                        tracks: vec![TrackData::new("CustomBoolean0", values)],
+                    }],
+                }],
+            };
+
+            let anim = Anim::read(&mut Cursor::new(anim_bytes(&data))).unwrap();
+            assert_eq!(data, AnimData::try_from(anim).unwrap());
+        }
+    }
 }
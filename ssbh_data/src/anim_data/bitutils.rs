@@ -1,11 +1,13 @@
 use bitvec::prelude::*;
 use thiserror::Error;
 
+/// Reads values with arbitrary bit counts from a byte buffer in least significant bit order.
 pub struct BitReader {
     bits: BitVec<u8, Lsb0>,
     index: usize,
 }
 
+/// Errors while reading from a [BitReader].
 #[derive(Debug, Error)]
 pub enum BitReadError {
     #[error("failed to read enough bits from reader.")]
@@ -61,6 +63,7 @@ impl BitReader {
 // Assume preallocated sizes for writing bits.
 // This requires storing the current index.
 // TODO: Find an efficient way to do this with just appending.
+/// Writes values with arbitrary bit counts to a preallocated bit buffer in least significant bit order.
 pub struct BitWriter {
     bits: BitVec<u8, Lsb0>,
     index: usize,
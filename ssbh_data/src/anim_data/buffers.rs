@@ -1,5 +1,11 @@
+//! Low level functions for encoding and decoding the track buffers in [Anim](ssbh_lib::formats::anim::Anim) files.
+//!
+//! Most applications should use [AnimData](super::AnimData) instead.
+//! These functions are intended for research tools experimenting with alternate compression settings.
+//! Compressed buffers start with a [CompressedTrackData] containing the header, default value, and compression ranges
+//! followed by the values for each frame packed into a bit buffer with [CompressedData::compress].
 use binrw::io::{Cursor, Read, Seek, Write};
-use binrw::{BinRead, BinReaderExt, BinResult};
+use binrw::{BinRead, BinReaderExt};
 use bitvec::prelude::*;
 use itertools::Itertools;
 
@@ -10,13 +16,15 @@ use ssbh_lib::{
     Ptr16, Ptr32, Vector4,
 };
 
-use super::{
-    bitutils::{BitReader, BitWriter},
-    compression::{
-        CompressedBuffer, CompressedHeader, CompressedTrackData, Compression, CompressionFlags,
-    },
+use super::{error::Error, TrackValues, Transform, UvTransform};
+
+pub use super::bitutils::{BitReadError, BitReader, BitWriter};
+pub use super::compression::{
+    Boolean, CompressedBits, CompressedBuffer, CompressedData, CompressedHeader,
+    CompressedTrackData, Compression, CompressionFlags, F32Compression, TransformCompression,
+    U32Compression, UncompressedTransform, UvTransformCompression, Vector3Compression,
+    Vector4Compression, DEFAULT_F32_BIT_COUNT,
 };
-use super::{compression::*, error::Error, TrackValues, Transform, UvTransform};
 
 impl TrackValues {
     pub(crate) fn write<W: Write + Seek>(
@@ -25,6 +33,12 @@ impl TrackValues {
         compression: CompressionType,
         compensate_scale: bool,
     ) -> Result<(), Error> {
+        // Raw data already includes any compression headers.
+        if let TrackValues::Raw(v) = self {
+            writer.write_all(&v.data)?;
+            return Ok(());
+        }
+
         // TODO: Find a way to simplify calculating the default and compression.
         // TODO: Find a way to clean up this code.
         // The default depends on the values.
@@ -67,6 +81,7 @@ impl TrackValues {
                     TrackValues::Vector4(values) => {
                         write_compressed(writer, values, flags, compensate_scale)?
                     }
+                    TrackValues::Raw(_) => (),
                 }
             }
             _ => match self {
@@ -85,6 +100,7 @@ impl TrackValues {
                     values.write(writer)?;
                 }
                 TrackValues::Vector4(values) => values.write(writer)?,
+                TrackValues::Raw(_) => (),
             },
         }
 
@@ -104,6 +120,7 @@ impl TrackValues {
             TrackValues::PatternIndex(_) => <u32 as CompressedData>::compressed_overhead_in_bytes(),
             TrackValues::Boolean(_) => <Boolean as CompressedData>::compressed_overhead_in_bytes(),
             TrackValues::Vector4(_) => <Vector4 as CompressedData>::compressed_overhead_in_bytes(),
+            TrackValues::Raw(_) => 0,
         }
     }
 
@@ -115,11 +132,26 @@ impl TrackValues {
             TrackValues::PatternIndex(_) => u32::default().size_in_bytes(),
             TrackValues::Boolean(_) => Boolean::default().size_in_bytes(),
             TrackValues::Vector4(_) => Vector4::default().size_in_bytes(),
+            TrackValues::Raw(_) => 0,
         }
     }
 }
 
-fn write_compressed<W: Write + Seek, T: CompressedData>(
+/// Encodes `values` as a track buffer with the given `compression`.
+/// This is the inverse of [read_track_values].
+pub fn write_track_values(
+    values: &TrackValues,
+    compression: CompressionType,
+    compensate_scale: bool,
+) -> Result<Vec<u8>, Error> {
+    let mut writer = Cursor::new(Vec::new());
+    values.write(&mut writer, compression, compensate_scale)?;
+    Ok(writer.into_inner())
+}
+
+/// Writes the header, compression, and compressed bits for `values` to `writer`.
+/// The default value and compression ranges are calculated with [CompressedData::get_default_and_compression].
+pub fn write_compressed<W: Write + Seek, T: CompressedData>(
     writer: &mut W,
     values: &[T],
     flags: CompressionFlags,
@@ -145,7 +177,8 @@ fn write_compressed<W: Write + Seek, T: CompressedData>(
     Ok(())
 }
 
-fn create_compressed_buffer<T: CompressedData>(
+/// Packs the bits for each of the `values` into a single buffer using [CompressedData::compress].
+pub fn create_compressed_buffer<T: CompressedData>(
     values: &[T],
     compression: &T::Compression,
     flags: CompressionFlags,
@@ -165,23 +198,75 @@ fn create_compressed_buffer<T: CompressedData>(
     writer.into_bytes()
 }
 
-fn read_uncompressed<R, T>(reader: &mut R, frame_count: usize) -> BinResult<Vec<T>>
+/// Controls whether reading stops with an error or returns the values read so far.
+#[derive(Debug, Default)]
+struct PartialRead {
+    lenient: bool,
+    error: Option<Error>,
+}
+
+impl PartialRead {
+    fn stop<T>(&mut self, values: Vec<T>, error: Error) -> Result<Vec<T>, Error> {
+        // Tracks without any values can't be recovered.
+        if self.lenient && !values.is_empty() {
+            self.error = Some(error);
+            Ok(values)
+        } else {
+            Err(error)
+        }
+    }
+}
+
+fn read_uncompressed<R, T>(
+    reader: &mut R,
+    frame_count: usize,
+    partial: &mut PartialRead,
+) -> Result<Vec<T>, Error>
 where
     R: Read + Seek,
     T: for<'a> BinRead<Args<'a> = ()>,
 {
     let mut values = Vec::new();
     for _ in 0..frame_count {
-        let value: T = reader.read_le()?;
-        values.push(value);
+        match reader.read_le::<T>() {
+            Ok(value) => values.push(value),
+            Err(e) => return partial.stop(values, e.into()),
+        }
     }
     Ok(values)
 }
 
+/// Decodes a track buffer with `count` frames using the type and compression in `flags`.
+/// Returns the values and whether the values compensate for the parent's scale.
 pub fn read_track_values(
     track_data: &[u8],
     flags: TrackFlags,
     count: usize,
+) -> Result<(TrackValues, bool), Error> {
+    read_track_values_inner(track_data, flags, count, &mut PartialRead::default())
+}
+
+/// Reads the values like [read_track_values] but returns the frames read before any error.
+/// The error is returned with the partial values if not all frames could be read.
+pub fn read_track_values_partial(
+    track_data: &[u8],
+    flags: TrackFlags,
+    count: usize,
+) -> Result<(TrackValues, bool, Option<Error>), Error> {
+    let mut partial = PartialRead {
+        lenient: true,
+        error: None,
+    };
+    let (values, compensate_scale) =
+        read_track_values_inner(track_data, flags, count, &mut partial)?;
+    Ok((values, compensate_scale, partial.error))
+}
+
+fn read_track_values_inner(
+    track_data: &[u8],
+    flags: TrackFlags,
+    count: usize,
+    partial: &mut PartialRead,
 ) -> Result<(TrackValues, bool), Error> {
     // TODO: Are Const, ConstTransform, and Direct all the same?
     // TODO: Can frame count be higher than 1 for Const and ConstTransform?
@@ -195,7 +280,8 @@ pub fn read_track_values(
     let (values, compensate_scale) = match flags.compression_type {
         CompressionType::Compressed => match flags.track_type {
             TrackTy::Transform => {
-                let values: Vec<UncompressedTransform> = read_compressed(&mut reader, count)?;
+                let values: Vec<UncompressedTransform> =
+                    read_compressed_partial(&mut reader, count, partial)?;
                 // TODO: This should be an error if the values aren't all the same.
                 let compensate_scale = values
                     .iter()
@@ -207,12 +293,15 @@ pub fn read_track_values(
                 (Values::Transform(values), compensate_scale)
             }
             TrackTy::UvTransform => (
-                Values::UvTransform(read_compressed(&mut reader, count)?),
+                Values::UvTransform(read_compressed_partial(&mut reader, count, partial)?),
+                false,
+            ),
+            TrackTy::Float => (
+                Values::Float(read_compressed_partial(&mut reader, count, partial)?),
                 false,
             ),
-            TrackTy::Float => (Values::Float(read_compressed(&mut reader, count)?), false),
             TrackTy::PatternIndex => (
-                Values::PatternIndex(read_compressed(&mut reader, count)?),
+                Values::PatternIndex(read_compressed_partial(&mut reader, count, partial)?),
                 false,
             ),
             TrackTy::Boolean => {
@@ -220,17 +309,21 @@ pub fn read_track_values(
                 // TODO: Create a separate UncompressedData trait?
                 // i.e. CompressedData: UncompressedData
                 // This may be able to simplify the conversion logic for bool and Transform.
-                let values: Vec<Boolean> = read_compressed(&mut reader, count)?;
+                let values: Vec<Boolean> = read_compressed_partial(&mut reader, count, partial)?;
                 (
                     Values::Boolean(values.iter().map(bool::from).collect()),
                     false,
                 )
             }
-            TrackTy::Vector4 => (Values::Vector4(read_compressed(&mut reader, count)?), false),
+            TrackTy::Vector4 => (
+                Values::Vector4(read_compressed_partial(&mut reader, count, partial)?),
+                false,
+            ),
         },
         _ => match flags.track_type {
             TrackTy::Transform => {
-                let values: Vec<UncompressedTransform> = read_uncompressed(&mut reader, count)?;
+                let values: Vec<UncompressedTransform> =
+                    read_uncompressed(&mut reader, count, partial)?;
                 // TODO: This should be an error if the values aren't all the same.
                 let compensate_scale = values
                     .iter()
@@ -243,23 +336,26 @@ pub fn read_track_values(
                 )
             }
             TrackTy::UvTransform => (
-                Values::UvTransform(read_uncompressed(&mut reader, count)?),
+                Values::UvTransform(read_uncompressed(&mut reader, count, partial)?),
+                false,
+            ),
+            TrackTy::Float => (
+                Values::Float(read_uncompressed(&mut reader, count, partial)?),
                 false,
             ),
-            TrackTy::Float => (Values::Float(read_uncompressed(&mut reader, count)?), false),
             TrackTy::PatternIndex => (
-                Values::PatternIndex(read_uncompressed(&mut reader, count)?),
+                Values::PatternIndex(read_uncompressed(&mut reader, count, partial)?),
                 false,
             ),
             TrackTy::Boolean => {
-                let values = read_uncompressed(&mut reader, count)?;
+                let values = read_uncompressed(&mut reader, count, partial)?;
                 (
                     Values::Boolean(values.iter().map(bool::from).collect_vec()),
                     false,
                 )
             }
             TrackTy::Vector4 => (
-                Values::Vector4(read_uncompressed(&mut reader, count)?),
+                Values::Vector4(read_uncompressed(&mut reader, count, partial)?),
                 false,
             ),
         },
@@ -268,18 +364,29 @@ pub fn read_track_values(
     Ok((values, compensate_scale))
 }
 
-fn read_compressed<R: Read + Seek, T: CompressedData>(
+/// Reads the header and decompresses `frame_count` many values from `reader`.
+/// A bit count of 0 produces a single frame with the default value.
+pub fn read_compressed<R: Read + Seek, T: CompressedData>(
     reader: &mut R,
     frame_count: usize,
+) -> Result<Vec<T>, Error> {
+    read_compressed_partial(reader, frame_count, &mut PartialRead::default())
+}
+
+fn read_compressed_partial<R: Read + Seek, T: CompressedData>(
+    reader: &mut R,
+    frame_count: usize,
+    partial: &mut PartialRead,
 ) -> Result<Vec<T>, Error> {
     let data: CompressedTrackData<T> = reader.read_le()?;
-    let values = read_compressed_inner(data, frame_count)?;
+    let values = read_compressed_inner(data, frame_count, partial)?;
     Ok(values)
 }
 
 fn read_compressed_inner<T: CompressedData>(
     data: CompressedTrackData<T>,
     frame_count: usize,
+    partial: &mut PartialRead,
 ) -> Result<Vec<T>, Error> {
     // Check for unexpected compression flags.
     // This is either an unresearched flag or an improperly compressed file.
@@ -311,19 +418,36 @@ fn read_compressed_inner<T: CompressedData>(
         frame_count
     };
 
+    let default = data
+        .header
+        .default_data
+        .as_ref()
+        .ok_or(Error::MalformedCompressionHeader)?;
+
+    // Check the bounds for each frame explicitly to identify the frame for truncated buffers.
+    let buffer_bits = buffer.len().saturating_mul(8);
+
     let mut values = Vec::new();
-    for _ in 0..actual_count {
-        let value = T::decompress(
+    for frame in 0..actual_count {
+        let end_bit = (frame + 1).checked_mul(expected_bit_count);
+        if end_bit.is_none_or(|end| end > buffer_bits) {
+            let error = Error::CompressedFrameOutOfRange {
+                frame,
+                bits_per_entry: expected_bit_count,
+                buffer_bits,
+            };
+            return partial.stop(values, error);
+        }
+
+        match T::decompress(
             &mut reader,
             &data.compression,
-            data.header
-                .default_data
-                .as_ref()
-                .ok_or(Error::MalformedCompressionHeader)?,
+            default,
             T::get_args(&data.header),
-        )?;
-
-        values.push(value);
+        ) {
+            Ok(value) => values.push(value),
+            Err(e) => return partial.stop(values, e.into()),
+        }
     }
 
     Ok(values)
@@ -332,10 +456,29 @@ fn read_compressed_inner<T: CompressedData>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::anim_data::compression::BitReaderExt;
     use crate::{anim_data::Transform, assert_hex_eq};
     use hexlit::hex;
     use ssbh_lib::{formats::anim::TrackTypeV2, Vector3};
 
+    #[test]
+    fn write_read_track_values_compressed_floats() {
+        let values = TrackValues::Float(vec![0.5, 1.0, 2.0]);
+        let data = write_track_values(&values, CompressionType::Compressed, false).unwrap();
+
+        let (new_values, compensate_scale) = read_track_values(
+            &data,
+            TrackFlags {
+                track_type: TrackTypeV2::Float,
+                compression_type: CompressionType::Compressed,
+            },
+            3,
+        )
+        .unwrap();
+        assert_eq!(values, new_values);
+        assert!(!compensate_scale);
+    }
+
     #[test]
     fn read_constant_vector4_single_frame() {
         // fighter/mario/motion/body/c00/a00wait1.nuanmb, EyeL, CustomVector30
@@ -996,6 +1139,46 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn write_compressed_pattern_index_multiple_frames() {
+        // Constant values are collapsed to a single frame when reading.
+        for (values, bits_per_entry, expected) in [
+            (
+                vec![1, 2, 2, 2, 2, 2, 2, 2],
+                1,
+                vec![1, 2, 2, 2, 2, 2, 2, 2],
+            ),
+            (vec![5; 8], 0, vec![5]),
+            (vec![u32::MAX, 0, 1, 2], 32, vec![u32::MAX, 0, 1, 2]),
+        ] {
+            let mut writer = Cursor::new(Vec::new());
+            TrackValues::write(
+                &TrackValues::PatternIndex(values.clone()),
+                &mut writer,
+                CompressionType::Compressed,
+                false,
+            )
+            .unwrap();
+
+            // The bits per entry are stored after the flags.
+            assert_eq!(
+                bits_per_entry,
+                u16::from_le_bytes(writer.get_ref()[6..8].try_into().unwrap())
+            );
+
+            let (new_values, _) = read_track_values(
+                writer.get_ref(),
+                TrackFlags {
+                    track_type: TrackTypeV2::PatternIndex,
+                    compression_type: CompressionType::Compressed,
+                },
+                values.len(),
+            )
+            .unwrap();
+            assert_eq!(TrackValues::PatternIndex(expected), new_values);
+        }
+    }
+
     #[test]
     fn read_constant_float_single_frame() {
         // assist/shovelknight/model/body/c00/model.nuanmb, asf_shovelknight_mat, CustomFloat8
@@ -1083,6 +1266,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn read_compressed_float_truncated_buffer() {
+        // The 2 bit frames only fit 8 frames in the 16 bit buffer.
+        let data = hex!(
+            04000000 20000200 24000000 05000000 // header
+            00000000 00004040 02000000 00000000 // compression
+            00000000                            // default value
+            e403                                // compressed values
+        );
+        let flags = TrackFlags {
+            track_type: TrackTypeV2::Float,
+            compression_type: CompressionType::Compressed,
+        };
+
+        let result = read_track_values(&data, flags, 9);
+        assert!(matches!(
+            result,
+            Err(Error::CompressedFrameOutOfRange {
+                frame: 8,
+                bits_per_entry: 2,
+                buffer_bits: 16
+            })
+        ));
+
+        let (values, _, error) = read_track_values_partial(&data, flags, 9).unwrap();
+        assert_eq!(
+            TrackValues::Float(vec![0.0, 1.0, 2.0, 3.0, 3.0, 0.0, 0.0, 0.0]),
+            values
+        );
+        assert!(matches!(
+            error,
+            Some(Error::CompressedFrameOutOfRange { frame: 8, .. })
+        ));
+    }
+
     #[test]
     fn write_compressed_floats_multiple_frame() {
         // Test that the min/max and bit counts are used properly
@@ -1108,7 +1326,7 @@ mod tests {
 
         assert_eq!(
             values,
-            read_compressed(&mut Cursor::new(writer.get_ref()), 2).unwrap()
+            read_compressed::<_, f32>(&mut Cursor::new(writer.get_ref()), 2).unwrap()
         );
     }
 
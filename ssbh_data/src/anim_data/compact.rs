@@ -0,0 +1,305 @@
+//! A compact serde representation for [AnimData] that stores each frame as an array of numbers.
+//!
+//! Transforms are stored as `[sx, sy, sz, rx, ry, rz, rw, tx, ty, tz]`,
+//! UV transforms as `[scale_u, scale_v, rotation, translate_u, translate_v]`,
+//! and vectors as `[x, y, z, w]`.
+//! This greatly reduces the size of the output for long animations.
+//! Formatters that write arrays of numbers on a single line will also have one frame per line.
+//!
+//! Deserializing [AnimData] accepts both the compact and default representation,
+//! so no special handling is needed when reading.
+/*!
+```rust
+use ssbh_data::anim_data::{compact::CompactAnimData, AnimData};
+
+let anim = AnimData {
+    major_version: 2,
+    minor_version: 0,
+    final_frame_index: 0.0,
+    groups: Vec::new(),
+};
+
+# fn to_json<T: serde::Serialize>(_: &T) {}
+to_json(&CompactAnimData::from(&anim));
+```
+ */
+use serde::{Deserialize, Serialize, Serializer};
+use ssbh_lib::Vector4;
+
+use super::{
+    AnimData, GroupData, GroupType, NodeData, RawTrackValues, TrackData, TrackValues,
+    TransformFlags, UvTransform,
+};
+use crate::anim_data::Transform;
+use ssbh_lib::Vector3;
+
+/// Serializes the borrowed [AnimData] using the compact representation.
+#[derive(Debug, Serialize)]
+pub struct CompactAnimData<'a> {
+    major_version: u16,
+    minor_version: u16,
+    final_frame_index: f32,
+    groups: Vec<CompactGroupData<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CompactGroupData<'a> {
+    group_type: GroupType,
+    nodes: Vec<CompactNodeData<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CompactNodeData<'a> {
+    name: &'a str,
+    tracks: Vec<CompactTrackData<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CompactTrackData<'a> {
+    name: &'a str,
+    compensate_scale: bool,
+    transform_flags: TransformFlags,
+    #[serde(serialize_with = "serialize_compact_values")]
+    values: &'a TrackValues,
+}
+
+impl<'a> From<&'a AnimData> for CompactAnimData<'a> {
+    fn from(data: &'a AnimData) -> Self {
+        Self {
+            major_version: data.major_version,
+            minor_version: data.minor_version,
+            final_frame_index: data.final_frame_index,
+            groups: data.groups.iter().map(compact_group).collect(),
+        }
+    }
+}
+
+fn compact_group(group: &GroupData) -> CompactGroupData<'_> {
+    CompactGroupData {
+        group_type: group.group_type,
+        nodes: group.nodes.iter().map(compact_node).collect(),
+    }
+}
+
+fn compact_node(node: &NodeData) -> CompactNodeData<'_> {
+    CompactNodeData {
+        name: &node.name,
+        tracks: node.tracks.iter().map(compact_track).collect(),
+    }
+}
+
+fn compact_track(track: &TrackData) -> CompactTrackData<'_> {
+    CompactTrackData {
+        name: &track.name,
+        compensate_scale: track.compensate_scale,
+        transform_flags: track.transform_flags,
+        values: &track.values,
+    }
+}
+
+fn serialize_compact_values<S: Serializer>(
+    values: &TrackValues,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    // Use the same variant names as the derived representation.
+    match values {
+        TrackValues::Transform(v) => serializer.serialize_newtype_variant(
+            "TrackValues",
+            0,
+            "Transform",
+            &v.iter().map(transform_to_array).collect::<Vec<_>>(),
+        ),
+        TrackValues::UvTransform(v) => serializer.serialize_newtype_variant(
+            "TrackValues",
+            1,
+            "UvTransform",
+            &v.iter().map(uv_transform_to_array).collect::<Vec<_>>(),
+        ),
+        TrackValues::Float(v) => serializer.serialize_newtype_variant("TrackValues", 2, "Float", v),
+        TrackValues::PatternIndex(v) => {
+            serializer.serialize_newtype_variant("TrackValues", 3, "PatternIndex", v)
+        }
+        TrackValues::Boolean(v) => {
+            serializer.serialize_newtype_variant("TrackValues", 4, "Boolean", v)
+        }
+        TrackValues::Vector4(v) => serializer.serialize_newtype_variant(
+            "TrackValues",
+            5,
+            "Vector4",
+            &v.iter().map(|v| v.to_array()).collect::<Vec<_>>(),
+        ),
+        TrackValues::Raw(v) => serializer.serialize_newtype_variant("TrackValues", 6, "Raw", v),
+    }
+}
+
+fn transform_to_array(t: &Transform) -> [f32; 10] {
+    [
+        t.scale.x,
+        t.scale.y,
+        t.scale.z,
+        t.rotation.x,
+        t.rotation.y,
+        t.rotation.z,
+        t.rotation.w,
+        t.translation.x,
+        t.translation.y,
+        t.translation.z,
+    ]
+}
+
+fn uv_transform_to_array(t: &UvTransform) -> [f32; 5] {
+    [
+        t.scale_u,
+        t.scale_v,
+        t.rotation,
+        t.translate_u,
+        t.translate_v,
+    ]
+}
+
+/// Deserialize either representation for [TrackValues].
+#[derive(Deserialize)]
+pub(crate) enum TrackValuesRepr {
+    Transform(Vec<TransformRepr>),
+    UvTransform(Vec<UvTransformRepr>),
+    Float(Vec<f32>),
+    PatternIndex(Vec<u32>),
+    Boolean(Vec<bool>),
+    Vector4(Vec<Vector4Repr>),
+    Raw(RawTrackValues),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum TransformRepr {
+    Compact([f32; 10]),
+    Full(Transform),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum UvTransformRepr {
+    Compact([f32; 5]),
+    Full(UvTransform),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Vector4Repr {
+    Compact([f32; 4]),
+    Full(Vector4),
+}
+
+impl From<TransformRepr> for Transform {
+    fn from(t: TransformRepr) -> Self {
+        match t {
+            TransformRepr::Compact([sx, sy, sz, rx, ry, rz, rw, tx, ty, tz]) => Transform {
+                scale: Vector3::new(sx, sy, sz),
+                rotation: Vector4::new(rx, ry, rz, rw),
+                translation: Vector3::new(tx, ty, tz),
+            },
+            TransformRepr::Full(t) => t,
+        }
+    }
+}
+
+impl From<UvTransformRepr> for UvTransform {
+    fn from(t: UvTransformRepr) -> Self {
+        match t {
+            UvTransformRepr::Compact([scale_u, scale_v, rotation, translate_u, translate_v]) => {
+                UvTransform {
+                    scale_u,
+                    scale_v,
+                    rotation,
+                    translate_u,
+                    translate_v,
+                }
+            }
+            UvTransformRepr::Full(t) => t,
+        }
+    }
+}
+
+impl From<Vector4Repr> for Vector4 {
+    fn from(v: Vector4Repr) -> Self {
+        match v {
+            Vector4Repr::Compact([x, y, z, w]) => Vector4::new(x, y, z, w),
+            Vector4Repr::Full(v) => v,
+        }
+    }
+}
+
+impl From<TrackValuesRepr> for TrackValues {
+    fn from(v: TrackValuesRepr) -> Self {
+        fn convert<A, B: From<A>>(values: Vec<A>) -> Vec<B> {
+            values.into_iter().map(Into::into).collect()
+        }
+
+        match v {
+            TrackValuesRepr::Transform(v) => TrackValues::Transform(convert(v)),
+            TrackValuesRepr::UvTransform(v) => TrackValues::UvTransform(convert(v)),
+            TrackValuesRepr::Float(v) => TrackValues::Float(v),
+            TrackValuesRepr::PatternIndex(v) => TrackValues::PatternIndex(v),
+            TrackValuesRepr::Boolean(v) => TrackValues::Boolean(v),
+            TrackValuesRepr::Vector4(v) => TrackValues::Vector4(convert(v)),
+            TrackValuesRepr::Raw(v) => TrackValues::Raw(v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anim(values: TrackValues) -> AnimData {
+        crate::test_fixtures::transform_track_anim_data("A", 0.0, vec![values])
+    }
+
+    #[test]
+    fn compact_transform_round_trip() {
+        let data = anim(TrackValues::Transform(vec![Transform {
+            scale: Vector3::new(1.0, 2.0, 3.0),
+            rotation: Vector4::new(4.0, 5.0, 6.0, 7.0),
+            translation: Vector3::new(8.0, 9.0, 10.0),
+        }]));
+
+        let json = serde_json::to_value(CompactAnimData::from(&data)).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "Transform": [[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]]
+            }),
+            json["groups"][0]["nodes"][0]["tracks"][0]["values"]
+        );
+
+        assert_eq!(data, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn compact_track_values_round_trip() {
+        for values in [
+            TrackValues::UvTransform(vec![UvTransform {
+                scale_u: 1.0,
+                scale_v: 2.0,
+                rotation: 3.0,
+                translate_u: 4.0,
+                translate_v: 5.0,
+            }]),
+            TrackValues::Vector4(vec![Vector4::new(1.0, 2.0, 3.0, 4.0)]),
+            TrackValues::Boolean(vec![true, false]),
+            TrackValues::PatternIndex(vec![0, 1, u32::MAX]),
+            TrackValues::Float(vec![0.5, -1.0]),
+        ] {
+            let data = anim(values);
+            let json = serde_json::to_string(&CompactAnimData::from(&data)).unwrap();
+            assert_eq!(data, serde_json::from_str(&json).unwrap());
+        }
+    }
+
+    #[test]
+    fn deserialize_default_representation() {
+        let data = anim(TrackValues::Vector4(vec![Vector4::new(1.0, 2.0, 3.0, 4.0)]));
+        let json = serde_json::to_string(&data).unwrap();
+        assert!(json.contains(r#""x":1.0"#));
+        assert_eq!(data, serde_json::from_str(&json).unwrap());
+    }
+}
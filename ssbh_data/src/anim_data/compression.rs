@@ -20,11 +20,14 @@ use super::bitutils::*;
 // The current uncompressed track value types are all 32 bits or smaller.
 // Smash Ultimate never uses bit counts above 24, so represent compressed bits with u32.
 // TODO: Can we handle arbitrary bit lengths with acceptable performance?
+/// The bits for a single compressed value.
 pub type CompressedBits = u32;
 
 // Use the highest bit count used for Smash Ultimate to reduce quality loss.
+/// The bit count used for compressed floats when saving.
 pub const DEFAULT_F32_BIT_COUNT: u64 = 24;
 
+/// The header and compression parameters at the start of a compressed track buffer.
 #[derive(Debug, BinRead, SsbhWrite)]
 pub struct CompressedTrackData<T: CompressedData> {
     pub header: CompressedHeader<T>,
@@ -32,6 +35,7 @@ pub struct CompressedTrackData<T: CompressedData> {
 }
 
 // TODO: These should be non nullable pointers?
+/// The frame count, bit count, default value, and compressed bits for a compressed track.
 #[derive(Debug, BinRead, SsbhWrite)]
 pub struct CompressedHeader<T: CompressedData> {
     pub unk_4: u16,              // TODO: Always 4?
@@ -49,6 +53,7 @@ fn read_to_end<R: Read + Seek>(reader: &mut R, _: binrw::Endian, _: ()) -> BinRe
     Ok(buf)
 }
 
+/// The packed bits for all compressed frames.
 #[derive(Debug, BinRead, SsbhWrite)]
 #[ssbhwrite(alignment = 1)] // TODO: Is 1 byte alignment correct?
 pub struct CompressedBuffer(#[br(parse_with = read_to_end)] pub Vec<u8>);
@@ -59,6 +64,7 @@ pub struct CompressedBuffer(#[br(parse_with = read_to_end)] pub Vec<u8>);
 // Determines what values are stored in the compressed bit buffer.
 // Missing values are determined based on the compression's default values.
 // TODO: Why is this needed if compression can already set these to defaults?
+/// Flags for which transform components are stored in the compressed bits.
 #[bitfield(bits = 16)]
 #[derive(Debug, BinRead, Clone, Copy, PartialEq, Eq)]
 #[br(map = Self::from_bytes)]
@@ -74,6 +80,7 @@ pub struct CompressionFlags {
 ssbh_write::ssbh_write_modular_bitfield_impl!(CompressionFlags, 2);
 
 impl CompressionFlags {
+    /// The flags used when compressing `values` on export.
     pub fn from_track(values: &TrackValues) -> CompressionFlags {
         match values {
             TrackValues::Transform(values) => {
@@ -100,12 +107,13 @@ impl CompressionFlags {
     }
 }
 
-// Shared logic for compressing track data to and from bits.
+/// Shared logic for compressing track data to and from bits.
 pub trait CompressedData: for<'a> BinRead<Args<'a> = ()> + SsbhWrite + Default {
     type Compression: Compression + std::fmt::Debug;
     type BitStore: BitStore;
     type CompressionArgs;
 
+    /// Writes the compressed bits for `self` to `writer`.
     fn compress(
         &self,
         writer: &mut BitWriter,
@@ -113,6 +121,7 @@ pub trait CompressedData: for<'a> BinRead<Args<'a> = ()> + SsbhWrite + Default {
         flags: CompressionFlags,
     );
 
+    /// Reads a single value from `reader` using `default` for any components without compressed bits.
     fn decompress(
         reader: &mut BitReader,
         compression: &Self::Compression,
@@ -120,7 +129,7 @@ pub trait CompressedData: for<'a> BinRead<Args<'a> = ()> + SsbhWrite + Default {
         args: Self::CompressionArgs,
     ) -> Result<Self, BitReadError>;
 
-    // The size in bytes for the compressed header, default, and a single frame value.
+    /// The size in bytes for the compressed header, default, and a single frame value.
     fn compressed_overhead_in_bytes() -> u64 {
         let header_size = 16;
 
@@ -129,15 +138,19 @@ pub trait CompressedData: for<'a> BinRead<Args<'a> = ()> + SsbhWrite + Default {
         header_size + Self::default().size_in_bytes() + Self::Compression::default().size_in_bytes()
     }
 
+    /// Additional data from the header needed for decompression.
     fn get_args(header: &CompressedHeader<Self>) -> Self::CompressionArgs;
 
+    /// Calculates the default value and compression ranges used when compressing `values`.
     fn get_default_and_compression(
         values: &[Self],
         compensate_scale: bool,
     ) -> (Self, Self::Compression);
 }
 
+/// The value ranges and bit counts for compressing a [CompressedData] type.
 pub trait Compression: for<'a> BinRead<Args<'a> = ()> + SsbhWrite + Default {
+    /// The number of bits for each compressed frame.
     fn bit_count(&self, flags: CompressionFlags) -> u64;
 }
 
@@ -161,6 +174,7 @@ impl BitReaderExt for BitReader {
     }
 }
 
+/// Compression for pattern index values.
 #[derive(Debug, BinRead, Clone, SsbhWrite, Default)]
 pub struct U32Compression {
     pub min: u32,
@@ -184,6 +198,7 @@ impl Compression for u128 {
     }
 }
 
+/// Compression for a float in the range `min` to `max` using `bit_count` many bits.
 #[derive(Debug, BinRead, SsbhWrite, Default, Clone, Copy)]
 pub struct F32Compression {
     pub min: f32,
@@ -195,6 +210,7 @@ pub struct F32Compression {
 }
 
 impl F32Compression {
+    /// Compression using [DEFAULT_F32_BIT_COUNT] or 0 bits if `min` and `max` are equal.
     pub fn from_range(min: f32, max: f32) -> Self {
         let bit_count = if min == max { 0 } else { DEFAULT_F32_BIT_COUNT };
 
@@ -217,6 +233,7 @@ impl Compression for F32Compression {
     }
 }
 
+/// Compression for each component of a [Vector3].
 #[derive(Debug, BinRead, SsbhWrite, Default)]
 pub struct Vector3Compression {
     pub x: F32Compression,
@@ -225,6 +242,7 @@ pub struct Vector3Compression {
 }
 
 impl Vector3Compression {
+    /// Component wise [F32Compression::from_range].
     pub fn from_range(min: Vector3, max: Vector3) -> Self {
         Self {
             x: F32Compression::from_range(min.x, max.x),
@@ -240,6 +258,7 @@ impl Compression for Vector3Compression {
     }
 }
 
+/// Compression for each component of a [Vector4].
 #[derive(Debug, BinRead, SsbhWrite, Default)]
 pub struct Vector4Compression {
     pub x: F32Compression,
@@ -249,6 +268,7 @@ pub struct Vector4Compression {
 }
 
 impl Vector4Compression {
+    /// Component wise [F32Compression::from_range].
     pub fn from_range(min: Vector4, max: Vector4) -> Self {
         Self {
             x: F32Compression::from_range(min.x, max.x),
@@ -268,6 +288,7 @@ impl Compression for Vector4Compression {
     }
 }
 
+/// Compression for transform tracks.
 #[derive(Debug, BinRead, SsbhWrite, Default)]
 pub struct TransformCompression {
     // The x component is used for uniform scale.
@@ -279,6 +300,7 @@ pub struct TransformCompression {
 
 // This is also used for compressed transforms but compensate_scale is omitted.
 // Compressed transforms set compensate_scale using the header's default value.
+/// The binary representation of a [Transform] including the compensate scale flag.
 #[derive(Debug, BinRead, PartialEq, SsbhWrite, Clone, Copy, Default)]
 pub struct UncompressedTransform {
     pub scale: Vector3,
@@ -299,6 +321,7 @@ impl From<&UncompressedTransform> for Transform {
 }
 
 impl UncompressedTransform {
+    /// Converts `t` and sets the compensate scale flag.
     pub fn from_transform(t: &Transform, compensate_scale: bool) -> Self {
         Self {
             scale: t.scale,
@@ -334,6 +357,7 @@ impl Compression for TransformCompression {
     }
 }
 
+/// Compression for UV transform tracks.
 #[derive(Debug, BinRead, SsbhWrite, Default)]
 pub struct UvTransformCompression {
     pub scale_u: F32Compression,
@@ -363,14 +387,13 @@ impl Compression for UvTransformCompression {
     }
 }
 
-fn calculate_rotation_w(reader: &mut BitReader, rotation: Vector3) -> f32 {
+fn calculate_rotation_w(reader: &mut BitReader, rotation: Vector3) -> Result<f32, BitReadError> {
     // Rotations are encoded as xyzw unit quaternions.
     // For a unit quaternion, x^2 + y^2 + z^2 + w^2 = 1.
     // Solving for the missing w gives two expressions:
     // w = sqrt(1 - x^2 + y^2 + z^2), w = -sqrt(1 - x^2 + y^2 + z^2).
     // Thus, we need only need to store the sign bit to uniquely determine w.
-    // TODO: Possible to read past end of stream?
-    let flip_w = reader.read_bit().unwrap();
+    let flip_w = reader.read_bit()?;
 
     let w2 = 1.0 - (rotation.x * rotation.x + rotation.y * rotation.y + rotation.z * rotation.z);
     let w = if w2.is_sign_negative() {
@@ -381,12 +404,13 @@ fn calculate_rotation_w(reader: &mut BitReader, rotation: Vector3) -> f32 {
     };
 
     if flip_w {
-        -w
+        Ok(-w)
     } else {
-        w
+        Ok(w)
     }
 }
 
+/// A mask with the lowest `bit_count` many bits set.
 fn bit_mask(bit_count: NonZeroU64) -> u64 {
     // Get a mask of bit_count many bits set to 1.
     // Don't allow zero to avoid overflow.
@@ -394,6 +418,8 @@ fn bit_mask(bit_count: NonZeroU64) -> u64 {
     (1u64 << bit_count.get()) - 1u64
 }
 
+/// Maps `value` to one of `2^bit_count` evenly spaced values from `min` to `max` by truncating.
+/// Out of range values saturate, and NaN maps to 0.
 fn compress_f32(value: f32, min: f32, max: f32, bit_count: NonZeroU64) -> CompressedBits {
     // The inverse operation of decompression.
     // We don't allow bit_count to be zero.
@@ -409,6 +435,7 @@ fn compress_f32(value: f32, min: f32, max: f32, bit_count: NonZeroU64) -> Compre
     compressed as CompressedBits
 }
 
+/// The inverse of [compress_f32] that linearly interpolates from `min` to `max`.
 fn decompress_f32(value: CompressedBits, min: f32, max: f32, bit_count: NonZeroU64) -> Option<f32> {
     // Anim supports custom ranges and non standard bit counts for fine tuning compression.
     // Unsigned normalized u8 would use min: 0.0, max: 1.0, and bit_count: 8.
@@ -461,7 +488,7 @@ impl CompressedData for UncompressedTransform {
         };
 
         let rotation_w = if args.has_rotation() {
-            calculate_rotation_w(reader, rotation_xyz)
+            calculate_rotation_w(reader, rotation_xyz)?
         } else {
             default.rotation.w
         };
@@ -777,20 +804,25 @@ impl CompressedData for u32 {
         _flags: CompressionFlags,
     ) {
         // TODO: This is just a guess.
-        // TODO: Add a test case?
-        let compressed_value = self - compression.min;
-        writer.write(compressed_value, compression.bit_count as usize);
+        // Values are stored relative to the min, so a bit count of 0 stores nothing.
+        if compression.bit_count > 0 {
+            let compressed_value = self - compression.min;
+            writer.write(compressed_value, compression.bit_count as usize);
+        }
     }
 
     fn get_args(_: &CompressedHeader<Self>) -> Self::CompressionArgs {}
 
     fn get_default_and_compression(values: &[Self], _: bool) -> (Self, Self::Compression) {
+        let min = values.iter().copied().min().unwrap_or(0);
+        let max = values.iter().copied().max().unwrap_or(0);
         (
             0, // TODO: Better default?
             U32Compression {
-                min: values.iter().copied().min().unwrap_or(0),
-                max: values.iter().copied().max().unwrap_or(0),
-                bit_count: super::compression::DEFAULT_F32_BIT_COUNT, // TODO: How should this work for u32?
+                min,
+                max,
+                // Use enough bits to store any value in the range exactly.
+                bit_count: (u32::BITS - (max - min).leading_zeros()) as u64,
             },
         )
     }
@@ -846,6 +878,7 @@ impl CompressedData for f32 {
     }
 }
 
+/// The binary representation of a boolean track value.
 #[derive(Debug, BinRead, SsbhWrite, Default, PartialEq, Eq, Clone, Copy)]
 pub struct Boolean(pub u8);
 
@@ -970,6 +1003,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compress_float_24bit_reference() {
+        // Compressing the in game values from decompress_float_24bit_reference should produce the original bits.
+        // The range -0.75 to -0.5 is skipped since f32 can't represent every 24 bit step in that range.
+        let bits = NonZeroU64::new(24).unwrap();
+        let f32_be = |u: u32| f32::from_be_bytes(u.to_be_bytes());
+        assert_eq!(0x808080, compress_f32(f32_be(0x3F008081), 0.0, 1.0, bits));
+        assert_eq!(0x808080, compress_f32(f32_be(0x3B808081), -1.0, 1.0, bits));
+        assert_eq!(0x808080, compress_f32(f32_be(0x3E028283), -0.5, 0.75, bits));
+        assert_eq!(0xFFFFFF, compress_f32(f32_be(0x3F800000), 0.0, 1.0, bits));
+        assert_eq!(0xFFFFFF, compress_f32(f32_be(0xBF800000), -2.0, -1.0, bits));
+    }
+
     #[test]
     fn decompress_float_14bit() {
         // stage/poke_unova/battle/motion/s13_a, D_lightning_B, CustomVector3
@@ -1036,7 +1082,7 @@ mod tests {
         let mut reader = BitReader::from_slice(&[1u8]);
         assert_eq!(
             0.0,
-            calculate_rotation_w(&mut reader, Vector3::new(1.0, 0.0, 0.0))
+            calculate_rotation_w(&mut reader, Vector3::new(1.0, 0.0, 0.0)).unwrap()
         );
     }
 
@@ -1049,7 +1095,7 @@ mod tests {
         // TODO: There may be a better approach with better animation quality.
         assert_eq!(
             0.0,
-            calculate_rotation_w(&mut reader, Vector3::new(1.0, 1.0, 1.0))
+            calculate_rotation_w(&mut reader, Vector3::new(1.0, 1.0, 1.0)).unwrap()
         );
     }
 
@@ -1059,7 +1105,7 @@ mod tests {
 
         assert_eq!(
             0.0,
-            calculate_rotation_w(&mut reader, Vector3::new(1.0, 0.0, 0.0))
+            calculate_rotation_w(&mut reader, Vector3::new(1.0, 0.0, 0.0)).unwrap()
         );
     }
 
@@ -1072,10 +1118,19 @@ mod tests {
         // TODO: There may be a better approach with better animation quality.
         assert_eq!(
             0.0,
-            calculate_rotation_w(&mut reader, Vector3::new(1.0, 1.0, 1.0))
+            calculate_rotation_w(&mut reader, Vector3::new(1.0, 1.0, 1.0)).unwrap()
         );
     }
 
+    #[test]
+    fn calculate_rotation_w_past_end() {
+        let mut reader = BitReader::from_slice(&[]);
+        assert!(matches!(
+            calculate_rotation_w(&mut reader, Vector3::new(1.0, 0.0, 0.0)),
+            Err(BitReadError::NotEnoughBits)
+        ));
+    }
+
     #[test]
     fn compression_flags_const_scale() {
         assert_eq!(
@@ -0,0 +1,395 @@
+use ssbh_lib::{Vector3, Vector4};
+
+use super::{AnimData, GroupData, TrackValues, Transform, TransformFlags, UvTransform};
+
+impl AnimData {
+    /// Converts tracks where every frame is within `tolerance` of the first frame to constant tracks with a single frame.
+    /// Returns the number of converted tracks.
+    ///
+    /// Floating point components are compared using the absolute difference.
+    /// Rotations that differ only in sign represent the same orientation and are considered equal.
+    /// A `tolerance` of `0.0` only collapses frames that are equal or differ only in the sign of the rotation.
+    /// [AnimData::optimize] only collapses exactly equal frames and keeps tracks with negated rotations.
+    ///
+    /// Exporters often bake a value for every frame even if the value never changes.
+    /// Constant tracks take up much less space in the file and are faster to decompress.
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags};
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 2.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Material,
+            nodes: vec![NodeData {
+                name: "EyeL".to_string(),
+                tracks: vec![TrackData {
+                    name: "CustomFloat8".to_string(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Float(vec![0.5, 0.50001, 0.49999]),
+                }],
+            }],
+        }],
+    };
+
+    assert_eq!(1, anim.collapse_constant_tracks(0.001));
+    assert_eq!(
+        TrackValues::Float(vec![0.5]),
+        anim.groups[0].nodes[0].tracks[0].values
+    );
+    ```
+     */
+    pub fn collapse_constant_tracks(&mut self, tolerance: f32) -> usize {
+        let mut count = 0;
+        for track in self
+            .groups
+            .iter_mut()
+            .flat_map(|g| g.nodes.iter_mut())
+            .flat_map(|n| n.tracks.iter_mut())
+        {
+            if track.values.len() > 1 && track.values.is_constant(tolerance) {
+                track.values.resize(1);
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl AnimData {
+    /// Removes tracks where every frame is within `tolerance` of the default value
+    /// and returns the number of removed tracks.
+    ///
+    /// The default value is the first frame of the track in `defaults`
+    /// with the same group type, node name, and track name.
+    /// Tracks without a default or with non default [TransformFlags] or scale compensation are never removed.
+    ///
+    /// Naive exporters often include a track for every bone even if the bone stays in its rest pose.
+    /// These tracks have no effect when the game uses the same defaults for missing tracks,
+    /// like the rest pose from [AnimData::create_transform_group_from_skel].
+    /// See [AnimData::retain_tracks] for the behavior of `remove_empty_groups`.
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, Transform};
+
+    let group = |values| GroupData {
+        group_type: GroupType::Transform,
+        nodes: vec![NodeData {
+            name: "Hip".to_string(),
+            tracks: vec![TrackData::new("Transform", TrackValues::Transform(values))],
+        }],
+    };
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 1.0,
+        groups: vec![group(vec![Transform::IDENTITY, Transform::IDENTITY])],
+    };
+
+    let defaults = vec![group(vec![Transform::IDENTITY])];
+    assert_eq!(1, anim.remove_default_tracks(&defaults, 0.0, true));
+    assert!(anim.groups.is_empty());
+    ```
+     */
+    pub fn remove_default_tracks(
+        &mut self,
+        defaults: &[GroupData],
+        tolerance: f32,
+        remove_empty_groups: bool,
+    ) -> usize {
+        self.retain_tracks(
+            |group_type, node_name, track| {
+                if track.compensate_scale || track.transform_flags != TransformFlags::default() {
+                    return true;
+                }
+
+                !defaults
+                    .iter()
+                    .filter(|g| g.group_type == group_type)
+                    .flat_map(|g| g.nodes.iter().filter(|n| n.name == node_name))
+                    .flat_map(|n| n.tracks.iter().filter(|t| t.name == track.name))
+                    .any(|t| is_default(&track.values, &t.values, tolerance))
+            },
+            remove_empty_groups,
+        )
+    }
+}
+
+impl TrackValues {
+    /// Returns `true` if every frame is within `tolerance` of the first frame.
+    /// [TrackValues::Raw] is never considered constant since the values aren't decoded.
+    /// See [AnimData::collapse_constant_tracks].
+    pub fn is_constant(&self, tolerance: f32) -> bool {
+        match self {
+            TrackValues::Transform(v) => is_constant(v, tolerance),
+            TrackValues::UvTransform(v) => is_constant(v, tolerance),
+            TrackValues::Float(v) => is_constant(v, tolerance),
+            TrackValues::PatternIndex(v) => is_constant(v, tolerance),
+            TrackValues::Boolean(v) => is_constant(v, tolerance),
+            TrackValues::Vector4(v) => is_constant(v, tolerance),
+            TrackValues::Raw(_) => false,
+        }
+    }
+}
+
+fn is_constant<T: Difference>(values: &[T], tolerance: f32) -> bool {
+    match values.split_first() {
+        Some((first, rest)) => rest.iter().all(|v| v.difference(first) <= tolerance),
+        None => true,
+    }
+}
+
+fn is_default(values: &TrackValues, default: &TrackValues, tolerance: f32) -> bool {
+    match (values, default) {
+        (TrackValues::Transform(v), TrackValues::Transform(d)) => all_within(v, d, tolerance),
+        (TrackValues::UvTransform(v), TrackValues::UvTransform(d)) => all_within(v, d, tolerance),
+        (TrackValues::Float(v), TrackValues::Float(d)) => all_within(v, d, tolerance),
+        (TrackValues::PatternIndex(v), TrackValues::PatternIndex(d)) => all_within(v, d, tolerance),
+        (TrackValues::Boolean(v), TrackValues::Boolean(d)) => all_within(v, d, tolerance),
+        (TrackValues::Vector4(v), TrackValues::Vector4(d)) => all_within(v, d, tolerance),
+        _ => false,
+    }
+}
+
+fn all_within<T: Difference>(values: &[T], default: &[T], tolerance: f32) -> bool {
+    match default.first() {
+        Some(default) => values.iter().all(|v| v.difference(default) <= tolerance),
+        None => false,
+    }
+}
+
+// The largest absolute difference between components.
+// Non floating point values are either equal or infinitely different.
+trait Difference {
+    fn difference(&self, other: &Self) -> f32;
+}
+
+impl Difference for f32 {
+    fn difference(&self, other: &Self) -> f32 {
+        if self == other {
+            // Handle infinity.
+            0.0
+        } else {
+            // NaN is never within tolerance.
+            let difference = (self - other).abs();
+            if difference.is_nan() {
+                f32::INFINITY
+            } else {
+                difference
+            }
+        }
+    }
+}
+
+impl Difference for bool {
+    fn difference(&self, other: &Self) -> f32 {
+        if self == other {
+            0.0
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+impl Difference for u32 {
+    fn difference(&self, other: &Self) -> f32 {
+        if self == other {
+            0.0
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+impl Difference for Vector3 {
+    fn difference(&self, other: &Self) -> f32 {
+        max_difference(&self.to_array(), &other.to_array())
+    }
+}
+
+impl Difference for Vector4 {
+    fn difference(&self, other: &Self) -> f32 {
+        max_difference(&self.to_array(), &other.to_array())
+    }
+}
+
+impl Difference for UvTransform {
+    fn difference(&self, other: &Self) -> f32 {
+        max_difference(
+            &[
+                self.scale_u,
+                self.scale_v,
+                self.rotation,
+                self.translate_u,
+                self.translate_v,
+            ],
+            &[
+                other.scale_u,
+                other.scale_v,
+                other.rotation,
+                other.translate_u,
+                other.translate_v,
+            ],
+        )
+    }
+}
+
+impl Difference for Transform {
+    fn difference(&self, other: &Self) -> f32 {
+        // The quaternions q and -q represent the same rotation.
+        let negated = Vector4::new(
+            -other.rotation.x,
+            -other.rotation.y,
+            -other.rotation.z,
+            -other.rotation.w,
+        );
+        let rotation = self
+            .rotation
+            .difference(&other.rotation)
+            .min(self.rotation.difference(&negated));
+
+        self.scale
+            .difference(&other.scale)
+            .max(rotation)
+            .max(self.translation.difference(&other.translation))
+    }
+}
+
+fn max_difference<const N: usize>(a: &[f32; N], b: &[f32; N]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.difference(b))
+        .fold(0.0, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anim(values: Vec<TrackValues>) -> AnimData {
+        crate::test_fixtures::transform_track_anim_data("A", 2.0, values)
+    }
+
+    #[test]
+    fn collapse_constant_tracks_exact() {
+        let mut data = anim(vec![
+            TrackValues::Boolean(vec![true, true, true]),
+            TrackValues::PatternIndex(vec![1, 2, 1]),
+            TrackValues::Float(vec![1.0]),
+        ]);
+        assert_eq!(1, data.collapse_constant_tracks(0.0));
+
+        let tracks = &data.groups[0].nodes[0].tracks;
+        assert_eq!(TrackValues::Boolean(vec![true]), tracks[0].values);
+        assert_eq!(TrackValues::PatternIndex(vec![1, 2, 1]), tracks[1].values);
+        assert_eq!(TrackValues::Float(vec![1.0]), tracks[2].values);
+    }
+
+    #[test]
+    fn collapse_constant_tracks_tolerance() {
+        let mut data = anim(vec![TrackValues::Transform(vec![
+            Transform::IDENTITY,
+            Transform {
+                translation: Vector3::new(0.0, 0.001, 0.0),
+                ..Transform::IDENTITY
+            },
+        ])]);
+        assert_eq!(0, data.collapse_constant_tracks(0.0001));
+        assert_eq!(1, data.collapse_constant_tracks(0.01));
+        assert_eq!(
+            TrackValues::Transform(vec![Transform::IDENTITY]),
+            data.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    #[test]
+    fn collapse_constant_tracks_negated_rotation() {
+        let negated = Transform {
+            rotation: Vector4::new(0.0, 0.0, 0.0, -1.0),
+            ..Transform::IDENTITY
+        };
+        let mut data = anim(vec![TrackValues::Transform(vec![
+            Transform::IDENTITY,
+            negated,
+        ])]);
+
+        // The exact comparison used for optimizing doesn't consider q and -q equal.
+        let mut optimized = data.clone();
+        optimized.optimize();
+        assert_eq!(data, optimized);
+
+        assert_eq!(1, data.collapse_constant_tracks(0.0));
+        assert_eq!(
+            TrackValues::Transform(vec![Transform::IDENTITY]),
+            data.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    #[test]
+    fn remove_default_tracks_tolerance() {
+        let mut data = anim(vec![
+            TrackValues::Transform(vec![
+                Transform::IDENTITY,
+                Transform {
+                    translation: Vector3::new(0.0, 0.001, 0.0),
+                    ..Transform::IDENTITY
+                },
+            ]),
+            TrackValues::Transform(vec![Transform {
+                translation: Vector3::new(0.0, 1.0, 0.0),
+                ..Transform::IDENTITY
+            }]),
+        ]);
+        let defaults = anim(vec![TrackValues::Transform(vec![Transform::IDENTITY])]).groups;
+
+        assert_eq!(0, data.remove_default_tracks(&defaults, 0.0001, false));
+        assert_eq!(1, data.remove_default_tracks(&defaults, 0.01, false));
+        assert_eq!(1, data.groups[0].nodes[0].tracks.len());
+    }
+
+    #[test]
+    fn remove_default_tracks_flags() {
+        let mut data = anim(vec![TrackValues::Transform(vec![Transform::IDENTITY])]);
+        data.groups[0].nodes[0].tracks[0].compensate_scale = true;
+        let defaults = anim(vec![TrackValues::Transform(vec![Transform::IDENTITY])]).groups;
+
+        // Removing the track would change the scale inheritance.
+        assert_eq!(0, data.remove_default_tracks(&defaults, 0.0, true));
+    }
+
+    #[test]
+    fn remove_default_tracks_missing_default() {
+        let mut data = anim(vec![TrackValues::Float(vec![0.0, 0.0])]);
+        let defaults = anim(vec![
+            TrackValues::Transform(vec![Transform::IDENTITY]),
+            TrackValues::Float(Vec::new()),
+        ])
+        .groups;
+
+        assert_eq!(0, data.remove_default_tracks(&defaults, 0.0, true));
+        assert_eq!(0, data.remove_default_tracks(&[], 0.0, true));
+    }
+
+    #[test]
+    fn is_constant_negated_rotation() {
+        let values = TrackValues::Transform(vec![
+            Transform::IDENTITY,
+            Transform {
+                rotation: Vector4::new(0.0, 0.0, 0.0, -1.0),
+                ..Transform::IDENTITY
+            },
+        ]);
+        assert!(values.is_constant(0.0));
+    }
+
+    #[test]
+    fn is_constant_nan() {
+        assert!(!TrackValues::Float(vec![f32::NAN, f32::NAN]).is_constant(1.0));
+        assert!(TrackValues::Float(vec![f32::INFINITY, f32::INFINITY]).is_constant(0.0));
+        assert!(TrackValues::Float(Vec::new()).is_constant(0.0));
+    }
+}
@@ -0,0 +1,260 @@
+use ssbh_lib::{Vector3, Vector4};
+
+use super::{AnimData, TrackValues, Transform, UvTransform};
+
+impl Transform {
+    /// Interpolates from `self` at `t = 0.0` to `other` at `t = 1.0`.
+    ///
+    /// Scale and translation are interpolated linearly.
+    /// Rotations use spherical linear interpolation along the shortest path,
+    /// which avoids the distortion from interpolating quaternion components directly.
+    /**
+    ```rust
+    # use ssbh_data::anim_data::Transform;
+    # use ssbh_data::Vector3;
+    let a = Transform {
+        translation: Vector3::new(0.0, 0.0, 0.0),
+        ..Transform::IDENTITY
+    };
+    let b = Transform {
+        translation: Vector3::new(2.0, 0.0, 0.0),
+        ..Transform::IDENTITY
+    };
+    assert_eq!(Vector3::new(1.0, 0.0, 0.0), a.blend(&b, 0.5).translation);
+    ```
+     */
+    pub fn blend(&self, other: &Transform, t: f32) -> Transform {
+        let rotation = glam::Quat::from_array(self.rotation.to_array())
+            .normalize()
+            .slerp(
+                glam::Quat::from_array(other.rotation.to_array()).normalize(),
+                t,
+            );
+
+        Transform {
+            scale: lerp_vector3(self.scale, other.scale, t),
+            rotation: rotation.to_array().into(),
+            translation: lerp_vector3(self.translation, other.translation, t),
+        }
+    }
+}
+
+// Linear interpolation shared by looping and sampling material tracks.
+// This returns b exactly when t is 1.0.
+pub(crate) fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
+    a * (1.0 - t) + b * t
+}
+
+fn lerp_vector3(a: Vector3, b: Vector3, t: f32) -> Vector3 {
+    Vector3::new(
+        lerp_f32(a.x, b.x, t),
+        lerp_f32(a.y, b.y, t),
+        lerp_f32(a.z, b.z, t),
+    )
+}
+
+pub(crate) fn lerp_vector4(a: Vector4, b: Vector4, t: f32) -> Vector4 {
+    Vector4::new(
+        lerp_f32(a.x, b.x, t),
+        lerp_f32(a.y, b.y, t),
+        lerp_f32(a.z, b.z, t),
+        lerp_f32(a.w, b.w, t),
+    )
+}
+
+fn lerp_uv_transform(a: UvTransform, b: UvTransform, t: f32) -> UvTransform {
+    UvTransform {
+        scale_u: lerp_f32(a.scale_u, b.scale_u, t),
+        scale_v: lerp_f32(a.scale_v, b.scale_v, t),
+        rotation: lerp_f32(a.rotation, b.rotation, t),
+        translate_u: lerp_f32(a.translate_u, b.translate_u, t),
+        translate_v: lerp_f32(a.translate_v, b.translate_v, t),
+    }
+}
+
+// Crossfade the last blend_frames frames into the start and remove them from the end.
+fn crossfade<T: Copy, F: Fn(T, T, f32) -> T>(values: &mut Vec<T>, blend_frames: usize, blend: F) {
+    let frame_count = values.len() - blend_frames;
+    for i in 0..blend_frames {
+        // The first frame continues from the last remaining frame.
+        let t = i as f32 / blend_frames as f32;
+        values[i] = blend(values[frame_count + i], values[i], t);
+    }
+    values.truncate(frame_count);
+}
+
+impl AnimData {
+    /// Modifies the animation to loop seamlessly by crossfading the last `blend_frames` frames into the first frames.
+    ///
+    /// The crossfaded frames are removed from the end,
+    /// so the [final_frame_index](#structfield.final_frame_index) decreases by `blend_frames`.
+    /// Transform tracks use [Transform::blend], and float, vector, and UV transform tracks are interpolated linearly.
+    /// Boolean and pattern index tracks can't be interpolated and are only trimmed.
+    /// Constant tracks are unchanged, and other tracks are first resized to [AnimData::expected_frame_count].
+    ///
+    /// The value of `blend_frames` is clamped so that at least one frame remains.
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags};
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 4.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Material,
+            nodes: vec![NodeData {
+                name: "EyeL".to_string(),
+                tracks: vec![TrackData {
+                    name: "CustomFloat8".to_string(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Float(vec![0.0, 1.0, 2.0, 3.0, 4.0]),
+                }],
+            }],
+        }],
+    };
+
+    anim.make_loop(2);
+    assert_eq!(2.0, anim.final_frame_index);
+    assert_eq!(
+        TrackValues::Float(vec![3.0, 2.5, 2.0]),
+        anim.groups[0].nodes[0].tracks[0].values
+    );
+    ```
+     */
+    pub fn make_loop(&mut self, blend_frames: usize) {
+        let frame_count = self.expected_frame_count();
+        let blend_frames = blend_frames.min(frame_count - 1);
+        if blend_frames == 0 {
+            return;
+        }
+
+        for track in self
+            .groups
+            .iter_mut()
+            .flat_map(|g| g.nodes.iter_mut())
+            .flat_map(|n| n.tracks.iter_mut())
+        {
+            if track.frame_count() <= 1 {
+                continue;
+            }
+
+            track.values.resize(frame_count);
+            match &mut track.values {
+                TrackValues::Transform(v) => crossfade(v, blend_frames, |a, b, t| a.blend(&b, t)),
+                TrackValues::UvTransform(v) => crossfade(v, blend_frames, lerp_uv_transform),
+                TrackValues::Float(v) => crossfade(v, blend_frames, lerp_f32),
+                TrackValues::Vector4(v) => crossfade(v, blend_frames, lerp_vector4),
+                TrackValues::PatternIndex(v) => v.truncate(frame_count - blend_frames),
+                TrackValues::Boolean(v) => v.truncate(frame_count - blend_frames),
+                TrackValues::Raw(_) => (),
+            }
+        }
+
+        self.final_frame_index = (frame_count - blend_frames - 1) as f32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn anim(final_frame_index: f32, values: Vec<TrackValues>) -> AnimData {
+        crate::test_fixtures::transform_track_anim_data("A", final_frame_index, values)
+    }
+
+    fn rotation_z(angle: f32) -> Transform {
+        Transform {
+            rotation: glam::Quat::from_rotation_z(angle).to_array().into(),
+            ..Transform::IDENTITY
+        }
+    }
+
+    #[test]
+    fn blend_transform_slerp() {
+        let a = rotation_z(0.0);
+        let b = rotation_z(std::f32::consts::FRAC_PI_2);
+        let expected = rotation_z(std::f32::consts::FRAC_PI_4).rotation;
+
+        let rotation = a.blend(&b, 0.5).rotation;
+        assert_relative_eq!(expected.x, rotation.x, epsilon = 1e-6);
+        assert_relative_eq!(expected.y, rotation.y, epsilon = 1e-6);
+        assert_relative_eq!(expected.z, rotation.z, epsilon = 1e-6);
+        assert_relative_eq!(expected.w, rotation.w, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn blend_transform_shortest_path() {
+        // q and -q represent the same rotation.
+        let a = rotation_z(0.0);
+        let mut b = rotation_z(0.0);
+        b.rotation = Vector4::new(0.0, 0.0, 0.0, -1.0);
+
+        let rotation = a.blend(&b, 0.5).rotation;
+        assert_relative_eq!(1.0, rotation.w.abs(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn make_loop_transforms() {
+        let translation = |x| Transform {
+            translation: Vector3::new(x, 0.0, 0.0),
+            ..Transform::IDENTITY
+        };
+
+        let mut anim = anim(
+            4.0,
+            vec![
+                TrackValues::Transform(vec![
+                    translation(0.0),
+                    translation(1.0),
+                    translation(2.0),
+                    translation(3.0),
+                    translation(4.0),
+                ]),
+                TrackValues::Boolean(vec![true]),
+                TrackValues::Boolean(vec![true, false, true]),
+            ],
+        );
+        anim.make_loop(2);
+
+        assert_eq!(2.0, anim.final_frame_index);
+        let tracks = &anim.groups[0].nodes[0].tracks;
+        assert_eq!(
+            TrackValues::Transform(vec![translation(3.0), translation(2.5), translation(2.0)]),
+            tracks[0].values
+        );
+        assert_eq!(TrackValues::Boolean(vec![true]), tracks[1].values);
+        // Shorter tracks are padded before trimming.
+        assert_eq!(
+            TrackValues::Boolean(vec![true, false, true]),
+            tracks[2].values
+        );
+        assert!(anim.frame_count_mismatches().is_empty());
+    }
+
+    #[test]
+    fn make_loop_clamp_blend_frames() {
+        let mut anim = anim(1.0, vec![TrackValues::Float(vec![0.0, 1.0])]);
+        anim.make_loop(5);
+
+        assert_eq!(0.0, anim.final_frame_index);
+        assert_eq!(
+            TrackValues::Float(vec![1.0]),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    #[test]
+    fn make_loop_zero_blend_frames() {
+        let mut anim = anim(1.0, vec![TrackValues::Float(vec![0.0, 1.0])]);
+        anim.make_loop(0);
+
+        assert_eq!(1.0, anim.final_frame_index);
+        assert_eq!(
+            TrackValues::Float(vec![0.0, 1.0]),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+    }
+}
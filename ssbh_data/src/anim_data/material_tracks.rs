@@ -0,0 +1,245 @@
+//! Typed access to the material parameter tracks in [AnimData].
+//!
+//! Tracks in a [GroupType::Material] group are named after the material parameter like `"CustomVector6"`.
+//! The [TrackValues] for each track must match the type of the parameter.
+//! Using [ParamId] instead of strings prevents typos and tracks with the wrong type of values,
+//! such as a [TrackValues::Float] for a vector parameter.
+/*!
+```rust
+use ssbh_data::anim_data::{AnimData, TrackValues};
+use ssbh_data::matl_data::ParamId;
+use ssbh_lib::Vector4;
+
+let mut anim = AnimData {
+    major_version: 2,
+    minor_version: 0,
+    final_frame_index: 0.0,
+    groups: Vec::new(),
+};
+
+anim.insert_material_track(
+    "mat",
+    ParamId::CustomVector6,
+    TrackValues::Vector4(vec![Vector4::new(1.0, 1.0, 0.0, 0.0)]),
+)
+.unwrap();
+
+// The values must have the same type as the parameter.
+assert!(anim
+    .insert_material_track("mat", ParamId::CustomFloat0, TrackValues::Boolean(vec![true]))
+    .is_err());
+
+assert!(anim.material_track("mat", ParamId::CustomVector6).is_some());
+```
+ */
+use ssbh_lib::formats::anim::{GroupType, TrackTypeV2};
+
+use super::{AnimData, TrackData, TrackValues, TransformFlags};
+use crate::matl_data::param_families::{param_family, param_id_from_name, param_name, ParamFamily};
+use crate::matl_data::ParamId;
+
+pub mod error {
+    use super::{ParamId, TrackTypeV2};
+    use thiserror::Error;
+
+    /// Errors while creating material tracks.
+    #[derive(Debug, Error, PartialEq, Eq)]
+    pub enum Error {
+        /// The parameter type can not be animated.
+        #[error("parameter {:?} does not support animation", param_id)]
+        UnsupportedParam { param_id: ParamId },
+
+        /// The track values do not match the type of the parameter.
+        #[error(
+            "parameter {:?} expects {:?} values but found {:?} values",
+            param_id,
+            expected,
+            actual
+        )]
+        TrackTypeMismatch {
+            param_id: ParamId,
+            expected: TrackTypeV2,
+            actual: TrackTypeV2,
+        },
+    }
+}
+
+/// The type of [TrackValues] for animating `param_id` or `None` if the parameter can not be animated.
+/**
+```rust
+use ssbh_data::anim_data::material_tracks::material_track_type;
+use ssbh_data::matl_data::ParamId;
+use ssbh_lib::formats::anim::TrackTypeV2;
+
+assert_eq!(Some(TrackTypeV2::Vector4), material_track_type(ParamId::CustomVector6));
+assert_eq!(None, material_track_type(ParamId::Texture0));
+```
+ */
+pub fn material_track_type(param_id: ParamId) -> Option<TrackTypeV2> {
+    match param_family(param_id)?.0 {
+        ParamFamily::CustomVector => Some(TrackTypeV2::Vector4),
+        ParamFamily::CustomFloat => Some(TrackTypeV2::Float),
+        ParamFamily::CustomBoolean => Some(TrackTypeV2::Boolean),
+        ParamFamily::UvTransform => Some(TrackTypeV2::UvTransform),
+        ParamFamily::Texture
+        | ParamFamily::Sampler
+        | ParamFamily::BlendState
+        | ParamFamily::RasterizerState => None,
+    }
+}
+
+/// Finds the [ParamId] with the given track name like `"CustomVector6"`.
+/// Only numbered parameters like `"CustomFloat0"` or `"Texture3"` have a corresponding [ParamId].
+pub fn material_param_id(name: &str) -> Option<ParamId> {
+    param_id_from_name(name)
+}
+
+impl TrackData {
+    /// Creates a track named after `param_id` with the default flags.
+    /// The `values` must have the type of values given by [material_track_type].
+    pub fn material(param_id: ParamId, values: TrackValues) -> Result<Self, error::Error> {
+        let expected =
+            material_track_type(param_id).ok_or(error::Error::UnsupportedParam { param_id })?;
+        let actual = values.track_type();
+        if actual != expected {
+            return Err(error::Error::TrackTypeMismatch {
+                param_id,
+                expected,
+                actual,
+            });
+        }
+
+        // Every parameter with a track type is numbered.
+        let name = param_name(param_id).ok_or(error::Error::UnsupportedParam { param_id })?;
+
+        Ok(Self {
+            name,
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values,
+        })
+    }
+
+    /// The [ParamId] for the track [name](#structfield.name) if it names a material parameter.
+    pub fn material_param_id(&self) -> Option<ParamId> {
+        material_param_id(&self.name)
+    }
+}
+
+impl AnimData {
+    /// Finds the track for `param_id` in the material group node named `material_label`.
+    pub fn material_track(&self, material_label: &str, param_id: ParamId) -> Option<&TrackData> {
+        let name = param_name(param_id)?;
+        self.groups
+            .iter()
+            .filter(|g| g.group_type == GroupType::Material)
+            .flat_map(|g| g.nodes.iter())
+            .filter(|n| n.name == material_label)
+            .flat_map(|n| n.tracks.iter())
+            .find(|t| t.name == name)
+    }
+
+    /// Adds or replaces the track for `param_id` in the material group node named `material_label`.
+    /// See [insert_track](#method.insert_track) and [TrackData::material].
+    pub fn insert_material_track(
+        &mut self,
+        material_label: &str,
+        param_id: ParamId,
+        values: TrackValues,
+    ) -> Result<(), error::Error> {
+        let track = TrackData::material(param_id, values)?;
+        self.insert_track(GroupType::Material, material_label, track);
+        Ok(())
+    }
+
+    /// The tracks in material groups with names that do not match a parameter
+    /// or values that do not match the parameter type.
+    /// Returns the material label and track for each invalid track.
+    pub fn invalid_material_tracks(&self) -> Vec<(&str, &TrackData)> {
+        self.groups
+            .iter()
+            .filter(|g| g.group_type == GroupType::Material)
+            .flat_map(|g| g.nodes.iter())
+            .flat_map(|n| n.tracks.iter().map(move |t| (n.name.as_str(), t)))
+            .filter(|(_, t)| {
+                t.material_param_id().and_then(material_track_type) != Some(t.values.track_type())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim_data::UvTransform;
+    use ssbh_lib::Vector4;
+
+    #[test]
+    fn param_id_from_track_name() {
+        assert_eq!(
+            Some(ParamId::CustomVector6),
+            material_param_id("CustomVector6")
+        );
+        assert_eq!(
+            Some(ParamId::UvTransform0),
+            material_param_id("UvTransform0")
+        );
+        assert_eq!(None, material_param_id("Transform"));
+    }
+
+    #[test]
+    fn material_track_type_mismatch() {
+        assert_eq!(
+            Err(error::Error::TrackTypeMismatch {
+                param_id: ParamId::CustomVector6,
+                expected: TrackTypeV2::Vector4,
+                actual: TrackTypeV2::Float,
+            }),
+            TrackData::material(ParamId::CustomVector6, TrackValues::Float(vec![1.0]))
+        );
+        assert_eq!(
+            Err(error::Error::UnsupportedParam {
+                param_id: ParamId::Texture0
+            }),
+            TrackData::material(ParamId::Texture0, TrackValues::Float(vec![1.0]))
+        );
+    }
+
+    #[test]
+    fn insert_and_query_material_tracks() {
+        let mut anim = crate::test_fixtures::empty_anim_data(0.0);
+        anim.insert_material_track(
+            "a",
+            ParamId::UvTransform1,
+            TrackValues::UvTransform(vec![UvTransform::default()]),
+        )
+        .unwrap();
+        anim.insert_material_track(
+            "b",
+            ParamId::CustomVector6,
+            TrackValues::Vector4(vec![Vector4::new(1.0, 2.0, 3.0, 4.0)]),
+        )
+        .unwrap();
+
+        let track = anim.material_track("b", ParamId::CustomVector6).unwrap();
+        assert_eq!("CustomVector6", track.name);
+        assert_eq!(Some(ParamId::CustomVector6), track.material_param_id());
+        assert!(anim.material_track("a", ParamId::CustomVector6).is_none());
+        assert!(anim.invalid_material_tracks().is_empty());
+
+        anim.insert_track(
+            GroupType::Material,
+            "a",
+            TrackData {
+                name: "CustomFloat0".to_string(),
+                compensate_scale: false,
+                transform_flags: TransformFlags::default(),
+                values: TrackValues::Boolean(vec![true]),
+            },
+        );
+        let invalid = anim.invalid_material_tracks();
+        assert_eq!(1, invalid.len());
+        assert_eq!("a", invalid[0].0);
+        assert_eq!("CustomFloat0", invalid[0].1.name);
+    }
+}
@@ -0,0 +1,244 @@
+use super::{AnimData, GroupType, TrackData};
+
+impl AnimData {
+    /// Removes the tracks where `f` returns `false` and returns the number of removed tracks.
+    /// The order of the remaining groups, nodes, and tracks is preserved.
+    ///
+    /// If `remove_empty_groups` is `true`, nodes with no tracks and groups with no nodes are also removed.
+    /// Some games reject animations with empty groups.
+    /**
+    ```rust
+    # use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags};
+    # let track = |name: &str| TrackData {
+    #     name: name.to_string(),
+    #     compensate_scale: false,
+    #     transform_flags: TransformFlags::default(),
+    #     values: TrackValues::Float(vec![0.0]),
+    # };
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 0.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Material,
+            nodes: vec![NodeData {
+                name: "EyeL".to_string(),
+                tracks: vec![track("CustomFloat8"), track("CustomFloat10")],
+            }],
+        }],
+    };
+
+    // Remove all tracks for CustomFloat8.
+    let count = anim.retain_tracks(|_, _, t| t.name != "CustomFloat8", true);
+    assert_eq!(1, count);
+    assert_eq!("CustomFloat10", anim.groups[0].nodes[0].tracks[0].name);
+    ```
+     */
+    pub fn retain_tracks<F>(&mut self, mut f: F, remove_empty_groups: bool) -> usize
+    where
+        F: FnMut(GroupType, &str, &TrackData) -> bool,
+    {
+        let mut count = 0;
+        for group in &mut self.groups {
+            for node in &mut group.nodes {
+                let length = node.tracks.len();
+                node.tracks.retain(|t| f(group.group_type, &node.name, t));
+                count += length - node.tracks.len();
+            }
+        }
+
+        if remove_empty_groups {
+            self.remove_empty_groups();
+        }
+
+        count
+    }
+
+    /// Removes the track named `track_name` from the node named `node_name` in the group with type `group_type`.
+    /// Returns `true` if the track was found.
+    ///
+    /// See [AnimData::retain_tracks] for the behavior of `remove_empty_groups`.
+    pub fn remove_track(
+        &mut self,
+        group_type: GroupType,
+        node_name: &str,
+        track_name: &str,
+        remove_empty_groups: bool,
+    ) -> bool {
+        let count = self.retain_tracks(
+            |g, n, t| !(g == group_type && n == node_name && t.name == track_name),
+            remove_empty_groups,
+        );
+        count > 0
+    }
+
+    /// Removes the node named `node_name` and all of its tracks from the group with type `group_type`.
+    /// Returns `true` if the node was found.
+    ///
+    /// See [AnimData::retain_tracks] for the behavior of `remove_empty_groups`.
+    pub fn remove_node(
+        &mut self,
+        group_type: GroupType,
+        node_name: &str,
+        remove_empty_groups: bool,
+    ) -> bool {
+        let mut found = false;
+        for group in self
+            .groups
+            .iter_mut()
+            .filter(|g| g.group_type == group_type)
+        {
+            let length = group.nodes.len();
+            group.nodes.retain(|n| n.name != node_name);
+            found |= group.nodes.len() < length;
+        }
+
+        if remove_empty_groups {
+            self.remove_empty_groups();
+        }
+
+        found
+    }
+
+    /// Removes nodes with no tracks and then groups with no nodes.
+    pub fn remove_empty_groups(&mut self) {
+        for group in &mut self.groups {
+            group.nodes.retain(|n| !n.tracks.is_empty());
+        }
+        self.groups.retain(|g| !g.nodes.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim_data::{GroupData, NodeData, TrackValues, TransformFlags};
+
+    fn track(name: &str) -> TrackData {
+        TrackData {
+            name: name.to_string(),
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values: TrackValues::Boolean(vec![true]),
+        }
+    }
+
+    fn node(name: &str, tracks: &[&str]) -> NodeData {
+        NodeData {
+            name: name.to_string(),
+            tracks: tracks.iter().map(|t| track(t)).collect(),
+        }
+    }
+
+    fn anim() -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![
+                GroupData {
+                    group_type: GroupType::Visibility,
+                    nodes: vec![node("a", &["Visibility"]), node("b", &["Visibility"])],
+                },
+                GroupData {
+                    group_type: GroupType::Material,
+                    nodes: vec![node("c", &["CustomFloat0", "CustomFloat1"])],
+                },
+            ],
+        }
+    }
+
+    fn names(anim: &AnimData) -> Vec<(GroupType, String, Vec<String>)> {
+        anim.groups
+            .iter()
+            .flat_map(|g| {
+                g.nodes.iter().map(|n| {
+                    (
+                        g.group_type,
+                        n.name.clone(),
+                        n.tracks.iter().map(|t| t.name.clone()).collect(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn remove_track_keep_empty() {
+        let mut anim = anim();
+        assert!(anim.remove_track(GroupType::Visibility, "a", "Visibility", false));
+        assert!(!anim.remove_track(GroupType::Material, "a", "Visibility", false));
+
+        assert_eq!(2, anim.groups.len());
+        assert!(anim.groups[0].nodes[0].tracks.is_empty());
+        assert_eq!("b", anim.groups[0].nodes[1].name);
+    }
+
+    #[test]
+    fn remove_track_remove_empty() {
+        let mut anim = anim();
+        assert!(anim.remove_track(GroupType::Material, "c", "CustomFloat0", true));
+        assert!(anim.remove_track(GroupType::Material, "c", "CustomFloat1", true));
+
+        assert_eq!(
+            vec![
+                (
+                    GroupType::Visibility,
+                    "a".to_string(),
+                    vec!["Visibility".to_string()]
+                ),
+                (
+                    GroupType::Visibility,
+                    "b".to_string(),
+                    vec!["Visibility".to_string()]
+                ),
+            ],
+            names(&anim)
+        );
+    }
+
+    #[test]
+    fn remove_node_preserves_order() {
+        let mut anim = anim();
+        assert!(anim.remove_node(GroupType::Visibility, "a", true));
+        assert!(!anim.remove_node(GroupType::Transform, "b", true));
+
+        assert_eq!(
+            vec![
+                (
+                    GroupType::Visibility,
+                    "b".to_string(),
+                    vec!["Visibility".to_string()]
+                ),
+                (
+                    GroupType::Material,
+                    "c".to_string(),
+                    vec!["CustomFloat0".to_string(), "CustomFloat1".to_string()]
+                ),
+            ],
+            names(&anim)
+        );
+    }
+
+    #[test]
+    fn remove_node_empty_group() {
+        let mut anim = anim();
+        anim.remove_node(GroupType::Material, "c", false);
+        assert_eq!(2, anim.groups.len());
+
+        anim.remove_empty_groups();
+        assert_eq!(1, anim.groups.len());
+        assert_eq!(GroupType::Visibility, anim.groups[0].group_type);
+    }
+
+    #[test]
+    fn retain_tracks_count() {
+        let mut anim = anim();
+        assert_eq!(
+            2,
+            anim.retain_tracks(|g, _, _| g != GroupType::Visibility, true)
+        );
+        assert_eq!(1, anim.groups.len());
+        assert_eq!(GroupType::Material, anim.groups[0].group_type);
+    }
+}
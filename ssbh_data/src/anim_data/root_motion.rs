@@ -0,0 +1,204 @@
+use ssbh_lib::Vector3;
+
+use super::{error, AnimData, GroupType, TrackValues, Transform};
+
+impl AnimData {
+    /// Removes the root motion from the transform track for the node `node_name` like `"Trans"`
+    /// and returns the translation for each frame relative to the first frame.
+    ///
+    /// Every frame keeps the translation of the first frame,
+    /// so the resulting animation plays in place.
+    /// Discard the result to only remove the root motion.
+    /// Apply the displacement again with [AnimData::add_root_motion].
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, Transform, TransformFlags};
+    use ssbh_data::Vector3;
+
+    let mut anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 1.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Transform,
+            nodes: vec![NodeData {
+                name: "Trans".to_string(),
+                tracks: vec![TrackData {
+                    name: "Transform".to_string(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Transform(vec![
+                        Transform::IDENTITY,
+                        Transform {
+                            translation: Vector3::new(0.0, 0.0, 5.0),
+                            ..Transform::IDENTITY
+                        },
+                    ]),
+                }],
+            }],
+        }],
+    };
+
+    let displacement = anim.extract_root_motion("Trans")?;
+    assert_eq!(vec![Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 5.0)], displacement);
+    # Ok::<(), ssbh_data::anim_data::error::Error>(())
+    ```
+     */
+    pub fn extract_root_motion(&mut self, node_name: &str) -> Result<Vec<Vector3>, error::Error> {
+        let values = self.transform_values_mut(node_name)?;
+
+        let origin = values.first().map(|t| t.translation).unwrap_or_default();
+        Ok(values
+            .iter_mut()
+            .map(|t| {
+                let displacement = sub(t.translation, origin);
+                t.translation = origin;
+                displacement
+            })
+            .collect())
+    }
+
+    /// Adds `displacement` to the translation of each frame in the transform track for the node `node_name`.
+    /// This is the inverse of [AnimData::extract_root_motion].
+    ///
+    /// Constant tracks with a single frame are expanded to the length of `displacement`.
+    /// Otherwise, `displacement` must have a value for each frame in the track.
+    pub fn add_root_motion(
+        &mut self,
+        node_name: &str,
+        displacement: &[Vector3],
+    ) -> Result<(), error::Error> {
+        let values = self.transform_values_mut(node_name)?;
+
+        if values.len() == 1 && displacement.len() > 1 {
+            values.resize(displacement.len(), values[0]);
+        }
+
+        if values.len() != displacement.len() {
+            return Err(error::Error::RootMotionFrameCountMismatch {
+                frame_count: values.len(),
+                displacement_count: displacement.len(),
+            });
+        }
+
+        for (t, d) in values.iter_mut().zip(displacement) {
+            t.translation = add(t.translation, *d);
+        }
+        Ok(())
+    }
+
+    fn transform_values_mut(
+        &mut self,
+        node_name: &str,
+    ) -> Result<&mut Vec<Transform>, error::Error> {
+        self.groups
+            .iter_mut()
+            .filter(|g| g.group_type == GroupType::Transform)
+            .flat_map(|g| g.nodes.iter_mut())
+            .filter(|n| n.name == node_name)
+            .flat_map(|n| n.tracks.iter_mut())
+            .find_map(|t| match &mut t.values {
+                TrackValues::Transform(values) => Some(values),
+                _ => None,
+            })
+            .ok_or_else(|| error::Error::MissingTransformTrack {
+                node_name: node_name.to_string(),
+            })
+    }
+}
+
+fn add(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+
+fn sub(a: Vector3, b: Vector3) -> Vector3 {
+    Vector3::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation(x: f32, y: f32, z: f32) -> Transform {
+        Transform {
+            translation: Vector3::new(x, y, z),
+            ..Transform::IDENTITY
+        }
+    }
+
+    fn anim(values: Vec<Transform>) -> AnimData {
+        let final_frame_index = values.len().saturating_sub(1) as f32;
+        crate::test_fixtures::transform_track_anim_data(
+            "Trans",
+            final_frame_index,
+            vec![TrackValues::Transform(values)],
+        )
+    }
+
+    #[test]
+    fn extract_add_root_motion() {
+        let values = vec![
+            translation(1.0, 2.0, 3.0),
+            translation(1.0, 2.5, 4.0),
+            translation(0.0, 2.0, 6.0),
+        ];
+        let mut anim = anim(values.clone());
+
+        let displacement = anim.extract_root_motion("Trans").unwrap();
+        assert_eq!(
+            vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(0.0, 0.5, 1.0),
+                Vector3::new(-1.0, 0.0, 3.0)
+            ],
+            displacement
+        );
+        assert_eq!(
+            TrackValues::Transform(vec![translation(1.0, 2.0, 3.0); 3]),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+
+        anim.add_root_motion("Trans", &displacement).unwrap();
+        assert_eq!(
+            TrackValues::Transform(values),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    #[test]
+    fn add_root_motion_constant_track() {
+        let mut anim = anim(vec![translation(1.0, 0.0, 0.0)]);
+        anim.add_root_motion(
+            "Trans",
+            &[Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 2.0)],
+        )
+        .unwrap();
+        assert_eq!(
+            TrackValues::Transform(vec![translation(1.0, 0.0, 0.0), translation(1.0, 0.0, 2.0)]),
+            anim.groups[0].nodes[0].tracks[0].values
+        );
+    }
+
+    #[test]
+    fn add_root_motion_frame_count_mismatch() {
+        let mut anim = anim(vec![Transform::IDENTITY; 3]);
+        let result = anim.add_root_motion("Trans", &[Vector3::new(0.0, 0.0, 0.0); 2]);
+        assert!(matches!(
+            result,
+            Err(error::Error::RootMotionFrameCountMismatch {
+                frame_count: 3,
+                displacement_count: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn extract_root_motion_missing_node() {
+        let mut anim = anim(vec![Transform::IDENTITY]);
+        let result = anim.extract_root_motion("Hip");
+        assert!(matches!(
+            result,
+            Err(error::Error::MissingTransformTrack { node_name }) if node_name == "Hip"
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+//! Summary statistics for checking animation values.
+//!
+//! Values are split into floating point components for each frame.
+//! Transforms use `[sx, sy, sz, rx, ry, rz, rw, tx, ty, tz]`,
+//! UV transforms use `[scale_u, scale_v, rotation, translate_u, translate_v]`,
+//! and vectors use `[x, y, z, w]`.
+//! Pattern indices and floats use a single component, and booleans use `0.0` or `1.0`.
+use super::{AnimData, GroupType, TrackData, TrackValues, Transform, UvTransform};
+
+/// The statistics for a track in [AnimData::track_stats].
+#[derive(Debug, PartialEq, Clone)]
+pub struct TrackStats {
+    pub group_type: GroupType,
+    pub node_name: String,
+    pub track_name: String,
+    pub stats: TrackValueStats,
+}
+
+/// Summary statistics for the components of the values in a track.
+/// The statistics for each component are stored at the index of the component.
+/// See the [module level documentation](self) for the component order.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TrackValueStats {
+    /// The smallest value of each component or empty if the track has no frames.
+    pub min: Vec<f32>,
+    /// The largest value of each component or empty if the track has no frames.
+    pub max: Vec<f32>,
+    /// The average value of each component or empty if the track has no frames.
+    pub mean: Vec<f32>,
+    /// The frames that change abruptly from the previous frame.
+    pub discontinuities: Vec<Discontinuity>,
+}
+
+/// A frame where at least one component changed by more than the threshold from the previous frame.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Discontinuity {
+    /// The index of the frame after the jump.
+    pub frame: usize,
+    /// The index of the component with the largest change.
+    pub component: usize,
+    /// The absolute difference for the component with the largest change.
+    pub difference: f32,
+}
+
+impl AnimData {
+    /// Calculates the [TrackValueStats] for each track.
+    ///
+    /// Frames are flagged as discontinuities if any component differs from the previous frame by more than `threshold`.
+    /// Large jumps in transform tracks usually appear as popping or teleporting bones in game.
+    /**
+    ```rust
+    use ssbh_data::anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags};
+
+    let anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: 3.0,
+        groups: vec![GroupData {
+            group_type: GroupType::Material,
+            nodes: vec![NodeData {
+                name: "EyeL".to_string(),
+                tracks: vec![TrackData {
+                    name: "CustomFloat8".to_string(),
+                    compensate_scale: false,
+                    transform_flags: TransformFlags::default(),
+                    values: TrackValues::Float(vec![0.0, 0.1, 5.0, 5.1]),
+                }],
+            }],
+        }],
+    };
+
+    let stats = anim.track_stats(1.0);
+    assert_eq!(vec![0.0], stats[0].stats.min);
+    assert_eq!(vec![5.1], stats[0].stats.max);
+    assert_eq!(2, stats[0].stats.discontinuities[0].frame);
+    ```
+     */
+    pub fn track_stats(&self, threshold: f32) -> Vec<TrackStats> {
+        self.tracks()
+            .map(|(group_type, node_name, track)| TrackStats {
+                group_type,
+                node_name: node_name.to_string(),
+                track_name: track.name.clone(),
+                stats: track.value_stats(threshold),
+            })
+            .collect()
+    }
+}
+
+impl TrackData {
+    /// Calculates the [TrackValueStats] for the values of this track.
+    /// See [AnimData::track_stats].
+    pub fn value_stats(&self, threshold: f32) -> TrackValueStats {
+        match &self.values {
+            TrackValues::Transform(v) => value_stats(&transform_components(v), threshold),
+            TrackValues::UvTransform(v) => {
+                let components: Vec<_> = v.iter().map(uv_transform_components).collect();
+                value_stats(&components, threshold)
+            }
+            TrackValues::Float(v) => {
+                let components: Vec<_> = v.iter().map(|f| [*f]).collect();
+                value_stats(&components, threshold)
+            }
+            TrackValues::PatternIndex(v) => {
+                let components: Vec<_> = v.iter().map(|i| [*i as f32]).collect();
+                value_stats(&components, threshold)
+            }
+            TrackValues::Boolean(v) => {
+                let components: Vec<_> = v.iter().map(|b| [if *b { 1.0 } else { 0.0 }]).collect();
+                value_stats(&components, threshold)
+            }
+            TrackValues::Vector4(v) => {
+                let components: Vec<_> = v.iter().map(|v| v.to_array()).collect();
+                value_stats(&components, threshold)
+            }
+            TrackValues::Raw(_) => value_stats::<0>(&[], threshold),
+        }
+    }
+}
+
+fn transform_components(values: &[Transform]) -> Vec<[f32; 10]> {
+    // The quaternions q and -q represent the same rotation.
+    // Choose the sign closest to the previous frame to avoid reporting sign flips as jumps.
+    let mut previous_rotation: Option<glam::Vec4> = None;
+    values
+        .iter()
+        .map(|t| {
+            let mut rotation = glam::Vec4::from_array(t.rotation.to_array());
+            if let Some(previous) = previous_rotation {
+                if previous.dot(rotation) < 0.0 {
+                    rotation = -rotation;
+                }
+            }
+            previous_rotation = Some(rotation);
+
+            [
+                t.scale.x,
+                t.scale.y,
+                t.scale.z,
+                rotation.x,
+                rotation.y,
+                rotation.z,
+                rotation.w,
+                t.translation.x,
+                t.translation.y,
+                t.translation.z,
+            ]
+        })
+        .collect()
+}
+
+fn uv_transform_components(t: &UvTransform) -> [f32; 5] {
+    [
+        t.scale_u,
+        t.scale_v,
+        t.rotation,
+        t.translate_u,
+        t.translate_v,
+    ]
+}
+
+fn value_stats<const N: usize>(frames: &[[f32; N]], threshold: f32) -> TrackValueStats {
+    if frames.is_empty() {
+        return TrackValueStats {
+            min: Vec::new(),
+            max: Vec::new(),
+            mean: Vec::new(),
+            discontinuities: Vec::new(),
+        };
+    }
+
+    let mut min = [f32::INFINITY; N];
+    let mut max = [f32::NEG_INFINITY; N];
+    // Accumulate using f64 to reduce precision loss for long animations.
+    let mut sum = [0.0f64; N];
+    for frame in frames {
+        for i in 0..N {
+            min[i] = min[i].min(frame[i]);
+            max[i] = max[i].max(frame[i]);
+            sum[i] += frame[i] as f64;
+        }
+    }
+
+    let discontinuities = frames
+        .windows(2)
+        .enumerate()
+        .filter_map(|(i, pair)| {
+            let (component, difference) = (0..N)
+                .map(|c| (c, (pair[1][c] - pair[0][c]).abs()))
+                .fold((0, 0.0), |a, b| if b.1 > a.1 { b } else { a });
+
+            (difference > threshold).then_some(Discontinuity {
+                frame: i + 1,
+                component,
+                difference,
+            })
+        })
+        .collect();
+
+    TrackValueStats {
+        min: min.to_vec(),
+        max: max.to_vec(),
+        mean: sum
+            .iter()
+            .map(|s| (s / frames.len() as f64) as f32)
+            .collect(),
+        discontinuities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim_data::TransformFlags;
+    use ssbh_lib::{Vector3, Vector4};
+
+    fn track(values: TrackValues) -> TrackData {
+        TrackData {
+            name: "Transform".to_string(),
+            compensate_scale: false,
+            transform_flags: TransformFlags::default(),
+            values,
+        }
+    }
+
+    #[test]
+    fn value_stats_empty() {
+        let stats = track(TrackValues::Float(Vec::new())).value_stats(0.0);
+        assert!(stats.min.is_empty());
+        assert!(stats.max.is_empty());
+        assert!(stats.mean.is_empty());
+        assert!(stats.discontinuities.is_empty());
+    }
+
+    #[test]
+    fn value_stats_vector4() {
+        let stats = track(TrackValues::Vector4(vec![
+            Vector4::new(1.0, 2.0, 3.0, 4.0),
+            Vector4::new(3.0, 2.0, -1.0, 4.5),
+        ]))
+        .value_stats(3.0);
+        assert_eq!(vec![1.0, 2.0, -1.0, 4.0], stats.min);
+        assert_eq!(vec![3.0, 2.0, 3.0, 4.5], stats.max);
+        assert_eq!(vec![2.0, 2.0, 1.0, 4.25], stats.mean);
+        assert_eq!(
+            vec![Discontinuity {
+                frame: 1,
+                component: 2,
+                difference: 4.0
+            }],
+            stats.discontinuities
+        );
+    }
+
+    #[test]
+    fn value_stats_boolean() {
+        let stats = track(TrackValues::Boolean(vec![true, true, false, false])).value_stats(0.5);
+        assert_eq!(vec![0.0], stats.min);
+        assert_eq!(vec![1.0], stats.max);
+        assert_eq!(vec![0.5], stats.mean);
+        assert_eq!(1, stats.discontinuities.len());
+        assert_eq!(2, stats.discontinuities[0].frame);
+    }
+
+    #[test]
+    fn value_stats_transform_translation_jump() {
+        let stats = track(TrackValues::Transform(vec![
+            Transform::IDENTITY,
+            Transform {
+                translation: Vector3::new(0.0, 0.5, 0.0),
+                ..Transform::IDENTITY
+            },
+            Transform {
+                translation: Vector3::new(0.0, 10.5, 0.0),
+                ..Transform::IDENTITY
+            },
+        ]))
+        .value_stats(1.0);
+        assert_eq!(
+            vec![Discontinuity {
+                frame: 2,
+                component: 8,
+                difference: 10.0
+            }],
+            stats.discontinuities
+        );
+    }
+
+    #[test]
+    fn value_stats_transform_rotation_sign_flip() {
+        // Negating the quaternion doesn't change the orientation.
+        let stats = track(TrackValues::Transform(vec![
+            Transform::IDENTITY,
+            Transform {
+                rotation: Vector4::new(0.0, 0.0, 0.0, -1.0),
+                ..Transform::IDENTITY
+            },
+        ]))
+        .value_stats(0.0);
+        assert!(stats.discontinuities.is_empty());
+        assert_eq!(1.0, stats.min[6]);
+    }
+}
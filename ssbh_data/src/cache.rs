@@ -0,0 +1,161 @@
+//! A thread-safe cache for repeatedly reading the same files.
+//!
+//! Entries are keyed by the file path and last modification time,
+//! so files are only read and converted again after they change on disk.
+//! This is mainly intended for applications like model browsers that reopen the same files.
+//! Enable the `cache` feature to use this module.
+/*!
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::cache::FileCache;
+use ssbh_data::prelude::*;
+
+let cache = FileCache::<MeshData>::new();
+
+// The second call returns the previously read data if the file is unchanged.
+let mesh = cache.from_file("model.numshb")?;
+let mesh = cache.from_file("model.numshb")?;
+println!("{}", mesh.objects.len());
+# Ok(()) }
+```
+ */
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use ssbh_lib::SsbhFile;
+
+use crate::SsbhData;
+
+/// A cache of parsed files of type `T` keyed by path and modification time.
+///
+/// The cache can be shared between threads. Values are returned as an [Arc] to avoid copying.
+#[derive(Debug)]
+pub struct FileCache<T> {
+    entries: Mutex<HashMap<PathBuf, (SystemTime, Arc<T>)>>,
+}
+
+impl<T> Default for FileCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T> FileCache<T> {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `path` or calls `read` if the file is not cached
+    /// or has been modified since it was last read.
+    /// Errors from `read` are not cached.
+    pub fn get_or_read<P, F>(&self, path: P, read: F) -> Result<Arc<T>, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(&Path) -> Result<T, Box<dyn Error>>,
+    {
+        let path = path.as_ref();
+        let modified = std::fs::metadata(path)?.modified()?;
+
+        if let Some((time, value)) = self.lock().get(path) {
+            if *time == modified {
+                return Ok(value.clone());
+            }
+        }
+
+        // Avoid holding the lock while reading so other threads can access the cache.
+        let value = Arc::new(read(path)?);
+        self.lock()
+            .insert(path.to_owned(), (modified, value.clone()));
+        Ok(value)
+    }
+
+    /// Removes the cached value for `path` if present.
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Option<Arc<T>> {
+        self.lock().remove(path.as_ref()).map(|(_, value)| value)
+    }
+
+    /// Removes all cached values.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// The number of cached files.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no files are cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, (SystemTime, Arc<T>)>> {
+        // A panic while holding the lock can't leave the map in an invalid state.
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: SsbhData> FileCache<T> {
+    /// Reads the data from `path` using [SsbhData::from_file] or returns the cached value.
+    pub fn from_file<P: AsRef<Path>>(&self, path: P) -> Result<Arc<T>, Box<dyn Error>> {
+        self.get_or_read(path, |p| T::from_file(p))
+    }
+}
+
+impl FileCache<SsbhFile> {
+    /// Reads the data from `path` using [SsbhFile::from_file] or returns the cached value.
+    pub fn from_file<P: AsRef<Path>>(&self, path: P) -> Result<Arc<SsbhFile>, Box<dyn Error>> {
+        self.get_or_read(path, |p| SsbhFile::from_file(p).map_err(Into::into))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hlpb_data::HlpbData;
+    use crate::test_fixtures;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn cache_unmodified_file() {
+        let path =
+            std::env::temp_dir().join(format!("ssbh_data_cache_{}.nuhlpb", std::process::id()));
+        test_fixtures::hlpb_data().write_to_file(&path).unwrap();
+
+        let cache = FileCache::<HlpbData>::new();
+        let reads = AtomicUsize::new(0);
+        let read = |p: &Path| {
+            reads.fetch_add(1, Ordering::SeqCst);
+            HlpbData::from_file(p)
+        };
+
+        let a = cache.get_or_read(&path, read).unwrap();
+        let b = cache.get_or_read(&path, read).unwrap();
+        let c = cache.from_file(&path).unwrap();
+
+        assert_eq!(1, reads.load(Ordering::SeqCst));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(Arc::ptr_eq(&a, &c));
+        assert_eq!(1, cache.len());
+
+        assert!(cache.remove(&path).is_some());
+        assert!(cache.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn cache_missing_file() {
+        let cache = FileCache::<SsbhFile>::new();
+        assert!(cache.from_file("does_not_exist.nuhlpb").is_err());
+        assert!(cache.is_empty());
+    }
+}
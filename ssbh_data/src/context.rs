@@ -0,0 +1,222 @@
+//! Context for errors while reading and converting files.
+//!
+//! Errors for the underlying data like an out of range buffer index don't identify the element that failed.
+//! Read errors are wrapped in a [ContextError] for each level of the file like the path, mesh object, and attribute.
+//! The [Display](std::fmt::Display) output for a [ContextError] only includes its own context.
+//! Iterate over the [source](std::error::Error::source) errors to list the context from outermost to innermost.
+//!
+//! Matl entries always convert successfully, so there is no context for matl entries.
+//!
+//! Errors from functions like [SsbhData::from_file](crate::SsbhData::from_file) are wrapped in a [ContextError].
+//! Use [root_cause] to access the original error type with [downcast_ref](std::error::Error::downcast_ref).
+/*!
+```rust no_run
+use ssbh_data::context::root_cause;
+use ssbh_data::prelude::*;
+
+match MeshData::from_file("model.numshb") {
+    Ok(mesh) => println!("{} objects", mesh.objects.len()),
+    Err(e) => {
+        // ex: file "model.numshb": mesh object 12 "body" subindex 0: attribute "map1": ...
+        let mut source = Some(e.as_ref());
+        while let Some(e) = source {
+            print!("{e}: ");
+            source = e.source();
+        }
+
+        let cause = root_cause(e.as_ref());
+        if let Some(e) = cause.downcast_ref::<ssbh_data::mesh_data::error::AttributeError>() {
+            println!("{e:?}");
+        }
+    }
+}
+```
+ */
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::anim_data::GroupType;
+
+/// The element of a file that caused an error.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ErrorContext {
+    /// The path of the file being read.
+    File(PathBuf),
+    /// The mesh object at `index` in [objects](crate::mesh_data::MeshData::objects).
+    MeshObject {
+        index: usize,
+        name: String,
+        subindex: u64,
+    },
+    /// The attribute for a mesh object like `"Position0"`.
+    Attribute { name: String },
+    /// The bone at `index` in [bones](crate::skel_data::SkelData::bones).
+    Bone { index: usize, name: String },
+    /// The track in a node and group for an animation.
+    Track {
+        group_type: GroupType,
+        node_name: String,
+        track_name: String,
+    },
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorContext::File(path) => write!(f, "file {path:?}"),
+            ErrorContext::MeshObject {
+                index,
+                name,
+                subindex,
+            } => write!(f, "mesh object {index} {name:?} subindex {subindex}"),
+            ErrorContext::Attribute { name } => write!(f, "attribute {name:?}"),
+            ErrorContext::Bone { index, name } => write!(f, "bone {index} {name:?}"),
+            ErrorContext::Track {
+                group_type,
+                node_name,
+                track_name,
+            } => write!(
+                f,
+                "{group_type:?} group node {node_name:?} track {track_name:?}"
+            ),
+        }
+    }
+}
+
+/// An error with additional context about where the error occurred.
+/// The [source](#structfield.source) may also be a [ContextError] for nested elements.
+#[derive(Debug, thiserror::Error)]
+#[error("{context}")]
+pub struct ContextError {
+    pub context: ErrorContext,
+    pub source: Box<dyn Error>,
+}
+
+impl ContextError {
+    /// The context for this error and any nested [ContextError] from outermost to innermost.
+    pub fn contexts(&self) -> Vec<&ErrorContext> {
+        let mut contexts = vec![&self.context];
+        let mut source = self.source.as_ref();
+        while let Some(e) = source.downcast_ref::<ContextError>() {
+            contexts.push(&e.context);
+            source = e.source.as_ref();
+        }
+        contexts
+    }
+
+    /// The innermost error without any context.
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        root_cause(self.source.as_ref())
+    }
+
+    /// The innermost error without any context if it has type `E`.
+    /**
+    ```rust
+    use ssbh_data::context::ContextError;
+    use ssbh_data::prelude::*;
+
+    let e = SkelData::from_file("missing/model.nusktb").unwrap_err();
+    let e = e.downcast_ref::<ContextError>().unwrap();
+    assert!(e.downcast_root_cause::<ssbh_lib::ReadSsbhError>().is_some());
+    ```
+     */
+    pub fn downcast_root_cause<E: Error + 'static>(&self) -> Option<&E> {
+        self.root_cause().downcast_ref()
+    }
+}
+
+/// The innermost error without any context or `error` if it is not a [ContextError].
+/**
+```rust
+use ssbh_data::context::root_cause;
+use ssbh_data::prelude::*;
+
+let e = SkelData::from_file("missing/model.nusktb").unwrap_err();
+assert!(root_cause(e.as_ref()).downcast_ref::<ssbh_lib::ReadSsbhError>().is_some());
+```
+ */
+pub fn root_cause<'a>(error: &'a (dyn Error + 'static)) -> &'a (dyn Error + 'static) {
+    let mut source = error;
+    while let Some(e) = source.downcast_ref::<ContextError>() {
+        source = e.source.as_ref();
+    }
+    source
+}
+
+/// Adds an [ErrorContext] to the error of a [Result].
+pub(crate) trait ResultExt<T> {
+    fn context<F: FnOnce() -> ErrorContext>(self, context: F) -> Result<T, Box<dyn Error>>;
+}
+
+impl<T, E: Into<Box<dyn Error>>> ResultExt<T> for Result<T, E> {
+    fn context<F: FnOnce() -> ErrorContext>(self, context: F) -> Result<T, Box<dyn Error>> {
+        self.map_err(|source| {
+            ContextError {
+                context: context(),
+                source: source.into(),
+            }
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_context_display() {
+        let result: Result<(), _> = Err("invalid data")
+            .context(|| ErrorContext::Attribute {
+                name: "map1".to_string(),
+            })
+            .context(|| ErrorContext::MeshObject {
+                index: 12,
+                name: "body".to_string(),
+                subindex: 0,
+            })
+            .context(|| ErrorContext::File("model.numshb".into()));
+
+        let e = result.unwrap_err();
+        assert_eq!(r#"file "model.numshb""#, e.to_string());
+
+        // Each error in the chain should only display its own message.
+        let messages: Vec<_> = std::iter::successors(Some(e.as_ref()), |e| (*e).source())
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                r#"file "model.numshb""#,
+                r#"mesh object 12 "body" subindex 0"#,
+                r#"attribute "map1""#,
+                "invalid data"
+            ],
+            messages
+        );
+        assert_eq!("invalid data", root_cause(e.as_ref()).to_string());
+
+        let e = e.downcast_ref::<ContextError>().unwrap();
+        assert_eq!(3, e.contexts().len());
+        assert_eq!(
+            &ErrorContext::Attribute {
+                name: "map1".to_string()
+            },
+            e.contexts()[2]
+        );
+        assert_eq!("invalid data", e.root_cause().to_string());
+    }
+
+    #[test]
+    fn downcast_root_cause() {
+        let result: Result<(), _> = Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))
+            .context(|| ErrorContext::File("model.numshb".into()));
+
+        let e = result.unwrap_err();
+        let e = e.downcast_ref::<ContextError>().unwrap();
+        assert_eq!(
+            std::io::ErrorKind::UnexpectedEof,
+            e.downcast_root_cause::<std::io::Error>().unwrap().kind()
+        );
+        assert!(e.downcast_root_cause::<std::fmt::Error>().is_none());
+    }
+}
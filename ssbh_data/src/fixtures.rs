@@ -0,0 +1,257 @@
+//! Small but structurally complete files for testing.
+//!
+//! The fixtures contain only a few bones, vertices, and frames but use every supported version.
+//! This allows testing code that reads or converts files without shipping copyrighted game files.
+//! Enable the `fixtures` feature to use this module.
+/*!
+```rust
+use ssbh_data::fixtures;
+use ssbh_data::prelude::*;
+use std::io::Cursor;
+
+let mut writer = Cursor::new(Vec::new());
+fixtures::mesh_v10().write(&mut writer).unwrap();
+
+writer.set_position(0);
+let mesh = MeshData::read(&mut writer).unwrap();
+assert_eq!("triangleShape", mesh.objects[0].name);
+```
+ */
+use ssbh_lib::{
+    formats::{anim::Anim, mesh::Mesh, skel::Skel},
+    SsbhArray, SsbhByteBuffer,
+};
+
+use crate::{
+    anim_data::{
+        AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, Transform, TransformFlags,
+    },
+    mesh_data::{AttributeData, BoneInfluence, MeshData, MeshObjectData, VectorData, VertexWeight},
+    skel_data::{BillboardType, BoneData, SkelData},
+    Vector3, Vector4,
+};
+
+/// The names of the bones in [skel_data] from root to leaf.
+pub const BONE_NAMES: [&str; 3] = ["Trans", "Rot", "Hip"];
+
+fn translation(x: f32, y: f32, z: f32) -> [[f32; 4]; 4] {
+    [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [x, y, z, 1.0],
+    ]
+}
+
+/// A version 1.0 skeleton with a chain of three bones named [BONE_NAMES].
+pub fn skel_data() -> SkelData {
+    SkelData {
+        major_version: 1,
+        minor_version: 0,
+        bones: BONE_NAMES
+            .iter()
+            .enumerate()
+            .map(|(i, name)| BoneData {
+                name: name.to_string(),
+                transform: translation(0.0, i as f32, 0.0),
+                parent_index: i.checked_sub(1),
+                billboard_type: BillboardType::Disabled,
+                unk_flags: 1,
+            })
+            .collect(),
+    }
+}
+
+/// The [Skel] for [skel_data].
+pub fn skel_v10() -> Skel {
+    Skel::try_from(&skel_data()).unwrap()
+}
+
+/// A mesh with a single triangle named `"triangleShape"` skinned to the last bone in [skel_data].
+///
+/// The object has positions, normals, tangents, texture coordinates, and color sets.
+pub fn mesh_data(major_version: u16, minor_version: u16) -> MeshData {
+    MeshData {
+        major_version,
+        minor_version,
+        objects: vec![MeshObjectData {
+            name: "triangleShape".to_string(),
+            subindex: 0,
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".to_string(),
+                data: VectorData::Vector3(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]),
+            }],
+            normals: vec![AttributeData {
+                name: "Normal0".to_string(),
+                data: VectorData::Vector4(vec![[0.0, 0.0, 1.0, 0.0]; 3]),
+            }],
+            tangents: vec![AttributeData {
+                name: "Tangent0".to_string(),
+                data: VectorData::Vector4(vec![[1.0, 0.0, 0.0, 1.0]; 3]),
+            }],
+            texture_coordinates: vec![AttributeData {
+                name: "map1".to_string(),
+                data: VectorData::Vector2(vec![[0.0, 1.0], [1.0, 1.0], [0.0, 0.0]]),
+            }],
+            color_sets: vec![AttributeData {
+                name: "colorSet1".to_string(),
+                data: VectorData::Vector4(vec![[0.5; 4]; 3]),
+            }],
+            bone_influences: vec![BoneInfluence {
+                bone_name: BONE_NAMES[2].to_string(),
+                vertex_weights: (0..3)
+                    .map(|i| VertexWeight {
+                        vertex_index: i,
+                        vertex_weight: 1.0,
+                    })
+                    .collect(),
+            }],
+            ..MeshObjectData::default()
+        }],
+    }
+}
+
+/// The version 1.8 [Mesh] for [mesh_data].
+pub fn mesh_v8() -> Mesh {
+    Mesh::try_from(&mesh_data(1, 8)).unwrap()
+}
+
+/// The version 1.9 [Mesh] for [mesh_data].
+pub fn mesh_v9() -> Mesh {
+    Mesh::try_from(&mesh_data(1, 9)).unwrap()
+}
+
+/// The version 1.10 [Mesh] for [mesh_data].
+pub fn mesh_v10() -> Mesh {
+    Mesh::try_from(&mesh_data(1, 10)).unwrap()
+}
+
+/// A two frame animation with transform, visibility, and material tracks for the bones in [skel_data].
+pub fn anim_data(major_version: u16, minor_version: u16) -> AnimData {
+    let track = |name: &str, values| TrackData {
+        name: name.to_string(),
+        compensate_scale: false,
+        transform_flags: TransformFlags::default(),
+        values,
+    };
+
+    AnimData {
+        major_version,
+        minor_version,
+        final_frame_index: 1.0,
+        groups: vec![
+            GroupData {
+                group_type: GroupType::Transform,
+                nodes: BONE_NAMES
+                    .iter()
+                    .map(|name| NodeData {
+                        name: name.to_string(),
+                        tracks: vec![track(
+                            "Transform",
+                            TrackValues::Transform(vec![
+                                Transform::IDENTITY,
+                                Transform {
+                                    translation: Vector3::new(0.0, 1.0, 0.0),
+                                    ..Transform::IDENTITY
+                                },
+                            ]),
+                        )],
+                    })
+                    .collect(),
+            },
+            GroupData {
+                group_type: GroupType::Visibility,
+                nodes: vec![NodeData {
+                    name: "triangleShape".to_string(),
+                    tracks: vec![track("Visibility", TrackValues::Boolean(vec![true, false]))],
+                }],
+            },
+            GroupData {
+                group_type: GroupType::Material,
+                nodes: vec![NodeData {
+                    name: "material".to_string(),
+                    tracks: vec![track(
+                        "CustomVector31",
+                        TrackValues::Vector4(vec![
+                            Vector4::new(1.0, 1.0, 0.0, 0.0),
+                            Vector4::new(1.0, 1.0, 0.5, 0.0),
+                        ]),
+                    )],
+                }],
+            },
+        ],
+    }
+}
+
+/// A version 1.2 [Anim] with no tracks.
+///
+/// The track data for version 1.2 is not supported by [AnimData],
+/// so this only tests reading the header and arrays.
+pub fn anim_v12() -> Anim {
+    Anim::V12 {
+        name: "fixture".into(),
+        unk1: 1.0,
+        final_frame_index: 1.0,
+        unk2: 1.0,
+        unk3: 1.0,
+        tracks: SsbhArray::new(),
+        buffers: SsbhArray::from_vec(vec![SsbhByteBuffer::new()]),
+    }
+}
+
+/// The version 2.0 [Anim] for [anim_data].
+pub fn anim_v20() -> Anim {
+    Anim::try_from(&anim_data(2, 0)).unwrap()
+}
+
+/// The version 2.1 [Anim] for [anim_data].
+pub fn anim_v21() -> Anim {
+    Anim::try_from(&anim_data(2, 1)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn write_read<T, F, R>(value: T, write: F, read: R) -> T
+    where
+        F: Fn(&T, &mut Cursor<Vec<u8>>) -> std::io::Result<()>,
+        R: Fn(&mut Cursor<Vec<u8>>) -> Result<T, ssbh_lib::ReadSsbhError>,
+    {
+        let mut writer = Cursor::new(Vec::new());
+        write(&value, &mut writer).unwrap();
+        writer.set_position(0);
+        read(&mut writer).unwrap()
+    }
+
+    #[test]
+    fn skel_fixtures() {
+        let skel = skel_v10();
+        assert_eq!(skel, write_read(skel.clone(), Skel::write, Skel::read));
+        assert_eq!(skel_data(), SkelData::try_from(&skel).unwrap());
+    }
+
+    #[test]
+    fn mesh_fixtures() {
+        for (mesh, version) in [(mesh_v8(), 8), (mesh_v9(), 9), (mesh_v10(), 10)] {
+            assert_eq!(mesh, write_read(mesh.clone(), Mesh::write, Mesh::read));
+
+            let data = MeshData::try_from(&mesh).unwrap();
+            assert_eq!((1, version), (data.major_version, data.minor_version));
+            assert_eq!(3, data.objects[0].vertex_count().unwrap());
+        }
+    }
+
+    #[test]
+    fn anim_fixtures() {
+        let anim = anim_v12();
+        assert_eq!(anim, write_read(anim.clone(), Anim::write, Anim::read));
+
+        for (anim, version) in [(anim_v20(), 0), (anim_v21(), 1)] {
+            assert_eq!(anim, write_read(anim.clone(), Anim::write, Anim::read));
+            assert_eq!(anim_data(2, version), AnimData::try_from(&anim).unwrap());
+        }
+    }
+}
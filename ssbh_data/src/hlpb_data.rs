@@ -1,13 +1,78 @@
 //! Types for working with [Hlpb] data in .nuhlpb files.
-use std::iter::repeat;
+use std::iter::repeat_n;
 
-use ssbh_lib::{formats::hlpb::*, Vector3, Vector4};
+use ssbh_lib::{formats::hlpb::*, SsbhArray, Vector3, Vector4};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+pub mod description;
+mod order;
+
+pub mod error {
+    use thiserror::Error;
+
+    /// Errors while creating a [Hlpb](super::Hlpb) from [HlpbData](super::HlpbData),
+    /// ordering constraints with [HlpbData::sort_constraints](super::HlpbData::sort_constraints),
+    /// or creating a [description](super::description::HlpbDescription).
+    #[derive(Debug, Error)]
+    pub enum Error {
+        /// Creating a [Hlpb](super::Hlpb) file for the given version is not supported.
+        #[error(
+            "creating a version {}.{} hlpb is not supported",
+            major_version,
+            minor_version
+        )]
+        UnsupportedVersion {
+            major_version: u16,
+            minor_version: u16,
+        },
+
+        /// An error occurred while writing data to a buffer.
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+
+        /// The constraints depend on each other, so no valid evaluation order exists.
+        #[error(
+            "constraints {:?} form or depend on a dependency cycle",
+            constraint_names
+        )]
+        ConstraintCycle { constraint_names: Vec<String> },
+
+        /// An aim constraint reads a bone modified by an orient constraint,
+        /// but aim constraints are always evaluated first.
+        #[error(
+            "aim constraint {:?} depends on orient constraint {:?} but aim constraints are evaluated first",
+            aim_constraint_name,
+            orient_constraint_name
+        )]
+        AimDependsOnOrient {
+            aim_constraint_name: String,
+            orient_constraint_name: String,
+        },
+
+        /// The constraint has a value that can't be represented by a
+        /// [description](super::description::HlpbDescription) without losing information.
+        #[error(
+            "constraint {:?} has a value for {} that can't be described",
+            constraint_name,
+            field
+        )]
+        UnsupportedDescriptionValue {
+            constraint_name: String,
+            field: &'static str,
+        },
+    }
+}
+
 /// The data associated with a [Hlpb] file.
-/// The supported version is 1.1.
+/// Supported versions are 1.0 and 1.1.
+///
+/// Version 1.0 files don't store the `unk17` to `unk22` values for [AimConstraintData],
+/// so these values are `0.0` when reading and ignored when writing.
+///
+/// Converting to [Hlpb] uses [TryFrom] since other versions return [UnsupportedVersion](error::Error::UnsupportedVersion).
+/// This replaces the previous [From] conversion that always created a version 1.1 file.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug, PartialEq, Clone)]
@@ -38,6 +103,20 @@ pub struct AimConstraintData {
     pub up: Vector3,
     pub quat1: Vector4,
     pub quat2: Vector4,
+    /// Additional values for version 1.1 that are usually all `0.0`.
+    /// Older JSON files without these fields use the default of `0.0`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unk17: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unk18: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unk19: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unk20: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unk21: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unk22: f32,
 }
 
 /// Data associated with an [OrientConstraint].
@@ -68,6 +147,16 @@ impl From<Hlpb> for HlpbData {
 impl From<&Hlpb> for HlpbData {
     fn from(h: &Hlpb) -> Self {
         match h {
+            Hlpb::V10 {
+                aim_constraints,
+                orient_constraints,
+                ..
+            } => Self {
+                major_version: 1,
+                minor_version: 0,
+                aim_constraints: aim_constraints.elements.iter().map(Into::into).collect(),
+                orient_constraints: orient_constraints.elements.iter().map(Into::into).collect(),
+            },
             Hlpb::V11 {
                 aim_constraints,
                 orient_constraints,
@@ -82,28 +171,54 @@ impl From<&Hlpb> for HlpbData {
     }
 }
 
-impl From<HlpbData> for Hlpb {
-    fn from(data: HlpbData) -> Self {
-        Self::from(&data)
+impl TryFrom<HlpbData> for Hlpb {
+    type Error = error::Error;
+
+    fn try_from(data: HlpbData) -> Result<Self, Self::Error> {
+        Self::try_from(&data)
     }
 }
 
-impl From<&HlpbData> for Hlpb {
-    fn from(data: &HlpbData) -> Self {
-        Self::V11 {
-            aim_constraints: data.aim_constraints.iter().map(Into::into).collect(),
-            orient_constraints: data.orient_constraints.iter().map(Into::into).collect(),
-            constraint_indices: (0..data.aim_constraints.len() as u32)
-                .chain(0..data.orient_constraints.len() as u32)
-                .collect(),
-            constraint_types: repeat(ConstraintType::Aim)
-                .take(data.aim_constraints.len())
-                .chain(repeat(ConstraintType::Orient).take(data.orient_constraints.len()))
-                .collect(),
+impl TryFrom<&HlpbData> for Hlpb {
+    type Error = error::Error;
+
+    fn try_from(data: &HlpbData) -> Result<Self, Self::Error> {
+        match (data.major_version, data.minor_version) {
+            (1, 0) => Ok(Self::V10 {
+                aim_constraints: data.aim_constraints.iter().map(Into::into).collect(),
+                orient_constraints: data.orient_constraints.iter().map(Into::into).collect(),
+                constraint_indices: constraint_indices(data),
+                constraint_types: constraint_types(data),
+            }),
+            (1, 1) => Ok(Self::V11 {
+                aim_constraints: data.aim_constraints.iter().map(Into::into).collect(),
+                orient_constraints: data.orient_constraints.iter().map(Into::into).collect(),
+                constraint_indices: constraint_indices(data),
+                constraint_types: constraint_types(data),
+            }),
+            _ => Err(error::Error::UnsupportedVersion {
+                major_version: data.major_version,
+                minor_version: data.minor_version,
+            }),
         }
     }
 }
 
+fn constraint_indices(data: &HlpbData) -> SsbhArray<u32> {
+    (0..data.aim_constraints.len() as u32)
+        .chain(0..data.orient_constraints.len() as u32)
+        .collect()
+}
+
+fn constraint_types(data: &HlpbData) -> SsbhArray<ConstraintType> {
+    repeat_n(ConstraintType::Aim, data.aim_constraints.len())
+        .chain(repeat_n(
+            ConstraintType::Orient,
+            data.orient_constraints.len(),
+        ))
+        .collect()
+}
+
 impl From<AimConstraint> for AimConstraintData {
     fn from(a: AimConstraint) -> Self {
         Self::from(&a)
@@ -126,6 +241,12 @@ impl From<&AimConstraint> for AimConstraintData {
             up: a.up,
             quat1: a.quat1,
             quat2: a.quat2,
+            unk17: a.unk17,
+            unk18: a.unk18,
+            unk19: a.unk19,
+            unk20: a.unk20,
+            unk21: a.unk21,
+            unk22: a.unk22,
         }
     }
 }
@@ -152,7 +273,38 @@ impl From<&AimConstraintData> for AimConstraint {
             up: a.up,
             quat1: a.quat1,
             quat2: a.quat2,
-            // TODO: Are these fields always 0?
+            unk17: a.unk17,
+            unk18: a.unk18,
+            unk19: a.unk19,
+            unk20: a.unk20,
+            unk21: a.unk21,
+            unk22: a.unk22,
+        }
+    }
+}
+
+impl From<AimConstraintV10> for AimConstraintData {
+    fn from(a: AimConstraintV10) -> Self {
+        Self::from(&a)
+    }
+}
+
+impl From<&AimConstraintV10> for AimConstraintData {
+    fn from(a: &AimConstraintV10) -> Self {
+        Self {
+            name: a.name.to_string_lossy(),
+            aim_bone_name1: a.aim_bone_name1.to_string_lossy(),
+            aim_bone_name2: a.aim_bone_name2.to_string_lossy(),
+            aim_type1: a.aim_type1.to_string_lossy(),
+            aim_type2: a.aim_type2.to_string_lossy(),
+            target_bone_name1: a.target_bone_name1.to_string_lossy(),
+            target_bone_name2: a.target_bone_name2.to_string_lossy(),
+            unk1: a.unk1,
+            unk2: a.unk2,
+            aim: a.aim,
+            up: a.up,
+            quat1: a.quat1,
+            quat2: a.quat2,
             unk17: 0.0,
             unk18: 0.0,
             unk19: 0.0,
@@ -163,6 +315,32 @@ impl From<&AimConstraintData> for AimConstraint {
     }
 }
 
+impl From<AimConstraintData> for AimConstraintV10 {
+    fn from(a: AimConstraintData) -> Self {
+        Self::from(&a)
+    }
+}
+
+impl From<&AimConstraintData> for AimConstraintV10 {
+    fn from(a: &AimConstraintData) -> Self {
+        Self {
+            name: a.name.as_str().into(),
+            aim_bone_name1: a.aim_bone_name1.as_str().into(),
+            aim_bone_name2: a.aim_bone_name2.as_str().into(),
+            aim_type1: a.aim_type1.as_str().into(),
+            aim_type2: a.aim_type2.as_str().into(),
+            target_bone_name1: a.target_bone_name1.as_str().into(),
+            target_bone_name2: a.target_bone_name2.as_str().into(),
+            unk1: a.unk1,
+            unk2: a.unk2,
+            aim: a.aim,
+            up: a.up,
+            quat1: a.quat1,
+            quat2: a.quat2,
+        }
+    }
+}
+
 impl From<OrientConstraint> for OrientConstraintData {
     fn from(o: OrientConstraint) -> Self {
         Self::from(&o)
@@ -215,6 +393,35 @@ impl From<&OrientConstraintData> for OrientConstraint {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_aim_constraint_without_unk_fields() {
+        // Older versions didn't have the unk17 to unk22 fields.
+        let aim: AimConstraintData = serde_json::from_str(
+            r#"{
+                "name": "aim1",
+                "aim_bone_name1": "root",
+                "aim_bone_name2": "root",
+                "aim_type1": "DEFAULT",
+                "aim_type2": "DEFAULT",
+                "target_bone_name1": "bone1",
+                "target_bone_name2": "bone2",
+                "unk1": 0,
+                "unk2": 1,
+                "aim": { "x": 1.0, "y": 0.0, "z": 0.0 },
+                "up": { "x": 0.0, "y": 1.0, "z": 0.0 },
+                "quat1": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 },
+                "quat2": { "x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0 }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            [0.0; 6],
+            [aim.unk17, aim.unk18, aim.unk19, aim.unk20, aim.unk21, aim.unk22]
+        );
+    }
+
     #[test]
     fn create_hlpb_hlpb_data() {
         // Test both conversion directions.
@@ -233,7 +440,7 @@ mod tests {
                 up: Vector3::new(0.0, 1.0, 0.0),
                 quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
                 quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
-                unk17: 0.0,
+                unk17: 0.5,
                 unk18: 0.0,
                 unk19: 0.0,
                 unk20: 0.0,
@@ -296,6 +503,12 @@ mod tests {
                 up: Vector3::new(0.0, 1.0, 0.0),
                 quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
                 quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                unk17: 0.5,
+                unk18: 0.0,
+                unk19: 0.0,
+                unk20: 0.0,
+                unk21: 0.0,
+                unk22: 0.0,
             }],
             orient_constraints: vec![
                 OrientConstraintData {
@@ -328,6 +541,64 @@ mod tests {
         };
 
         assert_eq!(data, HlpbData::from(&ssbh));
-        assert_eq!(ssbh, Hlpb::from(&data));
+        assert_eq!(ssbh, Hlpb::try_from(&data).unwrap());
+    }
+
+    #[test]
+    fn create_hlpb_hlpb_data_v10() {
+        let ssbh = Hlpb::V10 {
+            aim_constraints: vec![AimConstraintV10 {
+                name: "aim1".into(),
+                aim_bone_name1: "root".into(),
+                aim_bone_name2: "root".into(),
+                aim_type1: "DEFAULT".into(),
+                aim_type2: "DEFAULT".into(),
+                target_bone_name1: "a".into(),
+                target_bone_name2: "a".into(),
+                unk1: 0,
+                unk2: 1,
+                aim: Vector3::new(1.0, 0.0, 0.0),
+                up: Vector3::new(0.0, 1.0, 0.0),
+                quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            }]
+            .into(),
+            orient_constraints: SsbhArray::new(),
+            constraint_indices: vec![0].into(),
+            constraint_types: vec![ConstraintType::Aim].into(),
+        };
+
+        let data = HlpbData::from(&ssbh);
+        assert_eq!((1, 0), (data.major_version, data.minor_version));
+        assert_eq!(
+            [0.0; 6],
+            [
+                data.aim_constraints[0].unk17,
+                data.aim_constraints[0].unk18,
+                data.aim_constraints[0].unk19,
+                data.aim_constraints[0].unk20,
+                data.aim_constraints[0].unk21,
+                data.aim_constraints[0].unk22
+            ]
+        );
+        assert_eq!(ssbh, Hlpb::try_from(&data).unwrap());
+    }
+
+    #[test]
+    fn create_hlpb_unsupported_version() {
+        let result = Hlpb::try_from(&HlpbData {
+            major_version: 1,
+            minor_version: 2,
+            aim_constraints: Vec::new(),
+            orient_constraints: Vec::new(),
+        });
+
+        assert!(matches!(
+            result,
+            Err(error::Error::UnsupportedVersion {
+                major_version: 1,
+                minor_version: 2
+            })
+        ));
     }
 }
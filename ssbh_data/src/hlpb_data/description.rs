@@ -0,0 +1,528 @@
+//! Simplified descriptions of helper bone constraints.
+//!
+//! The fields in [AimConstraintData] and [OrientConstraintData] closely match the binary format
+//! and contain values that are almost always the same.
+//! The description types only contain the values that are meaningful when authoring constraints
+//! and compile down to valid data for a version 1.1 [Hlpb](ssbh_lib::formats::hlpb::Hlpb).
+//!
+//! Existing data can be converted back to a description with [HlpbDescription::try_from].
+//! The conversion fails if the data has values that the description would not preserve.
+/*!
+```rust
+use ssbh_data::hlpb_data::description::{AimConstraintDescription, Axis, HlpbDescription};
+use ssbh_data::hlpb_data::HlpbData;
+
+let description = HlpbDescription {
+    aim_constraints: vec![AimConstraintDescription {
+        aim_axis: Axis::NegativeZ,
+        ..AimConstraintDescription::new("nuHelperBoneRotateAim1", "H_EyeL", "EyeTargetL")
+    }],
+    orient_constraints: Vec::new(),
+};
+
+let data = HlpbData::from(&description);
+assert_eq!("H_EyeL", data.aim_constraints[0].aim_bone_name1);
+
+let new_description = HlpbDescription::try_from(&data)?;
+assert_eq!(description, new_description);
+# Ok::<(), ssbh_data::hlpb_data::error::Error>(())
+```
+ */
+use glam::{EulerRot, Quat};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use ssbh_lib::{Vector3, Vector4};
+
+use super::{error::Error, AimConstraintData, HlpbData, OrientConstraintData};
+
+/// A simplified description of all the constraints in a [HlpbData].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct HlpbDescription {
+    pub aim_constraints: Vec<AimConstraintDescription>,
+    pub orient_constraints: Vec<OrientConstraintDescription>,
+}
+
+/// A positive or negative coordinate axis in a bone's local space.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+    NegativeX,
+    NegativeY,
+    NegativeZ,
+}
+
+impl Axis {
+    /// The unit vector for this axis.
+    pub fn to_vector(self) -> Vector3 {
+        match self {
+            Axis::X => Vector3::new(1.0, 0.0, 0.0),
+            Axis::Y => Vector3::new(0.0, 1.0, 0.0),
+            Axis::Z => Vector3::new(0.0, 0.0, 1.0),
+            Axis::NegativeX => Vector3::new(-1.0, 0.0, 0.0),
+            Axis::NegativeY => Vector3::new(0.0, -1.0, 0.0),
+            Axis::NegativeZ => Vector3::new(0.0, 0.0, -1.0),
+        }
+    }
+
+    /// The axis with unit vector exactly equal to `v` or `None` if `v` is not a coordinate axis.
+    pub fn from_vector(v: Vector3) -> Option<Self> {
+        [
+            Axis::X,
+            Axis::Y,
+            Axis::Z,
+            Axis::NegativeX,
+            Axis::NegativeY,
+            Axis::NegativeZ,
+        ]
+        .into_iter()
+        .find(|a| a.to_vector() == v)
+    }
+}
+
+/// A simplified description of an [AimConstraintData].
+///
+/// Rotates the `source` bone so that its `aim_axis` points towards the `target` bone.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct AimConstraintDescription {
+    /// The name of the constraint like `"nuHelperBoneRotateAim1"`.
+    pub name: String,
+    /// The name of the constrained bone.
+    pub source: String,
+    /// The name of the bone to aim towards.
+    pub target: String,
+    /// The axis of `source` that points towards `target`.
+    #[cfg_attr(feature = "serde", serde(default = "default_aim_axis"))]
+    pub aim_axis: Axis,
+    /// The axis of `source` used to determine the twist around the aim axis.
+    #[cfg_attr(feature = "serde", serde(default = "default_up_axis"))]
+    pub up_axis: Axis,
+    /// Additional XYZ euler rotations in degrees stored as [quat1](super::AimConstraintData::quat1)
+    /// and [quat2](super::AimConstraintData::quat2).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rotations: [[f32; 3]; 2],
+}
+
+impl AimConstraintDescription {
+    /// Creates an aim constraint with the default aim axis X, up axis Y, and no additional rotations.
+    pub fn new(name: &str, source: &str, target: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            aim_axis: default_aim_axis(),
+            up_axis: default_up_axis(),
+            rotations: [[0.0; 3]; 2],
+        }
+    }
+}
+
+/// A simplified description of an [OrientConstraintData].
+///
+/// Interpolates the rotation of the `target` bone towards the rotation of the `source` bone.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct OrientConstraintDescription {
+    /// The name of the constraint like `"nuHelperBoneRotateInterp1"`.
+    pub name: String,
+    /// The name of the parent bone of `target`.
+    pub parent: String,
+    /// The name of the bone to interpolate rotation with.
+    pub source: String,
+    /// The name of the constrained bone.
+    pub target: String,
+    /// The interpolation factors for the XYZ rotation axes from `0.0` for `target` to `1.0` for `source`.
+    #[cfg_attr(feature = "serde", serde(default = "default_weights"))]
+    pub weights: [f32; 3],
+    /// See [unk_type](super::OrientConstraintData::unk_type).
+    #[cfg_attr(feature = "serde", serde(default = "default_orient_type"))]
+    pub unk_type: u32,
+}
+
+impl OrientConstraintDescription {
+    /// Creates an orient constraint with equal weights of `0.5` for each axis.
+    pub fn new(name: &str, parent: &str, source: &str, target: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            parent: parent.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            weights: default_weights(),
+            unk_type: default_orient_type(),
+        }
+    }
+}
+
+fn default_aim_axis() -> Axis {
+    Axis::X
+}
+
+fn default_up_axis() -> Axis {
+    Axis::Y
+}
+
+fn default_weights() -> [f32; 3] {
+    [0.5; 3]
+}
+
+fn default_orient_type() -> u32 {
+    1
+}
+
+fn quaternion([x, y, z]: [f32; 3]) -> Vector4 {
+    let q = Quat::from_euler(
+        EulerRot::XYZ,
+        x.to_radians(),
+        y.to_radians(),
+        z.to_radians(),
+    );
+    Vector4::new(q.x, q.y, q.z, q.w)
+}
+
+impl From<&HlpbDescription> for HlpbData {
+    fn from(d: &HlpbDescription) -> Self {
+        Self {
+            major_version: 1,
+            minor_version: 1,
+            aim_constraints: d.aim_constraints.iter().map(Into::into).collect(),
+            orient_constraints: d.orient_constraints.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<HlpbDescription> for HlpbData {
+    fn from(d: HlpbDescription) -> Self {
+        Self::from(&d)
+    }
+}
+
+impl From<&AimConstraintDescription> for AimConstraintData {
+    fn from(a: &AimConstraintDescription) -> Self {
+        Self {
+            name: a.name.clone(),
+            aim_bone_name1: a.source.clone(),
+            aim_bone_name2: a.source.clone(),
+            aim_type1: "DEFAULT".to_string(),
+            aim_type2: "DEFAULT".to_string(),
+            target_bone_name1: a.target.clone(),
+            target_bone_name2: a.target.clone(),
+            unk1: 0,
+            unk2: 1,
+            aim: a.aim_axis.to_vector(),
+            up: a.up_axis.to_vector(),
+            quat1: quaternion(a.rotations[0]),
+            quat2: quaternion(a.rotations[1]),
+            unk17: 0.0,
+            unk18: 0.0,
+            unk19: 0.0,
+            unk20: 0.0,
+            unk21: 0.0,
+            unk22: 0.0,
+        }
+    }
+}
+
+impl From<AimConstraintDescription> for AimConstraintData {
+    fn from(a: AimConstraintDescription) -> Self {
+        Self::from(&a)
+    }
+}
+
+impl From<&OrientConstraintDescription> for OrientConstraintData {
+    fn from(o: &OrientConstraintDescription) -> Self {
+        Self {
+            name: o.name.clone(),
+            parent_bone_name1: o.parent.clone(),
+            parent_bone_name2: o.parent.clone(),
+            source_bone_name: o.source.clone(),
+            target_bone_name: o.target.clone(),
+            unk_type: o.unk_type,
+            constraint_axes: Vector3::new(o.weights[0], o.weights[1], o.weights[2]),
+            quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            range_min: Vector3::new(-180.0, -180.0, -180.0),
+            range_max: Vector3::new(180.0, 180.0, 180.0),
+        }
+    }
+}
+
+impl From<OrientConstraintDescription> for OrientConstraintData {
+    fn from(o: OrientConstraintDescription) -> Self {
+        Self::from(&o)
+    }
+}
+
+fn euler_degrees(q: Vector4) -> [f32; 3] {
+    let (x, y, z) = Quat::from_xyzw(q.x, q.y, q.z, q.w).to_euler(EulerRot::XYZ);
+    [x.to_degrees(), y.to_degrees(), z.to_degrees()]
+}
+
+fn check<T: PartialEq>(
+    constraint_name: &str,
+    field: &'static str,
+    value: T,
+    expected: T,
+) -> Result<(), Error> {
+    if value == expected {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedDescriptionValue {
+            constraint_name: constraint_name.to_string(),
+            field,
+        })
+    }
+}
+
+fn axis(constraint_name: &str, field: &'static str, v: Vector3) -> Result<Axis, Error> {
+    Axis::from_vector(v).ok_or_else(|| Error::UnsupportedDescriptionValue {
+        constraint_name: constraint_name.to_string(),
+        field,
+    })
+}
+
+impl TryFrom<&HlpbData> for HlpbDescription {
+    type Error = Error;
+
+    fn try_from(data: &HlpbData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            aim_constraints: data
+                .aim_constraints
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            orient_constraints: data
+                .orient_constraints
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<HlpbData> for HlpbDescription {
+    type Error = Error;
+
+    fn try_from(data: HlpbData) -> Result<Self, Self::Error> {
+        Self::try_from(&data)
+    }
+}
+
+impl TryFrom<&AimConstraintData> for AimConstraintDescription {
+    type Error = Error;
+
+    fn try_from(a: &AimConstraintData) -> Result<Self, Self::Error> {
+        let name = &a.name;
+        check(name, "aim_bone_name2", &a.aim_bone_name2, &a.aim_bone_name1)?;
+        check(
+            name,
+            "target_bone_name2",
+            &a.target_bone_name2,
+            &a.target_bone_name1,
+        )?;
+        check(name, "aim_type1", a.aim_type1.as_str(), "DEFAULT")?;
+        check(name, "aim_type2", a.aim_type2.as_str(), "DEFAULT")?;
+        check(name, "unk1", a.unk1, 0)?;
+        check(name, "unk2", a.unk2, 1)?;
+        check(
+            name,
+            "unk17 to unk22",
+            [a.unk17, a.unk18, a.unk19, a.unk20, a.unk21, a.unk22],
+            [0.0; 6],
+        )?;
+
+        Ok(Self {
+            name: a.name.clone(),
+            source: a.aim_bone_name1.clone(),
+            target: a.target_bone_name1.clone(),
+            aim_axis: axis(name, "aim", a.aim)?,
+            up_axis: axis(name, "up", a.up)?,
+            rotations: [euler_degrees(a.quat1), euler_degrees(a.quat2)],
+        })
+    }
+}
+
+impl TryFrom<&OrientConstraintData> for OrientConstraintDescription {
+    type Error = Error;
+
+    fn try_from(o: &OrientConstraintData) -> Result<Self, Self::Error> {
+        let name = &o.name;
+        check(
+            name,
+            "parent_bone_name2",
+            &o.parent_bone_name2,
+            &o.parent_bone_name1,
+        )?;
+        check(name, "quat1", o.quat1, Vector4::new(0.0, 0.0, 0.0, 1.0))?;
+        check(name, "quat2", o.quat2, Vector4::new(0.0, 0.0, 0.0, 1.0))?;
+        check(
+            name,
+            "range_min",
+            o.range_min,
+            Vector3::new(-180.0, -180.0, -180.0),
+        )?;
+        check(
+            name,
+            "range_max",
+            o.range_max,
+            Vector3::new(180.0, 180.0, 180.0),
+        )?;
+
+        Ok(Self {
+            name: o.name.clone(),
+            parent: o.parent_bone_name1.clone(),
+            source: o.source_bone_name.clone(),
+            target: o.target_bone_name.clone(),
+            weights: o.constraint_axes.to_array(),
+            unk_type: o.unk_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn aim_constraint_data() {
+        let data = AimConstraintData::from(AimConstraintDescription {
+            up_axis: Axis::NegativeY,
+            rotations: [[0.0, 0.0, 0.0], [0.0, 0.0, 90.0]],
+            ..AimConstraintDescription::new("aim1", "H_EyeL", "EyeL")
+        });
+
+        assert_eq!("H_EyeL", data.aim_bone_name1);
+        assert_eq!("H_EyeL", data.aim_bone_name2);
+        assert_eq!("EyeL", data.target_bone_name1);
+        assert_eq!("EyeL", data.target_bone_name2);
+        assert_eq!("DEFAULT", data.aim_type1);
+        assert_eq!(Vector3::new(1.0, 0.0, 0.0), data.aim);
+        assert_eq!(Vector3::new(0.0, -1.0, 0.0), data.up);
+        assert_eq!(Vector4::new(0.0, 0.0, 0.0, 1.0), data.quat1);
+        assert_relative_eq!(0.0, data.quat2.x);
+        assert_relative_eq!(0.0, data.quat2.y);
+        assert_relative_eq!(0.5f32.sqrt(), data.quat2.z);
+        assert_relative_eq!(0.5f32.sqrt(), data.quat2.w);
+    }
+
+    #[test]
+    fn orient_constraint_data() {
+        let data = OrientConstraintData::from(OrientConstraintDescription::new(
+            "orient1", "ArmL", "HandL", "H_WristL",
+        ));
+
+        assert_eq!(
+            OrientConstraintData {
+                name: "orient1".to_string(),
+                parent_bone_name1: "ArmL".to_string(),
+                parent_bone_name2: "ArmL".to_string(),
+                source_bone_name: "HandL".to_string(),
+                target_bone_name: "H_WristL".to_string(),
+                unk_type: 1,
+                constraint_axes: Vector3::new(0.5, 0.5, 0.5),
+                quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                range_min: Vector3::new(-180.0, -180.0, -180.0),
+                range_max: Vector3::new(180.0, 180.0, 180.0),
+            },
+            data
+        );
+    }
+
+    #[test]
+    fn aim_constraint_description() {
+        let description = AimConstraintDescription {
+            aim_axis: Axis::NegativeZ,
+            up_axis: Axis::X,
+            rotations: [[0.0, 0.0, 0.0], [0.0, 0.0, 90.0]],
+            ..AimConstraintDescription::new("aim1", "H_EyeL", "EyeL")
+        };
+
+        let new_description =
+            AimConstraintDescription::try_from(&AimConstraintData::from(&description)).unwrap();
+        assert_eq!(description.aim_axis, new_description.aim_axis);
+        assert_eq!(description.up_axis, new_description.up_axis);
+        assert_eq!(description.rotations[0], new_description.rotations[0]);
+        assert_relative_eq!(90.0, new_description.rotations[1][2], epsilon = 0.0001);
+    }
+
+    #[test]
+    fn aim_constraint_description_unsupported() {
+        let mut data = AimConstraintData::from(AimConstraintDescription::new("aim1", "a", "b"));
+        data.target_bone_name2 = "c".to_string();
+        assert!(matches!(
+            AimConstraintDescription::try_from(&data),
+            Err(Error::UnsupportedDescriptionValue {
+                constraint_name,
+                field: "target_bone_name2"
+            }) if constraint_name == "aim1"
+        ));
+
+        let mut data = AimConstraintData::from(AimConstraintDescription::new("aim1", "a", "b"));
+        data.aim = Vector3::new(1.0, 1.0, 0.0);
+        assert!(matches!(
+            AimConstraintDescription::try_from(&data),
+            Err(Error::UnsupportedDescriptionValue { field: "aim", .. })
+        ));
+    }
+
+    #[test]
+    fn orient_constraint_description() {
+        let description = OrientConstraintDescription {
+            weights: [0.25, 0.5, 1.0],
+            ..OrientConstraintDescription::new("orient1", "ArmL", "HandL", "H_WristL")
+        };
+        assert_eq!(
+            description,
+            OrientConstraintDescription::try_from(&OrientConstraintData::from(&description))
+                .unwrap()
+        );
+
+        let mut data = OrientConstraintData::from(&description);
+        data.range_max = Vector3::new(90.0, 180.0, 180.0);
+        assert!(matches!(
+            OrientConstraintDescription::try_from(&data),
+            Err(Error::UnsupportedDescriptionValue {
+                field: "range_max",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn axis_from_vector() {
+        assert_eq!(
+            Some(Axis::NegativeZ),
+            Axis::from_vector(Vector3::new(0.0, 0.0, -1.0))
+        );
+        assert_eq!(None, Axis::from_vector(Vector3::new(1.0, 1.0, 0.0)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_description_defaults() {
+        let description: HlpbDescription = serde_json::from_str(
+            r#"{
+                "aim_constraints": [{ "name": "aim1", "source": "a", "target": "b" }],
+                "orient_constraints": [
+                    { "name": "orient1", "parent": "c", "source": "d", "target": "e" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            AimConstraintDescription::new("aim1", "a", "b"),
+            description.aim_constraints[0]
+        );
+        assert_eq!(
+            OrientConstraintDescription::new("orient1", "c", "d", "e"),
+            description.orient_constraints[0]
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeSet;
+
+use super::{error, AimConstraintData, HlpbData, OrientConstraintData};
+
+// The bones read and written by a single constraint.
+struct Dependency<'a> {
+    name: &'a str,
+    constrained: &'a str,
+    inputs: [&'a str; 2],
+}
+
+impl<'a> From<&'a AimConstraintData> for Dependency<'a> {
+    fn from(c: &'a AimConstraintData) -> Self {
+        Self {
+            name: &c.name,
+            constrained: &c.aim_bone_name1,
+            inputs: [&c.target_bone_name1, &c.target_bone_name2],
+        }
+    }
+}
+
+impl<'a> From<&'a OrientConstraintData> for Dependency<'a> {
+    fn from(c: &'a OrientConstraintData) -> Self {
+        Self {
+            name: &c.name,
+            constrained: &c.target_bone_name,
+            inputs: [&c.source_bone_name, &c.parent_bone_name1],
+        }
+    }
+}
+
+impl HlpbData {
+    /// Reorders the constraints so that a constraint that reads a bone is evaluated
+    /// after any constraint that modifies that bone.
+    /// Constraints without dependencies keep their original relative order.
+    ///
+    /// Aim constraints are always evaluated before orient constraints in the converted [Hlpb](ssbh_lib::formats::hlpb::Hlpb),
+    /// so an aim constraint can't depend on an orient constraint.
+    /// Dependencies only consider bone names directly referenced by constraints and not the skeleton hierarchy.
+    /// Returns an error without modifying the constraints if no valid order exists.
+    /**
+    ```rust
+    use ssbh_data::hlpb_data::description::{OrientConstraintDescription, HlpbDescription};
+    use ssbh_data::hlpb_data::HlpbData;
+
+    let mut data = HlpbData::from(&HlpbDescription {
+        aim_constraints: Vec::new(),
+        orient_constraints: vec![
+            // H_ElbowL is modified by the second constraint.
+            OrientConstraintDescription::new("nuHelperBoneRotateInterp2", "ArmL", "H_ElbowL", "H_ArmL"),
+            OrientConstraintDescription::new("nuHelperBoneRotateInterp1", "ArmL", "HandL", "H_ElbowL"),
+        ],
+    });
+
+    data.sort_constraints()?;
+    assert_eq!("nuHelperBoneRotateInterp1", data.orient_constraints[0].name);
+    assert_eq!("nuHelperBoneRotateInterp2", data.orient_constraints[1].name);
+    # Ok::<(), ssbh_data::hlpb_data::error::Error>(())
+    ```
+     */
+    pub fn sort_constraints(&mut self) -> Result<(), error::Error> {
+        let aim_count = self.aim_constraints.len();
+        let order = {
+            let dependencies: Vec<Dependency> = self
+                .aim_constraints
+                .iter()
+                .map(Dependency::from)
+                .chain(self.orient_constraints.iter().map(Dependency::from))
+                .collect();
+            constraint_order(&dependencies, aim_count)?
+        };
+
+        let mut aim_constraints: Vec<_> = self.aim_constraints.drain(..).map(Some).collect();
+        let mut orient_constraints: Vec<_> = self.orient_constraints.drain(..).map(Some).collect();
+        for i in order {
+            if i < aim_count {
+                self.aim_constraints.extend(aim_constraints[i].take());
+            } else {
+                self.orient_constraints
+                    .extend(orient_constraints[i - aim_count].take());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn constraint_order(
+    dependencies: &[Dependency],
+    aim_count: usize,
+) -> Result<Vec<usize>, error::Error> {
+    // Find the constraints that must be evaluated after each constraint.
+    let mut dependents = vec![Vec::new(); dependencies.len()];
+    let mut dependency_counts = vec![0usize; dependencies.len()];
+    for (i, a) in dependencies.iter().enumerate() {
+        for (j, b) in dependencies.iter().enumerate() {
+            if i != j && b.inputs.contains(&a.constrained) {
+                if j < aim_count && i >= aim_count {
+                    return Err(error::Error::AimDependsOnOrient {
+                        aim_constraint_name: b.name.to_string(),
+                        orient_constraint_name: a.name.to_string(),
+                    });
+                }
+                dependents[i].push(j);
+                dependency_counts[j] += 1;
+            }
+        }
+    }
+
+    // Always pick the earliest available constraint to preserve the original order when possible.
+    let mut available: BTreeSet<_> = (0..dependencies.len())
+        .filter(|i| dependency_counts[*i] == 0)
+        .collect();
+    let mut order = Vec::new();
+    while let Some(i) = available.pop_first() {
+        order.push(i);
+        for j in &dependents[i] {
+            dependency_counts[*j] -= 1;
+            if dependency_counts[*j] == 0 {
+                available.insert(*j);
+            }
+        }
+    }
+
+    if order.len() < dependencies.len() {
+        // The remaining constraints are part of a cycle or depend on a cycle.
+        Err(error::Error::ConstraintCycle {
+            constraint_names: dependency_counts
+                .iter()
+                .enumerate()
+                .filter(|(_, count)| **count > 0)
+                .map(|(i, _)| dependencies[i].name.to_string())
+                .collect(),
+        })
+    } else {
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hlpb_data::description::{
+        AimConstraintDescription, HlpbDescription, OrientConstraintDescription,
+    };
+
+    fn names(data: &HlpbData) -> (Vec<&str>, Vec<&str>) {
+        (
+            data.aim_constraints
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect(),
+            data.orient_constraints
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn sort_constraints_no_dependencies() {
+        let mut data = HlpbData::from(&HlpbDescription {
+            aim_constraints: vec![
+                AimConstraintDescription::new("aim2", "a", "b"),
+                AimConstraintDescription::new("aim1", "c", "d"),
+            ],
+            orient_constraints: vec![OrientConstraintDescription::new("orient1", "p", "a", "e")],
+        });
+        data.sort_constraints().unwrap();
+        assert_eq!((vec!["aim2", "aim1"], vec!["orient1"]), names(&data));
+    }
+
+    #[test]
+    fn sort_constraints_chain() {
+        let mut data = HlpbData::from(&HlpbDescription {
+            aim_constraints: vec![
+                AimConstraintDescription::new("aim3", "c", "b"),
+                AimConstraintDescription::new("aim2", "b", "a"),
+                AimConstraintDescription::new("aim1", "a", "target"),
+                AimConstraintDescription::new("aim0", "x", "y"),
+            ],
+            orient_constraints: vec![
+                OrientConstraintDescription::new("orient2", "p", "d", "e"),
+                OrientConstraintDescription::new("orient1", "p", "c", "d"),
+            ],
+        });
+        data.sort_constraints().unwrap();
+        assert_eq!(
+            (
+                vec!["aim1", "aim2", "aim3", "aim0"],
+                vec!["orient1", "orient2"]
+            ),
+            names(&data)
+        );
+    }
+
+    #[test]
+    fn sort_constraints_cycle() {
+        let mut data = HlpbData::from(&HlpbDescription {
+            aim_constraints: vec![
+                AimConstraintDescription::new("aim0", "x", "y"),
+                AimConstraintDescription::new("aim1", "a", "b"),
+                AimConstraintDescription::new("aim2", "b", "a"),
+            ],
+            orient_constraints: Vec::new(),
+        });
+        let original = data.clone();
+
+        let result = data.sort_constraints();
+        assert!(matches!(
+            result,
+            Err(error::Error::ConstraintCycle { constraint_names })
+                if constraint_names == vec!["aim1".to_string(), "aim2".to_string()]
+        ));
+        assert_eq!(original, data);
+    }
+
+    #[test]
+    fn sort_constraints_aim_depends_on_orient() {
+        let mut data = HlpbData::from(&HlpbDescription {
+            aim_constraints: vec![AimConstraintDescription::new("aim1", "a", "b")],
+            orient_constraints: vec![OrientConstraintDescription::new("orient1", "p", "c", "b")],
+        });
+        let result = data.sort_constraints();
+        assert!(matches!(
+            result,
+            Err(error::Error::AimDependsOnOrient {
+                aim_constraint_name,
+                orient_constraint_name
+            }) if aim_constraint_name == "aim1" && orient_constraint_name == "orient1"
+        ));
+    }
+}
@@ -43,17 +43,60 @@ data.write_to_file("model_new.numshb")?;
 //! These differences are minor in practice but may cause issues for some applications.
 //! Applications needing a stronger guarantee that all data will be preserved
 //! should use [ssbh_lib](https://crates.io/crates/ssbh_lib).
+//!
+//! ## Ordering
+//! Conversions preserve the order of items in the file in both directions.
+//! This includes mesh objects, material and model entries, skeleton bones, and animation groups, nodes, and tracks.
+//! Material parameters are grouped by type, so only the relative order of parameters with the same type is preserved.
+//! Types like [MeshData](crate::mesh_data::MeshData) provide explicit `sort_*` methods for applications that need a consistent order.
+//!
+//! ## Mixing with ssbh_lib
+//! Each type provides `from_ssbh` and `to_ssbh` for converting to and from the corresponding ssbh_lib type.
+//! This allows editing some fields with ssbh_lib without writing and reading the file again.
+/*!
+```rust
+use ssbh_data::prelude::*;
+use ssbh_lib::formats::mesh::Mesh;
+
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+let data = MeshData {
+    major_version: 1,
+    minor_version: 10,
+    objects: Vec::new(),
+};
+
+let mesh: Mesh = data.to_ssbh()?;
+assert!(matches!(mesh, Mesh::V10(_)));
+
+let data = MeshData::from_ssbh(&mesh)?;
+assert_eq!((1, 10), (data.major_version, data.minor_version));
+# Ok(())
+# }
+```
+ */
 pub mod adj_data;
 pub mod anim_data;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod context;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
 pub mod hlpb_data;
 pub mod matl_data;
 pub mod mesh_data;
 pub mod meshex_data;
+pub mod model_folder;
 pub mod modl_data;
+pub mod nufx_data;
 pub mod shdr_data;
 pub mod skel_data;
+// Some helpers are only used by tests for optional features.
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_fixtures;
 
 use binrw::io::{Read, Seek, Write};
+use context::{ErrorContext, ResultExt};
 use ssbh_lib::prelude::*;
 use std::convert::TryFrom;
 use std::error::Error;
@@ -68,6 +111,11 @@ pub trait SsbhData: Sized {
 
     /// Tries to read and convert the data from `reader`.
     /// The entire file is buffered for performance.
+    ///
+    /// Errors are wrapped in a [ContextError](context::ContextError) with the path of the file.
+    /// This is a breaking change from previous versions that returned the original error directly.
+    /// Use [ContextError::downcast_root_cause](context::ContextError::downcast_root_cause)
+    /// or [root_cause](context::root_cause) to access the original error type.
     fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>>;
 
     /// Tries to read and convert the data from `reader`.
@@ -81,6 +129,21 @@ pub trait SsbhData: Sized {
     /// Converts the data and writes to the given `path`.
     /// The entire file is buffered for performance.
     fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Self::WriteError>;
+
+    /// Converts the data and writes to a `writer` that does not implement [Seek]
+    /// like a network socket or compression stream.
+    ///
+    /// This is not a streaming write.
+    /// The entire file is buffered in memory and then passed to `writer` all at once.
+    fn write_to_stream<W: Write>(&self, writer: &mut W) -> Result<(), Self::WriteError>
+    where
+        Self::WriteError: From<std::io::Error>,
+    {
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        self.write(&mut buffer)?;
+        writer.write_all(buffer.get_ref())?;
+        Ok(())
+    }
 }
 
 /// Common imports for supported types and important traits.
@@ -92,6 +155,7 @@ pub mod prelude {
     pub use crate::mesh_data::MeshData;
     pub use crate::meshex_data::MeshExData;
     pub use crate::modl_data::ModlData;
+    pub use crate::nufx_data::NufxData;
     pub use crate::shdr_data::ShdrData;
     pub use crate::skel_data::SkelData;
     pub use crate::SsbhData;
@@ -105,7 +169,11 @@ macro_rules! ssbh_data_impl {
             fn from_file<P: AsRef<std::path::Path>>(
                 path: P,
             ) -> Result<Self, Box<dyn std::error::Error>> {
-                <$ssbh_lib>::from_file(path)?.try_into().map_err(Into::into)
+                let path = path.as_ref();
+                let read = || -> Result<Self, Box<dyn std::error::Error>> {
+                    <$ssbh_lib>::from_file(path)?.try_into().map_err(Into::into)
+                };
+                read().context(|| ErrorContext::File(path.to_owned()))
             }
 
             fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, Box<dyn std::error::Error>> {
@@ -158,6 +226,18 @@ macro_rules! ssbh_data_impl {
             ) -> Result<(), <Self as SsbhData>::WriteError> {
                 <Self as SsbhData>::write_to_file(self, path)
             }
+
+            #[doc = concat!("Tries to convert from the ssbh_lib [", stringify!($ssbh_lib), "] type.")]
+            /// This is the same conversion used when reading files.
+            pub fn from_ssbh(data: &$ssbh_lib) -> Result<Self, Box<dyn std::error::Error>> {
+                Self::try_from(data).map_err(Into::into)
+            }
+
+            #[doc = concat!("Tries to convert to the ssbh_lib [", stringify!($ssbh_lib), "] type.")]
+            /// This is the same conversion used when writing files.
+            pub fn to_ssbh(&self) -> Result<$ssbh_lib, <Self as SsbhData>::WriteError> {
+                <$ssbh_lib>::try_from(self).map_err(Into::into)
+            }
         }
     };
 }
@@ -170,7 +250,10 @@ macro_rules! ssbh_data_infallible_impl {
             fn from_file<P: AsRef<std::path::Path>>(
                 path: P,
             ) -> Result<Self, Box<dyn std::error::Error>> {
-                Ok(<$ssbh_lib>::from_file(path)?.into())
+                let path = path.as_ref();
+                <$ssbh_lib>::from_file(path)
+                    .map(Into::into)
+                    .context(|| ErrorContext::File(path.to_owned()))
             }
 
             fn read<R: std::io::Read + std::io::Seek>(
@@ -224,6 +307,18 @@ macro_rules! ssbh_data_infallible_impl {
             ) -> Result<(), <Self as SsbhData>::WriteError> {
                 <Self as SsbhData>::write_to_file(self, path)
             }
+
+            #[doc = concat!("Converts from the ssbh_lib [", stringify!($ssbh_lib), "] type.")]
+            /// This is the same conversion used when reading files.
+            pub fn from_ssbh(data: &$ssbh_lib) -> Self {
+                data.into()
+            }
+
+            #[doc = concat!("Converts to the ssbh_lib [", stringify!($ssbh_lib), "] type.")]
+            /// This is the same conversion used when writing files.
+            pub fn to_ssbh(&self) -> $ssbh_lib {
+                self.into()
+            }
         }
     };
 }
@@ -234,9 +329,10 @@ ssbh_data_impl!(matl_data::MatlData, Matl, matl_data::error::Error);
 ssbh_data_impl!(mesh_data::MeshData, Mesh, mesh_data::error::Error);
 ssbh_data_infallible_impl!(meshex_data::MeshExData, MeshEx, std::io::Error);
 ssbh_data_infallible_impl!(modl_data::ModlData, Modl, std::io::Error);
-ssbh_data_infallible_impl!(hlpb_data::HlpbData, Hlpb, std::io::Error);
+ssbh_data_impl!(hlpb_data::HlpbData, Hlpb, hlpb_data::error::Error);
 ssbh_data_impl!(skel_data::SkelData, Skel, skel_data::error::Error);
-// TODO: ShdrData.
+ssbh_data_infallible_impl!(shdr_data::ShdrData, Shdr, std::io::Error);
+ssbh_data_infallible_impl!(nufx_data::NufxData, Nufx, std::io::Error);
 
 #[cfg(test)]
 pub(crate) fn group_hex(a: &str, words_per_line: usize) -> String {
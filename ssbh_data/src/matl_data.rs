@@ -42,6 +42,18 @@ use ssbh_lib::{
 };
 use std::{convert::TryFrom, ops::Deref};
 
+pub mod animation;
+pub mod anisotropy;
+pub mod material_labels;
+pub(crate) mod param_families;
+pub mod shader_label;
+pub mod shader_params;
+#[cfg(feature = "matl_text")]
+pub mod text;
+pub mod texture_memory;
+pub mod texture_names;
+pub mod texture_set;
+
 pub type BlendStateParam = ParamData<BlendStateData>;
 pub type FloatParam = ParamData<f32>;
 pub type BooleanParam = ParamData<bool>;
@@ -180,12 +192,7 @@ impl Default for SamplerData {
             wrapr: WrapMode::ClampToEdge,
             min_filter: MinFilter::LinearMipmapLinear,
             mag_filter: MagFilter::Linear,
-            border_color: Color4f {
-                r: 0.0,
-                g: 0.0,
-                b: 0.0,
-                a: 0.0,
-            },
+            border_color: Color4f::default(),
             lod_bias: 0.0,
             max_anisotropy: MaxAnisotropy::default(),
         }
@@ -468,6 +475,91 @@ macro_rules! get_attributes {
     };
 }
 
+impl MatlData {
+    /// Sorts the [entries](#structfield.entries) by material label.
+    pub fn sort_entries(&mut self) {
+        self.entries
+            .sort_by(|a, b| a.material_label.cmp(&b.material_label));
+    }
+
+    /// Sorts the [entries](#structfield.entries) by the first entry in `modl` that references each material label.
+    /// Entries not referenced by `modl` are moved to the end.
+    /// Entries with the same position keep their original relative order.
+    /**
+    ```rust
+    # use ssbh_data::matl_data::{MatlData, MatlEntryData};
+    # use ssbh_data::modl_data::{ModlData, ModlEntryData};
+    # fn entry(label: &str) -> MatlEntryData {
+    #     MatlEntryData {
+    #         material_label: label.into(),
+    #         shader_label: String::new(),
+    #         blend_states: Vec::new(),
+    #         floats: Vec::new(),
+    #         booleans: Vec::new(),
+    #         vectors: Vec::new(),
+    #         rasterizer_states: Vec::new(),
+    #         samplers: Vec::new(),
+    #         textures: Vec::new(),
+    #         uv_transforms: Vec::new(),
+    #     }
+    # }
+    # fn modl_entry(name: &str, material_label: &str) -> ModlEntryData {
+    #     ModlEntryData {
+    #         mesh_object_name: name.into(),
+    #         mesh_object_subindex: 0,
+    #         material_label: material_label.into(),
+    #     }
+    # }
+    let mut matl = MatlData {
+        major_version: 1,
+        minor_version: 6,
+        entries: vec![entry("unused"), entry("eye"), entry("body")],
+    };
+    let modl = ModlData {
+        major_version: 1,
+        minor_version: 7,
+        model_name: "model".into(),
+        skeleton_file_name: "model.nusktb".into(),
+        material_file_names: vec!["model.numatb".into()],
+        animation_file_name: None,
+        mesh_file_name: "model.numshb".into(),
+        entries: vec![modl_entry("body", "body"), modl_entry("eye", "eye")],
+    };
+
+    matl.sort_entries_by_modl(&modl);
+    let labels: Vec<_> = matl.entries.iter().map(|e| e.material_label.as_str()).collect();
+    assert_eq!(vec!["body", "eye", "unused"], labels);
+    ```
+     */
+    pub fn sort_entries_by_modl(&mut self, modl: &crate::modl_data::ModlData) {
+        let mut positions = std::collections::HashMap::new();
+        for (i, entry) in modl.entries.iter().enumerate() {
+            positions.entry(entry.material_label.as_str()).or_insert(i);
+        }
+
+        self.entries.sort_by_key(|e| {
+            positions
+                .get(e.material_label.as_str())
+                .copied()
+                .unwrap_or(usize::MAX)
+        });
+    }
+}
+
+impl MatlEntryData {
+    /// Sorts the parameters for each type by the value of their [ParamId].
+    pub fn sort_params(&mut self) {
+        self.blend_states.sort_by_key(|p| p.param_id as u64);
+        self.floats.sort_by_key(|p| p.param_id as u64);
+        self.booleans.sort_by_key(|p| p.param_id as u64);
+        self.vectors.sort_by_key(|p| p.param_id as u64);
+        self.rasterizer_states.sort_by_key(|p| p.param_id as u64);
+        self.samplers.sort_by_key(|p| p.param_id as u64);
+        self.textures.sort_by_key(|p| p.param_id as u64);
+        self.uv_transforms.sort_by_key(|p| p.param_id as u64);
+    }
+}
+
 impl TryFrom<Matl> for MatlData {
     type Error = error::Error;
 
@@ -866,6 +958,8 @@ impl ToParamV15 for RasterizerStateData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::modl_data::{ModlData, ModlEntryData};
+    use crate::test_fixtures::{self, matl_entry_data};
 
     use ssbh_lib::{
         formats::matl::{AttributeV16, MatlEntryV16, UvTransform},
@@ -1577,4 +1671,97 @@ mod tests {
             );
         }
     }
+
+    fn entry_with_label(material_label: &str) -> MatlEntryData {
+        MatlEntryData {
+            floats: vec![
+                ParamData::new(ParamId::CustomFloat8, 1.0),
+                ParamData::new(ParamId::CustomFloat0, 2.0),
+            ],
+            vectors: vec![
+                ParamData::new(ParamId::CustomVector8, Vector4::new(1.0, 1.0, 1.0, 1.0)),
+                ParamData::new(ParamId::CustomVector0, Vector4::new(0.0, 0.0, 0.0, 0.0)),
+            ],
+            ..matl_entry_data(material_label, "SFX_PBS_0100000008008269_opaque")
+        }
+    }
+
+    #[test]
+    fn convert_matl_preserves_order() {
+        let data = test_fixtures::matl_data(vec![entry_with_label("b"), entry_with_label("a")]);
+
+        assert_eq!(
+            data,
+            MatlData::try_from(Matl::try_from(&data).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn sort_matl_entries_and_params() {
+        let mut data = test_fixtures::matl_data(vec![entry_with_label("b"), entry_with_label("a")]);
+        data.sort_entries();
+        data.entries[0].sort_params();
+
+        assert_eq!("a", data.entries[0].material_label);
+        assert_eq!("b", data.entries[1].material_label);
+        assert_eq!(
+            vec![ParamId::CustomFloat0, ParamId::CustomFloat8],
+            data.entries[0]
+                .floats
+                .iter()
+                .map(|p| p.param_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![ParamId::CustomVector0, ParamId::CustomVector8],
+            data.entries[0]
+                .vectors
+                .iter()
+                .map(|p| p.param_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn sort_matl_entries_by_modl() {
+        let modl_entry = |name: &str, material_label: &str| ModlEntryData {
+            mesh_object_name: name.to_string(),
+            mesh_object_subindex: 0,
+            material_label: material_label.to_string(),
+        };
+        let modl = ModlData {
+            major_version: 1,
+            minor_version: 7,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: Vec::new(),
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries: vec![
+                modl_entry("hair", "c"),
+                modl_entry("body", "a"),
+                modl_entry("hat", "c"),
+            ],
+        };
+
+        let mut data = MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: vec![
+                entry_with_label("unused2"),
+                entry_with_label("a"),
+                entry_with_label("unused1"),
+                entry_with_label("c"),
+            ],
+        };
+        data.sort_entries_by_modl(&modl);
+
+        assert_eq!(
+            vec!["c", "a", "unused2", "unused1"],
+            data.entries
+                .iter()
+                .map(|e| e.material_label.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
 }
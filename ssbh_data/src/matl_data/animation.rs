@@ -0,0 +1,244 @@
+//! Evaluating material animations from [AnimData] for a [MatlData].
+//!
+//! Each node in a [GroupType::Material] group animates the material with the same label.
+//! Tracks are named after the parameter like `"CustomVector6"`.
+//! Renderers and exporters can apply the same frame to get identical material values.
+/*!
+```rust
+use ssbh_data::anim_data::{AnimData, TrackValues};
+use ssbh_data::matl_data::*;
+
+let matl = MatlData {
+    major_version: 1,
+    minor_version: 6,
+    entries: vec![MatlEntryData {
+        material_label: "eye".to_string(),
+        shader_label: String::new(),
+        blend_states: Vec::new(),
+        floats: vec![FloatParam::new(ParamId::CustomFloat8, 0.0)],
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: Vec::new(),
+        textures: Vec::new(),
+        uv_transforms: Vec::new(),
+    }],
+};
+
+let mut anim = AnimData {
+    major_version: 2,
+    minor_version: 0,
+    final_frame_index: 2.0,
+    groups: Vec::new(),
+};
+anim.insert_material_track("eye", ParamId::CustomFloat8, TrackValues::Float(vec![0.0, 0.5, 1.0]))
+    .unwrap();
+
+let animated = matl.apply_anim_frame(&anim, 1.5);
+assert_eq!(0.75, animated.entries[0].floats[0].data);
+```
+ */
+use super::{MatlData, MatlEntryData, UvTransform};
+use crate::anim_data::{lerp_f32, lerp_vector4, AnimData, GroupType, TrackValues};
+
+impl MatlData {
+    /// Returns a copy of the materials with the parameter values from the material tracks in `anim` at `frame`.
+    ///
+    /// Float, vector, and UV transform values are linearly interpolated between frames.
+    /// Boolean values use the previous frame.
+    /// Frames past the end of a track use the track's final value,
+    /// so constant tracks with a single frame apply to every frame.
+    ///
+    /// Only parameters already present in the material are changed.
+    /// Tracks with values that don't match the parameter type are ignored.
+    pub fn apply_anim_frame(&self, anim: &AnimData, frame: f32) -> MatlData {
+        let mut matl = self.clone();
+        for entry in &mut matl.entries {
+            entry.apply_anim_frame(anim, frame);
+        }
+        matl
+    }
+}
+
+impl MatlEntryData {
+    fn apply_anim_frame(&mut self, anim: &AnimData, frame: f32) {
+        let tracks = anim
+            .groups
+            .iter()
+            .filter(|g| g.group_type == GroupType::Material)
+            .flat_map(|g| g.nodes.iter())
+            .filter(|n| n.name == self.material_label)
+            .flat_map(|n| n.tracks.iter());
+
+        for track in tracks {
+            let Some(param_id) = track.material_param_id() else {
+                continue;
+            };
+
+            match &track.values {
+                TrackValues::Float(values) => {
+                    for param in self.floats.iter_mut().filter(|p| p.param_id == param_id) {
+                        if let Some(value) = sample(values, frame, |a, b, t| lerp_f32(*a, *b, t)) {
+                            param.data = value;
+                        }
+                    }
+                }
+                TrackValues::Boolean(values) => {
+                    for param in self.booleans.iter_mut().filter(|p| p.param_id == param_id) {
+                        if let Some(value) = sample(values, frame, |a, _, _| *a) {
+                            param.data = value;
+                        }
+                    }
+                }
+                TrackValues::Vector4(values) => {
+                    for param in self.vectors.iter_mut().filter(|p| p.param_id == param_id) {
+                        if let Some(value) =
+                            sample(values, frame, |a, b, t| lerp_vector4(*a, *b, t))
+                        {
+                            param.data = value;
+                        }
+                    }
+                }
+                TrackValues::UvTransform(values) => {
+                    for param in self
+                        .uv_transforms
+                        .iter_mut()
+                        .filter(|p| p.param_id == param_id)
+                    {
+                        if let Some(value) = sample(values, frame, |a, b, t| UvTransform {
+                            scale_u: lerp_f32(a.scale_u, b.scale_u, t),
+                            scale_v: lerp_f32(a.scale_v, b.scale_v, t),
+                            rotation: lerp_f32(a.rotation, b.rotation, t),
+                            translate_u: lerp_f32(a.translate_u, b.translate_u, t),
+                            translate_v: lerp_f32(a.translate_v, b.translate_v, t),
+                        }) {
+                            param.data = value;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+// Interpolate between the frames before and after frame.
+// Frames outside the track are clamped to the first or last value.
+fn sample<T, R, F: Fn(&T, &T, f32) -> R>(values: &[T], frame: f32, interpolate: F) -> Option<R> {
+    let last = values.len().checked_sub(1)?;
+    let frame = if frame.is_nan() {
+        0.0
+    } else {
+        frame.clamp(0.0, last as f32)
+    };
+
+    let previous = (frame.floor() as usize).min(last);
+    let next = (previous + 1).min(last);
+    Some(interpolate(
+        &values[previous],
+        &values[next],
+        frame - previous as f32,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim_data::{TrackData, TransformFlags};
+    use crate::matl_data::{BooleanParam, FloatParam, ParamId, UvTransformParam, Vector4Param};
+    use crate::test_fixtures;
+    use ssbh_lib::Vector4;
+
+    fn matl() -> MatlData {
+        test_fixtures::matl_data(vec![MatlEntryData {
+            floats: vec![FloatParam::new(ParamId::CustomFloat8, 0.0)],
+            booleans: vec![BooleanParam::new(ParamId::CustomBoolean1, false)],
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector6,
+                Vector4::new(1.0, 1.0, 0.0, 0.0),
+            )],
+            uv_transforms: vec![UvTransformParam::new(
+                ParamId::UvTransform0,
+                UvTransform::IDENTITY,
+            )],
+            ..test_fixtures::matl_entry_data("a", "")
+        }])
+    }
+
+    fn anim() -> AnimData {
+        let mut anim = test_fixtures::empty_anim_data(2.0);
+        anim.insert_material_track(
+            "a",
+            ParamId::CustomVector6,
+            TrackValues::Vector4(vec![
+                Vector4::new(1.0, 1.0, 0.0, 0.0),
+                Vector4::new(2.0, 1.0, 0.5, 0.0),
+                Vector4::new(4.0, 1.0, 1.0, 0.0),
+            ]),
+        )
+        .unwrap();
+        anim.insert_material_track(
+            "a",
+            ParamId::CustomBoolean1,
+            TrackValues::Boolean(vec![false, true, false]),
+        )
+        .unwrap();
+        anim.insert_material_track(
+            "a",
+            ParamId::UvTransform0,
+            TrackValues::UvTransform(vec![crate::anim_data::UvTransform {
+                scale_u: 1.0,
+                scale_v: 1.0,
+                rotation: 0.0,
+                translate_u: 0.5,
+                translate_v: 0.0,
+            }]),
+        )
+        .unwrap();
+        anim
+    }
+
+    #[test]
+    fn apply_anim_frame_interpolate() {
+        let animated = matl().apply_anim_frame(&anim(), 1.5);
+        let entry = &animated.entries[0];
+        assert_eq!(Vector4::new(3.0, 1.0, 0.75, 0.0), entry.vectors[0].data);
+        assert!(entry.booleans[0].data);
+        // Constant tracks apply to every frame.
+        assert_eq!(0.5, entry.uv_transforms[0].data.translate_u);
+        // Parameters without tracks are unchanged.
+        assert_eq!(0.0, entry.floats[0].data);
+    }
+
+    #[test]
+    fn apply_anim_frame_out_of_range() {
+        let animated = matl().apply_anim_frame(&anim(), 10.0);
+        assert_eq!(
+            Vector4::new(4.0, 1.0, 1.0, 0.0),
+            animated.entries[0].vectors[0].data
+        );
+
+        let animated = matl().apply_anim_frame(&anim(), -1.0);
+        assert_eq!(
+            Vector4::new(1.0, 1.0, 0.0, 0.0),
+            animated.entries[0].vectors[0].data
+        );
+    }
+
+    #[test]
+    fn apply_anim_frame_ignores_mismatched_tracks() {
+        let mut anim = anim();
+        anim.insert_track(
+            GroupType::Material,
+            "a",
+            TrackData {
+                name: "CustomFloat8".to_string(),
+                compensate_scale: false,
+                transform_flags: TransformFlags::default(),
+                values: TrackValues::Boolean(vec![true]),
+            },
+        );
+        let animated = matl().apply_anim_frame(&anim, 0.0);
+        assert_eq!(0.0, animated.entries[0].floats[0].data);
+    }
+}
@@ -0,0 +1,260 @@
+//! Checking for anisotropic filtering on textures without mipmaps.
+//!
+//! Anisotropic filtering samples from multiple mipmaps to reduce aliasing at oblique angles.
+//! Textures with only the base mipmap can't be filtered this way,
+//! and materials using anisotropic filtering for these textures tend to shimmer in game.
+//!
+//! Each texture parameter like [ParamId::Texture0] uses the sampler parameter with the same index like [ParamId::Sampler0].
+//! The mipmap count for each texture is provided as a [TextureInfo] from the corresponding texture files.
+/*!
+```rust
+use std::collections::HashMap;
+use ssbh_data::matl_data::texture_memory::{TextureFormat, TextureInfo};
+use ssbh_data::matl_data::*;
+
+let mut matl = MatlData {
+    major_version: 1,
+    minor_version: 6,
+    entries: vec![MatlEntryData {
+        material_label: "a".to_string(),
+        shader_label: String::new(),
+        blend_states: Vec::new(),
+        floats: Vec::new(),
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: vec![SamplerParam::new(
+            ParamId::Sampler0,
+            SamplerData {
+                max_anisotropy: MaxAnisotropy::Four,
+                ..SamplerData::default()
+            },
+        )],
+        textures: vec![TextureParam::new(ParamId::Texture0, "body_col".to_string())],
+        uv_transforms: Vec::new(),
+    }],
+};
+
+let textures = HashMap::from([(
+    "body_col".to_string(),
+    TextureInfo::new_2d(512, 512, 1, TextureFormat::Bc7),
+)]);
+
+assert_eq!(1, matl.anisotropy_without_mipmaps(&textures).len());
+matl.fix_anisotropy_without_mipmaps(&textures);
+assert!(matl.anisotropy_without_mipmaps(&textures).is_empty());
+```
+ */
+use std::collections::HashMap;
+
+use super::param_families::{param_family, ParamFamily};
+use super::texture_memory::TextureInfo;
+use super::texture_names::normalize_texture_name;
+use super::{MatlData, MatlEntryData, MaxAnisotropy, ParamId};
+
+/// A sampler using anisotropic filtering for a texture with a single mipmap.
+/// See [MatlData::anisotropy_without_mipmaps].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AnisotropyWithoutMipmaps {
+    /// The [material_label](super::MatlEntryData::material_label) of the material.
+    pub material_label: String,
+    pub texture_param_id: ParamId,
+    pub sampler_param_id: ParamId,
+    /// The texture name assigned to the texture parameter.
+    pub texture: String,
+    pub max_anisotropy: MaxAnisotropy,
+}
+
+impl MatlData {
+    /// Finds samplers using anisotropic filtering for textures without mipmaps.
+    /// The keys of `textures` are texture names like `"def_mario_001_col"` and are compared ignoring case.
+    ///
+    /// Textures without an entry in `textures` are not checked.
+    pub fn anisotropy_without_mipmaps(
+        &self,
+        textures: &HashMap<String, TextureInfo>,
+    ) -> Vec<AnisotropyWithoutMipmaps> {
+        let mipmap_counts = mipmap_counts(textures);
+        self.entries
+            .iter()
+            .flat_map(|e| e.anisotropy_without_mipmaps_inner(&mipmap_counts))
+            .collect()
+    }
+
+    /// Disables anisotropic filtering by setting [max_anisotropy](super::SamplerData::max_anisotropy) to [MaxAnisotropy::One]
+    /// for each sampler found by [MatlData::anisotropy_without_mipmaps].
+    /// Returns the samplers that were changed.
+    pub fn fix_anisotropy_without_mipmaps(
+        &mut self,
+        textures: &HashMap<String, TextureInfo>,
+    ) -> Vec<AnisotropyWithoutMipmaps> {
+        let mipmap_counts = mipmap_counts(textures);
+        let mut fixed = Vec::new();
+        for entry in &mut self.entries {
+            let warnings = entry.anisotropy_without_mipmaps_inner(&mipmap_counts);
+            for warning in &warnings {
+                for sampler in entry
+                    .samplers
+                    .iter_mut()
+                    .filter(|s| s.param_id == warning.sampler_param_id)
+                {
+                    sampler.data.max_anisotropy = MaxAnisotropy::One;
+                }
+            }
+            fixed.extend(warnings);
+        }
+        fixed
+    }
+}
+
+impl MatlEntryData {
+    fn anisotropy_without_mipmaps_inner(
+        &self,
+        mipmap_counts: &HashMap<String, u32>,
+    ) -> Vec<AnisotropyWithoutMipmaps> {
+        self.textures
+            .iter()
+            .filter(|t| {
+                mipmap_counts
+                    .get(&normalize_texture_name(&t.data))
+                    .is_some_and(|count| *count <= 1)
+            })
+            .filter_map(|t| {
+                let sampler_param_id = sampler_param_id(t.param_id)?;
+                let sampler = self
+                    .samplers
+                    .iter()
+                    .find(|s| s.param_id == sampler_param_id)?;
+
+                (sampler.data.max_anisotropy != MaxAnisotropy::One).then(|| {
+                    AnisotropyWithoutMipmaps {
+                        material_label: self.material_label.clone(),
+                        texture_param_id: t.param_id,
+                        sampler_param_id: sampler.param_id,
+                        texture: t.data.clone(),
+                        max_anisotropy: sampler.data.max_anisotropy,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+fn mipmap_counts(textures: &HashMap<String, TextureInfo>) -> HashMap<String, u32> {
+    textures
+        .iter()
+        .map(|(name, info)| (normalize_texture_name(name), info.mipmap_count))
+        .collect()
+}
+
+// The sampler for a texture parameter has the same index like "Texture3" and "Sampler3".
+fn sampler_param_id(texture_param_id: ParamId) -> Option<ParamId> {
+    match param_family(texture_param_id)? {
+        (ParamFamily::Texture, index) => ParamFamily::Sampler.param_id(index),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::texture_memory::TextureFormat;
+    use crate::matl_data::{SamplerData, SamplerParam, TextureParam};
+    use crate::test_fixtures::matl_entry_data;
+
+    fn sampler(param_id: ParamId, max_anisotropy: MaxAnisotropy) -> SamplerParam {
+        SamplerParam::new(
+            param_id,
+            SamplerData {
+                max_anisotropy,
+                ..SamplerData::default()
+            },
+        )
+    }
+
+    fn matl() -> MatlData {
+        crate::test_fixtures::matl_data(vec![MatlEntryData {
+            samplers: vec![
+                sampler(ParamId::Sampler0, MaxAnisotropy::Sixteen),
+                sampler(ParamId::Sampler4, MaxAnisotropy::Four),
+                sampler(ParamId::Sampler10, MaxAnisotropy::One),
+            ],
+            textures: vec![
+                TextureParam::new(ParamId::Texture0, "a_col".to_string()),
+                TextureParam::new(ParamId::Texture4, "A_NOR".to_string()),
+                TextureParam::new(ParamId::Texture10, "b_col".to_string()),
+            ],
+            ..matl_entry_data("a", "")
+        }])
+    }
+
+    fn textures() -> HashMap<String, TextureInfo> {
+        HashMap::from([
+            (
+                "a_col".to_string(),
+                TextureInfo::new_2d(64, 64, 7, TextureFormat::Bc7),
+            ),
+            (
+                "a_nor".to_string(),
+                TextureInfo::new_2d(64, 64, 1, TextureFormat::Bc5),
+            ),
+            (
+                "b_col".to_string(),
+                TextureInfo::new_2d(64, 64, 1, TextureFormat::Bc7),
+            ),
+        ])
+    }
+
+    #[test]
+    fn anisotropy_without_mipmaps() {
+        assert_eq!(
+            vec![AnisotropyWithoutMipmaps {
+                material_label: "a".to_string(),
+                texture_param_id: ParamId::Texture4,
+                sampler_param_id: ParamId::Sampler4,
+                texture: "A_NOR".to_string(),
+                max_anisotropy: MaxAnisotropy::Four,
+            }],
+            matl().anisotropy_without_mipmaps(&textures())
+        );
+    }
+
+    #[test]
+    fn anisotropy_without_mipmaps_missing_texture_info() {
+        assert!(matl()
+            .anisotropy_without_mipmaps(&HashMap::new())
+            .is_empty());
+    }
+
+    #[test]
+    fn fix_anisotropy_without_mipmaps() {
+        let mut matl = matl();
+        let fixed = matl.fix_anisotropy_without_mipmaps(&textures());
+        assert_eq!(1, fixed.len());
+
+        let samplers: Vec<_> = matl.entries[0]
+            .samplers
+            .iter()
+            .map(|s| s.data.max_anisotropy)
+            .collect();
+        assert_eq!(
+            vec![
+                MaxAnisotropy::Sixteen,
+                MaxAnisotropy::One,
+                MaxAnisotropy::One
+            ],
+            samplers
+        );
+    }
+
+    #[test]
+    fn sampler_for_texture() {
+        assert_eq!(Some(ParamId::Sampler0), sampler_param_id(ParamId::Texture0));
+        assert_eq!(
+            Some(ParamId::Sampler19),
+            sampler_param_id(ParamId::Texture19)
+        );
+        assert_eq!(None, sampler_param_id(ParamId::Sampler3));
+        assert_eq!(None, sampler_param_id(ParamId::DiffuseMap));
+    }
+}
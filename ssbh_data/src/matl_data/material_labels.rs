@@ -0,0 +1,194 @@
+//! Validation and repair for duplicate material labels in [MatlData].
+//!
+//! Each [ModlEntryData](crate::modl_data::ModlEntryData) assigns a material using its label.
+//! Smash Ultimate does not consistently pick the same material when multiple entries share a label,
+//! so material assignments for files with duplicate labels are nondeterministic in game.
+/*!
+```rust
+use ssbh_data::matl_data::{MatlData, MatlEntryData};
+
+# fn entry(label: &str) -> MatlEntryData {
+#     MatlEntryData {
+#         material_label: label.into(),
+#         shader_label: String::new(),
+#         blend_states: Vec::new(),
+#         floats: Vec::new(),
+#         booleans: Vec::new(),
+#         vectors: Vec::new(),
+#         rasterizer_states: Vec::new(),
+#         samplers: Vec::new(),
+#         textures: Vec::new(),
+#         uv_transforms: Vec::new(),
+#     }
+# }
+let mut matl = MatlData {
+    major_version: 1,
+    minor_version: 6,
+    entries: vec![entry("skin"), entry("skin")],
+};
+assert_eq!(1, matl.duplicate_material_labels().len());
+
+let renames = matl.uniquify_material_labels();
+assert_eq!("skin_1", renames[0].new_label);
+assert!(matl.duplicate_material_labels().is_empty());
+```
+ */
+use std::collections::HashSet;
+
+use super::MatlData;
+
+/// A material label used by more than one entry.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DuplicateMaterialLabel<'a> {
+    pub material_label: &'a str,
+    /// The index in [entries](super::MatlData::entries) for each entry with this label.
+    pub entry_indices: Vec<usize>,
+}
+
+/// A material label changed by [MatlData::uniquify_material_labels].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MaterialLabelRename {
+    /// The index in [entries](super::MatlData::entries) of the renamed entry.
+    pub entry_index: usize,
+    pub old_label: String,
+    pub new_label: String,
+}
+
+impl MatlData {
+    /// The labels shared by more than one entry in order of first appearance.
+    /// An empty list means all labels are unique.
+    pub fn duplicate_material_labels(&self) -> Vec<DuplicateMaterialLabel<'_>> {
+        let mut duplicates: Vec<DuplicateMaterialLabel> = Vec::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let label = entry.material_label.as_str();
+            if let Some(d) = duplicates.iter_mut().find(|d| d.material_label == label) {
+                d.entry_indices.push(i);
+            } else if self.entries[i + 1..]
+                .iter()
+                .any(|e| e.material_label == label)
+            {
+                duplicates.push(DuplicateMaterialLabel {
+                    material_label: label,
+                    entry_indices: vec![i],
+                });
+            }
+        }
+        duplicates
+    }
+
+    /// Renames entries with duplicate labels by appending a numeric suffix like `"_1"`.
+    /// The first entry with each label is left unchanged.
+    /// New labels are chosen to not match any existing label.
+    ///
+    /// Returns the renamed entries, so any [ModlData](crate::modl_data::ModlData)
+    /// entries can be updated to use the new labels.
+    pub fn uniquify_material_labels(&mut self) -> Vec<MaterialLabelRename> {
+        let mut used: HashSet<String> = self
+            .entries
+            .iter()
+            .map(|e| e.material_label.clone())
+            .collect();
+        let mut seen = HashSet::new();
+
+        let mut renames = Vec::new();
+        for (entry_index, entry) in self.entries.iter_mut().enumerate() {
+            if seen.insert(entry.material_label.clone()) {
+                continue;
+            }
+
+            let new_label = (1..)
+                .map(|i| format!("{}_{i}", entry.material_label))
+                .find(|l| !used.contains(l))
+                .unwrap();
+            used.insert(new_label.clone());
+            seen.insert(new_label.clone());
+
+            let old_label = std::mem::replace(&mut entry.material_label, new_label.clone());
+            renames.push(MaterialLabelRename {
+                entry_index,
+                old_label,
+                new_label,
+            });
+        }
+        renames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::MatlEntryData;
+    use crate::test_fixtures::matl_entry_data;
+
+    fn entry(material_label: &str) -> MatlEntryData {
+        matl_entry_data(material_label, "SFX_PBS_0100000008008269_opaque")
+    }
+
+    fn matl(labels: &[&str]) -> MatlData {
+        MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: labels.iter().map(|l| entry(l)).collect(),
+        }
+    }
+
+    fn labels(matl: &MatlData) -> Vec<&str> {
+        matl.entries
+            .iter()
+            .map(|e| e.material_label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_labels_unique() {
+        assert!(matl(&["a", "b", "c"])
+            .duplicate_material_labels()
+            .is_empty());
+    }
+
+    #[test]
+    fn duplicate_labels() {
+        assert_eq!(
+            vec![
+                DuplicateMaterialLabel {
+                    material_label: "a",
+                    entry_indices: vec![0, 2, 3]
+                },
+                DuplicateMaterialLabel {
+                    material_label: "b",
+                    entry_indices: vec![1, 4]
+                }
+            ],
+            matl(&["a", "b", "a", "a", "b", "c"]).duplicate_material_labels()
+        );
+    }
+
+    #[test]
+    fn uniquify_labels_unique() {
+        let mut matl = matl(&["a", "b"]);
+        assert!(matl.uniquify_material_labels().is_empty());
+        assert_eq!(vec!["a", "b"], labels(&matl));
+    }
+
+    #[test]
+    fn uniquify_labels_avoid_existing() {
+        let mut matl = matl(&["a", "a", "a_1", "a"]);
+        assert_eq!(
+            vec![
+                MaterialLabelRename {
+                    entry_index: 1,
+                    old_label: "a".to_string(),
+                    new_label: "a_2".to_string()
+                },
+                MaterialLabelRename {
+                    entry_index: 3,
+                    old_label: "a".to_string(),
+                    new_label: "a_3".to_string()
+                }
+            ],
+            matl.uniquify_material_labels()
+        );
+        assert_eq!(vec!["a", "a_2", "a_1", "a_3"], labels(&matl));
+        assert!(matl.duplicate_material_labels().is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+//! The numbered material parameters like `CustomVector0` to `CustomVector63`.
+//!
+//! Each family lists its [ParamId] values in index order.
+//! This converts between names, values, and related parameters like `Texture3` and `Sampler3`
+//! without relying on the [Debug] output of [ParamId].
+use super::ParamId;
+
+/// A group of numbered parameters sharing the same type of value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParamFamily {
+    CustomVector,
+    CustomFloat,
+    CustomBoolean,
+    UvTransform,
+    Texture,
+    Sampler,
+    BlendState,
+    RasterizerState,
+}
+
+impl ParamFamily {
+    const ALL: [ParamFamily; 8] = [
+        ParamFamily::CustomVector,
+        ParamFamily::CustomFloat,
+        ParamFamily::CustomBoolean,
+        ParamFamily::UvTransform,
+        ParamFamily::Texture,
+        ParamFamily::Sampler,
+        ParamFamily::BlendState,
+        ParamFamily::RasterizerState,
+    ];
+
+    /// The parameter name without the index like `"CustomVector"`.
+    pub(crate) fn prefix(self) -> &'static str {
+        match self {
+            ParamFamily::CustomVector => "CustomVector",
+            ParamFamily::CustomFloat => "CustomFloat",
+            ParamFamily::CustomBoolean => "CustomBoolean",
+            ParamFamily::UvTransform => "UvTransform",
+            ParamFamily::Texture => "Texture",
+            ParamFamily::Sampler => "Sampler",
+            ParamFamily::BlendState => "BlendState",
+            ParamFamily::RasterizerState => "RasterizerState",
+        }
+    }
+
+    fn params(self) -> &'static [ParamId] {
+        match self {
+            ParamFamily::CustomVector => &CUSTOM_VECTORS,
+            ParamFamily::CustomFloat => &CUSTOM_FLOATS,
+            ParamFamily::CustomBoolean => &CUSTOM_BOOLEANS,
+            ParamFamily::UvTransform => &UV_TRANSFORMS,
+            ParamFamily::Texture => &TEXTURES,
+            ParamFamily::Sampler => &SAMPLERS,
+            ParamFamily::BlendState => &BLEND_STATES,
+            ParamFamily::RasterizerState => &RASTERIZER_STATES,
+        }
+    }
+
+    /// The parameter in this family with the given `index` like `Sampler3` for `3`.
+    pub(crate) fn param_id(self, index: usize) -> Option<ParamId> {
+        self.params().get(index).copied()
+    }
+}
+
+/// The family and index of `param_id` like `(ParamFamily::Texture, 3)` for `Texture3`.
+pub(crate) fn param_family(param_id: ParamId) -> Option<(ParamFamily, usize)> {
+    ParamFamily::ALL.into_iter().find_map(|family| {
+        let index = family.params().iter().position(|p| *p == param_id)?;
+        Some((family, index))
+    })
+}
+
+/// The name of `param_id` like `"CustomVector6"`.
+pub(crate) fn param_name(param_id: ParamId) -> Option<String> {
+    let (family, index) = param_family(param_id)?;
+    Some(format!("{}{index}", family.prefix()))
+}
+
+/// Finds the parameter with the given `name` like `"CustomVector6"`.
+pub(crate) fn param_id_from_name(name: &str) -> Option<ParamId> {
+    ParamFamily::ALL.into_iter().find_map(|family| {
+        let digits = name.strip_prefix(family.prefix())?;
+        let index: usize = digits.parse().ok()?;
+        // Reject alternate spellings like "CustomVector06" or "CustomVector+6".
+        if index.to_string() != digits {
+            return None;
+        }
+        family.param_id(index)
+    })
+}
+
+/// Finds the parameter with the given [ParamId] value.
+pub(crate) fn param_id_from_value(value: u64) -> Option<ParamId> {
+    ParamFamily::ALL
+        .into_iter()
+        .flat_map(|family| family.params())
+        .find(|p| **p as u64 == value)
+        .copied()
+}
+
+const CUSTOM_VECTORS: [ParamId; 64] = [
+    ParamId::CustomVector0,
+    ParamId::CustomVector1,
+    ParamId::CustomVector2,
+    ParamId::CustomVector3,
+    ParamId::CustomVector4,
+    ParamId::CustomVector5,
+    ParamId::CustomVector6,
+    ParamId::CustomVector7,
+    ParamId::CustomVector8,
+    ParamId::CustomVector9,
+    ParamId::CustomVector10,
+    ParamId::CustomVector11,
+    ParamId::CustomVector12,
+    ParamId::CustomVector13,
+    ParamId::CustomVector14,
+    ParamId::CustomVector15,
+    ParamId::CustomVector16,
+    ParamId::CustomVector17,
+    ParamId::CustomVector18,
+    ParamId::CustomVector19,
+    ParamId::CustomVector20,
+    ParamId::CustomVector21,
+    ParamId::CustomVector22,
+    ParamId::CustomVector23,
+    ParamId::CustomVector24,
+    ParamId::CustomVector25,
+    ParamId::CustomVector26,
+    ParamId::CustomVector27,
+    ParamId::CustomVector28,
+    ParamId::CustomVector29,
+    ParamId::CustomVector30,
+    ParamId::CustomVector31,
+    ParamId::CustomVector32,
+    ParamId::CustomVector33,
+    ParamId::CustomVector34,
+    ParamId::CustomVector35,
+    ParamId::CustomVector36,
+    ParamId::CustomVector37,
+    ParamId::CustomVector38,
+    ParamId::CustomVector39,
+    ParamId::CustomVector40,
+    ParamId::CustomVector41,
+    ParamId::CustomVector42,
+    ParamId::CustomVector43,
+    ParamId::CustomVector44,
+    ParamId::CustomVector45,
+    ParamId::CustomVector46,
+    ParamId::CustomVector47,
+    ParamId::CustomVector48,
+    ParamId::CustomVector49,
+    ParamId::CustomVector50,
+    ParamId::CustomVector51,
+    ParamId::CustomVector52,
+    ParamId::CustomVector53,
+    ParamId::CustomVector54,
+    ParamId::CustomVector55,
+    ParamId::CustomVector56,
+    ParamId::CustomVector57,
+    ParamId::CustomVector58,
+    ParamId::CustomVector59,
+    ParamId::CustomVector60,
+    ParamId::CustomVector61,
+    ParamId::CustomVector62,
+    ParamId::CustomVector63,
+];
+
+const CUSTOM_FLOATS: [ParamId; 20] = [
+    ParamId::CustomFloat0,
+    ParamId::CustomFloat1,
+    ParamId::CustomFloat2,
+    ParamId::CustomFloat3,
+    ParamId::CustomFloat4,
+    ParamId::CustomFloat5,
+    ParamId::CustomFloat6,
+    ParamId::CustomFloat7,
+    ParamId::CustomFloat8,
+    ParamId::CustomFloat9,
+    ParamId::CustomFloat10,
+    ParamId::CustomFloat11,
+    ParamId::CustomFloat12,
+    ParamId::CustomFloat13,
+    ParamId::CustomFloat14,
+    ParamId::CustomFloat15,
+    ParamId::CustomFloat16,
+    ParamId::CustomFloat17,
+    ParamId::CustomFloat18,
+    ParamId::CustomFloat19,
+];
+
+const CUSTOM_BOOLEANS: [ParamId; 20] = [
+    ParamId::CustomBoolean0,
+    ParamId::CustomBoolean1,
+    ParamId::CustomBoolean2,
+    ParamId::CustomBoolean3,
+    ParamId::CustomBoolean4,
+    ParamId::CustomBoolean5,
+    ParamId::CustomBoolean6,
+    ParamId::CustomBoolean7,
+    ParamId::CustomBoolean8,
+    ParamId::CustomBoolean9,
+    ParamId::CustomBoolean10,
+    ParamId::CustomBoolean11,
+    ParamId::CustomBoolean12,
+    ParamId::CustomBoolean13,
+    ParamId::CustomBoolean14,
+    ParamId::CustomBoolean15,
+    ParamId::CustomBoolean16,
+    ParamId::CustomBoolean17,
+    ParamId::CustomBoolean18,
+    ParamId::CustomBoolean19,
+];
+
+const UV_TRANSFORMS: [ParamId; 16] = [
+    ParamId::UvTransform0,
+    ParamId::UvTransform1,
+    ParamId::UvTransform2,
+    ParamId::UvTransform3,
+    ParamId::UvTransform4,
+    ParamId::UvTransform5,
+    ParamId::UvTransform6,
+    ParamId::UvTransform7,
+    ParamId::UvTransform8,
+    ParamId::UvTransform9,
+    ParamId::UvTransform10,
+    ParamId::UvTransform11,
+    ParamId::UvTransform12,
+    ParamId::UvTransform13,
+    ParamId::UvTransform14,
+    ParamId::UvTransform15,
+];
+
+const TEXTURES: [ParamId; 20] = [
+    ParamId::Texture0,
+    ParamId::Texture1,
+    ParamId::Texture2,
+    ParamId::Texture3,
+    ParamId::Texture4,
+    ParamId::Texture5,
+    ParamId::Texture6,
+    ParamId::Texture7,
+    ParamId::Texture8,
+    ParamId::Texture9,
+    ParamId::Texture10,
+    ParamId::Texture11,
+    ParamId::Texture12,
+    ParamId::Texture13,
+    ParamId::Texture14,
+    ParamId::Texture15,
+    ParamId::Texture16,
+    ParamId::Texture17,
+    ParamId::Texture18,
+    ParamId::Texture19,
+];
+
+const SAMPLERS: [ParamId; 20] = [
+    ParamId::Sampler0,
+    ParamId::Sampler1,
+    ParamId::Sampler2,
+    ParamId::Sampler3,
+    ParamId::Sampler4,
+    ParamId::Sampler5,
+    ParamId::Sampler6,
+    ParamId::Sampler7,
+    ParamId::Sampler8,
+    ParamId::Sampler9,
+    ParamId::Sampler10,
+    ParamId::Sampler11,
+    ParamId::Sampler12,
+    ParamId::Sampler13,
+    ParamId::Sampler14,
+    ParamId::Sampler15,
+    ParamId::Sampler16,
+    ParamId::Sampler17,
+    ParamId::Sampler18,
+    ParamId::Sampler19,
+];
+
+const BLEND_STATES: [ParamId; 11] = [
+    ParamId::BlendState0,
+    ParamId::BlendState1,
+    ParamId::BlendState2,
+    ParamId::BlendState3,
+    ParamId::BlendState4,
+    ParamId::BlendState5,
+    ParamId::BlendState6,
+    ParamId::BlendState7,
+    ParamId::BlendState8,
+    ParamId::BlendState9,
+    ParamId::BlendState10,
+];
+
+const RASTERIZER_STATES: [ParamId; 11] = [
+    ParamId::RasterizerState0,
+    ParamId::RasterizerState1,
+    ParamId::RasterizerState2,
+    ParamId::RasterizerState3,
+    ParamId::RasterizerState4,
+    ParamId::RasterizerState5,
+    ParamId::RasterizerState6,
+    ParamId::RasterizerState7,
+    ParamId::RasterizerState8,
+    ParamId::RasterizerState9,
+    ParamId::RasterizerState10,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn param_names_round_trip() {
+        for family in ParamFamily::ALL {
+            for (i, param_id) in family.params().iter().enumerate() {
+                let name = param_name(*param_id).unwrap();
+                assert_eq!(format!("{}{i}", family.prefix()), name);
+                assert_eq!(Some(*param_id), param_id_from_name(&name));
+                assert_eq!(Some(*param_id), param_id_from_value(*param_id as u64));
+            }
+        }
+    }
+
+    #[test]
+    fn param_id_from_invalid_name() {
+        assert_eq!(None, param_id_from_name("CustomVector"));
+        assert_eq!(None, param_id_from_name("CustomVector64"));
+        assert_eq!(None, param_id_from_name("CustomVector06"));
+        assert_eq!(None, param_id_from_name("CustomVector+6"));
+        assert_eq!(None, param_id_from_name("DiffuseMap"));
+    }
+
+    #[test]
+    fn param_family_index() {
+        assert_eq!(
+            Some((ParamFamily::Texture, 3)),
+            param_family(ParamId::Texture3)
+        );
+        assert_eq!(
+            Some((ParamFamily::CustomVector, 47)),
+            param_family(ParamId::CustomVector47)
+        );
+        assert_eq!(None, param_family(ParamId::DiffuseMap));
+        assert_eq!(None, param_id_from_value(0));
+    }
+}
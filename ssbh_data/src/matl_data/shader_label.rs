@@ -0,0 +1,436 @@
+//! Parsing and validation for the shader label of a [MatlEntryData].
+//!
+//! Shader labels in Smash Ultimate combine the shader program name with a render pass suffix
+//! like `"SFX_PBS_0100000008008269_opaque"`.
+//! The render pass determines when the material is drawn and implies the expected blending.
+/*!
+```rust
+use ssbh_data::matl_data::shader_label::{RenderPass, ShaderLabel};
+
+let label: ShaderLabel = "SFX_PBS_0100000008008269_sort".parse().unwrap();
+assert_eq!("SFX_PBS_0100000008008269", label.tag);
+assert_eq!(RenderPass::Sort, label.render_pass);
+assert_eq!("SFX_PBS_0100000008008269_sort", label.to_string());
+```
+ */
+use std::{fmt::Display, str::FromStr};
+
+use super::{BlendFactor, MatlData, MatlEntryData};
+
+pub mod error {
+    use super::RenderPass;
+    use thiserror::Error;
+
+    /// Errors while parsing or validating a shader label.
+    #[derive(Debug, Error, PartialEq, Eq)]
+    pub enum Error {
+        /// The label does not have a render pass suffix like `"_opaque"` or `"_sort"`.
+        #[error("shader label {:?} does not have a valid render pass suffix", label)]
+        InvalidLabel { label: String },
+
+        /// The material blends with the framebuffer but the render pass expects opaque materials.
+        #[error(
+            "material {:?} uses alpha blending but the {} render pass expects opaque materials",
+            material_label,
+            render_pass
+        )]
+        UnexpectedAlphaBlending {
+            material_label: String,
+            render_pass: RenderPass,
+        },
+
+        /// The material does not blend with the framebuffer but the render pass expects alpha blending.
+        #[error(
+            "material {:?} does not use alpha blending but the {} render pass expects blended materials",
+            material_label,
+            render_pass
+        )]
+        MissingAlphaBlending {
+            material_label: String,
+            render_pass: RenderPass,
+        },
+    }
+}
+
+/// The render pass from the suffix of a shader label.
+///
+/// Passes are ordered by when the game draws them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RenderPass {
+    /// `"_opaque"` for opaque and alpha tested materials.
+    Opaque,
+    /// `"_far"` for materials drawn after opaque materials like backgrounds.
+    Far,
+    /// `"_sort"` for alpha blended materials sorted by depth.
+    Sort,
+    /// `"_near"` for materials drawn after all other passes.
+    Near,
+}
+
+impl RenderPass {
+    /// All passes in the order they are drawn.
+    pub const ALL: [RenderPass; 4] = [
+        RenderPass::Opaque,
+        RenderPass::Far,
+        RenderPass::Sort,
+        RenderPass::Near,
+    ];
+
+    /// The suffix for this pass without the leading underscore like `"opaque"`.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            RenderPass::Opaque => "opaque",
+            RenderPass::Far => "far",
+            RenderPass::Sort => "sort",
+            RenderPass::Near => "near",
+        }
+    }
+
+    /// Returns `Some(true)` if the pass expects alpha blending,
+    /// `Some(false)` if the pass expects no blending,
+    /// and [None] if the pass allows either.
+    pub fn expects_alpha_blending(&self) -> Option<bool> {
+        match self {
+            RenderPass::Opaque | RenderPass::Far => Some(false),
+            RenderPass::Sort => Some(true),
+            RenderPass::Near => None,
+        }
+    }
+}
+
+impl Display for RenderPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.suffix())
+    }
+}
+
+/// A shader label like `"SFX_PBS_0100000008008269_opaque"` split into its components.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShaderLabel {
+    /// The name of the shader program like `"SFX_PBS_0100000008008269"`.
+    pub tag: String,
+    /// The render pass from the label's suffix.
+    pub render_pass: RenderPass,
+}
+
+impl FromStr for ShaderLabel {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || error::Error::InvalidLabel {
+            label: s.to_string(),
+        };
+
+        let (tag, suffix) = s.rsplit_once('_').ok_or_else(invalid)?;
+        let render_pass = match suffix {
+            "opaque" => RenderPass::Opaque,
+            "far" => RenderPass::Far,
+            "sort" => RenderPass::Sort,
+            "near" => RenderPass::Near,
+            _ => return Err(invalid()),
+        };
+
+        if tag.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            tag: tag.to_string(),
+            render_pass,
+        })
+    }
+}
+
+impl Display for ShaderLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.tag, self.render_pass)
+    }
+}
+
+impl MatlEntryData {
+    /// Parses the [shader_label](struct.MatlEntryData.html#structfield.shader_label) into its components.
+    pub fn parsed_shader_label(&self) -> Result<ShaderLabel, error::Error> {
+        self.shader_label.parse()
+    }
+
+    /// The render pass from the [shader_label](struct.MatlEntryData.html#structfield.shader_label)
+    /// or [None] if the label is not valid.
+    pub fn render_pass(&self) -> Option<RenderPass> {
+        self.parsed_shader_label().ok().map(|l| l.render_pass)
+    }
+}
+
+/// The entries of a [MatlData] grouped by render pass.
+/// See [MatlData::group_by_render_pass].
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RenderPassGroups<'a> {
+    /// The entries for each pass in draw order.
+    /// Passes without any entries are omitted.
+    pub passes: Vec<(RenderPass, Vec<&'a MatlEntryData>)>,
+    /// The entries with shader labels that do not have a valid render pass.
+    pub invalid: Vec<&'a MatlEntryData>,
+}
+
+impl MatlData {
+    /// Groups the entries by the render pass of their shader label in the order the game draws each pass.
+    /// The order of entries within each pass is preserved.
+    /**
+    ```rust
+    # use ssbh_data::matl_data::{MatlData, MatlEntryData};
+    # let entry = |material_label: &str, shader_label: &str| MatlEntryData {
+    #     material_label: material_label.to_string(),
+    #     shader_label: shader_label.to_string(),
+    #     blend_states: Vec::new(),
+    #     floats: Vec::new(),
+    #     booleans: Vec::new(),
+    #     vectors: Vec::new(),
+    #     rasterizer_states: Vec::new(),
+    #     samplers: Vec::new(),
+    #     textures: Vec::new(),
+    #     uv_transforms: Vec::new(),
+    # };
+    use ssbh_data::matl_data::shader_label::RenderPass;
+
+    let matl = MatlData {
+        major_version: 1,
+        minor_version: 6,
+        entries: vec![
+            entry("hair", "SFX_PBS_0100000008008269_sort"),
+            entry("body", "SFX_PBS_0100000008008269_opaque"),
+        ],
+    };
+
+    let groups = matl.group_by_render_pass();
+    assert_eq!(RenderPass::Opaque, groups.passes[0].0);
+    assert_eq!("body", groups.passes[0].1[0].material_label);
+    assert_eq!(RenderPass::Sort, groups.passes[1].0);
+    ```
+     */
+    pub fn group_by_render_pass(&self) -> RenderPassGroups<'_> {
+        let passes = RenderPass::ALL
+            .into_iter()
+            .map(|pass| {
+                (
+                    pass,
+                    self.entries
+                        .iter()
+                        .filter(|e| e.render_pass() == Some(pass))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(_, entries)| !entries.is_empty())
+            .collect();
+
+        RenderPassGroups {
+            passes,
+            invalid: self
+                .entries
+                .iter()
+                .filter(|e| e.render_pass().is_none())
+                .collect(),
+        }
+    }
+
+    /// Sorts the entries by the render pass of their shader label in the order the game draws each pass.
+    /// The sort is stable, and entries without a valid render pass are moved to the end.
+    pub fn sort_by_render_pass(&mut self) {
+        // None compares less than Some, so invert the option to move invalid labels to the end.
+        self.entries.sort_by_key(|e| {
+            let pass = e.render_pass();
+            (pass.is_none(), pass)
+        });
+    }
+}
+
+/// Returns `true` if the first blend state for `entry` blends with the framebuffer.
+/// Materials without blend states are assumed to be opaque.
+pub fn uses_alpha_blending(entry: &MatlEntryData) -> bool {
+    entry
+        .blend_states
+        .first()
+        .map(|b| b.data.destination_color != BlendFactor::Zero)
+        .unwrap_or_default()
+}
+
+/// Checks that the blend state for `entry` matches the render pass from its shader label.
+///
+/// Opaque materials that blend with the framebuffer or sorted materials that don't blend
+/// will usually render incorrectly in game.
+/**
+```rust
+use ssbh_data::matl_data::{MatlEntryData, shader_label::validate_blend_state};
+
+let entry = MatlEntryData {
+    material_label: "a".to_string(),
+    shader_label: "SFX_PBS_0100000008008269_opaque".to_string(),
+    blend_states: Vec::new(),
+    floats: Vec::new(),
+    booleans: Vec::new(),
+    vectors: Vec::new(),
+    rasterizer_states: Vec::new(),
+    samplers: Vec::new(),
+    textures: Vec::new(),
+    uv_transforms: Vec::new(),
+};
+assert!(validate_blend_state(&entry).is_ok());
+```
+ */
+pub fn validate_blend_state(entry: &MatlEntryData) -> Result<ShaderLabel, error::Error> {
+    let label = entry.parsed_shader_label()?;
+    let blending = uses_alpha_blending(entry);
+
+    match label.render_pass.expects_alpha_blending() {
+        Some(false) if blending => Err(error::Error::UnexpectedAlphaBlending {
+            material_label: entry.material_label.clone(),
+            render_pass: label.render_pass,
+        }),
+        Some(true) if !blending => Err(error::Error::MissingAlphaBlending {
+            material_label: entry.material_label.clone(),
+            render_pass: label.render_pass,
+        }),
+        _ => Ok(label),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::{BlendStateData, BlendStateParam, ParamId};
+    use crate::test_fixtures::matl_entry_data;
+
+    fn entry(shader_label: &str, destination_color: Option<BlendFactor>) -> MatlEntryData {
+        MatlEntryData {
+            blend_states: destination_color
+                .map(|destination_color| {
+                    BlendStateParam::new(
+                        ParamId::BlendState0,
+                        BlendStateData {
+                            destination_color,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .into_iter()
+                .collect(),
+            ..matl_entry_data("mat", shader_label)
+        }
+    }
+
+    fn labeled_entry(material_label: &str, shader_label: &str) -> MatlEntryData {
+        matl_entry_data(material_label, shader_label)
+    }
+
+    fn render_pass_matl() -> MatlData {
+        MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: vec![
+                labeled_entry("a", "SFX_PBS_0_near"),
+                labeled_entry("b", "invalid"),
+                labeled_entry("c", "SFX_PBS_0_sort"),
+                labeled_entry("d", "SFX_PBS_0_opaque"),
+                labeled_entry("e", "SFX_PBS_1_sort"),
+            ],
+        }
+    }
+
+    #[test]
+    fn group_entries_by_render_pass() {
+        let matl = render_pass_matl();
+        let groups = matl.group_by_render_pass();
+
+        let labels = |entries: &[&MatlEntryData]| {
+            entries
+                .iter()
+                .map(|e| e.material_label.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![
+                (RenderPass::Opaque, vec!["d".to_string()]),
+                (RenderPass::Sort, vec!["c".to_string(), "e".to_string()]),
+                (RenderPass::Near, vec!["a".to_string()]),
+            ],
+            groups
+                .passes
+                .iter()
+                .map(|(pass, entries)| (*pass, labels(entries)))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec!["b".to_string()], labels(&groups.invalid));
+    }
+
+    #[test]
+    fn sort_entries_by_render_pass() {
+        let mut matl = render_pass_matl();
+        matl.sort_by_render_pass();
+        assert_eq!(
+            vec!["d", "c", "e", "a", "b"],
+            matl.entries
+                .iter()
+                .map(|e| e.material_label.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parse_shader_labels() {
+        for (label, render_pass) in [
+            ("SFX_PBS_0100000008008269_opaque", RenderPass::Opaque),
+            ("SFX_PBS_0100000008008269_far", RenderPass::Far),
+            ("SFX_PBS_0100000008008269_sort", RenderPass::Sort),
+            ("SFX_PBS_0100000008008269_near", RenderPass::Near),
+        ] {
+            let parsed: ShaderLabel = label.parse().unwrap();
+            assert_eq!("SFX_PBS_0100000008008269", parsed.tag);
+            assert_eq!(render_pass, parsed.render_pass);
+            assert_eq!(label, parsed.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_invalid_shader_labels() {
+        for label in ["", "SFX_PBS_0100000008008269", "SFX_PBS_abc", "_opaque"] {
+            assert_eq!(
+                Err(error::Error::InvalidLabel {
+                    label: label.to_string()
+                }),
+                label.parse::<ShaderLabel>()
+            );
+        }
+    }
+
+    #[test]
+    fn validate_opaque_blend_state() {
+        assert!(validate_blend_state(&entry("SFX_PBS_0_opaque", None)).is_ok());
+        assert!(validate_blend_state(&entry("SFX_PBS_0_opaque", Some(BlendFactor::Zero))).is_ok());
+        assert_eq!(
+            Err(error::Error::UnexpectedAlphaBlending {
+                material_label: "mat".to_string(),
+                render_pass: RenderPass::Opaque
+            }),
+            validate_blend_state(&entry(
+                "SFX_PBS_0_opaque",
+                Some(BlendFactor::OneMinusSourceAlpha)
+            ))
+        );
+    }
+
+    #[test]
+    fn validate_sort_blend_state() {
+        assert!(validate_blend_state(&entry("SFX_PBS_0_sort", Some(BlendFactor::One))).is_ok());
+        assert_eq!(
+            Err(error::Error::MissingAlphaBlending {
+                material_label: "mat".to_string(),
+                render_pass: RenderPass::Sort
+            }),
+            validate_blend_state(&entry("SFX_PBS_0_sort", None))
+        );
+    }
+
+    #[test]
+    fn validate_near_blend_state() {
+        assert!(validate_blend_state(&entry("SFX_PBS_0_near", None)).is_ok());
+        assert!(validate_blend_state(&entry("SFX_PBS_0_near", Some(BlendFactor::One))).is_ok());
+    }
+}
@@ -0,0 +1,258 @@
+//! Checks for the material parameters required by the shader program of a [MatlEntryData].
+//!
+//! Deleting a parameter that the shader still reads like `CustomVector0` is a common cause of crashes in game.
+//! The required parameters for each shader program are listed in the [Nufx] file.
+/*!
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::prelude::*;
+use ssbh_data::matl_data::{shader_params::check_param_removal, ParamId};
+use ssbh_lib::formats::nufx::Nufx;
+
+let matl = MatlData::from_file("model.numatb")?;
+let nufx = Nufx::from_file("nuc2effectlibrary.nufxlb")?;
+
+for entry in &matl.entries {
+    if let Err(e) = check_param_removal(entry, ParamId::CustomVector0, &nufx) {
+        println!("{}", e);
+    }
+}
+# Ok(()) }
+```
+ */
+use ssbh_lib::formats::nufx::{MaterialParameter, Nufx};
+
+use super::{MatlEntryData, ParamId};
+
+pub mod error {
+    use super::ParamId;
+    use thiserror::Error;
+
+    /// Errors while checking the required parameters for a material.
+    #[derive(Debug, Error, PartialEq, Eq)]
+    pub enum Error {
+        /// The shader label does not match any shader program.
+        #[error("no shader program found for shader label {:?}", shader_label)]
+        ShaderNotFound { shader_label: String },
+
+        /// The shader program reads the parameter.
+        #[error(
+            "parameter {:?} for material {:?} is required by shader {:?}",
+            param_id,
+            material_label,
+            shader_label
+        )]
+        RequiredParameter {
+            material_label: String,
+            shader_label: String,
+            param_id: ParamId,
+        },
+    }
+}
+
+/// A material parameter required by a shader program that is not present in the material.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingParameter {
+    /// The value of the corresponding [ParamId].
+    pub param_id: u64,
+    /// The name of the parameter like `"CustomVector0"`.
+    pub name: String,
+}
+
+impl MatlEntryData {
+    /// The [ParamId] of every parameter in the material.
+    pub fn param_ids(&self) -> Vec<ParamId> {
+        self.blend_states
+            .iter()
+            .map(|p| p.param_id)
+            .chain(self.floats.iter().map(|p| p.param_id))
+            .chain(self.booleans.iter().map(|p| p.param_id))
+            .chain(self.vectors.iter().map(|p| p.param_id))
+            .chain(self.rasterizer_states.iter().map(|p| p.param_id))
+            .chain(self.samplers.iter().map(|p| p.param_id))
+            .chain(self.textures.iter().map(|p| p.param_id))
+            .chain(self.uv_transforms.iter().map(|p| p.param_id))
+            .collect()
+    }
+
+    /// Removes all parameters with the given `param_id`.
+    /// Returns `true` if any parameters were removed.
+    pub fn remove_param(&mut self, param_id: ParamId) -> bool {
+        let count = self.param_ids().len();
+
+        self.blend_states.retain(|p| p.param_id != param_id);
+        self.floats.retain(|p| p.param_id != param_id);
+        self.booleans.retain(|p| p.param_id != param_id);
+        self.vectors.retain(|p| p.param_id != param_id);
+        self.rasterizer_states.retain(|p| p.param_id != param_id);
+        self.samplers.retain(|p| p.param_id != param_id);
+        self.textures.retain(|p| p.param_id != param_id);
+        self.uv_transforms.retain(|p| p.param_id != param_id);
+
+        self.param_ids().len() != count
+    }
+}
+
+/// Finds the required parameters for the program in `nufx` with a name matching `shader_label`.
+/// Program names include the render pass like `"SFX_PBS_0100000008008269_opaque"`.
+pub fn required_parameters<'a>(
+    nufx: &'a Nufx,
+    shader_label: &str,
+) -> Option<&'a [MaterialParameter]> {
+    match nufx {
+        Nufx::V0(v0) => v0
+            .programs
+            .elements
+            .iter()
+            .find(|p| p.name.to_str() == Some(shader_label))
+            .map(|p| p.material_parameters.elements.as_slice()),
+        Nufx::V1(v1) => v1
+            .programs
+            .elements
+            .iter()
+            .find(|p| p.name.to_str() == Some(shader_label))
+            .map(|p| p.material_parameters.elements.as_slice()),
+    }
+}
+
+/// Returns the parameters required by the shader program for `entry` that are not in `entry`.
+pub fn missing_parameters(
+    entry: &MatlEntryData,
+    nufx: &Nufx,
+) -> Result<Vec<MissingParameter>, error::Error> {
+    let required = required_parameters(nufx, &entry.shader_label).ok_or_else(|| {
+        error::Error::ShaderNotFound {
+            shader_label: entry.shader_label.clone(),
+        }
+    })?;
+
+    let param_ids = entry.param_ids();
+    Ok(required
+        .iter()
+        .filter(|r| !param_ids.iter().any(|p| *p as u64 == r.param_id))
+        .map(|r| MissingParameter {
+            param_id: r.param_id,
+            name: r.parameter_name.to_string_lossy(),
+        })
+        .collect())
+}
+
+/// Checks if removing `param_id` from `entry` would leave the shader program without a required parameter.
+/// This does not modify `entry`. Use [MatlEntryData::remove_param] to remove the parameter.
+pub fn check_param_removal(
+    entry: &MatlEntryData,
+    param_id: ParamId,
+    nufx: &Nufx,
+) -> Result<(), error::Error> {
+    let required = required_parameters(nufx, &entry.shader_label).ok_or_else(|| {
+        error::Error::ShaderNotFound {
+            shader_label: entry.shader_label.clone(),
+        }
+    })?;
+
+    if required.iter().any(|r| r.param_id == param_id as u64) {
+        Err(error::Error::RequiredParameter {
+            material_label: entry.material_label.clone(),
+            shader_label: entry.shader_label.clone(),
+            param_id,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::{FloatParam, Vector4Param};
+    use crate::test_fixtures::matl_entry_data;
+    use ssbh_lib::formats::nufx::{NufxV1, ShaderProgramV1, ShaderStages};
+    use ssbh_lib::{SsbhArray, Vector4};
+
+    fn nufx() -> Nufx {
+        Nufx::V1(NufxV1 {
+            programs: SsbhArray::from_vec(vec![ShaderProgramV1 {
+                name: "SFX_PBS_0_opaque".into(),
+                render_pass: "nu::Opaque".into(),
+                shaders: ShaderStages {
+                    vertex_shader: "".into(),
+                    unk_shader1: "".into(),
+                    unk_shader2: "".into(),
+                    geometry_shader: "".into(),
+                    pixel_shader: "".into(),
+                    compute_shader: "".into(),
+                },
+                vertex_attributes: SsbhArray::new(),
+                material_parameters: SsbhArray::from_vec(vec![
+                    MaterialParameter {
+                        param_id: ParamId::CustomVector0 as u64,
+                        parameter_name: "CustomVector0".into(),
+                    },
+                    MaterialParameter {
+                        param_id: ParamId::CustomFloat8 as u64,
+                        parameter_name: "CustomFloat8".into(),
+                    },
+                ]),
+            }]),
+            unk_string_list: SsbhArray::new(),
+        })
+    }
+
+    fn entry(shader_label: &str) -> MatlEntryData {
+        MatlEntryData {
+            floats: vec![FloatParam::new(ParamId::CustomFloat1, 0.5)],
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector0,
+                Vector4::new(1.0, 1.0, 1.0, 1.0),
+            )],
+            ..matl_entry_data("mat", shader_label)
+        }
+    }
+
+    #[test]
+    fn check_param_removal_required() {
+        assert_eq!(
+            Err(error::Error::RequiredParameter {
+                material_label: "mat".to_string(),
+                shader_label: "SFX_PBS_0_opaque".to_string(),
+                param_id: ParamId::CustomVector0
+            }),
+            check_param_removal(&entry("SFX_PBS_0_opaque"), ParamId::CustomVector0, &nufx())
+        );
+    }
+
+    #[test]
+    fn check_param_removal_unused() {
+        assert_eq!(
+            Ok(()),
+            check_param_removal(&entry("SFX_PBS_0_opaque"), ParamId::CustomFloat1, &nufx())
+        );
+    }
+
+    #[test]
+    fn check_param_removal_missing_shader() {
+        assert_eq!(
+            Err(error::Error::ShaderNotFound {
+                shader_label: "SFX_PBS_1_opaque".to_string()
+            }),
+            check_param_removal(&entry("SFX_PBS_1_opaque"), ParamId::CustomFloat1, &nufx())
+        );
+    }
+
+    #[test]
+    fn missing_parameters_after_removal() {
+        let mut entry = entry("SFX_PBS_0_opaque");
+        assert_eq!(
+            vec![MissingParameter {
+                param_id: ParamId::CustomFloat8 as u64,
+                name: "CustomFloat8".to_string()
+            }],
+            missing_parameters(&entry, &nufx()).unwrap()
+        );
+
+        assert!(entry.remove_param(ParamId::CustomVector0));
+        assert!(!entry.remove_param(ParamId::CustomVector0));
+        assert_eq!(vec![ParamId::CustomFloat1], entry.param_ids());
+        assert_eq!(2, missing_parameters(&entry, &nufx()).unwrap().len());
+    }
+}
@@ -0,0 +1,224 @@
+//! A human readable text format for sharing material presets.
+//!
+//! The format is [TOML](https://toml.io) using the same field names as the JSON for [MatlEntryData]
+//! and is easier to read and edit than JSON.
+//! Enable the `matl_text` feature to use this module.
+/*!
+```toml
+material_label = "alp_mario_002"
+shader_label = "SFX_PBS_0100000008008269_opaque"
+blend_states = []
+booleans = []
+vectors = []
+rasterizer_states = []
+samplers = []
+uv_transforms = []
+
+[[floats]]
+param_id = "CustomFloat8"
+data = 0.7
+
+[[textures]]
+param_id = "Texture0"
+data = "/common/shader/sfxpbs/default_white"
+```
+ */
+use super::MatlEntryData;
+
+pub use toml::de::Error as ParseTextError;
+
+impl MatlEntryData {
+    /// Converts the material to the text format described in the [text](crate::matl_data::text) module.
+    /// Parameters are written in the same order as the fields of [MatlEntryData].
+    /**
+    ```rust
+    use ssbh_data::matl_data::{FloatParam, MatlEntryData, ParamId};
+
+    let entry = MatlEntryData {
+        material_label: "a".to_string(),
+        shader_label: "SFX_PBS_0100000008008269_opaque".to_string(),
+        floats: vec![FloatParam::new(ParamId::CustomFloat8, 0.7)],
+        blend_states: Vec::new(),
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: Vec::new(),
+        textures: Vec::new(),
+        uv_transforms: Vec::new(),
+    };
+
+    let text = entry.to_text();
+    assert_eq!(entry, MatlEntryData::from_text(&text).unwrap());
+    ```
+     */
+    pub fn to_text(&self) -> String {
+        // The fields only use strings, numbers, booleans, and arrays of tables.
+        toml::to_string(self).unwrap()
+    }
+
+    /// Parses a material from the text format described in the [text](crate::matl_data::text) module.
+    pub fn from_text(text: &str) -> Result<Self, ParseTextError> {
+        toml::from_str(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::{
+        BlendFactor, BlendOperation, BlendStateData, BlendStateParam, BooleanParam, CullMode,
+        FillMode, FloatParam, MagFilter, MaxAnisotropy, MinFilter, ParamId, RasterizerStateData,
+        RasterizerStateParam, SamplerData, SamplerParam, TextureParam, UvTransform,
+        UvTransformParam, Vector4Param, WrapMode,
+    };
+    use pretty_assertions::assert_eq;
+    use ssbh_lib::{Color4f, Vector4};
+
+    fn entry() -> MatlEntryData {
+        MatlEntryData {
+            material_label: "alp_mario_002".to_string(),
+            shader_label: "SFX_PBS_0100000008008269_opaque".to_string(),
+            blend_states: vec![BlendStateParam::new(
+                ParamId::BlendState0,
+                BlendStateData {
+                    source_color: BlendFactor::One,
+                    color_operation: BlendOperation::Add,
+                    destination_color: BlendFactor::OneMinusSourceAlpha,
+                    source_alpha: BlendFactor::One,
+                    alpha_operation: BlendOperation::Add,
+                    destination_alpha: BlendFactor::Zero,
+                    alpha_sample_to_coverage: true,
+                },
+            )],
+            floats: vec![FloatParam::new(ParamId::CustomFloat8, 0.7)],
+            booleans: vec![BooleanParam::new(ParamId::CustomBoolean1, true)],
+            vectors: vec![Vector4Param::new(
+                ParamId::CustomVector0,
+                Vector4::new(1.0, 0.5, -0.25, 1e-8),
+            )],
+            rasterizer_states: vec![RasterizerStateParam::new(
+                ParamId::RasterizerState0,
+                RasterizerStateData {
+                    fill_mode: FillMode::Solid,
+                    cull_mode: CullMode::Disabled,
+                    depth_bias: 0.0,
+                },
+            )],
+            samplers: vec![SamplerParam::new(
+                ParamId::Sampler0,
+                SamplerData {
+                    wraps: WrapMode::Repeat,
+                    wrapt: WrapMode::ClampToBorder,
+                    wrapr: WrapMode::MirroredRepeat,
+                    min_filter: MinFilter::LinearMipmapLinear,
+                    mag_filter: MagFilter::Linear,
+                    border_color: Color4f::new(1.0, 0.0, 0.0, 1.0),
+                    lod_bias: -1.0,
+                    max_anisotropy: MaxAnisotropy::Four,
+                },
+            )],
+            textures: vec![TextureParam::new(
+                ParamId::Texture0,
+                "/common/shader/sfxpbs/default_white".to_string(),
+            )],
+            uv_transforms: vec![UvTransformParam::new(
+                ParamId::UvTransform0,
+                UvTransform::IDENTITY,
+            )],
+        }
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let entry = entry();
+        assert_eq!(entry, MatlEntryData::from_text(&entry.to_text()).unwrap());
+    }
+
+    #[test]
+    fn text_round_trip_special_values() {
+        let mut entry = entry();
+        entry.material_label = "a\n\"b\"\t\\c\u{1}".to_string();
+        entry.floats = vec![
+            FloatParam::new(ParamId::CustomFloat0, f32::INFINITY),
+            FloatParam::new(ParamId::CustomFloat1, f32::NEG_INFINITY),
+            FloatParam::new(ParamId::CustomFloat2, -0.0),
+            FloatParam::new(ParamId::CustomFloat3, f32::MIN_POSITIVE),
+        ];
+
+        let text = entry.to_text();
+        assert_eq!(entry, MatlEntryData::from_text(&text).unwrap());
+
+        // NaN can't be compared with PartialEq.
+        entry.floats = vec![FloatParam::new(ParamId::CustomFloat0, f32::NAN)];
+        let new_entry = MatlEntryData::from_text(&entry.to_text()).unwrap();
+        assert!(new_entry.floats[0].data.is_nan());
+    }
+
+    #[test]
+    fn to_text_float() {
+        let mut entry = entry();
+        entry.samplers.clear();
+        entry.blend_states.clear();
+        entry.rasterizer_states.clear();
+        entry.uv_transforms.clear();
+        entry.vectors.clear();
+        entry.booleans.clear();
+        entry.textures.clear();
+        assert_eq!(
+            "material_label = \"alp_mario_002\"\n\
+             shader_label = \"SFX_PBS_0100000008008269_opaque\"\n\
+             blend_states = []\n\
+             booleans = []\n\
+             vectors = []\n\
+             rasterizer_states = []\n\
+             samplers = []\n\
+             textures = []\n\
+             uv_transforms = []\n\
+             \n\
+             [[floats]]\n\
+             param_id = \"CustomFloat8\"\n\
+             data = 0.7\n",
+            entry.to_text()
+        );
+    }
+
+    #[test]
+    fn from_text_toml_syntax() {
+        // Check TOML features not used when writing.
+        let entry = MatlEntryData::from_text(
+            r#"# A preset
+            material_label = 'a "b" # c' # The label
+            shader_label = "d\teA"
+            blend_states = []
+            floats = []
+            booleans = []
+            vectors = [{ param_id = "CustomVector0", data = { x = 1.0, y = 2.0, z = 3.0, w = nan } }]
+            rasterizer_states = []
+            samplers = []
+
+            [[textures]]
+            param_id = "Texture4"
+            data = """e\\f"""
+            "#,
+        )
+        .unwrap();
+        assert_eq!("a \"b\" # c", entry.material_label);
+        assert_eq!("d\teA", entry.shader_label);
+        assert!(entry.vectors[0].data.w.is_nan());
+        assert_eq!("e\\f", entry.textures[0].data);
+        assert_eq!(ParamId::Texture4, entry.textures[0].param_id);
+        assert!(entry.uv_transforms.is_empty());
+    }
+
+    #[test]
+    fn from_text_errors() {
+        // Missing key.
+        assert!(MatlEntryData::from_text("material_label = \"a\"").is_err());
+        // Invalid syntax.
+        assert!(MatlEntryData::from_text("material_label = \"a\"\nshader_label").is_err());
+        // Invalid param id.
+        let mut text = entry().to_text();
+        text = text.replace("CustomFloat8", "CustomFloat999");
+        assert!(MatlEntryData::from_text(&text).is_err());
+    }
+}
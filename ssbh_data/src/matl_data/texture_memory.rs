@@ -0,0 +1,360 @@
+//! Estimating the GPU memory used by the textures in [MatlData].
+//!
+//! The .numatb file only contains texture names, so the dimensions and format for each texture
+//! are provided separately as a [TextureInfo] from the corresponding texture files.
+//! Texture names are compared ignoring case like [texture_names_eq](super::texture_names::texture_names_eq).
+/*!
+```rust
+use std::collections::HashMap;
+use ssbh_data::matl_data::texture_memory::{TextureFormat, TextureInfo};
+use ssbh_data::matl_data::{MatlData, MatlEntryData, ParamId, TextureParam};
+
+# let entry = |label: &str, textures: &[&str]| MatlEntryData {
+#     material_label: label.to_string(),
+#     shader_label: String::new(),
+#     blend_states: Vec::new(),
+#     floats: Vec::new(),
+#     booleans: Vec::new(),
+#     vectors: Vec::new(),
+#     rasterizer_states: Vec::new(),
+#     samplers: Vec::new(),
+#     textures: textures
+#         .iter()
+#         .map(|t| TextureParam::new(ParamId::Texture0, t.to_string()))
+#         .collect(),
+#     uv_transforms: Vec::new(),
+# };
+let matl = MatlData {
+    major_version: 1,
+    minor_version: 6,
+    entries: vec![entry("a", &["body_col"]), entry("b", &["body_col", "eye_col"])],
+};
+
+let textures = HashMap::from([
+    ("body_col".to_string(), TextureInfo::new_2d(1024, 1024, 11, TextureFormat::Bc7)),
+    ("eye_col".to_string(), TextureInfo::new_2d(256, 256, 1, TextureFormat::Rgba8)),
+]);
+
+let memory = matl.texture_memory(&textures);
+println!("{} bytes", memory.total_bytes);
+for material in memory.materials {
+    println!("{}: {} bytes", material.material_label, material.texture_bytes);
+}
+```
+ */
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::texture_names::normalize_texture_name;
+use super::{MatlData, MatlEntryData};
+
+/// The storage format for texture data in GPU memory.
+/// Formats that differ only in color space like sRGB and linear variants have the same size.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TextureFormat {
+    /// 1 byte per pixel.
+    R8,
+    /// 2 bytes per pixel.
+    Rg8,
+    /// 4 bytes per pixel.
+    Rgba8,
+    /// 8 bytes per pixel.
+    Rgba16Float,
+    /// 16 bytes per pixel.
+    Rgba32Float,
+    /// 8 bytes per 4x4 block.
+    Bc1,
+    /// 16 bytes per 4x4 block.
+    Bc2,
+    /// 16 bytes per 4x4 block.
+    Bc3,
+    /// 8 bytes per 4x4 block.
+    Bc4,
+    /// 16 bytes per 4x4 block.
+    Bc5,
+    /// 16 bytes per 4x4 block.
+    Bc6,
+    /// 16 bytes per 4x4 block.
+    Bc7,
+}
+
+impl TextureFormat {
+    // The width or height in pixels and the size in bytes of a block.
+    fn block_dimensions_size(&self) -> (u64, u64) {
+        match self {
+            TextureFormat::R8 => (1, 1),
+            TextureFormat::Rg8 => (1, 2),
+            TextureFormat::Rgba8 => (1, 4),
+            TextureFormat::Rgba16Float => (1, 8),
+            TextureFormat::Rgba32Float => (1, 16),
+            TextureFormat::Bc1 | TextureFormat::Bc4 => (4, 8),
+            TextureFormat::Bc2
+            | TextureFormat::Bc3
+            | TextureFormat::Bc5
+            | TextureFormat::Bc6
+            | TextureFormat::Bc7 => (4, 16),
+        }
+    }
+}
+
+/// The dimensions and format of a texture.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct TextureInfo {
+    pub width: u32,
+    pub height: u32,
+    /// The depth for 3D textures or `1` otherwise.
+    pub depth: u32,
+    /// The number of array layers like `6` for cube maps or `1` otherwise.
+    pub layers: u32,
+    /// The number of mipmaps including the base level.
+    pub mipmap_count: u32,
+    pub format: TextureFormat,
+}
+
+impl TextureInfo {
+    /// Creates a 2D texture with a single layer.
+    pub fn new_2d(width: u32, height: u32, mipmap_count: u32, format: TextureFormat) -> Self {
+        Self {
+            width,
+            height,
+            depth: 1,
+            layers: 1,
+            mipmap_count,
+            format,
+        }
+    }
+
+    /// The size in bytes of all mipmaps and layers.
+    /// Compressed formats round each mipmap up to whole blocks.
+    /**
+    ```rust
+    use ssbh_data::matl_data::texture_memory::{TextureFormat, TextureInfo};
+
+    assert_eq!(64 * 64 * 4, TextureInfo::new_2d(64, 64, 1, TextureFormat::Rgba8).size_in_bytes());
+    // A 2x2 mipmap still uses an entire 4x4 block.
+    assert_eq!(16, TextureInfo::new_2d(2, 2, 1, TextureFormat::Bc7).size_in_bytes());
+    ```
+     */
+    pub fn size_in_bytes(&self) -> u64 {
+        let (block_dimension, block_size) = self.format.block_dimensions_size();
+        let mip_size = |mip: u32| {
+            let dimension = |x: u32| (x.checked_shr(mip).unwrap_or(0) as u64).max(1);
+            let blocks_x = dimension(self.width).div_ceil(block_dimension);
+            let blocks_y = dimension(self.height).div_ceil(block_dimension);
+            blocks_x * blocks_y * dimension(self.depth) * block_size
+        };
+
+        // Textures always have at least the base mipmap.
+        (0..self.mipmap_count.max(1)).map(mip_size).sum::<u64>() * self.layers.max(1) as u64
+    }
+}
+
+/// The estimated texture memory for a single material.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MaterialTextureMemory {
+    /// The [material_label](super::MatlEntryData::material_label) of the material.
+    pub material_label: String,
+    /// The size in bytes of the unique textures used by this material.
+    pub texture_bytes: u64,
+    /// The textures used by this material that were not found.
+    pub missing_textures: Vec<String>,
+}
+
+/// The estimated texture memory for all materials.
+/// See [MatlData::texture_memory].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextureMemory {
+    /// The memory for each material in the same order as the entries.
+    pub materials: Vec<MaterialTextureMemory>,
+    /// The size in bytes of the unique textures used by any material.
+    /// Textures shared between materials are only counted once.
+    pub total_bytes: u64,
+    /// The unique textures used by any material that were not found.
+    pub missing_textures: Vec<String>,
+}
+
+impl MatlData {
+    /// Estimates the GPU memory used by the textures assigned to each material.
+    /// The keys of `textures` are texture names like `"def_mario_001_col"`.
+    ///
+    /// Textures without an entry in `textures` do not contribute to the size and are listed as missing.
+    /// This includes default textures like `"/common/shader/sfxpbs/default_normal"` unless they are provided.
+    pub fn texture_memory(&self, textures: &HashMap<String, TextureInfo>) -> TextureMemory {
+        let sizes = texture_sizes(textures);
+
+        let (total_bytes, missing_textures) = memory(
+            self.entries
+                .iter()
+                .flat_map(|e| e.textures.iter().map(|t| t.data.as_str())),
+            &sizes,
+        );
+
+        TextureMemory {
+            materials: self
+                .entries
+                .iter()
+                .map(|e| e.texture_memory_inner(&sizes))
+                .collect(),
+            total_bytes,
+            missing_textures,
+        }
+    }
+}
+
+impl MatlEntryData {
+    /// Estimates the GPU memory used by the textures assigned to this material.
+    /// See [MatlData::texture_memory].
+    pub fn texture_memory(&self, textures: &HashMap<String, TextureInfo>) -> MaterialTextureMemory {
+        let sizes = texture_sizes(textures);
+        self.texture_memory_inner(&sizes)
+    }
+
+    fn texture_memory_inner(&self, sizes: &HashMap<String, u64>) -> MaterialTextureMemory {
+        let (texture_bytes, missing_textures) =
+            memory(self.textures.iter().map(|t| t.data.as_str()), sizes);
+        MaterialTextureMemory {
+            material_label: self.material_label.clone(),
+            texture_bytes,
+            missing_textures,
+        }
+    }
+}
+
+fn texture_sizes(textures: &HashMap<String, TextureInfo>) -> HashMap<String, u64> {
+    textures
+        .iter()
+        .map(|(name, info)| (normalize_texture_name(name), info.size_in_bytes()))
+        .collect()
+}
+
+// Each texture is only counted once even if the name differs in case.
+fn memory<'a>(
+    names: impl Iterator<Item = &'a str>,
+    sizes: &HashMap<String, u64>,
+) -> (u64, Vec<String>) {
+    let mut total = 0;
+    let mut missing = Vec::new();
+    let mut seen = HashSet::new();
+    for name in names {
+        let key = normalize_texture_name(name);
+        if seen.insert(key.clone()) {
+            match sizes.get(&key) {
+                Some(size) => total += size,
+                None => missing.push(name.to_string()),
+            }
+        }
+    }
+    (total, missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::{ParamId, TextureParam};
+    use crate::test_fixtures::matl_entry_data;
+
+    fn entry(label: &str, textures: &[&str]) -> MatlEntryData {
+        MatlEntryData {
+            textures: textures
+                .iter()
+                .map(|t| TextureParam::new(ParamId::Texture0, t.to_string()))
+                .collect(),
+            ..matl_entry_data(label, "")
+        }
+    }
+
+    #[test]
+    fn size_in_bytes_mipmaps() {
+        // 16 + 4 + 1 pixels.
+        assert_eq!(
+            21 * 4,
+            TextureInfo::new_2d(4, 4, 3, TextureFormat::Rgba8).size_in_bytes()
+        );
+        // Non square textures stop at 1 pixel for each dimension.
+        assert_eq!(
+            8 + 4 + 2 + 1,
+            TextureInfo::new_2d(8, 1, 4, TextureFormat::R8).size_in_bytes()
+        );
+        // Mipmap counts of 0 still include the base level.
+        assert_eq!(
+            16,
+            TextureInfo::new_2d(2, 2, 0, TextureFormat::Rgba8).size_in_bytes()
+        );
+    }
+
+    #[test]
+    fn size_in_bytes_compressed() {
+        // 64 + 16 + 4 + 1 + 1 + 1 + 1 blocks.
+        assert_eq!(
+            88 * 16,
+            TextureInfo::new_2d(32, 32, 7, TextureFormat::Bc7).size_in_bytes()
+        );
+        assert_eq!(
+            2 * 8,
+            TextureInfo::new_2d(8, 4, 1, TextureFormat::Bc1).size_in_bytes()
+        );
+    }
+
+    #[test]
+    fn size_in_bytes_cube_3d() {
+        let cube = TextureInfo {
+            layers: 6,
+            ..TextureInfo::new_2d(4, 4, 1, TextureFormat::Rgba8)
+        };
+        assert_eq!(6 * 64, cube.size_in_bytes());
+
+        let volume = TextureInfo {
+            depth: 4,
+            ..TextureInfo::new_2d(4, 4, 2, TextureFormat::R8)
+        };
+        assert_eq!(64 + 8, volume.size_in_bytes());
+    }
+
+    #[test]
+    fn texture_memory_shared_and_missing() {
+        let matl = MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: vec![
+                entry("a", &["body_col", "BODY_COL", "missing"]),
+                entry("b", &["Body_Col", "eye_col"]),
+            ],
+        };
+        let textures = HashMap::from([
+            (
+                "body_col".to_string(),
+                TextureInfo::new_2d(4, 4, 1, TextureFormat::Rgba8),
+            ),
+            (
+                "eye_col".to_string(),
+                TextureInfo::new_2d(4, 4, 1, TextureFormat::R8),
+            ),
+        ]);
+
+        assert_eq!(
+            TextureMemory {
+                materials: vec![
+                    MaterialTextureMemory {
+                        material_label: "a".to_string(),
+                        texture_bytes: 64,
+                        missing_textures: vec!["missing".to_string()],
+                    },
+                    MaterialTextureMemory {
+                        material_label: "b".to_string(),
+                        texture_bytes: 80,
+                        missing_textures: Vec::new(),
+                    },
+                ],
+                total_bytes: 80,
+                missing_textures: vec!["missing".to_string()],
+            },
+            matl.texture_memory(&textures)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Case-insensitive comparisons for the texture names in [MatlData].
+//!
+//! Smash Ultimate treats texture names like `"def_mario_001_col"` and `"DEF_Mario_001_col"` as the same texture.
+//! Comparing names exactly can report false mismatches when validating materials against texture files.
+/*!
+```rust
+use ssbh_data::matl_data::texture_names::texture_names_eq;
+
+assert!(texture_names_eq("def_mario_001_col", "DEF_Mario_001_col"));
+assert!(!texture_names_eq("def_mario_001_col", "def_mario_001_nor"));
+```
+ */
+use super::{MatlData, MatlEntryData};
+
+/// Returns `true` if `a` and `b` refer to the same texture ignoring ASCII case.
+pub fn texture_names_eq(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// The lowercase form of `name` for use as a key in maps or sets.
+pub fn normalize_texture_name(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+/// Removes names that match a previous name ignoring case.
+/// The first spelling of each name is kept, and the order is preserved.
+pub fn dedup_texture_names<S: AsRef<str>>(names: &mut Vec<S>) {
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|n| seen.insert(normalize_texture_name(n.as_ref())));
+}
+
+/// The materials that use a texture.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextureUsage<'a> {
+    /// The first spelling of the texture name in the materials.
+    pub name: &'a str,
+    /// The [material_label](super::MatlEntryData::material_label) of each material using the texture.
+    pub material_labels: Vec<&'a str>,
+}
+
+impl MatlEntryData {
+    /// Returns `true` if any texture parameter has a name matching `name` ignoring case.
+    pub fn uses_texture(&self, name: &str) -> bool {
+        self.textures
+            .iter()
+            .any(|t| texture_names_eq(&t.data, name))
+    }
+}
+
+impl MatlData {
+    /// The unique texture names for all materials ignoring case.
+    /// Names are in order of first appearance.
+    pub fn texture_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self
+            .entries
+            .iter()
+            .flat_map(|e| e.textures.iter().map(|t| t.data.as_str()))
+            .collect();
+        dedup_texture_names(&mut names);
+        names
+    }
+
+    /// The materials using each unique texture name ignoring case.
+    /// Textures are in order of first appearance.
+    pub fn texture_usage(&self) -> Vec<TextureUsage<'_>> {
+        self.texture_names()
+            .into_iter()
+            .map(|name| TextureUsage {
+                name,
+                material_labels: self
+                    .entries
+                    .iter()
+                    .filter(|e| e.uses_texture(name))
+                    .map(|e| e.material_label.as_str())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matl_data::{ParamData, ParamId};
+    use crate::test_fixtures::matl_entry_data;
+
+    fn entry(material_label: &str, textures: &[&str]) -> MatlEntryData {
+        MatlEntryData {
+            textures: textures
+                .iter()
+                .map(|t| ParamData::new(ParamId::Texture0, t.to_string()))
+                .collect(),
+            ..matl_entry_data(material_label, "SFX_PBS_0100000008008269_opaque")
+        }
+    }
+
+    fn matl() -> MatlData {
+        crate::test_fixtures::matl_data(vec![
+            entry("a", &["Body_col", "body_nor"]),
+            entry("b", &["body_COL", "eye_col"]),
+        ])
+    }
+
+    #[test]
+    fn dedup_names_ignoring_case() {
+        let mut names = vec!["a_col", "A_COL", "b_col", "a_Col"];
+        dedup_texture_names(&mut names);
+        assert_eq!(vec!["a_col", "b_col"], names);
+    }
+
+    #[test]
+    fn matl_texture_names() {
+        assert_eq!(
+            vec!["Body_col", "body_nor", "eye_col"],
+            matl().texture_names()
+        );
+    }
+
+    #[test]
+    fn matl_texture_usage() {
+        let matl = matl();
+        assert!(matl.entries[0].uses_texture("BODY_COL"));
+        assert!(!matl.entries[0].uses_texture("eye_col"));
+        assert_eq!(
+            vec![
+                TextureUsage {
+                    name: "Body_col",
+                    material_labels: vec!["a", "b"]
+                },
+                TextureUsage {
+                    name: "body_nor",
+                    material_labels: vec!["a"]
+                },
+                TextureUsage {
+                    name: "eye_col",
+                    material_labels: vec!["b"]
+                },
+            ],
+            matl.texture_usage()
+        );
+    }
+}
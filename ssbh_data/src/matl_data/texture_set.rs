@@ -0,0 +1,378 @@
+//! Creating a [MatlEntryData] from a set of textures following Smash Ultimate's naming conventions.
+//!
+//! Textures are assigned by the suffix of their name.
+//! `"_col"` is the base color in [ParamId::Texture0],
+//! `"_nor"` is the normal map in [ParamId::Texture4],
+//! `"_emi"` is the emissive map in [ParamId::Texture5],
+//! and `"_prm"` is the metalness, roughness, ambient occlusion, and specular map in [ParamId::Texture6].
+//!
+//! The shader program is selected from the [Nufx] to use all of the provided textures.
+//! Any other parameters required by the shader are assigned default values.
+/*!
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::prelude::*;
+use ssbh_data::matl_data::texture_set::{material_from_textures, TextureSet};
+use ssbh_lib::formats::nufx::Nufx;
+
+let nufx = Nufx::from_file("nuc2effectlibrary.nufxlb")?;
+
+let textures = TextureSet::from_names(&["body_col.nutexb", "body_nor.nutexb", "body_prm.nutexb"]);
+let entry = material_from_textures("body", &textures, &nufx)?;
+
+let matl = MatlData {
+    major_version: 1,
+    minor_version: 6,
+    entries: vec![entry],
+};
+matl.write_to_file("model.numatb")?;
+# Ok(()) }
+```
+ */
+use ssbh_lib::formats::nufx::{MaterialParameter, Nufx};
+use ssbh_lib::Vector4;
+
+use super::param_families::{param_family, param_id_from_value, ParamFamily};
+use super::shader_label::{RenderPass, ShaderLabel};
+use super::{
+    BlendStateData, MatlEntryData, ParamData, ParamId, RasterizerStateData, SamplerData,
+    UvTransform,
+};
+
+pub mod error {
+    use thiserror::Error;
+
+    /// Errors while creating a material from a texture set.
+    #[derive(Debug, Error, PartialEq, Eq)]
+    pub enum Error {
+        /// No opaque shader program uses all of the textures in the set.
+        #[error("no shader program found that uses textures {:?}", textures)]
+        NoMatchingShader { textures: Vec<String> },
+    }
+}
+
+/// Texture names for each texture type in a material.
+/// Names should not include the file extension like `"def_mario_001_col"`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextureSet {
+    /// The base color texture ending in `"_col"`.
+    pub col: Option<String>,
+    /// The normal map texture ending in `"_nor"`.
+    pub nor: Option<String>,
+    /// The PRM texture ending in `"_prm"`.
+    pub prm: Option<String>,
+    /// The emissive texture ending in `"_emi"`.
+    pub emi: Option<String>,
+}
+
+impl TextureSet {
+    /// Assigns each name to a texture type based on its suffix.
+    /// File extensions are removed, and names with an unrecognized suffix are ignored.
+    /// If multiple names have the same suffix, the first name is used.
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Self {
+        let mut textures = Self::default();
+        for name in names {
+            let name = name.as_ref();
+            let name = name.split_once('.').map(|(n, _)| n).unwrap_or(name);
+
+            let texture = match name.to_lowercase() {
+                n if n.ends_with("_col") => &mut textures.col,
+                n if n.ends_with("_nor") => &mut textures.nor,
+                n if n.ends_with("_prm") => &mut textures.prm,
+                n if n.ends_with("_emi") => &mut textures.emi,
+                _ => continue,
+            };
+            texture.get_or_insert_with(|| name.to_string());
+        }
+        textures
+    }
+
+    /// The provided textures and their corresponding parameter.
+    pub fn textures(&self) -> Vec<(ParamId, &str)> {
+        [
+            (ParamId::Texture0, &self.col),
+            (ParamId::Texture4, &self.nor),
+            (ParamId::Texture5, &self.emi),
+            (ParamId::Texture6, &self.prm),
+        ]
+        .into_iter()
+        .filter_map(|(p, t)| t.as_deref().map(|t| (p, t)))
+        .collect()
+    }
+}
+
+/// Creates a material using the opaque shader program in `nufx` with the fewest parameters
+/// that uses all of the textures in `textures`.
+pub fn material_from_textures(
+    material_label: &str,
+    textures: &TextureSet,
+    nufx: &Nufx,
+) -> Result<MatlEntryData, error::Error> {
+    let texture_params = textures.textures();
+
+    let (shader_label, parameters) = opaque_programs(nufx)
+        .into_iter()
+        .filter(|(_, parameters)| {
+            texture_params
+                .iter()
+                .all(|(p, _)| parameters.iter().any(|r| r.param_id == *p as u64))
+        })
+        .min_by_key(|(_, parameters)| parameters.len())
+        .ok_or_else(|| error::Error::NoMatchingShader {
+            textures: texture_params.iter().map(|(_, t)| t.to_string()).collect(),
+        })?;
+
+    let mut entry = MatlEntryData {
+        material_label: material_label.to_string(),
+        shader_label,
+        blend_states: Vec::new(),
+        floats: Vec::new(),
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: Vec::new(),
+        textures: Vec::new(),
+        uv_transforms: Vec::new(),
+    };
+
+    for parameter in parameters {
+        if let Some(param_id) = param_id_from_value(parameter.param_id) {
+            add_default_param(&mut entry, param_id, &texture_params);
+        }
+    }
+
+    Ok(entry)
+}
+
+fn opaque_programs(nufx: &Nufx) -> Vec<(String, &[MaterialParameter])> {
+    let programs: Vec<_> = match nufx {
+        Nufx::V0(v0) => v0
+            .programs
+            .elements
+            .iter()
+            .map(|p| {
+                (
+                    p.name.to_string_lossy(),
+                    p.material_parameters.elements.as_slice(),
+                )
+            })
+            .collect(),
+        Nufx::V1(v1) => v1
+            .programs
+            .elements
+            .iter()
+            .map(|p| {
+                (
+                    p.name.to_string_lossy(),
+                    p.material_parameters.elements.as_slice(),
+                )
+            })
+            .collect(),
+    };
+
+    programs
+        .into_iter()
+        .filter(|(name, _)| {
+            name.parse::<ShaderLabel>()
+                .map(|l| l.render_pass == RenderPass::Opaque)
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+fn add_default_param(entry: &mut MatlEntryData, param_id: ParamId, textures: &[(ParamId, &str)]) {
+    let Some((family, _)) = param_family(param_id) else {
+        return;
+    };
+
+    match family {
+        ParamFamily::Texture => {
+            let texture = textures
+                .iter()
+                .find(|(p, _)| *p == param_id)
+                .map(|(_, t)| t.to_string())
+                .unwrap_or_else(|| default_texture(param_id).to_string());
+            entry.textures.push(ParamData::new(param_id, texture));
+        }
+        ParamFamily::Sampler => entry
+            .samplers
+            .push(ParamData::new(param_id, SamplerData::default())),
+        ParamFamily::CustomVector => entry
+            .vectors
+            .push(ParamData::new(param_id, default_vector(param_id))),
+        ParamFamily::CustomFloat => entry.floats.push(ParamData::new(param_id, 0.0)),
+        ParamFamily::CustomBoolean => entry.booleans.push(ParamData::new(param_id, false)),
+        ParamFamily::BlendState => entry
+            .blend_states
+            .push(ParamData::new(param_id, BlendStateData::default())),
+        ParamFamily::RasterizerState => entry
+            .rasterizer_states
+            .push(ParamData::new(param_id, RasterizerStateData::default())),
+        ParamFamily::UvTransform => entry.uv_transforms.push(ParamData::new(
+            param_id,
+            UvTransform {
+                scale_u: 1.0,
+                scale_v: 1.0,
+                rotation: 0.0,
+                translate_u: 0.0,
+                translate_v: 0.0,
+            },
+        )),
+    }
+}
+
+fn default_texture(param_id: ParamId) -> &'static str {
+    // Use the placeholder textures from the game's common shader folder.
+    match param_id {
+        ParamId::Texture4 => "/common/shader/sfxpbs/default_normal",
+        ParamId::Texture5 => "/common/shader/sfxpbs/default_black",
+        ParamId::Texture6 => "/common/shader/sfxpbs/default_params",
+        ParamId::Texture7 => "#replace_cubemap",
+        _ => "/common/shader/sfxpbs/default_white",
+    }
+}
+
+fn default_vector(param_id: ParamId) -> Vector4 {
+    match param_id {
+        // Color multipliers should not affect the final color.
+        ParamId::CustomVector8 | ParamId::CustomVector13 | ParamId::CustomVector14 => {
+            Vector4::new(1.0, 1.0, 1.0, 1.0)
+        }
+        _ => Vector4::new(0.0, 0.0, 0.0, 0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_lib::formats::nufx::{NufxV1, ShaderProgramV1, ShaderStages};
+    use ssbh_lib::SsbhArray;
+
+    fn program(name: &str, param_ids: &[(ParamId, &str)]) -> ShaderProgramV1 {
+        ShaderProgramV1 {
+            name: name.into(),
+            render_pass: "nu::Opaque".into(),
+            shaders: ShaderStages {
+                vertex_shader: "".into(),
+                unk_shader1: "".into(),
+                unk_shader2: "".into(),
+                geometry_shader: "".into(),
+                pixel_shader: "".into(),
+                compute_shader: "".into(),
+            },
+            vertex_attributes: SsbhArray::new(),
+            material_parameters: SsbhArray::from_vec(
+                param_ids
+                    .iter()
+                    .map(|(p, name)| MaterialParameter {
+                        param_id: *p as u64,
+                        parameter_name: (*name).into(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn nufx() -> Nufx {
+        Nufx::V1(NufxV1 {
+            programs: SsbhArray::from_vec(vec![
+                program(
+                    "SFX_PBS_1_opaque",
+                    &[
+                        (ParamId::Texture0, "Texture0"),
+                        (ParamId::Texture4, "Texture4"),
+                        (ParamId::Texture5, "Texture5"),
+                        (ParamId::Texture6, "Texture6"),
+                        (ParamId::Sampler0, "Sampler0"),
+                        (ParamId::CustomVector13, "CustomVector13"),
+                        (ParamId::BlendState0, "BlendState0"),
+                    ],
+                ),
+                program("SFX_PBS_0_sort", &[(ParamId::Texture0, "Texture0")]),
+                program(
+                    "SFX_PBS_0_opaque",
+                    &[
+                        (ParamId::Texture0, "Texture0"),
+                        (ParamId::Texture4, "Texture4"),
+                        (ParamId::Texture6, "Texture6"),
+                        (ParamId::CustomFloat8, "CustomFloat8"),
+                    ],
+                ),
+            ]),
+            unk_string_list: SsbhArray::new(),
+        })
+    }
+
+    #[test]
+    fn texture_set_from_names() {
+        assert_eq!(
+            TextureSet {
+                col: Some("a_col".to_string()),
+                nor: Some("a_NOR".to_string()),
+                prm: None,
+                emi: Some("a_emi".to_string()),
+            },
+            TextureSet::from_names(&["a_col.nutexb", "b_col", "a_NOR", "a_emi", "a_gao"])
+        );
+    }
+
+    #[test]
+    fn material_without_emission() {
+        let textures = TextureSet::from_names(&["a_col", "a_prm"]);
+        let entry = material_from_textures("a", &textures, &nufx()).unwrap();
+
+        assert_eq!("a", entry.material_label);
+        assert_eq!("SFX_PBS_0_opaque", entry.shader_label);
+        assert_eq!(
+            vec![
+                ParamData::new(ParamId::Texture0, "a_col".to_string()),
+                ParamData::new(
+                    ParamId::Texture4,
+                    "/common/shader/sfxpbs/default_normal".to_string()
+                ),
+                ParamData::new(ParamId::Texture6, "a_prm".to_string()),
+            ],
+            entry.textures
+        );
+        assert_eq!(
+            vec![ParamData::new(ParamId::CustomFloat8, 0.0)],
+            entry.floats
+        );
+    }
+
+    #[test]
+    fn material_with_emission() {
+        let textures = TextureSet::from_names(&["a_col", "a_emi"]);
+        let entry = material_from_textures("a", &textures, &nufx()).unwrap();
+
+        assert_eq!("SFX_PBS_1_opaque", entry.shader_label);
+        assert_eq!(4, entry.textures.len());
+        assert_eq!(1, entry.samplers.len());
+        assert_eq!(1, entry.blend_states.len());
+        assert_eq!(
+            vec![ParamData::new(
+                ParamId::CustomVector13,
+                Vector4::new(1.0, 1.0, 1.0, 1.0)
+            )],
+            entry.vectors
+        );
+    }
+
+    #[test]
+    fn material_no_matching_shader() {
+        let nufx = Nufx::V1(NufxV1 {
+            programs: SsbhArray::from_vec(vec![program(
+                "SFX_PBS_0_sort",
+                &[(ParamId::Texture0, "Texture0")],
+            )]),
+            unk_string_list: SsbhArray::new(),
+        });
+
+        assert_eq!(
+            Err(error::Error::NoMatchingShader {
+                textures: vec!["a_col".to_string()]
+            }),
+            material_from_textures("a", &TextureSet::from_names(&["a_col"]), &nufx)
+        );
+    }
+}
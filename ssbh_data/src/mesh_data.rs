@@ -22,6 +22,7 @@
 //! Bounding information is recalculated on export and is unlikely to match the original file
 //! due to algorithmic differences and floating point errors.
 //! The current algorithm is efficient but often overestimates the required bounding sphere size.
+use crate::context::{ErrorContext, ResultExt};
 use ahash::{AHashMap, AHashSet};
 use binrw::io::Seek;
 use binrw::{io::Cursor, BinRead};
@@ -50,25 +51,109 @@ use std::io::{Read, SeekFrom};
 use std::{error::Error, io::Write};
 
 mod vector_data;
-pub use vector_data::VectorData;
+pub use vector_data::{read_attribute, VectorData};
 
 mod mesh_attributes;
 use mesh_attributes::*;
 
-// A union of data types across all mesh versions.
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub(crate) enum DataType {
+mod weight_transfer;
+pub use weight_transfer::{transfer_bone_influences, WeightTransferMethod};
+
+mod stats;
+pub use stats::{AttributeStats, MeshObjectStats, MeshStats};
+
+mod skinning;
+pub use skinning::{skin_mesh, PosedMeshObject};
+
+mod color_sets;
+pub use color_sets::ColorSetRangeError;
+
+mod precision;
+pub use precision::{AttributePrecision, ReducedPrecision};
+
+mod prune;
+pub use prune::RemovedAttribute;
+
+mod version;
+pub use version::LossyConversion;
+
+mod requantize;
+pub use requantize::RequantizedAttribute;
+
+mod mirror;
+pub use mirror::{mirrored_bone_name, MirrorAxis};
+
+mod seams;
+
+#[cfg(any(feature = "obj", feature = "ply"))]
+mod import;
+#[cfg(feature = "obj")]
+pub use import::{read_obj, read_obj_file};
+#[cfg(feature = "ply")]
+pub use import::{read_ply, read_ply_file};
+
+/// The storage format for vertex indices when converting [MeshData] to [Mesh].
+/// See [MeshData::to_mesh_with_index_format].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum IndexFormat {
+    /// Use [DrawElementType::UnsignedShort] if all of an object's indices fit in a [u16]
+    /// and [DrawElementType::UnsignedInt] otherwise.
+    #[default]
+    Smallest,
+    /// Always use [DrawElementType::UnsignedShort] and return an error if an index does not fit in a [u16].
+    UnsignedShort,
+    /// Always use [DrawElementType::UnsignedInt].
+    UnsignedInt,
+}
+
+/// A union of attribute data types across all mesh versions.
+/// See [read_attribute].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DataType {
+    /// 2 little endian [f32] components.
     Float2,
+    /// 3 little endian [f32] components.
     Float3,
+    /// 4 little endian [f32] components.
     Float4,
+    /// 2 little endian [f16] components.
     HalfFloat2,
+    /// 4 little endian [f16] components.
     HalfFloat4,
+    /// 4 [u8] components normalized to the range `0.0` to `1.0`.
     Byte4,
 }
 
-// A union of usages across all mesh versions.
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum AttributeUsage {
+impl DataType {
+    /// The size in bytes of a single vector.
+    pub fn size_in_bytes(&self) -> u64 {
+        match self {
+            DataType::Float2 => 8,
+            DataType::Float3 => 12,
+            DataType::Float4 => 16,
+            DataType::HalfFloat2 => 4,
+            DataType::HalfFloat4 => 8,
+            DataType::Byte4 => 4,
+        }
+    }
+}
+
+/// The number of vertex buffers each [MeshObject] can reference.
+/// Each [MeshObject] stores an offset and stride for exactly this many buffers
+/// even if the buffer contains no attribute data,
+/// so attributes must use a buffer index less than this value.
+///
+/// A [Mesh] always has at least this many vertex buffers but may contain more.
+/// See [MeshData::to_mesh_with_buffer_count].
+pub const VERTEX_BUFFER_COUNT: usize = 4;
+
+/// A union of attribute usages across all mesh versions.
+/// Each usage corresponds to a collection in [MeshObjectData] like [positions](struct.MeshObjectData.html#structfield.positions).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum AttributeUsage {
     Position,
     Normal,
     Binormal,
@@ -100,6 +185,13 @@ pub mod error {
             vertex_count: usize,
         },
 
+        /// A vertex index is too large to store with [IndexFormat::UnsignedShort](super::IndexFormat::UnsignedShort).
+        #[error(
+            "vertex index {} is too large to store as an unsigned short",
+            vertex_index
+        )]
+        UnsignedShortIndexOutOfRange { vertex_index: usize },
+
         #[error(
             "vertex index count {} is not a multiple of 3. Only triangles are supported",
             vertex_index_count
@@ -141,6 +233,70 @@ pub mod error {
             mesh_object_subindex: u64,
         },
 
+        /// An attribute for the mesh object is not assigned a buffer in the layout.
+        #[error(
+            "attribute {} for mesh {} is missing from the layout",
+            attribute_name,
+            mesh_object_name
+        )]
+        MissingAttributeLayout {
+            mesh_object_name: String,
+            attribute_name: String,
+        },
+
+        /// The layout contains an attribute not present in the mesh object or repeats an attribute.
+        #[error(
+            "layout attribute {} does not match a unique attribute for mesh {}",
+            attribute_name,
+            mesh_object_name
+        )]
+        InvalidAttributeLayout {
+            mesh_object_name: String,
+            attribute_name: String,
+        },
+
+        /// The layout uses a vertex buffer that can't store attribute data.
+        #[error(
+            "buffer index {} is not supported for attribute layouts. Expected a value less than {}",
+            buffer_index,
+            super::VERTEX_BUFFER_COUNT
+        )]
+        UnsupportedLayoutBufferIndex { buffer_index: u32 },
+
+        /// The layout data type does not have the same number of components as the attribute data.
+        #[error(
+            "data type {:?} for attribute {} for mesh {} does not support {} components",
+            data_type,
+            attribute_name,
+            mesh_object_name,
+            component_count
+        )]
+        IncompatibleAttributeDataType {
+            mesh_object_name: String,
+            attribute_name: String,
+            data_type: super::DataType,
+            component_count: usize,
+        },
+
+        /// The mesh object has vertices but no position attribute.
+        #[error("mesh {} has no position attribute", mesh_object_name)]
+        MissingPositions { mesh_object_name: String },
+
+        /// The number of bone transforms does not match the number of bones in the skeleton.
+        #[error(
+            "expected {} bone transforms but found {}",
+            bone_count,
+            transform_count
+        )]
+        BoneTransformCountMismatch {
+            transform_count: usize,
+            bone_count: usize,
+        },
+
+        /// An error occurred while calculating bone transforms.
+        #[error(transparent)]
+        BoneTransform(#[from] crate::skel_data::BoneTransformError),
+
         /// An error occurred while writing data to a buffer.
         #[error(transparent)]
         Io(#[from] std::io::Error),
@@ -160,6 +316,21 @@ pub mod error {
             buffer_count: usize,
         },
 
+        /// The attribute data extends past the end of each vertex in the buffer.
+        #[error(
+            "attribute {} with offset {} and size {} does not fit in a stride of {} bytes",
+            attribute_name,
+            offset,
+            size,
+            stride
+        )]
+        OffsetExceedsStride {
+            attribute_name: String,
+            offset: u64,
+            size: u64,
+            stride: u64,
+        },
+
         /// Failed to find the offset or stride in bytes for the given buffer index.
         #[error("found index {0}. Buffer indices higher than 4 are not supported")]
         NoOffsetOrStride(u64),
@@ -172,6 +343,23 @@ pub mod error {
         #[error(transparent)]
         BinRead(#[from] binrw::error::Error),
     }
+
+    /// Errors while importing [MeshObjectData](super::MeshObjectData) from OBJ or PLY files.
+    #[cfg(any(feature = "obj", feature = "ply"))]
+    #[derive(Debug, Error)]
+    pub enum ImportError {
+        /// The file contents could not be parsed.
+        #[error("line {line}: {message}")]
+        Parse { line: usize, message: String },
+
+        /// A face references an element that does not exist.
+        #[error("index {} is out of range for a collection of size {}", index, count)]
+        IndexOutOfRange { index: i64, count: usize },
+
+        /// An error occurred while reading the file.
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
 }
 
 /// Assigns a weight to a particular vertex.
@@ -396,6 +584,7 @@ fn read_attribute_data<A: Attribute, W: Weight>(
 
     let (offset, stride) = calculate_offset_stride(attribute, mesh_object)?;
     let count = mesh_object.vertex_count as usize;
+
     let mut reader = Cursor::new(&attribute_buffer.elements);
 
     VectorData::read(&mut reader, count, offset, stride, attribute.data_type).map_err(Into::into)
@@ -431,10 +620,14 @@ fn read_attributes<A: Attribute, W: Weight>(
     mesh: &MeshInner<A, W>,
     mesh_object: &MeshObject<A>,
     usage: AttributeUsage,
-) -> Result<Vec<AttributeData>, error::AttributeError> {
+) -> Result<Vec<AttributeData>, Box<dyn Error>> {
     let mut attributes = Vec::new();
     for attribute in &get_attributes(mesh_object, usage) {
-        let data = read_attribute_data(mesh, mesh_object, attribute)?;
+        let data = read_attribute_data(mesh, mesh_object, attribute).context(|| {
+            ErrorContext::Attribute {
+                name: attribute.name.clone(),
+            }
+        })?;
         attributes.push(AttributeData {
             name: attribute.name.to_string(),
             data,
@@ -443,7 +636,86 @@ fn read_attributes<A: Attribute, W: Weight>(
     Ok(attributes)
 }
 
-// TODO: Find ways to test this?
+/// Checks that each attribute in `mesh` fits within the stride of its vertex buffer.
+/// Returns an error for the first attribute where the attribute's offset plus its size exceeds the stride.
+///
+/// Attributes that don't fit in the stride overlap the data for the next vertex.
+/// This isn't checked when converting to [MeshData] since the game may still load these meshes.
+/// Buffers with a stride of 0 are skipped since every vertex reads the same data.
+pub fn validate_attribute_strides(mesh: &Mesh) -> Result<(), error::AttributeError> {
+    match mesh {
+        Mesh::V8(mesh) => validate_attribute_strides_inner(&mesh.objects.elements),
+        Mesh::V9(mesh) => validate_attribute_strides_inner(&mesh.objects.elements),
+        Mesh::V10(mesh) => validate_attribute_strides_inner(&mesh.objects.elements),
+    }
+}
+
+fn validate_attribute_strides_inner<A: Attribute>(
+    objects: &[MeshObject<A>],
+) -> Result<(), error::AttributeError> {
+    for mesh_object in objects.iter().filter(|o| o.vertex_count > 1) {
+        for attribute in mesh_object
+            .attributes
+            .elements
+            .iter()
+            .map(Attribute::to_attribute)
+        {
+            let (_, stride) = calculate_offset_stride(&attribute, mesh_object)?;
+            let size = attribute.data_type.size_in_bytes();
+            if stride > 0 && attribute.offset + size > stride {
+                return Err(error::AttributeError::OffsetExceedsStride {
+                    attribute_name: attribute.name,
+                    offset: attribute.offset,
+                    size,
+                    stride,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the bone influences for the mesh object in `mesh` with the given name and subindex
+/// without decoding any vertex buffers or index data.
+/// This is faster than converting to [MeshData] for applications that only edit skin weights.
+/// Mesh objects without any rigging data or that aren't in `mesh` have no influences.
+/**
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::mesh_data::read_bone_influences;
+use ssbh_lib::formats::mesh::Mesh;
+
+let mesh = Mesh::from_file("model.numshb")?;
+for influence in read_bone_influences(&mesh, "body", 0)? {
+    println!("{}: {}", influence.bone_name, influence.vertex_weights.len());
+}
+# Ok(()) }
+```
+ */
+pub fn read_bone_influences(
+    mesh: &Mesh,
+    mesh_object_name: &str,
+    mesh_object_subindex: u64,
+) -> Result<Vec<BoneInfluence>, Box<dyn Error>> {
+    match mesh {
+        Mesh::V8(mesh) => read_rigging_data(
+            &mesh.rigging_buffers.elements,
+            mesh_object_name,
+            mesh_object_subindex,
+        ),
+        Mesh::V9(mesh) => read_rigging_data(
+            &mesh.rigging_buffers.elements,
+            mesh_object_name,
+            mesh_object_subindex,
+        ),
+        Mesh::V10(mesh) => read_rigging_data(
+            &mesh.rigging_buffers.elements,
+            mesh_object_name,
+            mesh_object_subindex,
+        ),
+    }
+}
+
 fn read_rigging_data<W: Weight>(
     rigging_buffers: &[RiggingGroup<W>],
     mesh_object_name: &str,
@@ -491,7 +763,7 @@ impl TryFrom<MeshData> for Mesh {
     type Error = error::Error;
 
     fn try_from(data: MeshData) -> Result<Self, Self::Error> {
-        create_mesh(&data)
+        create_mesh(&data, IndexFormat::Smallest, VERTEX_BUFFER_COUNT)
     }
 }
 
@@ -499,7 +771,7 @@ impl TryFrom<&MeshData> for Mesh {
     type Error = error::Error;
 
     fn try_from(data: &MeshData) -> Result<Self, Self::Error> {
-        create_mesh(data)
+        create_mesh(data, IndexFormat::Smallest, VERTEX_BUFFER_COUNT)
     }
 }
 
@@ -559,11 +831,17 @@ pub struct MeshObjectData {
     /// The name of this object.
     pub name: String,
     /// An additional identifier to differentiate multiple [MeshObjectData] with the same name.
+    // Older versions used "sub_index".
+    #[cfg_attr(feature = "serde", serde(alias = "sub_index"))]
     pub subindex: u64,
     /// The name of the parent bone or an empty string if there is no parent bone.
     pub parent_bone_name: String,
+    // The depth and sorting fields aren't present in older versions.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub sort_bias: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub disable_depth_write: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub disable_depth_test: bool,
     /// Vertex indices for the data for all [AttributeData] for this [MeshObjectData].
     pub vertex_indices: Vec<u32>,
@@ -589,7 +867,206 @@ pub struct AttributeData {
     pub data: VectorData,
 }
 
+/// The usage and vertex buffer for an attribute in a custom vertex buffer layout.
+/// See [MeshData::to_mesh_with_layout].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AttributeLayout {
+    /// The collection in [MeshObjectData] containing the attribute.
+    pub usage: AttributeUsage,
+    /// The [name](struct.AttributeData.html#structfield.name) of the attribute.
+    pub name: String,
+    /// The index of the vertex buffer for the attribute data.
+    /// This must be less than [VERTEX_BUFFER_COUNT].
+    ///
+    /// Buffers 0 and 1 are used by in game meshes.
+    /// Buffer 2 is only written for objects that assign attributes to it.
+    pub buffer_index: u32,
+    /// The storage format for the attribute data or `None` to use the default format for the usage.
+    /// The data type must have the same number of components as the attribute data.
+    pub data_type: Option<DataType>,
+}
+
+/// The layout used when converting [MeshData] to [Mesh] without a custom layout.
+///
+/// Positions, normals, binormals, and tangents are stored in buffer 0.
+/// Texture coordinates and color sets are stored in buffer 1.
+/// Attributes are ordered by usage and then by their order in [MeshObjectData].
+pub fn default_attribute_layout(data: &MeshObjectData) -> Vec<AttributeLayout> {
+    let layout = |attributes: &[AttributeData], usage, buffer_index| {
+        attributes
+            .iter()
+            .map(move |a| AttributeLayout {
+                usage,
+                name: a.name.clone(),
+                buffer_index,
+                data_type: None,
+            })
+            .collect_vec()
+    };
+
+    [
+        layout(&data.positions, AttributeUsage::Position, 0),
+        layout(&data.normals, AttributeUsage::Normal, 0),
+        layout(&data.binormals, AttributeUsage::Binormal, 0),
+        layout(&data.tangents, AttributeUsage::Tangent, 0),
+        layout(
+            &data.texture_coordinates,
+            AttributeUsage::TextureCoordinate,
+            1,
+        ),
+        layout(&data.color_sets, AttributeUsage::ColorSet, 1),
+    ]
+    .concat()
+}
+
+impl MeshData {
+    /// Creates a version 1.10 [Mesh] using the attribute layout from `layout` for each object.
+    /// The attributes for each buffer are interleaved in the order they appear in the layout.
+    ///
+    /// Every attribute in the object must appear exactly once in the returned layout.
+    /// The buffer strides and attribute offsets are calculated from the layout.
+    /// # Examples
+    /// Store all attributes in a single buffer using the default attribute order.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{default_attribute_layout, MeshData};
+
+    # let mesh_data = MeshData { major_version: 1, minor_version: 10, objects: Vec::new() };
+    let mesh = mesh_data.to_mesh_with_layout(|o| {
+        default_attribute_layout(o)
+            .into_iter()
+            .map(|mut l| {
+                l.buffer_index = 0;
+                l
+            })
+            .collect()
+    })?;
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn to_mesh_with_layout<F: Fn(&MeshObjectData) -> Vec<AttributeLayout>>(
+        &self,
+        layout: F,
+    ) -> Result<Mesh, error::Error> {
+        if (self.major_version, self.minor_version) != (1, 10) {
+            return Err(error::Error::UnsupportedVersion {
+                major_version: self.major_version,
+                minor_version: self.minor_version,
+            });
+        }
+
+        validate_mesh_object_subindices(&self.objects)?;
+
+        Ok(Mesh::V10(create_mesh_inner(
+            &all_positions(self),
+            create_mesh_objects(&self.objects, IndexFormat::Smallest, |o| {
+                create_attributes_v10_with_layout(o, &layout(o))
+            })?,
+            self,
+            VERTEX_BUFFER_COUNT,
+        )?))
+    }
+
+    /// Creates a [Mesh] like [Mesh::try_from] but with vertex indices stored using `index_format`.
+    /// Converting with [IndexFormat::Smallest] is identical to [Mesh::try_from].
+    ///
+    /// Some tools only support a single index type,
+    /// so this avoids the format silently changing when an object grows past [u16::MAX] vertices.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{IndexFormat, MeshData};
+
+    # let mesh_data = MeshData { major_version: 1, minor_version: 10, objects: Vec::new() };
+    let mesh = mesh_data.to_mesh_with_index_format(IndexFormat::UnsignedInt)?;
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn to_mesh_with_index_format(
+        &self,
+        index_format: IndexFormat,
+    ) -> Result<Mesh, error::Error> {
+        create_mesh(self, index_format, VERTEX_BUFFER_COUNT)
+    }
+
+    /// Creates a [Mesh] like [Mesh::try_from] but with `buffer_count` many vertex buffers.
+    /// Converting with a `buffer_count` of [VERTEX_BUFFER_COUNT] or less is identical to [Mesh::try_from].
+    ///
+    /// Attributes are only written to the first [VERTEX_BUFFER_COUNT] buffers
+    /// since each [MeshObject] only stores offsets and strides for those buffers.
+    /// The additional buffers are empty.
+    /// This preserves the buffer count for files with more buffers than usual.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::MeshData;
+    use ssbh_lib::formats::mesh::Mesh;
+
+    # let mesh_data = MeshData { major_version: 1, minor_version: 10, objects: Vec::new() };
+    let mesh = mesh_data.to_mesh_with_buffer_count(6)?;
+    match mesh {
+        Mesh::V10(mesh) => assert_eq!(6, mesh.vertex_buffers.elements.len()),
+        _ => unreachable!(),
+    }
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn to_mesh_with_buffer_count(&self, buffer_count: usize) -> Result<Mesh, error::Error> {
+        create_mesh(self, IndexFormat::Smallest, buffer_count)
+    }
+
+    /// Applies [transform_texture_coordinates] to the [texture_coordinates](struct.MeshObjectData.html#structfield.texture_coordinates)
+    /// named `attribute_name` for every object.
+    /// Objects without the attribute are not changed.
+    /// Returns the number of modified objects.
+    ///
+    /// Applying the same transform to all objects keeps UVs consistent after repacking a texture atlas.
+    pub fn transform_texture_coordinates(
+        &mut self,
+        attribute_name: &str,
+        scale: [f32; 2],
+        offset: [f32; 2],
+    ) -> usize {
+        self.objects
+            .iter_mut()
+            .map(|o| o.transform_texture_coordinates(attribute_name, scale, offset))
+            .filter(|found| *found)
+            .count()
+    }
+}
+
+impl MeshData {
+    /// Sorts the [objects](#structfield.objects) by name and then by subindex.
+    ///
+    /// Other files like [AdjData](crate::adj_data::AdjData) may refer to objects by index
+    /// and will need to be updated after sorting.
+    pub fn sort_objects(&mut self) {
+        self.objects
+            .sort_by(|a, b| (&a.name, a.subindex).cmp(&(&b.name, b.subindex)));
+    }
+}
+
 impl MeshObjectData {
+    /// Applies [transform_texture_coordinates] to the [texture_coordinates](#structfield.texture_coordinates)
+    /// named `attribute_name`.
+    /// Returns `true` if the attribute was found.
+    pub fn transform_texture_coordinates(
+        &mut self,
+        attribute_name: &str,
+        scale: [f32; 2],
+        offset: [f32; 2],
+    ) -> bool {
+        match self
+            .texture_coordinates
+            .iter_mut()
+            .find(|a| a.name == attribute_name)
+        {
+            Some(attribute) => {
+                attribute.data = transform_texture_coordinates(&attribute.data, scale, offset);
+                true
+            }
+            None => false,
+        }
+    }
+
     // TODO: Document error conditions.
     // TODO: Tests?
     /// Calculates the vertex count.
@@ -632,75 +1109,88 @@ fn read_mesh_objects(mesh: &Mesh) -> Result<Vec<MeshObjectData>, Box<dyn Error>>
 fn read_mesh_objects_inner<A: Attribute, W: Weight>(
     mesh: &MeshInner<A, W>,
 ) -> Result<Vec<MeshObjectData>, Box<dyn Error>> {
-    let mut mesh_objects = Vec::new();
-    for mesh_object in &mesh.objects.elements {
-        let name = mesh_object.name.to_string_lossy();
-
-        let indices = read_vertex_indices(&mesh.index_buffer.elements, mesh_object)?;
-        let positions = read_attributes(mesh, mesh_object, AttributeUsage::Position)?;
-        let normals = read_attributes(mesh, mesh_object, AttributeUsage::Normal)?;
-        let tangents = read_attributes(mesh, mesh_object, AttributeUsage::Tangent)?;
-        let binormals = read_attributes(mesh, mesh_object, AttributeUsage::Binormal)?;
-        let texture_coordinates =
-            read_attributes(mesh, mesh_object, AttributeUsage::TextureCoordinate)?;
-        let color_sets = read_attributes(mesh, mesh_object, AttributeUsage::ColorSet)?;
-        let bone_influences =
-            read_rigging_data(&mesh.rigging_buffers.elements, &name, mesh_object.subindex)?;
-
-        let data = MeshObjectData {
-            name,
-            subindex: mesh_object.subindex,
-            parent_bone_name: mesh_object
-                .parent_bone_name
-                .to_str()
-                .unwrap_or("")
-                .to_string(),
-            vertex_indices: indices,
-            positions,
-            normals,
-            tangents,
-            binormals,
-            texture_coordinates,
-            color_sets,
-            bone_influences,
-            sort_bias: mesh_object.sort_bias,
-            disable_depth_test: mesh_object.depth_flags.disable_depth_test != 0,
-            disable_depth_write: mesh_object.depth_flags.disable_depth_write != 0,
-        };
+    mesh.objects
+        .elements
+        .iter()
+        .enumerate()
+        .map(|(i, mesh_object)| {
+            read_mesh_object(mesh, mesh_object).context(|| ErrorContext::MeshObject {
+                index: i,
+                name: mesh_object.name.to_string_lossy(),
+                subindex: mesh_object.subindex,
+            })
+        })
+        .collect()
+}
 
-        mesh_objects.push(data);
-    }
-    Ok(mesh_objects)
+fn read_mesh_object<A: Attribute, W: Weight>(
+    mesh: &MeshInner<A, W>,
+    mesh_object: &MeshObject<A>,
+) -> Result<MeshObjectData, Box<dyn Error>> {
+    let name = mesh_object.name.to_string_lossy();
+
+    let indices = read_vertex_indices(&mesh.index_buffer.elements, mesh_object)?;
+    let positions = read_attributes(mesh, mesh_object, AttributeUsage::Position)?;
+    let normals = read_attributes(mesh, mesh_object, AttributeUsage::Normal)?;
+    let tangents = read_attributes(mesh, mesh_object, AttributeUsage::Tangent)?;
+    let binormals = read_attributes(mesh, mesh_object, AttributeUsage::Binormal)?;
+    let texture_coordinates =
+        read_attributes(mesh, mesh_object, AttributeUsage::TextureCoordinate)?;
+    let color_sets = read_attributes(mesh, mesh_object, AttributeUsage::ColorSet)?;
+    let bone_influences =
+        read_rigging_data(&mesh.rigging_buffers.elements, &name, mesh_object.subindex)?;
+
+    Ok(MeshObjectData {
+        name,
+        subindex: mesh_object.subindex,
+        parent_bone_name: mesh_object
+            .parent_bone_name
+            .to_str()
+            .unwrap_or("")
+            .to_string(),
+        vertex_indices: indices,
+        positions,
+        normals,
+        tangents,
+        binormals,
+        texture_coordinates,
+        color_sets,
+        bone_influences,
+        sort_bias: mesh_object.sort_bias,
+        disable_depth_test: mesh_object.depth_flags.disable_depth_test != 0,
+        disable_depth_write: mesh_object.depth_flags.disable_depth_write != 0,
+    })
 }
 
-fn create_mesh(data: &MeshData) -> Result<Mesh, error::Error> {
+fn create_mesh(
+    data: &MeshData,
+    index_format: IndexFormat,
+    buffer_count: usize,
+) -> Result<Mesh, error::Error> {
     validate_mesh_object_subindices(&data.objects)?;
 
-    // TODO: It might be more efficient to reuse the data for mesh object bounding or reuse the generated points.
-    let all_positions: Vec<geometry_tools::glam::Vec3A> = data
-        .objects
-        .iter()
-        .flat_map(|o| match o.positions.first() {
-            Some(attribute) => attribute.data.to_glam_vec3a(),
-            None => Vec::new(),
-        })
-        .collect();
+    let all_positions = all_positions(data);
 
     match (data.major_version, data.minor_version) {
         (1, 10) => Ok(Mesh::V10(create_mesh_inner(
             &all_positions,
-            create_mesh_objects(&data.objects, create_attributes_v10)?,
+            create_mesh_objects(&data.objects, index_format, |o| {
+                Ok(create_attributes_v10(o))
+            })?,
             data,
+            buffer_count,
         )?)),
         (1, 8) => Ok(Mesh::V8(create_mesh_inner(
             &all_positions,
-            create_mesh_objects(&data.objects, create_attributes_v8)?,
+            create_mesh_objects(&data.objects, index_format, |o| Ok(create_attributes_v8(o)))?,
             data,
+            buffer_count,
         )?)),
         (1, 9) => Ok(Mesh::V9(create_mesh_inner(
             &all_positions,
-            create_mesh_objects(&data.objects, create_attributes_v9)?,
+            create_mesh_objects(&data.objects, index_format, |o| Ok(create_attributes_v9(o)))?,
             data,
+            buffer_count,
         )?)),
         _ => Err(error::Error::UnsupportedVersion {
             major_version: data.major_version,
@@ -709,28 +1199,42 @@ fn create_mesh(data: &MeshData) -> Result<Mesh, error::Error> {
     }
 }
 
+fn all_positions(data: &MeshData) -> Vec<glam::Vec3A> {
+    // TODO: It might be more efficient to reuse the data for mesh object bounding or reuse the generated points.
+    data.objects
+        .iter()
+        .flat_map(|o| match o.positions.first() {
+            Some(attribute) => attribute.data.to_glam_vec3a(),
+            None => Vec::new(),
+        })
+        .collect()
+}
+
 fn create_mesh_inner<A: Attribute, W: Weight>(
     all_positions: &[glam::Vec3A],
     mesh_vertex_data: MeshVertexData<A>,
     data: &MeshData,
+    buffer_count: usize,
 ) -> Result<MeshInner<A, W>, error::Error> {
+    // There are always at least 4 buffer entries even if only 2 are used.
+    let buffer_count = buffer_count.max(VERTEX_BUFFER_COUNT);
     Ok(MeshInner {
         model_name: "".into(),
-        bounding_info: calculate_bounding_info(all_positions),
+        bounding_info: calculate_bounding_info_vec3a(all_positions),
         unk1: 0,
         objects: mesh_vertex_data.mesh_objects.into(),
-        // There are always at least 4 buffer entries even if only 2 are used.
         buffer_sizes: mesh_vertex_data
             .vertex_buffers
             .iter()
             .map(|b| b.len() as u32)
             // TODO: This is handled differently for v1.8.
-            .pad_using(4, |_| 0u32)
+            .pad_using(buffer_count, |_| 0u32)
             .collect(),
         polygon_index_size: mesh_vertex_data.index_buffer.len() as u64,
         vertex_buffers: mesh_vertex_data
             .vertex_buffers
             .into_iter()
+            .pad_using(buffer_count, |_| Vec::new())
             .map(SsbhByteBuffer::from_vec)
             .collect(),
         index_buffer: mesh_vertex_data.index_buffer.into(),
@@ -893,8 +1397,12 @@ enum VertexIndices {
     UnsignedShort(Vec<u16>),
 }
 
-fn create_mesh_objects<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A> + Copy>(
+fn create_mesh_objects<
+    A: Attribute,
+    F: Fn(&MeshObjectData) -> Result<MeshAttributes<A>, error::Error> + Copy,
+>(
     mesh_object_data: &[MeshObjectData],
+    index_format: IndexFormat,
     create_attributes: F,
 ) -> Result<MeshVertexData<A>, error::Error> {
     let mut mesh_objects = Vec::new();
@@ -917,6 +1425,7 @@ fn create_mesh_objects<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>
             &mut [&mut buffer0, &mut buffer1, &mut buffer2, &mut buffer3],
             &mut vertex_buffer2_offset,
             &mut index_buffer,
+            index_format,
             create_attributes,
         )?;
 
@@ -935,14 +1444,18 @@ fn create_mesh_objects<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>
     })
 }
 
-fn create_mesh_object<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>>(
+fn create_mesh_object<
+    A: Attribute,
+    F: Fn(&MeshObjectData) -> Result<MeshAttributes<A>, error::Error>,
+>(
     data: &MeshObjectData,
     buffers: &mut [&mut Cursor<Vec<u8>>; 4],
     vertex_buffer2_offset: &mut u64,
     index_buffer: &mut Cursor<Vec<u8>>,
+    index_format: IndexFormat,
     create_attributes: F,
 ) -> Result<MeshObject<A>, error::Error> {
-    if data.vertex_indices.len() % 3 != 0 {
+    if !data.vertex_indices.len().is_multiple_of(3) {
         return Err(error::Error::NonTriangulatedFaces {
             vertex_index_count: data.vertex_indices.len(),
         });
@@ -961,7 +1474,7 @@ fn create_mesh_object<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>>
         }
     }
 
-    let vertex_indices = convert_indices(&data.vertex_indices);
+    let vertex_indices = convert_indices(&data.vertex_indices, index_format)?;
 
     let draw_element_type = match vertex_indices {
         VertexIndices::UnsignedInt(_) => DrawElementType::UnsignedInt,
@@ -977,7 +1490,7 @@ fn create_mesh_object<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>>
         buffer_info,
         attributes,
         use_buffer2,
-    } = create_attributes(data);
+    } = create_attributes(data)?;
 
     let stride0 = buffer_info[0].0;
     let stride1 = buffer_info[1].0;
@@ -998,8 +1511,13 @@ fn create_mesh_object<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>>
 
     // Just write dummy data to buffer2 to match in game meshes for v1.8 and v.1.9.
     // Mesh v1.10 calculates offsets for this buffer but zeros stride and writes no data.
+    // Pad the buffer to the end of this object to also handle any attribute data written above.
     if use_buffer2 {
-        buffers[2].write_all(&vec![0u8; stride2 as usize * vertex_count])?;
+        let end = (*vertex_buffer2_offset + stride2 as u64 * vertex_count as u64) as usize;
+        if buffers[2].get_ref().len() < end {
+            buffers[2].get_mut().resize(end, 0u8);
+        }
+        buffers[2].set_position(end as u64);
     }
 
     let positions = match data.positions.first() {
@@ -1035,7 +1553,7 @@ fn create_mesh_object<A: Attribute, F: Fn(&MeshObjectData) -> MeshAttributes<A>>
             disable_depth_write: if data.disable_depth_write { 1 } else { 0 },
             disable_depth_test: if data.disable_depth_test { 1 } else { 0 },
         },
-        bounding_info: calculate_bounding_info(&positions),
+        bounding_info: calculate_bounding_info_vec3a(&positions),
         attributes,
     };
 
@@ -1067,12 +1585,22 @@ fn write_vertex_indices(
     Ok(())
 }
 
-fn convert_indices(indices: &[u32]) -> VertexIndices {
+fn convert_indices(
+    indices: &[u32],
+    index_format: IndexFormat,
+) -> Result<VertexIndices, error::Error> {
+    if index_format == IndexFormat::UnsignedInt {
+        return Ok(VertexIndices::UnsignedInt(indices.into()));
+    }
+
     // Try and convert the vertex indices to a smaller type.
     let u16_indices: Result<Vec<u16>, _> = indices.iter().map(|i| u16::try_from(*i)).collect();
-    match u16_indices {
-        Ok(indices) => VertexIndices::UnsignedShort(indices),
-        Err(_) => VertexIndices::UnsignedInt(indices.into()),
+    match (u16_indices, index_format) {
+        (Ok(indices), _) => Ok(VertexIndices::UnsignedShort(indices)),
+        (Err(_), IndexFormat::UnsignedShort) => Err(error::Error::UnsignedShortIndexOutOfRange {
+            vertex_index: indices.iter().copied().max().unwrap_or_default() as usize,
+        }),
+        (Err(_), _) => Ok(VertexIndices::UnsignedInt(indices.into())),
     }
 }
 
@@ -1165,6 +1693,51 @@ pub fn transform_vectors(data: &VectorData, transform: &[[f32; 4]; 4]) -> Vector
     transform_inner(data, transform, 0.0)
 }
 
+/// Scales and offsets the first two components of the elements in `data` as `uv * scale + offset`.
+/// The remaining components are preserved.
+/// This can be used to move texture coordinates into a region of a texture atlas.
+/**
+```rust
+# use ssbh_data::mesh_data::{VectorData, transform_texture_coordinates};
+// Move the UVs into the top right quadrant of the atlas.
+let uvs = VectorData::Vector2(vec![[0.0, 0.0], [1.0, 1.0]]);
+assert_eq!(
+    VectorData::Vector2(vec![[0.5, 0.0], [1.0, 0.5]]),
+    transform_texture_coordinates(&uvs, [0.5, 0.5], [0.5, 0.0])
+);
+```
+*/
+pub fn transform_texture_coordinates(
+    data: &VectorData,
+    scale: [f32; 2],
+    offset: [f32; 2],
+) -> VectorData {
+    let uv = |u: f32, v: f32| [u * scale[0] + offset[0], v * scale[1] + offset[1]];
+    match data {
+        VectorData::Vector2(values) => {
+            VectorData::Vector2(values.iter().map(|[u, v]| uv(*u, *v)).collect())
+        }
+        VectorData::Vector3(values) => VectorData::Vector3(
+            values
+                .iter()
+                .map(|[u, v, z]| {
+                    let [u, v] = uv(*u, *v);
+                    [u, v, *z]
+                })
+                .collect(),
+        ),
+        VectorData::Vector4(values) => VectorData::Vector4(
+            values
+                .iter()
+                .map(|[u, v, z, w]| {
+                    let [u, v] = uv(*u, *v);
+                    [u, v, *z, *w]
+                })
+                .collect(),
+        ),
+    }
+}
+
 // TODO: Add tests for these?
 /// Calculates smooth per-vertex normals by by averaging over the vertices in each face.
 /// See [geometry_tools::vectors::calculate_smooth_normals].
@@ -1177,6 +1750,110 @@ pub fn calculate_smooth_normals(positions: &VectorData, vertex_indices: &[u32])
     normals.iter().map(|t| t.to_array()).collect()
 }
 
+/// Calculates smooth per-vertex normals while keeping edges sharper than `angle_threshold` in radians.
+///
+/// Unlike [calculate_smooth_normals], faces are smoothed across vertices with identical positions,
+/// so normals are also smoothed across vertices that were split for seams in other attributes.
+/// Adjacent faces are only smoothed together if the angle between the face normals is at most `angle_threshold`.
+///
+/// If `normals` is provided, vertices with the same position are only smoothed together
+/// when their existing normals are approximately equal.
+/// This preserves hard edges from vertices that were intentionally split with divergent normals.
+/**
+```rust
+# use ssbh_data::mesh_data::{VectorData, calculate_smooth_normals_with_angle};
+// Two triangles meeting at a right angle with split vertices along the shared edge.
+let positions = VectorData::Vector3(vec![
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.0, 0.0],
+]);
+let vertex_indices = [0, 1, 2, 3, 4, 5];
+
+// The 90 degree edge exceeds the threshold and is preserved.
+let normals =
+    calculate_smooth_normals_with_angle(&positions, None, &vertex_indices, 60f32.to_radians());
+assert_eq!([0.0, 0.0, 1.0], normals[0]);
+assert_eq!([0.0, 1.0, 0.0], normals[3]);
+```
+*/
+pub fn calculate_smooth_normals_with_angle(
+    positions: &VectorData,
+    normals: Option<&VectorData>,
+    vertex_indices: &[u32],
+    angle_threshold: f32,
+) -> Vec<[f32; 3]> {
+    let positions = positions.to_glam_vec3a();
+    let normals = normals.map(|n| n.to_glam_vec3a());
+    let min_cos = angle_threshold.cos();
+
+    // Skip faces with out of range indices.
+    let faces: Vec<_> = vertex_indices
+        .chunks_exact(3)
+        .filter_map(|face| {
+            let [v0, v1, v2] = [face[0], face[1], face[2]].map(|v| v as usize);
+            let (p0, p1, p2) = (positions.get(v0)?, positions.get(v1)?, positions.get(v2)?);
+
+            // The cross product magnitude weights each face by its area.
+            let normal = (*p1 - *p0).cross(*p2 - *p0);
+            Some(([v0, v1, v2], normal))
+        })
+        .collect();
+
+    // Connect faces by position instead of index to smooth across split vertices.
+    let mut corners_by_position = AHashMap::new();
+    for (face_index, (face, _)) in faces.iter().enumerate() {
+        for v in face {
+            corners_by_position
+                .entry(positions[*v].to_array().map(f32::to_bits))
+                .or_insert_with(Vec::new)
+                .push((face_index, *v));
+        }
+    }
+
+    let mut vertex_normals = vec![geometry_tools::glam::Vec3A::ZERO; positions.len()];
+    for corners in corners_by_position.values() {
+        for (face_index, v) in corners {
+            let face_normal = faces[*face_index].1;
+
+            let corner_normal: geometry_tools::glam::Vec3A = corners
+                .iter()
+                .filter(|(other_face_index, other_v)| {
+                    let other_normal = faces[*other_face_index].1;
+                    let is_smooth_face = face_normal
+                        .normalize_or_zero()
+                        .dot(other_normal.normalize_or_zero())
+                        >= min_cos;
+
+                    let is_smooth_vertex = normals.as_ref().is_none_or(|normals| {
+                        match (normals.get(*v), normals.get(*other_v)) {
+                            (Some(n0), Some(n1)) => {
+                                // Allow for some error from the precision of the normals data.
+                                n0.normalize_or_zero().dot(n1.normalize_or_zero()) >= 0.999
+                            }
+                            _ => true,
+                        }
+                    });
+
+                    other_face_index == face_index || (is_smooth_face && is_smooth_vertex)
+                })
+                .map(|(other_face_index, _)| faces[*other_face_index].1)
+                .sum();
+
+            // Vertices shared by faces on both sides of a sharp edge use the average.
+            vertex_normals[*v] += corner_normal.normalize_or_zero();
+        }
+    }
+
+    vertex_normals
+        .iter()
+        .map(|n| n.normalize_or_zero().to_array())
+        .collect()
+}
+
 /// Calculates smooth per-vertex tangents by averaging over the vertices in each face.
 /// See [geometry_tools::vectors::calculate_tangents].
 pub fn calculate_tangents_vec4(
@@ -1195,7 +1872,34 @@ pub fn calculate_tangents_vec4(
     Ok(tangents.iter().map(|t| t.to_array()).collect())
 }
 
-fn calculate_bounding_info(positions: &[geometry_tools::glam::Vec3A]) -> BoundingInfo {
+/// Calculates the bounding sphere, axis aligned bounding box, and oriented bounding box for `positions`.
+///
+/// This uses the same algorithm as converting [MeshData] to [Mesh],
+/// so tools can update the bounding information after editing vertices without recreating the [Mesh].
+/// The oriented bounding box currently uses the axis aligned bounding box with an identity transform.
+/**
+```rust
+# use ssbh_data::mesh_data::{VectorData, calculate_bounding_info};
+let positions = VectorData::Vector3(vec![[-1.0, 0.0, 0.0], [1.0, 2.0, 0.0]]);
+let info = calculate_bounding_info(&positions);
+assert_eq!(-1.0, info.bounding_volume.min.x);
+assert_eq!(2.0, info.bounding_volume.max.y);
+```
+ */
+pub fn calculate_bounding_info(positions: &VectorData) -> BoundingInfo {
+    calculate_bounding_info_vec3a(&positions.to_glam_vec3a())
+}
+
+/// Calculates the bounding information for `positions` like [calculate_bounding_info].
+pub fn calculate_bounding_info_from_points(positions: &[[f32; 3]]) -> BoundingInfo {
+    let points: Vec<_> = positions
+        .iter()
+        .map(|p| geometry_tools::glam::Vec3A::from_array(*p))
+        .collect();
+    calculate_bounding_info_vec3a(&points)
+}
+
+fn calculate_bounding_info_vec3a(positions: &[geometry_tools::glam::Vec3A]) -> BoundingInfo {
     // Calculate bounding info based on the current points.
     let sphere = geometry_tools::bounding::calculate_bounding_sphere_from_points(positions);
     let (aabb_min, aabb_max) = geometry_tools::bounding::calculate_aabb_from_points(positions);
@@ -1286,8 +1990,36 @@ pub fn read_data<R: Read + Seek, TIn: for<'a> BinRead<Args<'a> = ()>, TOut: From
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::context::ContextError;
     use hexlit::hex;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_mesh_object_older_version() {
+        // Older versions used sub_index and didn't have depth or sorting fields.
+        let object: MeshObjectData = serde_json::from_str(
+            r#"{
+                "name": "a",
+                "sub_index": 2,
+                "parent_bone_name": "",
+                "vertex_indices": [0, 1, 2],
+                "positions": [],
+                "normals": [],
+                "binormals": [],
+                "tangents": [],
+                "texture_coordinates": [],
+                "color_sets": [],
+                "bone_influences": []
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(2, object.subindex);
+        assert_eq!(0, object.sort_bias);
+        assert!(!object.disable_depth_write);
+        assert!(!object.disable_depth_test);
+    }
+
     #[test]
     fn read_data_count0() {
         let mut reader = Cursor::new(hex!("01020304"));
@@ -1409,7 +2141,7 @@ mod tests {
         // In this case, it's safe to convert to a smaller type.
         assert_eq!(
             VertexIndices::UnsignedShort(vec![0, 1, u16::MAX]),
-            convert_indices(&[0, 1, u16::MAX as u32])
+            convert_indices(&[0, 1, u16::MAX as u32], IndexFormat::Smallest).unwrap()
         )
     }
 
@@ -1417,7 +2149,7 @@ mod tests {
     fn draw_element_type_empty() {
         assert_eq!(
             VertexIndices::UnsignedShort(Vec::new()),
-            convert_indices(&[])
+            convert_indices(&[], IndexFormat::Smallest).unwrap()
         )
     }
 
@@ -1426,10 +2158,62 @@ mod tests {
         // Add elements not representable by u16.
         assert_eq!(
             VertexIndices::UnsignedInt(vec![0, 1, u16::MAX as u32 + 1]),
-            convert_indices(&[0, 1, u16::MAX as u32 + 1])
+            convert_indices(&[0, 1, u16::MAX as u32 + 1], IndexFormat::Smallest).unwrap()
+        )
+    }
+
+    #[test]
+    fn to_mesh_with_index_format_u32() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                vertex_indices: vec![0, 1, 2],
+                positions: vec![AttributeData {
+                    name: "Position0".to_string(),
+                    data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+                }],
+                ..MeshObjectData::default()
+            }],
+        };
+
+        match data
+            .to_mesh_with_index_format(IndexFormat::UnsignedInt)
+            .unwrap()
+        {
+            Mesh::V10(mesh) => {
+                assert_eq!(
+                    DrawElementType::UnsignedInt,
+                    mesh.objects.elements[0].draw_element_type
+                );
+                assert_eq!(12, mesh.index_buffer.elements.len());
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn draw_element_type_force_u32() {
+        assert_eq!(
+            VertexIndices::UnsignedInt(vec![0, 1, 2]),
+            convert_indices(&[0, 1, 2], IndexFormat::UnsignedInt).unwrap()
         )
     }
 
+    #[test]
+    fn draw_element_type_force_u16() {
+        assert_eq!(
+            VertexIndices::UnsignedShort(vec![0, 1, u16::MAX]),
+            convert_indices(&[0, 1, u16::MAX as u32], IndexFormat::UnsignedShort).unwrap()
+        );
+        assert!(matches!(
+            convert_indices(&[0, u16::MAX as u32 + 2, 1], IndexFormat::UnsignedShort),
+            Err(error::Error::UnsignedShortIndexOutOfRange {
+                vertex_index: 65537
+            })
+        ));
+    }
+
     #[test]
     fn size_in_bytes_attributes_v10() {
         assert_eq!(4, AttributeDataTypeV10::Byte4.get_size_in_bytes_v10());
@@ -1548,7 +2332,7 @@ mod tests {
 
     #[test]
     fn create_empty_mesh_1_10() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 10,
             objects: Vec::new(),
@@ -1562,7 +2346,7 @@ mod tests {
 
     #[test]
     fn create_empty_mesh_1_8() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 8,
             objects: Vec::new(),
@@ -1577,7 +2361,7 @@ mod tests {
 
     #[test]
     fn create_empty_mesh_v_1_9() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 9,
             objects: Vec::new(),
@@ -1592,7 +2376,7 @@ mod tests {
 
     #[test]
     fn create_empty_mesh_invalid_version() {
-        let result = create_mesh(&MeshData {
+        let result = Mesh::try_from(&MeshData {
             major_version: 2,
             minor_version: 301,
             objects: Vec::new(),
@@ -1609,7 +2393,7 @@ mod tests {
 
     #[test]
     fn create_mesh_1_10() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 10,
             objects: vec![
@@ -1679,9 +2463,46 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn read_bone_influences_by_subindex() {
+        let object = |subindex, bone_name: &str| MeshObjectData {
+            name: "a".to_owned(),
+            subindex,
+            positions: vec![AttributeData {
+                name: String::new(),
+                data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+            }],
+            bone_influences: vec![BoneInfluence {
+                bone_name: bone_name.to_owned(),
+                vertex_weights: vec![VertexWeight {
+                    vertex_index: 2,
+                    vertex_weight: 0.5,
+                }],
+            }],
+            ..Default::default()
+        };
+
+        for minor_version in [8, 10] {
+            let mesh = Mesh::try_from(&MeshData {
+                major_version: 1,
+                minor_version,
+                objects: vec![object(0, "x"), object(1, "y")],
+            })
+            .unwrap();
+
+            let influences = read_bone_influences(&mesh, "a", 1).unwrap();
+            assert_eq!(1, influences.len());
+            assert_eq!("y", influences[0].bone_name);
+            assert_eq!(2, influences[0].vertex_weights[0].vertex_index);
+            assert_eq!(0.5, influences[0].vertex_weights[0].vertex_weight);
+
+            assert!(read_bone_influences(&mesh, "b", 0).unwrap().is_empty());
+        }
+    }
+
     #[test]
     fn create_mesh_1_10_too_many_vertices() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 10,
             objects: vec![MeshObjectData {
@@ -1714,7 +2535,7 @@ mod tests {
 
     #[test]
     fn create_mesh_1_10_duplicate_subindices() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 10,
             objects: vec![
@@ -1746,7 +2567,7 @@ mod tests {
 
     #[test]
     fn create_mesh_1_8() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 8,
             objects: vec![
@@ -1817,7 +2638,7 @@ mod tests {
 
     #[test]
     fn create_mesh_v_1_9() {
-        let mesh = create_mesh(&MeshData {
+        let mesh = Mesh::try_from(&MeshData {
             major_version: 1,
             minor_version: 9,
             objects: vec![
@@ -1886,6 +2707,50 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn transform_texture_coordinates_vec4() {
+        let data = VectorData::Vector4(vec![[0.5, 1.0, 2.0, 3.0]]);
+        assert_eq!(
+            VectorData::Vector4(vec![[1.5, -1.0, 2.0, 3.0]]),
+            transform_texture_coordinates(&data, [2.0, -1.0], [0.5, 0.0])
+        );
+    }
+
+    #[test]
+    fn mesh_transform_texture_coordinates() {
+        let object = |uvs: Vec<AttributeData>| MeshObjectData {
+            texture_coordinates: uvs,
+            ..Default::default()
+        };
+        let uvs = |name: &str| AttributeData {
+            name: name.to_string(),
+            data: VectorData::Vector2(vec![[0.0, 0.0], [1.0, 1.0]]),
+        };
+
+        let mut mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                object(vec![uvs("map1"), uvs("uvSet")]),
+                object(Vec::new()),
+                object(vec![uvs("map1")]),
+            ],
+        };
+
+        assert_eq!(
+            2,
+            mesh.transform_texture_coordinates("map1", [0.5, 0.25], [0.5, 0.75])
+        );
+
+        let expected = VectorData::Vector2(vec![[0.5, 0.75], [1.0, 1.0]]);
+        assert_eq!(expected, mesh.objects[0].texture_coordinates[0].data);
+        assert_eq!(
+            uvs("uvSet").data,
+            mesh.objects[0].texture_coordinates[1].data
+        );
+        assert_eq!(expected, mesh.objects[2].texture_coordinates[0].data);
+    }
+
     #[test]
     fn transform_points_vec2() {
         let data = VectorData::Vector2(vec![[0.0, 1.0], [2.0, 3.0]]);
@@ -1943,6 +2808,149 @@ mod tests {
         assert_eq!(expected, transformed)
     }
 
+    fn right_angle_positions() -> VectorData {
+        // Two triangles meeting at a right angle with split vertices along the shared edge.
+        VectorData::Vector3(vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+        ])
+    }
+
+    #[test]
+    fn smooth_normals_with_angle_empty() {
+        assert!(calculate_smooth_normals_with_angle(
+            &VectorData::Vector3(Vec::new()),
+            None,
+            &[],
+            0.0
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn smooth_normals_with_angle_flat_split_vertices() {
+        // A quad with split vertices along the diagonal.
+        let positions = VectorData::Vector3(vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ]);
+        let normals =
+            calculate_smooth_normals_with_angle(&positions, None, &[0, 1, 2, 3, 4, 5], 0.1);
+        assert_eq!(vec![[0.0, 0.0, 1.0]; 6], normals);
+    }
+
+    #[test]
+    fn smooth_normals_with_angle_sharp_edge() {
+        let normals = calculate_smooth_normals_with_angle(
+            &right_angle_positions(),
+            None,
+            &[0, 1, 2, 3, 4, 5],
+            60f32.to_radians(),
+        );
+        assert_eq!(
+            vec![
+                [0.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0]
+            ],
+            normals
+        );
+    }
+
+    #[test]
+    fn smooth_normals_with_angle_smooth_edge() {
+        let normals = calculate_smooth_normals_with_angle(
+            &right_angle_positions(),
+            None,
+            &[0, 1, 2, 3, 4, 5],
+            100f32.to_radians(),
+        );
+
+        // Only the vertices along the shared edge are smoothed.
+        let edge = [0.0, 0.5f32.sqrt(), 0.5f32.sqrt()];
+        for (expected, actual) in [edge, edge, [0.0, 0.0, 1.0], edge, [0.0, 1.0, 0.0], edge]
+            .iter()
+            .zip(normals)
+        {
+            for i in 0..3 {
+                approx::assert_relative_eq!(expected[i], actual[i], epsilon = 0.0001);
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_normals_with_angle_preserve_hard_edges() {
+        // The existing normals already form a hard edge.
+        let existing = VectorData::Vector3(vec![
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ]);
+        let normals = calculate_smooth_normals_with_angle(
+            &right_angle_positions(),
+            Some(&existing),
+            &[0, 1, 2, 3, 4, 5],
+            100f32.to_radians(),
+        );
+        assert_eq!(
+            vec![
+                [0.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0]
+            ],
+            normals
+        );
+    }
+
+    #[test]
+    fn smooth_normals_with_angle_invalid_indices() {
+        let normals = calculate_smooth_normals_with_angle(
+            &right_angle_positions(),
+            None,
+            &[0, 1, 2, 3, 4, 6],
+            0.0,
+        );
+        assert_eq!([0.0, 0.0, 1.0], normals[0]);
+        assert_eq!([0.0, 0.0, 0.0], normals[3]);
+    }
+
+    #[test]
+    fn bounding_info_points_vector4() {
+        let info = calculate_bounding_info(&VectorData::Vector4(vec![
+            [-1.0, -2.0, -3.0, 1.0],
+            [1.0, 2.0, 3.0, 1.0],
+        ]));
+        assert_eq!(
+            calculate_bounding_info_from_points(&[[-1.0, -2.0, -3.0], [1.0, 2.0, 3.0]]),
+            info
+        );
+        assert_eq!(Vector3::new(-1.0, -2.0, -3.0), info.bounding_volume.min);
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), info.bounding_volume.max);
+        assert_eq!(
+            Vector3::new(0.0, 0.0, 0.0),
+            info.oriented_bounding_box.center
+        );
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), info.oriented_bounding_box.size);
+        assert_eq!(Matrix3x3::identity(), info.oriented_bounding_box.transform);
+    }
+
     #[test]
     fn calculate_offset_stride_buffer_indices() {
         let mesh_object = MeshObject::<AttributeV10> {
@@ -2064,7 +3072,8 @@ mod tests {
             ],
             &mut 0,
             &mut Cursor::new(Vec::new()),
-            create_attributes_v10,
+            IndexFormat::Smallest,
+            |o| Ok(create_attributes_v10(o)),
         )
         .unwrap();
 
@@ -2074,6 +3083,256 @@ mod tests {
         assert_eq!(1, object.depth_flags.disable_depth_test);
     }
 
+    #[test]
+    fn to_mesh_with_layout_single_buffer() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                name: "a".into(),
+                vertex_indices: vec![0, 1, 2],
+                positions: vec![AttributeData {
+                    name: "Position0".into(),
+                    data: VectorData::Vector3(vec![[0.0, 1.0, 2.0]; 3]),
+                }],
+                texture_coordinates: vec![AttributeData {
+                    name: "map1".into(),
+                    data: VectorData::Vector2(vec![[0.5, 0.25]; 3]),
+                }],
+                ..MeshObjectData::default()
+            }],
+        };
+
+        let mesh = data
+            .to_mesh_with_layout(|o| {
+                default_attribute_layout(o)
+                    .into_iter()
+                    .map(|l| AttributeLayout {
+                        buffer_index: 0,
+                        ..l
+                    })
+                    .collect()
+            })
+            .unwrap();
+
+        match &mesh {
+            Mesh::V10(mesh) => {
+                let object = &mesh.objects.elements[0];
+                assert_eq!(16, object.stride0);
+                assert_eq!(0, object.stride1);
+            }
+            _ => panic!("unexpected version"),
+        }
+
+        // The data should be the same regardless of the buffer layout.
+        let new_data = MeshData::try_from(&mesh).unwrap();
+        assert_eq!(
+            data.objects[0].positions[0].data,
+            new_data.objects[0].positions[0].data
+        );
+        assert_eq!(
+            data.objects[0].texture_coordinates[0].data,
+            new_data.objects[0].texture_coordinates[0].data
+        );
+    }
+
+    #[test]
+    fn to_mesh_with_layout_buffers2_3() {
+        let object = |name: &str| MeshObjectData {
+            name: name.into(),
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".into(),
+                data: VectorData::Vector3(vec![[0.0, 1.0, 2.0]; 3]),
+            }],
+            texture_coordinates: vec![AttributeData {
+                name: "map1".into(),
+                data: VectorData::Vector2(vec![[0.5, 0.25]; 3]),
+            }],
+            color_sets: vec![AttributeData {
+                name: "colorSet1".into(),
+                data: VectorData::Vector4(vec![[1.0, 0.0, 0.0, 1.0]; 3]),
+            }],
+            ..MeshObjectData::default()
+        };
+
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a"), object("b")],
+        };
+
+        // Only the second object uses the additional buffers.
+        let mesh = data
+            .to_mesh_with_layout(|o| {
+                default_attribute_layout(o)
+                    .into_iter()
+                    .map(|l| AttributeLayout {
+                        buffer_index: match (o.name.as_str(), l.usage) {
+                            ("b", AttributeUsage::TextureCoordinate) => 2,
+                            ("b", AttributeUsage::ColorSet) => 3,
+                            _ => l.buffer_index,
+                        },
+                        ..l
+                    })
+                    .collect()
+            })
+            .unwrap();
+
+        match &mesh {
+            Mesh::V10(mesh) => {
+                let [a, b] = [&mesh.objects.elements[0], &mesh.objects.elements[1]];
+                assert_eq!((12, 8, 0, 0), (a.stride0, a.stride1, a.stride2, a.stride3));
+                assert_eq!((12, 0, 4, 4), (b.stride0, b.stride1, b.stride2, b.stride3));
+
+                // The first object reserves space in buffer 2 without writing data.
+                assert_eq!(96, b.vertex_buffer2_offset);
+                assert_eq!(vec![72, 24, 96 + 12, 12], mesh.buffer_sizes.elements);
+            }
+            _ => panic!("unexpected version"),
+        }
+
+        let new_data = MeshData::try_from(&mesh).unwrap();
+        for (expected, actual) in data.objects.iter().zip(new_data.objects.iter()) {
+            assert_eq!(
+                expected.texture_coordinates[0].data,
+                actual.texture_coordinates[0].data
+            );
+            assert_eq!(expected.color_sets[0].data, actual.color_sets[0].data);
+        }
+    }
+
+    #[test]
+    fn validate_attribute_strides_offset_exceeds_stride() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                name: "a".into(),
+                vertex_indices: vec![0, 1, 2],
+                positions: vec![AttributeData {
+                    name: "Position0".into(),
+                    data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+                }],
+                ..MeshObjectData::default()
+            }],
+        };
+
+        let mut mesh = Mesh::try_from(&data).unwrap();
+        assert!(validate_attribute_strides(&mesh).is_ok());
+
+        if let Mesh::V10(mesh) = &mut mesh {
+            mesh.objects.elements[0].stride0 = 8;
+        }
+        assert!(matches!(
+            validate_attribute_strides(&mesh),
+            Err(error::AttributeError::OffsetExceedsStride {
+                offset: 0,
+                size: 12,
+                stride: 8,
+                ..
+            })
+        ));
+
+        // Overlapping attributes are still readable.
+        assert!(MeshData::try_from(&mesh).is_ok());
+    }
+
+    #[test]
+    fn to_mesh_with_buffer_count() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                name: "a".into(),
+                vertex_indices: vec![0, 1, 2],
+                positions: vec![AttributeData {
+                    name: "Position0".into(),
+                    data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+                }],
+                ..MeshObjectData::default()
+            }],
+        };
+
+        match data.to_mesh_with_buffer_count(6).unwrap() {
+            Mesh::V10(mesh) => {
+                assert_eq!(vec![36, 0, 0, 0, 0, 0], mesh.buffer_sizes.elements);
+                assert_eq!(6, mesh.vertex_buffers.elements.len());
+            }
+            _ => panic!("unexpected version"),
+        }
+
+        // The minimum buffer count is always used.
+        match data.to_mesh_with_buffer_count(0).unwrap() {
+            Mesh::V10(mesh) => assert_eq!(4, mesh.vertex_buffers.elements.len()),
+            _ => panic!("unexpected version"),
+        }
+    }
+
+    #[test]
+    fn read_mesh_object_error_context() {
+        let object = |name: &str| MeshObjectData {
+            name: name.into(),
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".into(),
+                data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+            }],
+            ..MeshObjectData::default()
+        };
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a"), object("b")],
+        };
+
+        let mut mesh = match Mesh::try_from(&data).unwrap() {
+            Mesh::V10(mesh) => mesh,
+            _ => panic!("unexpected version"),
+        };
+        mesh.objects.elements[1].attributes.elements[0].buffer_index = 7;
+
+        let result = MeshData::try_from(&Mesh::V10(mesh));
+        let e = result.unwrap_err();
+        let e = e.downcast_ref::<ContextError>().unwrap();
+        assert_eq!(
+            vec![
+                &ErrorContext::MeshObject {
+                    index: 1,
+                    name: "b".into(),
+                    subindex: 0
+                },
+                &ErrorContext::Attribute {
+                    name: "Position0".into()
+                }
+            ],
+            e.contexts()
+        );
+        assert!(matches!(
+            e.root_cause().downcast_ref::<error::AttributeError>(),
+            Some(error::AttributeError::BufferIndexOutOfRange {
+                buffer_index: 7,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn to_mesh_with_layout_unsupported_version() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 8,
+            objects: Vec::new(),
+        };
+        assert!(matches!(
+            data.to_mesh_with_layout(default_attribute_layout),
+            Err(error::Error::UnsupportedVersion {
+                major_version: 1,
+                minor_version: 8
+            })
+        ));
+    }
+
     #[test]
     fn create_mesh_object_vertex_count_mismatch() {
         // The vertex count can't be determined since 1 != 2.
@@ -2097,7 +3356,8 @@ mod tests {
             ],
             &mut 0,
             &mut Cursor::new(Vec::new()),
-            create_attributes_v10,
+            IndexFormat::Smallest,
+            |o| Ok(create_attributes_v10(o)),
         );
 
         assert!(matches!(
@@ -2129,7 +3389,8 @@ mod tests {
             ],
             &mut 0,
             &mut Cursor::new(Vec::new()),
-            create_attributes_v10,
+            IndexFormat::Smallest,
+            |o| Ok(create_attributes_v10(o)),
         )
         .unwrap();
     }
@@ -2158,7 +3419,8 @@ mod tests {
             ],
             &mut 0,
             &mut Cursor::new(Vec::new()),
-            create_attributes_v10,
+            IndexFormat::Smallest,
+            |o| Ok(create_attributes_v10(o)),
         );
 
         assert!(matches!(
@@ -2193,7 +3455,8 @@ mod tests {
             ],
             &mut 0,
             &mut Cursor::new(Vec::new()),
-            create_attributes_v10,
+            IndexFormat::Smallest,
+            |o| Ok(create_attributes_v10(o)),
         );
 
         assert!(matches!(
@@ -2221,7 +3484,8 @@ mod tests {
             ],
             &mut 0,
             &mut Cursor::new(Vec::new()),
-            create_attributes_v10,
+            IndexFormat::Smallest,
+            |o| Ok(create_attributes_v10(o)),
         );
 
         assert!(matches!(
@@ -2232,4 +3496,56 @@ mod tests {
             })
         ));
     }
+
+    fn object_with_name(name: &str, subindex: u64) -> MeshObjectData {
+        MeshObjectData {
+            name: name.into(),
+            subindex,
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".into(),
+                data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+            }],
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn object_names(data: &MeshData) -> Vec<(&str, u64)> {
+        data.objects
+            .iter()
+            .map(|o| (o.name.as_str(), o.subindex))
+            .collect()
+    }
+
+    #[test]
+    fn convert_mesh_preserves_object_order() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                object_with_name("b", 1),
+                object_with_name("a", 0),
+                object_with_name("b", 0),
+            ],
+        };
+
+        let mesh = Mesh::try_from(&data).unwrap();
+        let new_data = MeshData::try_from(&mesh).unwrap();
+        assert_eq!(vec![("b", 1), ("a", 0), ("b", 0)], object_names(&new_data));
+    }
+
+    #[test]
+    fn sort_mesh_objects() {
+        let mut data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                object_with_name("b", 1),
+                object_with_name("a", 0),
+                object_with_name("b", 0),
+            ],
+        };
+        data.sort_objects();
+        assert_eq!(vec![("a", 0), ("b", 0), ("b", 1)], object_names(&data));
+    }
 }
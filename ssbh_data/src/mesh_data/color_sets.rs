@@ -0,0 +1,154 @@
+use super::{MeshData, MeshObjectData, VectorData};
+
+/// A [color_sets](struct.MeshObjectData.html#structfield.color_sets) value that can't be represented exactly
+/// after converting to [DataType::Byte4](super::DataType::Byte4).
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColorSetRangeError {
+    /// The name of the color set attribute like `"colorSet1"`.
+    pub attribute_name: String,
+    /// The index of the vertex in the attribute data.
+    pub vertex_index: usize,
+    /// The original value before clamping.
+    pub value: [f32; 4],
+}
+
+fn is_in_range(value: &[f32; 4]) -> bool {
+    value.iter().all(|c| (0.0..=1.0).contains(c))
+}
+
+impl MeshObjectData {
+    /// Finds the values in the [color_sets](#structfield.color_sets) outside the range `0.0` to `1.0`.
+    ///
+    /// Color sets with 4 components are stored as unsigned normalized bytes,
+    /// so values outside this range or NaN are clamped when converting to [Mesh](ssbh_lib::formats::mesh::Mesh).
+    /// In game shaders typically scale color sets by 2.0, so `0.5` is neutral and `1.0` is the brightest representable value.
+    /// Color sets with 2 or 3 components are stored as floats and are not checked.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, MeshObjectData, VectorData};
+
+    let object = MeshObjectData {
+        color_sets: vec![AttributeData {
+            name: "colorSet1".to_string(),
+            data: VectorData::Vector4(vec![[0.5, 0.5, 0.5, 0.5], [2.0, 0.5, 0.5, 0.5]]),
+        }],
+        ..MeshObjectData::default()
+    };
+
+    let errors = object.color_set_range_errors();
+    assert_eq!(1, errors.len());
+    assert_eq!(1, errors[0].vertex_index);
+    ```
+     */
+    pub fn color_set_range_errors(&self) -> Vec<ColorSetRangeError> {
+        self.color_sets
+            .iter()
+            .filter_map(|a| match &a.data {
+                VectorData::Vector4(values) => Some((&a.name, values)),
+                _ => None,
+            })
+            .flat_map(|(name, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, v)| !is_in_range(v))
+                    .map(move |(i, v)| ColorSetRangeError {
+                        attribute_name: name.clone(),
+                        vertex_index: i,
+                        value: *v,
+                    })
+            })
+            .collect()
+    }
+
+    /// Clamps the values in the [color_sets](#structfield.color_sets) with 4 components to the range `0.0` to `1.0`.
+    /// NaN values are replaced with `0.0`.
+    /// Returns the number of modified vertices.
+    ///
+    /// This matches the values that will be written to the file and makes the clamping explicit.
+    /// See [MeshObjectData::color_set_range_errors].
+    pub fn clamp_color_sets(&mut self) -> usize {
+        let mut count = 0;
+        for attribute in &mut self.color_sets {
+            if let VectorData::Vector4(values) = &mut attribute.data {
+                for value in values.iter_mut().filter(|v| !is_in_range(v)) {
+                    for c in value.iter_mut() {
+                        *c = if c.is_nan() { 0.0 } else { c.clamp(0.0, 1.0) };
+                    }
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+}
+
+impl MeshData {
+    /// Applies [MeshObjectData::clamp_color_sets] to every object.
+    /// Returns the total number of modified vertices.
+    pub fn clamp_color_sets(&mut self) -> usize {
+        self.objects.iter_mut().map(|o| o.clamp_color_sets()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::AttributeData;
+
+    fn object(data: VectorData) -> MeshObjectData {
+        MeshObjectData {
+            color_sets: vec![AttributeData {
+                name: "colorSet1".to_string(),
+                data,
+            }],
+            ..MeshObjectData::default()
+        }
+    }
+
+    #[test]
+    fn color_set_range_errors_in_range() {
+        let object = object(VectorData::Vector4(vec![[0.0, 0.5, 1.0, 1.0]]));
+        assert!(object.color_set_range_errors().is_empty());
+    }
+
+    #[test]
+    fn color_set_range_errors_out_of_range() {
+        let object = object(VectorData::Vector4(vec![
+            [0.5; 4],
+            [-0.1, 0.5, 0.5, 0.5],
+            [0.5, 0.5, 0.5, f32::NAN],
+        ]));
+        let errors = object.color_set_range_errors();
+        assert_eq!(2, errors.len());
+        assert_eq!(
+            ColorSetRangeError {
+                attribute_name: "colorSet1".to_string(),
+                vertex_index: 1,
+                value: [-0.1, 0.5, 0.5, 0.5]
+            },
+            errors[0]
+        );
+        assert_eq!(2, errors[1].vertex_index);
+    }
+
+    #[test]
+    fn color_set_range_errors_float_vectors() {
+        let object = object(VectorData::Vector3(vec![[2.0; 3]]));
+        assert!(object.color_set_range_errors().is_empty());
+    }
+
+    #[test]
+    fn clamp_color_sets() {
+        let mut object = object(VectorData::Vector4(vec![
+            [0.5; 4],
+            [-1.0, 2.0, 0.5, f32::NAN],
+        ]));
+        assert_eq!(1, object.clamp_color_sets());
+        assert_eq!(
+            VectorData::Vector4(vec![[0.5; 4], [0.0, 1.0, 0.5, 0.0]]),
+            object.color_sets[0].data
+        );
+        assert!(object.color_set_range_errors().is_empty());
+    }
+}
@@ -0,0 +1,988 @@
+//! Importers for building [MeshObjectData] from common interchange formats.
+//!
+//! Imported attributes use the names expected by in game shaders.
+//! Positions are stored in `"Position0"`, normals in `"Normal0"`,
+//! texture coordinates in `"map1"`, and vertex colors in `"colorSet1"`.
+//! OBJ and PLY place the texture coordinate origin at the bottom left,
+//! so the V coordinate is flipped to match the top left origin used by in game meshes.
+#[cfg(feature = "ply")]
+use std::io::Read;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use ahash::AHashMap;
+
+use super::{error::ImportError, AttributeData, MeshObjectData, VectorData};
+
+// Unnamed objects use the file name when reading from a file.
+fn name_unnamed_objects<P: AsRef<Path>>(objects: &mut [MeshObjectData], path: P) {
+    let stem = path
+        .as_ref()
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for o in objects.iter_mut().filter(|o| o.name.is_empty()) {
+        o.name.clone_from(&stem);
+    }
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ImportError {
+    ImportError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+#[cfg(feature = "obj")]
+fn parse_f32(value: Option<&str>, line: usize) -> Result<f32, ImportError> {
+    let value = value.ok_or_else(|| parse_error(line, "expected a value"))?;
+    value
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid number {value:?}")))
+}
+
+fn flip_uv([u, v]: [f32; 2]) -> [f32; 2] {
+    [u, 1.0 - v]
+}
+
+// Attributes are only included if every vertex defines a value.
+fn optional_attribute<T, F: Fn(Vec<T>) -> VectorData>(
+    name: &str,
+    values: Vec<Option<T>>,
+    f: F,
+) -> Vec<AttributeData> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+
+    values
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .map(|values| {
+            vec![AttributeData {
+                name: name.into(),
+                data: f(values),
+            }]
+        })
+        .unwrap_or_default()
+}
+
+// Triangulate polygons as a triangle fan.
+fn triangle_fan(polygon: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    (1..polygon.len().saturating_sub(1)).flat_map(move |i| [polygon[0], polygon[i], polygon[i + 1]])
+}
+
+/// Reads the OBJ data from `reader` with one [MeshObjectData] for each object or group.
+///
+/// Faces with more than three vertices are triangulated.
+/// Texture coordinates and normals are only imported if every face vertex defines them.
+/// Objects are named using the `o` or `g` statements,
+/// and objects sharing the same name are assigned increasing subindices.
+/**
+```rust
+use ssbh_data::mesh_data::read_obj;
+
+let obj = "
+o cube
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 1.0 1.0 0.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+f 1/1 2/2 3/3
+";
+let objects = read_obj(obj.as_bytes())?;
+assert_eq!("cube", objects[0].name);
+assert_eq!(vec![0, 1, 2], objects[0].vertex_indices);
+assert_eq!("map1", objects[0].texture_coordinates[0].name);
+# Ok::<(), ssbh_data::mesh_data::error::ImportError>(())
+```
+ */
+#[cfg(feature = "obj")]
+pub fn read_obj<R: BufRead>(reader: R) -> Result<Vec<MeshObjectData>, ImportError> {
+    let mut positions = Vec::new();
+    let mut texture_coordinates = Vec::new();
+    let mut normals = Vec::new();
+
+    let mut objects = Vec::new();
+    let mut current = ObjObject::new(String::new());
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = i + 1;
+
+        let mut parts = line.split_whitespace();
+        match parts.next() {
+            Some("v") => {
+                let x = parse_f32(parts.next(), line_number)?;
+                let y = parse_f32(parts.next(), line_number)?;
+                let z = parse_f32(parts.next(), line_number)?;
+                positions.push([x, y, z]);
+            }
+            Some("vt") => {
+                let u = parse_f32(parts.next(), line_number)?;
+                // The V coordinate is optional and defaults to 0.
+                let v = parts
+                    .next()
+                    .map_or(Ok(0.0), |v| parse_f32(Some(v), line_number))?;
+                texture_coordinates.push(flip_uv([u, v]));
+            }
+            Some("vn") => {
+                let x = parse_f32(parts.next(), line_number)?;
+                let y = parse_f32(parts.next(), line_number)?;
+                let z = parse_f32(parts.next(), line_number)?;
+                normals.push([x, y, z]);
+            }
+            Some("f") => {
+                let polygon = parts
+                    .map(|p| {
+                        let key = parse_obj_face_vertex(
+                            p,
+                            line_number,
+                            positions.len(),
+                            texture_coordinates.len(),
+                            normals.len(),
+                        )?;
+                        Ok(current.vertex_index(key, &positions, &texture_coordinates, &normals))
+                    })
+                    .collect::<Result<Vec<_>, ImportError>>()?;
+                if polygon.len() < 3 {
+                    return Err(parse_error(
+                        line_number,
+                        "faces must have at least 3 vertices",
+                    ));
+                }
+                current.vertex_indices.extend(triangle_fan(&polygon));
+            }
+            Some("o") | Some("g") => {
+                let name = parts.collect::<Vec<_>>().join(" ");
+                let previous = std::mem::replace(&mut current, ObjObject::new(name));
+                objects.extend(previous.finish());
+            }
+            // Ignore comments and unsupported statements like materials or smoothing groups.
+            _ => (),
+        }
+    }
+    objects.extend(current.finish());
+
+    // Repeated names use the subindex to keep objects uniquely identified.
+    let mut name_counts = AHashMap::new();
+    for o in &mut objects {
+        let count = name_counts.entry(o.name.clone()).or_insert(0u64);
+        o.subindex = *count;
+        *count += 1;
+    }
+
+    Ok(objects)
+}
+
+/// Reads the OBJ data from `path`.
+/// Unnamed objects use the file name without the extension.
+/// See [read_obj].
+#[cfg(feature = "obj")]
+pub fn read_obj_file<P: AsRef<Path>>(path: P) -> Result<Vec<MeshObjectData>, ImportError> {
+    let file = std::fs::File::open(path.as_ref())?;
+    let mut objects = read_obj(BufReader::new(file))?;
+    name_unnamed_objects(&mut objects, path);
+    Ok(objects)
+}
+
+// Indices into the position, texture coordinate, and normal lists.
+#[cfg(feature = "obj")]
+type ObjVertexKey = (usize, Option<usize>, Option<usize>);
+
+#[cfg(feature = "obj")]
+struct ObjObject {
+    name: String,
+    vertices: AHashMap<ObjVertexKey, u32>,
+    positions: Vec<[f32; 3]>,
+    texture_coordinates: Vec<Option<[f32; 2]>>,
+    normals: Vec<Option<[f32; 3]>>,
+    vertex_indices: Vec<u32>,
+}
+
+#[cfg(feature = "obj")]
+impl ObjObject {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            vertices: AHashMap::new(),
+            positions: Vec::new(),
+            texture_coordinates: Vec::new(),
+            normals: Vec::new(),
+            vertex_indices: Vec::new(),
+        }
+    }
+
+    // Each unique combination of indices becomes a separate vertex.
+    fn vertex_index(
+        &mut self,
+        key: ObjVertexKey,
+        positions: &[[f32; 3]],
+        texture_coordinates: &[[f32; 2]],
+        normals: &[[f32; 3]],
+    ) -> u32 {
+        *self.vertices.entry(key).or_insert_with(|| {
+            let (p, t, n) = key;
+            self.positions.push(positions[p]);
+            self.texture_coordinates
+                .push(t.map(|t| texture_coordinates[t]));
+            self.normals.push(n.map(|n| normals[n]));
+            self.positions.len() as u32 - 1
+        })
+    }
+
+    fn finish(self) -> Option<MeshObjectData> {
+        // Skip groups without any faces.
+        if self.vertex_indices.is_empty() {
+            return None;
+        }
+
+        Some(MeshObjectData {
+            name: self.name,
+            vertex_indices: self.vertex_indices,
+            positions: vec![AttributeData {
+                name: "Position0".into(),
+                data: VectorData::Vector3(self.positions),
+            }],
+            normals: optional_attribute("Normal0", self.normals, VectorData::Vector3),
+            texture_coordinates: optional_attribute(
+                "map1",
+                self.texture_coordinates,
+                VectorData::Vector2,
+            ),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "obj")]
+fn parse_obj_face_vertex(
+    vertex: &str,
+    line: usize,
+    position_count: usize,
+    texture_coordinate_count: usize,
+    normal_count: usize,
+) -> Result<ObjVertexKey, ImportError> {
+    let mut indices = vertex.split('/');
+
+    let position = indices
+        .next()
+        .filter(|i| !i.is_empty())
+        .ok_or_else(|| parse_error(line, format!("face vertex {vertex:?} has no position")))?;
+    let position = resolve_obj_index(position, line, position_count)?;
+
+    let texture_coordinate = indices
+        .next()
+        .filter(|i| !i.is_empty())
+        .map(|i| resolve_obj_index(i, line, texture_coordinate_count))
+        .transpose()?;
+
+    let normal = indices
+        .next()
+        .filter(|i| !i.is_empty())
+        .map(|i| resolve_obj_index(i, line, normal_count))
+        .transpose()?;
+
+    Ok((position, texture_coordinate, normal))
+}
+
+// OBJ indices start from 1, and negative indices are relative to the end of the list.
+#[cfg(feature = "obj")]
+fn resolve_obj_index(index: &str, line: usize, count: usize) -> Result<usize, ImportError> {
+    let index: i64 = index
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid index {index:?}")))?;
+
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+
+    if resolved >= 0 && (resolved as usize) < count {
+        Ok(resolved as usize)
+    } else {
+        Err(ImportError::IndexOutOfRange { index, count })
+    }
+}
+
+#[cfg(feature = "ply")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[cfg(feature = "ply")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum PlyScalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+#[cfg(feature = "ply")]
+impl PlyScalar {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::I8),
+            "uchar" | "uint8" => Some(Self::U8),
+            "short" | "int16" => Some(Self::I16),
+            "ushort" | "uint16" => Some(Self::U16),
+            "int" | "int32" => Some(Self::I32),
+            "uint" | "uint32" => Some(Self::U32),
+            "float" | "float32" => Some(Self::F32),
+            "double" | "float64" => Some(Self::F64),
+            _ => None,
+        }
+    }
+
+    // The value that maps to 1.0 for normalized vertex colors.
+    fn max_color_value(&self) -> f64 {
+        match self {
+            Self::U8 | Self::I8 => 255.0,
+            Self::U16 | Self::I16 => 65535.0,
+            Self::U32 | Self::I32 => u32::MAX as f64,
+            Self::F32 | Self::F64 => 1.0,
+        }
+    }
+}
+
+#[cfg(feature = "ply")]
+#[derive(Debug)]
+enum PlyProperty {
+    Scalar(String, PlyScalar),
+    List(String, PlyScalar, PlyScalar),
+}
+
+#[cfg(feature = "ply")]
+#[derive(Debug)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+/// Reads the PLY data from `reader` as a single unnamed [MeshObjectData].
+///
+/// ASCII and binary files are supported.
+/// Vertex properties `x`, `y`, `z` are imported as positions,
+/// `nx`, `ny`, `nz` as normals, `s`, `t` or `u`, `v` as texture coordinates,
+/// and `red`, `green`, `blue`, `alpha` as vertex colors normalized to the range 0.0 to 1.0.
+/// Faces with more than three vertices are triangulated.
+/**
+```rust
+use ssbh_data::mesh_data::read_ply;
+
+let ply = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+1 1 0
+3 0 1 2
+";
+let object = read_ply(ply.as_bytes())?;
+assert_eq!(vec![0, 1, 2], object.vertex_indices);
+assert_eq!("Position0", object.positions[0].name);
+# Ok::<(), ssbh_data::mesh_data::error::ImportError>(())
+```
+ */
+#[cfg(feature = "ply")]
+pub fn read_ply<R: BufRead>(mut reader: R) -> Result<MeshObjectData, ImportError> {
+    let (format, elements) = read_ply_header(&mut reader)?;
+
+    let mut values = match format {
+        PlyFormat::Ascii => PlyValues::Ascii {
+            reader: &mut reader,
+            tokens: Vec::new(),
+            line: 0,
+        },
+        PlyFormat::BinaryLittleEndian => PlyValues::Binary {
+            reader: &mut reader,
+            big_endian: false,
+        },
+        PlyFormat::BinaryBigEndian => PlyValues::Binary {
+            reader: &mut reader,
+            big_endian: true,
+        },
+    };
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut texture_coordinates = Vec::new();
+    let mut colors = Vec::new();
+    let mut vertex_indices = Vec::new();
+
+    for element in &elements {
+        for _ in 0..element.count {
+            let mut scalars = AHashMap::new();
+            let mut lists = AHashMap::new();
+            for property in &element.properties {
+                match property {
+                    PlyProperty::Scalar(name, ty) => {
+                        scalars.insert(name.as_str(), (values.read(*ty)?, *ty));
+                    }
+                    PlyProperty::List(name, count_ty, ty) => {
+                        let count = values.read(*count_ty)? as usize;
+                        let list = (0..count)
+                            .map(|_| values.read(*ty))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        lists.insert(name.as_str(), list);
+                    }
+                }
+            }
+
+            match element.name.as_str() {
+                "vertex" => {
+                    let get = |names: &[&str]| {
+                        names
+                            .iter()
+                            .map(|n| scalars.get(n).map(|(v, _)| *v as f32))
+                            .collect::<Option<Vec<_>>>()
+                    };
+                    let position = get(&["x", "y", "z"]).unwrap_or_default();
+                    if position.len() != 3 {
+                        return Err(parse_error(
+                            values.line(),
+                            "vertex element is missing x, y, or z",
+                        ));
+                    }
+                    positions.push([position[0], position[1], position[2]]);
+                    normals.push(get(&["nx", "ny", "nz"]).map(|n| [n[0], n[1], n[2]]));
+                    texture_coordinates.push(
+                        get(&["s", "t"])
+                            .or_else(|| get(&["u", "v"]))
+                            .or_else(|| get(&["texture_u", "texture_v"]))
+                            .map(|uv| flip_uv([uv[0], uv[1]])),
+                    );
+                    colors.push(ply_color(&scalars));
+                }
+                "face" => {
+                    let polygon = lists
+                        .get("vertex_indices")
+                        .or_else(|| lists.get("vertex_index"))
+                        .ok_or_else(|| {
+                            parse_error(values.line(), "face element has no vertex indices")
+                        })?
+                        .iter()
+                        .map(|i| ply_vertex_index(*i, positions.len(), values.line()))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if polygon.len() < 3 {
+                        return Err(parse_error(
+                            values.line(),
+                            "faces must have at least 3 vertices",
+                        ));
+                    }
+                    vertex_indices.extend(triangle_fan(&polygon));
+                }
+                // Ignore unsupported elements like edges.
+                _ => (),
+            }
+        }
+    }
+
+    if let Some(index) = vertex_indices
+        .iter()
+        .find(|i| **i as usize >= positions.len())
+    {
+        return Err(ImportError::IndexOutOfRange {
+            index: *index as i64,
+            count: positions.len(),
+        });
+    }
+
+    Ok(MeshObjectData {
+        vertex_indices,
+        positions: vec![AttributeData {
+            name: "Position0".into(),
+            data: VectorData::Vector3(positions),
+        }],
+        normals: optional_attribute("Normal0", normals, VectorData::Vector3),
+        texture_coordinates: optional_attribute("map1", texture_coordinates, VectorData::Vector2),
+        color_sets: optional_attribute("colorSet1", colors, VectorData::Vector4),
+        ..Default::default()
+    })
+}
+
+/// Reads the PLY data from `path` and names the object using the file name without the extension.
+/// See [read_ply].
+#[cfg(feature = "ply")]
+pub fn read_ply_file<P: AsRef<Path>>(path: P) -> Result<MeshObjectData, ImportError> {
+    let file = std::fs::File::open(path.as_ref())?;
+    let mut object = read_ply(BufReader::new(file))?;
+    name_unnamed_objects(std::slice::from_mut(&mut object), path);
+    Ok(object)
+}
+
+// List values are read as f64 to support any scalar type,
+// so check that each index is a whole number that fits in a u32.
+#[cfg(feature = "ply")]
+fn ply_vertex_index(value: f64, count: usize, line: usize) -> Result<u32, ImportError> {
+    // This also rejects NaN and infinity since their fractional part is NaN.
+    if value.fract() != 0.0 {
+        return Err(parse_error(line, format!("invalid vertex index {value}")));
+    }
+
+    u32::try_from(value as i64).map_err(|_| ImportError::IndexOutOfRange {
+        index: value as i64,
+        count,
+    })
+}
+
+#[cfg(feature = "ply")]
+fn ply_color(scalars: &AHashMap<&str, (f64, PlyScalar)>) -> Option<[f32; 4]> {
+    let channel = |name| {
+        scalars
+            .get(name)
+            .map(|(v, ty): &(f64, PlyScalar)| (v / ty.max_color_value()) as f32)
+    };
+    Some([
+        channel("red")?,
+        channel("green")?,
+        channel("blue")?,
+        channel("alpha").unwrap_or(1.0),
+    ])
+}
+
+#[cfg(feature = "ply")]
+fn read_ply_header<R: BufRead>(
+    reader: &mut R,
+) -> Result<(PlyFormat, Vec<PlyElement>), ImportError> {
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+
+    let mut line_number = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(parse_error(line_number, "unexpected end of header"));
+        }
+        line_number += 1;
+
+        let mut parts = line.split_whitespace();
+        match (line_number, parts.next()) {
+            (1, Some("ply")) => (),
+            (1, _) => return Err(parse_error(line_number, "missing ply magic")),
+            (_, Some("format")) => {
+                format = match parts.next() {
+                    Some("ascii") => Some(PlyFormat::Ascii),
+                    Some("binary_little_endian") => Some(PlyFormat::BinaryLittleEndian),
+                    Some("binary_big_endian") => Some(PlyFormat::BinaryBigEndian),
+                    f => {
+                        return Err(parse_error(
+                            line_number,
+                            format!("unsupported format {f:?}"),
+                        ))
+                    }
+                };
+            }
+            (_, Some("element")) => {
+                let name = parts
+                    .next()
+                    .ok_or_else(|| parse_error(line_number, "expected an element name"))?;
+                let count = parts
+                    .next()
+                    .and_then(|c| c.parse().ok())
+                    .ok_or_else(|| parse_error(line_number, "expected an element count"))?;
+                elements.push(PlyElement {
+                    name: name.to_string(),
+                    count,
+                    properties: Vec::new(),
+                });
+            }
+            (_, Some("property")) => {
+                let scalar = |name: Option<&str>| {
+                    name.and_then(PlyScalar::from_name).ok_or_else(|| {
+                        parse_error(line_number, format!("unsupported property type {name:?}"))
+                    })
+                };
+
+                let property = match parts.next() {
+                    Some("list") => {
+                        let count_ty = scalar(parts.next())?;
+                        let ty = scalar(parts.next())?;
+                        PlyProperty::List(parts.next().unwrap_or_default().into(), count_ty, ty)
+                    }
+                    ty => PlyProperty::Scalar(parts.next().unwrap_or_default().into(), scalar(ty)?),
+                };
+
+                elements
+                    .last_mut()
+                    .ok_or_else(|| parse_error(line_number, "property is not part of an element"))?
+                    .properties
+                    .push(property);
+            }
+            (_, Some("end_header")) => break,
+            // Ignore comments and obj_info.
+            _ => (),
+        }
+    }
+
+    let format = format.ok_or_else(|| parse_error(line_number, "missing format"))?;
+    Ok((format, elements))
+}
+
+#[cfg(feature = "ply")]
+enum PlyValues<'a, R: BufRead> {
+    Ascii {
+        reader: &'a mut R,
+        tokens: Vec<String>,
+        line: usize,
+    },
+    Binary {
+        reader: &'a mut R,
+        big_endian: bool,
+    },
+}
+
+#[cfg(feature = "ply")]
+impl<R: BufRead> PlyValues<'_, R> {
+    // The current line relative to the end of the header for ASCII files.
+    fn line(&self) -> usize {
+        match self {
+            PlyValues::Ascii { line, .. } => *line,
+            PlyValues::Binary { .. } => 0,
+        }
+    }
+
+    fn read(&mut self, ty: PlyScalar) -> Result<f64, ImportError> {
+        match self {
+            PlyValues::Ascii {
+                reader,
+                tokens,
+                line,
+            } => {
+                // Store tokens in reverse to efficiently pop the next value.
+                while tokens.is_empty() {
+                    let mut text = String::new();
+                    if reader.read_line(&mut text)? == 0 {
+                        return Err(parse_error(*line, "unexpected end of file"));
+                    }
+                    *line += 1;
+                    tokens.extend(text.split_whitespace().rev().map(String::from));
+                }
+                let token = tokens.pop().unwrap();
+                token
+                    .parse()
+                    .map_err(|_| parse_error(*line, format!("invalid number {token:?}")))
+            }
+            PlyValues::Binary { reader, big_endian } => {
+                macro_rules! read_value {
+                    ($ty:ty) => {{
+                        let mut bytes = [0u8; std::mem::size_of::<$ty>()];
+                        reader.read_exact(&mut bytes)?;
+                        if *big_endian {
+                            <$ty>::from_be_bytes(bytes) as f64
+                        } else {
+                            <$ty>::from_le_bytes(bytes) as f64
+                        }
+                    }};
+                }
+
+                Ok(match ty {
+                    PlyScalar::I8 => read_value!(i8),
+                    PlyScalar::U8 => read_value!(u8),
+                    PlyScalar::I16 => read_value!(i16),
+                    PlyScalar::U16 => read_value!(u16),
+                    PlyScalar::I32 => read_value!(i32),
+                    PlyScalar::U32 => read_value!(u32),
+                    PlyScalar::F32 => read_value!(f32),
+                    PlyScalar::F64 => read_value!(f64),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "obj")]
+    #[test]
+    fn read_obj_quad_with_uvs_and_normals() {
+        let obj = "
+# comment
+o quad
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 1.0 1.0 0.0
+v 0.0 1.0 0.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+vt 0.0 1.0
+vn 0.0 0.0 1.0
+f 1/1/1 2/2/1 3/3/1 4/4/1
+";
+        let objects = read_obj(obj.as_bytes()).unwrap();
+        assert_eq!(1, objects.len());
+
+        let o = &objects[0];
+        assert_eq!("quad", o.name);
+        assert_eq!(0, o.subindex);
+        assert_eq!(vec![0, 1, 2, 0, 2, 3], o.vertex_indices);
+        assert_eq!("Position0", o.positions[0].name);
+        assert_eq!(
+            VectorData::Vector3(vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0]
+            ]),
+            o.positions[0].data
+        );
+        assert_eq!("Normal0", o.normals[0].name);
+        assert_eq!(
+            VectorData::Vector3(vec![[0.0, 0.0, 1.0]; 4]),
+            o.normals[0].data
+        );
+        assert_eq!("map1", o.texture_coordinates[0].name);
+        assert_eq!(
+            VectorData::Vector2(vec![[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]),
+            o.texture_coordinates[0].data
+        );
+    }
+
+    #[cfg(feature = "obj")]
+    #[test]
+    fn read_obj_multiple_objects() {
+        let obj = "
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 1.0 1.0 0.0
+o a
+f 1 2 3
+o a
+f -3 -2 -1
+g empty
+o b
+f 3//1 2//1 1//1
+vn 0.0 0.0 1.0
+";
+        // Normals must be defined before being referenced.
+        assert!(matches!(
+            read_obj(obj.as_bytes()),
+            Err(ImportError::IndexOutOfRange { index: 1, count: 0 })
+        ));
+
+        let obj = obj.replace(
+            "f 3//1 2//1 1//1\nvn 0.0 0.0 1.0",
+            "vn 0.0 0.0 1.0\nf 3//1 2//1 1//1",
+        );
+        let objects = read_obj(obj.as_bytes()).unwrap();
+        assert_eq!(
+            vec![("a", 0), ("a", 1), ("b", 0)],
+            objects
+                .iter()
+                .map(|o| (o.name.as_str(), o.subindex))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0, 1, 2], objects[1].vertex_indices);
+        assert!(objects[0].normals.is_empty());
+        assert!(objects[0].texture_coordinates.is_empty());
+        assert_eq!(1, objects[2].normals.len());
+    }
+
+    #[cfg(feature = "obj")]
+    #[test]
+    fn read_obj_invalid() {
+        assert!(matches!(
+            read_obj("v 0.0 a 0.0".as_bytes()),
+            Err(ImportError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            read_obj("v 0 0 0\nf 1 2 3".as_bytes()),
+            Err(ImportError::IndexOutOfRange { index: 2, count: 1 })
+        ));
+        assert!(matches!(
+            read_obj("v 0 0 0\nf 1 1".as_bytes()),
+            Err(ImportError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[cfg(feature = "ply")]
+    #[test]
+    fn read_ply_ascii() {
+        let ply = "ply
+format ascii 1.0
+comment test
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property float s
+property float t
+property uchar red
+property uchar green
+property uchar blue
+element face 1
+property list uchar uint vertex_indices
+end_header
+0 0 0 0 0 1 0 0 255 0 0
+1 0 0 0 0 1 1 0 0 255 0
+1 1 0 0 0 1 1 1 0 0 255
+0 1 0 0 0 1 0 1 255 255 255
+4 0 1 2 3
+";
+        let o = read_ply(ply.as_bytes()).unwrap();
+        assert_eq!("", o.name);
+        assert_eq!(vec![0, 1, 2, 0, 2, 3], o.vertex_indices);
+        assert_eq!(
+            VectorData::Vector3(vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0]
+            ]),
+            o.positions[0].data
+        );
+        assert_eq!(
+            VectorData::Vector3(vec![[0.0, 0.0, 1.0]; 4]),
+            o.normals[0].data
+        );
+        assert_eq!(
+            VectorData::Vector2(vec![[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]),
+            o.texture_coordinates[0].data
+        );
+        assert_eq!("colorSet1", o.color_sets[0].name);
+        assert_eq!(
+            VectorData::Vector4(vec![
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 1.0],
+                [1.0, 1.0, 1.0, 1.0]
+            ]),
+            o.color_sets[0].data
+        );
+    }
+
+    #[cfg(feature = "ply")]
+    #[test]
+    fn read_ply_binary_little_endian() {
+        let mut ply = b"ply
+format binary_little_endian 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+"
+        .to_vec();
+        for v in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]] {
+            for c in v {
+                ply.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        ply.push(3);
+        for i in [2i32, 1, 0] {
+            ply.extend_from_slice(&i.to_le_bytes());
+        }
+
+        let o = read_ply(ply.as_slice()).unwrap();
+        assert_eq!(vec![2, 1, 0], o.vertex_indices);
+        assert_eq!(
+            VectorData::Vector3(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]]),
+            o.positions[0].data
+        );
+        assert!(o.normals.is_empty());
+        assert!(o.texture_coordinates.is_empty());
+        assert!(o.color_sets.is_empty());
+    }
+
+    #[cfg(feature = "ply")]
+    #[test]
+    fn read_ply_invalid() {
+        assert!(matches!(
+            read_ply("obj\n".as_bytes()),
+            Err(ImportError::Parse { line: 1, .. })
+        ));
+
+        let ply = "ply
+format ascii 1.0
+element vertex 1
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+3 0 1 2
+";
+        assert!(matches!(
+            read_ply(ply.as_bytes()),
+            Err(ImportError::IndexOutOfRange { index: 1, count: 1 })
+        ));
+    }
+
+    #[cfg(feature = "ply")]
+    #[test]
+    fn read_ply_invalid_face_indices() {
+        let ply = |indices: &str| {
+            format!(
+                "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar double vertex_indices
+end_header
+0 0 0
+1 0 0
+1 1 0
+3 {indices}
+"
+            )
+        };
+
+        assert!(matches!(
+            read_ply(ply("0 1 -1").as_bytes()),
+            Err(ImportError::IndexOutOfRange {
+                index: -1,
+                count: 3
+            })
+        ));
+        assert!(matches!(
+            read_ply(ply("0 1 1.5").as_bytes()),
+            Err(ImportError::Parse { line: 4, .. })
+        ));
+        assert!(matches!(
+            read_ply(ply("0 1 4294967296").as_bytes()),
+            Err(ImportError::IndexOutOfRange {
+                index: 4294967296,
+                count: 3
+            })
+        ));
+        assert!(matches!(
+            read_ply(ply("0 1 3").as_bytes()),
+            Err(ImportError::IndexOutOfRange { index: 3, count: 3 })
+        ));
+        assert_eq!(
+            vec![0, 1, 2],
+            read_ply(ply("0 1 2").as_bytes()).unwrap().vertex_indices
+        );
+    }
+}
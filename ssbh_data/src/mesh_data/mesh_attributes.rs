@@ -1,6 +1,7 @@
 use super::vector_data::*;
 use super::{
-    AttributeData, AttributeDataTypeV10Ext, AttributeDataTypeV8Ext, MeshObjectData, VectorData,
+    error, AttributeData, AttributeDataTypeV10Ext, AttributeDataTypeV8Ext, AttributeLayout,
+    AttributeUsage, MeshObjectData, VectorData, VERTEX_BUFFER_COUNT,
 };
 use binrw::io::{Seek, Write};
 use itertools::Itertools;
@@ -11,6 +12,7 @@ use ssbh_lib::{
     },
     SsbhArray, SsbhString,
 };
+use std::collections::HashSet;
 
 pub struct MeshAttributes<A> {
     pub buffer_info: [(u32, VersionedVectorData); 4],
@@ -139,6 +141,132 @@ pub fn create_attributes_v10(data: &MeshObjectData) -> MeshAttributes<AttributeV
     )
 }
 
+pub fn create_attributes_v10_with_layout(
+    data: &MeshObjectData,
+    layout: &[AttributeLayout],
+) -> Result<MeshAttributes<AttributeV10>, error::Error> {
+    let mut strides = [0usize; VERTEX_BUFFER_COUNT];
+    let mut vector_data: [Vec<VectorDataV10>; VERTEX_BUFFER_COUNT] = Default::default();
+    let mut attributes = Vec::new();
+    let mut used = HashSet::new();
+
+    // Attributes are interleaved in the order they appear in the layout.
+    for entry in layout {
+        let buffer_index = entry.buffer_index as usize;
+        if buffer_index >= strides.len() {
+            return Err(error::Error::UnsupportedLayoutBufferIndex {
+                buffer_index: entry.buffer_index,
+            });
+        }
+
+        let (collection, usage, f): (_, _, fn(&VectorData) -> VectorDataV10) = match entry.usage {
+            AttributeUsage::Position => (
+                &data.positions,
+                AttributeUsageV9::Position,
+                VectorDataV10::from_positions,
+            ),
+            AttributeUsage::Normal => (
+                &data.normals,
+                AttributeUsageV9::Normal,
+                VectorDataV10::from_vectors,
+            ),
+            AttributeUsage::Binormal => (
+                &data.binormals,
+                AttributeUsageV9::Binormal,
+                VectorDataV10::from_vectors,
+            ),
+            AttributeUsage::Tangent => (
+                &data.tangents,
+                AttributeUsageV9::Tangent,
+                VectorDataV10::from_vectors,
+            ),
+            AttributeUsage::TextureCoordinate => (
+                &data.texture_coordinates,
+                AttributeUsageV9::TextureCoordinate,
+                VectorDataV10::from_vectors,
+            ),
+            AttributeUsage::ColorSet => (
+                &data.color_sets,
+                AttributeUsageV9::ColorSet,
+                VectorDataV10::from_colors,
+            ),
+        };
+
+        // The subindex is determined by the attribute's position within its usage.
+        let subindex = collection
+            .iter()
+            .position(|a| a.name == entry.name)
+            .filter(|i| used.insert((entry.usage, *i)))
+            .ok_or_else(|| error::Error::InvalidAttributeLayout {
+                mesh_object_name: data.name.clone(),
+                attribute_name: entry.name.clone(),
+            })?;
+
+        let attribute_data = &collection[subindex].data;
+        let vectors = match entry.data_type {
+            Some(data_type) => VectorDataV10::from_data_type(attribute_data, data_type)
+                .ok_or_else(|| error::Error::IncompatibleAttributeDataType {
+                    mesh_object_name: data.name.clone(),
+                    attribute_name: entry.name.clone(),
+                    data_type,
+                    component_count: attribute_data.component_count(),
+                })?,
+            None => f(attribute_data),
+        };
+        let attribute = create_attribute_v10(
+            &entry.name,
+            subindex,
+            entry.buffer_index,
+            usage,
+            vectors.data_type(),
+            strides[buffer_index],
+        );
+
+        strides[buffer_index] += attribute.data_type.get_size_in_bytes_v10();
+        vector_data[buffer_index].push(vectors);
+        attributes.push(attribute);
+    }
+
+    // Check for attributes that would otherwise be silently excluded from the buffers.
+    for (usage, collection) in [
+        (AttributeUsage::Position, &data.positions),
+        (AttributeUsage::Normal, &data.normals),
+        (AttributeUsage::Binormal, &data.binormals),
+        (AttributeUsage::Tangent, &data.tangents),
+        (AttributeUsage::TextureCoordinate, &data.texture_coordinates),
+        (AttributeUsage::ColorSet, &data.color_sets),
+    ] {
+        if let Some((_, a)) = collection
+            .iter()
+            .enumerate()
+            .find(|(i, _)| !used.contains(&(usage, *i)))
+        {
+            return Err(error::Error::MissingAttributeLayout {
+                mesh_object_name: data.name.clone(),
+                attribute_name: a.name.clone(),
+            });
+        }
+    }
+
+    // Buffer 2 uses a placeholder stride to match in game meshes unless it contains attributes.
+    let use_buffer2 = !vector_data[2].is_empty();
+    if !use_buffer2 {
+        strides[2] = 32;
+    }
+
+    let [vector_data0, vector_data1, vector_data2, vector_data3] = vector_data;
+    Ok(MeshAttributes {
+        buffer_info: [
+            (strides[0] as u32, VersionedVectorData::V10(vector_data0)),
+            (strides[1] as u32, VersionedVectorData::V10(vector_data1)),
+            (strides[2] as u32, VersionedVectorData::V10(vector_data2)),
+            (strides[3] as u32, VersionedVectorData::V10(vector_data3)),
+        ],
+        attributes: attributes.into(),
+        use_buffer2,
+    })
+}
+
 fn get_attributes<U: Copy, V, F: Fn(&VectorData) -> V>(
     attributes: &[AttributeData],
     usage: U,
@@ -386,6 +514,7 @@ pub(crate) fn write_attributes<W: Write + Seek>(
 mod tests {
     use super::*;
     use crate::assert_hex_eq;
+    use crate::mesh_data::{default_attribute_layout, DataType};
     use binrw::io::Cursor;
     use half::f16;
     use hexlit::hex;
@@ -1025,6 +1154,259 @@ mod tests {
         );
     }
 
+    fn layout_object() -> MeshObjectData {
+        MeshObjectData {
+            name: "name".into(),
+            positions: vec![AttributeData {
+                name: "p0".into(),
+                data: VectorData::Vector3(vec![[0.0; 3]]),
+            }],
+            normals: vec![AttributeData {
+                name: "n0".into(),
+                data: VectorData::Vector3(vec![[0.0; 3]]),
+            }],
+            texture_coordinates: vec![
+                AttributeData {
+                    name: "map1".into(),
+                    data: VectorData::Vector2(vec![[0.0; 2]]),
+                },
+                AttributeData {
+                    name: "uvSet".into(),
+                    data: VectorData::Vector2(vec![[0.0; 2]]),
+                },
+            ],
+            color_sets: vec![AttributeData {
+                name: "colorSet1".into(),
+                data: VectorData::Vector4(vec![[0.0; 4]]),
+            }],
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn layout(usage: AttributeUsage, name: &str, buffer_index: u32) -> AttributeLayout {
+        AttributeLayout {
+            usage,
+            name: name.into(),
+            buffer_index,
+            data_type: None,
+        }
+    }
+
+    #[test]
+    fn create_attributes_v10_default_layout() {
+        let data = layout_object();
+
+        let expected = create_attributes_v10(&data);
+        let actual =
+            create_attributes_v10_with_layout(&data, &default_attribute_layout(&data)).unwrap();
+
+        assert_eq!(expected.buffer_info, actual.buffer_info);
+        assert_eq!(expected.attributes, actual.attributes);
+        assert_eq!(expected.use_buffer2, actual.use_buffer2);
+    }
+
+    #[test]
+    fn create_attributes_v10_custom_layout() {
+        let data = layout_object();
+
+        // Interleave the UVs with the positions and normals in buffer 0.
+        let MeshAttributes {
+            buffer_info: [(stride0, _), (stride1, _), (stride2, _), (stride3, _)],
+            attributes,
+            use_buffer2,
+        } = create_attributes_v10_with_layout(
+            &data,
+            &[
+                layout(AttributeUsage::Position, "p0", 0),
+                layout(AttributeUsage::TextureCoordinate, "uvSet", 0),
+                layout(AttributeUsage::Normal, "n0", 0),
+                layout(AttributeUsage::ColorSet, "colorSet1", 1),
+                layout(AttributeUsage::TextureCoordinate, "map1", 0),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(32, stride0);
+        assert_eq!(4, stride1);
+        assert_eq!(32, stride2);
+        assert_eq!(0, stride3);
+        assert!(!use_buffer2);
+
+        assert_eq!(
+            vec![
+                (AttributeUsageV9::Position, 0, 0, 0),
+                (AttributeUsageV9::TextureCoordinate, 0, 12, 1),
+                (AttributeUsageV9::Normal, 0, 16, 0),
+                (AttributeUsageV9::ColorSet, 1, 0, 0),
+                (AttributeUsageV9::TextureCoordinate, 0, 28, 0),
+            ],
+            attributes
+                .elements
+                .iter()
+                .map(|a| (a.usage, a.buffer_index, a.buffer_offset, a.subindex))
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn create_attributes_v10_layout_missing_attribute() {
+        let data = layout_object();
+
+        let result = create_attributes_v10_with_layout(
+            &data,
+            &[
+                layout(AttributeUsage::Position, "p0", 0),
+                layout(AttributeUsage::Normal, "n0", 0),
+                layout(AttributeUsage::TextureCoordinate, "map1", 1),
+                layout(AttributeUsage::ColorSet, "colorSet1", 1),
+            ],
+        );
+
+        assert!(matches!(
+            result,
+            Err(error::Error::MissingAttributeLayout { mesh_object_name, attribute_name })
+            if mesh_object_name == "name" && attribute_name == "uvSet"
+        ));
+    }
+
+    #[test]
+    fn create_attributes_v10_layout_invalid_attribute() {
+        let data = layout_object();
+
+        // The name must match an attribute with the same usage.
+        let result =
+            create_attributes_v10_with_layout(&data, &[layout(AttributeUsage::Normal, "p0", 0)]);
+        assert!(matches!(
+            result,
+            Err(error::Error::InvalidAttributeLayout { attribute_name, .. })
+            if attribute_name == "p0"
+        ));
+
+        // Each attribute can only be assigned once.
+        let result = create_attributes_v10_with_layout(
+            &data,
+            &[
+                layout(AttributeUsage::Position, "p0", 0),
+                layout(AttributeUsage::Position, "p0", 1),
+            ],
+        );
+        assert!(matches!(
+            result,
+            Err(error::Error::InvalidAttributeLayout { attribute_name, .. })
+            if attribute_name == "p0"
+        ));
+    }
+
+    #[test]
+    fn create_attributes_v10_layout_buffers2_3() {
+        let data = layout_object();
+
+        let MeshAttributes {
+            buffer_info: [(stride0, _), (stride1, _), (stride2, _), (stride3, _)],
+            attributes,
+            use_buffer2,
+        } = create_attributes_v10_with_layout(
+            &data,
+            &[
+                layout(AttributeUsage::Position, "p0", 0),
+                layout(AttributeUsage::Normal, "n0", 2),
+                layout(AttributeUsage::TextureCoordinate, "map1", 3),
+                layout(AttributeUsage::TextureCoordinate, "uvSet", 3),
+                layout(AttributeUsage::ColorSet, "colorSet1", 1),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(12, stride0);
+        assert_eq!(4, stride1);
+        assert_eq!(12, stride2);
+        assert_eq!(8, stride3);
+        assert!(use_buffer2);
+
+        assert_eq!(
+            vec![
+                (AttributeUsageV9::Position, 0, 0),
+                (AttributeUsageV9::Normal, 2, 0),
+                (AttributeUsageV9::TextureCoordinate, 3, 0),
+                (AttributeUsageV9::TextureCoordinate, 3, 4),
+                (AttributeUsageV9::ColorSet, 1, 0),
+            ],
+            attributes
+                .elements
+                .iter()
+                .map(|a| (a.usage, a.buffer_index, a.buffer_offset))
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn create_attributes_v10_layout_invalid_buffer() {
+        let result = create_attributes_v10_with_layout(
+            &layout_object(),
+            &[layout(AttributeUsage::Position, "p0", 4)],
+        );
+        assert!(matches!(
+            result,
+            Err(error::Error::UnsupportedLayoutBufferIndex { buffer_index: 4 })
+        ));
+    }
+
+    #[test]
+    fn create_attributes_v10_layout_data_type() {
+        let data = layout_object();
+
+        let mut layout = default_attribute_layout(&data);
+        for l in &mut layout {
+            l.data_type = match l.usage {
+                AttributeUsage::TextureCoordinate => Some(DataType::Float2),
+                AttributeUsage::ColorSet => Some(DataType::HalfFloat4),
+                _ => None,
+            };
+        }
+
+        let MeshAttributes {
+            buffer_info: [(stride0, _), (stride1, _), _, _],
+            attributes,
+            ..
+        } = create_attributes_v10_with_layout(&data, &layout).unwrap();
+
+        assert_eq!(24, stride0);
+        assert_eq!(24, stride1);
+        assert_eq!(
+            vec![
+                AttributeDataTypeV10::Float3,
+                AttributeDataTypeV10::Float3,
+                AttributeDataTypeV10::Float2,
+                AttributeDataTypeV10::Float2,
+                AttributeDataTypeV10::HalfFloat4,
+            ],
+            attributes
+                .elements
+                .iter()
+                .map(|a| a.data_type)
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn create_attributes_v10_layout_incompatible_data_type() {
+        let data = layout_object();
+
+        let mut layout = default_attribute_layout(&data);
+        layout[0].data_type = Some(DataType::HalfFloat4);
+
+        let result = create_attributes_v10_with_layout(&data, &layout);
+        assert!(matches!(
+            result,
+            Err(error::Error::IncompatibleAttributeDataType {
+                attribute_name,
+                data_type: DataType::HalfFloat4,
+                component_count: 3,
+                ..
+            }) if attribute_name == "p0"
+        ));
+    }
+
     #[test]
     fn write_attributes_empty() {
         write_attributes::<std::io::Cursor<Vec<u8>>>(&[], &mut [], &[]).unwrap();
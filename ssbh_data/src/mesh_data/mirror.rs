@@ -0,0 +1,276 @@
+use super::{AttributeData, MeshData, MeshObjectData, VectorData};
+
+/// The axis perpendicular to the mirror plane for [MeshObjectData::mirror].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MirrorAxis {
+    /// Mirror across the YZ plane by negating X coordinates.
+    #[default]
+    X,
+    /// Mirror across the XZ plane by negating Y coordinates.
+    Y,
+    /// Mirror across the XY plane by negating Z coordinates.
+    Z,
+}
+
+impl MirrorAxis {
+    fn component(self) -> usize {
+        match self {
+            MirrorAxis::X => 0,
+            MirrorAxis::Y => 1,
+            MirrorAxis::Z => 2,
+        }
+    }
+}
+
+impl MeshObjectData {
+    /// Mirrors the object across the plane perpendicular to `axis` through the origin.
+    ///
+    /// The [positions](#structfield.positions), [normals](#structfield.normals),
+    /// [binormals](#structfield.binormals), and [tangents](#structfield.tangents) are reflected.
+    /// The W component of 4 component tangents stores the bitangent sign and is negated to preserve handedness.
+    /// The [vertex_indices](#structfield.vertex_indices) are reordered to reverse the triangle winding
+    /// so front faces remain visible after mirroring.
+    ///
+    /// Bone names are not changed. See [MeshObjectData::mirror_bone_names].
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, MeshObjectData, MirrorAxis, VectorData};
+
+    let mut object = MeshObjectData {
+        vertex_indices: vec![0, 1, 2],
+        positions: vec![AttributeData {
+            name: "Position0".to_string(),
+            data: VectorData::Vector3(vec![[1.0, 0.0, 0.0], [2.0, 1.0, 0.0], [3.0, 0.0, 1.0]]),
+        }],
+        ..MeshObjectData::default()
+    };
+
+    object.mirror(MirrorAxis::X);
+    assert_eq!(vec![0, 2, 1], object.vertex_indices);
+    assert_eq!(
+        VectorData::Vector3(vec![[-1.0, 0.0, 0.0], [-2.0, 1.0, 0.0], [-3.0, 0.0, 1.0]]),
+        object.positions[0].data
+    );
+    ```
+     */
+    pub fn mirror(&mut self, axis: MirrorAxis) {
+        let component = axis.component();
+        for attribute in self
+            .positions
+            .iter_mut()
+            .chain(&mut self.normals)
+            .chain(&mut self.binormals)
+        {
+            reflect(attribute, component, false);
+        }
+        for attribute in &mut self.tangents {
+            reflect(attribute, component, true);
+        }
+
+        for triangle in self.vertex_indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+
+    /// Swaps the left and right side of the [bone_influences](#structfield.bone_influences)
+    /// and [parent_bone_name](#structfield.parent_bone_name) using [mirrored_bone_name].
+    /// Returns the number of renamed bones.
+    ///
+    /// This assigns the weights to the bones on the opposite side after [MeshObjectData::mirror].
+    pub fn mirror_bone_names(&mut self) -> usize {
+        let mut count = 0;
+        for name in self
+            .bone_influences
+            .iter_mut()
+            .map(|i| &mut i.bone_name)
+            .chain(std::iter::once(&mut self.parent_bone_name))
+        {
+            if let Some(mirrored) = mirrored_bone_name(name) {
+                *name = mirrored;
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl MeshData {
+    /// Applies [MeshObjectData::mirror] to every object.
+    /// If `mirror_bone_names` is `true`, also applies [MeshObjectData::mirror_bone_names].
+    pub fn mirror(&mut self, axis: MirrorAxis, mirror_bone_names: bool) {
+        for object in &mut self.objects {
+            object.mirror(axis);
+            if mirror_bone_names {
+                object.mirror_bone_names();
+            }
+        }
+    }
+}
+
+/// Returns the name of the bone on the opposite side
+/// or `None` if the name doesn't indicate a side.
+///
+/// Sides are indicated by an uppercase `L` or `R` at the end of the name or before any trailing digits
+/// following the naming conventions for Smash Ultimate like `"ArmL"` or `"FingerR12"`.
+/**
+```rust
+use ssbh_data::mesh_data::mirrored_bone_name;
+
+assert_eq!(Some("ArmR".to_string()), mirrored_bone_name("ArmL"));
+assert_eq!(Some("FingerL12".to_string()), mirrored_bone_name("FingerR12"));
+assert_eq!(None, mirrored_bone_name("Hip"));
+```
+ */
+pub fn mirrored_bone_name(name: &str) -> Option<String> {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let suffix = &name[prefix.len()..];
+
+    let mut chars = prefix.chars().rev();
+    let side = match chars.next()? {
+        'L' => 'R',
+        'R' => 'L',
+        _ => return None,
+    };
+    // Avoid matching abbreviations like "COL" by requiring a lowercase letter or separator.
+    let previous = chars.next()?;
+    if !(previous.is_ascii_lowercase() || previous == '_') {
+        return None;
+    }
+
+    Some(format!("{}{side}{suffix}", &prefix[..prefix.len() - 1]))
+}
+
+fn reflect(attribute: &mut AttributeData, component: usize, negate_w: bool) {
+    match &mut attribute.data {
+        VectorData::Vector2(values) => reflect_values(values, component, false),
+        VectorData::Vector3(values) => reflect_values(values, component, false),
+        VectorData::Vector4(values) => reflect_values(values, component, negate_w),
+    }
+}
+
+fn reflect_values<const N: usize>(values: &mut [[f32; N]], component: usize, negate_w: bool) {
+    for value in values {
+        if let Some(c) = value.get_mut(component) {
+            *c = -*c;
+        }
+        if negate_w {
+            value[N - 1] = -value[N - 1];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::BoneInfluence;
+
+    fn attribute(data: VectorData) -> AttributeData {
+        AttributeData {
+            name: String::new(),
+            data,
+        }
+    }
+
+    #[test]
+    fn mirror_object_x() {
+        let mut object = MeshObjectData {
+            vertex_indices: vec![0, 1, 2, 2, 1, 0],
+            positions: vec![attribute(VectorData::Vector3(vec![[1.0, 2.0, 3.0]]))],
+            normals: vec![attribute(VectorData::Vector4(vec![[1.0, 0.0, 0.0, 0.0]]))],
+            binormals: vec![attribute(VectorData::Vector3(vec![[0.5, 1.0, 0.0]]))],
+            tangents: vec![attribute(VectorData::Vector4(vec![[0.0, 0.0, 1.0, 1.0]]))],
+            texture_coordinates: vec![attribute(VectorData::Vector2(vec![[0.25, 0.5]]))],
+            ..MeshObjectData::default()
+        };
+        object.mirror(MirrorAxis::X);
+
+        assert_eq!(vec![0, 2, 1, 2, 0, 1], object.vertex_indices);
+        assert_eq!(
+            VectorData::Vector3(vec![[-1.0, 2.0, 3.0]]),
+            object.positions[0].data
+        );
+        assert_eq!(
+            VectorData::Vector4(vec![[-1.0, 0.0, 0.0, 0.0]]),
+            object.normals[0].data
+        );
+        assert_eq!(
+            VectorData::Vector3(vec![[-0.5, 1.0, 0.0]]),
+            object.binormals[0].data
+        );
+        assert_eq!(
+            VectorData::Vector4(vec![[0.0, 0.0, 1.0, -1.0]]),
+            object.tangents[0].data
+        );
+        // Texture coordinates aren't affected by mirroring.
+        assert_eq!(
+            VectorData::Vector2(vec![[0.25, 0.5]]),
+            object.texture_coordinates[0].data
+        );
+    }
+
+    #[test]
+    fn mirror_object_z_vector2() {
+        let mut object = MeshObjectData {
+            positions: vec![attribute(VectorData::Vector2(vec![[1.0, 2.0]]))],
+            ..MeshObjectData::default()
+        };
+        object.mirror(MirrorAxis::Z);
+        assert_eq!(
+            VectorData::Vector2(vec![[1.0, 2.0]]),
+            object.positions[0].data
+        );
+    }
+
+    #[test]
+    fn mirror_mesh_bone_names() {
+        let mut mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                parent_bone_name: "HandL".to_string(),
+                bone_influences: vec![
+                    BoneInfluence {
+                        bone_name: "ArmL".to_string(),
+                        vertex_weights: Vec::new(),
+                    },
+                    BoneInfluence {
+                        bone_name: "ArmR".to_string(),
+                        vertex_weights: Vec::new(),
+                    },
+                    BoneInfluence {
+                        bone_name: "Hip".to_string(),
+                        vertex_weights: Vec::new(),
+                    },
+                ],
+                ..MeshObjectData::default()
+            }],
+        };
+        mesh.mirror(MirrorAxis::X, true);
+
+        let names: Vec<_> = mesh.objects[0]
+            .bone_influences
+            .iter()
+            .map(|i| i.bone_name.as_str())
+            .collect();
+        assert_eq!(vec!["ArmR", "ArmL", "Hip"], names);
+        assert_eq!("HandR", mesh.objects[0].parent_bone_name);
+    }
+
+    #[test]
+    fn mirrored_bone_names() {
+        assert_eq!(
+            Some("ShoulderR".to_string()),
+            mirrored_bone_name("ShoulderL")
+        );
+        assert_eq!(
+            Some("FingerL11".to_string()),
+            mirrored_bone_name("FingerR11")
+        );
+        assert_eq!(Some("eye_R".to_string()), mirrored_bone_name("eye_L"));
+        assert_eq!(None, mirrored_bone_name("Tail"));
+        assert_eq!(None, mirrored_bone_name("L"));
+        assert_eq!(None, mirrored_bone_name("COL"));
+        assert_eq!(None, mirrored_bone_name("Hip1"));
+        assert_eq!(None, mirrored_bone_name(""));
+    }
+}
@@ -0,0 +1,424 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    error,
+    vector_data::{VectorDataV10, VectorDataV8},
+    AttributeData, AttributeUsage, BoneInfluence, MeshObjectData, VectorData,
+};
+
+/// A storage format with less precision than the `f32` values in [VectorData].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReducedPrecision {
+    /// 16 bit floating point with roughly 3 significant decimal digits.
+    HalfFloat,
+    /// Unsigned normalized bytes clamped to the range `0.0` to `1.0`.
+    Byte,
+}
+
+/// An attribute that will be stored at reduced precision after converting to [Mesh](ssbh_lib::formats::mesh::Mesh).
+/// See [MeshObjectData::reduced_precision_attributes].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct AttributePrecision {
+    pub usage: AttributeUsage,
+    pub name: String,
+    pub precision: ReducedPrecision,
+    /// The largest absolute difference between a component and its stored value.
+    /// NaN components are ignored.
+    pub max_error: f32,
+}
+
+impl MeshObjectData {
+    /// Finds the attributes that will be stored at reduced precision for the given mesh version
+    /// and calculates the maximum quantization error for this object's data.
+    ///
+    /// Large errors for texture coordinates or color sets often explain visual artifacts in game.
+    /// Attributes stored as `f32` are not included.
+    /// Returns an error if the version is not supported.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, MeshObjectData, ReducedPrecision, VectorData};
+
+    let object = MeshObjectData {
+        texture_coordinates: vec![AttributeData {
+            name: "map1".to_string(),
+            data: VectorData::Vector2(vec![[0.1, 0.2]]),
+        }],
+        ..MeshObjectData::default()
+    };
+
+    let attributes = object.reduced_precision_attributes(1, 10)?;
+    assert_eq!(ReducedPrecision::HalfFloat, attributes[0].precision);
+    assert!(attributes[0].max_error > 0.0);
+
+    // Version 1.8 stores texture coordinates as floats.
+    assert!(object.reduced_precision_attributes(1, 8)?.is_empty());
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn reduced_precision_attributes(
+        &self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Vec<AttributePrecision>, error::Error> {
+        let is_v10 = match (major_version, minor_version) {
+            (1, 10) => true,
+            (1, 8) | (1, 9) => false,
+            _ => {
+                return Err(error::Error::UnsupportedVersion {
+                    major_version,
+                    minor_version,
+                })
+            }
+        };
+
+        let collections: [(&[AttributeData], AttributeUsage, Kind); 6] = [
+            (&self.positions, AttributeUsage::Position, Kind::Position),
+            (&self.normals, AttributeUsage::Normal, Kind::Vector),
+            (&self.binormals, AttributeUsage::Binormal, Kind::Vector),
+            (&self.tangents, AttributeUsage::Tangent, Kind::Vector),
+            (
+                &self.texture_coordinates,
+                AttributeUsage::TextureCoordinate,
+                Kind::Vector,
+            ),
+            (&self.color_sets, AttributeUsage::ColorSet, Kind::Color),
+        ];
+
+        Ok(collections
+            .into_iter()
+            .flat_map(|(attributes, usage, kind)| {
+                attributes.iter().filter_map(move |a| {
+                    let (precision, values) = kind.stored(&a.data, is_v10)?;
+                    Some(AttributePrecision {
+                        usage,
+                        name: a.name.clone(),
+                        precision,
+                        max_error: max_error(&a.data, &values),
+                    })
+                })
+            })
+            .collect())
+    }
+}
+
+impl MeshObjectData {
+    /// Returns `true` if `other` has the same names, vertex indices, and attributes as `self`
+    /// with attribute values and skin weights that differ by at most `tolerance`.
+    ///
+    /// Attribute values are compared after converting to the lowest precision used for any supported version.
+    /// This allows comparing edited data to data read from a file without false positives from
+    /// half precision floats or unsigned normalized bytes.
+    /// Attribute and bone influence order must match. NaN values are equal to other NaN values.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, MeshObjectData, VectorData};
+
+    let object = MeshObjectData {
+        color_sets: vec![AttributeData {
+            name: "colorSet1".to_string(),
+            data: VectorData::Vector4(vec![[0.5; 4]]),
+        }],
+        ..MeshObjectData::default()
+    };
+
+    // Color sets are stored as bytes, so both values are stored as 128 / 255.
+    let mut other = object.clone();
+    other.color_sets[0].data = VectorData::Vector4(vec![[0.501; 4]]);
+    assert!(object.approx_eq(&other, 0.0));
+
+    other.color_sets[0].data = VectorData::Vector4(vec![[0.6; 4]]);
+    assert!(!object.approx_eq(&other, 0.01));
+    ```
+     */
+    pub fn approx_eq(&self, other: &MeshObjectData, tolerance: f32) -> bool {
+        let attributes_eq = |a: &[AttributeData], b: &[AttributeData], kind: Kind| {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| a.name == b.name && kind.approx_eq(&a.data, &b.data, tolerance))
+        };
+
+        self.name == other.name
+            && self.subindex == other.subindex
+            && self.parent_bone_name == other.parent_bone_name
+            && self.sort_bias == other.sort_bias
+            && self.disable_depth_write == other.disable_depth_write
+            && self.disable_depth_test == other.disable_depth_test
+            && self.vertex_indices == other.vertex_indices
+            && attributes_eq(&self.positions, &other.positions, Kind::Position)
+            && attributes_eq(&self.normals, &other.normals, Kind::Vector)
+            && attributes_eq(&self.binormals, &other.binormals, Kind::Vector)
+            && attributes_eq(&self.tangents, &other.tangents, Kind::Vector)
+            && attributes_eq(
+                &self.texture_coordinates,
+                &other.texture_coordinates,
+                Kind::Vector,
+            )
+            && attributes_eq(&self.color_sets, &other.color_sets, Kind::Color)
+            && bone_influences_approx_eq(&self.bone_influences, &other.bone_influences, tolerance)
+    }
+}
+
+fn bone_influences_approx_eq(a: &[BoneInfluence], b: &[BoneInfluence], tolerance: f32) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.bone_name == b.bone_name
+                && a.vertex_weights.len() == b.vertex_weights.len()
+                && a.vertex_weights
+                    .iter()
+                    .zip(&b.vertex_weights)
+                    .all(|(a, b)| {
+                        a.vertex_index == b.vertex_index
+                            && value_approx_eq(a.vertex_weight, b.vertex_weight, tolerance)
+                    })
+        })
+}
+
+fn value_approx_eq(a: f32, b: f32, tolerance: f32) -> bool {
+    a == b || (a - b).abs() <= tolerance || (a.is_nan() && b.is_nan())
+}
+
+// The conversion used for each usage when creating the vertex buffers.
+#[derive(Clone, Copy)]
+enum Kind {
+    Position,
+    Vector,
+    Color,
+}
+
+impl Kind {
+    fn stored(self, data: &VectorData, is_v10: bool) -> Option<(ReducedPrecision, Vec<f32>)> {
+        if is_v10 {
+            stored_v10(&self.convert_v10(data))
+        } else {
+            stored_v8(&self.convert_v8(data))
+        }
+    }
+
+    fn approx_eq(self, a: &VectorData, b: &VectorData, tolerance: f32) -> bool {
+        // Version 1.10 uses the lowest precision for each usage.
+        a.component_count() == b.component_count()
+            && a.len() == b.len()
+            && self
+                .stored_v10_values(a)
+                .into_iter()
+                .zip(self.stored_v10_values(b))
+                .all(|(a, b)| value_approx_eq(a, b, tolerance))
+    }
+
+    fn stored_v10_values(self, data: &VectorData) -> Vec<f32> {
+        stored_v10(&self.convert_v10(data))
+            .map(|(_, values)| values)
+            .unwrap_or_else(|| components(data))
+    }
+
+    fn convert_v10(self, data: &VectorData) -> VectorDataV10 {
+        match self {
+            Kind::Position => VectorDataV10::from_positions(data),
+            Kind::Vector => VectorDataV10::from_vectors(data),
+            Kind::Color => VectorDataV10::from_colors(data),
+        }
+    }
+
+    fn convert_v8(self, data: &VectorData) -> VectorDataV8 {
+        match self {
+            Kind::Position => VectorDataV8::from_positions(data),
+            Kind::Vector => VectorDataV8::from_vectors(data),
+            Kind::Color => VectorDataV8::from_colors(data),
+        }
+    }
+}
+
+fn stored_v10(data: &VectorDataV10) -> Option<(ReducedPrecision, Vec<f32>)> {
+    match data {
+        VectorDataV10::Float2(_) | VectorDataV10::Float3(_) | VectorDataV10::Float4(_) => None,
+        VectorDataV10::HalfFloat2(v) => Some((ReducedPrecision::HalfFloat, half_values(v))),
+        VectorDataV10::HalfFloat4(v) => Some((ReducedPrecision::HalfFloat, half_values(v))),
+        VectorDataV10::Byte4(v) => Some((ReducedPrecision::Byte, byte_values(v))),
+    }
+}
+
+fn stored_v8(data: &VectorDataV8) -> Option<(ReducedPrecision, Vec<f32>)> {
+    match data {
+        VectorDataV8::Float2(_) | VectorDataV8::Float3(_) | VectorDataV8::Float4(_) => None,
+        VectorDataV8::HalfFloat4(v) => Some((ReducedPrecision::HalfFloat, half_values(v))),
+        VectorDataV8::Byte4(v) => Some((ReducedPrecision::Byte, byte_values(v))),
+    }
+}
+
+pub(super) fn half_values<const N: usize>(values: &[[half::f16; N]]) -> Vec<f32> {
+    values.iter().flatten().map(|v| v.to_f32()).collect()
+}
+
+pub(super) fn byte_values<const N: usize>(values: &[[u8; N]]) -> Vec<f32> {
+    values.iter().flatten().map(|v| *v as f32 / 255.0).collect()
+}
+
+pub(super) fn components(data: &VectorData) -> Vec<f32> {
+    match data {
+        VectorData::Vector2(v) => v.iter().flatten().copied().collect(),
+        VectorData::Vector3(v) => v.iter().flatten().copied().collect(),
+        VectorData::Vector4(v) => v.iter().flatten().copied().collect(),
+    }
+}
+
+fn max_error(data: &VectorData, stored: &[f32]) -> f32 {
+    // f32::max ignores NaN, so NaN components don't hide the error for other components.
+    components(data)
+        .iter()
+        .zip(stored)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(name: &str, data: VectorData) -> Vec<AttributeData> {
+        vec![AttributeData {
+            name: name.to_string(),
+            data,
+        }]
+    }
+
+    #[test]
+    fn reduced_precision_v10() {
+        let object = MeshObjectData {
+            positions: attribute("Position0", VectorData::Vector3(vec![[0.1; 3]])),
+            normals: attribute("Normal0", VectorData::Vector4(vec![[1.0, 0.0, 0.0, 0.0]])),
+            texture_coordinates: attribute("map1", VectorData::Vector2(vec![[2049.0, 0.5]])),
+            color_sets: attribute("colorSet1", VectorData::Vector4(vec![[2.0, 0.5, 0.5, 0.5]])),
+            ..MeshObjectData::default()
+        };
+
+        assert_eq!(
+            vec![
+                AttributePrecision {
+                    usage: AttributeUsage::Normal,
+                    name: "Normal0".to_string(),
+                    precision: ReducedPrecision::HalfFloat,
+                    max_error: 0.0,
+                },
+                AttributePrecision {
+                    usage: AttributeUsage::TextureCoordinate,
+                    name: "map1".to_string(),
+                    precision: ReducedPrecision::HalfFloat,
+                    max_error: 1.0,
+                },
+                AttributePrecision {
+                    usage: AttributeUsage::ColorSet,
+                    name: "colorSet1".to_string(),
+                    precision: ReducedPrecision::Byte,
+                    max_error: 1.0,
+                },
+            ],
+            object.reduced_precision_attributes(1, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn reduced_precision_v8() {
+        let object = MeshObjectData {
+            normals: attribute("Normal0", VectorData::Vector3(vec![[0.1; 3]])),
+            tangents: attribute("Tangent0", VectorData::Vector4(vec![[0.1; 4]])),
+            texture_coordinates: attribute("map1", VectorData::Vector2(vec![[0.1; 2]])),
+            ..MeshObjectData::default()
+        };
+
+        let attributes = object.reduced_precision_attributes(1, 8).unwrap();
+        assert_eq!(1, attributes.len());
+        assert_eq!("Tangent0", attributes[0].name);
+        assert_eq!(ReducedPrecision::HalfFloat, attributes[0].precision);
+        assert!(attributes[0].max_error > 0.0 && attributes[0].max_error < 0.001);
+    }
+
+    #[test]
+    fn reduced_precision_byte_rounding() {
+        let object = MeshObjectData {
+            color_sets: attribute("colorSet1", VectorData::Vector4(vec![[0.5; 4]])),
+            ..MeshObjectData::default()
+        };
+
+        let attributes = object.reduced_precision_attributes(1, 9).unwrap();
+        // 0.5 is stored as 128 / 255.
+        assert_eq!(128.0 / 255.0 - 0.5, attributes[0].max_error);
+    }
+
+    #[test]
+    fn approx_eq_half_float_quantization() {
+        let object = MeshObjectData {
+            positions: attribute("Position0", VectorData::Vector3(vec![[0.1; 3]])),
+            texture_coordinates: attribute("map1", VectorData::Vector2(vec![[0.1, 0.2]])),
+            ..MeshObjectData::default()
+        };
+
+        // Both values have the same half precision representation.
+        let mut other = object.clone();
+        other.texture_coordinates = attribute("map1", VectorData::Vector2(vec![[0.100005, 0.2]]));
+        assert!(object.approx_eq(&other, 0.0));
+
+        // Positions are stored at full precision.
+        other.positions = attribute("Position0", VectorData::Vector3(vec![[0.10001, 0.1, 0.1]]));
+        assert!(!object.approx_eq(&other, 0.0));
+        assert!(object.approx_eq(&other, 0.001));
+    }
+
+    #[test]
+    fn approx_eq_structure_mismatch() {
+        let object = MeshObjectData {
+            normals: attribute("Normal0", VectorData::Vector3(vec![[f32::NAN; 3]])),
+            vertex_indices: vec![0, 1, 2],
+            ..MeshObjectData::default()
+        };
+        assert!(object.approx_eq(&object, 0.0));
+
+        let mut other = object.clone();
+        other.normals = attribute("Normal0", VectorData::Vector4(vec![[f32::NAN; 4]]));
+        assert!(!object.approx_eq(&other, 1.0));
+
+        let mut other = object.clone();
+        other.normals[0].name = "Normal1".to_string();
+        assert!(!object.approx_eq(&other, 1.0));
+
+        let mut other = object.clone();
+        other.vertex_indices = vec![0, 2, 1];
+        assert!(!object.approx_eq(&other, 1.0));
+    }
+
+    #[test]
+    fn approx_eq_bone_influences() {
+        let influence = |vertex_weight| BoneInfluence {
+            bone_name: "Hip".to_string(),
+            vertex_weights: vec![crate::mesh_data::VertexWeight {
+                vertex_index: 0,
+                vertex_weight,
+            }],
+        };
+        let object = MeshObjectData {
+            bone_influences: vec![influence(0.5)],
+            ..MeshObjectData::default()
+        };
+        let other = MeshObjectData {
+            bone_influences: vec![influence(0.501)],
+            ..MeshObjectData::default()
+        };
+        assert!(!object.approx_eq(&other, 0.0));
+        assert!(object.approx_eq(&other, 0.01));
+    }
+
+    #[test]
+    fn reduced_precision_unsupported_version() {
+        let result = MeshObjectData::default().reduced_precision_attributes(1, 11);
+        assert!(matches!(
+            result,
+            Err(error::Error::UnsupportedVersion {
+                major_version: 1,
+                minor_version: 11
+            })
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::{HashMap, HashSet};
+
+use ssbh_lib::formats::nufx::Nufx;
+
+use super::{AttributeData, MeshData, MeshObjectData};
+use crate::{matl_data::MatlData, modl_data::ModlData, nufx_data::NufxData};
+
+/// An attribute removed by [MeshData::remove_unused_attributes].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RemovedAttribute {
+    pub mesh_object_name: String,
+    pub mesh_object_subindex: u64,
+    /// The name of the attribute like `"colorSet1"`.
+    pub attribute_name: String,
+}
+
+impl MeshObjectData {
+    /// Removes the attributes with names not in `required_attributes`
+    /// and returns the names of the removed attributes.
+    ///
+    /// [positions](#structfield.positions) are never removed since they determine the vertex count.
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, MeshObjectData, VectorData};
+
+    let mut object = MeshObjectData {
+        color_sets: vec![
+            AttributeData {
+                name: "colorSet1".to_string(),
+                data: VectorData::Vector4(vec![[0.5; 4]]),
+            },
+            AttributeData {
+                name: "colorSet5".to_string(),
+                data: VectorData::Vector4(vec![[0.5; 4]]),
+            },
+        ],
+        ..MeshObjectData::default()
+    };
+
+    assert_eq!(vec!["colorSet5"], object.retain_attributes(&["colorSet1"]));
+    assert_eq!(1, object.color_sets.len());
+    ```
+     */
+    pub fn retain_attributes<S: AsRef<str>>(&mut self, required_attributes: &[S]) -> Vec<String> {
+        let required: HashSet<_> = required_attributes.iter().map(|a| a.as_ref()).collect();
+
+        let mut removed = Vec::new();
+        for attributes in [
+            &mut self.normals,
+            &mut self.binormals,
+            &mut self.tangents,
+            &mut self.texture_coordinates,
+            &mut self.color_sets,
+        ] {
+            retain_required(attributes, &required, &mut removed);
+        }
+        removed
+    }
+}
+
+fn retain_required(
+    attributes: &mut Vec<AttributeData>,
+    required: &HashSet<&str>,
+    removed: &mut Vec<String>,
+) {
+    attributes.retain(|a| {
+        let is_required = required.contains(a.name.as_str());
+        if !is_required {
+            removed.push(a.name.clone());
+        }
+        is_required
+    });
+}
+
+impl MeshData {
+    /// Removes attributes not required by the shader program for each object's material
+    /// and returns the removed attributes.
+    ///
+    /// The material for each object is found using the entries in `modl`.
+    /// The shader program is the program in `nufx` with the same name as the material's shader label.
+    /// Objects without a material, materials without a shader program,
+    /// and programs without vertex attribute information are left unchanged.
+    ///
+    /// Unused attributes like extra color sets still take up space in the vertex buffers.
+    /// See [MeshObjectData::retain_attributes] for removing attributes manually.
+    pub fn remove_unused_attributes(
+        &mut self,
+        modl: &ModlData,
+        matl: &MatlData,
+        nufx: &NufxData,
+    ) -> Vec<RemovedAttribute> {
+        let required_attributes = program_attributes(nufx);
+
+        let mut removed = Vec::new();
+        for object in &mut self.objects {
+            let required = modl
+                .entries
+                .iter()
+                .find(|e| {
+                    e.mesh_object_name == object.name && e.mesh_object_subindex == object.subindex
+                })
+                .and_then(|e| {
+                    matl.entries
+                        .iter()
+                        .find(|m| m.material_label == e.material_label)
+                })
+                .and_then(|m| required_attributes.get(m.shader_label.as_str()));
+
+            if let Some(required) = required {
+                removed.extend(object.retain_attributes(required).into_iter().map(|name| {
+                    RemovedAttribute {
+                        mesh_object_name: object.name.clone(),
+                        mesh_object_subindex: object.subindex,
+                        attribute_name: name,
+                    }
+                }));
+            }
+        }
+        removed
+    }
+}
+
+// Only version 1.1 stores the required vertex attributes for each program.
+fn program_attributes(nufx: &NufxData) -> HashMap<String, Vec<String>> {
+    match &nufx.nufx {
+        Nufx::V0(_) => HashMap::new(),
+        Nufx::V1(nufx) => nufx
+            .programs
+            .elements
+            .iter()
+            .map(|p| {
+                (
+                    p.name.to_string_lossy(),
+                    p.vertex_attributes
+                        .elements
+                        .iter()
+                        .map(|a| a.name.to_string_lossy())
+                        .collect(),
+                )
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::VectorData;
+    use crate::modl_data::ModlEntryData;
+    use crate::test_fixtures::matl_entry_data;
+    use ssbh_lib::formats::nufx::{NufxV1, ShaderProgramV1, ShaderStages, VertexAttribute};
+    use ssbh_lib::SsbhArray;
+
+    fn attribute(name: &str) -> AttributeData {
+        AttributeData {
+            name: name.to_string(),
+            data: VectorData::Vector2(vec![[0.0; 2]]),
+        }
+    }
+
+    fn object(name: &str) -> MeshObjectData {
+        MeshObjectData {
+            name: name.to_string(),
+            positions: vec![attribute("Position0")],
+            normals: vec![attribute("Normal0")],
+            texture_coordinates: vec![attribute("map1"), attribute("uvSet")],
+            color_sets: vec![attribute("colorSet1"), attribute("colorSet5")],
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn program(name: &str, attributes: &[&str]) -> ShaderProgramV1 {
+        ShaderProgramV1 {
+            name: name.into(),
+            render_pass: "nu::Opaque".into(),
+            shaders: ShaderStages {
+                vertex_shader: "".into(),
+                unk_shader1: "".into(),
+                unk_shader2: "".into(),
+                geometry_shader: "".into(),
+                pixel_shader: "".into(),
+                compute_shader: "".into(),
+            },
+            vertex_attributes: attributes
+                .iter()
+                .map(|a| VertexAttribute {
+                    name: (*a).into(),
+                    attribute_name: (*a).into(),
+                })
+                .collect(),
+            material_parameters: SsbhArray::new(),
+        }
+    }
+
+    fn modl_entry(name: &str, material_label: &str) -> ModlEntryData {
+        ModlEntryData {
+            mesh_object_name: name.to_string(),
+            mesh_object_subindex: 0,
+            material_label: material_label.to_string(),
+        }
+    }
+
+    #[test]
+    fn retain_attributes_keeps_positions() {
+        let mut object = object("a");
+        let removed = object.retain_attributes::<&str>(&[]);
+        assert_eq!(
+            vec!["Normal0", "map1", "uvSet", "colorSet1", "colorSet5"],
+            removed
+        );
+        assert_eq!(1, object.positions.len());
+    }
+
+    #[test]
+    fn remove_unused_attributes_by_material() {
+        let mut mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object("a"), object("b"), object("c")],
+        };
+        let modl = ModlData {
+            major_version: 1,
+            minor_version: 0,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: Vec::new(),
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries: vec![modl_entry("a", "mat1"), modl_entry("b", "mat2")],
+        };
+        let matl = crate::test_fixtures::matl_data(vec![
+            matl_entry_data("mat1", "SFX_PBS_1_opaque"),
+            matl_entry_data("mat2", "SFX_PBS_2_opaque"),
+        ]);
+        let nufx = NufxData {
+            nufx: Nufx::V1(NufxV1 {
+                programs: vec![program(
+                    "SFX_PBS_1_opaque",
+                    &["Position0", "Normal0", "map1", "colorSet1"],
+                )]
+                .into(),
+                unk_string_list: SsbhArray::new(),
+            }),
+        };
+
+        let removed = mesh.remove_unused_attributes(&modl, &matl, &nufx);
+        assert_eq!(
+            vec![
+                RemovedAttribute {
+                    mesh_object_name: "a".to_string(),
+                    mesh_object_subindex: 0,
+                    attribute_name: "uvSet".to_string()
+                },
+                RemovedAttribute {
+                    mesh_object_name: "a".to_string(),
+                    mesh_object_subindex: 0,
+                    attribute_name: "colorSet5".to_string()
+                }
+            ],
+            removed
+        );
+
+        // Objects without a known shader program are unchanged.
+        assert_eq!(2, mesh.objects[1].color_sets.len());
+        assert_eq!(2, mesh.objects[2].texture_coordinates.len());
+    }
+}
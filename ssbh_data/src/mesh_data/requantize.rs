@@ -0,0 +1,348 @@
+use ssbh_lib::formats::mesh::Mesh;
+
+use super::{
+    default_attribute_layout, error,
+    precision::{byte_values, components, half_values},
+    vector_data::VectorDataV10,
+    AttributeData, AttributeLayout, AttributeUsage, DataType, MeshData, MeshObjectData, VectorData,
+};
+
+/// An attribute stored with a different data type than the default for its usage.
+/// See [MeshData::to_mesh_requantized].
+#[derive(Debug, PartialEq, Clone)]
+pub struct RequantizedAttribute {
+    pub mesh_object_name: String,
+    pub mesh_object_subindex: u64,
+    pub usage: AttributeUsage,
+    /// The name of the attribute like `"map1"`.
+    pub attribute_name: String,
+    /// The data type used when converting with [Mesh::try_from].
+    pub default_data_type: DataType,
+    pub data_type: DataType,
+    /// The largest absolute difference between a component and its stored value.
+    pub max_error: f32,
+    /// The size of the attribute data with the default data type minus the size with the new data type.
+    /// This is negative if the default data type exceeds the error bound.
+    pub saved_bytes: i64,
+}
+
+impl MeshObjectData {
+    /// Finds the smallest data type for each attribute that stores every component
+    /// with an absolute error of at most `max_error`.
+    /// Only attributes with a data type different than the default for version 1.10 are included.
+    ///
+    /// Each data type keeps the attribute's component count.
+    /// Attributes that can't be stored at reduced precision use full precision floats
+    /// even if the default data type for the usage has less precision.
+    /// NaN components must also be NaN after converting.
+    pub fn requantized_attributes(&self, max_error: f32) -> Vec<RequantizedAttribute> {
+        self.requantized_data_types(max_error)
+            .into_iter()
+            .filter(|r| r.data_type != r.default_data_type)
+            .map(|r| RequantizedAttribute {
+                mesh_object_name: self.name.clone(),
+                mesh_object_subindex: self.subindex,
+                usage: r.usage,
+                attribute_name: r.attribute.name.clone(),
+                default_data_type: r.default_data_type,
+                data_type: r.data_type,
+                max_error: r.max_error,
+                saved_bytes: r.attribute.data.len() as i64
+                    * (r.default_data_type.size_in_bytes() as i64
+                        - r.data_type.size_in_bytes() as i64),
+            })
+            .collect()
+    }
+
+    /// The [default_attribute_layout] with the data types from [MeshObjectData::requantized_attributes].
+    pub fn requantized_attribute_layout(&self, max_error: f32) -> Vec<AttributeLayout> {
+        let data_types = self.requantized_data_types(max_error);
+
+        let mut layout = default_attribute_layout(self);
+        for l in &mut layout {
+            l.data_type = data_types
+                .iter()
+                .find(|r| r.usage == l.usage && r.attribute.name == l.name)
+                .map(|r| r.data_type);
+        }
+        layout
+    }
+
+    fn requantized_data_types(&self, max_error: f32) -> Vec<RequantizedDataType<'_>> {
+        [
+            (&self.positions, AttributeUsage::Position),
+            (&self.normals, AttributeUsage::Normal),
+            (&self.binormals, AttributeUsage::Binormal),
+            (&self.tangents, AttributeUsage::Tangent),
+            (&self.texture_coordinates, AttributeUsage::TextureCoordinate),
+            (&self.color_sets, AttributeUsage::ColorSet),
+        ]
+        .into_iter()
+        .flat_map(|(attributes, usage)| {
+            attributes
+                .iter()
+                .map(move |a| requantize(a, usage, max_error))
+        })
+        .collect()
+    }
+}
+
+impl MeshData {
+    /// Creates a version 1.10 [Mesh] like [MeshData::to_mesh_with_layout]
+    /// using the smallest data type for each attribute that preserves the error bound `max_error`.
+    /// Returns the mesh and the attributes with a different data type than [Mesh::try_from].
+    ///
+    /// Reducing precision can significantly reduce file sizes for meshes with many vertices.
+    /// Check the error bound against the data ranges with [MeshObjectData::reduced_precision_attributes]
+    /// or compare the result with [MeshObjectData::approx_eq].
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, DataType, MeshData, MeshObjectData, VectorData};
+
+    let data = MeshData {
+        major_version: 1,
+        minor_version: 10,
+        objects: vec![MeshObjectData {
+            name: "a".to_string(),
+            positions: vec![AttributeData {
+                name: "Position0".to_string(),
+                data: VectorData::Vector3(vec![[0.0; 3]]),
+            }],
+            // Values in the range 0.0 to 1.0 can be stored as bytes.
+            texture_coordinates: vec![AttributeData {
+                name: "bake1".to_string(),
+                data: VectorData::Vector4(vec![[0.0, 0.25, 0.5, 1.0]]),
+            }],
+            ..MeshObjectData::default()
+        }],
+    };
+
+    let (mesh, requantized) = data.to_mesh_requantized(0.002)?;
+    assert_eq!(DataType::HalfFloat4, requantized[0].default_data_type);
+    assert_eq!(DataType::Byte4, requantized[0].data_type);
+    assert_eq!(4, requantized[0].saved_bytes);
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn to_mesh_requantized(
+        &self,
+        max_error: f32,
+    ) -> Result<(Mesh, Vec<RequantizedAttribute>), error::Error> {
+        let mesh = self.to_mesh_with_layout(|o| o.requantized_attribute_layout(max_error))?;
+        let requantized = self
+            .objects
+            .iter()
+            .flat_map(|o| o.requantized_attributes(max_error))
+            .collect();
+        Ok((mesh, requantized))
+    }
+}
+
+struct RequantizedDataType<'a> {
+    usage: AttributeUsage,
+    attribute: &'a AttributeData,
+    default_data_type: DataType,
+    data_type: DataType,
+    max_error: f32,
+}
+
+fn requantize(
+    attribute: &AttributeData,
+    usage: AttributeUsage,
+    max_error: f32,
+) -> RequantizedDataType<'_> {
+    // Candidates are sorted from smallest to largest and end with full precision floats.
+    let candidates: &[DataType] = match attribute.data {
+        VectorData::Vector2(_) => &[DataType::HalfFloat2, DataType::Float2],
+        VectorData::Vector3(_) => &[DataType::Float3],
+        VectorData::Vector4(_) => &[DataType::Byte4, DataType::HalfFloat4, DataType::Float4],
+    };
+
+    let (data_type, error) = candidates
+        .iter()
+        .map(|t| (*t, stored_error(&attribute.data, *t)))
+        .find(|(_, error)| *error <= max_error)
+        .unwrap_or((candidates[candidates.len() - 1], 0.0));
+
+    RequantizedDataType {
+        usage,
+        attribute,
+        default_data_type: default_data_type(&attribute.data, usage),
+        data_type,
+        max_error: error,
+    }
+}
+
+// Matches the conversions used by VectorDataV10 for each usage.
+fn default_data_type(data: &VectorData, usage: AttributeUsage) -> DataType {
+    match (data, usage) {
+        (VectorData::Vector2(_), AttributeUsage::Position) => DataType::Float2,
+        (VectorData::Vector4(_), AttributeUsage::Position) => DataType::Float4,
+        (VectorData::Vector4(_), AttributeUsage::ColorSet) => DataType::Byte4,
+        (VectorData::Vector2(_), _) => DataType::HalfFloat2,
+        (VectorData::Vector3(_), _) => DataType::Float3,
+        (VectorData::Vector4(_), _) => DataType::HalfFloat4,
+    }
+}
+
+fn stored_error(data: &VectorData, data_type: DataType) -> f32 {
+    let stored = match VectorDataV10::from_data_type(data, data_type) {
+        Some(VectorDataV10::HalfFloat2(v)) => half_values(&v),
+        Some(VectorDataV10::HalfFloat4(v)) => half_values(&v),
+        Some(VectorDataV10::Byte4(v)) => byte_values(&v),
+        Some(_) => return 0.0,
+        None => return f32::INFINITY,
+    };
+
+    components(data)
+        .iter()
+        .zip(&stored)
+        .map(|(a, b)| component_error(*a, *b))
+        .fold(0.0, f32::max)
+}
+
+fn component_error(value: f32, stored: f32) -> f32 {
+    if value == stored || (value.is_nan() && stored.is_nan()) {
+        0.0
+    } else if value.is_nan() || stored.is_nan() {
+        f32::INFINITY
+    } else {
+        (value - stored).abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(name: &str, data: VectorData) -> Vec<AttributeData> {
+        vec![AttributeData {
+            name: name.to_string(),
+            data,
+        }]
+    }
+
+    fn object() -> MeshObjectData {
+        MeshObjectData {
+            name: "a".to_string(),
+            positions: attribute(
+                "Position0",
+                VectorData::Vector3(vec![[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]),
+            ),
+            normals: attribute(
+                "Normal0",
+                VectorData::Vector4(vec![[-1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0]]),
+            ),
+            texture_coordinates: attribute(
+                "map1",
+                VectorData::Vector2(vec![[0.5, 0.25], [2049.0, 0.0]]),
+            ),
+            color_sets: attribute(
+                "colorSet1",
+                VectorData::Vector4(vec![[0.5; 4], [2.0, 0.5, 0.5, f32::NAN]]),
+            ),
+            ..MeshObjectData::default()
+        }
+    }
+
+    #[test]
+    fn requantized_attributes() {
+        assert_eq!(
+            vec![
+                // 2049.0 is not representable as a half precision float.
+                RequantizedAttribute {
+                    mesh_object_name: "a".to_string(),
+                    mesh_object_subindex: 0,
+                    usage: AttributeUsage::TextureCoordinate,
+                    attribute_name: "map1".to_string(),
+                    default_data_type: DataType::HalfFloat2,
+                    data_type: DataType::Float2,
+                    max_error: 0.0,
+                    saved_bytes: -8,
+                },
+                // Bytes are clamped and don't support NaN.
+                RequantizedAttribute {
+                    mesh_object_name: "a".to_string(),
+                    mesh_object_subindex: 0,
+                    usage: AttributeUsage::ColorSet,
+                    attribute_name: "colorSet1".to_string(),
+                    default_data_type: DataType::Byte4,
+                    data_type: DataType::HalfFloat4,
+                    max_error: 0.0,
+                    saved_bytes: -8,
+                },
+            ],
+            object().requantized_attributes(0.01)
+        );
+    }
+
+    #[test]
+    fn requantized_attributes_bytes() {
+        let object = MeshObjectData {
+            normals: attribute("Normal0", VectorData::Vector4(vec![[0.5; 4]; 3])),
+            ..MeshObjectData::default()
+        };
+
+        let requantized = object.requantized_attributes(0.01);
+        assert_eq!(1, requantized.len());
+        assert_eq!(DataType::HalfFloat4, requantized[0].default_data_type);
+        assert_eq!(DataType::Byte4, requantized[0].data_type);
+        assert_eq!(12, requantized[0].saved_bytes);
+        assert!(requantized[0].max_error > 0.0 && requantized[0].max_error <= 0.01);
+
+        // The error bound is smaller than the error for bytes.
+        assert!(object.requantized_attributes(0.001).is_empty());
+    }
+
+    #[test]
+    fn requantized_attribute_layout() {
+        let layout = object().requantized_attribute_layout(0.01);
+        assert_eq!(
+            vec![
+                (AttributeUsage::Position, Some(DataType::Float3)),
+                (AttributeUsage::Normal, Some(DataType::HalfFloat4)),
+                (AttributeUsage::TextureCoordinate, Some(DataType::Float2)),
+                (AttributeUsage::ColorSet, Some(DataType::HalfFloat4)),
+            ],
+            layout
+                .iter()
+                .map(|l| (l.usage, l.data_type))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn to_mesh_requantized() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![object()],
+        };
+
+        let (mesh, requantized) = data.to_mesh_requantized(0.01).unwrap();
+        assert_eq!(2, requantized.len());
+
+        let data = MeshData::try_from(&mesh).unwrap();
+        assert!(data.objects[0].approx_eq(&object(), 0.0));
+        assert_eq!(
+            VectorData::Vector2(vec![[0.5, 0.25], [2049.0, 0.0]]),
+            data.objects[0].texture_coordinates[0].data
+        );
+    }
+
+    #[test]
+    fn to_mesh_requantized_unsupported_version() {
+        let data = MeshData {
+            major_version: 1,
+            minor_version: 8,
+            objects: vec![object()],
+        };
+        assert!(matches!(
+            data.to_mesh_requantized(0.01),
+            Err(error::Error::UnsupportedVersion {
+                major_version: 1,
+                minor_version: 8
+            })
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+use ahash::AHashMap;
+use geometry_tools::glam::Vec3A;
+
+use super::{MeshData, VectorData};
+use crate::adj_data::AdjData;
+
+impl MeshData {
+    /// Averages the first normal attribute for vertices with identical positions in different objects
+    /// and returns the number of modified vertices.
+    ///
+    /// Only objects with an entry in `adj` are smoothed.
+    /// This matches the seam blending for objects with adjacency data in game,
+    /// so objects split for materials or visibility don't show visible edges.
+    /// Vertices are only smoothed when at least two objects share the position.
+    /// The W component of 4 component normals is preserved.
+    /**
+    ```rust
+    use ssbh_data::adj_data::{AdjData, AdjEntryData};
+    use ssbh_data::mesh_data::{AttributeData, MeshData, MeshObjectData, VectorData};
+
+    let object = |normal: [f32; 3]| MeshObjectData {
+        positions: vec![AttributeData {
+            name: "Position0".to_string(),
+            data: VectorData::Vector3(vec![[0.0, 1.0, 0.0]]),
+        }],
+        normals: vec![AttributeData {
+            name: "Normal0".to_string(),
+            data: VectorData::Vector3(vec![normal]),
+        }],
+        ..MeshObjectData::default()
+    };
+
+    let mut mesh = MeshData {
+        major_version: 1,
+        minor_version: 10,
+        objects: vec![object([1.0, 0.0, 0.0]), object([0.0, 1.0, 0.0])],
+    };
+    let adj = AdjData {
+        entries: vec![
+            AdjEntryData { mesh_object_index: 0, vertex_adjacency: Vec::new() },
+            AdjEntryData { mesh_object_index: 1, vertex_adjacency: Vec::new() },
+        ],
+    };
+
+    assert_eq!(2, mesh.smooth_seam_normals(&adj));
+    ```
+     */
+    pub fn smooth_seam_normals(&mut self, adj: &AdjData) -> usize {
+        // Group the vertices for objects with adjacency by position.
+        let mut vertices_by_position: AHashMap<_, Vec<(usize, usize)>> = AHashMap::new();
+        for (object_index, object) in self.objects.iter().enumerate() {
+            if !adj
+                .entries
+                .iter()
+                .any(|e| e.mesh_object_index == object_index)
+            {
+                continue;
+            }
+
+            if let (Some(positions), Some(_)) = (object.positions.first(), object.normals.first()) {
+                for (i, position) in positions.data.to_glam_vec3a().iter().enumerate() {
+                    vertices_by_position
+                        .entry(position.to_array().map(f32::to_bits))
+                        .or_default()
+                        .push((object_index, i));
+                }
+            }
+        }
+
+        let normals: Vec<_> = self
+            .objects
+            .iter()
+            .map(|o| o.normals.first().map(|n| n.data.to_glam_vec3a()))
+            .collect();
+
+        let mut count = 0;
+        for vertices in vertices_by_position.values() {
+            let is_seam = vertices.iter().any(|(o, _)| *o != vertices[0].0);
+            if !is_seam {
+                continue;
+            }
+
+            let average: Vec3A = vertices
+                .iter()
+                .filter_map(|(o, i)| normals[*o].as_ref()?.get(*i))
+                .map(|n| n.normalize_or_zero())
+                .sum::<Vec3A>()
+                .normalize_or_zero();
+
+            for (o, i) in vertices {
+                if let Some(normals) = self.objects[*o].normals.first_mut() {
+                    if set_normal(&mut normals.data, *i, average) {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+}
+
+fn set_normal(data: &mut VectorData, index: usize, normal: Vec3A) -> bool {
+    match data {
+        VectorData::Vector2(_) => false,
+        VectorData::Vector3(values) => match values.get_mut(index) {
+            Some(value) => {
+                *value = normal.to_array();
+                true
+            }
+            None => false,
+        },
+        VectorData::Vector4(values) => match values.get_mut(index) {
+            Some(value) => {
+                *value = [normal.x, normal.y, normal.z, value[3]];
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adj_data::AdjEntryData;
+    use crate::mesh_data::{AttributeData, MeshObjectData};
+
+    fn object(positions: Vec<[f32; 3]>, normals: Vec<[f32; 4]>) -> MeshObjectData {
+        MeshObjectData {
+            positions: vec![AttributeData {
+                name: "Position0".to_string(),
+                data: VectorData::Vector3(positions),
+            }],
+            normals: vec![AttributeData {
+                name: "Normal0".to_string(),
+                data: VectorData::Vector4(normals),
+            }],
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn adj(indices: &[usize]) -> AdjData {
+        AdjData {
+            entries: indices
+                .iter()
+                .map(|i| AdjEntryData {
+                    mesh_object_index: *i,
+                    vertex_adjacency: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn mesh() -> MeshData {
+        MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                object(
+                    vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+                    vec![[1.0, 0.0, 0.0, 1.0], [1.0, 0.0, 0.0, 1.0]],
+                ),
+                object(vec![[0.0, 0.0, 0.0]], vec![[0.0, 0.0, 1.0, 0.0]]),
+                object(vec![[1.0, 0.0, 0.0]], vec![[0.0, 1.0, 0.0, 1.0]]),
+            ],
+        }
+    }
+
+    #[test]
+    fn smooth_seam_normals_objects_with_adj() {
+        let mut mesh = mesh();
+        assert_eq!(2, mesh.smooth_seam_normals(&adj(&[0, 1])));
+
+        let n = 0.5f32.sqrt();
+        assert_eq!(
+            VectorData::Vector4(vec![[n, 0.0, n, 1.0], [1.0, 0.0, 0.0, 1.0]]),
+            mesh.objects[0].normals[0].data
+        );
+        assert_eq!(
+            VectorData::Vector4(vec![[n, 0.0, n, 0.0]]),
+            mesh.objects[1].normals[0].data
+        );
+        // Objects without adjacency data are unchanged.
+        assert_eq!(
+            VectorData::Vector4(vec![[0.0, 1.0, 0.0, 1.0]]),
+            mesh.objects[2].normals[0].data
+        );
+    }
+
+    #[test]
+    fn smooth_seam_normals_no_seams() {
+        let mut mesh = mesh();
+        assert_eq!(0, mesh.smooth_seam_normals(&adj(&[1, 2])));
+        assert_eq!(0, mesh.smooth_seam_normals(&adj(&[])));
+    }
+}
@@ -0,0 +1,333 @@
+use glam::{Mat3, Mat4, Vec3};
+
+use super::{error, MeshData, MeshObjectData};
+use crate::skel_data::SkelData;
+
+/// The vertex data for a [MeshObjectData] after applying a pose with [skin_mesh].
+#[derive(Debug, PartialEq, Clone)]
+pub struct PosedMeshObject {
+    pub name: String,
+    pub subindex: u64,
+    /// The transformed vertices of the first [positions](super::MeshObjectData::positions) attribute.
+    pub positions: Vec<[f32; 3]>,
+    /// The transformed and normalized vertices of the first [normals](super::MeshObjectData::normals) attribute
+    /// or an empty list if the object has no normals.
+    pub normals: Vec<[f32; 3]>,
+}
+
+/// Calculates the posed vertex positions and normals for each object in `mesh`
+/// using linear blend skinning on the CPU.
+///
+/// The `world_transforms` are the posed world transforms in column-major order for each bone in `skel`
+/// like those calculated by [SkelData::animated_world_transforms].
+/// Vertices are transformed from the rest pose of `skel` to the pose in `world_transforms`.
+///
+/// Objects without [bone_influences](super::MeshObjectData::bone_influences) are transformed
+/// by the world transform of their [parent_bone_name](super::MeshObjectData::parent_bone_name) if present.
+/// Influences for bones not in `skel` are ignored, and vertices without any weights are not transformed.
+/**
+```rust
+# use ssbh_data::mesh_data::{AttributeData, MeshData, MeshObjectData, VectorData, skin_mesh};
+# use ssbh_data::skel_data::SkelData;
+# let mesh = MeshData { major_version: 1, minor_version: 10, objects: Vec::new() };
+# let skel = SkelData { major_version: 1, minor_version: 0, bones: Vec::new() };
+// Skinning with the rest pose doesn't change the vertices.
+let world_transforms = skel
+    .bones
+    .iter()
+    .map(|b| skel.calculate_world_transform(b))
+    .collect::<Result<Vec<_>, _>>()?;
+let posed_objects = skin_mesh(&mesh, &skel, &world_transforms)?;
+# Ok::<(), Box<dyn std::error::Error>>(())
+```
+ */
+pub fn skin_mesh(
+    mesh: &MeshData,
+    skel: &SkelData,
+    world_transforms: &[[[f32; 4]; 4]],
+) -> Result<Vec<PosedMeshObject>, error::Error> {
+    if world_transforms.len() != skel.bones.len() {
+        return Err(error::Error::BoneTransformCountMismatch {
+            transform_count: world_transforms.len(),
+            bone_count: skel.bones.len(),
+        });
+    }
+
+    // Skinning matrices transform vertices from the rest pose to the animated pose.
+    let skinning_transforms = skel
+        .bones
+        .iter()
+        .zip(world_transforms)
+        .map(|(b, world)| {
+            let rest = Mat4::from_cols_array_2d(&skel.calculate_world_transform(b)?);
+            Ok(Mat4::from_cols_array_2d(world) * rest.inverse())
+        })
+        .collect::<Result<Vec<_>, error::Error>>()?;
+
+    mesh.objects
+        .iter()
+        .map(|o| skin_mesh_object(o, skel, world_transforms, &skinning_transforms))
+        .collect()
+}
+
+fn skin_mesh_object(
+    object: &MeshObjectData,
+    skel: &SkelData,
+    world_transforms: &[[[f32; 4]; 4]],
+    skinning_transforms: &[Mat4],
+) -> Result<PosedMeshObject, error::Error> {
+    let positions = object
+        .positions
+        .first()
+        .map(|a| a.data.to_glam_vec3a())
+        .unwrap_or_default();
+    let normals = object
+        .normals
+        .first()
+        .map(|a| a.data.to_glam_vec3a())
+        .unwrap_or_default();
+
+    let bone_index = |name: &str| skel.bones.iter().position(|b| b.name == name);
+
+    let vertex_transforms = if object.bone_influences.is_empty() {
+        // Single bound objects use the parent bone's transform for all vertices.
+        let transform = bone_index(&object.parent_bone_name)
+            .map(|i| Mat4::from_cols_array_2d(&world_transforms[i]))
+            .unwrap_or(Mat4::IDENTITY);
+        vec![transform; positions.len()]
+    } else {
+        let mut transforms = vec![Mat4::ZERO; positions.len()];
+        let mut weighted = vec![false; positions.len()];
+        for influence in &object.bone_influences {
+            if let Some(i) = bone_index(&influence.bone_name) {
+                for w in &influence.vertex_weights {
+                    let index = w.vertex_index as usize;
+                    let transform =
+                        transforms
+                            .get_mut(index)
+                            .ok_or(error::Error::VertexIndexOutOfRange {
+                                vertex_index: index,
+                                vertex_count: positions.len(),
+                            })?;
+                    *transform += skinning_transforms[i] * w.vertex_weight;
+                    weighted[index] = true;
+                }
+            }
+        }
+
+        transforms
+            .into_iter()
+            .zip(weighted)
+            .map(|(t, weighted)| if weighted { t } else { Mat4::IDENTITY })
+            .collect()
+    };
+
+    Ok(PosedMeshObject {
+        name: object.name.clone(),
+        subindex: object.subindex,
+        positions: positions
+            .iter()
+            .zip(&vertex_transforms)
+            .map(|(p, t)| t.transform_point3a(*p).to_array())
+            .collect(),
+        normals: normals
+            .iter()
+            .zip(&vertex_transforms)
+            .map(|(n, t)| {
+                let n = normal_matrix(t) * Vec3::from(*n);
+                n.normalize_or_zero().to_array()
+            })
+            .collect(),
+    })
+}
+
+// Normals stay perpendicular to the surface under non uniform scale
+// by using the inverse transpose of the upper 3x3 portion of the matrix.
+fn normal_matrix(transform: &Mat4) -> Mat3 {
+    let m = Mat3::from_mat4(*transform);
+    if m.determinant().abs() > f32::EPSILON {
+        m.inverse().transpose()
+    } else {
+        // Singular matrices have no inverse, so fall back to the original matrix.
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::{AttributeData, BoneInfluence, VectorData, VertexWeight};
+    use crate::skel_data::{BillboardType, BoneData};
+    use approx::assert_relative_eq;
+
+    fn bone(name: &str, translation: Vec3, parent_index: Option<usize>) -> BoneData {
+        BoneData {
+            name: name.to_string(),
+            transform: Mat4::from_translation(translation).to_cols_array_2d(),
+            parent_index,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        }
+    }
+
+    fn skel() -> SkelData {
+        SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                bone("A", Vec3::new(0.0, 1.0, 0.0), None),
+                bone("B", Vec3::new(0.0, 1.0, 0.0), Some(0)),
+            ],
+        }
+    }
+
+    fn object(bone_influences: Vec<BoneInfluence>, parent_bone_name: &str) -> MeshObjectData {
+        MeshObjectData {
+            name: "a".into(),
+            parent_bone_name: parent_bone_name.into(),
+            vertex_indices: vec![0, 1, 0],
+            positions: vec![AttributeData {
+                name: "Position0".into(),
+                data: VectorData::Vector3(vec![[0.0, 2.0, 0.0], [1.0, 2.0, 0.0]]),
+            }],
+            normals: vec![AttributeData {
+                name: "Normal0".into(),
+                data: VectorData::Vector4(vec![[1.0, 0.0, 0.0, 0.0]; 2]),
+            }],
+            bone_influences,
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn mesh(objects: Vec<MeshObjectData>) -> MeshData {
+        MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects,
+        }
+    }
+
+    fn posed_transforms(skel: &SkelData) -> Vec<[[f32; 4]; 4]> {
+        // Rotate B 90 degrees around the Z axis at its rest position.
+        let a = Mat4::from_cols_array_2d(&skel.bones[0].transform);
+        let b = Mat4::from_cols_array_2d(&skel.bones[1].transform)
+            * Mat4::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        vec![a.to_cols_array_2d(), (a * b).to_cols_array_2d()]
+    }
+
+    #[test]
+    fn skin_mesh_rest_pose() {
+        let skel = skel();
+        let world_transforms: Vec<_> = skel
+            .bones
+            .iter()
+            .map(|b| skel.calculate_world_transform(b).unwrap())
+            .collect();
+        let influences = vec![BoneInfluence {
+            bone_name: "B".into(),
+            vertex_weights: vec![
+                VertexWeight {
+                    vertex_index: 0,
+                    vertex_weight: 1.0,
+                },
+                VertexWeight {
+                    vertex_index: 1,
+                    vertex_weight: 1.0,
+                },
+            ],
+        }];
+
+        let posed = skin_mesh(
+            &mesh(vec![object(influences, "")]),
+            &skel,
+            &world_transforms,
+        )
+        .unwrap();
+        assert_eq!(vec![[0.0, 2.0, 0.0], [1.0, 2.0, 0.0]], posed[0].positions);
+        assert_eq!(vec![[1.0, 0.0, 0.0]; 2], posed[0].normals);
+    }
+
+    #[test]
+    fn skin_mesh_weights() {
+        let skel = skel();
+        let influences = vec![
+            BoneInfluence {
+                bone_name: "B".into(),
+                vertex_weights: vec![VertexWeight {
+                    vertex_index: 1,
+                    vertex_weight: 1.0,
+                }],
+            },
+            BoneInfluence {
+                bone_name: "missing".into(),
+                vertex_weights: vec![VertexWeight {
+                    vertex_index: 0,
+                    vertex_weight: 1.0,
+                }],
+            },
+        ];
+
+        let posed = skin_mesh(
+            &mesh(vec![object(influences, "")]),
+            &skel,
+            &posed_transforms(&skel),
+        )
+        .unwrap();
+
+        // Vertex 0 only has weights for a missing bone.
+        assert_eq!([0.0, 2.0, 0.0], posed[0].positions[0]);
+        assert_eq!([1.0, 0.0, 0.0], posed[0].normals[0]);
+
+        // Vertex 1 rotates around B's world position.
+        assert_relative_eq!(0.0, posed[0].positions[1][0], epsilon = 1e-6);
+        assert_relative_eq!(3.0, posed[0].positions[1][1], epsilon = 1e-6);
+        assert_relative_eq!(0.0, posed[0].normals[1][0], epsilon = 1e-6);
+        assert_relative_eq!(1.0, posed[0].normals[1][1], epsilon = 1e-6);
+    }
+
+    #[test]
+    fn skin_mesh_parent_bone() {
+        let skel = skel();
+        let posed = skin_mesh(
+            &mesh(vec![object(Vec::new(), "A")]),
+            &skel,
+            &posed_transforms(&skel),
+        )
+        .unwrap();
+
+        // Single bound vertices are relative to the parent bone.
+        assert_eq!(vec![[0.0, 3.0, 0.0], [1.0, 3.0, 0.0]], posed[0].positions);
+    }
+
+    #[test]
+    fn skin_mesh_non_uniform_scale_normals() {
+        let skel = skel();
+        let mut world_transforms = posed_transforms(&skel);
+        world_transforms[0] = (Mat4::from_cols_array_2d(&world_transforms[0])
+            * Mat4::from_scale(Vec3::new(2.0, 1.0, 1.0)))
+        .to_cols_array_2d();
+
+        let mut object = object(Vec::new(), "A");
+        object.normals[0].data = VectorData::Vector4(vec![[1.0, 1.0, 0.0, 0.0]; 2]);
+
+        let posed = skin_mesh(&mesh(vec![object]), &skel, &world_transforms).unwrap();
+
+        // The normal for the plane x = y should stay perpendicular to the scaled plane 2x = y.
+        let expected = Vec3::new(0.5, 1.0, 0.0).normalize();
+        assert_relative_eq!(expected.x, posed[0].normals[0][0], epsilon = 1e-6);
+        assert_relative_eq!(expected.y, posed[0].normals[0][1], epsilon = 1e-6);
+        assert_relative_eq!(0.0, posed[0].normals[0][2], epsilon = 1e-6);
+    }
+
+    #[test]
+    fn skin_mesh_transform_count_mismatch() {
+        let result = skin_mesh(&mesh(Vec::new()), &skel(), &[]);
+        assert!(matches!(
+            result,
+            Err(error::Error::BoneTransformCountMismatch {
+                transform_count: 0,
+                bone_count: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use ssbh_lib::formats::mesh::{DrawElementType, Mesh, MeshObject};
+use ssbh_write::SsbhWrite;
+
+use super::{error, AttributeData, AttributeUsage, MeshData, MeshObjectData};
+
+/// Summary statistics for the objects in a [MeshData].
+/// See [MeshData::stats].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MeshStats {
+    pub objects: Vec<MeshObjectStats>,
+}
+
+impl MeshStats {
+    /// The total size in bytes of the vertex and index buffers for all objects.
+    pub fn buffer_bytes(&self) -> usize {
+        self.objects
+            .iter()
+            .map(|o| o.vertex_buffer_bytes + o.index_buffer_bytes)
+            .sum()
+    }
+}
+
+/// Summary statistics for a single [MeshObjectData].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MeshObjectStats {
+    pub name: String,
+    pub subindex: u64,
+    pub vertex_count: usize,
+    /// The number of triangles, which is a third of the number of vertex indices.
+    pub triangle_count: usize,
+    pub attributes: Vec<AttributeStats>,
+    /// The number of vertices influenced by exactly `i` bones at index `i`.
+    /// Vertices without skin weights are counted at index 0.
+    pub influence_counts: Vec<usize>,
+    /// The size in bytes of this object's data in the vertex buffers.
+    pub vertex_buffer_bytes: usize,
+    /// The size in bytes of this object's data in the index buffer.
+    pub index_buffer_bytes: usize,
+}
+
+/// The usage and component count for an [AttributeData].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AttributeStats {
+    pub usage: AttributeUsage,
+    pub name: String,
+    pub component_count: usize,
+}
+
+impl MeshData {
+    /// Calculates statistics for each object for reviewing models or estimating file sizes.
+    ///
+    /// The buffer sizes use the data types selected when converting to [Mesh] for this version.
+    /// Returns an error if the data can not be converted to [Mesh].
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, MeshData, MeshObjectData, VectorData};
+
+    let mesh = MeshData {
+        major_version: 1,
+        minor_version: 10,
+        objects: vec![MeshObjectData {
+            name: "triangle".into(),
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".into(),
+                data: VectorData::Vector3(vec![[0.0; 3]; 3]),
+            }],
+            ..MeshObjectData::default()
+        }],
+    };
+
+    let stats = mesh.stats()?;
+    assert_eq!(1, stats.objects[0].triangle_count);
+    assert_eq!(3, stats.objects[0].vertex_count);
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn stats(&self) -> Result<MeshStats, error::Error> {
+        let mesh = Mesh::try_from(self)?;
+        let buffer_bytes: Vec<_> = match &mesh {
+            Mesh::V8(m) => m.objects.elements.iter().map(buffer_bytes).collect(),
+            Mesh::V9(m) => m.objects.elements.iter().map(buffer_bytes).collect(),
+            Mesh::V10(m) => m.objects.elements.iter().map(buffer_bytes).collect(),
+        };
+
+        let objects = self
+            .objects
+            .iter()
+            .zip(buffer_bytes)
+            .map(|(o, (vertex_buffer_bytes, index_buffer_bytes))| {
+                Ok(MeshObjectStats {
+                    name: o.name.clone(),
+                    subindex: o.subindex,
+                    vertex_count: o.vertex_count()?,
+                    triangle_count: o.vertex_indices.len() / 3,
+                    attributes: attribute_stats(o),
+                    influence_counts: influence_counts(o)?,
+                    vertex_buffer_bytes,
+                    index_buffer_bytes,
+                })
+            })
+            .collect::<Result<_, error::Error>>()?;
+
+        Ok(MeshStats { objects })
+    }
+}
+
+fn buffer_bytes<A: for<'a> binrw::BinRead<Args<'a> = ()> + SsbhWrite>(
+    object: &MeshObject<A>,
+) -> (usize, usize) {
+    let stride = object.stride0 + object.stride1 + object.stride2 + object.stride3;
+    let vertex_bytes = stride as usize * object.vertex_count as usize;
+
+    let index_size = match object.draw_element_type {
+        DrawElementType::UnsignedShort => 2,
+        DrawElementType::UnsignedInt => 4,
+    };
+    let index_bytes = index_size * object.vertex_index_count as usize;
+
+    (vertex_bytes, index_bytes)
+}
+
+fn attribute_stats(object: &MeshObjectData) -> Vec<AttributeStats> {
+    let stats = |attributes: &[AttributeData], usage| {
+        attributes
+            .iter()
+            .map(move |a| AttributeStats {
+                usage,
+                name: a.name.clone(),
+                component_count: a.data.component_count(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    [
+        stats(&object.positions, AttributeUsage::Position),
+        stats(&object.normals, AttributeUsage::Normal),
+        stats(&object.binormals, AttributeUsage::Binormal),
+        stats(&object.tangents, AttributeUsage::Tangent),
+        stats(
+            &object.texture_coordinates,
+            AttributeUsage::TextureCoordinate,
+        ),
+        stats(&object.color_sets, AttributeUsage::ColorSet),
+    ]
+    .concat()
+}
+
+fn influence_counts(object: &MeshObjectData) -> Result<Vec<usize>, error::Error> {
+    let mut counts_per_vertex = vec![0usize; object.vertex_count()?];
+    for influence in &object.bone_influences {
+        for weight in &influence.vertex_weights {
+            if let Some(count) = counts_per_vertex.get_mut(weight.vertex_index as usize) {
+                *count += 1;
+            }
+        }
+    }
+
+    let max_count = counts_per_vertex.iter().copied().max().unwrap_or_default();
+    let mut histogram = vec![0; max_count + 1];
+    for count in counts_per_vertex {
+        histogram[count] += 1;
+    }
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::{BoneInfluence, VectorData, VertexWeight};
+
+    fn weights(indices: &[u32]) -> Vec<VertexWeight> {
+        indices
+            .iter()
+            .map(|i| VertexWeight {
+                vertex_index: *i,
+                vertex_weight: 0.5,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mesh_object_stats() {
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![MeshObjectData {
+                name: "a".into(),
+                vertex_indices: vec![0, 1, 2, 2, 1, 3],
+                positions: vec![AttributeData {
+                    name: "Position0".into(),
+                    data: VectorData::Vector3(vec![[0.0; 3]; 4]),
+                }],
+                texture_coordinates: vec![AttributeData {
+                    name: "map1".into(),
+                    data: VectorData::Vector2(vec![[0.0; 2]; 4]),
+                }],
+                bone_influences: vec![
+                    BoneInfluence {
+                        bone_name: "A".into(),
+                        vertex_weights: weights(&[0, 1]),
+                    },
+                    BoneInfluence {
+                        bone_name: "B".into(),
+                        vertex_weights: weights(&[1]),
+                    },
+                ],
+                ..MeshObjectData::default()
+            }],
+        };
+
+        let stats = mesh.stats().unwrap();
+        let object = &stats.objects[0];
+        assert_eq!(4, object.vertex_count);
+        assert_eq!(2, object.triangle_count);
+        assert_eq!(
+            vec![
+                AttributeStats {
+                    usage: AttributeUsage::Position,
+                    name: "Position0".into(),
+                    component_count: 3
+                },
+                AttributeStats {
+                    usage: AttributeUsage::TextureCoordinate,
+                    name: "map1".into(),
+                    component_count: 2
+                }
+            ],
+            object.attributes
+        );
+        assert_eq!(vec![2, 1, 1], object.influence_counts);
+
+        // Positions use Float3 and texture coordinates use HalfFloat2.
+        assert_eq!((12 + 4) * 4, object.vertex_buffer_bytes);
+        assert_eq!(6 * 2, object.index_buffer_bytes);
+        assert_eq!((12 + 4) * 4 + 6 * 2, stats.buffer_bytes());
+    }
+}
@@ -48,6 +48,21 @@ impl VectorData {
         self.len() == 0
     }
 
+    /// The number of components for each vector.
+    /**
+    ```rust
+    # use ssbh_data::mesh_data::VectorData;
+    assert_eq!(2, VectorData::Vector2(Vec::new()).component_count());
+    ```
+    */
+    pub fn component_count(&self) -> usize {
+        match self {
+            VectorData::Vector2(_) => 2,
+            VectorData::Vector3(_) => 3,
+            VectorData::Vector4(_) => 4,
+        }
+    }
+
     /// Pads the data to 4 components per vector with a specified w component.
     /// This includes replacing the w component for [VectorData::Vector4].
     /**
@@ -162,6 +177,45 @@ impl VectorData {
     }
 }
 
+/// Decodes `count` vectors of `data_type` from `buffer` starting at `offset`.
+/// Each vector starts `stride` bytes after the previous vector.
+///
+/// This uses the same decoding as [MeshData](super::MeshData),
+/// so [DataType::Byte4] is normalized to the range `0.0` to `1.0`.
+/// The stride can be larger than the size of the data type to read attributes from interleaved buffers.
+/// Returns an error if the data extends past the end of `buffer` or `stride` is zero for a nonzero `count`.
+/**
+```rust
+use ssbh_data::mesh_data::{read_attribute, DataType, VectorData};
+
+// Two vertices with an interleaved Float2 and Byte4 attribute.
+let buffer = [
+    0, 0, 128, 63, 0, 0, 0, 64, 255, 0, 255, 0,
+    0, 0, 64, 64, 0, 0, 128, 64, 0, 255, 0, 255,
+];
+
+let data = read_attribute(&buffer, 0, 12, DataType::Float2, 2)?;
+assert_eq!(VectorData::Vector2(vec![[1.0, 2.0], [3.0, 4.0]]), data);
+
+let data = read_attribute(&buffer, 8, 12, DataType::Byte4, 2)?;
+assert_eq!(
+    VectorData::Vector4(vec![[1.0, 0.0, 1.0, 0.0], [0.0, 1.0, 0.0, 1.0]]),
+    data
+);
+# Ok::<(), binrw::Error>(())
+```
+ */
+pub fn read_attribute(
+    buffer: &[u8],
+    offset: u64,
+    stride: u64,
+    data_type: DataType,
+    count: usize,
+) -> BinResult<VectorData> {
+    let mut reader = std::io::Cursor::new(buffer);
+    VectorData::read(&mut reader, count, offset, stride, data_type)
+}
+
 #[derive(Debug, PartialEq)]
 pub enum VersionedVectorData {
     V8(Vec<VectorDataV8>),
@@ -243,6 +297,25 @@ impl VectorDataV10 {
             VectorData::Vector4(v) => VectorDataV10::Byte4(get_clamped_u8_vectors(v)),
         }
     }
+
+    /// Converts `data` to `data_type` or returns `None` if the number of components differs.
+    pub fn from_data_type(data: &VectorData, data_type: DataType) -> Option<Self> {
+        match (data, data_type) {
+            (VectorData::Vector2(v), DataType::Float2) => Some(VectorDataV10::Float2(v.clone())),
+            (VectorData::Vector2(v), DataType::HalfFloat2) => {
+                Some(VectorDataV10::HalfFloat2(get_f16_vectors(v)))
+            }
+            (VectorData::Vector3(v), DataType::Float3) => Some(VectorDataV10::Float3(v.clone())),
+            (VectorData::Vector4(v), DataType::Float4) => Some(VectorDataV10::Float4(v.clone())),
+            (VectorData::Vector4(v), DataType::HalfFloat4) => {
+                Some(VectorDataV10::HalfFloat4(get_f16_vectors(v)))
+            }
+            (VectorData::Vector4(v), DataType::Byte4) => {
+                Some(VectorDataV10::Byte4(get_clamped_u8_vectors(v)))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl VectorDataV8 {
@@ -448,6 +521,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn read_attribute_half_float_interleaved() {
+        // 1.0, 2.0 with 4 bytes of padding between vectors.
+        let buffer = hex!("003C0040 FFFFFFFF 003C0040");
+        assert_eq!(
+            VectorData::Vector2(vec![[1.0, 2.0], [1.0, 2.0]]),
+            read_attribute(&buffer, 0, 8, DataType::HalfFloat2, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn read_attribute_out_of_bounds() {
+        let buffer = hex!("003C0040");
+        assert!(read_attribute(&buffer, 0, 4, DataType::HalfFloat2, 2).is_err());
+        assert!(read_attribute(&buffer, 4, 4, DataType::HalfFloat2, 1).is_err());
+        assert!(read_attribute(&buffer, 0, 0, DataType::HalfFloat2, 1).is_err());
+    }
+
     #[test]
     fn read_vector_data_stride_equals_size() {
         let mut reader = Cursor::new(hex!("00010203 04050607"));
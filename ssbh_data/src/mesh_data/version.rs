@@ -0,0 +1,324 @@
+use super::{error, AttributeData, MeshData, MeshObjectData, VectorData};
+
+/// Data that can't be represented exactly after [MeshData::convert_version].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LossyConversion {
+    /// Version 1.8 doesn't support binormals, so the attribute was removed.
+    RemovedBinormal {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+        attribute_name: String,
+    },
+    /// Version 1.8 doesn't store attribute names,
+    /// so the attribute was renamed to the name generated when reading the file.
+    RenamedAttribute {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+        attribute_name: String,
+        new_name: String,
+    },
+    /// Version 1.10 stores two component attributes other than positions as half precision floats.
+    ReducedPrecision {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+        attribute_name: String,
+    },
+}
+
+impl MeshData {
+    /// Converts the data to the mesh version `major_version`.`minor_version`
+    /// and returns the changes that can't be undone by converting back to the original version.
+    ///
+    /// The attribute data types and rigging layout are chosen when writing based on the version.
+    /// Converting explicitly reports any lost data up front instead of silently discarding it on write.
+    /// The data is left unchanged if an error occurs.
+    ///
+    /// Supported versions are 1.8, 1.9, and 1.10.
+    /// Version 1.10 only supports skin weights for vertex indices up to [u16::MAX].
+    /**
+    ```rust
+    use ssbh_data::mesh_data::{AttributeData, LossyConversion, MeshData, MeshObjectData, VectorData};
+
+    let mut mesh = MeshData {
+        major_version: 1,
+        minor_version: 10,
+        objects: vec![MeshObjectData {
+            name: "a".to_string(),
+            binormals: vec![AttributeData {
+                name: "map1".to_string(),
+                data: VectorData::Vector3(vec![[0.0, 1.0, 0.0]]),
+            }],
+            ..MeshObjectData::default()
+        }],
+    };
+
+    let lossy = mesh.convert_version(1, 8)?;
+    assert_eq!(
+        vec![LossyConversion::RemovedBinormal {
+            mesh_object_name: "a".to_string(),
+            mesh_object_subindex: 0,
+            attribute_name: "map1".to_string(),
+        }],
+        lossy
+    );
+    assert!(mesh.objects[0].binormals.is_empty());
+    # Ok::<(), ssbh_data::mesh_data::error::Error>(())
+    ```
+     */
+    pub fn convert_version(
+        &mut self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Vec<LossyConversion>, error::Error> {
+        let source = (self.major_version, self.minor_version);
+        let target = (major_version, minor_version);
+        for version in [source, target] {
+            if !matches!(version, (1, 8) | (1, 9) | (1, 10)) {
+                return Err(error::Error::UnsupportedVersion {
+                    major_version: version.0,
+                    minor_version: version.1,
+                });
+            }
+        }
+
+        if target == (1, 10) {
+            check_vertex_weight_indices(&self.objects)?;
+        }
+
+        let mut lossy = Vec::new();
+        for object in &mut self.objects {
+            match target {
+                (1, 8) => convert_object_v8(object, &mut lossy),
+                (1, 10) if source != (1, 10) => convert_object_v10(object, &mut lossy),
+                _ => (),
+            }
+        }
+
+        self.major_version = major_version;
+        self.minor_version = minor_version;
+        Ok(lossy)
+    }
+}
+
+fn check_vertex_weight_indices(objects: &[MeshObjectData]) -> Result<(), error::Error> {
+    let vertex_index = objects
+        .iter()
+        .flat_map(|o| &o.bone_influences)
+        .flat_map(|i| &i.vertex_weights)
+        .map(|w| w.vertex_index)
+        .find(|i| *i > u16::MAX as u32);
+
+    match vertex_index {
+        Some(vertex_index) => Err(error::Error::SkinWeightVertexIndexExceedsLimit {
+            vertex_index: vertex_index as usize,
+            limit: u16::MAX as usize,
+            major_version: 1,
+            minor_version: 10,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn convert_object_v8(object: &mut MeshObjectData, lossy: &mut Vec<LossyConversion>) {
+    for binormal in object.binormals.drain(..) {
+        lossy.push(LossyConversion::RemovedBinormal {
+            mesh_object_name: object.name.clone(),
+            mesh_object_subindex: object.subindex,
+            attribute_name: binormal.name,
+        });
+    }
+
+    // Use the same names generated when reading version 1.8 attributes.
+    for (attributes, prefix) in [
+        (&mut object.positions, "Position"),
+        (&mut object.normals, "Normal"),
+        (&mut object.tangents, "Tangent"),
+        (&mut object.texture_coordinates, "TextureCoordinate"),
+        (&mut object.color_sets, "colorSet"),
+    ] {
+        for (i, attribute) in attributes.iter_mut().enumerate() {
+            let new_name = format!("{prefix}{i}");
+            if attribute.name != new_name {
+                let attribute_name = std::mem::replace(&mut attribute.name, new_name.clone());
+                lossy.push(LossyConversion::RenamedAttribute {
+                    mesh_object_name: object.name.clone(),
+                    mesh_object_subindex: object.subindex,
+                    attribute_name,
+                    new_name,
+                });
+            }
+        }
+    }
+}
+
+fn convert_object_v10(object: &MeshObjectData, lossy: &mut Vec<LossyConversion>) {
+    // Version 1.8 and 1.9 store these attributes as full precision floats.
+    let attributes = object
+        .normals
+        .iter()
+        .chain(&object.binormals)
+        .chain(&object.tangents)
+        .chain(&object.texture_coordinates)
+        .chain(&object.color_sets);
+
+    lossy.extend(
+        attributes
+            .filter(|a| is_vector2(a))
+            .map(|a| LossyConversion::ReducedPrecision {
+                mesh_object_name: object.name.clone(),
+                mesh_object_subindex: object.subindex,
+                attribute_name: a.name.clone(),
+            }),
+    );
+}
+
+fn is_vector2(attribute: &AttributeData) -> bool {
+    matches!(attribute.data, VectorData::Vector2(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::{BoneInfluence, VertexWeight};
+
+    fn attribute(name: &str, data: VectorData) -> AttributeData {
+        AttributeData {
+            name: name.to_string(),
+            data,
+        }
+    }
+
+    fn mesh(major_version: u16, minor_version: u16, object: MeshObjectData) -> MeshData {
+        MeshData {
+            major_version,
+            minor_version,
+            objects: vec![object],
+        }
+    }
+
+    #[test]
+    fn convert_v10_to_v8() {
+        let mut mesh = mesh(
+            1,
+            10,
+            MeshObjectData {
+                name: "a".to_string(),
+                positions: vec![attribute("Position0", VectorData::Vector3(vec![[0.0; 3]]))],
+                binormals: vec![attribute("map1", VectorData::Vector3(vec![[0.0; 3]]))],
+                texture_coordinates: vec![attribute("map1", VectorData::Vector2(vec![[0.0; 2]]))],
+                ..MeshObjectData::default()
+            },
+        );
+
+        let lossy = mesh.convert_version(1, 8).unwrap();
+        assert_eq!(
+            vec![
+                LossyConversion::RemovedBinormal {
+                    mesh_object_name: "a".to_string(),
+                    mesh_object_subindex: 0,
+                    attribute_name: "map1".to_string()
+                },
+                LossyConversion::RenamedAttribute {
+                    mesh_object_name: "a".to_string(),
+                    mesh_object_subindex: 0,
+                    attribute_name: "map1".to_string(),
+                    new_name: "TextureCoordinate0".to_string()
+                }
+            ],
+            lossy
+        );
+        assert_eq!((1, 8), (mesh.major_version, mesh.minor_version));
+        assert!(mesh.objects[0].binormals.is_empty());
+        assert_eq!(
+            "TextureCoordinate0",
+            mesh.objects[0].texture_coordinates[0].name
+        );
+    }
+
+    #[test]
+    fn convert_v8_to_v10() {
+        let mut mesh = mesh(
+            1,
+            8,
+            MeshObjectData {
+                name: "a".to_string(),
+                positions: vec![attribute("Position0", VectorData::Vector2(vec![[0.0; 2]]))],
+                normals: vec![attribute("Normal0", VectorData::Vector4(vec![[0.0; 4]]))],
+                texture_coordinates: vec![attribute(
+                    "TextureCoordinate0",
+                    VectorData::Vector2(vec![[0.0; 2]]),
+                )],
+                ..MeshObjectData::default()
+            },
+        );
+
+        let lossy = mesh.convert_version(1, 10).unwrap();
+        assert_eq!(
+            vec![LossyConversion::ReducedPrecision {
+                mesh_object_name: "a".to_string(),
+                mesh_object_subindex: 0,
+                attribute_name: "TextureCoordinate0".to_string()
+            }],
+            lossy
+        );
+        assert_eq!((1, 10), (mesh.major_version, mesh.minor_version));
+    }
+
+    #[test]
+    fn convert_v10_to_v10() {
+        let mut mesh = mesh(
+            1,
+            10,
+            MeshObjectData {
+                texture_coordinates: vec![attribute("map1", VectorData::Vector2(vec![[0.0; 2]]))],
+                ..MeshObjectData::default()
+            },
+        );
+        assert!(mesh.convert_version(1, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn convert_v8_to_v10_vertex_index_limit() {
+        let mut mesh = mesh(
+            1,
+            8,
+            MeshObjectData {
+                bone_influences: vec![BoneInfluence {
+                    bone_name: "Hip".to_string(),
+                    vertex_weights: vec![VertexWeight {
+                        vertex_index: 65536,
+                        vertex_weight: 1.0,
+                    }],
+                }],
+                ..MeshObjectData::default()
+            },
+        );
+
+        let result = mesh.convert_version(1, 10);
+        assert!(matches!(
+            result,
+            Err(error::Error::SkinWeightVertexIndexExceedsLimit {
+                vertex_index: 65536,
+                limit: 65535,
+                major_version: 1,
+                minor_version: 10
+            })
+        ));
+        // The data is unchanged on error.
+        assert_eq!((1, 8), (mesh.major_version, mesh.minor_version));
+    }
+
+    #[test]
+    fn convert_unsupported_version() {
+        let mut mesh = mesh(1, 10, MeshObjectData::default());
+        let result = mesh.convert_version(2, 0);
+        assert!(matches!(
+            result,
+            Err(error::Error::UnsupportedVersion {
+                major_version: 2,
+                minor_version: 0
+            })
+        ));
+        assert_eq!((1, 10), (mesh.major_version, mesh.minor_version));
+    }
+}
@@ -0,0 +1,430 @@
+use geometry_tools::glam::Vec3A;
+
+use super::{error, BoneInfluence, MeshObjectData, VertexWeight};
+
+/// The method for sampling the skin weights of the source mesh in [transfer_bone_influences].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WeightTransferMethod {
+    /// Copy the weights of the closest source vertex.
+    NearestPoint,
+    /// Interpolate the weights of the closest point on the source triangles using barycentric coordinates.
+    /// Only the 4 largest bone weights for each vertex are kept, and the weights are normalized to sum to 1.0.
+    Barycentric,
+}
+
+/// The maximum number of bones influencing each vertex after interpolating weights.
+const MAX_INFLUENCES: usize = 4;
+
+/// Calculates the bone influences for `target` by sampling the skin weights of `source` at each vertex position of `target`.
+/// This allows rigging an edited mesh using the weights of the original mesh.
+///
+/// The first position attribute is used for both objects.
+/// The bones are sorted in the same order as the bone influences for `source`,
+/// and bones that do not influence any vertices in `target` are omitted.
+/**
+```rust
+# use ssbh_data::mesh_data::{
+#     AttributeData, BoneInfluence, MeshObjectData, VectorData, VertexWeight,
+#     transfer_bone_influences, WeightTransferMethod,
+# };
+let source = MeshObjectData {
+    positions: vec![AttributeData {
+        name: "Position0".into(),
+        data: VectorData::Vector3(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]]),
+    }],
+    bone_influences: vec![BoneInfluence {
+        bone_name: "Hip".into(),
+        vertex_weights: vec![VertexWeight { vertex_index: 1, vertex_weight: 1.0 }],
+    }],
+    ..MeshObjectData::default()
+};
+
+let mut target = MeshObjectData {
+    positions: vec![AttributeData {
+        name: "Position0".into(),
+        data: VectorData::Vector3(vec![[0.9, 0.0, 0.0]]),
+    }],
+    ..MeshObjectData::default()
+};
+
+target.bone_influences =
+    transfer_bone_influences(&source, &target, WeightTransferMethod::NearestPoint)?;
+assert_eq!(0, target.bone_influences[0].vertex_weights[0].vertex_index);
+# Ok::<(), ssbh_data::mesh_data::error::Error>(())
+```
+ */
+pub fn transfer_bone_influences(
+    source: &MeshObjectData,
+    target: &MeshObjectData,
+    method: WeightTransferMethod,
+) -> Result<Vec<BoneInfluence>, error::Error> {
+    let target_positions = positions(target)?;
+    if target_positions.is_empty() {
+        return Ok(Vec::new());
+    }
+    let source_positions = positions(source)?;
+
+    let source_weights = vertex_weights(source, source_positions.len())?;
+
+    let target_weights: Vec<Vec<(usize, f32)>> = match method {
+        WeightTransferMethod::NearestPoint => target_positions
+            .iter()
+            .map(|p| {
+                nearest_vertex(&source_positions, *p)
+                    .map(|i| source_weights[i].clone())
+                    .unwrap_or_default()
+            })
+            .collect(),
+        WeightTransferMethod::Barycentric => {
+            let faces = faces(source, source_positions.len())?;
+            target_positions
+                .iter()
+                .map(|p| match nearest_face(&source_positions, &faces, *p) {
+                    Some((face, coords)) => interpolate_weights(&source_weights, face, coords),
+                    // Meshes without faces can still be sampled by vertex.
+                    None => nearest_vertex(&source_positions, *p)
+                        .map(|i| source_weights[i].clone())
+                        .unwrap_or_default(),
+                })
+                .collect()
+        }
+    };
+
+    Ok(source
+        .bone_influences
+        .iter()
+        .enumerate()
+        .filter_map(|(bone_index, influence)| {
+            let vertex_weights: Vec<_> = target_weights
+                .iter()
+                .enumerate()
+                .filter_map(|(vertex_index, weights)| {
+                    weights
+                        .iter()
+                        .find(|(i, _)| *i == bone_index)
+                        .map(|(_, weight)| VertexWeight {
+                            vertex_index: vertex_index as u32,
+                            vertex_weight: *weight,
+                        })
+                })
+                .collect();
+
+            if vertex_weights.is_empty() {
+                None
+            } else {
+                Some(BoneInfluence {
+                    bone_name: influence.bone_name.clone(),
+                    vertex_weights,
+                })
+            }
+        })
+        .collect())
+}
+
+fn positions(data: &MeshObjectData) -> Result<Vec<Vec3A>, error::Error> {
+    match data.positions.first() {
+        Some(a) => Ok(a.data.to_glam_vec3a()),
+        None if data.vertex_count()? == 0 => Ok(Vec::new()),
+        None => Err(error::Error::MissingPositions {
+            mesh_object_name: data.name.clone(),
+        }),
+    }
+}
+
+// Group the weights by vertex as (bone index, weight) pairs.
+fn vertex_weights(
+    data: &MeshObjectData,
+    vertex_count: usize,
+) -> Result<Vec<Vec<(usize, f32)>>, error::Error> {
+    let mut weights = vec![Vec::new(); vertex_count];
+    for (bone_index, influence) in data.bone_influences.iter().enumerate() {
+        for w in &influence.vertex_weights {
+            let vertex_weights = weights.get_mut(w.vertex_index as usize).ok_or(
+                error::Error::VertexIndexOutOfRange {
+                    vertex_index: w.vertex_index as usize,
+                    vertex_count,
+                },
+            )?;
+            if w.vertex_weight > 0.0 {
+                vertex_weights.push((bone_index, w.vertex_weight));
+            }
+        }
+    }
+    Ok(weights)
+}
+
+fn faces(data: &MeshObjectData, vertex_count: usize) -> Result<Vec<[usize; 3]>, error::Error> {
+    data.vertex_indices
+        .chunks_exact(3)
+        .map(|face| {
+            let mut indices = [0; 3];
+            for (index, vertex_index) in indices.iter_mut().zip(face) {
+                *index = *vertex_index as usize;
+                if *index >= vertex_count {
+                    return Err(error::Error::VertexIndexOutOfRange {
+                        vertex_index: *index,
+                        vertex_count,
+                    });
+                }
+            }
+            Ok(indices)
+        })
+        .collect()
+}
+
+fn nearest_vertex(positions: &[Vec3A], point: Vec3A) -> Option<usize> {
+    positions
+        .iter()
+        .map(|p| p.distance_squared(point))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+}
+
+fn nearest_face(
+    positions: &[Vec3A],
+    faces: &[[usize; 3]],
+    point: Vec3A,
+) -> Option<([usize; 3], [f32; 3])> {
+    faces
+        .iter()
+        .map(|face| {
+            let [a, b, c] = face.map(|i| positions[i]);
+            let coords = closest_point_barycentric(point, a, b, c);
+            let closest = a * coords[0] + b * coords[1] + c * coords[2];
+            (*face, coords, closest.distance_squared(point))
+        })
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        .map(|(face, coords, _)| (face, coords))
+}
+
+// The barycentric coordinates of the closest point on the triangle abc to p.
+// Based on "Real-Time Collision Detection" by Christer Ericson.
+fn closest_point_barycentric(p: Vec3A, a: Vec3A, b: Vec3A, c: Vec3A) -> [f32; 3] {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return [1.0, 0.0, 0.0];
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return [0.0, 1.0, 0.0];
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return [1.0 - v, v, 0.0];
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return [0.0, 0.0, 1.0];
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return [1.0 - w, 0.0, w];
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return [0.0, 1.0 - w, w];
+    }
+
+    let denom = va + vb + vc;
+    if denom == 0.0 {
+        // Degenerate triangles have no well defined interior.
+        return [1.0, 0.0, 0.0];
+    }
+    let v = vb / denom;
+    let w = vc / denom;
+    [1.0 - v - w, v, w]
+}
+
+fn interpolate_weights(
+    weights: &[Vec<(usize, f32)>],
+    face: [usize; 3],
+    coords: [f32; 3],
+) -> Vec<(usize, f32)> {
+    let mut combined: Vec<(usize, f32)> = Vec::new();
+    for (vertex, coord) in face.iter().zip(coords) {
+        for (bone_index, weight) in &weights[*vertex] {
+            match combined.iter_mut().find(|(i, _)| i == bone_index) {
+                Some((_, w)) => *w += weight * coord,
+                None => combined.push((*bone_index, weight * coord)),
+            }
+        }
+    }
+
+    combined.retain(|(_, w)| *w > 0.0);
+    combined.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    combined.truncate(MAX_INFLUENCES);
+
+    let total: f32 = combined.iter().map(|(_, w)| w).sum();
+    if total > 0.0 {
+        for (_, w) in &mut combined {
+            *w /= total;
+        }
+    }
+    combined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_data::{AttributeData, VectorData};
+
+    fn weight(vertex_index: u32, vertex_weight: f32) -> VertexWeight {
+        VertexWeight {
+            vertex_index,
+            vertex_weight,
+        }
+    }
+
+    fn source() -> MeshObjectData {
+        MeshObjectData {
+            name: "source".to_string(),
+            vertex_indices: vec![0, 1, 2],
+            positions: vec![AttributeData {
+                name: "Position0".to_string(),
+                data: VectorData::Vector3(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]),
+            }],
+            bone_influences: vec![
+                BoneInfluence {
+                    bone_name: "A".to_string(),
+                    vertex_weights: vec![weight(0, 1.0), weight(2, 0.5)],
+                },
+                BoneInfluence {
+                    bone_name: "B".to_string(),
+                    vertex_weights: vec![weight(1, 1.0), weight(2, 0.5)],
+                },
+                BoneInfluence {
+                    bone_name: "C".to_string(),
+                    vertex_weights: Vec::new(),
+                },
+            ],
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn target(positions: Vec<[f32; 3]>) -> MeshObjectData {
+        MeshObjectData {
+            name: "target".to_string(),
+            positions: vec![AttributeData {
+                name: "Position0".to_string(),
+                data: VectorData::Vector3(positions),
+            }],
+            ..MeshObjectData::default()
+        }
+    }
+
+    fn bone_weights(influences: &[BoneInfluence]) -> Vec<(&str, Vec<(u32, f32)>)> {
+        influences
+            .iter()
+            .map(|i| {
+                (
+                    i.bone_name.as_str(),
+                    i.vertex_weights
+                        .iter()
+                        .map(|w| (w.vertex_index, w.vertex_weight))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transfer_nearest_point() {
+        let influences = transfer_bone_influences(
+            &source(),
+            &target(vec![[0.9, 0.1, 0.0], [0.1, 0.0, 0.0], [0.0, 2.0, 0.0]]),
+            WeightTransferMethod::NearestPoint,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("A", vec![(1, 1.0), (2, 0.5)]),
+                ("B", vec![(0, 1.0), (2, 0.5)])
+            ],
+            bone_weights(&influences)
+        );
+    }
+
+    #[test]
+    fn transfer_barycentric() {
+        let influences = transfer_bone_influences(
+            &source(),
+            &target(vec![[0.5, 0.0, 1.0], [0.0, 0.0, -1.0], [2.0, 0.0, 0.0]]),
+            WeightTransferMethod::Barycentric,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("A", vec![(0, 0.5), (1, 1.0)]),
+                ("B", vec![(0, 0.5), (2, 1.0)])
+            ],
+            bone_weights(&influences)
+        );
+    }
+
+    #[test]
+    fn transfer_barycentric_interior() {
+        let influences = transfer_bone_influences(
+            &source(),
+            &target(vec![[0.25, 0.5, 0.0]]),
+            WeightTransferMethod::Barycentric,
+        )
+        .unwrap();
+
+        // 0.25 * A + 0.25 * B + 0.5 * (0.5 * A + 0.5 * B)
+        assert_eq!(
+            vec![("A", vec![(0, 0.5)]), ("B", vec![(0, 0.5)])],
+            bone_weights(&influences)
+        );
+    }
+
+    #[test]
+    fn transfer_empty_target() {
+        assert!(transfer_bone_influences(
+            &source(),
+            &MeshObjectData::default(),
+            WeightTransferMethod::NearestPoint
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn transfer_invalid_source_weight() {
+        let mut source = source();
+        source.bone_influences[0]
+            .vertex_weights
+            .push(weight(3, 1.0));
+
+        let result = transfer_bone_influences(
+            &source,
+            &target(vec![[0.0; 3]]),
+            WeightTransferMethod::NearestPoint,
+        );
+        assert!(matches!(
+            result,
+            Err(error::Error::VertexIndexOutOfRange {
+                vertex_index: 3,
+                vertex_count: 3
+            })
+        ));
+    }
+}
@@ -13,7 +13,7 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 pub use ssbh_lib::formats::mesh::BoundingSphere;
 use ssbh_lib::formats::meshex::AllData;
-use ssbh_lib::{formats::meshex::MeshEx, Ptr64, Vector3};
+use ssbh_lib::{formats::meshex::MeshEx, Ptr64, Ptr64Aligned, Vector3};
 
 /// The data associated with a [MeshEx] file.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -40,12 +40,44 @@ pub struct MeshObjectGroupData {
 
 /// Rendering related toggles for a [MeshObjectData].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub struct EntryFlags {
     pub draw_model: bool,
     pub cast_shadow: bool,
-    // TODO: Preserve remaining flags?
+    /// Hides the model in reflections like the water on Fountain of Dreams.
+    pub hide_in_reflections: bool,
+    /// Only draws the model in reflections like the water on Fountain of Dreams.
+    pub reflections_only: bool,
+    /// The remaining unresearched bits of the flags.
+    /// This preserves the original value when converting to [MeshEx].
+    pub unk_bits: u16,
+}
+
+// The bits used by the named fields of EntryFlags.
+const ENTRY_FLAGS_KNOWN_BITS: u16 = 0b11011;
+
+impl From<ssbh_lib::formats::meshex::EntryFlag> for EntryFlags {
+    fn from(f: ssbh_lib::formats::meshex::EntryFlag) -> Self {
+        Self {
+            draw_model: f.draw_model(),
+            cast_shadow: f.cast_shadow(),
+            hide_in_reflections: f.unk3(),
+            reflections_only: f.unk4(),
+            unk_bits: u16::from_le_bytes(f.into_bytes()) & !ENTRY_FLAGS_KNOWN_BITS,
+        }
+    }
+}
+
+impl From<EntryFlags> for ssbh_lib::formats::meshex::EntryFlag {
+    fn from(f: EntryFlags) -> Self {
+        Self::from_bytes((f.unk_bits & !ENTRY_FLAGS_KNOWN_BITS).to_le_bytes())
+            .with_draw_model(f.draw_model)
+            .with_cast_shadow(f.cast_shadow)
+            .with_unk3(f.hide_in_reflections)
+            .with_unk4(f.reflections_only)
+    }
 }
 
 impl MeshExData {
@@ -82,6 +114,7 @@ impl MeshExData {
                             .map(|_| EntryFlags {
                                 draw_model: true,
                                 cast_shadow: true,
+                                ..Default::default()
                             })
                             .collect(),
                     }
@@ -162,11 +195,8 @@ impl From<&MeshEx> for MeshExData {
                         .positions(|e| e.mesh_object_group_index as usize == i)
                         .filter_map(|entry_index| {
                             // TODO: Return an error for invalid indices?
-                            let entry_flags = m.entry_flags.as_ref()?.0.get(entry_index)?;
-                            Some(EntryFlags {
-                                draw_model: entry_flags.draw_model(),
-                                cast_shadow: entry_flags.cast_shadow(),
-                            })
+                            let entry_flags = m.entry_flags.as_ref()?.get(entry_index)?;
+                            Some(EntryFlags::from(*entry_flags))
                         })
                         .collect(),
                 })
@@ -228,18 +258,16 @@ impl From<&MeshExData> for MeshEx {
                     })
                     .collect(),
             ),
-            entry_flags: Ptr64::new(ssbh_lib::formats::meshex::EntryFlags(
+            entry_flags: Ptr64Aligned::new(
                 m.mesh_object_groups
                     .iter()
                     .flat_map(|g| {
-                        g.entry_flags.iter().map(|e| {
-                            ssbh_lib::formats::meshex::EntryFlag::new()
-                                .with_draw_model(e.draw_model)
-                                .with_cast_shadow(e.cast_shadow)
-                        })
+                        g.entry_flags
+                            .iter()
+                            .map(|e| ssbh_lib::formats::meshex::EntryFlag::from(*e))
                     })
                     .collect(),
-            )),
+            ),
             unk1: 0, // TODO: Preserve this value?
         }
     }
@@ -297,7 +325,7 @@ mod tests {
                     unk1: Vector3::new(0.0, 1.0, 0.0),
                 },
             ]),
-            entry_flags: Ptr64::new(ssbh_lib::formats::meshex::EntryFlags(vec![
+            entry_flags: Ptr64Aligned::new(vec![
                 ssbh_lib::formats::meshex::EntryFlag::new()
                     .with_draw_model(false)
                     .with_cast_shadow(true),
@@ -307,7 +335,7 @@ mod tests {
                 ssbh_lib::formats::meshex::EntryFlag::new()
                     .with_draw_model(true)
                     .with_cast_shadow(true),
-            ])),
+            ]),
             unk1: 0,
         };
 
@@ -324,10 +352,12 @@ mod tests {
                         EntryFlags {
                             draw_model: false,
                             cast_shadow: true,
+                            ..Default::default()
                         },
                         EntryFlags {
                             draw_model: true,
                             cast_shadow: false,
+                            ..Default::default()
                         },
                     ],
                 },
@@ -341,6 +371,7 @@ mod tests {
                     entry_flags: vec![EntryFlags {
                         draw_model: true,
                         cast_shadow: true,
+                        ..Default::default()
                     }],
                 },
             ],
@@ -436,19 +467,19 @@ mod tests {
             ssbh_lib::formats::meshex::EntryFlag::new()
                 .with_draw_model(false)
                 .with_cast_shadow(true),
-            new_meshex.entry_flags.as_ref().unwrap().0[0]
+            new_meshex.entry_flags.as_ref().unwrap()[0]
         );
         assert_eq!(
             ssbh_lib::formats::meshex::EntryFlag::new()
                 .with_draw_model(true)
                 .with_cast_shadow(false),
-            new_meshex.entry_flags.as_ref().unwrap().0[1]
+            new_meshex.entry_flags.as_ref().unwrap()[1]
         );
         assert_eq!(
             ssbh_lib::formats::meshex::EntryFlag::new()
                 .with_draw_model(true)
                 .with_cast_shadow(true),
-            new_meshex.entry_flags.as_ref().unwrap().0[2]
+            new_meshex.entry_flags.as_ref().unwrap()[2]
         );
     }
 
@@ -492,11 +523,13 @@ mod tests {
             vec![
                 EntryFlags {
                     draw_model: true,
-                    cast_shadow: true
+                    cast_shadow: true,
+                    ..Default::default()
                 },
                 EntryFlags {
                     draw_model: true,
-                    cast_shadow: true
+                    cast_shadow: true,
+                    ..Default::default()
                 },
             ],
             data.mesh_object_groups[0].entry_flags
@@ -509,7 +542,8 @@ mod tests {
         assert_eq!(
             vec![EntryFlags {
                 draw_model: true,
-                cast_shadow: true
+                cast_shadow: true,
+                ..Default::default()
             }],
             data.mesh_object_groups[1].entry_flags
         );
@@ -687,4 +721,25 @@ mod tests {
             strip_mesh_name_tags("hairmid_rev_O_OBJ_O_SORTEACHNODEShape_rev")
         );
     }
+
+    #[test]
+    fn entry_flags_preserve_unknown_bits() {
+        let flags = ssbh_lib::formats::meshex::EntryFlag::new()
+            .with_draw_model(true)
+            .with_unk4(true)
+            .with_unk5(true);
+
+        let data = EntryFlags::from(flags);
+        assert_eq!(
+            EntryFlags {
+                draw_model: true,
+                cast_shadow: false,
+                hide_in_reflections: false,
+                reflections_only: true,
+                unk_bits: 0b100000,
+            },
+            data
+        );
+        assert_eq!(flags, ssbh_lib::formats::meshex::EntryFlag::from(data));
+    }
 }
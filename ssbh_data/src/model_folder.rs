@@ -0,0 +1,620 @@
+//! Reading, validating, and writing the files for a model in a single folder.
+//!
+//! Applications like model viewers and importers typically need several files at once.
+//! The [ModlData] in `"model.numdlb"` determines the file names for the mesh, skeleton, and material.
+//! The remaining files use their standard names like `"model.nuhlpb"`.
+//! Files that aren't present in the folder are [None].
+//!
+//! The files reference each other by name, so edits to one file can invalidate another.
+//! Check the references with [ModelFolder::validate] before saving.
+/*!
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::model_folder::ModelFolder;
+
+let mut folder = ModelFolder::from_folder("fighter/mario/model/body/c00")?;
+if let Some(matl) = &mut folder.matl {
+    matl.sort_entries();
+}
+
+for warning in folder.validate() {
+    println!("{warning}");
+}
+folder.write_to_folder("output")?;
+# Ok(()) }
+```
+ */
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use itertools::Itertools;
+
+use crate::prelude::*;
+
+const MODL_FILE_NAME: &str = "model.numdlb";
+const MESH_FILE_NAME: &str = "model.numshb";
+const SKEL_FILE_NAME: &str = "model.nusktb";
+const MATL_FILE_NAME: &str = "model.numatb";
+const HLPB_FILE_NAME: &str = "model.nuhlpb";
+const ADJ_FILE_NAME: &str = "model.adjb";
+const MESHEX_FILE_NAME: &str = "model.numshexb";
+
+pub mod error {
+    use thiserror::Error;
+
+    /// Errors while determining the file paths for a [ModelFolder](super::ModelFolder).
+    #[derive(Debug, Error)]
+    pub enum Error {
+        /// The file name from the modl is not a single file name in the folder
+        /// like an absolute path or a path containing `".."`.
+        #[error("file name {:?} is not a file name in the model folder", name)]
+        InvalidFileName { name: String },
+    }
+}
+
+/// The files for a single model like `"fighter/mario/model/body/c00"`.
+#[derive(Debug, Clone, Default)]
+pub struct ModelFolder {
+    /// The model from `"model.numdlb"` that assigns materials to mesh objects.
+    pub modl: Option<ModlData>,
+    pub mesh: Option<MeshData>,
+    pub skel: Option<SkelData>,
+    /// The material file for the first name in [material_file_names](crate::modl_data::ModlData::material_file_names).
+    pub matl: Option<MatlData>,
+    pub hlpb: Option<HlpbData>,
+    pub adj: Option<AdjData>,
+    pub meshex: Option<MeshExData>,
+}
+
+/// A reference between files in a [ModelFolder] that doesn't match the referenced file.
+/// See [ModelFolder::validate].
+#[derive(Debug, thiserror::Error)]
+pub enum ModelFolderWarning {
+    /// A [ModlEntryData](crate::modl_data::ModlEntryData) doesn't match an object in the mesh.
+    #[error(
+        "modl entry for mesh object {:?} subindex {} does not match an object in the mesh",
+        mesh_object_name,
+        mesh_object_subindex
+    )]
+    MissingMeshObject {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+    },
+
+    /// A [ModlEntryData](crate::modl_data::ModlEntryData) uses a label not present in the matl.
+    #[error(
+        "material {:?} for mesh object {:?} subindex {} does not match a material in the matl",
+        material_label,
+        mesh_object_name,
+        mesh_object_subindex
+    )]
+    MissingMaterial {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+        material_label: String,
+    },
+
+    /// The mesh object has no material assigned in the modl and will not render in game.
+    #[error(
+        "mesh object {:?} subindex {} has no modl entry",
+        mesh_object_name,
+        mesh_object_subindex
+    )]
+    MissingModlEntry {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+    },
+
+    /// The mesh object's parent bone or skin weights use a bone not present in the skel.
+    #[error(
+        "bone {:?} for mesh object {:?} subindex {} does not match a bone in the skel",
+        bone_name,
+        mesh_object_name,
+        mesh_object_subindex
+    )]
+    MissingMeshObjectBone {
+        mesh_object_name: String,
+        mesh_object_subindex: u64,
+        bone_name: String,
+    },
+
+    /// An aim or orient constraint in the hlpb uses a bone not present in the skel.
+    #[error(
+        "bone {:?} for helper bone constraint {:?} does not match a bone in the skel",
+        bone_name,
+        constraint_name
+    )]
+    MissingConstraintBone {
+        constraint_name: String,
+        bone_name: String,
+    },
+
+    /// The adj entries are not valid for the mesh.
+    /// See [AdjData::validate].
+    #[error("adj is not valid for the mesh: {}", source)]
+    InvalidAdj {
+        source: crate::adj_data::error::Error,
+    },
+
+    /// The meshex is missing the group for mesh objects with this name
+    /// or has a different number of objects in the group.
+    /// Regenerate the meshex with [MeshExData::from_mesh_objects].
+    #[error(
+        "meshex group for mesh object {:?} does not match the {} mesh objects with this name",
+        mesh_object_name,
+        mesh_object_count
+    )]
+    MeshExMismatch {
+        mesh_object_name: String,
+        mesh_object_count: usize,
+    },
+}
+
+impl ModelFolder {
+    /// Reads the files in `folder` using the file names from `"model.numdlb"` if present.
+    /// Returns an error if any of the files present can't be read
+    /// or if a file name from the modl is not a file name in `folder`.
+    pub fn from_folder<P: AsRef<Path>>(folder: P) -> Result<Self, Box<dyn Error>> {
+        let folder = folder.as_ref();
+
+        let modl: Option<ModlData> = read_optional(&folder.join(MODL_FILE_NAME))?;
+        let names = FileNames::new(modl.as_ref())?;
+
+        Ok(Self {
+            mesh: read_optional(&folder.join(names.mesh))?,
+            skel: read_optional(&folder.join(names.skel))?,
+            matl: read_optional(&folder.join(names.matl))?,
+            hlpb: read_optional(&folder.join(HLPB_FILE_NAME))?,
+            adj: read_optional(&folder.join(ADJ_FILE_NAME))?,
+            meshex: read_optional(&folder.join(MESHEX_FILE_NAME))?,
+            modl,
+        })
+    }
+
+    /// Writes the files that are not [None] to `folder` using the same names as [ModelFolder::from_folder].
+    /// The folder is created if it does not exist.
+    ///
+    /// The mesh, skeleton, and material names come from the [modl](#structfield.modl),
+    /// so the written files can be read again with [ModelFolder::from_folder].
+    /// Returns an error without writing any files if a file name from the modl is not a file name in `folder`.
+    pub fn write_to_folder<P: AsRef<Path>>(&self, folder: P) -> Result<(), Box<dyn Error>> {
+        let folder = folder.as_ref();
+        let names = FileNames::new(self.modl.as_ref())?;
+        std::fs::create_dir_all(folder)?;
+
+        write_optional(&self.modl, &folder.join(MODL_FILE_NAME))?;
+        write_optional(&self.mesh, &folder.join(names.mesh))?;
+        write_optional(&self.skel, &folder.join(names.skel))?;
+        write_optional(&self.matl, &folder.join(names.matl))?;
+        write_optional(&self.hlpb, &folder.join(HLPB_FILE_NAME))?;
+        write_optional(&self.adj, &folder.join(ADJ_FILE_NAME))?;
+        write_optional(&self.meshex, &folder.join(MESHEX_FILE_NAME))?;
+        Ok(())
+    }
+
+    /// Checks the names and indices used to reference other files in the folder.
+    /// Checks that depend on a missing file are skipped.
+    ///
+    /// An empty result does not guarantee the model will load in game,
+    /// but each warning is likely to cause missing or incorrectly rendered meshes.
+    pub fn validate(&self) -> Vec<ModelFolderWarning> {
+        let mut warnings = Vec::new();
+
+        if let (Some(modl), Some(mesh)) = (&self.modl, &self.mesh) {
+            validate_modl_mesh(modl, mesh, &mut warnings);
+        }
+        if let (Some(modl), Some(matl)) = (&self.modl, &self.matl) {
+            validate_modl_matl(modl, matl, &mut warnings);
+        }
+        if let (Some(mesh), Some(skel)) = (&self.mesh, &self.skel) {
+            validate_mesh_skel(mesh, skel, &mut warnings);
+        }
+        if let (Some(hlpb), Some(skel)) = (&self.hlpb, &self.skel) {
+            validate_hlpb_skel(hlpb, skel, &mut warnings);
+        }
+        if let (Some(adj), Some(mesh)) = (&self.adj, &self.mesh) {
+            if let Err(source) = adj.validate(mesh) {
+                warnings.push(ModelFolderWarning::InvalidAdj { source });
+            }
+        }
+        if let (Some(meshex), Some(mesh)) = (&self.meshex, &self.mesh) {
+            validate_meshex_mesh(meshex, mesh, &mut warnings);
+        }
+
+        warnings
+    }
+}
+
+struct FileNames<'a> {
+    mesh: &'a str,
+    skel: &'a str,
+    matl: &'a str,
+}
+
+impl<'a> FileNames<'a> {
+    fn new(modl: Option<&'a ModlData>) -> Result<Self, error::Error> {
+        let names = match modl {
+            Some(modl) => Self {
+                mesh: &modl.mesh_file_name,
+                skel: &modl.skeleton_file_name,
+                matl: modl
+                    .material_file_names
+                    .first()
+                    .map(String::as_str)
+                    .unwrap_or(MATL_FILE_NAME),
+            },
+            None => Self {
+                mesh: MESH_FILE_NAME,
+                skel: SKEL_FILE_NAME,
+                matl: MATL_FILE_NAME,
+            },
+        };
+
+        // The modl may come from an untrusted file.
+        // Only allow names for files directly in the folder.
+        for name in [names.mesh, names.skel, names.matl] {
+            if Path::new(name).file_name() != Some(OsStr::new(name)) {
+                return Err(error::Error::InvalidFileName {
+                    name: name.to_string(),
+                });
+            }
+        }
+        Ok(names)
+    }
+}
+
+fn read_optional<T: SsbhData>(path: &Path) -> Result<Option<T>, Box<dyn Error>> {
+    if path.is_file() {
+        T::from_file(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn write_optional<T: SsbhData>(data: &Option<T>, path: &Path) -> Result<(), Box<dyn Error>>
+where
+    T::WriteError: 'static,
+{
+    if let Some(data) = data {
+        data.write_to_file(path)?;
+    }
+    Ok(())
+}
+
+fn validate_modl_mesh(modl: &ModlData, mesh: &MeshData, warnings: &mut Vec<ModelFolderWarning>) {
+    let objects: HashSet<_> = mesh
+        .objects
+        .iter()
+        .map(|o| (o.name.as_str(), o.subindex))
+        .collect();
+    let entries: HashSet<_> = modl
+        .entries
+        .iter()
+        .map(|e| (e.mesh_object_name.as_str(), e.mesh_object_subindex))
+        .collect();
+
+    for e in &modl.entries {
+        if !objects.contains(&(e.mesh_object_name.as_str(), e.mesh_object_subindex)) {
+            warnings.push(ModelFolderWarning::MissingMeshObject {
+                mesh_object_name: e.mesh_object_name.clone(),
+                mesh_object_subindex: e.mesh_object_subindex,
+            });
+        }
+    }
+
+    for o in &mesh.objects {
+        if !entries.contains(&(o.name.as_str(), o.subindex)) {
+            warnings.push(ModelFolderWarning::MissingModlEntry {
+                mesh_object_name: o.name.clone(),
+                mesh_object_subindex: o.subindex,
+            });
+        }
+    }
+}
+
+fn validate_modl_matl(modl: &ModlData, matl: &MatlData, warnings: &mut Vec<ModelFolderWarning>) {
+    let labels: HashSet<_> = matl
+        .entries
+        .iter()
+        .map(|e| e.material_label.as_str())
+        .collect();
+
+    for e in &modl.entries {
+        if !labels.contains(e.material_label.as_str()) {
+            warnings.push(ModelFolderWarning::MissingMaterial {
+                mesh_object_name: e.mesh_object_name.clone(),
+                mesh_object_subindex: e.mesh_object_subindex,
+                material_label: e.material_label.clone(),
+            });
+        }
+    }
+}
+
+fn validate_mesh_skel(mesh: &MeshData, skel: &SkelData, warnings: &mut Vec<ModelFolderWarning>) {
+    let bones: HashSet<_> = skel.bones.iter().map(|b| b.name.as_str()).collect();
+
+    for o in &mesh.objects {
+        // Objects without a parent bone use an empty name.
+        let bone_names = std::iter::once(o.parent_bone_name.as_str())
+            .filter(|n| !n.is_empty())
+            .chain(o.bone_influences.iter().map(|i| i.bone_name.as_str()))
+            .unique();
+
+        for bone_name in bone_names {
+            if !bones.contains(bone_name) {
+                warnings.push(ModelFolderWarning::MissingMeshObjectBone {
+                    mesh_object_name: o.name.clone(),
+                    mesh_object_subindex: o.subindex,
+                    bone_name: bone_name.to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn validate_hlpb_skel(hlpb: &HlpbData, skel: &SkelData, warnings: &mut Vec<ModelFolderWarning>) {
+    let bones: HashSet<_> = skel.bones.iter().map(|b| b.name.as_str()).collect();
+
+    let aim_bones = hlpb.aim_constraints.iter().map(|c| {
+        (
+            &c.name,
+            [
+                &c.aim_bone_name1,
+                &c.aim_bone_name2,
+                &c.target_bone_name1,
+                &c.target_bone_name2,
+            ],
+        )
+    });
+    let orient_bones = hlpb.orient_constraints.iter().map(|c| {
+        (
+            &c.name,
+            [
+                &c.parent_bone_name1,
+                &c.parent_bone_name2,
+                &c.source_bone_name,
+                &c.target_bone_name,
+            ],
+        )
+    });
+
+    for (constraint_name, bone_names) in aim_bones.chain(orient_bones) {
+        for bone_name in bone_names.into_iter().unique() {
+            if !bones.contains(bone_name.as_str()) {
+                warnings.push(ModelFolderWarning::MissingConstraintBone {
+                    constraint_name: constraint_name.clone(),
+                    bone_name: bone_name.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn validate_meshex_mesh(
+    meshex: &MeshExData,
+    mesh: &MeshData,
+    warnings: &mut Vec<ModelFolderWarning>,
+) {
+    let counts = mesh.objects.iter().map(|o| o.name.as_str()).counts();
+
+    for (name, count) in mesh
+        .objects
+        .iter()
+        .map(|o| o.name.as_str())
+        .unique()
+        .map(|name| (name, counts[name]))
+    {
+        let group = meshex
+            .mesh_object_groups
+            .iter()
+            .find(|g| g.mesh_object_full_name == name);
+        if group.map(|g| g.entry_flags.len()) != Some(count) {
+            warnings.push(ModelFolderWarning::MeshExMismatch {
+                mesh_object_name: name.to_string(),
+                mesh_object_count: count,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{self, matl_entry_data};
+    use crate::{
+        fixtures, hlpb_data::OrientConstraintData, mesh_data::MeshObjectData,
+        modl_data::ModlEntryData,
+    };
+    use ssbh_lib::{Vector3, Vector4};
+
+    fn folder() -> ModelFolder {
+        let mesh = fixtures::mesh_data(1, 10);
+        let matl = test_fixtures::matl_data(vec![matl_entry_data("a", "")]);
+        ModelFolder {
+            modl: Some(ModlData::from_parts("model", &mesh, &matl, "a")),
+            meshex: Some(MeshExData::from_mesh_objects(&mesh.objects)),
+            adj: Some(AdjData {
+                entries: mesh
+                    .objects
+                    .iter()
+                    .enumerate()
+                    .map(|(i, o)| crate::adj_data::AdjEntryData::from_mesh_object(i, o))
+                    .collect(),
+            }),
+            mesh: Some(mesh),
+            skel: Some(fixtures::skel_data()),
+            matl: Some(matl),
+            hlpb: Some(test_fixtures::hlpb_data()),
+        }
+    }
+
+    #[test]
+    fn validate_consistent_files() {
+        assert!(folder().validate().is_empty());
+    }
+
+    #[test]
+    fn validate_missing_files() {
+        assert!(ModelFolder::default().validate().is_empty());
+    }
+
+    #[test]
+    fn validate_modl_references() {
+        let mut folder = folder();
+        let modl = folder.modl.as_mut().unwrap();
+        modl.entries[0].material_label = "b".to_string();
+        modl.entries.push(ModlEntryData {
+            mesh_object_name: "missingShape".to_string(),
+            mesh_object_subindex: 0,
+            material_label: "a".to_string(),
+        });
+        folder.mesh.as_mut().unwrap().objects[0].subindex = 1;
+
+        let warnings = folder.validate();
+        assert!(matches!(
+            &warnings[..],
+            [
+                ModelFolderWarning::MissingMeshObject { mesh_object_name: a, mesh_object_subindex: 0 },
+                ModelFolderWarning::MissingMeshObject { mesh_object_name: b, mesh_object_subindex: 0 },
+                ModelFolderWarning::MissingModlEntry { mesh_object_name: c, mesh_object_subindex: 1 },
+                ModelFolderWarning::MissingMaterial { material_label: d, .. },
+            ] if a == "triangleShape" && b == "missingShape" && c == "triangleShape" && d == "b"
+        ));
+    }
+
+    #[test]
+    fn validate_bone_references() {
+        let mut folder = folder();
+        let mesh = folder.mesh.as_mut().unwrap();
+        mesh.objects[0].parent_bone_name = "Head".to_string();
+        mesh.objects[0].bone_influences[0].bone_name = "Head".to_string();
+
+        folder.hlpb.as_mut().unwrap().orient_constraints = vec![OrientConstraintData {
+            name: "nuHelperBoneRotateInterp1".to_string(),
+            parent_bone_name1: "Hip".to_string(),
+            parent_bone_name2: "Hip".to_string(),
+            source_bone_name: "Rot".to_string(),
+            target_bone_name: "H_Knee".to_string(),
+            unk_type: 1,
+            constraint_axes: Vector3::new(1.0, 1.0, 1.0),
+            quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            range_min: Vector3::new(-180.0, -180.0, -180.0),
+            range_max: Vector3::new(180.0, 180.0, 180.0),
+        }];
+
+        let warnings = folder.validate();
+        assert_eq!(2, warnings.len());
+        assert!(matches!(
+            &warnings[0],
+            ModelFolderWarning::MissingMeshObjectBone { bone_name, .. } if bone_name == "Head"
+        ));
+        assert!(matches!(
+            &warnings[1],
+            ModelFolderWarning::MissingConstraintBone { constraint_name, bone_name }
+            if constraint_name == "nuHelperBoneRotateInterp1" && bone_name == "H_Knee"
+        ));
+    }
+
+    #[test]
+    fn validate_meshex_adj() {
+        let mut folder = folder();
+        folder.mesh.as_mut().unwrap().objects.push(MeshObjectData {
+            name: "triangleShape".to_string(),
+            subindex: 1,
+            ..MeshObjectData::default()
+        });
+        folder.adj.as_mut().unwrap().entries[0].mesh_object_index = 5;
+
+        let warnings = folder.validate();
+        assert!(matches!(
+            &warnings[..],
+            [
+                ModelFolderWarning::MissingModlEntry { mesh_object_subindex: 1, .. },
+                ModelFolderWarning::InvalidAdj { .. },
+                ModelFolderWarning::MeshExMismatch { mesh_object_name, mesh_object_count: 2 },
+            ] if mesh_object_name == "triangleShape"
+        ));
+    }
+
+    #[test]
+    fn write_read_folder() {
+        let path =
+            std::env::temp_dir().join(format!("ssbh_data_model_folder_{}", std::process::id()));
+
+        let mut folder = folder();
+        let modl = folder.modl.as_mut().unwrap();
+        modl.mesh_file_name = "custom.numshb".to_string();
+        modl.material_file_names = vec!["custom.numatb".to_string()];
+        folder.write_to_folder(&path).unwrap();
+
+        assert!(path.join("custom.numshb").is_file());
+        assert!(!path.join("model.numshb").exists());
+
+        let new_folder = ModelFolder::from_folder(&path).unwrap();
+        std::fs::remove_dir_all(&path).unwrap();
+
+        assert_eq!(folder.modl, new_folder.modl);
+        assert_eq!(folder.skel, new_folder.skel);
+        assert_eq!(folder.matl, new_folder.matl);
+        assert!(new_folder.mesh.is_some());
+        assert!(new_folder.adj.is_some());
+        assert!(new_folder.meshex.is_some());
+        assert_eq!(folder.hlpb, new_folder.hlpb);
+        assert!(new_folder.validate().is_empty());
+    }
+
+    #[test]
+    fn write_folder_invalid_file_names() {
+        let path = std::env::temp_dir().join(format!(
+            "ssbh_data_model_folder_invalid_{}",
+            std::process::id()
+        ));
+
+        for name in [
+            "../model.numshb",
+            "/model.numshb",
+            "a/model.numshb",
+            "..",
+            "",
+        ] {
+            let mut folder = folder();
+            folder.modl.as_mut().unwrap().mesh_file_name = name.to_string();
+
+            let error = folder.write_to_folder(&path).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<error::Error>(),
+                Some(error::Error::InvalidFileName { name: n }) if n == name
+            ));
+            assert!(!path.exists());
+        }
+    }
+
+    #[test]
+    fn read_folder_invalid_file_names() {
+        let path = std::env::temp_dir().join(format!(
+            "ssbh_data_model_folder_read_invalid_{}",
+            std::process::id()
+        ));
+
+        let mut folder = folder();
+        folder.modl.as_mut().unwrap().skeleton_file_name = "../model.nusktb".to_string();
+        std::fs::create_dir_all(&path).unwrap();
+        folder
+            .modl
+            .as_ref()
+            .unwrap()
+            .write_to_file(path.join(MODL_FILE_NAME))
+            .unwrap();
+
+        let result = ModelFolder::from_folder(&path);
+        std::fs::remove_dir_all(&path).unwrap();
+
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<error::Error>(),
+            Some(error::Error::InvalidFileName { name }) if name == "../model.nusktb"
+        ));
+    }
+}
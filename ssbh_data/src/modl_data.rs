@@ -21,6 +21,8 @@ for entry in modl.entries {
 
 use ssbh_lib::{formats::modl::*, Version};
 
+use crate::{matl_data::MatlData, mesh_data::MeshData};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -46,10 +48,128 @@ pub struct ModlData {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ModlEntryData {
     pub mesh_object_name: String,
+    // Older versions used "mesh_object_sub_index".
+    #[cfg_attr(feature = "serde", serde(alias = "mesh_object_sub_index"))]
     pub mesh_object_subindex: u64,
     pub material_label: String,
 }
 
+impl ModlData {
+    /// Sorts the [entries](#structfield.entries) by mesh object name and then by mesh object subindex.
+    pub fn sort_entries(&mut self) {
+        self.entries.sort_by(|a, b| {
+            (&a.mesh_object_name, a.mesh_object_subindex)
+                .cmp(&(&b.mesh_object_name, b.mesh_object_subindex))
+        });
+    }
+}
+
+impl ModlData {
+    /// Creates a version 1.7 [ModlData] with an entry for each object in `mesh`
+    /// using the standard file names like `"model.numshb"`.
+    ///
+    /// Each object is assigned the material in `matl` with the best matching label ignoring case.
+    /// Labels matching the object name take priority over labels matching the name without
+    /// suffixes like `"_VIS_O_OBJShape"` or `"Shape"`, followed by the longest label contained in the name.
+    /// Objects without a matching material use `default_material`.
+    /**
+    ```rust
+    # use ssbh_data::prelude::*;
+    # use ssbh_data::mesh_data::MeshObjectData;
+    # use ssbh_data::matl_data::MatlEntryData;
+    # fn entry(label: &str) -> MatlEntryData {
+    #     MatlEntryData {
+    #         material_label: label.into(),
+    #         shader_label: String::new(),
+    #         blend_states: Vec::new(),
+    #         floats: Vec::new(),
+    #         booleans: Vec::new(),
+    #         vectors: Vec::new(),
+    #         rasterizer_states: Vec::new(),
+    #         samplers: Vec::new(),
+    #         textures: Vec::new(),
+    #         uv_transforms: Vec::new(),
+    #     }
+    # }
+    let mesh = MeshData {
+        major_version: 1,
+        minor_version: 10,
+        objects: vec![
+            MeshObjectData {
+                name: "body_VIS_O_OBJShape".into(),
+                ..Default::default()
+            },
+            MeshObjectData {
+                name: "hat".into(),
+                ..Default::default()
+            },
+        ],
+    };
+    let matl = MatlData {
+        major_version: 1,
+        minor_version: 6,
+        entries: vec![entry("Body"), entry("skin")],
+    };
+
+    let modl = ModlData::from_parts("model", &mesh, &matl, "skin");
+    assert_eq!("Body", modl.entries[0].material_label);
+    assert_eq!("skin", modl.entries[1].material_label);
+    ```
+     */
+    pub fn from_parts(
+        model_name: &str,
+        mesh: &MeshData,
+        matl: &MatlData,
+        default_material: &str,
+    ) -> Self {
+        Self {
+            major_version: 1,
+            minor_version: 7,
+            model_name: model_name.to_string(),
+            skeleton_file_name: "model.nusktb".to_string(),
+            material_file_names: vec!["model.numatb".to_string()],
+            animation_file_name: None,
+            mesh_file_name: "model.numshb".to_string(),
+            entries: mesh
+                .objects
+                .iter()
+                .map(|o| ModlEntryData {
+                    mesh_object_name: o.name.clone(),
+                    mesh_object_subindex: o.subindex,
+                    material_label: find_material_label(&o.name, matl)
+                        .unwrap_or(default_material)
+                        .to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn find_material_label<'a>(mesh_object_name: &str, matl: &'a MatlData) -> Option<&'a str> {
+    let labels = || matl.entries.iter().map(|e| e.material_label.as_str());
+
+    let name = mesh_object_name.to_ascii_lowercase();
+    let base_name = strip_mesh_object_suffix(&name);
+
+    labels()
+        .find(|l| l.eq_ignore_ascii_case(&name))
+        .or_else(|| labels().find(|l| l.eq_ignore_ascii_case(base_name)))
+        .or_else(|| {
+            labels()
+                .filter(|l| !l.is_empty() && base_name.contains(&l.to_ascii_lowercase()))
+                .max_by_key(|l| l.len())
+        })
+}
+
+fn strip_mesh_object_suffix(name: &str) -> &str {
+    // Smash Ultimate object names often use tags like "_VIS_O_OBJShape".
+    let name = name.strip_suffix("shape").unwrap_or(name);
+    match name.find("_vis") {
+        Some(index) => &name[..index],
+        None => name,
+    }
+}
+
 // Define two way conversions between types.
 impl From<Modl> for ModlData {
     fn from(m: Modl) -> Self {
@@ -142,6 +262,102 @@ mod tests {
     use super::*;
     use ssbh_lib::SsbhString;
 
+    fn matl(labels: &[&str]) -> MatlData {
+        MatlData {
+            major_version: 1,
+            minor_version: 6,
+            entries: labels
+                .iter()
+                .map(|l| crate::matl_data::MatlEntryData {
+                    material_label: l.to_string(),
+                    shader_label: String::new(),
+                    blend_states: Vec::new(),
+                    floats: Vec::new(),
+                    booleans: Vec::new(),
+                    vectors: Vec::new(),
+                    rasterizer_states: Vec::new(),
+                    samplers: Vec::new(),
+                    textures: Vec::new(),
+                    uv_transforms: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn find_material_labels() {
+        let matl = matl(&["eye", "Face_VIS", "eyeL", "face"]);
+        assert_eq!(Some("Face_VIS"), find_material_label("face_VIS", &matl));
+        assert_eq!(
+            Some("face"),
+            find_material_label("Face_VIS_O_OBJShape", &matl)
+        );
+        assert_eq!(Some("eyeL"), find_material_label("EyeLShape", &matl));
+        assert_eq!(
+            Some("eyeL"),
+            find_material_label("mario_eyel_VIS_O_OBJ", &matl)
+        );
+        assert_eq!(None, find_material_label("body", &matl));
+        assert_eq!(None, find_material_label("", &matl));
+    }
+
+    #[test]
+    fn modl_from_parts() {
+        use crate::mesh_data::MeshObjectData;
+
+        let mesh = MeshData {
+            major_version: 1,
+            minor_version: 10,
+            objects: vec![
+                MeshObjectData {
+                    name: "a".into(),
+                    subindex: 0,
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "a".into(),
+                    subindex: 1,
+                    ..Default::default()
+                },
+                MeshObjectData {
+                    name: "b".into(),
+                    subindex: 0,
+                    ..Default::default()
+                },
+            ],
+        };
+
+        assert_eq!(
+            ModlData {
+                major_version: 1,
+                minor_version: 7,
+                model_name: "model".into(),
+                skeleton_file_name: "model.nusktb".into(),
+                material_file_names: vec!["model.numatb".into()],
+                animation_file_name: None,
+                mesh_file_name: "model.numshb".into(),
+                entries: vec![
+                    ModlEntryData {
+                        mesh_object_name: "a".into(),
+                        mesh_object_subindex: 0,
+                        material_label: "A".into()
+                    },
+                    ModlEntryData {
+                        mesh_object_name: "a".into(),
+                        mesh_object_subindex: 1,
+                        material_label: "A".into()
+                    },
+                    ModlEntryData {
+                        mesh_object_name: "b".into(),
+                        mesh_object_subindex: 0,
+                        material_label: "default".into()
+                    }
+                ]
+            },
+            ModlData::from_parts("model", &mesh, &matl(&["A", "default"]), "default")
+        );
+    }
+
     #[test]
     fn create_modl() {
         let data = ModlData {
@@ -252,4 +468,69 @@ mod tests {
         assert_eq!(2, ssbh.mesh_object_subindex);
         assert_eq!("b", ssbh.material_label.to_str().unwrap());
     }
+
+    fn entry(name: &str, subindex: u64) -> ModlEntryData {
+        ModlEntryData {
+            mesh_object_name: name.into(),
+            mesh_object_subindex: subindex,
+            material_label: "mat".into(),
+        }
+    }
+
+    #[test]
+    fn convert_modl_preserves_entry_order() {
+        let data = ModlData {
+            major_version: 1,
+            minor_version: 7,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: vec!["b".into(), "a".into()],
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries: vec![entry("b", 1), entry("a", 0), entry("b", 0)],
+        };
+
+        assert_eq!(data, ModlData::from(Modl::from(&data)));
+    }
+
+    #[test]
+    fn sort_modl_entries() {
+        let mut data = ModlData {
+            major_version: 1,
+            minor_version: 7,
+            model_name: String::new(),
+            skeleton_file_name: String::new(),
+            material_file_names: Vec::new(),
+            animation_file_name: None,
+            mesh_file_name: String::new(),
+            entries: vec![entry("b", 1), entry("a", 0), entry("b", 0)],
+        };
+        data.sort_entries();
+        assert_eq!(
+            vec![entry("a", 0), entry("b", 0), entry("b", 1)],
+            data.entries
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_modl_entry_older_version() {
+        let entry: ModlEntryData = serde_json::from_str(
+            r#"{
+                "mesh_object_name": "a",
+                "mesh_object_sub_index": 1,
+                "material_label": "b"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ModlEntryData {
+                mesh_object_name: "a".to_string(),
+                mesh_object_subindex: 1,
+                material_label: "b".to_string()
+            },
+            entry
+        );
+    }
 }
@@ -0,0 +1,96 @@
+//! Types for working with [Nufx] data in .nufxlb files.
+//!
+//! The shader program data is not decoded further, so [NufxData] stores the [Nufx] unmodified.
+//! This allows converting .nufxlb files with the same [SsbhData](crate::SsbhData) API as the other formats.
+/*!
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::prelude::*;
+use ssbh_lib::formats::nufx::Nufx;
+
+let data = NufxData::from_file("nuc2effectlibrary.nufxlb")?;
+if let Nufx::V1(nufx) = &data.nufx {
+    for program in &nufx.programs.elements {
+        println!("{}", program.name.to_string_lossy());
+    }
+}
+data.write_to_file("nuc2effectlibrary.nufxlb")?;
+# Ok(()) }
+```
+ */
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use ssbh_lib::formats::nufx::Nufx;
+
+pub mod search;
+
+/// The data associated with a [Nufx] file.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct NufxData {
+    pub nufx: Nufx,
+}
+
+impl From<Nufx> for NufxData {
+    fn from(nufx: Nufx) -> Self {
+        Self { nufx }
+    }
+}
+
+impl From<&Nufx> for NufxData {
+    fn from(nufx: &Nufx) -> Self {
+        Self { nufx: nufx.clone() }
+    }
+}
+
+impl From<NufxData> for Nufx {
+    fn from(data: NufxData) -> Self {
+        data.nufx
+    }
+}
+
+impl From<&NufxData> for Nufx {
+    fn from(data: &NufxData) -> Self {
+        data.nufx.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SsbhData;
+    use binrw::io::Cursor;
+    use ssbh_lib::formats::nufx::{NufxV1, ShaderProgramV1, ShaderStages};
+    use ssbh_lib::SsbhArray;
+
+    #[test]
+    fn nufx_data_round_trip() {
+        let data = NufxData {
+            nufx: Nufx::V1(NufxV1 {
+                programs: vec![ShaderProgramV1 {
+                    name: "SFX_PBS_0100000008008269_opaque".into(),
+                    render_pass: "nu::Opaque".into(),
+                    shaders: ShaderStages {
+                        vertex_shader: "vs".into(),
+                        unk_shader1: "".into(),
+                        unk_shader2: "".into(),
+                        geometry_shader: "".into(),
+                        pixel_shader: "ps".into(),
+                        compute_shader: "".into(),
+                    },
+                    vertex_attributes: SsbhArray::new(),
+                    material_parameters: SsbhArray::new(),
+                }]
+                .into(),
+                unk_string_list: SsbhArray::new(),
+            }),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        SsbhData::write(&data, &mut writer).unwrap();
+        writer.set_position(0);
+        assert_eq!(data, NufxData::read(&mut writer).unwrap());
+    }
+}
@@ -0,0 +1,211 @@
+//! Fast lookup and search for the shader programs in a [NufxData].
+//!
+//! Smash Ultimate's shader library contains thousands of programs.
+//! A [ProgramIndex] finds programs by render pass and name without scanning every program.
+/*!
+```rust
+use ssbh_data::nufx_data::{search::ProgramIndex, NufxData};
+use ssbh_lib::formats::nufx::{Nufx, NufxV1, ShaderProgramV1, ShaderStages};
+use ssbh_lib::SsbhArray;
+
+let program = |name: &str| ShaderProgramV1 {
+    name: name.into(),
+    render_pass: "nu::Opaque".into(),
+    shaders: ShaderStages {
+        vertex_shader: "".into(),
+        unk_shader1: "".into(),
+        unk_shader2: "".into(),
+        geometry_shader: "".into(),
+        pixel_shader: "".into(),
+        compute_shader: "".into(),
+    },
+    vertex_attributes: SsbhArray::new(),
+    material_parameters: SsbhArray::new(),
+};
+
+let data = NufxData {
+    nufx: Nufx::V1(NufxV1 {
+        programs: vec![
+            program("SFX_PBS_0100000008008269_opaque"),
+            program("SFX_PBS_010000000800826b_opaque"),
+        ]
+        .into(),
+        unk_string_list: SsbhArray::new(),
+    }),
+};
+
+let index = ProgramIndex::new(&data);
+let entry = index.get("nu::Opaque", "SFX_PBS_010000000800826b_opaque").unwrap();
+assert_eq!(1, entry.index);
+
+assert_eq!(2, index.search("pbs 0100").len());
+```
+ */
+use std::collections::HashMap;
+
+use ssbh_lib::formats::nufx::Nufx;
+
+use super::NufxData;
+
+/// The name and render pass for a program in a [ProgramIndex].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProgramEntry {
+    /// The index of the program in the programs of the [Nufx].
+    pub index: usize,
+    /// The program name like `"SFX_PBS_0100000008008269_opaque"`.
+    /// This matches the [shader_label](crate::matl_data::MatlEntryData::shader_label) of materials using the program.
+    pub name: String,
+    /// The render pass like `"nu::Opaque"`.
+    pub render_pass: String,
+}
+
+/// An index over the programs in a [NufxData] for constant time lookup and substring search.
+#[derive(Debug, Clone)]
+pub struct ProgramIndex {
+    entries: Vec<ProgramEntry>,
+    // Names are converted once to avoid allocating for each search.
+    lowercase_names: Vec<String>,
+    // Keyed by render pass and then name to allow lookups using &str.
+    indices: HashMap<String, HashMap<String, usize>>,
+}
+
+impl ProgramIndex {
+    /// Creates an index for the programs in `nufx`.
+    /// If multiple programs have the same render pass and name, lookups return the first program.
+    pub fn new(nufx: &NufxData) -> Self {
+        let programs: Vec<(String, String)> = match &nufx.nufx {
+            Nufx::V0(v0) => v0
+                .programs
+                .elements
+                .iter()
+                .map(|p| (p.name.to_string_lossy(), p.render_pass.to_string_lossy()))
+                .collect(),
+            Nufx::V1(v1) => v1
+                .programs
+                .elements
+                .iter()
+                .map(|p| (p.name.to_string_lossy(), p.render_pass.to_string_lossy()))
+                .collect(),
+        };
+
+        let entries: Vec<_> = programs
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, render_pass))| ProgramEntry {
+                index,
+                name,
+                render_pass,
+            })
+            .collect();
+
+        let mut indices: HashMap<String, HashMap<String, usize>> = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            indices
+                .entry(entry.render_pass.clone())
+                .or_default()
+                .entry(entry.name.clone())
+                .or_insert(i);
+        }
+
+        Self {
+            lowercase_names: entries.iter().map(|e| e.name.to_lowercase()).collect(),
+            entries,
+            indices,
+        }
+    }
+
+    /// Finds the program with the given `render_pass` like `"nu::Opaque"`
+    /// and `name` like `"SFX_PBS_0100000008008269_opaque"`.
+    pub fn get(&self, render_pass: &str, name: &str) -> Option<&ProgramEntry> {
+        self.indices
+            .get(render_pass)?
+            .get(name)
+            .map(|i| &self.entries[*i])
+    }
+
+    /// Finds the programs with names containing every whitespace separated term in `query` ignoring case.
+    /// The results are in the same order as the programs in the file.
+    /// An empty `query` matches every program.
+    pub fn search(&self, query: &str) -> Vec<&ProgramEntry> {
+        let terms: Vec<_> = query.split_whitespace().map(|t| t.to_lowercase()).collect();
+
+        self.entries
+            .iter()
+            .zip(&self.lowercase_names)
+            .filter(|(_, name)| terms.iter().all(|t| name.contains(t.as_str())))
+            .map(|(entry, _)| entry)
+            .collect()
+    }
+
+    /// The entries for all programs in the same order as the file.
+    pub fn entries(&self) -> &[ProgramEntry] {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssbh_lib::formats::nufx::{NufxV0, ShaderProgramV0, ShaderStages};
+    use ssbh_lib::SsbhArray;
+
+    fn program(name: &str, render_pass: &str) -> ShaderProgramV0 {
+        ShaderProgramV0 {
+            name: name.into(),
+            render_pass: render_pass.into(),
+            shaders: ShaderStages {
+                vertex_shader: "".into(),
+                unk_shader1: "".into(),
+                unk_shader2: "".into(),
+                geometry_shader: "".into(),
+                pixel_shader: "".into(),
+                compute_shader: "".into(),
+            },
+            material_parameters: SsbhArray::new(),
+        }
+    }
+
+    fn index() -> ProgramIndex {
+        ProgramIndex::new(&NufxData {
+            nufx: Nufx::V0(NufxV0 {
+                programs: vec![
+                    program("SFX_PBS_0100000008008269_opaque", "nu::Opaque"),
+                    program("SFX_PBS_0100000008008269_sort", "nu::Sort"),
+                    program("SFX_PBS_0100000008008269_sort", "nu::Sort"),
+                    program("SFX_PBS_2b00000000000000_far", "nu::Far"),
+                ]
+                .into(),
+                unk_string_list: SsbhArray::new(),
+            }),
+        })
+    }
+
+    #[test]
+    fn get_program() {
+        let index = index();
+        assert_eq!(
+            Some(&ProgramEntry {
+                index: 1,
+                name: "SFX_PBS_0100000008008269_sort".to_string(),
+                render_pass: "nu::Sort".to_string()
+            }),
+            index.get("nu::Sort", "SFX_PBS_0100000008008269_sort")
+        );
+        assert_eq!(
+            None,
+            index.get("nu::Opaque", "SFX_PBS_0100000008008269_sort")
+        );
+        assert_eq!(None, index.get("nu::Near", "SFX_PBS_2b00000000000000_far"));
+    }
+
+    #[test]
+    fn search_programs() {
+        let index = index();
+        let names = |query| -> Vec<_> { index.search(query).iter().map(|e| e.index).collect() };
+        assert_eq!(vec![0, 1, 2], names("08008269"));
+        assert_eq!(vec![1, 2], names("8269 SORT"));
+        assert_eq!(vec![3], names("sfx_pbs_2b"));
+        assert!(names("SFX_PBS_3").is_empty());
+        assert_eq!(4, names("").len());
+    }
+}
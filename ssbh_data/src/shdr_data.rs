@@ -1,13 +1,19 @@
 //! Types for working with [Shdr] data in .nushdb files.
+//!
+//! The [meta_data](struct.ShaderEntryData.html#structfield.meta_data) is read from the compiled shader binary.
+//! The binary data is preserved as is when converting back to [Shdr],
+//! so changes to the metadata are not saved.
 use binrw::io::{Cursor, Seek, SeekFrom};
 use binrw::BinReaderExt;
 use binrw::{binread, BinRead, BinResult, VecArgs};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use ssbh_lib::formats::shdr::{ShaderStage, Shdr};
-use std::convert::{TryFrom, TryInto};
+use ssbh_lib::formats::shdr::{Shader, ShaderStage, Shdr};
 use std::io::Read;
 
+use crate::matl_data::param_families::param_id_from_name;
+use crate::matl_data::{MatlEntryData, ParamId};
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug)]
@@ -16,6 +22,10 @@ pub struct ShdrData {
 }
 
 // TODO: Convert the binary data to another format?
+/// A compiled shader and its metadata.
+///
+/// Use [ShaderEntryData::from_binary] to create new shaders,
+/// which remains compatible if more fields are added to preserve the original data.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug)]
@@ -23,6 +33,14 @@ pub struct ShaderEntryData {
     pub name: String,
     pub shader_stage: ShaderStage,
     pub meta_data: MetaData,
+    /// See [unk3](ssbh_lib::formats::shdr::Shader::unk3).
+    pub unk3: u32,
+    /// The compiled shader code and metadata that is written unmodified to the [Shdr].
+    /// This is serialized as a hex string to keep the output readable.
+    #[cfg_attr(feature = "serde", serde(with = "hex_bytes"))]
+    pub binary_data: Vec<u8>,
+    /// See [binary_size](ssbh_lib::formats::shdr::Shader::binary_size).
+    pub binary_size: u64,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -123,6 +141,92 @@ impl Uniform {
     }
 }
 
+impl Uniform {
+    /// The material parameter for the uniform's [name](#structfield.name) like `"CustomVector47"`.
+    /// See [uniform_param_id].
+    pub fn param_id(&self) -> Option<ParamId> {
+        uniform_param_id(&self.name)
+    }
+}
+
+/// Finds the [ParamId] for a shader uniform name like `"CustomVector47"` or `"Texture0"`.
+/// Any buffer prefix like `"MC."` or array index like `"[0]"` is ignored.
+/// Returns [None] if the uniform does not correspond to a numbered material parameter.
+/**
+```rust
+use ssbh_data::shdr_data::uniform_param_id;
+use ssbh_data::matl_data::ParamId;
+
+assert_eq!(Some(ParamId::CustomVector47), uniform_param_id("CustomVector47"));
+assert_eq!(Some(ParamId::CustomFloat8), uniform_param_id("MC.CustomFloat8[0]"));
+assert_eq!(None, uniform_param_id("gl_Position"));
+```
+ */
+pub fn uniform_param_id(name: &str) -> Option<ParamId> {
+    let name = name.rsplit('.').next().unwrap_or(name);
+    let name = name.split('[').next().unwrap_or(name);
+    param_id_from_name(name)
+}
+
+impl ShaderEntryData {
+    /// Creates a shader by reading the [meta_data](#structfield.meta_data) from `binary_data`.
+    pub fn from_binary(
+        name: &str,
+        shader_stage: ShaderStage,
+        binary_data: Vec<u8>,
+    ) -> BinResult<Self> {
+        let mut reader = Cursor::new(&binary_data);
+        let shader: ShaderBinary = reader.read_le()?;
+        let meta_data = MetaData::new(&mut reader, &shader);
+
+        Ok(Self {
+            name: name.to_string(),
+            shader_stage,
+            meta_data,
+            // Smash Ultimate always uses 2.
+            unk3: 2,
+            binary_size: binary_data.len() as u64,
+            binary_data,
+        })
+    }
+
+    /// The material parameters read by the shader's [uniforms](struct.MetaData.html#structfield.uniforms) in order without duplicates.
+    pub fn param_ids(&self) -> Vec<ParamId> {
+        let mut param_ids = Vec::new();
+        for param_id in self.meta_data.uniforms.iter().filter_map(Uniform::param_id) {
+            if !param_ids.contains(&param_id) {
+                param_ids.push(param_id);
+            }
+        }
+        param_ids
+    }
+
+    /// The material parameters read by the shader that are not present in `entry`.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_data::prelude::*;
+
+    let matl = MatlData::from_file("model.numatb")?;
+    let shdr = ShdrData::from_file("nuc2effectlibrary.nushdb")?;
+
+    for shader in &shdr.shaders {
+        for param_id in shader.missing_params(&matl.entries[0]) {
+            println!("shader {} requires {:?}", shader.name, param_id);
+        }
+    }
+    # Ok(()) }
+    ```
+     */
+    pub fn missing_params(&self, entry: &MatlEntryData) -> Vec<ParamId> {
+        let entry_params = entry.param_ids();
+        self.param_ids()
+            .into_iter()
+            .filter(|p| !entry_params.contains(p))
+            .collect()
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug)]
@@ -380,21 +484,30 @@ fn read_string<R: Read + Seek>(
     Ok(String::from_utf8_lossy(&bytes).to_string())
 }
 
-impl TryFrom<Shdr> for ShdrData {
-    type Error = std::convert::Infallible;
+#[cfg(feature = "serde")]
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
 
-    fn try_from(shdr: Shdr) -> Result<Self, Self::Error> {
-        Self::try_from(&shdr)
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        hex::decode(text).map_err(serde::de::Error::custom)
     }
 }
 
-impl TryFrom<&Shdr> for ShdrData {
-    type Error = std::convert::Infallible;
+impl From<Shdr> for ShdrData {
+    fn from(shdr: Shdr) -> Self {
+        Self::from(&shdr)
+    }
+}
 
-    fn try_from(shdr: &Shdr) -> Result<Self, Self::Error> {
-        // TODO: Rebuild Shdr from ShdrData?
+impl From<&Shdr> for ShdrData {
+    fn from(shdr: &Shdr) -> Self {
         // TODO: Avoid unwrap.
-        Ok(Self {
+        Self {
             shaders: match shdr {
                 Shdr::V12 { shaders } => shaders
                     .elements
@@ -406,25 +519,175 @@ impl TryFrom<&Shdr> for ShdrData {
                             name: s.name.to_string_lossy(),
                             shader_stage: s.shader_stage,
                             meta_data: MetaData::new(&mut reader, &shader),
+                            unk3: s.unk3,
+                            binary_data: s.shader_binary.elements.clone(),
+                            binary_size: s.binary_size,
                         }
                     })
                     .collect(),
             },
-        })
+        }
     }
 }
 
-impl ShdrData {
-    pub fn from_file<P: AsRef<std::path::Path>>(
-        path: P,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        Shdr::from_file(path)?.try_into().map_err(Into::into)
+impl From<ShdrData> for Shdr {
+    fn from(data: ShdrData) -> Self {
+        Self::from(&data)
     }
+}
 
-    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, Box<dyn std::error::Error>> {
-        Shdr::read(reader)?.try_into().map_err(Into::into)
+impl From<&ShdrData> for Shdr {
+    fn from(data: &ShdrData) -> Self {
+        Shdr::V12 {
+            shaders: data
+                .shaders
+                .iter()
+                .map(|s| Shader {
+                    name: s.name.as_str().into(),
+                    shader_stage: s.shader_stage,
+                    unk3: s.unk3,
+                    shader_binary: s.binary_data.clone().into(),
+                    binary_size: s.binary_size,
+                })
+                .collect(),
+        }
     }
 }
 
-// TODO: Convert ShdrData -> Shdr.
-// TODO: Tests.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::matl_entry_data;
+
+    fn uniform(name: &str) -> Uniform {
+        Uniform {
+            name: name.to_string(),
+            data_type: DataType::Vector4,
+            buffer_index: 0,
+            uniform_buffer_offset: 0,
+            unk11: 0,
+        }
+    }
+
+    fn shader(uniforms: &[&str]) -> ShaderEntryData {
+        ShaderEntryData {
+            name: "a".to_string(),
+            shader_stage: ShaderStage::Fragment,
+            meta_data: MetaData {
+                buffers: Vec::new(),
+                uniforms: uniforms.iter().map(|n| uniform(n)).collect(),
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+            },
+            unk3: 2,
+            binary_data: Vec::new(),
+            binary_size: 0,
+        }
+    }
+
+    #[test]
+    fn create_shdr() {
+        let data = ShdrData {
+            shaders: vec![ShaderEntryData {
+                binary_data: vec![1, 2, 3],
+                binary_size: 3,
+                ..shader(&[])
+            }],
+        };
+        assert_eq!(
+            Shdr::V12 {
+                shaders: vec![Shader {
+                    name: "a".into(),
+                    shader_stage: ShaderStage::Fragment,
+                    unk3: 2,
+                    shader_binary: vec![1, 2, 3].into(),
+                    binary_size: 3,
+                }]
+                .into()
+            },
+            Shdr::from(&data)
+        );
+    }
+
+    #[test]
+    fn read_write_empty_shdr() {
+        let mut writer = Cursor::new(Vec::new());
+        ShdrData {
+            shaders: Vec::new(),
+        }
+        .write(&mut writer)
+        .unwrap();
+        writer.set_position(0);
+
+        // The inherent methods don't require importing SsbhData.
+        let data = ShdrData::read(&mut writer).unwrap();
+        assert!(data.shaders.is_empty());
+    }
+
+    #[test]
+    fn shader_from_invalid_binary() {
+        assert!(ShaderEntryData::from_binary("a", ShaderStage::Fragment, vec![1, 2, 3]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_binary_data_hex() {
+        let shader = ShaderEntryData {
+            binary_data: vec![0x01, 0xab, 0xff],
+            binary_size: 3,
+            ..shader(&[])
+        };
+        let json = serde_json::to_value(&shader).unwrap();
+        assert_eq!(serde_json::json!("01abff"), json["binary_data"]);
+
+        let new_shader: ShaderEntryData = serde_json::from_value(json).unwrap();
+        assert_eq!(vec![0x01, 0xab, 0xff], new_shader.binary_data);
+    }
+
+    #[test]
+    fn uniform_param_ids() {
+        assert_eq!(Some(ParamId::Texture0), uniform_param_id("Texture0"));
+        assert_eq!(
+            Some(ParamId::CustomBoolean1),
+            uniform_param_id("MC.CustomBoolean1")
+        );
+        assert_eq!(
+            Some(ParamId::CustomVector0),
+            uniform_param_id("CustomVector0[2]")
+        );
+        assert_eq!(
+            Some(ParamId::CustomVector63),
+            uniform_param_id("MC.CustomVector63")
+        );
+        assert_eq!(None, uniform_param_id("CustomVector"));
+        assert_eq!(None, uniform_param_id("CustomVector064"));
+        assert_eq!(None, uniform_param_id(""));
+    }
+
+    #[test]
+    fn shader_param_ids_no_duplicates() {
+        let shader = shader(&[
+            "CustomVector0",
+            "gl_Position",
+            "Texture0",
+            "MC.CustomVector0",
+        ]);
+        assert_eq!(
+            vec![ParamId::CustomVector0, ParamId::Texture0],
+            shader.param_ids()
+        );
+    }
+
+    #[test]
+    fn shader_missing_params() {
+        let shader = shader(&["CustomVector0", "CustomVector47"]);
+        let entry = MatlEntryData {
+            vectors: vec![crate::matl_data::Vector4Param {
+                param_id: ParamId::CustomVector0,
+                data: ssbh_lib::Vector4::default(),
+            }],
+            ..matl_entry_data("a", "b")
+        };
+        assert_eq!(vec![ParamId::CustomVector47], shader.missing_params(&entry));
+    }
+}
@@ -8,7 +8,7 @@
 //! Calculated matrices may differ from the originals due to slightly different algorithms and floating point errors.
 //! These errors are very small in practice but may cause gameplay differences such as online desyncs.
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
 };
 
@@ -21,9 +21,15 @@ use ssbh_lib::{
 
 use thiserror::Error;
 
+use crate::anim_data::{AnimData, GroupType, TrackValues, Transform};
+use crate::context::{ErrorContext, ResultExt};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+pub mod bvh;
+pub mod pose;
+
 // TODO: Add methods to SkelData to find the index of a given bone?
 
 /// The data associated with a [Skel] file.
@@ -55,16 +61,29 @@ pub struct BoneData {
     pub parent_index: Option<usize>,
     // TODO: Make this an Option for clarity?
     pub billboard_type: BillboardType,
-    // TODO: Flags?
+    /// The unknown flags value from [SkelEntryFlags]. This is usually `1`.
+    ///
+    /// The value is preserved when converting to [Skel] since some bones use other values.
+    #[cfg_attr(feature = "serde", serde(default = "default_unk_flags"))]
+    pub unk_flags: u8,
 }
 
 pub mod error {
     use super::*;
     use thiserror::Error;
 
-    /// Errors while creating an [Skel] from [SkelData].
+    /// Errors while converting [Skel] to and from [SkelData].
     #[derive(Debug, Error)]
     pub enum Error {
+        /// Multiple bone entries have the same [index](ssbh_lib::formats::skel::SkelBoneEntry::index),
+        /// so parent indices can't be resolved.
+        #[error("bone index {} is repeated", index)]
+        DuplicateBoneIndex { index: u16 },
+
+        /// The [parent_index](ssbh_lib::formats::skel::SkelBoneEntry::parent_index) does not match the index of any bone entry.
+        #[error("parent index {} does not match the index of any bone", parent_index)]
+        InvalidParentIndex { parent_index: i16 },
+
         /// Creating a [Skel] file for the given version is not supported.
         #[error(
             "creating a version {}.{} skel is not supported",
@@ -170,9 +189,8 @@ impl TryFrom<&SkelData> for Skel {
                         Some(index) => index as i16,
                         None => -1,
                     },
-                    // TODO: Preserve or calculate flags?
                     flags: SkelEntryFlags {
-                        unk1: 1,
+                        unk1: b.unk_flags,
                         billboard_type: b.billboard_type,
                     },
                 })
@@ -196,16 +214,26 @@ impl TryFrom<&SkelData> for Skel {
     }
 }
 
-impl From<Skel> for SkelData {
-    fn from(skel: Skel) -> Self {
-        Self::from(&skel)
+impl TryFrom<Skel> for SkelData {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(skel: Skel) -> Result<Self, Self::Error> {
+        Self::try_from(&skel)
     }
 }
 
-impl From<&Skel> for SkelData {
-    fn from(skel: &Skel) -> Self {
+/// Converting a [Skel] resolves each [parent_index](ssbh_lib::formats::skel::SkelBoneEntry::parent_index)
+/// using the [index](ssbh_lib::formats::skel::SkelBoneEntry::index) of the bone entries.
+/// Duplicate or invalid indices return an error wrapped in a [ContextError](crate::context::ContextError) for the bone.
+///
+/// This conversion previously used [From] and assumed the indices matched the bone order.
+/// Use [SkelData::from_skel_by_position] for the previous infallible conversion.
+impl TryFrom<&Skel> for SkelData {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(skel: &Skel) -> Result<Self, Self::Error> {
         let (major_version, minor_version) = skel.major_minor_version();
-        Self {
+        Ok(Self {
             major_version,
             minor_version,
             // TODO: Add additional validation for mismatched array lengths?
@@ -214,15 +242,61 @@ impl From<&Skel> for SkelData {
                     bone_entries,
                     transforms,
                     ..
-                } => bone_entries
-                    .elements
-                    .iter()
-                    .zip(transforms.elements.iter())
-                    .map(|(b, t)| create_bone_data(b, t))
-                    .collect(),
+                } => create_bones(&bone_entries.elements, &transforms.elements)?,
             },
+        })
+    }
+}
+
+fn create_bones(
+    entries: &[SkelBoneEntry],
+    transforms: &[Matrix4x4],
+) -> Result<Vec<BoneData>, Box<dyn std::error::Error>> {
+    let bone_context = |i: usize, b: &SkelBoneEntry| ErrorContext::Bone {
+        index: i,
+        name: b.name.to_string_lossy(),
+    };
+
+    // Parent indices refer to the index field of the parent entry.
+    // The index usually matches the position in the array,
+    // but some modified files store the entries in a different order.
+    let mut positions = HashMap::new();
+    for (i, b) in entries.iter().enumerate() {
+        if positions.insert(b.index, i).is_some() {
+            return Err(error::Error::DuplicateBoneIndex { index: b.index })
+                .context(|| bone_context(i, b));
         }
     }
+
+    entries
+        .iter()
+        .zip(transforms.iter())
+        .enumerate()
+        .map(|(i, (b, t))| {
+            // Treat all negative indices as no parent.
+            let parent_index = match u16::try_from(b.parent_index) {
+                Ok(index) => Some(
+                    *positions
+                        .get(&index)
+                        .ok_or(error::Error::InvalidParentIndex {
+                            parent_index: b.parent_index,
+                        })
+                        .context(|| bone_context(i, b))?,
+                ),
+                Err(_) => None,
+            };
+
+            Ok(BoneData {
+                parent_index,
+                ..create_bone_data(b, t)
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "serde")]
+fn default_unk_flags() -> u8 {
+    1
 }
 
 fn create_bone_data(b: &SkelBoneEntry, transform: &Matrix4x4) -> BoneData {
@@ -231,10 +305,37 @@ fn create_bone_data(b: &SkelBoneEntry, transform: &Matrix4x4) -> BoneData {
         transform: transform.to_cols_array(),
         parent_index: b.parent_index.try_into().ok(),
         billboard_type: b.flags.billboard_type,
+        unk_flags: b.flags.unk1,
     }
 }
 
 impl SkelData {
+    /// Converts `skel` while treating each [parent_index](ssbh_lib::formats::skel::SkelBoneEntry::parent_index)
+    /// as the position of the parent in the bone entries.
+    ///
+    /// Unlike [SkelData::try_from], this never fails.
+    /// Parent indices are not validated, so files with reordered or duplicate bone indices
+    /// may produce an incorrect hierarchy.
+    pub fn from_skel_by_position(skel: &Skel) -> Self {
+        let (major_version, minor_version) = skel.major_minor_version();
+        Self {
+            major_version,
+            minor_version,
+            bones: match skel {
+                Skel::V10 {
+                    bone_entries,
+                    transforms,
+                    ..
+                } => bone_entries
+                    .elements
+                    .iter()
+                    .zip(transforms.elements.iter())
+                    .map(|(b, t)| create_bone_data(b, t))
+                    .collect(),
+            },
+        }
+    }
+
     /// Calculates the world transform for `bone` by accumulating the transform with the parents transform recursively.
     /// Returns the resulting matrix in column-major order.
     /// # Examples
@@ -251,6 +352,7 @@ impl SkelData {
     #         transform: [[0f32; 4]; 4],
     #         parent_index: None,
     #         billboard_type: BillboardType::Disabled,
+    #         unk_flags: 1,
     #     }],
     # };
     let parent_bone_name = "Head";
@@ -289,6 +391,209 @@ impl SkelData {
         // Save the result in column-major order.
         Ok(transform.to_cols_array_2d())
     }
+
+    /// Calculates the world transform for each bone in column-major order for `frame` of `anim`.
+    ///
+    /// Bones use the first [TrackValues::Transform] track for the node with the same name
+    /// in a [GroupType::Transform] group. Bones without a track use their rest pose [transform](BoneData::transform).
+    /// Constant tracks and tracks with fewer frames use their last frame.
+    /// The [compensate_scale](crate::anim_data::TrackData::compensate_scale) setting is not applied.
+    /**
+    ```rust
+    # use ssbh_data::anim_data::AnimData;
+    # use ssbh_data::skel_data::SkelData;
+    # let skel = SkelData { major_version: 1, minor_version: 0, bones: Vec::new() };
+    # let anim = AnimData { major_version: 2, minor_version: 0, final_frame_index: 0.0, groups: Vec::new() };
+    let world_transforms = skel.animated_world_transforms(&anim, 0)?;
+    assert_eq!(skel.bones.len(), world_transforms.len());
+    # Ok::<(), ssbh_data::skel_data::BoneTransformError>(())
+    ```
+     */
+    pub fn animated_world_transforms(
+        &self,
+        anim: &AnimData,
+        frame: usize,
+    ) -> Result<Vec<[[f32; 4]; 4]>, BoneTransformError> {
+        let transforms: Vec<_> = self
+            .bones
+            .iter()
+            .map(|b| match animated_transform(anim, &b.name, frame) {
+                Some(t) => Mat4::from_scale_rotation_translation(
+                    t.scale.to_array().into(),
+                    glam::Quat::from_array(t.rotation.to_array()),
+                    t.translation.to_array().into(),
+                ),
+                None => Mat4::from_cols_array_2d(&b.transform),
+            })
+            .collect();
+
+        (0..self.bones.len())
+            .map(|i| {
+                let mut transform = transforms[i];
+                let mut visited = HashSet::new();
+                let mut bone = &self.bones[i];
+
+                // Accumulate transforms by travelling up the bone hierarchy.
+                while let Some(parent_index) = bone.parent_index {
+                    if !visited.insert(parent_index) {
+                        return Err(BoneTransformError::CycleDetected {
+                            index: parent_index,
+                        });
+                    }
+                    match self.bones.get(parent_index) {
+                        Some(parent_bone) => {
+                            transform = transforms[parent_index] * transform;
+                            bone = parent_bone;
+                        }
+                        None => break,
+                    }
+                }
+
+                Ok(transform.to_cols_array_2d())
+            })
+            .collect()
+    }
+}
+
+fn animated_transform(anim: &AnimData, name: &str, frame: usize) -> Option<Transform> {
+    anim.groups
+        .iter()
+        .filter(|g| g.group_type == GroupType::Transform)
+        .flat_map(|g| g.nodes.iter())
+        .filter(|n| n.name == name)
+        .flat_map(|n| n.tracks.iter())
+        .find_map(|t| match &t.values {
+            TrackValues::Transform(values) => values.get(frame).or(values.last()).copied(),
+            _ => None,
+        })
+}
+
+/// The pair of matrices in a [Skel] containing an invalid inverse.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InverseTransformKind {
+    /// The matrix in `inv_world_transforms` does not invert the matrix in `world_transforms`.
+    World,
+    /// The matrix in `inv_transforms` does not invert the matrix in `transforms`.
+    Relative,
+}
+
+/// A bone with an inverse matrix that does not match its corresponding transform.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InverseTransformMismatch {
+    /// The index of the bone in `bone_entries`.
+    pub index: usize,
+    /// The name of the bone.
+    pub name: String,
+    pub kind: InverseTransformKind,
+    /// The largest absolute difference between the product of the matrices and the identity matrix
+    /// or [f32::INFINITY] if the inverse matrix is missing.
+    pub max_error: f32,
+}
+
+/// Checks that the inverse matrices for each bone in `skel` invert the corresponding transforms within `epsilon`.
+/// Files edited by hand often contain inverse matrices that no longer match,
+/// resulting in distorted vertex skinning in game.
+/// See [repair_inverse_transforms] for recalculating the inverse matrices.
+/**
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::skel_data::{repair_inverse_transforms, validate_inverse_transforms};
+use ssbh_lib::formats::skel::Skel;
+
+let mut skel = Skel::from_file("model.nusktb")?;
+if !validate_inverse_transforms(&skel, 1e-4).is_empty() {
+    repair_inverse_transforms(&mut skel);
+    skel.write_to_file("model.nusktb")?;
+}
+# Ok(()) }
+```
+ */
+pub fn validate_inverse_transforms(skel: &Skel, epsilon: f32) -> Vec<InverseTransformMismatch> {
+    match skel {
+        Skel::V10 {
+            bone_entries,
+            world_transforms,
+            inv_world_transforms,
+            transforms,
+            inv_transforms,
+        } => {
+            let mut mismatches = Vec::new();
+            for (index, bone) in bone_entries.elements.iter().enumerate() {
+                for (kind, matrices, inverses) in [
+                    (
+                        InverseTransformKind::World,
+                        world_transforms,
+                        inv_world_transforms,
+                    ),
+                    (InverseTransformKind::Relative, transforms, inv_transforms),
+                ] {
+                    // Bones without a transform can't be checked.
+                    if let Some(m) = matrices.elements.get(index) {
+                        let max_error = inverses
+                            .elements
+                            .get(index)
+                            .map(|inv| inverse_error(m, inv))
+                            .unwrap_or(f32::INFINITY);
+
+                        // Also catch NaN from invalid matrices.
+                        if max_error.is_nan() || max_error > epsilon {
+                            mismatches.push(InverseTransformMismatch {
+                                index,
+                                name: bone.name.to_string_lossy(),
+                                kind,
+                                max_error,
+                            });
+                        }
+                    }
+                }
+            }
+            mismatches
+        }
+    }
+}
+
+/// Recalculates `inv_world_transforms` and `inv_transforms` in `skel`
+/// from `world_transforms` and `transforms`.
+/// All other matrices are left unchanged.
+pub fn repair_inverse_transforms(skel: &mut Skel) {
+    match skel {
+        Skel::V10 {
+            world_transforms,
+            inv_world_transforms,
+            transforms,
+            inv_transforms,
+            ..
+        } => {
+            *inv_world_transforms = world_transforms
+                .elements
+                .iter()
+                .map(|m| inv_transform(&m.to_cols_array()))
+                .collect();
+            *inv_transforms = transforms
+                .elements
+                .iter()
+                .map(|m| inv_transform(&m.to_cols_array()))
+                .collect();
+        }
+    }
+}
+
+fn inverse_error(m: &Matrix4x4, inv: &Matrix4x4) -> f32 {
+    let product = Mat4::from_cols_array_2d(&m.to_cols_array())
+        * Mat4::from_cols_array_2d(&inv.to_cols_array());
+    product
+        .to_cols_array()
+        .iter()
+        .zip(Mat4::IDENTITY.to_cols_array())
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, |max, e| {
+            // Propagate NaN instead of ignoring it like f32::max.
+            if max.is_nan() || e.is_nan() {
+                f32::NAN
+            } else {
+                max.max(e)
+            }
+        })
 }
 
 /// Errors while calculating [BoneData] transformation matrices.
@@ -304,6 +609,7 @@ pub enum BoneTransformError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::context::ContextError;
 
     macro_rules! assert_matrix_relative_eq {
         ($a:expr, $b:expr) => {
@@ -345,6 +651,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn animated_world_transforms() {
+        let bone = |name: &str, parent_index| BoneData {
+            name: name.to_owned(),
+            transform: Mat4::from_translation(glam::vec3(0.0, 1.0, 0.0)).to_cols_array_2d(),
+            parent_index,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        };
+        let data = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![bone("A", None), bone("B", Some(0))],
+        };
+
+        let transform = |x| crate::anim_data::Transform {
+            translation: ssbh_lib::Vector3::new(x, 0.0, 0.0),
+            ..crate::anim_data::Transform::IDENTITY
+        };
+        let anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 2.0,
+            groups: vec![crate::anim_data::GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![crate::anim_data::NodeData {
+                    name: "A".to_owned(),
+                    tracks: vec![crate::anim_data::TrackData {
+                        name: "Transform".to_owned(),
+                        compensate_scale: false,
+                        transform_flags: Default::default(),
+                        values: TrackValues::Transform(vec![transform(1.0), transform(2.0)]),
+                    }],
+                }],
+            }],
+        };
+
+        // B isn't animated and uses its rest pose relative to A.
+        let world_transforms = data.animated_world_transforms(&anim, 1).unwrap();
+        assert_eq!(
+            Mat4::from_translation(glam::vec3(2.0, 0.0, 0.0)).to_cols_array_2d(),
+            world_transforms[0]
+        );
+        assert_eq!(
+            Mat4::from_translation(glam::vec3(2.0, 1.0, 0.0)).to_cols_array_2d(),
+            world_transforms[1]
+        );
+
+        // Frames past the end of the track use the last frame.
+        assert_eq!(
+            world_transforms,
+            data.animated_world_transforms(&anim, 5).unwrap()
+        );
+    }
+
     #[test]
     fn create_skel_mario_three_bone_chain() {
         // The first three bones of /fighter/mario/model/body/c00/model.nusktb.
@@ -363,6 +724,7 @@ mod tests {
                     ],
                     parent_index: None,
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "Rot".to_owned(),
@@ -374,6 +736,7 @@ mod tests {
                     ],
                     parent_index: Some(0),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "Hip".to_owned(),
@@ -385,6 +748,7 @@ mod tests {
                     ],
                     parent_index: Some(1),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
             ],
         };
@@ -566,7 +930,8 @@ mod tests {
                     [0.0, 0.0, 0.0, 1.0]
                 ],
                 parent_index: None,
-                billboard_type: BillboardType::XYAxisViewPointAligned
+                billboard_type: BillboardType::XYAxisViewPointAligned,
+                unk_flags: 1,
             },
             create_bone_data(&b, &Matrix4x4::identity())
         );
@@ -596,12 +961,45 @@ mod tests {
                     [0.0, 0.0, 0.0, 1.0]
                 ],
                 parent_index: None,
-                billboard_type: BillboardType::Disabled
+                billboard_type: BillboardType::Disabled,
+                unk_flags: 1,
             },
             create_bone_data(&b, &Matrix4x4::identity())
         );
     }
 
+    #[test]
+    fn preserve_unk_flags() {
+        let data = SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![BoneData {
+                name: "a".to_owned(),
+                transform: [
+                    [1.0, 0.0, 0.0, 0.0],
+                    [0.0, 1.0, 0.0, 0.0],
+                    [0.0, 0.0, 1.0, 0.0],
+                    [0.0, 0.0, 0.0, 1.0],
+                ],
+                parent_index: None,
+                billboard_type: BillboardType::YAxisViewPlaneAligned,
+                unk_flags: 0,
+            }],
+        };
+
+        let skel = Skel::try_from(&data).unwrap();
+        match &skel {
+            Skel::V10 { bone_entries, .. } => assert_eq!(
+                SkelEntryFlags {
+                    unk1: 0,
+                    billboard_type: BillboardType::YAxisViewPlaneAligned
+                },
+                bone_entries.elements[0].flags
+            ),
+        }
+        assert_eq!(data, SkelData::try_from(&skel).unwrap());
+    }
+
     #[test]
     fn calculate_relative_transform_with_parent() {
         let world_transform = [
@@ -660,6 +1058,7 @@ mod tests {
                 transform,
                 parent_index: None,
                 billboard_type: BillboardType::Disabled,
+                unk_flags: 1,
             }],
         };
 
@@ -679,6 +1078,7 @@ mod tests {
                 transform: [[0.0; 4]; 4],
                 parent_index: Some(0),
                 billboard_type: BillboardType::Disabled,
+                unk_flags: 1,
             }],
         };
 
@@ -701,24 +1101,28 @@ mod tests {
                     transform: [[0.0; 4]; 4],
                     parent_index: None,
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "b".to_owned(),
                     transform: [[0.0; 4]; 4],
                     parent_index: Some(2),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "c".to_owned(),
                     transform: [[0.0; 4]; 4],
                     parent_index: Some(1),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "d".to_owned(),
                     transform: [[0.0; 4]; 4],
                     parent_index: Some(2),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
             ],
         };
@@ -748,6 +1152,7 @@ mod tests {
                     ],
                     parent_index: None,
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "Rot".to_owned(),
@@ -759,6 +1164,7 @@ mod tests {
                     ],
                     parent_index: Some(0),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "Hip".to_owned(),
@@ -770,6 +1176,7 @@ mod tests {
                     ],
                     parent_index: Some(1),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
                 BoneData {
                     name: "Waist".to_owned(),
@@ -781,6 +1188,7 @@ mod tests {
                     ],
                     parent_index: Some(2),
                     billboard_type: BillboardType::Disabled,
+                    unk_flags: 1,
                 },
             ],
         };
@@ -795,4 +1203,164 @@ mod tests {
             data.calculate_world_transform(&data.bones[3]).unwrap()
         );
     }
+
+    fn two_bone_skel() -> Skel {
+        let bone = |name: &str, parent_index| BoneData {
+            name: name.to_string(),
+            transform: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [1.0, 2.0, 3.0, 1.0],
+            ],
+            parent_index,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        };
+        Skel::try_from(SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![bone("A", None), bone("B", Some(0))],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn validate_inverse_transforms_valid() {
+        assert!(validate_inverse_transforms(&two_bone_skel(), 1e-4).is_empty());
+    }
+
+    #[test]
+    fn validate_repair_inverse_transforms() {
+        let mut skel = two_bone_skel();
+        match &mut skel {
+            Skel::V10 {
+                inv_world_transforms,
+                inv_transforms,
+                ..
+            } => {
+                inv_world_transforms.elements[1] = inv_world_transforms.elements[0];
+                inv_transforms.elements.pop();
+            }
+        }
+
+        let mismatches = validate_inverse_transforms(&skel, 1e-4);
+        assert_eq!(2, mismatches.len());
+        assert_eq!(1, mismatches[0].index);
+        assert_eq!("B", mismatches[0].name);
+        assert_eq!(InverseTransformKind::World, mismatches[0].kind);
+        assert!(mismatches[0].max_error > 1e-4);
+        assert_eq!(
+            InverseTransformMismatch {
+                index: 1,
+                name: "B".to_string(),
+                kind: InverseTransformKind::Relative,
+                max_error: f32::INFINITY
+            },
+            mismatches[1]
+        );
+
+        repair_inverse_transforms(&mut skel);
+        assert!(validate_inverse_transforms(&skel, 1e-4).is_empty());
+        assert_eq!(two_bone_skel(), skel);
+    }
+
+    #[test]
+    fn convert_skel_preserves_bone_order() {
+        let data = SkelData::try_from(two_bone_skel()).unwrap();
+        assert_eq!(
+            vec!["A", "B"],
+            data.bones
+                .iter()
+                .map(|b| b.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(two_bone_skel(), Skel::try_from(&data).unwrap());
+    }
+
+    fn skel_from_entries(entries: &[(&str, u16, i16)]) -> Skel {
+        let identity = || vec![Matrix4x4::identity(); entries.len()].into();
+        Skel::V10 {
+            bone_entries: entries
+                .iter()
+                .map(|(name, index, parent_index)| SkelBoneEntry {
+                    name: (*name).into(),
+                    index: *index,
+                    parent_index: *parent_index,
+                    flags: SkelEntryFlags {
+                        unk1: 1,
+                        billboard_type: BillboardType::Disabled,
+                    },
+                })
+                .collect(),
+            world_transforms: identity(),
+            inv_world_transforms: identity(),
+            transforms: identity(),
+            inv_transforms: identity(),
+        }
+    }
+
+    #[test]
+    fn convert_skel_remap_parent_indices() {
+        // The parent indices refer to the index field instead of the array position.
+        let skel = skel_from_entries(&[("Hip", 5, 3), ("Trans", 2, -1), ("Rot", 3, 2)]);
+        let data = SkelData::try_from(&skel).unwrap();
+        assert_eq!(
+            vec![("Hip", Some(2)), ("Trans", None), ("Rot", Some(1))],
+            data.bones
+                .iter()
+                .map(|b| (b.name.as_str(), b.parent_index))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn convert_skel_by_position() {
+        // Parent indices are used as is even if they don't match the index field.
+        let skel = skel_from_entries(&[("A", 0, -1), ("B", 0, 0), ("C", 2, 5)]);
+        let data = SkelData::from_skel_by_position(&skel);
+        assert_eq!(
+            vec![("A", None), ("B", Some(0)), ("C", Some(5))],
+            data.bones
+                .iter()
+                .map(|b| (b.name.as_str(), b.parent_index))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn convert_skel_duplicate_index() {
+        let skel = skel_from_entries(&[("A", 0, -1), ("B", 0, 0)]);
+        let e = SkelData::try_from(&skel).unwrap_err();
+        let e = e.downcast_ref::<ContextError>().unwrap();
+        assert_eq!(
+            &ErrorContext::Bone {
+                index: 1,
+                name: "B".to_string()
+            },
+            e.contexts()[0]
+        );
+        assert!(matches!(
+            e.root_cause().downcast_ref::<error::Error>(),
+            Some(error::Error::DuplicateBoneIndex { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn convert_skel_invalid_parent_index() {
+        let skel = skel_from_entries(&[("A", 0, -1), ("B", 1, 2)]);
+        let e = SkelData::try_from(&skel).unwrap_err();
+        let e = e.downcast_ref::<ContextError>().unwrap();
+        assert_eq!(
+            &ErrorContext::Bone {
+                index: 1,
+                name: "B".to_string()
+            },
+            e.contexts()[0]
+        );
+        assert!(matches!(
+            e.root_cause().downcast_ref::<error::Error>(),
+            Some(error::Error::InvalidParentIndex { parent_index: 2 })
+        ));
+    }
 }
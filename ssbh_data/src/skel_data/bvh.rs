@@ -0,0 +1,734 @@
+//! Conversions between [SkelData] with [AnimData] and Biovision Hierarchy (BVH) files.
+//!
+//! BVH files store a joint hierarchy with per joint offsets and a table of channel values for each frame.
+//! This makes BVH a common interchange format for motion capture and animation tools.
+//!
+//! # Limitations
+//! BVH does not store rest pose rotations or scale.
+//! Bones read from BVH use the joint offset as the translation with no rotation or scale.
+//! Exported animations store the full rotation for each frame, so the posed skeleton is preserved.
+//! Scale values from the [AnimData] are not exported.
+//!
+//! Each joint is written with 6 channels using the order
+//! `Xposition Yposition Zposition Zrotation Xrotation Yrotation`.
+//! Position channels store the translation relative to the joint's offset.
+//! Reading supports any combination and order of position and rotation channels.
+use std::io::{Read, Write};
+
+use glam::{EulerRot, Mat4, Quat, Vec3};
+use ssbh_lib::{Vector3, Vector4};
+
+use crate::anim_data::{
+    AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, Transform, TransformFlags,
+};
+
+use super::{BillboardType, BoneData, SkelData};
+
+/// The frame time in seconds written to BVH files.
+/// Smash Ultimate animations play at 60 frames per second.
+pub const FRAME_TIME: f32 = 1.0 / 60.0;
+
+pub mod error {
+    use thiserror::Error;
+
+    /// Errors while reading or writing BVH data.
+    #[derive(Debug, Error)]
+    pub enum Error {
+        /// An error occurred while reading or writing the BVH text.
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+
+        /// The bone hierarchy contains a cycle and can't be written as a tree.
+        #[error(
+            "cyclical bone chains are not supported. A cycle was detected at index {}",
+            index
+        )]
+        CycleDetected { index: usize },
+
+        /// The BVH text ended before the hierarchy or motion data was complete.
+        #[error("unexpected end of file")]
+        UnexpectedEof,
+
+        /// The BVH text contains an unexpected keyword or symbol.
+        #[error("expected {} but found {}", expected, found)]
+        UnexpectedToken { expected: String, found: String },
+
+        /// The BVH text contains a value that is not a valid number.
+        #[error("failed to parse {} as a number", value)]
+        InvalidNumber { value: String },
+
+        /// The channel name is not a supported position or rotation channel.
+        #[error("unsupported channel {}", name)]
+        UnsupportedChannel { name: String },
+
+        /// The motion data contains more frames than the frame count in the header.
+        #[error("expected {} frames but found {}", expected, found)]
+        FrameCountMismatch { expected: usize, found: usize },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Channel {
+    XPosition,
+    YPosition,
+    ZPosition,
+    XRotation,
+    YRotation,
+    ZRotation,
+}
+
+impl Channel {
+    fn from_name(name: &str) -> Result<Self, error::Error> {
+        match name {
+            "Xposition" => Ok(Self::XPosition),
+            "Yposition" => Ok(Self::YPosition),
+            "Zposition" => Ok(Self::ZPosition),
+            "Xrotation" => Ok(Self::XRotation),
+            "Yrotation" => Ok(Self::YRotation),
+            "Zrotation" => Ok(Self::ZRotation),
+            _ => Err(error::Error::UnsupportedChannel {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Joint {
+    name: String,
+    parent_index: Option<usize>,
+    offset: Vec3,
+    channels: Vec<Channel>,
+}
+
+/// Writes the bone hierarchy of `skel` and the transforms in `anim` as BVH text.
+///
+/// Bones without a corresponding node in a [GroupType::Transform] group use their rest pose values.
+/// If `anim` is [None], a single frame with the rest pose is written.
+/**
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_data::prelude::*;
+use ssbh_data::skel_data::bvh::write_bvh;
+
+let skel = SkelData::from_file("model.nusktb")?;
+let anim = AnimData::from_file("a00wait1.nuanmb")?;
+
+let mut writer = std::io::BufWriter::new(std::fs::File::create("a00wait1.bvh")?);
+write_bvh(&mut writer, &skel, Some(&anim))?;
+# Ok(()) }
+```
+*/
+pub fn write_bvh<W: Write>(
+    writer: &mut W,
+    skel: &SkelData,
+    anim: Option<&AnimData>,
+) -> Result<(), error::Error> {
+    // BVH requires each joint to be written after its parent.
+    let order = hierarchy_order(skel)?;
+    let rest_transforms: Vec<_> = skel
+        .bones
+        .iter()
+        .map(|b| Mat4::from_cols_array_2d(&b.transform).to_scale_rotation_translation())
+        .collect();
+
+    writeln!(writer, "HIERARCHY")?;
+    for root in order.iter().filter(|(_, depth)| *depth == 0) {
+        write_joint(writer, skel, &rest_transforms, root.0, 0)?;
+    }
+
+    let frame_count = anim
+        .map(|a| a.final_frame_index.max(0.0).floor() as usize + 1)
+        .unwrap_or(1);
+
+    let tracks: Vec<_> = skel
+        .bones
+        .iter()
+        .map(|b| anim.and_then(|a| find_transform_values(a, &b.name)))
+        .collect();
+
+    writeln!(writer, "MOTION")?;
+    writeln!(writer, "Frames: {frame_count}")?;
+    writeln!(writer, "Frame Time: {FRAME_TIME}")?;
+    for frame in 0..frame_count {
+        let values: Vec<_> = order
+            .iter()
+            .flat_map(|(i, _)| {
+                let (_, rest_rotation, offset) = rest_transforms[*i];
+                let (rotation, translation) = tracks[*i]
+                    .and_then(|values| values.get(frame).or_else(|| values.last()))
+                    .map(|t| {
+                        (
+                            Quat::from_xyzw(t.rotation.x, t.rotation.y, t.rotation.z, t.rotation.w),
+                            Vec3::new(t.translation.x, t.translation.y, t.translation.z),
+                        )
+                    })
+                    .unwrap_or((rest_rotation, offset));
+
+                let position = translation - offset;
+                let (z, x, y) = rotation.normalize().to_euler(EulerRot::ZXY);
+                [
+                    position.x,
+                    position.y,
+                    position.z,
+                    z.to_degrees(),
+                    x.to_degrees(),
+                    y.to_degrees(),
+                ]
+            })
+            // Adding zero avoids writing negative zero.
+            .map(|v| (v + 0.0).to_string())
+            .collect();
+        writeln!(writer, "{}", values.join(" "))?;
+    }
+
+    Ok(())
+}
+
+fn find_transform_values<'a>(anim: &'a AnimData, name: &str) -> Option<&'a [Transform]> {
+    anim.groups
+        .iter()
+        .filter(|g| g.group_type == GroupType::Transform)
+        .flat_map(|g| g.nodes.iter())
+        .filter(|n| n.name == name)
+        .flat_map(|n| n.tracks.iter())
+        .find_map(|t| match &t.values {
+            TrackValues::Transform(values) if !values.is_empty() => Some(values.as_slice()),
+            _ => None,
+        })
+}
+
+fn children(skel: &SkelData, parent_index: Option<usize>) -> impl Iterator<Item = usize> + '_ {
+    skel.bones.iter().enumerate().filter_map(move |(i, b)| {
+        // Treat bones with invalid parents as roots.
+        let parent = b.parent_index.filter(|p| *p < skel.bones.len());
+        (parent == parent_index).then_some(i)
+    })
+}
+
+fn hierarchy_order(skel: &SkelData) -> Result<Vec<(usize, usize)>, error::Error> {
+    // Find the bone indices and depths in depth first order.
+    let mut order = Vec::new();
+    let mut stack: Vec<_> = children(skel, None).map(|i| (i, 0)).collect();
+    stack.reverse();
+
+    while let Some((i, depth)) = stack.pop() {
+        order.push((i, depth));
+        let start = stack.len();
+        stack.extend(children(skel, Some(i)).map(|c| (c, depth + 1)));
+        stack[start..].reverse();
+    }
+
+    // Bones in a cycle are never reachable from a root.
+    match (0..skel.bones.len()).find(|i| !order.iter().any(|(o, _)| o == i)) {
+        Some(index) => Err(error::Error::CycleDetected { index }),
+        None => Ok(order),
+    }
+}
+
+fn write_joint<W: Write>(
+    writer: &mut W,
+    skel: &SkelData,
+    rest_transforms: &[(Vec3, Quat, Vec3)],
+    index: usize,
+    depth: usize,
+) -> Result<(), error::Error> {
+    let indent = "\t".repeat(depth);
+    let keyword = if depth == 0 { "ROOT" } else { "JOINT" };
+    let offset = rest_transforms[index].2;
+
+    writeln!(writer, "{indent}{keyword} {}", skel.bones[index].name)?;
+    writeln!(writer, "{indent}{{")?;
+    writeln!(
+        writer,
+        "{indent}\tOFFSET {} {} {}",
+        offset.x, offset.y, offset.z
+    )?;
+    writeln!(
+        writer,
+        "{indent}\tCHANNELS 6 Xposition Yposition Zposition Zrotation Xrotation Yrotation"
+    )?;
+
+    let mut has_children = false;
+    for child in children(skel, Some(index)) {
+        write_joint(writer, skel, rest_transforms, child, depth + 1)?;
+        has_children = true;
+    }
+
+    // Some applications expect leaf joints to define an end site.
+    if !has_children {
+        writeln!(writer, "{indent}\tEnd Site")?;
+        writeln!(writer, "{indent}\t{{")?;
+        writeln!(writer, "{indent}\t\tOFFSET 0 0 0")?;
+        writeln!(writer, "{indent}\t}}")?;
+    }
+
+    writeln!(writer, "{indent}}}")?;
+    Ok(())
+}
+
+/// Reads the joint hierarchy and motion from BVH text.
+///
+/// The [AnimData] contains a single [GroupType::Transform] group with a node for each bone.
+/// Bones are ordered so that parents always appear before their children.
+pub fn read_bvh<R: Read>(reader: &mut R) -> Result<(SkelData, AnimData), error::Error> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut tokens = Tokens(text.split_whitespace());
+
+    tokens.expect("HIERARCHY")?;
+
+    let mut joints = Vec::new();
+    let mut token = tokens.next()?;
+    while token == "ROOT" {
+        read_joint(&mut tokens, None, &mut joints)?;
+        token = tokens.next()?;
+    }
+    if token != "MOTION" {
+        return Err(error::Error::UnexpectedToken {
+            expected: "MOTION".to_string(),
+            found: token.to_string(),
+        });
+    }
+
+    tokens.expect("Frames:")?;
+    let frame_count: usize = tokens.number()?;
+    tokens.expect("Frame")?;
+    tokens.expect("Time:")?;
+    let _frame_time: f32 = tokens.number()?;
+
+    // Don't trust the header for allocations since the frame count may be invalid.
+    // Frames without channels don't consume any tokens, so only the first frame is read.
+    let channel_count: usize = joints.iter().map(|j| j.channels.len()).sum();
+    let read_frame_count = if channel_count == 0 {
+        frame_count.min(1)
+    } else {
+        frame_count
+    };
+
+    let mut values = vec![Vec::new(); joints.len()];
+    for _ in 0..read_frame_count {
+        for (joint, joint_values) in joints.iter().zip(values.iter_mut()) {
+            let mut translation = joint.offset;
+            let mut rotation = Quat::IDENTITY;
+            for channel in &joint.channels {
+                let value: f32 = tokens.number()?;
+                match channel {
+                    Channel::XPosition => translation.x += value,
+                    Channel::YPosition => translation.y += value,
+                    Channel::ZPosition => translation.z += value,
+                    // Rotations are applied in the order the channels are listed.
+                    Channel::XRotation => rotation *= Quat::from_rotation_x(value.to_radians()),
+                    Channel::YRotation => rotation *= Quat::from_rotation_y(value.to_radians()),
+                    Channel::ZRotation => rotation *= Quat::from_rotation_z(value.to_radians()),
+                }
+            }
+
+            joint_values.push(Transform {
+                scale: Vector3::new(1.0, 1.0, 1.0),
+                rotation: Vector4::new(rotation.x, rotation.y, rotation.z, rotation.w),
+                translation: Vector3::new(translation.x, translation.y, translation.z),
+            });
+        }
+    }
+
+    let remaining = tokens.0.count();
+    if remaining > 0 {
+        return Err(error::Error::FrameCountMismatch {
+            expected: frame_count,
+            found: frame_count + remaining.div_ceil(channel_count.max(1)),
+        });
+    }
+
+    let skel = SkelData {
+        major_version: 1,
+        minor_version: 0,
+        bones: joints
+            .iter()
+            .map(|j| BoneData {
+                name: j.name.clone(),
+                transform: Mat4::from_translation(j.offset).to_cols_array_2d(),
+                parent_index: j.parent_index,
+                billboard_type: BillboardType::Disabled,
+                unk_flags: 1,
+            })
+            .collect(),
+    };
+
+    let anim = AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index: frame_count.saturating_sub(1) as f32,
+        groups: vec![GroupData {
+            group_type: GroupType::Transform,
+            nodes: joints
+                .into_iter()
+                .zip(values)
+                .map(|(j, values)| NodeData {
+                    name: j.name,
+                    tracks: vec![TrackData {
+                        name: "Transform".to_string(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values: TrackValues::Transform(values),
+                    }],
+                })
+                .collect(),
+        }],
+    };
+
+    Ok((skel, anim))
+}
+
+struct Tokens<'a>(std::str::SplitWhitespace<'a>);
+
+impl<'a> Tokens<'a> {
+    fn next(&mut self) -> Result<&'a str, error::Error> {
+        self.0.next().ok_or(error::Error::UnexpectedEof)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), error::Error> {
+        let found = self.next()?;
+        if found == expected {
+            Ok(())
+        } else {
+            Err(error::Error::UnexpectedToken {
+                expected: expected.to_string(),
+                found: found.to_string(),
+            })
+        }
+    }
+
+    fn number<T: std::str::FromStr>(&mut self) -> Result<T, error::Error> {
+        let value = self.next()?;
+        value.parse().map_err(|_| error::Error::InvalidNumber {
+            value: value.to_string(),
+        })
+    }
+
+    fn vec3(&mut self) -> Result<Vec3, error::Error> {
+        Ok(Vec3::new(self.number()?, self.number()?, self.number()?))
+    }
+}
+
+fn read_joint(
+    tokens: &mut Tokens,
+    parent_index: Option<usize>,
+    joints: &mut Vec<Joint>,
+) -> Result<(), error::Error> {
+    let name = tokens.next()?.to_string();
+    tokens.expect("{")?;
+    tokens.expect("OFFSET")?;
+    let offset = tokens.vec3()?;
+
+    let index = joints.len();
+    joints.push(Joint {
+        name,
+        parent_index,
+        offset,
+        channels: Vec::new(),
+    });
+
+    loop {
+        match tokens.next()? {
+            "CHANNELS" => {
+                let count: usize = tokens.number()?;
+                joints[index].channels = (0..count)
+                    .map(|_| Channel::from_name(tokens.next()?))
+                    .collect::<Result<_, _>>()?;
+            }
+            "JOINT" => read_joint(tokens, Some(index), joints)?,
+            "End" => {
+                // End sites only define the length of the final bone.
+                tokens.expect("Site")?;
+                tokens.expect("{")?;
+                tokens.expect("OFFSET")?;
+                tokens.vec3()?;
+                tokens.expect("}")?;
+            }
+            "}" => return Ok(()),
+            found => {
+                return Err(error::Error::UnexpectedToken {
+                    expected: "CHANNELS, JOINT, End, or }".to_string(),
+                    found: found.to_string(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bone(name: &str, translation: [f32; 3], parent_index: Option<usize>) -> BoneData {
+        BoneData {
+            name: name.to_string(),
+            transform: Mat4::from_translation(translation.into()).to_cols_array_2d(),
+            parent_index,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        }
+    }
+
+    fn skel(bones: Vec<BoneData>) -> SkelData {
+        SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones,
+        }
+    }
+
+    fn write_string(skel: &SkelData, anim: Option<&AnimData>) -> String {
+        let mut output = Vec::new();
+        write_bvh(&mut output, skel, anim).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn write_bvh_rest_pose() {
+        let skel = skel(vec![
+            bone("Trans", [0.0, 0.0, 0.0], None),
+            bone("Hip", [0.0, 2.0, 0.0], Some(0)),
+            bone("Rot", [1.0, 0.0, 0.0], Some(0)),
+        ]);
+
+        pretty_assertions::assert_eq!(
+            "HIERARCHY
+ROOT Trans
+{
+\tOFFSET 0 0 0
+\tCHANNELS 6 Xposition Yposition Zposition Zrotation Xrotation Yrotation
+\tJOINT Hip
+\t{
+\t\tOFFSET 0 2 0
+\t\tCHANNELS 6 Xposition Yposition Zposition Zrotation Xrotation Yrotation
+\t\tEnd Site
+\t\t{
+\t\t\tOFFSET 0 0 0
+\t\t}
+\t}
+\tJOINT Rot
+\t{
+\t\tOFFSET 1 0 0
+\t\tCHANNELS 6 Xposition Yposition Zposition Zrotation Xrotation Yrotation
+\t\tEnd Site
+\t\t{
+\t\t\tOFFSET 0 0 0
+\t\t}
+\t}
+}
+MOTION
+Frames: 1
+Frame Time: 0.016666668
+0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+",
+            write_string(&skel, None)
+        );
+    }
+
+    #[test]
+    fn write_bvh_cycle() {
+        let skel = skel(vec![
+            bone("A", [0.0; 3], None),
+            bone("B", [0.0; 3], Some(2)),
+            bone("C", [0.0; 3], Some(1)),
+        ]);
+
+        let mut output = Vec::new();
+        assert!(matches!(
+            write_bvh(&mut output, &skel, None),
+            Err(error::Error::CycleDetected { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn read_bvh_channel_order() {
+        let text = "HIERARCHY
+ROOT Hips
+{
+    OFFSET 1 2 3
+    CHANNELS 6 Xposition Yposition Zposition Zrotation Xrotation Yrotation
+    JOINT Spine
+    {
+        OFFSET 0 5 0
+        CHANNELS 3 Xrotation Yrotation Zrotation
+        End Site
+        {
+            OFFSET 0 1 0
+        }
+    }
+}
+MOTION
+Frames: 2
+Frame Time: 0.033333
+0 0 0 0 0 0 0 0 0
+1 0 0 90 0 0 0 0 0
+";
+        let (skel, anim) = read_bvh(&mut text.as_bytes()).unwrap();
+
+        assert_eq!(2, skel.bones.len());
+        assert_eq!("Hips", skel.bones[0].name);
+        assert_eq!(None, skel.bones[0].parent_index);
+        assert_eq!([1.0, 2.0, 3.0, 1.0], skel.bones[0].transform[3]);
+        assert_eq!("Spine", skel.bones[1].name);
+        assert_eq!(Some(0), skel.bones[1].parent_index);
+        assert_eq!([0.0, 5.0, 0.0, 1.0], skel.bones[1].transform[3]);
+
+        assert_eq!(1.0, anim.final_frame_index);
+        let nodes = &anim.groups[0].nodes;
+        assert_eq!("Hips", nodes[0].name);
+        match &nodes[0].tracks[0].values {
+            TrackValues::Transform(values) => {
+                assert_eq!(2, values.len());
+                assert_eq!(Vector3::new(2.0, 2.0, 3.0), values[1].translation);
+                let rotation = values[1].rotation;
+                approx::assert_relative_eq!(0.0, rotation.x, epsilon = 0.0001);
+                approx::assert_relative_eq!(0.0, rotation.y, epsilon = 0.0001);
+                approx::assert_relative_eq!(0.5f32.sqrt(), rotation.z, epsilon = 0.0001);
+                approx::assert_relative_eq!(0.5f32.sqrt(), rotation.w, epsilon = 0.0001);
+            }
+            _ => panic!("unexpected track values"),
+        }
+    }
+
+    #[test]
+    fn read_bvh_unexpected_eof() {
+        let text = "HIERARCHY
+ROOT Hips
+{
+    OFFSET 0 0 0
+    CHANNELS 1 Xrotation
+}
+MOTION
+Frames: 2
+Frame Time: 0.033333
+0
+";
+        assert!(matches!(
+            read_bvh(&mut text.as_bytes()),
+            Err(error::Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn read_bvh_extra_frames() {
+        let text = "HIERARCHY
+ROOT Hips
+{
+    OFFSET 0 0 0
+    CHANNELS 1 Xrotation
+}
+MOTION
+Frames: 1
+Frame Time: 0.033333
+0
+1
+2
+";
+        assert!(matches!(
+            read_bvh(&mut text.as_bytes()),
+            Err(error::Error::FrameCountMismatch {
+                expected: 1,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn read_bvh_invalid_frame_count() {
+        let text = "HIERARCHY
+ROOT Hips
+{
+    OFFSET 0 0 0
+    CHANNELS 1 Xrotation
+}
+MOTION
+Frames: 18446744073709551615
+Frame Time: 0.033333
+0
+";
+        assert!(matches!(
+            read_bvh(&mut text.as_bytes()),
+            Err(error::Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn read_bvh_unsupported_channel() {
+        let text = "HIERARCHY ROOT Hips { OFFSET 0 0 0 CHANNELS 1 Xscale }";
+        assert!(matches!(
+            read_bvh(&mut text.as_bytes()),
+            Err(error::Error::UnsupportedChannel { name }) if name == "Xscale"
+        ));
+    }
+
+    #[test]
+    fn bvh_round_trip_animation() {
+        let skel = skel(vec![
+            bone("Trans", [0.0, 0.0, 0.0], None),
+            bone("Hip", [0.0, 2.0, 0.0], Some(0)),
+        ]);
+
+        let rotation = Quat::from_euler(EulerRot::XYZ, 0.1, 0.2, 0.3);
+        let transforms = vec![
+            Transform::IDENTITY,
+            Transform {
+                scale: Vector3::new(1.0, 1.0, 1.0),
+                rotation: Vector4::new(rotation.x, rotation.y, rotation.z, rotation.w),
+                translation: Vector3::new(0.5, 2.0, -1.0),
+            },
+        ];
+
+        let anim = AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 1.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "Hip".to_string(),
+                    tracks: vec![TrackData {
+                        name: "Transform".to_string(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values: TrackValues::Transform(transforms.clone()),
+                    }],
+                }],
+            }],
+        };
+
+        let text = write_string(&skel, Some(&anim));
+        let (new_skel, new_anim) = read_bvh(&mut text.as_bytes()).unwrap();
+
+        assert_eq!(skel, new_skel);
+        assert_eq!(1.0, new_anim.final_frame_index);
+
+        let nodes = &new_anim.groups[0].nodes;
+        assert_eq!("Trans", nodes[0].name);
+        assert_eq!(
+            TrackValues::Transform(vec![Transform::IDENTITY; 2]),
+            nodes[0].tracks[0].values
+        );
+
+        assert_eq!("Hip", nodes[1].name);
+        match &nodes[1].tracks[0].values {
+            TrackValues::Transform(values) => {
+                for (expected, actual) in transforms.iter().zip(values) {
+                    for (e, a) in expected
+                        .translation
+                        .to_array()
+                        .iter()
+                        .zip(actual.translation.to_array())
+                    {
+                        approx::assert_relative_eq!(*e, a, epsilon = 0.0001);
+                    }
+                    let q1 = Quat::from_array(expected.rotation.to_array());
+                    let q2 = Quat::from_array(actual.rotation.to_array());
+                    approx::assert_relative_eq!(1.0, q1.dot(q2).abs(), epsilon = 0.0001);
+                }
+            }
+            _ => panic!("unexpected track values"),
+        }
+    }
+}
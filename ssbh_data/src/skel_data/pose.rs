@@ -0,0 +1,509 @@
+//! Calculating the final bone transforms for a frame of animation including helper bone constraints.
+//!
+//! The pose is calculated in the following order.
+//! 1. Each bone uses the transform from `anim` for the frame or its rest pose [transform](super::BoneData::transform).
+//! 2. World transforms are accumulated from each root bone down to its children.
+//! 3. The [aim_constraints](crate::hlpb_data::HlpbData::aim_constraints) are evaluated in order
+//!    followed by the [orient_constraints](crate::hlpb_data::HlpbData::orient_constraints) in order.
+//!    Each constraint sees the bones modified by previous constraints
+//!    and updates the world transforms of the constrained bone and its children.
+//! 4. The skinning transforms are calculated from the rest pose to the final world transforms.
+//!
+//! See [HlpbData::sort_constraints](crate::hlpb_data::HlpbData::sort_constraints)
+//! for ordering constraints that depend on each other.
+//!
+//! # Limitations
+//! Constraints only change the rotation of the constrained bone.
+//! The unknown values for each constraint like [unk_type](crate::hlpb_data::OrientConstraintData::unk_type) are not applied.
+//! Constraints referencing bones not in the skeleton are skipped.
+/*!
+```rust
+# use ssbh_data::anim_data::AnimData;
+# use ssbh_data::hlpb_data::HlpbData;
+# use ssbh_data::skel_data::SkelData;
+use ssbh_data::skel_data::pose::calculate_pose;
+
+# let skel = SkelData { major_version: 1, minor_version: 0, bones: Vec::new() };
+# let hlpb = HlpbData { major_version: 1, minor_version: 1, aim_constraints: Vec::new(), orient_constraints: Vec::new() };
+# let anim = AnimData { major_version: 2, minor_version: 0, final_frame_index: 0.0, groups: Vec::new() };
+let pose = calculate_pose(&skel, Some(&hlpb), Some(&anim), 0)?;
+assert_eq!(skel.bones.len(), pose.world_transforms.len());
+assert_eq!(skel.bones.len(), pose.skinning_transforms.len());
+# Ok::<(), ssbh_data::skel_data::BoneTransformError>(())
+```
+ */
+use std::collections::HashMap;
+
+use glam::{EulerRot, Mat4, Quat, Vec3, Vec4Swizzles};
+use ssbh_lib::{Vector3, Vector4};
+
+use super::{animated_transform, BoneTransformError, SkelData};
+use crate::anim_data::AnimData;
+use crate::hlpb_data::{AimConstraintData, HlpbData, OrientConstraintData};
+
+/// The final transforms for each bone in a [SkelData] calculated by [calculate_pose].
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pose {
+    /// The posed world transform for each bone in column-major order.
+    pub world_transforms: Vec<[[f32; 4]; 4]>,
+    /// The transform from the rest pose world transform to the posed world transform
+    /// for each bone in column-major order.
+    /// These are the matrices used for vertex skinning.
+    pub skinning_transforms: Vec<[[f32; 4]; 4]>,
+}
+
+/// Calculates the final world and skinning transforms for each bone in `skel`
+/// for `frame` of `anim` and the constraints in `hlpb`.
+///
+/// Bones use their rest pose if `anim` is [None] or has no transform track for the bone.
+/// Tracks are found in the same way as [SkelData::animated_world_transforms].
+/// No helper bone constraints are applied if `hlpb` is [None].
+/// See the [module level documentation](self) for the evaluation order.
+pub fn calculate_pose(
+    skel: &SkelData,
+    hlpb: Option<&HlpbData>,
+    anim: Option<&AnimData>,
+    frame: usize,
+) -> Result<Pose, BoneTransformError> {
+    let rest_world_transforms = skel
+        .bones
+        .iter()
+        .map(|b| {
+            skel.calculate_world_transform(b)
+                .map(|t| Mat4::from_cols_array_2d(&t))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut solver = PoseSolver::new(skel, anim, frame)?;
+    if let Some(hlpb) = hlpb {
+        for constraint in &hlpb.aim_constraints {
+            solver.apply_aim_constraint(constraint);
+        }
+        for constraint in &hlpb.orient_constraints {
+            solver.apply_orient_constraint(constraint);
+        }
+    }
+
+    Ok(Pose {
+        world_transforms: solver
+            .world_transforms
+            .iter()
+            .map(|t| t.to_cols_array_2d())
+            .collect(),
+        skinning_transforms: solver
+            .world_transforms
+            .iter()
+            .zip(rest_world_transforms)
+            .map(|(world, rest)| (*world * rest.inverse()).to_cols_array_2d())
+            .collect(),
+    })
+}
+
+struct PoseSolver<'a> {
+    skel: &'a SkelData,
+    bone_indices: HashMap<&'a str, usize>,
+    /// Bone indices with parents always before their children.
+    order: Vec<usize>,
+    transforms: Vec<Mat4>,
+    world_transforms: Vec<Mat4>,
+}
+
+impl<'a> PoseSolver<'a> {
+    fn new(
+        skel: &'a SkelData,
+        anim: Option<&AnimData>,
+        frame: usize,
+    ) -> Result<Self, BoneTransformError> {
+        let transforms: Vec<_> = skel
+            .bones
+            .iter()
+            .map(
+                |b| match anim.and_then(|a| animated_transform(a, &b.name, frame)) {
+                    Some(t) => Mat4::from_scale_rotation_translation(
+                        t.scale.to_array().into(),
+                        Quat::from_array(t.rotation.to_array()),
+                        t.translation.to_array().into(),
+                    ),
+                    None => Mat4::from_cols_array_2d(&b.transform),
+                },
+            )
+            .collect();
+
+        // Use the first bone if names are repeated.
+        let mut bone_indices = HashMap::new();
+        for (i, bone) in skel.bones.iter().enumerate() {
+            bone_indices.entry(bone.name.as_str()).or_insert(i);
+        }
+
+        let mut solver = Self {
+            skel,
+            bone_indices,
+            order: parent_first_order(skel)?,
+            world_transforms: transforms.clone(),
+            transforms,
+        };
+        solver.update_world_transforms();
+        Ok(solver)
+    }
+
+    fn parent_index(&self, index: usize) -> Option<usize> {
+        self.skel.bones[index]
+            .parent_index
+            .filter(|p| *p < self.skel.bones.len())
+    }
+
+    fn update_world_transforms(&mut self) {
+        for i in 0..self.order.len() {
+            let index = self.order[i];
+            self.world_transforms[index] = match self.parent_index(index) {
+                Some(parent) => self.world_transforms[parent] * self.transforms[index],
+                None => self.transforms[index],
+            };
+        }
+    }
+
+    fn world_rotation(&self, index: Option<usize>) -> Quat {
+        index
+            .map(|i| self.world_transforms[i].to_scale_rotation_translation().1)
+            .unwrap_or(Quat::IDENTITY)
+    }
+
+    fn world_position(&self, index: usize) -> Vec3 {
+        self.world_transforms[index].w_axis.xyz()
+    }
+
+    /// Replaces the world rotation for the bone at `index` while preserving its world scale and translation.
+    fn set_world_rotation(&mut self, index: usize, rotation: Quat) {
+        let (scale, _, translation) = self.world_transforms[index].to_scale_rotation_translation();
+        let world = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+        self.transforms[index] = match self.parent_index(index) {
+            Some(parent) => self.world_transforms[parent].inverse() * world,
+            None => world,
+        };
+        self.update_world_transforms();
+    }
+
+    fn apply_aim_constraint(&mut self, constraint: &AimConstraintData) {
+        let (Some(aim), Some(target)) = (
+            self.bone_indices
+                .get(constraint.aim_bone_name1.as_str())
+                .copied(),
+            self.bone_indices
+                .get(constraint.target_bone_name1.as_str())
+                .copied(),
+        ) else {
+            return;
+        };
+
+        let position = self.world_position(aim);
+        let aim_direction = (self.world_position(target) - position).normalize_or_zero();
+        let aim_axis = vec3(constraint.aim).normalize_or_zero();
+        if aim_direction == Vec3::ZERO || aim_axis == Vec3::ZERO {
+            return;
+        }
+
+        // Use the smallest rotation that points the aim axis at the target.
+        let current = self.world_rotation(Some(aim));
+        let mut rotation = Quat::from_rotation_arc(current * aim_axis, aim_direction) * current;
+
+        // Twist around the aim direction to point the up axis towards the second target if possible.
+        if let Some(up_target) = self
+            .bone_indices
+            .get(constraint.target_bone_name2.as_str())
+            .copied()
+        {
+            let up_direction = reject(self.world_position(up_target) - position, aim_direction);
+            let up = reject(rotation * vec3(constraint.up), aim_direction);
+            if up_direction != Vec3::ZERO && up != Vec3::ZERO {
+                let angle = up
+                    .cross(up_direction)
+                    .dot(aim_direction)
+                    .atan2(up.dot(up_direction));
+                rotation = Quat::from_axis_angle(aim_direction, angle) * rotation;
+            }
+        }
+
+        let rotation = rotation * quat(constraint.quat1) * quat(constraint.quat2);
+        self.set_world_rotation(aim, rotation);
+    }
+
+    fn apply_orient_constraint(&mut self, constraint: &OrientConstraintData) {
+        let (Some(source), Some(target)) = (
+            self.bone_indices
+                .get(constraint.source_bone_name.as_str())
+                .copied(),
+            self.bone_indices
+                .get(constraint.target_bone_name.as_str())
+                .copied(),
+        ) else {
+            return;
+        };
+        let source_parent = self.world_rotation(
+            self.bone_indices
+                .get(constraint.parent_bone_name1.as_str())
+                .copied(),
+        );
+        let target_parent = self.world_rotation(
+            self.bone_indices
+                .get(constraint.parent_bone_name2.as_str())
+                .copied(),
+        );
+
+        // Compare rotations relative to the parent bones.
+        let source_rotation = quat(constraint.quat1)
+            * source_parent.inverse()
+            * self.world_rotation(Some(source))
+            * quat(constraint.quat2);
+        let target_rotation = target_parent.inverse() * self.world_rotation(Some(target));
+
+        // Interpolate each axis from the target rotation to the source rotation.
+        let (sx, sy, sz) = source_rotation.to_euler(EulerRot::XYZ);
+        let (tx, ty, tz) = target_rotation.to_euler(EulerRot::XYZ);
+        let angle = |t: f32, s: f32, weight: f32, min: f32, max: f32| {
+            let degrees = (t + (s - t) * weight).to_degrees();
+            // Avoid panicking if the range is reversed.
+            degrees.max(min).min(max).to_radians()
+        };
+        let (weights, min, max) = (
+            constraint.constraint_axes,
+            constraint.range_min,
+            constraint.range_max,
+        );
+        let rotation = Quat::from_euler(
+            EulerRot::XYZ,
+            angle(tx, sx, weights.x, min.x, max.x),
+            angle(ty, sy, weights.y, min.y, max.y),
+            angle(tz, sz, weights.z, min.z, max.z),
+        );
+
+        self.set_world_rotation(target, target_parent * rotation);
+    }
+}
+
+fn parent_first_order(skel: &SkelData) -> Result<Vec<usize>, BoneTransformError> {
+    let mut order = Vec::with_capacity(skel.bones.len());
+    let mut visited = vec![false; skel.bones.len()];
+    for i in 0..skel.bones.len() {
+        // Collect any ancestors that haven't been added yet.
+        let mut chain = Vec::new();
+        let mut current = Some(i);
+        while let Some(index) = current {
+            if visited[index] {
+                break;
+            }
+            if chain.contains(&index) {
+                return Err(BoneTransformError::CycleDetected { index });
+            }
+            chain.push(index);
+            current = skel.bones[index]
+                .parent_index
+                .filter(|p| *p < skel.bones.len());
+        }
+
+        for index in chain.into_iter().rev() {
+            visited[index] = true;
+            order.push(index);
+        }
+    }
+    Ok(order)
+}
+
+fn vec3(v: Vector3) -> Vec3 {
+    Vec3::from_array(v.to_array())
+}
+
+fn quat(v: Vector4) -> Quat {
+    let q = Quat::from_array(v.to_array());
+    if q.length_squared() > 0.0 {
+        q.normalize()
+    } else {
+        Quat::IDENTITY
+    }
+}
+
+// The component of v perpendicular to the unit vector axis.
+fn reject(v: Vec3, axis: Vec3) -> Vec3 {
+    (v - axis * v.dot(axis)).normalize_or_zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anim_data::{
+        GroupData, GroupType, NodeData, TrackData, TrackValues, Transform, TransformFlags,
+    };
+    use crate::hlpb_data::description::{
+        AimConstraintDescription, HlpbDescription, OrientConstraintDescription,
+    };
+    use crate::skel_data::{BillboardType, BoneData};
+
+    fn assert_components_eq(expected: &[f32], actual: &[f32]) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual) {
+            approx::assert_relative_eq!(e, a, epsilon = 1e-6);
+        }
+    }
+
+    fn bone(name: &str, parent_index: Option<usize>, translation: [f32; 3]) -> BoneData {
+        BoneData {
+            name: name.to_string(),
+            transform: Mat4::from_translation(translation.into()).to_cols_array_2d(),
+            parent_index,
+            billboard_type: BillboardType::Disabled,
+            unk_flags: 1,
+        }
+    }
+
+    fn skel() -> SkelData {
+        SkelData {
+            major_version: 1,
+            minor_version: 0,
+            bones: vec![
+                bone("Trans", None, [0.0; 3]),
+                bone("ArmL", Some(0), [1.0, 0.0, 0.0]),
+                bone("HandL", Some(1), [1.0, 0.0, 0.0]),
+                bone("H_ElbowL", Some(1), [0.0, 0.0, 0.0]),
+                bone("H_Child", Some(3), [0.0, 1.0, 0.0]),
+            ],
+        }
+    }
+
+    fn anim(rotation: Quat) -> AnimData {
+        AnimData {
+            major_version: 2,
+            minor_version: 0,
+            final_frame_index: 0.0,
+            groups: vec![GroupData {
+                group_type: GroupType::Transform,
+                nodes: vec![NodeData {
+                    name: "HandL".to_string(),
+                    tracks: vec![TrackData {
+                        name: "Transform".to_string(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values: TrackValues::Transform(vec![Transform {
+                            translation: ssbh_lib::Vector3::new(1.0, 0.0, 0.0),
+                            rotation: rotation.to_array().into(),
+                            ..Transform::IDENTITY
+                        }]),
+                    }],
+                }],
+            }],
+        }
+    }
+
+    fn hlpb(
+        aim_constraints: Vec<AimConstraintDescription>,
+        orient_constraints: Vec<OrientConstraintDescription>,
+    ) -> HlpbData {
+        HlpbData::from(&HlpbDescription {
+            aim_constraints,
+            orient_constraints,
+        })
+    }
+
+    fn rotation(pose: &Pose, index: usize) -> Quat {
+        Mat4::from_cols_array_2d(&pose.world_transforms[index])
+            .to_scale_rotation_translation()
+            .1
+    }
+
+    #[test]
+    fn pose_without_constraints() {
+        let skel = skel();
+        let anim = anim(Quat::from_rotation_z(1.0));
+        let pose = calculate_pose(&skel, None, Some(&anim), 0).unwrap();
+        assert_eq!(
+            skel.animated_world_transforms(&anim, 0).unwrap(),
+            pose.world_transforms
+        );
+
+        // Only the animated bone moves from the rest pose.
+        let rest = calculate_pose(&skel, None, None, 0).unwrap();
+        for (i, transform) in rest.skinning_transforms.iter().enumerate() {
+            assert_components_eq(
+                &Mat4::IDENTITY.to_cols_array(),
+                &Mat4::from_cols_array_2d(transform).to_cols_array(),
+            );
+            let expected = if i == 2 {
+                Quat::from_rotation_z(1.0)
+            } else {
+                Quat::IDENTITY
+            };
+            assert_components_eq(&expected.to_array(), &rotation(&pose, i).to_array());
+        }
+    }
+
+    #[test]
+    fn pose_orient_constraint() {
+        let skel = skel();
+        let anim = anim(Quat::from_rotation_z(1.0));
+        let hlpb = hlpb(
+            Vec::new(),
+            vec![OrientConstraintDescription::new(
+                "nuHelperBoneRotateInterp1",
+                "ArmL",
+                "HandL",
+                "H_ElbowL",
+            )],
+        );
+
+        let pose = calculate_pose(&skel, Some(&hlpb), Some(&anim), 0).unwrap();
+        assert_components_eq(
+            &Quat::from_rotation_z(0.5).to_array(),
+            &rotation(&pose, 3).to_array(),
+        );
+
+        // Children of the constrained bone also move.
+        let child = Mat4::from_cols_array_2d(&pose.world_transforms[4]);
+        assert_components_eq(
+            &[1.0 - 0.5f32.sin(), 0.5f32.cos(), 0.0],
+            &child.w_axis.xyz().to_array(),
+        );
+    }
+
+    #[test]
+    fn pose_aim_constraint() {
+        let skel = skel();
+        let hlpb = hlpb(
+            vec![AimConstraintDescription::new(
+                "nuHelperBoneRotateAim1",
+                "H_Child",
+                "HandL",
+            )],
+            Vec::new(),
+        );
+
+        // H_Child at (1, 1, 0) aims its X axis down towards HandL at (2, 0, 0).
+        let pose = calculate_pose(&skel, Some(&hlpb), None, 0).unwrap();
+        assert_components_eq(
+            &Vec3::new(1.0, -1.0, 0.0).normalize().to_array(),
+            &(rotation(&pose, 4) * Vec3::X).to_array(),
+        );
+    }
+
+    #[test]
+    fn pose_missing_constraint_bones() {
+        let skel = skel();
+        let hlpb = hlpb(
+            vec![AimConstraintDescription::new("aim", "H_Child", "Missing")],
+            vec![OrientConstraintDescription::new(
+                "orient", "ArmL", "Missing", "H_ElbowL",
+            )],
+        );
+        assert_eq!(
+            calculate_pose(&skel, None, None, 0).unwrap(),
+            calculate_pose(&skel, Some(&hlpb), None, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn pose_cycle() {
+        let mut skel = skel();
+        skel.bones[0].parent_index = Some(2);
+        assert!(matches!(
+            calculate_pose(&skel, None, None, 0),
+            Err(BoneTransformError::CycleDetected { .. })
+        ));
+    }
+}
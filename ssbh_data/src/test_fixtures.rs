@@ -0,0 +1,80 @@
+// Minimal data shared between unit tests.
+// See the fixtures module for complete files with every supported version.
+use crate::{
+    anim_data::{AnimData, GroupData, GroupType, NodeData, TrackData, TrackValues, TransformFlags},
+    matl_data::{MatlData, MatlEntryData},
+};
+
+// A version 2.0 animation with no groups.
+pub fn empty_anim_data(final_frame_index: f32) -> AnimData {
+    AnimData {
+        major_version: 2,
+        minor_version: 0,
+        final_frame_index,
+        groups: Vec::new(),
+    }
+}
+
+// A version 2.0 animation with a single node in the transform group
+// and a track named "Transform" for each of the values.
+// Tests for code that doesn't check the track type can use other values.
+pub fn transform_track_anim_data(
+    node_name: &str,
+    final_frame_index: f32,
+    values: Vec<TrackValues>,
+) -> AnimData {
+    AnimData {
+        groups: vec![GroupData {
+            group_type: GroupType::Transform,
+            nodes: vec![NodeData {
+                name: node_name.to_string(),
+                tracks: values
+                    .into_iter()
+                    .map(|values| TrackData {
+                        name: "Transform".to_string(),
+                        compensate_scale: false,
+                        transform_flags: TransformFlags::default(),
+                        values,
+                    })
+                    .collect(),
+            }],
+        }],
+        ..empty_anim_data(final_frame_index)
+    }
+}
+
+// A material entry with no parameters.
+// Use struct update syntax to add parameters.
+pub fn matl_entry_data(material_label: &str, shader_label: &str) -> MatlEntryData {
+    MatlEntryData {
+        material_label: material_label.to_string(),
+        shader_label: shader_label.to_string(),
+        blend_states: Vec::new(),
+        floats: Vec::new(),
+        booleans: Vec::new(),
+        vectors: Vec::new(),
+        rasterizer_states: Vec::new(),
+        samplers: Vec::new(),
+        textures: Vec::new(),
+        uv_transforms: Vec::new(),
+    }
+}
+
+// A version 1.6 material with the given entries.
+pub fn matl_data(entries: Vec<MatlEntryData>) -> MatlData {
+    MatlData {
+        major_version: 1,
+        minor_version: 6,
+        entries,
+    }
+}
+
+// A version 1.1 helper bone file with no constraints.
+pub fn hlpb_data() -> crate::hlpb_data::HlpbData {
+    crate::hlpb_data::HlpbData {
+        major_version: 1,
+        minor_version: 1,
+        aim_constraints: Vec::new(),
+        orient_constraints: Vec::new(),
+    }
+}
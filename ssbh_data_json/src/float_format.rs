@@ -0,0 +1,246 @@
+//! Pretty printed JSON with configurable float formatting.
+//!
+//! Floats use the shortest representation that reads back as the same value by default.
+//! A fixed precision rounds away insignificant digits like `0.3000001`,
+//! which keeps diffs between exported files small when values only change by rounding error.
+//!
+//! The compact output writes arrays of numbers inside other arrays on a single line,
+//! so compact animation tracks have one frame per line.
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::ser::{Formatter, PrettyFormatter};
+
+/// A [PrettyFormatter] that writes floats with at most `precision` digits after the decimal point.
+struct FloatFormatter<'a> {
+    pretty: PrettyFormatter<'a>,
+    precision: Option<usize>,
+}
+
+/// Formats `value` rounded to `precision` decimal places without trailing zeros like `"1.5"` or `"2.0"`.
+fn format_fixed(value: f64, precision: usize) -> String {
+    let text = format!("{value:.precision$}");
+    match text.split_once('.') {
+        Some((whole, fraction)) => {
+            let fraction = fraction.trim_end_matches('0');
+            if fraction.is_empty() {
+                format!("{whole}.0")
+            } else {
+                format!("{whole}.{fraction}")
+            }
+        }
+        // Keep the decimal point so the value still reads as a float.
+        None => format!("{text}.0"),
+    }
+}
+
+impl Formatter for FloatFormatter<'_> {
+    fn write_f32<W: ?Sized + Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        match self.precision {
+            Some(precision) => writer.write_all(format_fixed(value as f64, precision).as_bytes()),
+            None => self.pretty.write_f32(writer, value),
+        }
+    }
+
+    fn write_f64<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        match self.precision {
+            Some(precision) => writer.write_all(format_fixed(value, precision).as_bytes()),
+            None => self.pretty.write_f64(writer, value),
+        }
+    }
+
+    // Delegate the indentation to the pretty formatter.
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.pretty.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        self.pretty.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.pretty.end_object_value(writer)
+    }
+}
+
+/// Serializes `value` as pretty printed JSON like [serde_json::to_string_pretty].
+/// Floats are rounded to `precision` digits after the decimal point if specified
+/// and use the shortest round trip representation otherwise.
+/// Non finite floats are written as `null` in either case.
+pub fn to_string_pretty<T: Serialize + ?Sized>(
+    value: &T,
+    precision: Option<usize>,
+) -> serde_json::Result<String> {
+    let mut writer = Vec::new();
+    let formatter = FloatFormatter {
+        pretty: PrettyFormatter::new(),
+        precision,
+    };
+    let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+    value.serialize(&mut serializer)?;
+
+    // The serializer only writes valid UTF-8.
+    Ok(String::from_utf8(writer).unwrap())
+}
+
+/// Serializes `value` like [to_string_pretty] but with arrays of scalar values
+/// that are elements of another array written on a single line.
+///
+/// Arrays stored directly in an object field still have one element per line.
+/// For the compact anim representation, this writes one frame per line.
+pub fn to_string_compact<T: Serialize + ?Sized>(
+    value: &T,
+    precision: Option<usize>,
+) -> serde_json::Result<String> {
+    to_string_pretty(value, precision).map(|json| collapse_nested_arrays(&json))
+}
+
+fn collapse_nested_arrays(json: &str) -> String {
+    let lines: Vec<_> = json.lines().collect();
+    let mut output = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        // Object fields and the root value have a key or no indentation.
+        if line.trim() == "[" && line.starts_with(' ') {
+            if let Some((elements, end)) = scalar_elements(&lines[i + 1..]) {
+                let indent = &line[..line.len() - 1];
+                let close = lines[i + 1 + end].trim();
+                output.push(format!("{indent}[{}{close}", elements.join(" ")));
+                i += end + 2;
+                continue;
+            }
+        }
+        output.push(line.to_string());
+        i += 1;
+    }
+
+    output.join("\n")
+}
+
+// Find the trimmed scalar elements and the index of the closing line.
+// Pretty printed scalar elements never end with an opening bracket or brace.
+fn scalar_elements<'a>(lines: &[&'a str]) -> Option<(Vec<&'a str>, usize)> {
+    let mut elements = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.starts_with(']') {
+            return (!elements.is_empty()).then_some((elements, i));
+        }
+        if line.ends_with('[') || line.ends_with('{') || line.starts_with('}') {
+            return None;
+        }
+        elements.push(line);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_round_trip() {
+        assert_eq!(
+            serde_json::to_string_pretty(&[0.1f32, 1.0, 0.3000001]).unwrap(),
+            to_string_pretty(&[0.1f32, 1.0, 0.3000001], None).unwrap()
+        );
+    }
+
+    #[test]
+    fn fixed_precision() {
+        assert_eq!(
+            "[\n  0.1,\n  1.0,\n  0.3,\n  -1.235,\n  null\n]",
+            to_string_pretty(&[0.1f32, 1.0, 0.3000001, -1.23456, f32::NAN], Some(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn compact_nested_arrays() {
+        let value = serde_json::json!({
+            "values": [[1.0, 2.5], [3.0, -4.0]],
+            "floats": [1.0, 2.0],
+            "empty": [[]],
+            "nested": [[[1.0]], [{"x": 1.0}]]
+        });
+        assert_eq!(
+            concat!(
+                "{\n",
+                "  \"empty\": [\n",
+                "    []\n",
+                "  ],\n",
+                "  \"floats\": [\n",
+                "    1.0,\n",
+                "    2.0\n",
+                "  ],\n",
+                "  \"nested\": [\n",
+                "    [\n",
+                "      [1.0]\n",
+                "    ],\n",
+                "    [\n",
+                "      {\n",
+                "        \"x\": 1.0\n",
+                "      }\n",
+                "    ]\n",
+                "  ],\n",
+                "  \"values\": [\n",
+                "    [1.0, 2.5],\n",
+                "    [3.0, -4.0]\n",
+                "  ]\n",
+                "}"
+            ),
+            to_string_compact(&value, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn compact_strings() {
+        // Brackets in strings shouldn't affect the output.
+        let value = serde_json::json!([["[", "{"], ["]"]]);
+        assert_eq!(
+            "[\n  [\"[\", \"{\"],\n  [\"]\"]\n]",
+            to_string_compact(&value, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn fixed_precision_zero() {
+        assert_eq!(
+            "{\n  \"x\": 2.0\n}",
+            to_string_pretty(&serde_json::json!({"x": 1.5f64}), Some(0)).unwrap()
+        );
+    }
+}
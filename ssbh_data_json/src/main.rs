@@ -4,8 +4,12 @@ use std::time::Instant;
 
 use clap::Parser;
 use serde::{Deserialize, Serialize};
+use ssbh_data::anim_data::compact::CompactAnimData;
 use ssbh_data::prelude::*;
 
+mod float_format;
+mod patch;
+
 /// Convert SSBH, Meshex, and Adjb files to and from JSON.
 /// Uses a higher level API than ssbh_lib_json.
 #[derive(Parser)]
@@ -16,22 +20,50 @@ struct Cli {
     /// The output JSON or binary file path.
     /// Set as `<input>.json` or inferred from the JSON data if not specified.
     output: Option<String>,
+    /// Store each animation frame as an array of numbers on a single line instead of an object.
+    #[arg(long)]
+    compact: bool,
+    /// Apply the RFC 6902 JSON patch file in `output` to the binary input file in place.
+    #[arg(long, requires = "output")]
+    apply: bool,
+    /// Round floats in the JSON output to the given number of digits after the decimal point.
+    /// Floats use the shortest representation that converts back to the same value if not specified.
+    #[arg(long)]
+    precision: Option<usize>,
+}
+
+fn parse_and_write_json<T: SsbhData + Serialize, P: AsRef<Path>>(
+    input: P,
+    output: P,
+    precision: Option<usize>,
+) {
+    parse_and_write_json_with(input, output, |data: &T| {
+        float_format::to_string_pretty(data, precision)
+    })
 }
 
-fn parse_and_write_json<T: SsbhData + Serialize, P: AsRef<Path>>(input: P, output: P) {
+fn parse_and_write_json_with<T, P, F>(input: P, output: P, to_json: F)
+where
+    T: SsbhData,
+    P: AsRef<Path>,
+    F: Fn(&T) -> serde_json::Result<String>,
+{
     let parse_start_time = Instant::now();
     match T::from_file(&input) {
         Ok(data) => {
             eprintln!("Parse: {:?}", parse_start_time.elapsed());
 
-            let json = serde_json::to_string_pretty(&data).unwrap();
+            let json = to_json(&data).unwrap();
 
             let mut output_file = std::fs::File::create(output).expect("unable to create file");
             output_file
                 .write_all(json.as_bytes())
                 .expect("unable to write");
         }
-        Err(error) => eprintln!("{error:?}"),
+        Err(error) => {
+            eprintln!("{error:?}");
+            std::process::exit(1);
+        }
     };
 }
 
@@ -51,9 +83,57 @@ fn deserialize_and_save<'a, T: SsbhData + Deserialize<'a>>(
     Ok(())
 }
 
+fn apply_patch_and_save<T>(input: &Path, patch: &Path) -> Result<(), Box<dyn std::error::Error>>
+where
+    T: SsbhData + Serialize + for<'de> Deserialize<'de>,
+    T::WriteError: 'static,
+{
+    let operations: Vec<patch::Operation> = serde_json::from_str(&std::fs::read_to_string(patch)?)?;
+
+    let mut document = serde_json::to_value(T::from_file(input)?)?;
+    patch::apply_patch(&mut document, &operations)?;
+
+    let data: T = serde_json::from_value(document)?;
+    data.write_to_file(input)?;
+    Ok(())
+}
+
+fn apply_patch(input: &Path, patch: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    match input.extension().and_then(|e| e.to_str()) {
+        Some("numshb") => apply_patch_and_save::<MeshData>(input, patch),
+        Some("nusktb") => apply_patch_and_save::<SkelData>(input, patch),
+        Some("nuanmb") => apply_patch_and_save::<AnimData>(input, patch),
+        Some("numdlb") => apply_patch_and_save::<ModlData>(input, patch),
+        Some("numatb") => apply_patch_and_save::<MatlData>(input, patch),
+        Some("nuhlpb") => apply_patch_and_save::<HlpbData>(input, patch),
+        Some("adjb") => apply_patch_and_save::<AdjData>(input, patch),
+        Some("numshexb") => apply_patch_and_save::<MeshExData>(input, patch),
+        Some("nushdb") => apply_patch_and_save::<ShdrData>(input, patch),
+        Some("nufxlb") => apply_patch_and_save::<NufxData>(input, patch),
+        _ => Err(format!("unsupported file {input:?}").into()),
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
+    if cli.apply {
+        // Clap ensures the patch path is present.
+        let patch_path = cli.output.unwrap();
+        if let Err(error) = apply_patch(Path::new(&cli.input), Path::new(&patch_path)) {
+            // Pipelines rely on the exit code to detect a failed patch.
+            let mut source: Option<&dyn std::error::Error> = Some(error.as_ref());
+            let mut messages = Vec::new();
+            while let Some(e) = source {
+                messages.push(e.to_string());
+                source = e.source();
+            }
+            eprintln!("{}", messages.join(": "));
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Modify the input if no output is specified to allow dragging a file onto the executable.
     let output_path = cli
         .output
@@ -64,14 +144,23 @@ fn main() {
     // Try parsing one of the supported formats.
     let input_path = Path::new(&cli.input);
     match input_path.extension().unwrap().to_str().unwrap() {
-        "numshb" => parse_and_write_json::<MeshData, _>(input_path, &output_path),
-        "nusktb" => parse_and_write_json::<SkelData, _>(input_path, &output_path),
-        "nuanmb" => parse_and_write_json::<AnimData, _>(input_path, &output_path),
-        "numdlb" => parse_and_write_json::<ModlData, _>(input_path, &output_path),
-        "numatb" => parse_and_write_json::<MatlData, _>(input_path, &output_path),
-        "nuhlpb" => parse_and_write_json::<HlpbData, _>(input_path, &output_path),
-        "adjb" => parse_and_write_json::<AdjData, _>(input_path, &output_path),
-        "numshexb" => parse_and_write_json::<MeshExData, _>(input_path, &output_path),
+        "numshb" => parse_and_write_json::<MeshData, _>(input_path, &output_path, cli.precision),
+        "nusktb" => parse_and_write_json::<SkelData, _>(input_path, &output_path, cli.precision),
+        "nuanmb" if cli.compact => {
+            parse_and_write_json_with(input_path, &output_path, |data: &AnimData| {
+                float_format::to_string_compact(&CompactAnimData::from(data), cli.precision)
+            })
+        }
+        "nuanmb" => parse_and_write_json::<AnimData, _>(input_path, &output_path, cli.precision),
+        "numdlb" => parse_and_write_json::<ModlData, _>(input_path, &output_path, cli.precision),
+        "numatb" => parse_and_write_json::<MatlData, _>(input_path, &output_path, cli.precision),
+        "nuhlpb" => parse_and_write_json::<HlpbData, _>(input_path, &output_path, cli.precision),
+        "adjb" => parse_and_write_json::<AdjData, _>(input_path, &output_path, cli.precision),
+        "numshexb" => {
+            parse_and_write_json::<MeshExData, _>(input_path, &output_path, cli.precision)
+        }
+        "nushdb" => parse_and_write_json::<ShdrData, _>(input_path, &output_path, cli.precision),
+        "nufxlb" => parse_and_write_json::<NufxData, _>(input_path, &output_path, cli.precision),
         "json" => {
             let json = std::fs::read_to_string(input_path).expect("Failed to read file.");
             let output_path = cli.output.map(PathBuf::from);
@@ -99,6 +188,12 @@ fn main() {
                 .or_else(|_| {
                     deserialize_and_save::<AdjData>(&json, input_path, &output_path, "adjb")
                 })
+                .or_else(|_| {
+                    deserialize_and_save::<ShdrData>(&json, input_path, &output_path, "nushdb")
+                })
+                .or_else(|_| {
+                    deserialize_and_save::<NufxData>(&json, input_path, &output_path, "nufxlb")
+                })
                 .unwrap();
         }
         _ => (),
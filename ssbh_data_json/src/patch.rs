@@ -0,0 +1,198 @@
+//! Applying JSON patches as described in [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902).
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Operation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+/// Applies each operation in `patch` to `document` in order.
+/// The document is unchanged if any operation fails.
+pub fn apply_patch(document: &mut Value, patch: &[Operation]) -> Result<(), String> {
+    let mut patched = document.clone();
+    for operation in patch {
+        apply_operation(&mut patched, operation)?;
+    }
+    *document = patched;
+    Ok(())
+}
+
+fn apply_operation(document: &mut Value, operation: &Operation) -> Result<(), String> {
+    match operation {
+        Operation::Add { path, value } => add(document, path, value.clone()),
+        Operation::Remove { path } => remove(document, path).map(|_| ()),
+        Operation::Replace { path, value } => {
+            let target = document
+                .pointer_mut(path)
+                .ok_or_else(|| format!("path {path:?} does not exist"))?;
+            *target = value.clone();
+            Ok(())
+        }
+        Operation::Move { from, path } => {
+            if path.starts_with(&format!("{from}/")) {
+                return Err(format!("cannot move {from:?} into its child {path:?}"));
+            }
+            let value = remove(document, from)?;
+            add(document, path, value)
+        }
+        Operation::Copy { from, path } => {
+            let value = document
+                .pointer(from)
+                .ok_or_else(|| format!("path {from:?} does not exist"))?
+                .clone();
+            add(document, path, value)
+        }
+        Operation::Test { path, value } => match document.pointer(path) {
+            Some(v) if v == value => Ok(()),
+            Some(v) => Err(format!(
+                "test failed for {path:?}: expected {value}, found {v}"
+            )),
+            None => Err(format!("path {path:?} does not exist")),
+        },
+    }
+}
+
+// Split a JSON pointer into the pointer for the parent and the unescaped last token.
+fn split_pointer(path: &str) -> Result<(&str, String), String> {
+    let index = path
+        .rfind('/')
+        .ok_or_else(|| format!("invalid JSON pointer {path:?}"))?;
+    let token = path[index + 1..].replace("~1", "/").replace("~0", "~");
+    Ok((&path[..index], token))
+}
+
+fn array_index(token: &str, len: usize) -> Result<usize, String> {
+    match token.parse::<usize>() {
+        Ok(i) if i <= len && (token == "0" || !token.starts_with('0')) => Ok(i),
+        _ => Err(format!("invalid array index {token:?}")),
+    }
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+
+    let (parent, token) = split_pointer(path)?;
+    match document.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Some(Value::Array(values)) => {
+            let index = if token == "-" {
+                values.len()
+            } else {
+                array_index(&token, values.len())?
+            };
+            values.insert(index, value);
+            Ok(())
+        }
+        Some(_) => Err(format!("parent of {path:?} is not an object or array")),
+        None => Err(format!("path {parent:?} does not exist")),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value, String> {
+    let (parent, token) = split_pointer(path)?;
+    match document.pointer_mut(parent) {
+        Some(Value::Object(map)) => map
+            .remove(&token)
+            .ok_or_else(|| format!("path {path:?} does not exist")),
+        Some(Value::Array(values)) => {
+            let index = array_index(&token, values.len())?;
+            if index < values.len() {
+                Ok(values.remove(index))
+            } else {
+                Err(format!("path {path:?} does not exist"))
+            }
+        }
+        _ => Err(format!("path {path:?} does not exist")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(document: Value, patch: Value) -> Result<Value, String> {
+        let mut document = document;
+        let operations: Vec<Operation> = serde_json::from_value(patch).unwrap();
+        apply_patch(&mut document, &operations)?;
+        Ok(document)
+    }
+
+    #[test]
+    fn replace_nested_value() {
+        assert_eq!(
+            json!({"entries": [{"floats": [{"data": 0.5}]}]}),
+            patch(
+                json!({"entries": [{"floats": [{"data": 1.0}]}]}),
+                json!([{"op": "replace", "path": "/entries/0/floats/0/data", "value": 0.5}])
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn add_remove_array_elements() {
+        assert_eq!(
+            json!({"a": [1, 3, 4]}),
+            patch(
+                json!({"a": [1, 2]}),
+                json!([
+                    {"op": "add", "path": "/a/-", "value": 3},
+                    {"op": "remove", "path": "/a/1"},
+                    {"op": "add", "path": "/a/2", "value": 4}
+                ])
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn move_copy_escaped_keys() {
+        assert_eq!(
+            json!({"b": 1, "c/d": 1}),
+            patch(
+                json!({"a~b": 1}),
+                json!([
+                    {"op": "move", "from": "/a~0b", "path": "/b"},
+                    {"op": "copy", "from": "/b", "path": "/c~1d"}
+                ])
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn failed_test_leaves_document_unchanged() {
+        let mut document = json!({"a": 1});
+        let operations: Vec<Operation> = serde_json::from_value(json!([
+            {"op": "replace", "path": "/a", "value": 2},
+            {"op": "test", "path": "/a", "value": 3}
+        ]))
+        .unwrap();
+        assert!(apply_patch(&mut document, &operations).is_err());
+        assert_eq!(json!({"a": 1}), document);
+    }
+
+    #[test]
+    fn missing_path() {
+        assert!(patch(
+            json!({"a": 1}),
+            json!([{"op": "replace", "path": "/b", "value": 2}])
+        )
+        .is_err());
+        assert!(patch(json!({"a": [1]}), json!([{"op": "remove", "path": "/a/1"}])).is_err());
+    }
+}
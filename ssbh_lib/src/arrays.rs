@@ -10,7 +10,9 @@ use binrw::{
 use serde::{Deserialize, Serialize};
 use ssbh_write::SsbhWrite;
 
-use crate::{absolute_offset_checked, round_up, write_relative_offset};
+use crate::offset_log::AlignmentSource;
+use crate::read_limits;
+use crate::{absolute_offset_checked, log_offset_write, round_up, write_relative_offset};
 
 // Array element types vary in size, so pick a more consersative value.
 const SSBH_ARRAY_MAX_INITIAL_CAPACITY: usize = 1024;
@@ -64,6 +66,54 @@ impl SsbhByteBuffer {
     }
 }
 
+#[cfg(feature = "zlib")]
+impl SsbhByteBuffer {
+    /// Creates a new array from the zlib compressed bytes of `data`.
+    /**
+    ```rust
+    # use ssbh_lib::SsbhByteBuffer;
+    let array = SsbhByteBuffer::zlib_compress(&[1, 2, 3])?;
+    assert_eq!(vec![1, 2, 3], array.zlib_decompress()?);
+    # Ok::<(), std::io::Error>(())
+    ```
+    */
+    pub fn zlib_compress(data: &[u8]) -> std::io::Result<Self> {
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish().map(Self::from_vec)
+    }
+
+    /// Decompresses the elements as zlib compressed data.
+    pub fn zlib_decompress(&self) -> std::io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        flate2::read::ZlibDecoder::new(self.elements.as_slice()).read_to_end(&mut data)?;
+        Ok(data)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl SsbhByteBuffer {
+    /// Creates a new array from the zstd compressed bytes of `data`.
+    /// A `level` of `0` uses the default compression level.
+    /**
+    ```rust
+    # use ssbh_lib::SsbhByteBuffer;
+    let array = SsbhByteBuffer::zstd_compress(&[1, 2, 3], 0)?;
+    assert_eq!(vec![1, 2, 3], array.zstd_decompress()?);
+    # Ok::<(), std::io::Error>(())
+    ```
+    */
+    pub fn zstd_compress(data: &[u8], level: i32) -> std::io::Result<Self> {
+        zstd::encode_all(data, level).map(Self::from_vec)
+    }
+
+    /// Decompresses the elements as zstd compressed data.
+    pub fn zstd_decompress(&self) -> std::io::Result<Vec<u8>> {
+        zstd::decode_all(self.elements.as_slice())
+    }
+}
+
 impl Default for SsbhByteBuffer {
     fn default() -> Self {
         Self::new()
@@ -84,7 +134,8 @@ impl BinRead for SsbhByteBuffer {
         endian: Endian,
         _args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        let elements = read_ssbh_array(reader, read_buffer, endian)?;
+        let max_count = read_limits::current().max_buffer_size;
+        let elements = read_ssbh_array(reader, read_buffer, endian, max_count)?;
         Ok(Self { elements })
     }
 }
@@ -164,6 +215,12 @@ impl<T: PartialEq> PartialEq for SsbhArray<T> {
 
 impl<T: Eq> Eq for SsbhArray<T> {}
 
+impl<T: std::hash::Hash> std::hash::Hash for SsbhArray<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.elements.hash(state);
+    }
+}
+
 impl<T> SsbhArray<T> {
     /// Creates an empty array.
     /**
@@ -218,7 +275,8 @@ where
         endian: Endian,
         _args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        let elements = read_ssbh_array(reader, read_elements, endian)?;
+        let max_count = read_limits::current().max_array_length;
+        let elements = read_ssbh_array(reader, read_elements, endian, max_count)?;
         Ok(Self { elements })
     }
 }
@@ -267,7 +325,12 @@ where
     }
 }
 
-fn read_ssbh_array<R, F, T>(reader: &mut R, read_elements: F, endian: Endian) -> BinResult<Vec<T>>
+fn read_ssbh_array<R, F, T>(
+    reader: &mut R,
+    read_elements: F,
+    endian: Endian,
+    max_count: Option<u64>,
+) -> BinResult<Vec<T>>
 where
     R: Read + Seek,
     F: Fn(&mut R, Endian, u64) -> BinResult<Vec<T>>,
@@ -277,8 +340,16 @@ where
     let pos_before_read = reader.stream_position()?;
 
     let relative_offset = u64::read_options(reader, endian, ())?;
+    let count_pos = reader.stream_position()?;
     let element_count = u64::read_options(reader, endian, ())?;
 
+    if let Some(max_count) = max_count.filter(|max| element_count > *max) {
+        return Err(binrw::error::Error::AssertFail {
+            pos: count_pos,
+            message: format!("Element count {element_count} exceeds the limit of {max_count}."),
+        });
+    }
+
     let saved_pos = reader.stream_position()?;
 
     let seek_pos = absolute_offset_checked(pos_before_read, relative_offset)?;
@@ -289,18 +360,27 @@ where
     result
 }
 
-fn write_array_header<W: Write + Seek>(
+fn write_array_header<W: Write + Seek, T>(
     writer: &mut W,
     data_ptr: &mut u64,
     count: usize,
+    alignment: u64,
 ) -> std::io::Result<()> {
-    // Arrays are always 8 byte aligned.
-    *data_ptr = round_up(*data_ptr, 8);
+    let unaligned_ptr = *data_ptr;
+    *data_ptr = round_up(*data_ptr, alignment);
 
     // Don't write the offset for empty arrays.
     if count == 0 {
         u64::write(&0u64, writer)?;
     } else {
+        log_offset_write(
+            writer,
+            unaligned_ptr,
+            *data_ptr,
+            alignment,
+            AlignmentSource::Array,
+            std::any::type_name::<T>,
+        )?;
         write_relative_offset(writer, data_ptr)?;
     }
 
@@ -308,18 +388,25 @@ fn write_array_header<W: Write + Seek>(
     Ok(())
 }
 
-impl SsbhWrite for SsbhByteBuffer {
-    fn ssbh_write<W: Write + Seek>(
+impl SsbhByteBuffer {
+    /// Writes the buffer like [SsbhWrite::ssbh_write] but aligns the data to `alignment` bytes instead of 8 bytes.
+    pub(crate) fn ssbh_write_aligned<W: Write + Seek>(
         &self,
         writer: &mut W,
         data_ptr: &mut u64,
+        alignment: u64,
     ) -> std::io::Result<()> {
         let current_pos = writer.stream_position()?;
         if *data_ptr < current_pos + self.size_in_bytes() {
             *data_ptr = current_pos + self.size_in_bytes();
         }
 
-        write_array_header(writer, data_ptr, self.elements.len())?;
+        write_array_header::<_, u8>(writer, data_ptr, self.elements.len(), alignment)?;
+
+        // Writing an empty slice past the end may still extend some writers.
+        if self.elements.is_empty() {
+            return Ok(());
+        }
 
         let current_pos = writer.stream_position()?;
         writer.seek(SeekFrom::Start(*data_ptr))?;
@@ -331,12 +418,42 @@ impl SsbhWrite for SsbhByteBuffer {
         writer.seek(SeekFrom::Start(current_pos))?;
         Ok(())
     }
+}
+
+impl SsbhWrite for SsbhByteBuffer {
+    fn ssbh_write<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        // Arrays are always 8 byte aligned.
+        self.ssbh_write_aligned(writer, data_ptr, 8)
+    }
 
     fn size_in_bytes(&self) -> u64 {
         16
     }
 }
 
+/// Writes the array header for an [SsbhArray] containing `elements` followed by the elements.
+pub(crate) fn write_array_elements<W: Write + Seek, T: SsbhWrite>(
+    elements: &[T],
+    writer: &mut W,
+    data_ptr: &mut u64,
+) -> std::io::Result<()> {
+    // Arrays are always 8 byte aligned.
+    write_array_header::<_, T>(writer, data_ptr, elements.len(), 8)?;
+
+    let pos_after_length = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(*data_ptr))?;
+
+    elements.ssbh_write(writer, data_ptr)?;
+
+    writer.seek(SeekFrom::Start(pos_after_length))?;
+
+    Ok(())
+}
+
 impl<T: SsbhWrite> SsbhWrite for SsbhArray<T> {
     fn ssbh_write<W: Write + Seek>(
         &self,
@@ -349,16 +466,7 @@ impl<T: SsbhWrite> SsbhWrite for SsbhArray<T> {
             *data_ptr = current_pos + self.size_in_bytes();
         }
 
-        write_array_header(writer, data_ptr, self.elements.len())?;
-
-        let pos_after_length = writer.stream_position()?;
-        writer.seek(SeekFrom::Start(*data_ptr))?;
-
-        self.elements.as_slice().ssbh_write(writer, data_ptr)?;
-
-        writer.seek(SeekFrom::Start(pos_after_length))?;
-
-        Ok(())
+        write_array_elements(&self.elements, writer, data_ptr)
     }
 
     fn size_in_bytes(&self) -> u64 {
@@ -650,4 +758,30 @@ mod tests {
         );
         assert_eq!(16, data_ptr);
     }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn zlib_compress_decompress() {
+        let data: Vec<u8> = (0..255).cycle().take(4096).collect();
+        let array = SsbhByteBuffer::zlib_compress(&data).unwrap();
+        assert!(array.elements.len() < data.len());
+        assert_eq!(data, array.zlib_decompress().unwrap());
+
+        assert!(SsbhByteBuffer::from_vec(vec![1, 2, 3])
+            .zlib_decompress()
+            .is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_compress_decompress() {
+        let data: Vec<u8> = (0..255).cycle().take(4096).collect();
+        let array = SsbhByteBuffer::zstd_compress(&data, 0).unwrap();
+        assert!(array.elements.len() < data.len());
+        assert_eq!(data, array.zstd_decompress().unwrap());
+
+        assert!(SsbhByteBuffer::from_vec(vec![1, 2, 3])
+            .zstd_decompress()
+            .is_err());
+    }
 }
@@ -0,0 +1,124 @@
+//! Known version compatibility for SSBH formats in different games.
+//!
+//! Each game only supports a subset of the versions for each format.
+//! Check the compatibility before converting a file to a different version
+//! to avoid writing files the target game can't read.
+/*!
+```rust no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use ssbh_lib::{compatibility::Game, SsbhFile};
+
+let ssbh = SsbhFile::from_file("model.numshb")?;
+if !ssbh.data.compatible_games().contains(&Game::SmashUltimate) {
+    println!("This version is not known to work in Smash Ultimate.");
+}
+# Ok(()) }
+```
+ */
+use crate::{Ssbh, Version};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A game that uses SSBH formats.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Game {
+    /// Super Smash Bros Ultimate
+    SmashUltimate,
+    /// New Pokemon Snap
+    NewPokemonSnap,
+}
+
+// The format magic, major version, minor version, and games known to read that version.
+// This only includes versions found in game files and is not exhaustive.
+const COMPATIBLE_VERSIONS: &[(&[u8; 4], u16, u16, &[Game])] = &[
+    (b"MINA", 2, 0, &[Game::SmashUltimate]),
+    (b"MINA", 2, 1, &[Game::SmashUltimate]),
+    (b"BPLH", 1, 1, &[Game::SmashUltimate]),
+    (b"LTAM", 1, 5, &[Game::SmashUltimate]),
+    (b"LTAM", 1, 6, &[Game::SmashUltimate, Game::NewPokemonSnap]),
+    (b"HSEM", 1, 8, &[Game::SmashUltimate]),
+    (b"HSEM", 1, 9, &[Game::NewPokemonSnap]),
+    (b"HSEM", 1, 10, &[Game::SmashUltimate]),
+    (b"LDOM", 1, 7, &[Game::SmashUltimate, Game::NewPokemonSnap]),
+    (b"TSLN", 1, 0, &[Game::SmashUltimate]),
+    (b"DPRN", 1, 6, &[Game::SmashUltimate]),
+    (b"XFUN", 1, 0, &[Game::SmashUltimate]),
+    (b"XFUN", 1, 1, &[Game::SmashUltimate]),
+    (b"RDHS", 1, 2, &[Game::SmashUltimate]),
+    (b"LEKS", 1, 0, &[Game::SmashUltimate, Game::NewPokemonSnap]),
+];
+
+/// Returns the games known to support the format with the given `magic` like `b"HSEM"` and version.
+/// Versions that aren't in the table return an empty list.
+/**
+```rust
+use ssbh_lib::compatibility::{compatible_games, Game};
+
+assert_eq!(&[Game::SmashUltimate], compatible_games(b"HSEM", 1, 10));
+assert!(compatible_games(b"HSEM", 1, 11).is_empty());
+```
+ */
+pub fn compatible_games(
+    magic: &[u8; 4],
+    major_version: u16,
+    minor_version: u16,
+) -> &'static [Game] {
+    COMPATIBLE_VERSIONS
+        .iter()
+        .find(|(m, major, minor, _)| {
+            *m == magic && *major == major_version && *minor == minor_version
+        })
+        .map(|(_, _, _, games)| *games)
+        .unwrap_or(&[])
+}
+
+impl Ssbh {
+    /// The magic for the format like `b"HSEM"` for [Ssbh::Mesh].
+    pub fn magic(&self) -> &'static [u8; 4] {
+        self.kind().magic()
+    }
+
+    /// Returns the games known to support the format and version of the data.
+    /// See [compatible_games].
+    pub fn compatible_games(&self) -> &'static [Game] {
+        let (major_version, minor_version) = self.major_minor_version();
+        compatible_games(self.magic(), major_version, minor_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formats::hlpb::Hlpb, SsbhArray, Versioned};
+
+    #[test]
+    fn compatible_games_hlpb() {
+        let hlpb = Ssbh::Hlpb(Versioned {
+            data: Hlpb::V11 {
+                aim_constraints: SsbhArray::new(),
+                orient_constraints: SsbhArray::new(),
+                constraint_indices: SsbhArray::new(),
+                constraint_types: SsbhArray::new(),
+            },
+        });
+        assert_eq!(b"BPLH", hlpb.magic());
+        assert_eq!(&[Game::SmashUltimate], hlpb.compatible_games());
+    }
+
+    #[test]
+    fn compatible_games_unknown_version() {
+        assert!(compatible_games(b"LTAM", 1, 4).is_empty());
+        assert!(compatible_games(b"ABCD", 1, 0).is_empty());
+    }
+
+    #[test]
+    fn compatible_versions_unique() {
+        for (i, (m, major, minor, _)) in COMPATIBLE_VERSIONS.iter().enumerate() {
+            assert!(!COMPATIBLE_VERSIONS[i + 1..]
+                .iter()
+                .any(|(m2, major2, minor2, _)| m == m2 && major == major2 && minor == minor2));
+        }
+    }
+}
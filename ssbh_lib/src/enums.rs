@@ -6,7 +6,7 @@ use ssbh_write::SsbhWrite;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{absolute_offset_checked, RelPtr64};
+use crate::{absolute_offset_checked, log_offset_write, offset_log, round_up, RelPtr64};
 
 /// Reads a struct with a relative offset to a structure of type T with some data type.
 /// Reading will fail if there is no matching variant for `data_type`.
@@ -74,6 +74,8 @@ impl<T: DataType + PartialEq> PartialEq for SsbhEnum64<T> {
     }
 }
 
+impl<T: DataType + Eq> Eq for SsbhEnum64<T> {}
+
 impl<T: DataType + Clone> Clone for SsbhEnum64<T> {
     fn clone(&self) -> Self {
         Self {
@@ -93,64 +95,246 @@ where
         endian: Endian,
         _args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        // The data type occurs after the offset, so it's difficult to just derive BinRead.
-        let pos_before_read = reader.stream_position()?;
-        let relative_offset = u64::read_options(reader, endian, ())?;
-        let data_type = u64::read_options(reader, endian, ())?;
-
-        if relative_offset == 0 {
-            return Ok(SsbhEnum64 {
-                data: RelPtr64(None),
-            });
+        Ok(SsbhEnum64 {
+            data: RelPtr64(read_ssbh_enum::<u64, T, R>(reader, endian)?),
+        })
+    }
+}
+
+impl<T: DataType + SsbhWrite> SsbhWrite for SsbhEnum64<T> {
+    fn ssbh_write<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        write_ssbh_enum::<u64, T, W>(self.data.as_ref(), writer, data_ptr)
+    }
+
+    fn size_in_bytes(&self) -> u64 {
+        // Relative offset + data type
+        8 + 8
+    }
+}
+
+/// A 32 bit relative offset and 32 bit data type like [SsbhEnum64].
+/// Reading will fail if there is no matching variant for `data_type`.
+///
+/// The data type is passed to `T` as a [u64], so the same `T` can be used with [SsbhEnum64].
+/// Writing will fail if the offset or data type does not fit in a [u32].
+/**
+```rust
+# use binrw::BinRead;
+# use ssbh_lib::SsbhEnum32;
+# use ssbh_write::SsbhWrite;
+#[derive(Debug, BinRead, SsbhWrite)]
+#[br(import(data_type: u64))]
+pub enum Data {
+    #[br(pre_assert(data_type == 1u64))]
+    Float(f32),
+    #[br(pre_assert(data_type == 2u64))]
+    Boolean(u32),
+}
+
+impl ssbh_lib::DataType for Data {
+    fn data_type(&self) -> u64 {
+        match self {
+            Data::Float(_) => 1,
+            Data::Boolean(_) => 2
         }
+    }
+}
+
+#[derive(Debug, BinRead, SsbhWrite)]
+pub struct EnumData {
+    data: SsbhEnum32<Data>,
+}
+
+# fn main() {}
+```
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug)]
+pub struct SsbhEnum32<T: DataType> {
+    pub data: Option<T>,
+}
 
-        let saved_pos = reader.stream_position()?;
+impl<T: DataType + PartialEq> PartialEq for SsbhEnum32<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
 
-        let seek_pos = absolute_offset_checked(pos_before_read, relative_offset)?;
-        reader.seek(SeekFrom::Start(seek_pos))?;
-        let value = T::read_options(reader, endian, (data_type,))?;
-        reader.seek(SeekFrom::Start(saved_pos))?;
+impl<T: DataType + Eq> Eq for SsbhEnum32<T> {}
 
-        Ok(SsbhEnum64 {
-            data: RelPtr64::new(value),
+impl<T: DataType + Clone> Clone for SsbhEnum32<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T> BinRead for SsbhEnum32<T>
+where
+    T: DataType + for<'a> BinRead<Args<'a> = (u64,)> + crate::SsbhWrite,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(SsbhEnum32 {
+            data: read_ssbh_enum::<u32, T, R>(reader, endian)?,
         })
     }
 }
 
-impl<T: DataType + SsbhWrite> SsbhWrite for SsbhEnum64<T> {
+impl<T: DataType + SsbhWrite> SsbhWrite for SsbhEnum32<T> {
     fn ssbh_write<W: std::io::Write + std::io::Seek>(
         &self,
         writer: &mut W,
         data_ptr: &mut u64,
     ) -> std::io::Result<()> {
-        // Ensure the next pointer won't point inside this struct.
-        let current_pos = writer.stream_position()?;
-        if *data_ptr < current_pos + self.size_in_bytes() {
-            *data_ptr = current_pos + self.size_in_bytes();
-        }
-        // Write all the fields.
-        self.data.ssbh_write(writer, data_ptr)?;
-        // TODO: How to handle null?
-        self.data
-            .as_ref()
-            .map(DataType::data_type)
-            .unwrap_or(0)
-            .ssbh_write(writer, data_ptr)?;
-        Ok(())
+        write_ssbh_enum::<u32, T, W>(self.data.as_ref(), writer, data_ptr)
     }
 
     fn size_in_bytes(&self) -> u64 {
         // Relative offset + data type
-        8 + 8
+        4 + 4
     }
 }
 
-// Use a macro to avoid specifying the data type in multiple places for variants.
+// The offset and data type have the same width P for both enum types.
+// The data type occurs after the offset, so it's difficult to just derive BinRead.
+fn read_ssbh_enum<P, T, R>(reader: &mut R, endian: Endian) -> BinResult<Option<T>>
+where
+    P: for<'a> BinRead<Args<'a> = ()> + Into<u64>,
+    T: for<'a> BinRead<Args<'a> = (u64,)>,
+    R: Read + Seek,
+{
+    let pos_before_read = reader.stream_position()?;
+    let relative_offset: u64 = P::read_options(reader, endian, ())?.into();
+    let data_type: u64 = P::read_options(reader, endian, ())?.into();
+
+    if relative_offset == 0 {
+        return Ok(None);
+    }
+
+    let saved_pos = reader.stream_position()?;
+
+    let seek_pos = absolute_offset_checked(pos_before_read, relative_offset)?;
+    reader.seek(SeekFrom::Start(seek_pos))?;
+    let value = T::read_options(reader, endian, (data_type,))?;
+    reader.seek(SeekFrom::Start(saved_pos))?;
+
+    Ok(Some(value))
+}
+
+fn write_ssbh_enum<P, T, W>(
+    data: Option<&T>,
+    writer: &mut W,
+    data_ptr: &mut u64,
+) -> std::io::Result<()>
+where
+    P: SsbhWrite + TryFrom<u64> + Default,
+    T: DataType + SsbhWrite,
+    W: std::io::Write + std::io::Seek,
+{
+    // Ensure the next pointer won't point inside this struct.
+    let current_pos = writer.stream_position()?;
+    let size = 2 * std::mem::size_of::<P>() as u64;
+    if *data_ptr < current_pos + size {
+        *data_ptr = current_pos + size;
+    }
+
+    match data {
+        Some(value) => {
+            let alignment = T::alignment_in_bytes();
+            let unaligned_ptr = *data_ptr;
+            *data_ptr = round_up(*data_ptr, alignment);
+            log_offset_write(
+                writer,
+                unaligned_ptr,
+                *data_ptr,
+                alignment,
+                offset_log::alignment_source::<T>(alignment),
+                std::any::type_name::<T>,
+            )?;
+
+            let relative_offset = to_offset::<P>(*data_ptr - current_pos, "offset")?;
+            let data_type = to_offset::<P>(value.data_type(), "data type")?;
+            relative_offset.ssbh_write(writer, data_ptr)?;
+            data_type.ssbh_write(writer, data_ptr)?;
+
+            // Write the data at the specified offset.
+            let pos_after_offset = writer.stream_position()?;
+            writer.seek(SeekFrom::Start(*data_ptr))?;
+            value.ssbh_write(writer, data_ptr)?;
+
+            // Point the data pointer past the current write.
+            let current_pos = writer.stream_position()?;
+            if current_pos > *data_ptr {
+                *data_ptr = round_up(current_pos, alignment);
+            }
+
+            writer.seek(SeekFrom::Start(pos_after_offset))?;
+        }
+        None => {
+            // Null offsets don't increment the data pointer.
+            P::default().ssbh_write(writer, data_ptr)?;
+            P::default().ssbh_write(writer, data_ptr)?;
+        }
+    }
+    Ok(())
+}
+
+fn to_offset<P: TryFrom<u64>>(value: u64, name: &str) -> std::io::Result<P> {
+    P::try_from(value).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Failed to convert {name} {value} to a {}.",
+                std::any::type_name::<P>()
+            ),
+        )
+    })
+}
+
+/// Defines an enum for use with [SsbhEnum64] or [SsbhEnum32] without specifying each data type twice.
+///
+/// The generated enum derives [BinRead] with a `pre_assert` for each variant's data type
+/// and implements [DataType] using the same values.
+/// Additional attributes like derives or doc comments are forwarded to the enum and its variants.
+/**
+```rust
+use ssbh_lib::{ssbh_enum, SsbhEnum32};
+# use binrw::BinRead;
+# use ssbh_write::SsbhWrite;
+
+ssbh_enum!(
+    /// Enum comment.
+    #[derive(Debug, PartialEq)]
+    Data,
+    1u64 => Float(f32),
+    /// Variants can have comments.
+    2u64 => Boolean(u32)
+);
+
+#[derive(Debug, BinRead, SsbhWrite)]
+pub struct EnumData {
+    data: SsbhEnum32<Data>,
+}
+
+# fn main() {}
+```
+ */
+#[macro_export]
 macro_rules! ssbh_enum {
-    ($(#[$attr1:meta])* $name:ident, $($(#[$attr2:meta])* $tag:literal => $variant:ident($body:tt)),*) => {
-        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-        #[derive(Debug, BinRead, SsbhWrite, PartialEq, Clone)]
+    ($(#[$attr1:meta])* $name:ident, $($(#[$attr2:meta])* $tag:literal => $variant:ident($body:ty)),*) => {
+        #[derive(binrw::BinRead, ssbh_write::SsbhWrite)]
         #[br(import(data_type: u64))]
         $(#[$attr1])*
         pub enum $name {
@@ -161,7 +345,7 @@ macro_rules! ssbh_enum {
             ),*
         }
 
-        impl crate::DataType for $name {
+        impl $crate::DataType for $name {
             fn data_type(&self) -> u64 {
                 match self {
                     $(
@@ -169,13 +353,10 @@ macro_rules! ssbh_enum {
                     ),*
                 }
             }
-
         }
     };
 }
 
-pub(crate) use ssbh_enum;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,8 +364,9 @@ mod tests {
     use binrw::BinReaderExt;
     use hexlit::hex;
 
-    ssbh_enum!(
+    crate::ssbh_enum!(
         /// Enum comment.
+        #[derive(Debug, PartialEq, Clone)]
         TestData,
         1 => Float(f32),
         /// Variants can have comments.
@@ -232,6 +414,76 @@ mod tests {
         assert_eq!(4u32, value);
     }
 
+    #[test]
+    fn read_ssbh_enum32_float() {
+        let mut reader = Cursor::new(hex!("08000000 01000000 0000803F"));
+        let value = reader.read_le::<SsbhEnum32<TestData>>().unwrap();
+        assert_eq!(Some(TestData::Float(1.0f32)), value.data);
+
+        // Make sure the reader position is restored.
+        let value = reader.read_le::<f32>().unwrap();
+        assert_eq!(1.0f32, value);
+    }
+
+    #[test]
+    fn read_ssbh_enum32_null() {
+        let mut reader = Cursor::new(hex!("00000000 00000000"));
+        let value = reader.read_le::<SsbhEnum32<TestData>>().unwrap();
+        assert_eq!(None, value.data);
+    }
+
+    #[test]
+    fn read_ssbh_enum32_invalid_data_type() {
+        let mut reader = Cursor::new(hex!("08000000 03000000 04000000"));
+        assert!(reader.read_le::<SsbhEnum32<TestData>>().is_err());
+    }
+
+    #[test]
+    fn ssbh_write_enum32_unsigned() {
+        let value = SsbhEnum32::<TestData> {
+            data: Some(TestData::Unsigned(5u32)),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(writer.into_inner(), hex!("08000000 02000000 05000000"));
+        assert_eq!(12, data_ptr);
+    }
+
+    #[test]
+    fn ssbh_write_enum32_null() {
+        let value = SsbhEnum32::<TestData> { data: None };
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(writer.into_inner(), hex!("00000000 00000000"));
+    }
+
+    #[test]
+    fn ssbh_write_enum32_data_type_overflow() {
+        crate::ssbh_enum!(
+            #[derive(Debug)]
+            LargeData,
+            0x100000000u64 => Unsigned(u32)
+        );
+
+        let value = SsbhEnum32::<LargeData> {
+            data: Some(LargeData::Unsigned(5u32)),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+        let result = value.ssbh_write(&mut writer, &mut data_ptr);
+        assert_eq!(
+            "Failed to convert data type 4294967296 to a u32.",
+            result.unwrap_err().to_string()
+        );
+    }
+
     #[test]
     fn ssbh_write_enum_float() {
         let value = SsbhEnum64::<TestData> {
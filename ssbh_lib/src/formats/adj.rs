@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 #[binread]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Adj {
     #[br(temp)]
     entry_count: u32,
@@ -45,7 +45,7 @@ pub struct Adj {
 /// Adjacency data for a [MeshObject](crate::mesh::MeshObject).
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct AdjEntry {
     /// The index of the [MeshObject](crate::mesh::MeshObject).
     pub mesh_object_index: u32,
@@ -73,7 +73,7 @@ pub enum Anim {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct Group {
     pub group_type: GroupType,
     pub nodes: SsbhArray<Node>,
@@ -81,7 +81,7 @@ pub struct Group {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct Node {
     pub name: SsbhString,
     pub tracks: SsbhArray<TrackV2>,
@@ -89,7 +89,7 @@ pub struct Node {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct TrackV2 {
     pub name: SsbhString,
     pub flags: TrackFlags,
@@ -111,7 +111,7 @@ impl Version for Anim {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct TrackV1 {
     pub name: SsbhString,
     pub track_type: TrackTypeV1,
@@ -120,7 +120,7 @@ pub struct TrackV1 {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct Property {
     pub name: SsbhString,
     /// The index of the corresponding buffer in [buffers](enum.Anim.html#variant.V12.field.buffers).
@@ -130,7 +130,7 @@ pub struct Property {
 // TODO: Is this interpolation data?
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct UnkData {
     pub unk1: SsbhArray<UnkItem1>,
     pub unk2: SsbhArray<UnkItem2>,
@@ -138,7 +138,7 @@ pub struct UnkData {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct UnkItem1 {
     pub unk1: u64,                   // TODO: Always 2?
     pub unk2: SsbhArray<UnkSubItem>, // TODO: Always (0, final_frame_index)?
@@ -146,7 +146,7 @@ pub struct UnkItem1 {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct UnkItem2 {
     pub unk1: SsbhString,            // TODO: node name?
     pub unk2: SsbhArray<UnkSubItem>, // TODO: (frame start, frame end)?
@@ -155,7 +155,7 @@ pub struct UnkItem2 {
 // TODO: These appear to be start and end frame indices.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct UnkSubItem {
     pub unk1: u32,
     pub unk2: u32,
@@ -163,7 +163,7 @@ pub struct UnkSubItem {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[ssbhwrite(pad_after = 2)]
 pub struct TrackFlags {
     pub track_type: TrackTypeV2,
@@ -191,7 +191,7 @@ let flags = TransformFlags::new()
 #[bitfield(bits = 32)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, BinRead, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[br(map = Self::from_bytes)]
 pub struct TransformFlags {
     /// Overrides the translation values with the default resting pose from the skeleton.
@@ -211,7 +211,7 @@ ssbh_write::ssbh_write_modular_bitfield_impl!(TransformFlags, 4);
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u64))]
 #[ssbhwrite(repr(u64))]
 pub enum TrackTypeV1 {
@@ -222,7 +222,7 @@ pub enum TrackTypeV1 {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u8))]
 #[ssbhwrite(repr(u8))]
 pub enum TrackTypeV2 {
@@ -236,7 +236,7 @@ pub enum TrackTypeV2 {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u8))]
 #[ssbhwrite(repr(u8))]
 pub enum CompressionType {
@@ -259,22 +259,123 @@ pub enum CompressionType {
 /// Determines the usage for a [Group].
 ///
 /// This often corresponds with [TrackTypeV2] like [GroupType::Transform] and [TrackTypeV2::Transform].
+/// Unrecognized values are read as [GroupType::Unknown] and written back unchanged,
+/// so files with unsupported groups can still be read and saved without modifying their data.
+/// Use [u64::from] to get the value stored in the file.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
-#[br(repr(u64))]
-#[ssbhwrite(repr(u64))]
+#[derive(Debug, BinRead, Clone, Copy, PartialEq, Eq, Hash)]
+#[br(map = |x: u64| Self::from(x))]
+#[repr(u64)]
 pub enum GroupType {
     Transform = 1,
     Visibility = 2,
     Material = 4,
     Camera = 5,
+    /// A group type value not recognized by ssbh_lib.
+    #[cfg_attr(feature = "strum", strum(disabled))]
+    Unknown(UnknownGroupType),
+}
+
+/// A group type value that doesn't match any of the known [GroupType] variants.
+///
+/// This can only be created with [GroupType::from],
+/// so each value has exactly one representation.
+/**
+```rust
+use ssbh_lib::formats::anim::GroupType;
+
+assert_eq!(GroupType::Transform, GroupType::from(1));
+match GroupType::from(3) {
+    GroupType::Unknown(unk) => assert_eq!(3, unk.value()),
+    _ => unreachable!(),
+}
+```
+ */
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnknownGroupType(u64);
+
+impl UnknownGroupType {
+    /// The group type value stored in the file.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for UnknownGroupType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = u64::deserialize(deserializer)?;
+        match GroupType::from(value) {
+            GroupType::Unknown(unk) => Ok(unk),
+            known => Err(serde::de::Error::custom(format!(
+                "group type value {value} should use the known variant {known:?}"
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for UnknownGroupType {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        match GroupType::from(u64::arbitrary(u)?) {
+            GroupType::Unknown(unk) => Ok(unk),
+            _ => Err(arbitrary::Error::IncorrectFormat),
+        }
+    }
+}
+
+impl From<u64> for GroupType {
+    fn from(value: u64) -> Self {
+        match value {
+            1 => GroupType::Transform,
+            2 => GroupType::Visibility,
+            4 => GroupType::Material,
+            5 => GroupType::Camera,
+            x => GroupType::Unknown(UnknownGroupType(x)),
+        }
+    }
+}
+
+impl From<GroupType> for u64 {
+    fn from(value: GroupType) -> Self {
+        match value {
+            GroupType::Transform => 1,
+            GroupType::Visibility => 2,
+            GroupType::Material => 4,
+            GroupType::Camera => 5,
+            GroupType::Unknown(unk) => unk.0,
+        }
+    }
+}
+
+impl SsbhWrite for GroupType {
+    fn ssbh_write<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        u64::from(*self).ssbh_write(writer, data_ptr)
+    }
+
+    fn size_in_bytes(&self) -> u64 {
+        8
+    }
+
+    fn alignment_in_bytes() -> u64 {
+        8
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use binrw::io::Cursor;
+    use binrw::{io::Cursor, BinReaderExt};
 
     use super::*;
 
@@ -314,4 +415,42 @@ mod tests {
         // Version 2.10 is aligned to 8 bytes.
         assert_eq!(0, buffer.into_inner().len() % 8);
     }
+
+    #[test]
+    fn read_write_group_type() {
+        for (value, group_type) in [
+            (1u64, GroupType::Transform),
+            (5u64, GroupType::Camera),
+            (3u64, GroupType::Unknown(UnknownGroupType(3))),
+            (0xFFu64, GroupType::Unknown(UnknownGroupType(0xFF))),
+        ] {
+            let bytes = value.to_le_bytes();
+            let mut reader = Cursor::new(bytes);
+            assert_eq!(group_type, reader.read_le::<GroupType>().unwrap());
+
+            let mut writer = Cursor::new(Vec::new());
+            group_type.write(&mut writer).unwrap();
+            assert_eq!(bytes.to_vec(), writer.into_inner());
+        }
+    }
+
+    #[cfg(feature = "strum")]
+    #[test]
+    fn group_type_from_repr() {
+        assert_eq!(Some(GroupType::Material), GroupType::from_repr(4));
+        // Unknown values are only created when reading.
+        assert_eq!(None, GroupType::from_repr(3));
+        assert_eq!(None, GroupType::from_repr(6));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_unknown_group_type() {
+        let json = serde_json::to_value(GroupType::from(3)).unwrap();
+        assert_eq!(serde_json::json!({ "Unknown": 3 }), json);
+        assert_eq!(GroupType::from(3), serde_json::from_value(json).unwrap());
+
+        // Known values can't be aliased as unknown.
+        assert!(serde_json::from_value::<GroupType>(serde_json::json!({ "Unknown": 1 })).is_err());
+    }
 }
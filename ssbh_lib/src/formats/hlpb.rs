@@ -11,12 +11,21 @@ use serde::{Deserialize, Serialize};
 use ssbh_write::SsbhWrite;
 
 /// Helper bone constraints.
-/// Compatible with file version 1.1.
+/// Compatible with file version 1.0 and 1.1.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug, BinRead, SsbhWrite, PartialEq, Clone)]
 #[br(import(major_version: u16, minor_version: u16))]
 pub enum Hlpb {
+    #[br(pre_assert(major_version == 1 && minor_version == 0))]
+    V10 {
+        aim_constraints: SsbhArray<AimConstraintV10>,
+        orient_constraints: SsbhArray<OrientConstraint>,
+        /// See [constraint_indices](enum.Hlpb.html#variant.V11.field.constraint_indices).
+        constraint_indices: SsbhArray<u32>,
+        /// See [constraint_types](enum.Hlpb.html#variant.V11.field.constraint_types).
+        constraint_types: SsbhArray<ConstraintType>,
+    },
     #[br(pre_assert(major_version == 1 && minor_version == 1))]
     V11 {
         aim_constraints: SsbhArray<AimConstraint>,
@@ -38,6 +47,7 @@ pub enum Hlpb {
 impl Version for Hlpb {
     fn major_minor_version(&self) -> (u16, u16) {
         match self {
+            Hlpb::V10 { .. } => (1, 0),
             Hlpb::V11 { .. } => (1, 1),
         }
     }
@@ -76,6 +86,26 @@ pub struct AimConstraint {
     pub unk22: f32, // always 0
 }
 
+/// An [AimConstraint] for version 1.0 without the additional `unk17` to `unk22` values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, BinRead, SsbhWrite, PartialEq, Clone)]
+pub struct AimConstraintV10 {
+    pub name: SsbhString,
+    pub aim_bone_name1: SsbhString,
+    pub aim_bone_name2: SsbhString,
+    pub aim_type1: SsbhString,
+    pub aim_type2: SsbhString,
+    pub target_bone_name1: SsbhString,
+    pub target_bone_name2: SsbhString,
+    pub unk1: u32,
+    pub unk2: u32,
+    pub aim: Vector3,
+    pub up: Vector3,
+    pub quat1: Vector4,
+    pub quat2: Vector4,
+}
+
 /// Constrains the orientation of a target bone to a source bone by interpolating XYZ rotations.
 ///
 /// This is similar to the orient constraint in Autodesk Maya.
@@ -114,7 +144,7 @@ pub struct OrientConstraint {
 /// The type of bone constraint for entries in [constraint_types](enum.Hlpb.html#variant.V11.field.constraint_types).
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum ConstraintType {
@@ -123,3 +153,42 @@ pub enum ConstraintType {
     /// [OrientConstraint]
     Orient = 1,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_write_v10() {
+        let hlpb = Hlpb::V10 {
+            aim_constraints: vec![AimConstraintV10 {
+                name: "aim1".into(),
+                aim_bone_name1: "root".into(),
+                aim_bone_name2: "root".into(),
+                aim_type1: "DEFAULT".into(),
+                aim_type2: "DEFAULT".into(),
+                target_bone_name1: "a".into(),
+                target_bone_name2: "a".into(),
+                unk1: 0,
+                unk2: 1,
+                aim: Vector3::new(1.0, 0.0, 0.0),
+                up: Vector3::new(0.0, 1.0, 0.0),
+                quat1: Vector4::new(0.0, 0.0, 0.0, 1.0),
+                quat2: Vector4::new(0.0, 0.0, 0.0, 1.0),
+            }]
+            .into(),
+            orient_constraints: SsbhArray::new(),
+            constraint_indices: vec![0].into(),
+            constraint_types: vec![ConstraintType::Aim].into(),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        hlpb.write(&mut writer).unwrap();
+
+        writer.set_position(0);
+        let new_hlpb = Hlpb::read(&mut writer).unwrap();
+        assert_eq!((1, 0), new_hlpb.major_minor_version());
+        assert_eq!(hlpb, new_hlpb);
+    }
+}
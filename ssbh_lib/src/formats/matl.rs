@@ -5,7 +5,7 @@
 //! The materials in the [Matl] file are assigned to objects in the [Mesh](crate::formats::mesh::Mesh) file by the [Modl](crate::formats::modl::Modl) file.
 
 use crate::ssbh_enum;
-use crate::{Color4f, SsbhString, Vector4, Version};
+use crate::{Color4f, Matrix3x3, SsbhString, Vector3, Vector4, Version};
 use crate::{SsbhArray, SsbhEnum64};
 use binrw::BinRead;
 use ssbh_write::SsbhWrite;
@@ -99,6 +99,9 @@ pub struct AttributeV16 {
 
 ssbh_enum!(
     /// A material parameter value.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     ParamV15,
     1u64 =>  Float(f32),
     2u64 =>  Boolean(u32),
@@ -117,6 +120,9 @@ ssbh_enum!(
 
 ssbh_enum!(
     /// A material parameter value.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     ParamV16,
     1u64 =>  Float(f32),
     2u64 =>  Boolean(u32),
@@ -141,7 +147,7 @@ ssbh_enum!(
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u64))]
 #[ssbhwrite(repr(u64))]
 pub enum ParamId {
@@ -519,7 +525,7 @@ pub enum ParamId {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum FillMode {
@@ -531,7 +537,7 @@ pub enum FillMode {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum CullMode {
@@ -565,7 +571,7 @@ pub struct RasterizerStateV16 {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum WrapMode {
@@ -578,7 +584,7 @@ pub enum WrapMode {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum MinFilter {
@@ -590,7 +596,7 @@ pub enum MinFilter {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum MagFilter {
@@ -602,7 +608,7 @@ pub enum MagFilter {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum FilteringType {
@@ -621,6 +627,7 @@ pub struct Sampler {
     pub min_filter: MinFilter,
     pub mag_filter: MagFilter,
     pub texture_filtering_type: FilteringType,
+    /// The RGBA color for [WrapMode::ClampToBorder].
     pub border_color: Color4f,
     pub unk11: u32,
     pub unk12: u32,
@@ -634,10 +641,11 @@ pub struct Sampler {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum MaxAnisotropy {
+    #[default]
     One = 1,
     Two = 2,
     Four = 4,
@@ -656,11 +664,162 @@ pub struct UvTransform {
     pub translate_v: f32,
 }
 
+impl UvTransform {
+    /// A transform that leaves texture coordinates unchanged.
+    pub const IDENTITY: UvTransform = UvTransform {
+        scale_u: 1.0,
+        scale_v: 1.0,
+        rotation: 0.0,
+        translate_u: 0.0,
+        translate_v: 0.0,
+    };
+
+    /// The column-major homogeneous matrix for transforming UV coordinates.
+    /// Coordinates are scaled first, then rotated counterclockwise by [rotation](#structfield.rotation) radians,
+    /// and then translated.
+    /**
+    ```rust
+    # use ssbh_lib::formats::matl::UvTransform;
+    # use ssbh_lib::{Matrix3x3, Vector3};
+    let transform = UvTransform {
+        scale_u: 2.0,
+        scale_v: 3.0,
+        rotation: 0.0,
+        translate_u: 0.5,
+        translate_v: 0.25,
+    };
+    assert_eq!(
+        Matrix3x3 {
+            col1: Vector3::new(2.0, 0.0, 0.0),
+            col2: Vector3::new(0.0, 3.0, 0.0),
+            col3: Vector3::new(0.5, 0.25, 1.0),
+        },
+        transform.to_matrix()
+    );
+    ```
+    */
+    pub fn to_matrix(&self) -> Matrix3x3 {
+        let (sin, cos) = self.rotation.sin_cos();
+        Matrix3x3 {
+            col1: Vector3::new(cos * self.scale_u, sin * self.scale_u, 0.0),
+            col2: Vector3::new(-sin * self.scale_v, cos * self.scale_v, 0.0),
+            col3: Vector3::new(self.translate_u, self.translate_v, 1.0),
+        }
+    }
+
+    /// Applies the transform to the texture coordinates `uv`.
+    /// This is equivalent to multiplying `[u, v, 1.0]` by [to_matrix](UvTransform::to_matrix).
+    /**
+    ```rust
+    # use ssbh_lib::formats::matl::UvTransform;
+    let transform = UvTransform {
+        scale_u: 2.0,
+        scale_v: 2.0,
+        rotation: 0.0,
+        translate_u: 0.5,
+        translate_v: 0.0,
+    };
+    assert_eq!([2.5, 2.0], transform.transform_uv([1.0, 1.0]));
+    ```
+    */
+    pub fn transform_uv(&self, uv: [f32; 2]) -> [f32; 2] {
+        transform_uv(&self.to_matrix(), uv)
+    }
+
+    /// The matrix that applies `other` and then `self`.
+    /// The result is not always representable as a [UvTransform] for non uniform scale and rotation.
+    /**
+    ```rust
+    # use ssbh_lib::formats::matl::UvTransform;
+    let scale = UvTransform {
+        scale_u: 2.0,
+        scale_v: 2.0,
+        ..UvTransform::IDENTITY
+    };
+    let translate = UvTransform {
+        translate_u: 1.0,
+        ..UvTransform::IDENTITY
+    };
+    let uv = [1.0, 1.0];
+    assert_eq!(
+        translate.transform_uv(scale.transform_uv(uv)),
+        ssbh_lib::formats::matl::transform_uv(&translate.compose(&scale), uv)
+    );
+    ```
+    */
+    pub fn compose(&self, other: &UvTransform) -> Matrix3x3 {
+        mul_mat3(&self.to_matrix(), &other.to_matrix())
+    }
+
+    /// The matrix that undoes this transform or `None` if either scale is zero.
+    /**
+    ```rust
+    # use ssbh_lib::formats::matl::{UvTransform, transform_uv};
+    let transform = UvTransform {
+        scale_u: 2.0,
+        scale_v: 4.0,
+        rotation: 0.0,
+        translate_u: 0.5,
+        translate_v: 0.25,
+    };
+    let inverse = transform.inverse_matrix().unwrap();
+    assert_eq!([1.0, 1.0], transform_uv(&inverse, transform.transform_uv([1.0, 1.0])));
+
+    assert_eq!(None, UvTransform { scale_u: 0.0, ..transform }.inverse_matrix());
+    ```
+    */
+    pub fn inverse_matrix(&self) -> Option<Matrix3x3> {
+        if self.scale_u == 0.0 || self.scale_v == 0.0 {
+            return None;
+        }
+
+        // The inverse of translate * rotate * scale is scale^-1 * rotate^-1 * translate^-1.
+        let inverse_scale = UvTransform {
+            scale_u: 1.0 / self.scale_u,
+            scale_v: 1.0 / self.scale_v,
+            ..UvTransform::IDENTITY
+        };
+        let inverse_rotation = UvTransform {
+            rotation: -self.rotation,
+            ..UvTransform::IDENTITY
+        };
+        let inverse_translation = UvTransform {
+            translate_u: -self.translate_u,
+            translate_v: -self.translate_v,
+            ..UvTransform::IDENTITY
+        };
+        Some(mul_mat3(
+            &inverse_scale.compose(&inverse_rotation),
+            &inverse_translation.to_matrix(),
+        ))
+    }
+}
+
+/// Applies a homogeneous UV matrix like [UvTransform::to_matrix] to the texture coordinates `uv`.
+pub fn transform_uv(matrix: &Matrix3x3, uv: [f32; 2]) -> [f32; 2] {
+    [
+        matrix.col1.x * uv[0] + matrix.col2.x * uv[1] + matrix.col3.x,
+        matrix.col1.y * uv[0] + matrix.col2.y * uv[1] + matrix.col3.y,
+    ]
+}
+
+fn mul_mat3(a: &Matrix3x3, b: &Matrix3x3) -> Matrix3x3 {
+    let a = a.to_cols_array();
+    let b = b.to_cols_array();
+    let mut result = [[0.0; 3]; 3];
+    for (col, b_col) in result.iter_mut().zip(b) {
+        for (row, value) in col.iter_mut().enumerate() {
+            *value = (0..3).map(|i| a[i][row] * b_col[i]).sum();
+        }
+    }
+    Matrix3x3::from_cols_array(&result)
+}
+
 /// Available blending modes for alpha blending.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum BlendFactor {
@@ -682,7 +841,7 @@ pub enum BlendFactor {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum BlendOperation {
@@ -728,9 +887,3 @@ pub struct BlendStateV16 {
     pub unk9: u32,
     pub unk10: u32,
 }
-
-impl Default for MaxAnisotropy {
-    fn default() -> Self {
-        Self::One
-    }
-}
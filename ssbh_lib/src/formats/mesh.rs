@@ -10,6 +10,10 @@ use crate::SsbhByteBuffer;
 use crate::SsbhString;
 use crate::Vector3;
 use crate::Version;
+use crate::{arrays::write_array_elements, round_up, write_buffered, write_ssbh_file, SizeHint};
+
+use binrw::io::{Seek, SeekFrom, Write};
+use std::path::Path;
 
 use binrw::BinRead;
 #[cfg(feature = "serde")]
@@ -50,6 +54,202 @@ impl Version for Mesh {
     }
 }
 
+/// Options for writing [Mesh] files with [Mesh::write_with_options].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshWriteOptions {
+    /// The alignment in bytes for the data of each buffer in [vertex_buffers](struct.MeshInner.html#structfield.vertex_buffers)
+    /// and the [index_buffer](struct.MeshInner.html#structfield.index_buffer).
+    /// The default of 8 matches the alignment used for all other arrays.
+    ///
+    /// Some game files use a larger alignment, so matching the original value
+    /// is necessary to produce binary identical files.
+    /// The alignment must be a power of two.
+    pub buffer_alignment: u64,
+}
+
+impl Default for MeshWriteOptions {
+    fn default() -> Self {
+        Self {
+            buffer_alignment: 8,
+        }
+    }
+}
+
+impl MeshWriteOptions {
+    fn validate(&self) -> std::io::Result<()> {
+        // This also rejects 0 to avoid dividing by zero when aligning.
+        if self.buffer_alignment.is_power_of_two() {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "buffer alignment {} is not a power of two",
+                    self.buffer_alignment
+                ),
+            ))
+        }
+    }
+}
+
+impl Mesh {
+    /// Tries to write the [Mesh] to `writer` using the settings in `options`.
+    /// Writing with [MeshWriteOptions::default] is identical to [Mesh::write].
+    /// Returns an error if [buffer_alignment](struct.MeshWriteOptions.html#structfield.buffer_alignment)
+    /// is not a power of two.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_lib::formats::mesh::{Mesh, MeshWriteOptions};
+
+    let mesh = Mesh::from_file("model.numshb")?;
+    let options = MeshWriteOptions {
+        buffer_alignment: 16,
+    };
+    let mut writer = std::io::Cursor::new(Vec::new());
+    mesh.write_with_options(&mut writer, &options)?;
+    # Ok(()) }
+    ```
+     */
+    pub fn write_with_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &MeshWriteOptions,
+    ) -> std::io::Result<()> {
+        options.validate()?;
+        write_ssbh_file(
+            writer,
+            &MeshWriter {
+                mesh: self,
+                options,
+            },
+            b"HSEM",
+        )
+    }
+
+    /// Tries to write the [Mesh] to `path` using the settings in `options`.
+    /// The entire file is buffered for performance.
+    pub fn write_to_file_with_options<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &MeshWriteOptions,
+    ) -> std::io::Result<()> {
+        // Check the options before creating the file.
+        options.validate()?;
+        let mut file = std::fs::File::create(path)?;
+        write_buffered(&mut file, self.size_hint(), |c| {
+            self.write_with_options(c, options)
+        })
+    }
+}
+
+// Write the mesh like the derived SsbhWrite implementation with a custom buffer alignment.
+// The tests check that the default options match the derived implementation for each version.
+struct MeshWriter<'a> {
+    mesh: &'a Mesh,
+    options: &'a MeshWriteOptions,
+}
+
+impl Version for MeshWriter<'_> {
+    fn major_minor_version(&self) -> (u16, u16) {
+        self.mesh.major_minor_version()
+    }
+}
+
+impl SsbhWrite for MeshWriter<'_> {
+    fn ssbh_write<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        let current_pos = writer.stream_position()?;
+        if *data_ptr < current_pos + self.size_in_bytes() {
+            *data_ptr = current_pos + self.size_in_bytes();
+        }
+
+        let alignment = self.options.buffer_alignment;
+        match self.mesh {
+            Mesh::V8(inner) => write_mesh_inner(inner, writer, data_ptr, alignment)?,
+            Mesh::V9(inner) => write_mesh_inner(inner, writer, data_ptr, alignment)?,
+            Mesh::V10(inner) => write_mesh_inner(inner, writer, data_ptr, alignment)?,
+        }
+
+        // Match the padding and alignment for Mesh.
+        writer.write_all(&[0u8; 16])?;
+        let current_pos = writer.seek(SeekFrom::End(0))?;
+        let aligned_pos = round_up(current_pos, 8);
+        writer.write_all(&vec![0u8; (aligned_pos - current_pos) as usize])?;
+
+        Ok(())
+    }
+
+    fn size_in_bytes(&self) -> u64 {
+        self.mesh.size_in_bytes()
+    }
+}
+
+struct AlignedByteBuffer<'a> {
+    buffer: &'a SsbhByteBuffer,
+    alignment: u64,
+}
+
+impl SsbhWrite for AlignedByteBuffer<'_> {
+    fn ssbh_write<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        self.buffer
+            .ssbh_write_aligned(writer, data_ptr, self.alignment)
+    }
+
+    fn size_in_bytes(&self) -> u64 {
+        self.buffer.size_in_bytes()
+    }
+}
+
+fn write_mesh_inner<A, W1, W>(
+    inner: &MeshInner<A, W1>,
+    writer: &mut W,
+    data_ptr: &mut u64,
+    buffer_alignment: u64,
+) -> std::io::Result<()>
+where
+    A: for<'a> BinRead<Args<'a> = ()> + SsbhWrite,
+    W1: for<'a> BinRead<Args<'a> = ()> + SsbhWrite,
+    W: Write + Seek,
+{
+    let current_pos = writer.stream_position()?;
+    if *data_ptr < current_pos + inner.size_in_bytes() {
+        *data_ptr = current_pos + inner.size_in_bytes();
+    }
+
+    let align = |buffer| AlignedByteBuffer {
+        buffer,
+        alignment: buffer_alignment,
+    };
+
+    inner.model_name.ssbh_write(writer, data_ptr)?;
+    inner.bounding_info.ssbh_write(writer, data_ptr)?;
+    inner.unk1.ssbh_write(writer, data_ptr)?;
+    inner.objects.ssbh_write(writer, data_ptr)?;
+    inner.buffer_sizes.ssbh_write(writer, data_ptr)?;
+    inner.polygon_index_size.ssbh_write(writer, data_ptr)?;
+    write_array_elements(
+        &inner
+            .vertex_buffers
+            .elements
+            .iter()
+            .map(align)
+            .collect::<Vec<_>>(),
+        writer,
+        data_ptr,
+    )?;
+    align(&inner.index_buffer).ssbh_write(writer, data_ptr)?;
+    inner.rigging_buffers.ssbh_write(writer, data_ptr)?;
+    Ok(())
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
@@ -152,7 +352,7 @@ pub struct OrientedBoundingBox {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Copy, Clone, PartialEq, Eq, Hash)]
 #[ssbhwrite(pad_after = 6)]
 pub struct RiggingFlags {
     /// The maximum number of influences assigned to a single vertex by the rigging buffers.
@@ -165,7 +365,7 @@ pub struct RiggingFlags {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct BoneBuffer<W2: for<'a> BinRead<Args<'a> = ()> + SsbhWrite> {
     pub bone_name: SsbhString,
     pub data: W2,
@@ -175,7 +375,7 @@ pub struct BoneBuffer<W2: for<'a> BinRead<Args<'a> = ()> + SsbhWrite> {
 /// determined by [mesh_object_name](#structfield.mesh_object_name) and [mesh_object_subindex](#structfield.mesh_object_subindex).
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 pub struct RiggingGroup<W3: for<'a> BinRead<Args<'a> = ()> + SsbhWrite> {
     pub mesh_object_name: SsbhString,
     pub mesh_object_subindex: u64,
@@ -185,7 +385,7 @@ pub struct RiggingGroup<W3: for<'a> BinRead<Args<'a> = ()> + SsbhWrite> {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 #[br(import(major_version: u16, minor_version: u16))]
 pub enum Attributes {
     #[br(pre_assert(major_version == 1 &&  minor_version == 8))]
@@ -282,7 +482,7 @@ pub struct DepthFlags {
 /// Possible values for [draw_element_type](struct.MeshObject.html#structfield.draw_element_type).
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum DrawElementType {
@@ -296,7 +496,7 @@ pub enum DrawElementType {
 /// This determines the stride and offset between attributes.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum AttributeDataTypeV10 {
@@ -318,7 +518,7 @@ pub enum AttributeDataTypeV10 {
 /// This determines the stride and offset between attributes.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum AttributeDataTypeV8 {
@@ -340,7 +540,7 @@ pub enum AttributeDataTypeV8 {
 /// [attribute_names](struct.MeshAttributeV10.html#structfield.attribute_names) when determing the usage in some cases.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum AttributeUsageV9 {
@@ -356,7 +556,7 @@ pub enum AttributeUsageV9 {
 /// Attributes with an identical usage should each have a unique [subindex](struct.MeshAttributeV8.html#structfield.subindex).
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum AttributeUsageV8 {
@@ -366,3 +566,183 @@ pub enum AttributeUsageV8 {
     TextureCoordinate = 4,
     ColorSet = 8,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binrw::io::Cursor;
+
+    fn mesh() -> Mesh {
+        Mesh::V10(MeshInner {
+            model_name: "a".into(),
+            bounding_info: BoundingInfo::default(),
+            unk1: 0,
+            objects: SsbhArray::new(),
+            buffer_sizes: SsbhArray::from_vec(vec![3, 5]),
+            polygon_index_size: 2,
+            vertex_buffers: SsbhArray::from_vec(vec![
+                SsbhByteBuffer::from_vec(vec![1, 2, 3]),
+                SsbhByteBuffer::from_vec(vec![4, 5, 6, 7, 8]),
+            ]),
+            index_buffer: SsbhByteBuffer::from_vec(vec![9, 10]),
+            rigging_buffers: SsbhArray::new(),
+        })
+    }
+
+    fn buffer_offset(bytes: &[u8], header_offset: usize) -> u64 {
+        let relative =
+            u64::from_le_bytes(bytes[header_offset..header_offset + 8].try_into().unwrap());
+        header_offset as u64 + relative
+    }
+
+    fn mesh_inner<A, W1>(attribute: A, weights: W1) -> MeshInner<A, W1>
+    where
+        A: for<'a> BinRead<Args<'a> = ()> + SsbhWrite,
+        W1: for<'a> BinRead<Args<'a> = ()> + SsbhWrite,
+    {
+        // Use odd sizes and every pointer field to check padding and alignment.
+        MeshInner {
+            model_name: "model".into(),
+            bounding_info: BoundingInfo::default(),
+            unk1: 0,
+            objects: SsbhArray::from_vec(vec![MeshObject {
+                name: "a".into(),
+                subindex: 0,
+                parent_bone_name: "b".into(),
+                vertex_count: 3,
+                vertex_index_count: 3,
+                unk2: 3,
+                vertex_buffer0_offset: 0,
+                vertex_buffer1_offset: 0,
+                vertex_buffer2_offset: 0,
+                vertex_buffer3_offset: 0,
+                stride0: 12,
+                stride1: 0,
+                stride2: 0,
+                stride3: 0,
+                index_buffer_offset: 0,
+                unk8: 4,
+                draw_element_type: DrawElementType::UnsignedShort,
+                use_vertex_skinning: 1,
+                sort_bias: 0,
+                depth_flags: DepthFlags {
+                    disable_depth_write: 0,
+                    disable_depth_test: 0,
+                },
+                bounding_info: BoundingInfo::default(),
+                attributes: SsbhArray::from_vec(vec![attribute]),
+            }]),
+            buffer_sizes: SsbhArray::from_vec(vec![3, 5, 0, 0]),
+            polygon_index_size: 2,
+            vertex_buffers: SsbhArray::from_vec(vec![
+                SsbhByteBuffer::from_vec(vec![1, 2, 3]),
+                SsbhByteBuffer::from_vec(vec![4, 5, 6, 7, 8]),
+                SsbhByteBuffer::new(),
+            ]),
+            index_buffer: SsbhByteBuffer::from_vec(vec![9, 10, 11]),
+            rigging_buffers: SsbhArray::from_vec(vec![RiggingGroup {
+                mesh_object_name: "a".into(),
+                mesh_object_subindex: 0,
+                flags: RiggingFlags {
+                    max_influences: 1,
+                    unk1: 1,
+                },
+                buffers: SsbhArray::from_vec(vec![BoneBuffer {
+                    bone_name: "c".into(),
+                    data: weights,
+                }]),
+            }]),
+        }
+    }
+
+    fn meshes() -> Vec<Mesh> {
+        vec![
+            Mesh::V8(mesh_inner(
+                AttributeV8 {
+                    usage: AttributeUsageV8::Position,
+                    data_type: AttributeDataTypeV8::Float3,
+                    buffer_index: 0,
+                    buffer_offset: 0,
+                    subindex: 0,
+                },
+                SsbhArray::from_vec(vec![VertexWeightV8 {
+                    vertex_index: 1,
+                    vertex_weight: 1.0,
+                }]),
+            )),
+            Mesh::V9(mesh_inner(
+                AttributeV9 {
+                    usage: AttributeUsageV9::Position,
+                    data_type: AttributeDataTypeV8::Float3,
+                    buffer_index: 0,
+                    buffer_offset: 0,
+                    subindex: 0,
+                    name: "Position0".into(),
+                    attribute_names: SsbhArray::from_vec(vec!["Position0".into()]),
+                },
+                SsbhArray::from_vec(vec![VertexWeightV8 {
+                    vertex_index: 1,
+                    vertex_weight: 1.0,
+                }]),
+            )),
+            Mesh::V10(mesh_inner(
+                AttributeV10 {
+                    usage: AttributeUsageV9::Position,
+                    data_type: AttributeDataTypeV10::Float3,
+                    buffer_index: 0,
+                    buffer_offset: 0,
+                    subindex: 0,
+                    name: "Position0".into(),
+                    attribute_names: SsbhArray::from_vec(vec!["Position0".into()]),
+                },
+                SsbhByteBuffer::from_vec(vec![1, 0, 0, 0, 0, 0x80, 0x3f]),
+            )),
+            mesh(),
+        ]
+    }
+
+    #[test]
+    fn write_default_options() {
+        // The custom writer should match the derived implementation for all versions.
+        for mesh in meshes() {
+            let mut expected = Cursor::new(Vec::new());
+            mesh.write(&mut expected).unwrap();
+
+            let mut writer = Cursor::new(Vec::new());
+            mesh.write_with_options(&mut writer, &MeshWriteOptions::default())
+                .unwrap();
+
+            assert_eq!(expected.into_inner(), writer.into_inner());
+        }
+    }
+
+    #[test]
+    fn write_invalid_buffer_alignment() {
+        for buffer_alignment in [0, 3, 24] {
+            let mut writer = Cursor::new(Vec::new());
+            let result =
+                mesh().write_with_options(&mut writer, &MeshWriteOptions { buffer_alignment });
+            assert_eq!(std::io::ErrorKind::InvalidInput, result.unwrap_err().kind());
+        }
+    }
+
+    #[test]
+    fn write_buffer_alignment() {
+        let options = MeshWriteOptions {
+            buffer_alignment: 64,
+        };
+        let mut writer = Cursor::new(Vec::new());
+        mesh().write_with_options(&mut writer, &options).unwrap();
+
+        let bytes = writer.into_inner();
+        // Find the byte buffer headers using the vertex buffers array offset.
+        // Skip the header, magic, version, and fields before vertex_buffers.
+        let vertex_buffers = 16 + 4 + 4 + 8 + 100 + 4 + 16 + 16 + 8;
+        let headers = buffer_offset(&bytes, vertex_buffers) as usize;
+        assert_eq!(0, buffer_offset(&bytes, headers) % 64);
+        assert_eq!(0, buffer_offset(&bytes, headers + 16) % 64);
+        assert_eq!(0, buffer_offset(&bytes, vertex_buffers + 16) % 64);
+
+        assert_eq!(mesh(), Mesh::read(&mut Cursor::new(bytes)).unwrap());
+    }
+}
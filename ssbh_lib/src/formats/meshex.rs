@@ -1,7 +1,7 @@
 use binrw::io::SeekFrom;
 
 use crate::mesh::BoundingSphere;
-use crate::{CString, Ptr64, Vector3};
+use crate::{CString, Ptr64, Ptr64Aligned, Vector3};
 use binrw::{binread, BinRead};
 use modular_bitfield::prelude::*;
 
@@ -33,9 +33,8 @@ pub struct MeshEx {
     #[br(count = entry_count)]
     pub entries: Ptr64<Vec<MeshEntry>>,
 
-    // TODO: Find a way to set the alignment without creating a new type.
-    #[br(args(entry_count as usize))]
-    pub entry_flags: Ptr64<EntryFlags>,
+    #[br(count = entry_count)]
+    pub entry_flags: Ptr64Aligned<Vec<EntryFlag>, 16>,
 
     pub unk1: u32,
 }
@@ -78,7 +77,7 @@ pub struct MeshObjectGroup {
 #[bitfield(bits = 16)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, BinRead, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[br(map = Self::from_bytes)]
 pub struct EntryFlag {
     pub draw_model: bool,
@@ -97,13 +96,36 @@ pub struct EntryFlag {
 
 ssbh_write::ssbh_write_modular_bitfield_impl!(EntryFlag, 2);
 
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
-#[ssbhwrite(alignment = 16)]
-#[br(import(count: usize))]
-pub struct EntryFlags(#[br(count = count)] pub Vec<EntryFlag>);
+#[allow(deprecated)]
+pub use deprecated::EntryFlags;
+
+// The derived implementations would otherwise warn about using a deprecated type.
+#[allow(deprecated)]
+mod deprecated {
+    use super::*;
+
+    /// The flags previously wrapped in a separate type to align the data to 16 bytes.
+    ///
+    /// This is a breaking change for [MeshEx::entry_flags],
+    /// which now uses [Ptr64Aligned] with a `Vec<EntryFlag>` to set the alignment instead.
+    /// Convert existing values with [From] like `Vec::from(flags)`.
+    #[deprecated(note = "MeshEx::entry_flags now uses Vec<EntryFlag>")]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+    #[ssbhwrite(alignment = 16)]
+    #[br(import(count: usize))]
+    pub struct EntryFlags(#[br(count = count)] pub Vec<EntryFlag>);
+
+    impl From<EntryFlags> for Vec<EntryFlag> {
+        fn from(flags: EntryFlags) -> Self {
+            flags.0
+        }
+    }
+}
 
+// The header stores the file length and counts that aren't fields of MeshEx,
+// so this can't use the derived implementation.
 impl SsbhWrite for MeshEx {
     fn ssbh_write<W: std::io::Write + std::io::Seek>(
         &self,
@@ -119,14 +141,13 @@ impl SsbhWrite for MeshEx {
         let entry_flag_count = self
             .entry_flags
             .as_ref()
-            .map(|e| e.0.len() as u32)
+            .map(|e| e.len() as u32)
             .unwrap_or(0u32);
 
         if entry_count != entry_flag_count {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Inconsistent entry count: {entry_count} != {entry_flag_count}"),
-            ));
+            return Err(std::io::Error::other(format!(
+                "Inconsistent entry count: {entry_count} != {entry_flag_count}"
+            )));
         }
 
         // Ensure the next pointer won't point inside this struct.
@@ -153,9 +174,8 @@ impl SsbhWrite for MeshEx {
         self.unk1.ssbh_write(writer, data_ptr)?;
 
         // Meshex files are aligned to 16 bytes.
-        let round_up = |value, n| ((value + n - 1) / n) * n;
         let size = writer.seek(SeekFrom::End(0))?;
-        let new_size = round_up(size, 16);
+        let new_size = size.div_ceil(16) * 16;
         writer.write_all(&vec![0u8; (new_size - size) as usize])?;
 
         // Write the file length.
@@ -11,7 +11,7 @@ use ssbh_write::SsbhWrite;
 /// Compatible with file version 1.7.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 #[br(import(major_version: u16, minor_version: u16))]
 pub enum Modl {
     #[br(pre_assert(major_version == 1 && minor_version == 7))]
@@ -9,7 +9,7 @@ use ssbh_write::SsbhWrite;
 /// A container of file names. Compatible with file version 1.0.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 #[br(import(major_version: u16, minor_version: u16))]
 pub enum Nlst {
     #[br(pre_assert(major_version == 1 && minor_version == 0))]
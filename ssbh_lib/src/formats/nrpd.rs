@@ -1,7 +1,7 @@
 //! The [Nrpd] format describes render pass data.
 //! These files typically use the ".nurpdb" suffix.
 use super::matl::{BlendFactor, CullMode, FillMode, Sampler};
-use crate::enums::ssbh_enum;
+use crate::ssbh_enum;
 use crate::{Color4f, RelPtr64, Version};
 use crate::{SsbhArray, SsbhEnum64, SsbhString};
 use binrw::BinRead;
@@ -52,9 +52,368 @@ impl Version for Nrpd {
     }
 }
 
+/// The results of [Nrpd::dedup_states].
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct StateDedupReport {
+    /// The name of each removed state and the name of the identical state that replaced it.
+    pub merged_states: Vec<(String, String)>,
+    /// The number of references in render passes updated to use the remaining state.
+    pub updated_references: usize,
+    /// The reduction in file size in bytes.
+    pub bytes_saved: u64,
+}
+
+/// A summary of the objects in an [Nrpd] and the references between them.
+/// See [Nrpd::reference_graph].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct NrpdGraph {
+    pub nodes: Vec<NrpdNode>,
+    pub edges: Vec<NrpdEdge>,
+}
+
+/// A frame buffer, state, or render pass in an [NrpdGraph].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NrpdNode {
+    /// The type of object like `"RenderPass"`, `"Framebuffer0"`, or `"RasterizerState"`.
+    pub kind: String,
+    pub name: String,
+}
+
+/// A render pass referencing a frame buffer or state by name.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NrpdEdge {
+    /// The index of the render pass in [nodes](struct.NrpdGraph.html#structfield.nodes).
+    pub from: usize,
+    /// The index of the referenced object in [nodes](struct.NrpdGraph.html#structfield.nodes).
+    pub to: usize,
+    /// The type of render pass data containing the reference like `"FramebufferRenderTarget"`.
+    pub label: String,
+}
+
+impl NrpdGraph {
+    /// Formats the graph using the Graphviz DOT language.
+    /**
+    ```rust
+    use ssbh_lib::formats::nrpd::{NrpdEdge, NrpdGraph, NrpdNode};
+
+    let graph = NrpdGraph {
+        nodes: vec![
+            NrpdNode { kind: "RenderPass".to_string(), name: "nu::Opaque".to_string() },
+            NrpdNode { kind: "Framebuffer0".to_string(), name: "FB_COLOR".to_string() },
+        ],
+        edges: vec![NrpdEdge { from: 0, to: 1, label: "FramebufferRenderTarget".to_string() }],
+    };
+    assert!(graph.to_dot().contains("n0 -> n1 [label=\"FramebufferRenderTarget\"];"));
+    ```
+     */
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph nrpd {\n    rankdir=LR;\n".to_string();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let shape = if node.kind == "RenderPass" {
+                "box"
+            } else {
+                "ellipse"
+            };
+            dot += &format!(
+                "    n{i} [label=\"{}\\n{}\", shape={shape}];\n",
+                escape_dot(&node.name),
+                escape_dot(&node.kind)
+            );
+        }
+        for edge in &self.edges {
+            dot += &format!(
+                "    n{} -> n{} [label=\"{}\"];\n",
+                edge.from,
+                edge.to,
+                escape_dot(&edge.label)
+            );
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Nrpd {
+    /// Summarizes the frame buffers, states, and render passes
+    /// and the frame buffers and states referenced by each render pass.
+    /// This is much smaller than the full data and easier to inspect for large files.
+    /// Names in render passes that don't match any frame buffer or state are not included.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_lib::formats::nrpd::Nrpd;
+
+    let nrpd = Nrpd::from_file("render_pass.nurpdb")?;
+    std::fs::write("render_pass.dot", nrpd.reference_graph().to_dot())?;
+    # Ok(()) }
+    ```
+     */
+    pub fn reference_graph(&self) -> NrpdGraph {
+        let Nrpd::V16 {
+            frame_buffers,
+            state_containers,
+            render_passes,
+            ..
+        } = self;
+
+        let mut nodes: Vec<_> = frame_buffers
+            .elements
+            .iter()
+            .filter_map(|f| f.data.as_ref())
+            .map(|f| NrpdNode {
+                kind: f.kind().to_string(),
+                name: f.name().to_string_lossy(),
+            })
+            .chain(
+                state_containers
+                    .elements
+                    .iter()
+                    .filter_map(|s| s.data.as_ref())
+                    .map(|s| NrpdNode {
+                        kind: s.kind().to_string(),
+                        name: s.name().to_string_lossy(),
+                    }),
+            )
+            .collect();
+        let object_count = nodes.len();
+
+        let mut edges = Vec::new();
+        for pass in &render_passes.elements {
+            let from = nodes.len();
+            nodes.push(NrpdNode {
+                kind: "RenderPass".to_string(),
+                name: pass.name.to_string_lossy(),
+            });
+
+            for data in pass
+                .unk1
+                .elements
+                .iter()
+                .chain(pass.unk2.elements.iter())
+                .filter_map(|d| d.data.as_ref())
+            {
+                for name in data.names() {
+                    let name = name.to_string_lossy();
+                    for to in (0..object_count).filter(|i| nodes[*i].name == name) {
+                        let edge = NrpdEdge {
+                            from,
+                            to,
+                            label: data.kind().to_string(),
+                        };
+                        // Avoid duplicate edges for data that repeats a name.
+                        if !edges.contains(&edge) {
+                            edges.push(edge);
+                        }
+                    }
+                }
+            }
+        }
+
+        NrpdGraph { nodes, edges }
+    }
+
+    /// Removes [state_containers](enum.Nrpd.html#variant.V16.field.state_containers) that are identical
+    /// to a previous state except for the name.
+    /// References to removed states in render passes are renamed to use the first identical state.
+    ///
+    /// The file size is calculated by writing the file before and after removing states.
+    /// Errors while writing are returned instead of reporting an incorrect [bytes_saved](StateDedupReport::bytes_saved).
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_lib::formats::nrpd::Nrpd;
+
+    let mut nrpd = Nrpd::from_file("render_pass.nurpdb")?;
+    let report = nrpd.dedup_states()?;
+    println!("Saved {} bytes", report.bytes_saved);
+    # Ok(()) }
+    ```
+     */
+    pub fn dedup_states(&mut self) -> std::io::Result<StateDedupReport> {
+        let size_before = self.size_in_file()?;
+
+        let Nrpd::V16 {
+            state_containers,
+            render_passes,
+            ..
+        } = self;
+
+        let mut kept: Vec<State> = Vec::new();
+        let mut merged_states = Vec::new();
+        state_containers.elements.retain(|container| {
+            let Some(state) = container.data.as_ref() else {
+                return true;
+            };
+            let unnamed = state.without_name();
+            match kept.iter().find(|k| k.without_name() == unnamed) {
+                Some(existing) => {
+                    merged_states.push((
+                        state.name().to_string_lossy(),
+                        existing.name().to_string_lossy(),
+                    ));
+                    false
+                }
+                None => {
+                    kept.push(state.clone());
+                    true
+                }
+            }
+        });
+
+        let mut updated_references = 0;
+        for pass in &mut render_passes.elements {
+            for data in pass
+                .unk1
+                .elements
+                .iter_mut()
+                .chain(pass.unk2.elements.iter_mut())
+                .filter_map(|d| d.data.as_mut())
+            {
+                for name in data.state_references_mut() {
+                    let old = name.to_string_lossy();
+                    if let Some((_, new)) =
+                        merged_states.iter().find(|(removed, _)| *removed == old)
+                    {
+                        *name = new.as_str().into();
+                        updated_references += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(StateDedupReport {
+            merged_states,
+            updated_references,
+            bytes_saved: size_before.saturating_sub(self.size_in_file()?),
+        })
+    }
+
+    fn size_in_file(&self) -> std::io::Result<u64> {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        self.write(&mut writer)?;
+        Ok(writer.get_ref().len() as u64)
+    }
+}
+
+impl FrameBuffer {
+    fn name(&self) -> &SsbhString {
+        match self {
+            FrameBuffer::Framebuffer0(f) => &f.name,
+            FrameBuffer::Framebuffer1(f) => &f.name,
+            FrameBuffer::UniformBuffer(f) => &f.name,
+            FrameBuffer::Framebuffer3(f) => &f.name,
+            FrameBuffer::Framebuffer4(f) => &f.name,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            FrameBuffer::Framebuffer0(_) => "Framebuffer0",
+            FrameBuffer::Framebuffer1(_) => "Framebuffer1",
+            FrameBuffer::UniformBuffer(_) => "UniformBuffer",
+            FrameBuffer::Framebuffer3(_) => "Framebuffer3",
+            FrameBuffer::Framebuffer4(_) => "Framebuffer4",
+        }
+    }
+}
+
+impl State {
+    fn kind(&self) -> &'static str {
+        match self {
+            State::Sampler(_) => "Sampler",
+            State::RasterizerState(_) => "RasterizerState",
+            State::DepthState(_) => "DepthState",
+            State::BlendState(_) => "BlendState",
+        }
+    }
+
+    fn name(&self) -> &SsbhString {
+        match self {
+            State::Sampler(s) => &s.name,
+            State::RasterizerState(s) => &s.name,
+            State::DepthState(s) => &s.name,
+            State::BlendState(s) => &s.name,
+        }
+    }
+
+    fn without_name(&self) -> Self {
+        let mut state = self.clone();
+        match &mut state {
+            State::Sampler(s) => s.name = "".into(),
+            State::RasterizerState(s) => s.name = "".into(),
+            State::DepthState(s) => s.name = "".into(),
+            State::BlendState(s) => s.name = "".into(),
+        }
+        state
+    }
+}
+
+impl RenderPassData {
+    fn kind(&self) -> &'static str {
+        match self {
+            RenderPassData::FramebufferRtp(_) => "FramebufferRtp",
+            RenderPassData::PassUnk1(_) => "PassUnk1",
+            RenderPassData::UnkTexture1(_) => "UnkTexture1",
+            RenderPassData::UnkLight(_) => "UnkLight",
+            RenderPassData::Unk8(_) => "Unk8",
+            RenderPassData::ColorClear(_) => "ColorClear",
+            RenderPassData::DepthStencilClear(_) => "DepthStencilClear",
+            RenderPassData::Viewport(_) => "Viewport",
+            RenderPassData::Sampler(_) => "Sampler",
+            RenderPassData::BlendState(_) => "BlendState",
+            RenderPassData::RasterizerState(_) => "RasterizerState",
+            RenderPassData::DepthStencilState(_) => "DepthStencilState",
+            RenderPassData::FramebufferRenderTarget(_) => "FramebufferRenderTarget",
+            RenderPassData::FramebufferDepthStencil(_) => "FramebufferDepthStencil",
+            RenderPassData::UnkTexture2(_) => "UnkTexture2",
+        }
+    }
+
+    // The meaning of most fields is unknown, so include every string that could name another object.
+    fn names(&self) -> Vec<&SsbhString> {
+        match self {
+            RenderPassData::FramebufferRtp(d) => vec![&d.unk1, &d.unk2],
+            RenderPassData::PassUnk1(d) => vec![&d.unk1, &d.unk2],
+            RenderPassData::UnkTexture1(d) => vec![&d.unk1, &d.unk2],
+            RenderPassData::UnkLight(d) => vec![&d.unk1, &d.unk2],
+            RenderPassData::Unk8(d) => vec![&d.unk1, &d.unk2],
+            RenderPassData::ColorClear(d) => vec![&d.name],
+            RenderPassData::DepthStencilClear(d) => vec![&d.name],
+            RenderPassData::Viewport(d) => vec![&d.name],
+            RenderPassData::Sampler(d) => vec![&d.unk1, &d.unk2],
+            RenderPassData::BlendState(d)
+            | RenderPassData::RasterizerState(d)
+            | RenderPassData::DepthStencilState(d) => vec![&d.item1, &d.item2],
+            RenderPassData::FramebufferRenderTarget(name)
+            | RenderPassData::FramebufferDepthStencil(name) => vec![name],
+            RenderPassData::UnkTexture2(d) => vec![&d.unk1],
+        }
+    }
+
+    fn state_references_mut(&mut self) -> Vec<&mut SsbhString> {
+        match self {
+            RenderPassData::Sampler(s) => vec![&mut s.unk1, &mut s.unk2],
+            RenderPassData::BlendState(s)
+            | RenderPassData::RasterizerState(s)
+            | RenderPassData::DepthStencilState(s) => vec![&mut s.item1, &mut s.item2],
+            _ => Vec::new(),
+        }
+    }
+}
+
 // TODO: Inputs?
 // TODO: These can just use named fields?
 ssbh_enum!(
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     FrameBuffer,
     0u64 => Framebuffer0(Framebuffer0),
     1u64 => Framebuffer1(Framebuffer1),
@@ -68,7 +427,7 @@ ssbh_enum!(
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "strum", derive(FromRepr, Display, EnumIter, EnumString))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u64))]
 #[ssbhwrite(repr(u64))]
 pub enum UnkFormat {
@@ -204,6 +563,9 @@ pub struct DepthState {
 }
 
 ssbh_enum!(
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     State,
     0u64 => Sampler(NrpdSampler),
     1u64 => RasterizerState(NrpdRasterizerState),
@@ -213,6 +575,9 @@ ssbh_enum!(
 
 // TODO: The variant names are just guesses based on the string values.
 ssbh_enum!(
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     RenderPassData,
     0u64 =>  FramebufferRtp(RenderPassData0),
     1u64 =>  PassUnk1(RenderPassData1), // TODO
@@ -359,6 +724,9 @@ pub struct RenderPassContainer {
 
 // TODO: Find a better way to handle shared offsets.
 ssbh_enum!(
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     RenderPassUnkData,
     0 => UnkDataUnk0(()), // TODO: These offsets can be shared?
     3 => UnkDataUnk3(Unk3Data)
@@ -432,6 +800,9 @@ pub struct UnkItem24 {
 }
 
 ssbh_enum!(
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    #[derive(Debug, PartialEq, Clone)]
     UnkItem2,
     0u64 => UnkItem20(UnkItem20),
     1u64 => UnkItem21(UnkItem21),
@@ -446,3 +817,182 @@ pub struct UnkItem3 {
     pub name: SsbhString,
     pub value: SsbhString,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rasterizer_state(name: &str, depth_bias: f32) -> SsbhEnum64<State> {
+        SsbhEnum64 {
+            data: RelPtr64::new(State::RasterizerState(NrpdRasterizerState {
+                name: name.into(),
+                fill_mode: FillMode::Solid,
+                cull_mode: CullMode::Back,
+                depth_bias,
+                unk4: 0.0,
+                unk5: 0.0,
+                unk6: 0,
+            })),
+        }
+    }
+
+    fn state_reference(name: &str) -> SsbhEnum64<RenderPassData> {
+        SsbhEnum64 {
+            data: RelPtr64::new(RenderPassData::RasterizerState(StringPair {
+                item1: "RasterizerState".into(),
+                item2: name.into(),
+            })),
+        }
+    }
+
+    fn nrpd(states: Vec<SsbhEnum64<State>>, references: Vec<SsbhEnum64<RenderPassData>>) -> Nrpd {
+        Nrpd::V16 {
+            frame_buffers: SsbhArray::new(),
+            state_containers: states.into(),
+            render_passes: vec![RenderPassContainer {
+                name: "pass".into(),
+                unk1: references.into(),
+                unk2: SsbhArray::new(),
+                unk3: SsbhEnum64 {
+                    data: RelPtr64::new(RenderPassUnkData::UnkDataUnk0(())),
+                },
+            }]
+            .into(),
+            unk_string_list1: SsbhArray::new(),
+            unk_string_list2: SsbhArray::new(),
+            unk_list: SsbhArray::new(),
+            unk_width1: 0,
+            unk_height1: 0,
+            unk3: 0,
+            unk4: 0,
+            unk5: 0,
+            unk6: 0,
+            unk7: 0,
+            unk8: 0,
+            unk9: "".into(),
+            unk_width2: 0,
+            unk_height2: 0,
+            unk10: 0,
+        }
+    }
+
+    #[test]
+    fn dedup_identical_states() {
+        let mut data = nrpd(
+            vec![
+                rasterizer_state("a", 0.0),
+                rasterizer_state("b", 1.0),
+                rasterizer_state("c", 0.0),
+            ],
+            vec![
+                state_reference("a"),
+                state_reference("b"),
+                state_reference("c"),
+            ],
+        );
+
+        let report = data.dedup_states().unwrap();
+        assert_eq!(
+            vec![("c".to_string(), "a".to_string())],
+            report.merged_states
+        );
+        assert_eq!(1, report.updated_references);
+        assert!(report.bytes_saved > 0);
+
+        assert_eq!(
+            nrpd(
+                vec![rasterizer_state("a", 0.0), rasterizer_state("b", 1.0)],
+                vec![
+                    state_reference("a"),
+                    state_reference("b"),
+                    state_reference("a"),
+                ],
+            ),
+            data
+        );
+    }
+
+    #[test]
+    fn reference_graph() {
+        let mut data = nrpd(
+            vec![rasterizer_state("a", 0.0), rasterizer_state("b", 1.0)],
+            vec![
+                state_reference("b"),
+                state_reference("b"),
+                state_reference("missing"),
+                SsbhEnum64 {
+                    data: RelPtr64::new(RenderPassData::FramebufferRenderTarget("fb".into())),
+                },
+            ],
+        );
+        let Nrpd::V16 { frame_buffers, .. } = &mut data;
+        *frame_buffers = vec![SsbhEnum64 {
+            data: RelPtr64::new(FrameBuffer::Framebuffer4(Framebuffer4 {
+                name: "fb".into(),
+                width: 1,
+                height: 1,
+                unk3: 0,
+            })),
+        }]
+        .into();
+
+        let node = |kind: &str, name: &str| NrpdNode {
+            kind: kind.to_string(),
+            name: name.to_string(),
+        };
+        let edge = |from, to, label: &str| NrpdEdge {
+            from,
+            to,
+            label: label.to_string(),
+        };
+        assert_eq!(
+            NrpdGraph {
+                nodes: vec![
+                    node("Framebuffer4", "fb"),
+                    node("RasterizerState", "a"),
+                    node("RasterizerState", "b"),
+                    node("RenderPass", "pass"),
+                ],
+                edges: vec![
+                    edge(3, 2, "RasterizerState"),
+                    edge(3, 0, "FramebufferRenderTarget")
+                ],
+            },
+            data.reference_graph()
+        );
+    }
+
+    #[test]
+    fn reference_graph_to_dot() {
+        let graph = NrpdGraph {
+            nodes: vec![
+                NrpdNode {
+                    kind: "RenderPass".to_string(),
+                    name: "pass".to_string(),
+                },
+                NrpdNode {
+                    kind: "BlendState".to_string(),
+                    name: "\"a\"".to_string(),
+                },
+            ],
+            edges: vec![NrpdEdge {
+                from: 0,
+                to: 1,
+                label: "BlendState".to_string(),
+            }],
+        };
+        assert_eq!(
+            "digraph nrpd {\n    rankdir=LR;\n    n0 [label=\"pass\\nRenderPass\", shape=box];\n    n1 [label=\"\\\"a\\\"\\nBlendState\", shape=ellipse];\n    n0 -> n1 [label=\"BlendState\"];\n}\n",
+            graph.to_dot()
+        );
+    }
+
+    #[test]
+    fn dedup_unique_states() {
+        let mut data = nrpd(
+            vec![rasterizer_state("a", 0.0), rasterizer_state("b", 1.0)],
+            Vec::new(),
+        );
+        assert_eq!(StateDedupReport::default(), data.dedup_states().unwrap());
+    }
+}
@@ -8,7 +8,7 @@ use ssbh_write::SsbhWrite;
 /// Compatible with file version 1.2.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 #[br(import(major_version: u16, minor_version: u16))]
 pub enum Shdr {
     #[br(pre_assert(major_version == 1 && minor_version == 2))]
@@ -25,7 +25,7 @@ impl Version for Shdr {
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, PartialEq, Eq)]
 #[ssbhwrite(pad_after = 16)]
 pub struct Shader {
     pub name: SsbhString,
@@ -41,7 +41,7 @@ pub struct Shader {
 #[repr(u32)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Eq, Hash)]
 #[br(repr(u32))]
 #[ssbhwrite(repr(u32))]
 pub enum ShaderStage {
@@ -59,6 +59,13 @@ mesh.write(&mut writer)?;
  */
 //! For the best performance when writing directly to a file, it's recommended to use the buffered `write_to_file` methods.
 //! Using other writers like [std::io::BufWriter] may give poor performance due to how relative offsets are written.
+//!
+//! The file types also implement [binrw::BinWrite] for writing SSBH data as part of other binrw types.
+//! The bytes are identical to writing a standalone file, so embedded files can be read with [SsbhFile::read_at].
+//! Only the file types in [prelude] and [SsbhFile] implement [binrw::BinWrite].
+//! Inner types like [SsbhArray] or [SsbhString] only implement [SsbhWrite]
+//! since their relative offsets depend on the data pointer for the rest of the file.
+//! SSBH formats are always little endian, so writing with [binrw::Endian::Big] returns an error.
 /*!
 ```no_run
 # fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -129,9 +136,20 @@ mod strings;
 pub use strings::{CString, CString1, SsbhString, SsbhString8};
 
 mod enums;
-pub use enums::{DataType, SsbhEnum64};
+pub use enums::{DataType, SsbhEnum32, SsbhEnum64};
+
+pub mod compatibility;
+
+mod peek;
+pub use peek::{peek_format, peek_format_from_reader, SsbhFormat, SsbhKind};
+
+pub mod offset_log;
 
-pub(crate) use enums::ssbh_enum;
+pub mod read_limits;
+
+#[cfg(feature = "scan")]
+pub mod scan;
+use offset_log::{AlignmentSource, OffsetWrite};
 
 /// Common imports for supported formats.
 pub mod prelude {
@@ -160,9 +178,10 @@ use binrw::{
 use thiserror::Error;
 
 use binrw::io::Write;
-use ssbh_write::SsbhWrite;
+use ssbh_write::{SsbhWrite, SsbhWriteAligned};
 use std::fs;
 use std::marker::PhantomData;
+use std::ops::Range;
 use std::path::Path;
 
 #[cfg(feature = "serde")]
@@ -185,6 +204,68 @@ impl SsbhFile {
         Ok(ssbh)
     }
 
+    /// Tries to read one of the SSBH types embedded in `reader` starting at `base_offset`.
+    /// Offsets are treated as relative to `base_offset` instead of the start of `reader`.
+    /// This allows reading files stored in archives or other containers without copying the data.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_lib::SsbhFile;
+    use std::io::BufReader;
+
+    let mut reader = BufReader::new(std::fs::File::open("archive.bin")?);
+    let ssbh = SsbhFile::read_at(&mut reader, 0x1000)?;
+    # Ok(()) }
+    ```
+     */
+    pub fn read_at<R: Read + Seek>(
+        reader: &mut R,
+        base_offset: u64,
+    ) -> Result<Self, ReadSsbhError> {
+        let mut reader = OffsetReader::new(reader, base_offset)?;
+        let ssbh = reader.read_le::<SsbhFile>()?;
+        Ok(ssbh)
+    }
+
+    /// Tries to read one of the SSBH types from `path` like [SsbhFile::from_file]
+    /// and returns the range of bytes after the end of the data that were never read.
+    /// See [SsbhFile::read_with_unused_bytes].
+    pub fn from_file_with_unused_bytes<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(Self, Option<Range<u64>>), ReadSsbhError> {
+        let mut file = Cursor::new(fs::read(path)?);
+        Self::read_with_unused_bytes(&mut file)
+    }
+
+    /// Tries to read one of the SSBH types from `reader` like [SsbhFile::read]
+    /// and returns the range of bytes after the end of the data that were never read.
+    ///
+    /// Files extracted from archives may contain extra padding at the end that the game ignores.
+    /// The range is `None` if the data extends to the end of `reader`.
+    /**
+    ```rust no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use ssbh_lib::SsbhFile;
+
+    let (ssbh, unused) = SsbhFile::from_file_with_unused_bytes("model.numdlb")?;
+    if let Some(range) = unused {
+        println!("{} unused bytes at offset {}", range.end - range.start, range.start);
+    }
+    # Ok(()) }
+    ```
+     */
+    pub fn read_with_unused_bytes<R: Read + Seek>(
+        reader: &mut R,
+    ) -> Result<(Self, Option<Range<u64>>), ReadSsbhError> {
+        let mut reader = ExtentReader::new(reader)?;
+        let ssbh = reader.read_le::<SsbhFile>()?;
+
+        let end = reader.end;
+        let len = reader.seek(SeekFrom::End(0))?;
+        let unused = (end < len).then_some(end..len);
+        Ok((ssbh, unused))
+    }
+
     /// Writes the data to the given writer.
     /// For best performance when writing to a file, use `write_to_file` instead.
     pub fn write<W: std::io::Write + Seek>(&self, writer: &mut W) -> std::io::Result<()> {
@@ -196,9 +277,66 @@ impl SsbhFile {
     /// The entire file is buffered for performance.
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
         let mut file = std::fs::File::create(path)?;
-        write_buffered(&mut file, |c| write_ssbh_header_and_data(c, &self.data))?;
+        write_buffered(&mut file, self.size_hint(), |c| {
+            write_ssbh_header_and_data(c, &self.data)
+        })?;
         Ok(())
     }
+
+    /// Writes the data to a `writer` that does not implement [Seek] like a network socket or compression stream.
+    ///
+    /// This is not a streaming write.
+    /// Relative offsets require seeking backwards, so the entire file is buffered in memory
+    /// and then passed to `writer` all at once.
+    pub fn write_to_stream<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_buffered(writer, self.size_hint(), |c| {
+            write_ssbh_header_and_data(c, &self.data)
+        })
+    }
+
+    /// Calculates a hash of the file's contents that ignores offsets and padding bytes.
+    /// Files with the same values have the same hash even if the exporters used different layouts.
+    ///
+    /// The hash is a 64-bit FNV-1a hash of the bytes written by [SsbhFile::write],
+    /// so the hash will not change between program runs.
+    /// Floats are compared by their bits, so `0.0` and `-0.0` have different hashes.
+    /// Changes to the written layout in future versions may change the hash.
+    pub fn content_hash(&self) -> std::io::Result<u64> {
+        content_hash(|c| write_ssbh_header_and_data(c, &self.data))
+    }
+}
+
+/// Writes the file as a standalone SSBH file.
+/// Writing with [binrw::Endian::Big] returns an error since SSBH formats are always little endian.
+impl binrw::BinWrite for SsbhFile {
+    type Args<'a> = ();
+
+    fn write_options<W: std::io::Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        _args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_binrw(writer, endian, self.size_hint(), |c| {
+            write_ssbh_header_and_data(c, &self.data)
+        })
+    }
+}
+
+/// Hashes the bytes from `write_data`.
+/// Writing recalculates all offsets and padding, so the hash only depends on the values.
+pub(crate) fn content_hash<F: Fn(&mut Cursor<Vec<u8>>) -> std::io::Result<()>>(
+    write_data: F,
+) -> std::io::Result<u64> {
+    let mut writer = Cursor::new(Vec::new());
+    write_data(&mut writer)?;
+    Ok(fnv1a(writer.get_ref()))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
 }
 
 /// Errors while reading SSBH files.
@@ -241,6 +379,18 @@ macro_rules! ssbh_read_write_impl {
                 }
             }
 
+            /// Tries to read the current SSBH type embedded in `reader` starting at `base_offset`.
+            /// See [SsbhFile::read_at].
+            pub fn read_at<R: Read + Seek>(
+                reader: &mut R,
+                base_offset: u64,
+            ) -> Result<Self, ReadSsbhError> {
+                match SsbhFile::read_at(reader, base_offset)?.data {
+                    $ty2(v) => Ok(v.data),
+                    _ => Err(ReadSsbhError::InvalidSsbhType),
+                }
+            }
+
             /// Tries to write the SSBH type to `writer`.
             /// For best performance when writing to a file, use `write_to_file` instead.
             pub fn write<W: std::io::Write + Seek>(&self, writer: &mut W) -> std::io::Result<()> {
@@ -252,9 +402,46 @@ macro_rules! ssbh_read_write_impl {
             /// The entire file is buffered for performance.
             pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
                 let mut file = std::fs::File::create(path)?;
-                write_buffered(&mut file, |c| write_ssbh_file(c, self, $magic))?;
+                write_buffered(&mut file, self.size_hint(), |c| {
+                    write_ssbh_file(c, self, $magic)
+                })?;
                 Ok(())
             }
+
+            /// Tries to write the SSBH type to a `writer` that does not implement [Seek].
+            /// This is not a streaming write, so the entire file is buffered in memory before writing.
+            /// See [SsbhFile::write_to_stream].
+            pub fn write_to_stream<W: std::io::Write>(
+                &self,
+                writer: &mut W,
+            ) -> std::io::Result<()> {
+                write_buffered(writer, self.size_hint(), |c| {
+                    write_ssbh_file(c, self, $magic)
+                })
+            }
+
+            /// Calculates a hash of the contents that ignores offsets and padding bytes.
+            /// See [SsbhFile::content_hash].
+            pub fn content_hash(&self) -> std::io::Result<u64> {
+                content_hash(|c| write_ssbh_file(c, self, $magic))
+            }
+        }
+
+        /// Writes the type as a standalone file.
+        /// Writing with [binrw::Endian::Big] returns an error since the format is always little endian.
+        impl binrw::BinWrite for $ty {
+            type Args<'a> = ();
+
+            fn write_options<W: std::io::Write + Seek>(
+                &self,
+                writer: &mut W,
+                endian: Endian,
+                _args: Self::Args<'_>,
+            ) -> BinResult<()> {
+                write_binrw(writer, endian, self.size_hint(), |c| {
+                    write_ssbh_file(c, self, $magic)
+                })
+            }
         }
     };
 }
@@ -290,9 +477,42 @@ macro_rules! read_write_impl {
             /// The entire file is buffered for performance.
             pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
                 let mut file = std::fs::File::create(path)?;
-                write_buffered(&mut file, |c| self.write(c))?;
+                write_buffered(&mut file, self.size_hint(), |c| self.write(c))?;
                 Ok(())
             }
+
+            /// Tries to write the type to a `writer` that does not implement [Seek].
+            /// This is not a streaming write, so the entire file is buffered in memory before writing.
+            /// See [SsbhFile::write_to_stream].
+            pub fn write_to_stream<W: std::io::Write>(
+                &self,
+                writer: &mut W,
+            ) -> std::io::Result<()> {
+                write_buffered(writer, self.size_hint(), |c| self.write(c))
+            }
+
+            /// Calculates a hash of the contents that ignores offsets and padding bytes.
+            /// See [SsbhFile::content_hash].
+            pub fn content_hash(&self) -> std::io::Result<u64> {
+                content_hash(|c| self.write(c))
+            }
+        }
+
+        /// Writes the type as a standalone file.
+        /// Writing with [binrw::Endian::Big] returns an error since the format is always little endian.
+        impl binrw::BinWrite for $ty {
+            type Args<'a> = ();
+
+            fn write_options<W: std::io::Write + Seek>(
+                &self,
+                writer: &mut W,
+                endian: Endian,
+                _args: Self::Args<'_>,
+            ) -> BinResult<()> {
+                write_binrw(writer, endian, self.size_hint(), |c| {
+                    SsbhWrite::write(self, c)
+                })
+            }
         }
     };
 }
@@ -327,11 +547,57 @@ pub(crate) fn absolute_offset_checked(
     }
 }
 
+// Methods for pointer types that store the pointed to value as Option<T> in the first field.
+// Most methods take &self to avoid moving out of the Deref to Option<T>.
+macro_rules! ptr_option_methods {
+    () => {
+        /// Returns `true` if the pointer is null.
+        /// Null pointers are written as an offset of `0`.
+        pub fn is_null(&self) -> bool {
+            self.0.is_none()
+        }
+
+        /// Converts to an [Option] containing a reference to the value.
+        pub fn as_ref(&self) -> Option<&T> {
+            self.0.as_ref()
+        }
+
+        /// Converts to an [Option] containing a mutable reference to the value.
+        pub fn as_mut(&mut self) -> Option<&mut T> {
+            self.0.as_mut()
+        }
+
+        /// Applies `f` to a reference to the value or returns [None] if the pointer is null.
+        /// Unlike [Option::map], this does not consume the pointer.
+        pub fn map<U, F: FnOnce(&T) -> U>(&self, f: F) -> Option<U> {
+            self.0.as_ref().map(f)
+        }
+
+        /// Applies `f` to a reference to the value or returns `default` if the pointer is null.
+        pub fn map_or<U, F: FnOnce(&T) -> U>(&self, default: U, f: F) -> U {
+            self.0.as_ref().map_or(default, f)
+        }
+
+        /// Consumes the pointer and returns the value or [None] if the pointer is null.
+        pub fn into_option(self) -> Option<T> {
+            self.0
+        }
+
+        /// Consumes the pointer and returns the value or the default value if the pointer is null.
+        pub fn unwrap_or_default(self) -> T
+        where
+            T: Default,
+        {
+            self.0.unwrap_or_default()
+        }
+    };
+}
+
 /// A file pointer relative to the start of the reader.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 #[repr(transparent)]
 pub struct Ptr<P, T>(
     Option<T>,
@@ -348,6 +614,8 @@ impl<P, T> Ptr<P, T> {
     pub fn null() -> Self {
         Self(None, PhantomData::<P>)
     }
+
+    ptr_option_methods!();
 }
 
 /// A 16 bit file pointer relative to the start of the reader.
@@ -373,20 +641,30 @@ where
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        let offset = P::read_options(reader, endian, P::Args::default())?;
-        if offset == P::default() {
-            return Ok(Self::null());
-        }
+        read_ptr::<R, P, T>(reader, endian, args).map(|value| Self(value, PhantomData::<P>))
+    }
+}
 
-        let saved_pos = reader.stream_position()?;
+fn read_ptr<R, P, T>(reader: &mut R, endian: Endian, args: T::Args<'_>) -> BinResult<Option<T>>
+where
+    R: Read + Seek,
+    P: BinRead + Default + PartialEq + Into<u64>,
+    T: BinRead,
+    for<'a> P: BinRead<Args<'a> = ()>,
+{
+    let offset = P::read_options(reader, endian, P::Args::default())?;
+    if offset == P::default() {
+        return Ok(None);
+    }
 
-        reader.seek(SeekFrom::Start(offset.into()))?;
-        let value = T::read_options(reader, endian, args)?;
+    let saved_pos = reader.stream_position()?;
 
-        reader.seek(SeekFrom::Start(saved_pos))?;
+    reader.seek(SeekFrom::Start(offset.into()))?;
+    let value = T::read_options(reader, endian, args)?;
 
-        Ok(Self::new(value))
-    }
+    reader.seek(SeekFrom::Start(saved_pos))?;
+
+    Ok(Some(value))
 }
 
 impl<P, T> core::ops::Deref for Ptr<P, T> {
@@ -403,6 +681,95 @@ impl<P, T> core::ops::DerefMut for Ptr<P, T> {
     }
 }
 
+/// A file pointer relative to the start of the reader like [Ptr]
+/// but with an alignment of `A` bytes for the pointed to data instead of the alignment of `T`.
+///
+/// This allows over-aligned data without defining a new type just for the alignment.
+/**
+```rust
+use ssbh_lib::Ptr64Aligned;
+use ssbh_write::SsbhWrite;
+
+#[derive(SsbhWrite)]
+struct Example {
+    data: Ptr64Aligned<Vec<u8>, 16>,
+}
+```
+
+The alignment must be a power of two, which is checked at compile time when writing.
+```rust compile_fail
+use ssbh_lib::Ptr64Aligned;
+use ssbh_write::SsbhWrite;
+
+let mut writer = std::io::Cursor::new(Vec::new());
+Ptr64Aligned::<u8, 0>::new(1u8).write(&mut writer).unwrap();
+```
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[repr(transparent)]
+pub struct PtrAligned<P, T, const A: u64>(
+    Option<T>,
+    #[cfg_attr(feature = "serde", serde(skip))] PhantomData<P>,
+);
+
+impl<P, T, const A: u64> PtrAligned<P, T, A> {
+    /// Creates an absolute offset for a value that is not null.
+    pub fn new(value: T) -> Self {
+        Self(Some(value), PhantomData::<P>)
+    }
+
+    /// Creates an absolute offset for a null value.
+    pub fn null() -> Self {
+        Self(None, PhantomData::<P>)
+    }
+
+    ptr_option_methods!();
+}
+
+/// A 16 bit file pointer relative to the start of the reader with an alignment of `A` bytes.
+pub type Ptr16Aligned<T, const A: u64> = PtrAligned<u16, T, A>;
+
+/// A 32 bit file pointer relative to the start of the reader with an alignment of `A` bytes.
+pub type Ptr32Aligned<T, const A: u64> = PtrAligned<u32, T, A>;
+
+/// A 64 bit file pointer relative to the start of the reader with an alignment of `A` bytes.
+pub type Ptr64Aligned<T, const A: u64> = PtrAligned<u64, T, A>;
+
+impl<P, T, const A: u64> BinRead for PtrAligned<P, T, A>
+where
+    P: BinRead + Default + PartialEq + Into<u64>,
+    T: BinRead,
+    for<'a> P: BinRead<Args<'a> = ()>,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_ptr::<R, P, T>(reader, endian, args).map(|value| Self(value, PhantomData::<P>))
+    }
+}
+
+impl<P, T, const A: u64> core::ops::Deref for PtrAligned<P, T, A> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P, T, const A: u64> core::ops::DerefMut for PtrAligned<P, T, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 /// A 64 bit file pointer relative to the start of the pointer type.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -420,6 +787,8 @@ impl<T> RelPtr64<T> {
     pub fn null() -> Self {
         Self(None)
     }
+
+    ptr_option_methods!();
 }
 
 impl<T: Clone> Clone for RelPtr64<T> {
@@ -436,6 +805,24 @@ impl<T: PartialEq> PartialEq for RelPtr64<T> {
 
 impl<T: Eq> Eq for RelPtr64<T> {}
 
+impl<T: PartialOrd> PartialOrd for RelPtr64<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord> Ord for RelPtr64<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: std::hash::Hash> std::hash::Hash for RelPtr64<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 impl<T> From<Option<T>> for RelPtr64<T> {
     fn from(v: Option<T>) -> Self {
         match v {
@@ -493,7 +880,7 @@ impl<T> core::ops::DerefMut for RelPtr64<T> {
 /// The container type for the various SSBH formats.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, Debug, Clone, PartialEq)]
 #[br(magic = b"HBSS")]
 pub struct SsbhFile {
     #[br(align_before = 0x10)]
@@ -503,7 +890,7 @@ pub struct SsbhFile {
 /// The associated magic and format for each SSBH type.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(BinRead, Debug)]
+#[derive(BinRead, Debug, Clone, PartialEq)]
 pub enum Ssbh {
     #[br(magic = b"BPLH")]
     Hlpb(Versioned<hlpb::Hlpb>),
@@ -541,7 +928,7 @@ pub enum Ssbh {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Versioned<T: BinRead<Args<'static> = (u16, u16)>> {
     #[br(temp)]
     major_version: u16,
@@ -585,9 +972,26 @@ pub trait Version {
     fn major_minor_version(&self) -> (u16, u16);
 }
 
+impl Version for Ssbh {
+    fn major_minor_version(&self) -> (u16, u16) {
+        match self {
+            Ssbh::Hlpb(v) => v.data.major_minor_version(),
+            Ssbh::Matl(v) => v.data.major_minor_version(),
+            Ssbh::Modl(v) => v.data.major_minor_version(),
+            Ssbh::Mesh(v) => v.data.major_minor_version(),
+            Ssbh::Skel(v) => v.data.major_minor_version(),
+            Ssbh::Anim(v) => v.data.major_minor_version(),
+            Ssbh::Nlst(v) => v.data.major_minor_version(),
+            Ssbh::Nrpd(v) => v.data.major_minor_version(),
+            Ssbh::Nufx(v) => v.data.major_minor_version(),
+            Ssbh::Shdr(v) => v.data.major_minor_version(),
+        }
+    }
+}
+
 pub(crate) fn round_up(value: u64, n: u64) -> u64 {
     // Find the next largest multiple of n.
-    ((value + n - 1) / n) * n
+    value.div_ceil(n) * n
 }
 
 pub(crate) fn write_relative_offset<W: Write + Seek>(
@@ -599,6 +1003,28 @@ pub(crate) fn write_relative_offset<W: Write + Seek>(
     Ok(())
 }
 
+pub(crate) fn log_offset_write<W: Seek, F: FnOnce() -> &'static str>(
+    writer: &mut W,
+    unaligned_ptr: u64,
+    data_ptr: u64,
+    alignment: u64,
+    source: AlignmentSource,
+    type_name: F,
+) -> std::io::Result<()> {
+    if offset_log::is_recording() {
+        let position = writer.stream_position()?;
+        offset_log::push(OffsetWrite {
+            position,
+            data_position: data_ptr,
+            alignment,
+            padding: data_ptr - unaligned_ptr,
+            source,
+            type_name: type_name().to_string(),
+        });
+    }
+    Ok(())
+}
+
 fn write_rel_ptr_aligned_specialized<
     W: Write + Seek,
     T,
@@ -608,12 +1034,17 @@ fn write_rel_ptr_aligned_specialized<
     data: &Option<T>,
     data_ptr: &mut u64,
     alignment: u64,
+    source: AlignmentSource,
     write_t: F,
 ) -> std::io::Result<()> {
     match data {
         Some(value) => {
             // Calculate the relative offset.
+            let unaligned_ptr = *data_ptr;
             *data_ptr = round_up(*data_ptr, alignment);
+            log_offset_write(writer, unaligned_ptr, *data_ptr, alignment, source, || {
+                std::any::type_name::<T>()
+            })?;
             write_relative_offset(writer, data_ptr)?;
 
             // Write the data at the specified offset.
@@ -647,7 +1078,14 @@ fn write_rel_ptr_aligned<W: Write + Seek, T: SsbhWrite>(
     data_ptr: &mut u64,
     alignment: u64,
 ) -> std::io::Result<()> {
-    write_rel_ptr_aligned_specialized(writer, data, data_ptr, alignment, T::ssbh_write)?;
+    write_rel_ptr_aligned_specialized(
+        writer,
+        data,
+        data_ptr,
+        alignment,
+        offset_log::alignment_source::<T>(alignment),
+        T::ssbh_write,
+    )?;
     Ok(())
 }
 
@@ -670,64 +1108,96 @@ where
         writer: &mut W,
         data_ptr: &mut u64,
     ) -> std::io::Result<()> {
-        // TODO: This is nearly identical to the relative pointer function.
-        // The data pointer must point past the containing struct.
-        let current_pos = writer.stream_position()?;
-        if *data_ptr < current_pos + self.size_in_bytes() {
-            *data_ptr = current_pos + self.size_in_bytes();
-        }
+        write_ptr_aligned::<W, P, T>(writer, &self.0, data_ptr, T::alignment_in_bytes())
+    }
 
-        match &self.0 {
-            Some(value) => {
-                let alignment = T::alignment_in_bytes();
+    fn size_in_bytes(&self) -> u64 {
+        // TODO: Use the size_in_bytes already defined for P?
+        std::mem::size_of::<P>() as u64
+    }
+}
 
-                // The data pointer must point past the containing type.
-                let current_pos = writer.stream_position()?;
-                if *data_ptr < current_pos + self.size_in_bytes() {
-                    *data_ptr = current_pos + self.size_in_bytes();
-                }
+impl<P, T, const A: u64> SsbhWrite for PtrAligned<P, T, A>
+where
+    P: SsbhWrite + Default + TryFrom<u64>,
+    T: SsbhWrite,
+{
+    fn ssbh_write<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        const { assert!(A.is_power_of_two(), "alignment must be a power of two") };
+        write_ptr_aligned::<W, P, T>(writer, &self.0, data_ptr, A)
+    }
 
-                // Calculate the absolute offset.
-                *data_ptr = round_up(*data_ptr, alignment);
-
-                let offset = P::try_from(*data_ptr).map_err(|_| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        format!(
-                            "Failed to convert offset {} to a pointer with {} bytes.",
-                            data_ptr,
-                            std::mem::size_of::<P>()
-                        ),
-                    )
-                })?;
-                P::ssbh_write(&offset, writer, data_ptr)?;
+    fn size_in_bytes(&self) -> u64 {
+        std::mem::size_of::<P>() as u64
+    }
+}
+
+fn write_ptr_aligned<W, P, T>(
+    writer: &mut W,
+    data: &Option<T>,
+    data_ptr: &mut u64,
+    alignment: u64,
+) -> std::io::Result<()>
+where
+    W: Write + Seek,
+    P: SsbhWrite + Default + TryFrom<u64>,
+    T: SsbhWrite,
+{
+    // TODO: This is nearly identical to the relative pointer function.
+    // The data pointer must point past the containing struct.
+    let size_in_bytes = std::mem::size_of::<P>() as u64;
+    let current_pos = writer.stream_position()?;
+    if *data_ptr < current_pos + size_in_bytes {
+        *data_ptr = current_pos + size_in_bytes;
+    }
 
-                // Write the data at the specified offset.
-                let pos_after_offset = writer.stream_position()?;
-                writer.seek(SeekFrom::Start(*data_ptr))?;
+    match data {
+        Some(value) => {
+            // Calculate the absolute offset.
+            let unaligned_ptr = *data_ptr;
+            *data_ptr = round_up(*data_ptr, alignment);
+            log_offset_write(
+                writer,
+                unaligned_ptr,
+                *data_ptr,
+                alignment,
+                offset_log::alignment_source::<T>(alignment),
+                std::any::type_name::<T>,
+            )?;
+
+            let offset = P::try_from(*data_ptr).map_err(|_| {
+                std::io::Error::other(format!(
+                    "Failed to convert offset {} to a pointer with {} bytes.",
+                    data_ptr,
+                    std::mem::size_of::<P>()
+                ))
+            })?;
+            P::ssbh_write(&offset, writer, data_ptr)?;
 
-                value.ssbh_write(writer, data_ptr)?;
+            // Write the data at the specified offset.
+            let pos_after_offset = writer.stream_position()?;
+            writer.seek(SeekFrom::Start(*data_ptr))?;
 
-                // Point the data pointer past the current write.
-                // Types with relative offsets will already increment the data pointer.
-                let current_pos = writer.stream_position()?;
-                if current_pos > *data_ptr {
-                    *data_ptr = round_up(current_pos, alignment);
-                }
+            value.ssbh_write(writer, data_ptr)?;
 
-                writer.seek(SeekFrom::Start(pos_after_offset))?;
-                Ok(())
-            }
-            None => {
-                P::default().ssbh_write(writer, data_ptr)?;
-                Ok(())
+            // Point the data pointer past the current write.
+            // Types with relative offsets will already increment the data pointer.
+            let current_pos = writer.stream_position()?;
+            if current_pos > *data_ptr {
+                *data_ptr = round_up(current_pos, alignment);
             }
-        }
-    }
 
-    fn size_in_bytes(&self) -> u64 {
-        // TODO: Use the size_in_bytes already defined for P?
-        std::mem::size_of::<P>() as u64
+            writer.seek(SeekFrom::Start(pos_after_offset))?;
+            Ok(())
+        }
+        None => {
+            P::default().ssbh_write(writer, data_ptr)?;
+            Ok(())
+        }
     }
 }
 
@@ -753,6 +1223,30 @@ impl<T: SsbhWrite> SsbhWrite for RelPtr64<T> {
     }
 }
 
+impl<T: SsbhWrite> SsbhWriteAligned for RelPtr64<T> {
+    fn ssbh_write_aligned<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+        alignment: u64,
+    ) -> std::io::Result<()> {
+        // The data pointer must point past the containing struct.
+        let current_pos = writer.stream_position()?;
+        if *data_ptr < current_pos + self.size_in_bytes() {
+            *data_ptr = current_pos + self.size_in_bytes();
+        }
+
+        write_rel_ptr_aligned_specialized(
+            writer,
+            &self.0,
+            data_ptr,
+            alignment,
+            AlignmentSource::Field,
+            T::ssbh_write,
+        )
+    }
+}
+
 pub(crate) fn write_ssbh_header_and_data<W: Write + Seek>(
     writer: &mut W,
     data: &Ssbh,
@@ -771,22 +1265,206 @@ pub(crate) fn write_ssbh_header_and_data<W: Write + Seek>(
     }
 }
 
-pub(crate) fn write_buffered<
-    W: Write + Seek,
-    F: Fn(&mut Cursor<Vec<u8>>) -> std::io::Result<()>,
->(
+/// A reader that treats `base_offset` in the inner reader as the start of the stream.
+struct OffsetReader<'a, R> {
+    inner: &'a mut R,
+    base_offset: u64,
+}
+
+impl<'a, R: Seek> OffsetReader<'a, R> {
+    fn new(inner: &'a mut R, base_offset: u64) -> std::io::Result<Self> {
+        inner.seek(SeekFrom::Start(base_offset))?;
+        Ok(Self { inner, base_offset })
+    }
+}
+
+impl<R: Read> Read for OffsetReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for OffsetReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => {
+                let offset = self.base_offset.checked_add(offset).ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "seek offset overflow")
+                })?;
+                SeekFrom::Start(offset)
+            }
+            pos => pos,
+        };
+
+        let position = self.inner.seek(pos)?;
+        position.checked_sub(self.base_offset).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek to a position before the base offset",
+            )
+        })
+    }
+}
+
+/// A reader that tracks the end of the furthest read from the inner reader.
+struct ExtentReader<'a, R> {
+    inner: &'a mut R,
+    position: u64,
+    end: u64,
+}
+
+impl<'a, R: Seek> ExtentReader<'a, R> {
+    fn new(inner: &'a mut R) -> std::io::Result<Self> {
+        let position = inner.stream_position()?;
+        Ok(Self {
+            inner,
+            position,
+            end: position,
+        })
+    }
+}
+
+impl<R: Read> Read for ExtentReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.position += count as u64;
+        self.end = self.end.max(self.position);
+        Ok(count)
+    }
+}
+
+impl<R: Seek> Seek for ExtentReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
+}
+
+/// Writes the bytes for a file using the [binrw::BinWrite] API.
+///
+/// The data is written to a separate buffer first to produce the same bytes as writing a standalone file.
+/// This allows reading the data at its position in `writer` using [SsbhFile::read_at].
+fn write_binrw<W: Write + Seek, F: FnOnce(&mut Cursor<Vec<u8>>) -> std::io::Result<()>>(
     writer: &mut W,
+    endian: Endian,
+    size_hint: usize,
+    write_data: F,
+) -> BinResult<()> {
+    if endian != Endian::Little {
+        return Err(binrw::Error::Custom {
+            pos: writer.stream_position()?,
+            err: Box::new("SSBH formats only support little endian"),
+        });
+    }
+    write_buffered(writer, size_hint, write_data)?;
+    Ok(())
+}
+
+pub(crate) fn write_buffered<W: Write, F: FnOnce(&mut Cursor<Vec<u8>>) -> std::io::Result<()>>(
+    writer: &mut W,
+    size_hint: usize,
     write_data: F,
 ) -> std::io::Result<()> {
     // Buffer the entire write operation into memory to improve performance.
     // The seeks used to write relative offsets cause flushes for BufWriter.
-    let mut cursor = Cursor::new(Vec::new());
+    // Reserve space for the largest buffers to avoid reallocating for large files.
+    let mut cursor = Cursor::new(Vec::with_capacity(size_hint));
     write_data(&mut cursor)?;
 
-    writer.write_all(cursor.get_mut())?;
+    writer.write_all(cursor.get_ref())?;
     Ok(())
 }
 
+/// A cheap estimate of the written size in bytes used to allocate the write buffer.
+///
+/// This only counts the large byte buffers that dominate the size of files like meshes.
+/// The buffer may still grow slightly to fit the remaining data.
+pub(crate) trait SizeHint {
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
+impl SizeHint for SsbhFile {
+    fn size_hint(&self) -> usize {
+        match &self.data {
+            Ssbh::Hlpb(v) => v.data.size_hint(),
+            Ssbh::Matl(v) => v.data.size_hint(),
+            Ssbh::Modl(v) => v.data.size_hint(),
+            Ssbh::Mesh(v) => v.data.size_hint(),
+            Ssbh::Skel(v) => v.data.size_hint(),
+            Ssbh::Anim(v) => v.data.size_hint(),
+            Ssbh::Nlst(v) => v.data.size_hint(),
+            Ssbh::Nrpd(v) => v.data.size_hint(),
+            Ssbh::Nufx(v) => v.data.size_hint(),
+            Ssbh::Shdr(v) => v.data.size_hint(),
+        }
+    }
+}
+
+impl SizeHint for prelude::Hlpb {}
+impl SizeHint for prelude::Matl {}
+impl SizeHint for prelude::Modl {}
+impl SizeHint for prelude::Skel {}
+impl SizeHint for prelude::Nlst {}
+impl SizeHint for prelude::Nrpd {}
+impl SizeHint for prelude::Nufx {}
+impl SizeHint for prelude::MeshEx {}
+
+impl SizeHint for prelude::Mesh {
+    fn size_hint(&self) -> usize {
+        fn buffers_size<A, W>(inner: &formats::mesh::MeshInner<A, W>) -> usize
+        where
+            A: for<'a> BinRead<Args<'a> = ()> + SsbhWrite,
+            W: for<'a> BinRead<Args<'a> = ()> + SsbhWrite,
+        {
+            inner
+                .vertex_buffers
+                .elements
+                .iter()
+                .map(|b| b.elements.len())
+                .sum::<usize>()
+                + inner.index_buffer.elements.len()
+        }
+
+        match self {
+            prelude::Mesh::V8(inner) => buffers_size(inner),
+            prelude::Mesh::V9(inner) => buffers_size(inner),
+            prelude::Mesh::V10(inner) => buffers_size(inner),
+        }
+    }
+}
+
+impl SizeHint for prelude::Anim {
+    fn size_hint(&self) -> usize {
+        match self {
+            prelude::Anim::V12 { buffers, .. } => {
+                buffers.elements.iter().map(|b| b.elements.len()).sum()
+            }
+            prelude::Anim::V20 { buffer, .. } => buffer.elements.len(),
+            prelude::Anim::V21 { buffer, .. } => buffer.elements.len(),
+        }
+    }
+}
+
+impl SizeHint for prelude::Shdr {
+    fn size_hint(&self) -> usize {
+        match self {
+            prelude::Shdr::V12 { shaders } => shaders
+                .elements
+                .iter()
+                .map(|s| s.shader_binary.elements.len())
+                .sum(),
+        }
+    }
+}
+
+impl SizeHint for prelude::Adj {
+    fn size_hint(&self) -> usize {
+        self.index_buffer.len() * std::mem::size_of::<i16>()
+    }
+}
+
 // TODO: This can probably just be derived.
 // TODO: Version all Ssbh types to avoid having a separate function.
 pub(crate) fn write_ssbh_file<W: Write + Seek, S: SsbhWrite + Version>(
@@ -812,6 +1490,167 @@ mod tests {
     use super::*;
     use hexlit::hex;
 
+    #[test]
+    fn read_at_embedded_file() {
+        let hlpb = formats::hlpb::Hlpb::V11 {
+            aim_constraints: SsbhArray::new(),
+            orient_constraints: SsbhArray::from_vec(vec![formats::hlpb::OrientConstraint {
+                name: "a".into(),
+                parent_bone_name1: "b".into(),
+                parent_bone_name2: "c".into(),
+                source_bone_name: "d".into(),
+                target_bone_name: "e".into(),
+                unk_type: 1,
+                constraint_axes: Vector3::new(1.0, 1.0, 1.0),
+                quat1: Vector4::default(),
+                quat2: Vector4::default(),
+                range_min: Vector3::default(),
+                range_max: Vector3::default(),
+            }]),
+            constraint_indices: SsbhArray::from_vec(vec![0]),
+            constraint_types: SsbhArray::from_vec(vec![formats::hlpb::ConstraintType::Orient]),
+        };
+
+        let mut file = Cursor::new(Vec::new());
+        hlpb.write(&mut file).unwrap();
+
+        // Embed the file between other data.
+        let mut container = vec![0xFFu8; 13];
+        container.extend_from_slice(file.get_ref());
+        container.extend_from_slice(&[0xFFu8; 7]);
+
+        let mut reader = Cursor::new(container);
+        assert_eq!(hlpb, formats::hlpb::Hlpb::read_at(&mut reader, 13).unwrap());
+        assert!(SsbhFile::read_at(&mut reader, 12).is_err());
+    }
+
+    #[test]
+    fn read_with_unused_bytes() {
+        let hlpb = formats::hlpb::Hlpb::V11 {
+            aim_constraints: SsbhArray::new(),
+            orient_constraints: SsbhArray::new(),
+            constraint_indices: SsbhArray::from_vec(vec![0, 1]),
+            constraint_types: SsbhArray::new(),
+        };
+
+        let mut file = Cursor::new(Vec::new());
+        hlpb.write(&mut file).unwrap();
+        let len = file.get_ref().len() as u64;
+
+        file.set_position(0);
+        let (ssbh, unused) = SsbhFile::read_with_unused_bytes(&mut file).unwrap();
+        assert!(matches!(ssbh.data, Ssbh::Hlpb(_)));
+        assert_eq!(None, unused);
+
+        // Simulate padding added by an archive.
+        file.get_mut().extend_from_slice(&[0u8; 16]);
+        file.set_position(0);
+        let (_, unused) = SsbhFile::read_with_unused_bytes(&mut file).unwrap();
+        assert_eq!(Some(len..len + 16), unused);
+    }
+
+    #[test]
+    fn bin_write_matches_write() {
+        use binrw::BinWriterExt;
+
+        let hlpb = formats::hlpb::Hlpb::V11 {
+            aim_constraints: SsbhArray::new(),
+            orient_constraints: SsbhArray::new(),
+            constraint_indices: SsbhArray::from_vec(vec![0, 1]),
+            constraint_types: SsbhArray::new(),
+        };
+
+        let mut expected = Cursor::new(Vec::new());
+        hlpb.write(&mut expected).unwrap();
+
+        // The file should be identical when composed into other binrw data.
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&0xFFu8).unwrap();
+        writer.write_le(&hlpb).unwrap();
+        writer.write_le(&0xFFu8).unwrap();
+
+        let bytes = writer.into_inner();
+        assert_eq!(expected.get_ref()[..], bytes[1..bytes.len() - 1]);
+        assert_eq!(
+            hlpb,
+            formats::hlpb::Hlpb::read_at(&mut Cursor::new(bytes), 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn bin_write_big_endian() {
+        use binrw::BinWriterExt;
+
+        let adj = formats::adj::Adj {
+            entries: Vec::new(),
+            index_buffer: Vec::new(),
+        };
+        let mut writer = Cursor::new(Vec::new());
+        assert!(writer.write_be(&adj).is_err());
+        assert!(writer.get_ref().is_empty());
+
+        writer.write_le(&adj).unwrap();
+        let mut expected = Cursor::new(Vec::new());
+        adj.write(&mut expected).unwrap();
+        assert_eq!(expected.into_inner(), writer.into_inner());
+    }
+
+    #[test]
+    fn write_to_stream_matches_write() {
+        let hlpb = formats::hlpb::Hlpb::V11 {
+            aim_constraints: SsbhArray::new(),
+            orient_constraints: SsbhArray::new(),
+            constraint_indices: SsbhArray::new(),
+            constraint_types: SsbhArray::new(),
+        };
+
+        let mut cursor = Cursor::new(Vec::new());
+        hlpb.write(&mut cursor).unwrap();
+
+        let mut stream = Vec::new();
+        hlpb.write_to_stream(&mut stream).unwrap();
+        assert_eq!(cursor.into_inner(), stream);
+    }
+
+    fn write_seek_sequence<W: Write + Seek>(writer: &mut W) {
+        writer.write_all(&[1, 2, 3, 4]).unwrap();
+        writer.seek(SeekFrom::Start(8)).unwrap();
+        writer.write_all(&[5, 6]).unwrap();
+        writer.seek(SeekFrom::Start(2)).unwrap();
+        writer.write_all(&[7, 8, 9]).unwrap();
+        writer.seek(SeekFrom::End(-1)).unwrap();
+        writer.write_all(&[10, 11]).unwrap();
+        writer.seek(SeekFrom::Current(-4)).unwrap();
+    }
+
+    #[test]
+    fn write_buffered_seeks() {
+        let mut output = Vec::new();
+        write_buffered(&mut output, 4, |w| {
+            write_seek_sequence(w);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(vec![1, 2, 7, 8, 9, 0, 0, 0, 5, 10, 11], output);
+    }
+
+    #[test]
+    fn size_hint_anim_buffer() {
+        let anim = prelude::Anim::V20 {
+            final_frame_index: 0.0,
+            unk1: 0,
+            unk2: 0,
+            name: "a".into(),
+            groups: SsbhArray::new(),
+            buffer: SsbhByteBuffer::from_vec(vec![1u8; 32]),
+        };
+        assert_eq!(32, anim.size_hint());
+
+        let mut writer = Cursor::new(Vec::new());
+        anim.write(&mut writer).unwrap();
+        assert!(writer.into_inner().len() >= anim.size_hint());
+    }
+
     #[test]
     fn new_relptr64() {
         let ptr = RelPtr64::new(5u32);
@@ -824,6 +1663,86 @@ mod tests {
         assert_eq!(RelPtr64::<u32>::null(), None.into());
     }
 
+    #[test]
+    fn ptr_option_methods() {
+        let mut ptr = Ptr64::new(5u32);
+        assert!(!ptr.is_null());
+        assert_eq!(Some(&5u32), ptr.as_ref());
+        assert_eq!(Some(10u32), ptr.map(|v| v * 2));
+        assert_eq!(10u32, ptr.map_or(0, |v| v * 2));
+
+        *ptr.as_mut().unwrap() = 7;
+        assert_eq!(Some(7u32), ptr.clone().into_option());
+        assert_eq!(7u32, ptr.unwrap_or_default());
+
+        let mut ptr = Ptr64::<u32>::null();
+        assert!(ptr.is_null());
+        assert_eq!(None, ptr.as_ref());
+        assert_eq!(None, ptr.as_mut());
+        assert_eq!(None, ptr.map(|v| v * 2));
+        assert_eq!(0u32, ptr.map_or(0, |v| v * 2));
+        assert_eq!(0u32, ptr.unwrap_or_default());
+    }
+
+    #[test]
+    fn ptr_aligned_option_methods() {
+        assert!(Ptr16Aligned::<u8, 8>::null().is_null());
+        assert_eq!(Some(2u8), Ptr16Aligned::<u8, 8>::new(2u8).into_option());
+        assert_eq!(0u8, Ptr16Aligned::<u8, 8>::null().unwrap_or_default());
+    }
+
+    #[test]
+    fn relptr64_option_methods() {
+        let ptr = RelPtr64::new(String::from("a"));
+        assert!(!ptr.is_null());
+        assert_eq!(Some(1), ptr.map(|v| v.len()));
+        assert_eq!("a", ptr.unwrap_or_default());
+
+        let ptr = RelPtr64::<String>::null();
+        assert!(ptr.is_null());
+        assert_eq!(0, ptr.map_or(0, |v| v.len()));
+        assert_eq!("", ptr.unwrap_or_default());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_ptr_null_round_trip() {
+        let json = serde_json::to_value(Ptr64::<u32>::null()).unwrap();
+        assert_eq!(serde_json::Value::Null, json);
+        assert!(serde_json::from_value::<Ptr64<u32>>(json)
+            .unwrap()
+            .is_null());
+
+        let json = serde_json::to_value(Ptr32::new(5u32)).unwrap();
+        assert_eq!(serde_json::json!(5), json);
+        assert_eq!(Ptr32::new(5u32), serde_json::from_value(json).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_ptr_aligned_null_round_trip() {
+        let json = serde_json::to_value(Ptr64Aligned::<u32, 16>::null()).unwrap();
+        assert_eq!(serde_json::Value::Null, json);
+        assert_eq!(
+            Ptr64Aligned::<u32, 16>::null(),
+            serde_json::from_value(json).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_relptr64_null_round_trip() {
+        let json = serde_json::to_value(RelPtr64::<u32>::null()).unwrap();
+        assert_eq!(serde_json::Value::Null, json);
+        assert!(serde_json::from_value::<RelPtr64<u32>>(json)
+            .unwrap()
+            .is_null());
+
+        let json = serde_json::to_value(RelPtr64::new(5u32)).unwrap();
+        assert_eq!(serde_json::json!(5), json);
+        assert_eq!(RelPtr64::new(5u32), serde_json::from_value(json).unwrap());
+    }
+
     #[test]
     fn read_relptr() {
         let mut reader = Cursor::new(hex!("09000000 00000000 05070000"));
@@ -867,6 +1786,82 @@ mod tests {
         assert_eq!(5u8, value);
     }
 
+    #[test]
+    fn content_hash_ignores_offsets() {
+        // The same value with different offsets and padding.
+        let a = Cursor::new(hex!("08000000 00000000 07000000"))
+            .read_le::<RelPtr64<u32>>()
+            .unwrap();
+        let b = Cursor::new(hex!("10000000 00000000 FFFFFFFF FFFFFFFF 07000000"))
+            .read_le::<RelPtr64<u32>>()
+            .unwrap();
+        let c = RelPtr64::new(8u32);
+
+        let hash = |p: &RelPtr64<u32>| content_hash(|c| p.write(c)).unwrap();
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(hash(&a), hash(&c));
+        assert_ne!(hash(&a), hash(&RelPtr64::<u32>::null()));
+    }
+
+    #[test]
+    fn content_hash_float_bits() {
+        let hash = |f: f32| content_hash(|c| f.write(c)).unwrap();
+        assert_ne!(hash(0.0), hash(-0.0));
+        assert_eq!(hash(f32::NAN), hash(f32::NAN));
+        assert_ne!(hash(f32::NAN), hash(f32::from_bits(0x7fc00001)));
+    }
+
+    #[test]
+    fn content_hash_fnv1a() {
+        // Hashes should not change between runs.
+        assert_eq!(0xcbf29ce484222325, content_hash(|_| Ok(())).unwrap());
+        assert_eq!(
+            0xaf63dc4c8601ec8c,
+            content_hash(|c| c.write_all(b"a")).unwrap()
+        );
+    }
+
+    #[test]
+    fn clone_and_compare_ssbh_file() {
+        let file = SsbhFile {
+            data: Ssbh::Hlpb(Versioned {
+                data: hlpb::Hlpb::V11 {
+                    aim_constraints: SsbhArray::new(),
+                    orient_constraints: SsbhArray::new(),
+                    constraint_indices: SsbhArray::from_vec(vec![0, 1]),
+                    constraint_types: SsbhArray::new(),
+                },
+            }),
+        };
+
+        let mut other = file.clone();
+        assert_eq!(file, other);
+
+        if let Ssbh::Hlpb(Versioned {
+            data: hlpb::Hlpb::V11 {
+                constraint_indices, ..
+            },
+        }) = &mut other.data
+        {
+            constraint_indices.elements[1] = 2;
+        }
+        assert_ne!(file, other);
+    }
+
+    #[test]
+    fn hash_wrapper_types() {
+        use std::collections::HashSet;
+
+        let values: HashSet<_> = [
+            SsbhArray::from_vec(vec![RelPtr64::new(1u32)]),
+            SsbhArray::from_vec(vec![RelPtr64::new(1u32)]),
+            SsbhArray::from_vec(vec![RelPtr64::null()]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(2, values.len());
+    }
+
     #[test]
     fn read_ptr8() {
         let mut reader = Cursor::new(hex!("04050000 07"));
@@ -961,6 +1956,45 @@ mod tests {
         assert_eq!(9, data_ptr);
     }
 
+    #[test]
+    fn write_ptr64_aligned_vec_u8() {
+        // Check that the alignment uses the explicit alignment.
+        let value = Ptr64Aligned::<_, 16>::new(vec![5u8]);
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            hex!("10000000 00000000 00000000 00000000 05")
+        );
+        assert_eq!(17, data_ptr);
+    }
+
+    #[test]
+    fn write_ptr16_aligned_null() {
+        let value = Ptr16Aligned::<u8, 8>::null();
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(writer.into_inner(), hex!("0000"));
+        assert_eq!(2, data_ptr);
+    }
+
+    #[test]
+    fn read_ptr32_aligned() {
+        let mut reader = Cursor::new(hex!("08000000 05000000 07"));
+        let value = reader.read_le::<Ptr32Aligned<u8, 8>>().unwrap();
+        assert_eq!(7u8, value.unwrap());
+
+        // Make sure the reader position is restored.
+        let value = reader.read_le::<u8>().unwrap();
+        assert_eq!(5u8, value);
+    }
+
     #[test]
     fn write_ptr64_vec_u32() {
         // Check that the alignment uses the inner type's alignment.
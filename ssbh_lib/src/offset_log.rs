@@ -0,0 +1,232 @@
+//! Logging of the alignment used for data written behind offsets.
+//!
+//! Writing SSBH data calculates the position of data behind each offset at runtime.
+//! The position depends on the alignment of the pointed to data, which can make it hard to
+//! determine why a written file doesn't match the original.
+//! Recording the offset writes produces a list of every alignment decision in write order.
+/*!
+```rust
+use ssbh_lib::{offset_log::record_offset_writes, SsbhArray};
+use ssbh_write::SsbhWrite;
+
+let value = SsbhArray::from_vec(vec![1u32, 2u32]);
+
+let mut writer = std::io::Cursor::new(Vec::new());
+let (result, writes) = record_offset_writes(|| value.write(&mut writer));
+result.unwrap();
+
+for write in writes {
+    println!("{:?} at {}: {:?}", write.type_name, write.data_position, write.source);
+}
+```
+ */
+use std::cell::RefCell;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use ssbh_write::SsbhWrite;
+
+/// The reason for the alignment used for data behind an offset.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlignmentSource {
+    /// The alignment of the pointed to type in memory.
+    Type,
+    /// An alignment from [SsbhWrite::alignment_in_bytes] that differs from the type's alignment in memory.
+    /// This includes types using the `alignment` attribute for the derive macro.
+    Explicit,
+    /// The alignment required for the elements of [SsbhArray](crate::SsbhArray) and [SsbhByteBuffer](crate::SsbhByteBuffer).
+    Array,
+    /// An alignment from the `data_alignment` attribute on a field for the derive macro.
+    /// See [SsbhWriteAligned](ssbh_write::SsbhWriteAligned).
+    Field,
+}
+
+/// Information about a single non null offset written to the output.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetWrite {
+    /// The absolute position of the offset.
+    pub position: u64,
+    /// The absolute position of the pointed to data.
+    pub data_position: u64,
+    /// The alignment in bytes applied to [data_position](#structfield.data_position).
+    pub alignment: u64,
+    /// The number of bytes skipped to align [data_position](#structfield.data_position).
+    pub padding: u64,
+    /// The reason for the value of [alignment](#structfield.alignment).
+    pub source: AlignmentSource,
+    /// The name of the pointed to type like `"ssbh_lib::strings::CString<4>"`.
+    pub type_name: String,
+}
+
+thread_local! {
+    static OFFSET_WRITES: RefCell<Option<Vec<OffsetWrite>>> = const { RefCell::new(None) };
+}
+
+/// Calls `f` and returns its result along with every offset written by `f` on the current thread.
+///
+/// Only writes using types in this crate are recorded.
+/// Offset writes are not recorded outside of this function, so normal writes have minimal overhead.
+pub fn record_offset_writes<T, F: FnOnce() -> T>(f: F) -> (T, Vec<OffsetWrite>) {
+    // Restore any previous log even if f panics to support nested calls.
+    struct Guard(Option<Vec<OffsetWrite>>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            OFFSET_WRITES.with(|w| *w.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous = OFFSET_WRITES.with(|w| w.borrow_mut().replace(Vec::new()));
+    let guard = Guard(previous);
+
+    let result = f();
+    let writes = OFFSET_WRITES.with(|w| w.borrow_mut().take().unwrap_or_default());
+
+    drop(guard);
+    (result, writes)
+}
+
+pub(crate) fn alignment_source<T: SsbhWrite>(alignment: u64) -> AlignmentSource {
+    if alignment == std::mem::align_of::<T>() as u64 {
+        AlignmentSource::Type
+    } else {
+        AlignmentSource::Explicit
+    }
+}
+
+/// Returns `true` if offset writes on the current thread should be recorded.
+/// This avoids the cost of creating log entries during normal writes.
+pub(crate) fn is_recording() -> bool {
+    OFFSET_WRITES.with(|w| w.borrow().is_some())
+}
+
+pub(crate) fn push(write: OffsetWrite) {
+    OFFSET_WRITES.with(|w| {
+        if let Some(writes) = w.borrow_mut().as_mut() {
+            writes.push(write);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CString, Ptr16, RelPtr64, SsbhArray, SsbhString};
+    use binrw::io::Cursor;
+
+    #[derive(SsbhWrite)]
+    struct RelPtrs {
+        a: RelPtr64<u8>,
+        b: RelPtr64<u32>,
+    }
+
+    #[derive(SsbhWrite)]
+    struct Arrays {
+        a: Ptr16<u8>,
+        b: SsbhArray<u16>,
+        c: SsbhArray<u16>,
+    }
+
+    #[test]
+    fn no_recording_outside_closure() {
+        let mut writer = Cursor::new(Vec::new());
+        RelPtr64::new(1u32).write(&mut writer).unwrap();
+        assert!(!is_recording());
+
+        let ((), writes) = record_offset_writes(|| ());
+        assert!(writes.is_empty());
+        assert!(!is_recording());
+    }
+
+    #[test]
+    fn record_rel_ptr_type_alignment() {
+        let value = RelPtrs {
+            a: RelPtr64::null(),
+            b: RelPtr64::new(1u32),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        let (result, writes) = record_offset_writes(|| value.write(&mut writer));
+        result.unwrap();
+
+        assert_eq!(
+            vec![OffsetWrite {
+                position: 8,
+                data_position: 16,
+                alignment: 4,
+                padding: 0,
+                source: AlignmentSource::Type,
+                type_name: "u32".to_string()
+            }],
+            writes
+        );
+    }
+
+    #[test]
+    fn record_string_explicit_alignment() {
+        let mut writer = Cursor::new(Vec::new());
+        let (result, writes) = record_offset_writes(|| SsbhString::from("a").write(&mut writer));
+        result.unwrap();
+
+        assert_eq!(
+            vec![OffsetWrite {
+                position: 0,
+                data_position: 8,
+                alignment: 4,
+                padding: 0,
+                source: AlignmentSource::Explicit,
+                type_name: std::any::type_name::<CString<4>>().to_string()
+            }],
+            writes
+        );
+    }
+
+    #[test]
+    fn record_array_padding() {
+        let value = Arrays {
+            a: Ptr16::new(1u8),
+            b: SsbhArray::from_vec(Vec::new()),
+            c: SsbhArray::from_vec(vec![2u16]),
+        };
+
+        let mut writer = Cursor::new(Vec::new());
+        let (result, writes) = record_offset_writes(|| value.write(&mut writer));
+        result.unwrap();
+
+        assert_eq!(
+            vec![
+                OffsetWrite {
+                    position: 0,
+                    data_position: 34,
+                    alignment: 1,
+                    padding: 0,
+                    source: AlignmentSource::Type,
+                    type_name: "u8".to_string()
+                },
+                OffsetWrite {
+                    position: 18,
+                    data_position: 40,
+                    alignment: 8,
+                    padding: 0,
+                    source: AlignmentSource::Array,
+                    type_name: "u16".to_string()
+                }
+            ],
+            writes
+        );
+    }
+
+    #[test]
+    fn nested_recording() {
+        let mut writer = Cursor::new(Vec::new());
+        let ((result, inner), outer) =
+            record_offset_writes(|| record_offset_writes(|| RelPtr64::new(1u8).write(&mut writer)));
+        result.unwrap();
+
+        assert_eq!(1, inner.len());
+        assert!(outer.is_empty());
+    }
+}
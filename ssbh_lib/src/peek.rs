@@ -0,0 +1,213 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Ssbh;
+
+// The file magic, padding, format magic, major version, and minor version.
+const HEADER_SIZE: usize = 24;
+
+/// The type of data for each variant of [Ssbh].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SsbhKind {
+    Hlpb,
+    Matl,
+    Modl,
+    Mesh,
+    Skel,
+    Anim,
+    Nlst,
+    Nrpd,
+    Nufx,
+    Shdr,
+}
+
+impl SsbhKind {
+    /// The magic for the format like `b"HSEM"` for [SsbhKind::Mesh].
+    pub fn magic(&self) -> &'static [u8; 4] {
+        match self {
+            SsbhKind::Hlpb => b"BPLH",
+            SsbhKind::Matl => b"LTAM",
+            SsbhKind::Modl => b"LDOM",
+            SsbhKind::Mesh => b"HSEM",
+            SsbhKind::Skel => b"LEKS",
+            SsbhKind::Anim => b"MINA",
+            SsbhKind::Nlst => b"TSLN",
+            SsbhKind::Nrpd => b"DPRN",
+            SsbhKind::Nufx => b"XFUN",
+            SsbhKind::Shdr => b"RDHS",
+        }
+    }
+
+    /// Finds the kind with the given `magic` or [None] if the magic is not recognized.
+    pub fn from_magic(magic: &[u8; 4]) -> Option<Self> {
+        [
+            SsbhKind::Hlpb,
+            SsbhKind::Matl,
+            SsbhKind::Modl,
+            SsbhKind::Mesh,
+            SsbhKind::Skel,
+            SsbhKind::Anim,
+            SsbhKind::Nlst,
+            SsbhKind::Nrpd,
+            SsbhKind::Nufx,
+            SsbhKind::Shdr,
+        ]
+        .into_iter()
+        .find(|k| k.magic() == magic)
+    }
+}
+
+impl Ssbh {
+    /// The kind of data without the contents.
+    pub fn kind(&self) -> SsbhKind {
+        match self {
+            Ssbh::Hlpb(_) => SsbhKind::Hlpb,
+            Ssbh::Matl(_) => SsbhKind::Matl,
+            Ssbh::Modl(_) => SsbhKind::Modl,
+            Ssbh::Mesh(_) => SsbhKind::Mesh,
+            Ssbh::Skel(_) => SsbhKind::Skel,
+            Ssbh::Anim(_) => SsbhKind::Anim,
+            Ssbh::Nlst(_) => SsbhKind::Nlst,
+            Ssbh::Nrpd(_) => SsbhKind::Nrpd,
+            Ssbh::Nufx(_) => SsbhKind::Nufx,
+            Ssbh::Shdr(_) => SsbhKind::Shdr,
+        }
+    }
+}
+
+/// The type and version of an SSBH file from the header.
+/// See [peek_format].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SsbhFormat {
+    pub kind: SsbhKind,
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+/// Reads only the header from `path` to determine the type and version of the SSBH data.
+/// Returns [None] if the file can't be opened or is not a supported SSBH file.
+///
+/// Only the first 24 bytes are read, so this is much faster than [SsbhFile::from_file](crate::SsbhFile::from_file)
+/// for identifying large numbers of files.
+/// The rest of the file is not checked and may still fail to parse.
+/**
+```rust no_run
+use ssbh_lib::{peek_format, SsbhKind};
+
+if let Some(format) = peek_format("model.numshb") {
+    assert_eq!(SsbhKind::Mesh, format.kind);
+    println!("Mesh {}.{}", format.major_version, format.minor_version);
+}
+```
+ */
+pub fn peek_format<P: AsRef<Path>>(path: P) -> Option<SsbhFormat> {
+    let mut file = File::open(path).ok()?;
+    peek_format_from_reader(&mut file)
+}
+
+/// Reads only the header from the current position of `reader` like [peek_format].
+/// The position of `reader` is restored after reading.
+pub fn peek_format_from_reader<R: Read + Seek>(reader: &mut R) -> Option<SsbhFormat> {
+    let start = reader.stream_position().ok()?;
+    let mut header = [0u8; HEADER_SIZE];
+    let result = reader.read_exact(&mut header);
+    reader.seek(SeekFrom::Start(start)).ok()?;
+    result.ok()?;
+
+    if &header[0..4] != b"HBSS" {
+        return None;
+    }
+
+    let kind = SsbhKind::from_magic(header[16..20].try_into().unwrap())?;
+    Some(SsbhFormat {
+        kind,
+        major_version: u16::from_le_bytes([header[20], header[21]]),
+        minor_version: u16::from_le_bytes([header[22], header[23]]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formats::hlpb::Hlpb, SsbhArray, SsbhFile, Versioned};
+    use hexlit::hex;
+    use std::io::Cursor;
+
+    #[test]
+    fn peek_format_written_file() {
+        let ssbh = SsbhFile {
+            data: Ssbh::Hlpb(Versioned {
+                data: Hlpb::V11 {
+                    aim_constraints: SsbhArray::new(),
+                    orient_constraints: SsbhArray::new(),
+                    constraint_indices: SsbhArray::new(),
+                    constraint_types: SsbhArray::new(),
+                },
+            }),
+        };
+        let mut writer = Cursor::new(Vec::new());
+        ssbh.write(&mut writer).unwrap();
+
+        writer.set_position(0);
+        assert_eq!(
+            Some(SsbhFormat {
+                kind: SsbhKind::Hlpb,
+                major_version: 1,
+                minor_version: 1
+            }),
+            peek_format_from_reader(&mut writer)
+        );
+        assert_eq!(0, writer.position());
+        assert_eq!(SsbhKind::Hlpb, ssbh.data.kind());
+    }
+
+    #[test]
+    fn peek_format_header_only() {
+        let mut reader = Cursor::new(hex!(
+            "48425353 40000000 00000000 00000000 4853454D 01000A00"
+        ));
+        assert_eq!(
+            Some(SsbhFormat {
+                kind: SsbhKind::Mesh,
+                major_version: 1,
+                minor_version: 10
+            }),
+            peek_format_from_reader(&mut reader)
+        );
+    }
+
+    #[test]
+    fn peek_format_invalid() {
+        // Unknown format magic.
+        let mut reader = Cursor::new(hex!(
+            "48425353 40000000 00000000 00000000 41424344 01000A00"
+        ));
+        assert_eq!(None, peek_format_from_reader(&mut reader));
+
+        // Not an SSBH file.
+        let mut reader = Cursor::new(hex!(
+            "00000000 40000000 00000000 00000000 4853454D 01000A00"
+        ));
+        assert_eq!(None, peek_format_from_reader(&mut reader));
+
+        // Too short.
+        let mut reader = Cursor::new(hex!("48425353"));
+        assert_eq!(None, peek_format_from_reader(&mut reader));
+        assert_eq!(0, reader.position());
+    }
+
+    #[test]
+    fn kind_magic_round_trip() {
+        for kind in [SsbhKind::Hlpb, SsbhKind::Anim, SsbhKind::Shdr] {
+            assert_eq!(Some(kind), SsbhKind::from_magic(kind.magic()));
+        }
+    }
+}
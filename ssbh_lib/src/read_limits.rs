@@ -0,0 +1,127 @@
+//! Limits for rejecting malformed files while reading.
+//!
+//! The element count for [SsbhArray](crate::SsbhArray) and [SsbhByteBuffer](crate::SsbhByteBuffer)
+//! is read from the file, so corrupted or fuzzed files can claim billions of elements.
+//! Reading with limits fails early for these files instead of attempting large allocations.
+/*!
+```rust no_run
+use ssbh_lib::read_limits::{with_read_limits, ReadLimits};
+use ssbh_lib::SsbhFile;
+
+let limits = ReadLimits {
+    max_array_length: Some(100000),
+    max_buffer_size: Some(50 * 1024 * 1024),
+};
+let result = with_read_limits(&limits, || SsbhFile::from_file("model.numshb"));
+```
+ */
+use std::cell::Cell;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Limits for [with_read_limits]. [None] does not limit the value.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReadLimits {
+    /// The maximum element count for an [SsbhArray](crate::SsbhArray).
+    pub max_array_length: Option<u64>,
+    /// The maximum size in bytes for an [SsbhByteBuffer](crate::SsbhByteBuffer).
+    pub max_buffer_size: Option<u64>,
+}
+
+thread_local! {
+    static READ_LIMITS: Cell<ReadLimits> = const {
+        Cell::new(ReadLimits {
+            max_array_length: None,
+            max_buffer_size: None,
+        })
+    };
+}
+
+/// Calls `f` with `limits` applied to all reads on the current thread.
+///
+/// Arrays or buffers with a count larger than the limit return an error before reading any elements.
+/// Reads outside of this function are not limited.
+pub fn with_read_limits<T, F: FnOnce() -> T>(limits: &ReadLimits, f: F) -> T {
+    // Restore any previous limits even if f panics to support nested calls.
+    struct Guard(ReadLimits);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            READ_LIMITS.with(|l| l.set(self.0));
+        }
+    }
+
+    let previous = READ_LIMITS.with(|l| l.replace(*limits));
+    let _guard = Guard(previous);
+
+    f()
+}
+
+pub(crate) fn current() -> ReadLimits {
+    READ_LIMITS.with(|l| l.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SsbhArray, SsbhByteBuffer};
+    use binrw::{io::Cursor, BinReaderExt};
+    use hexlit::hex;
+
+    #[test]
+    fn array_within_limit() {
+        let limits = ReadLimits {
+            max_array_length: Some(2),
+            max_buffer_size: None,
+        };
+        let mut reader = Cursor::new(hex!("10000000 00000000 02000000 00000000 0100 0200"));
+        let value = with_read_limits(&limits, || reader.read_le::<SsbhArray<u16>>()).unwrap();
+        assert_eq!(vec![1u16, 2u16], value.elements);
+    }
+
+    #[test]
+    fn array_exceeds_limit() {
+        let limits = ReadLimits {
+            max_array_length: Some(1),
+            max_buffer_size: None,
+        };
+        let mut reader = Cursor::new(hex!("10000000 00000000 02000000 00000000 0100 0200"));
+        let result = with_read_limits(&limits, || reader.read_le::<SsbhArray<u16>>());
+        assert!(result.is_err());
+
+        // The limit no longer applies after returning.
+        reader.set_position(0);
+        assert!(reader.read_le::<SsbhArray<u16>>().is_ok());
+    }
+
+    #[test]
+    fn buffer_exceeds_limit() {
+        let limits = ReadLimits {
+            max_array_length: None,
+            max_buffer_size: Some(1024),
+        };
+        // A huge count fails without attempting to read or allocate the elements.
+        let mut reader = Cursor::new(hex!("10000000 00000000 FFFFFFFF FFFFFFFF"));
+        let result = with_read_limits(&limits, || reader.read_le::<SsbhByteBuffer>());
+        assert!(matches!(
+            result,
+            Err(binrw::Error::AssertFail { pos: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn nested_limits_restored() {
+        let outer = ReadLimits {
+            max_array_length: Some(5),
+            max_buffer_size: None,
+        };
+        let inner = ReadLimits::default();
+        with_read_limits(&outer, || {
+            with_read_limits(&inner, || assert_eq!(inner, current()));
+            assert_eq!(outer, current());
+        });
+        assert_eq!(ReadLimits::default(), current());
+    }
+}
@@ -0,0 +1,281 @@
+//! Parallel reading and round trip testing for all supported files in a folder.
+//!
+//! This is mainly intended for checking the supported formats against a game dump.
+//! Enable the `scan` feature to use this module.
+/*!
+```rust no_run
+use ssbh_lib::scan::{scan_dump, ScanOptions};
+
+for report in scan_dump("root", &ScanOptions::default())? {
+    if report.round_trip == Some(false) {
+        println!("Read/write not 1:1 for {:?}", report.path);
+    }
+}
+# Ok::<(), std::io::Error>(())
+```
+ */
+use std::{
+    fmt::Display,
+    io::Cursor,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    prelude::{Adj, MeshEx},
+    SsbhFile, Version,
+};
+
+/// The glob patterns for the file extensions supported by [scan_dump].
+pub const DEFAULT_PATTERNS: [&str; 3] = [
+    "*.{numatb,numdlb,numshb,nusktb,nurpdb,nufxlb,nuanmb,nuhlpb,nushdb}",
+    "*.numshexb",
+    "*.adjb",
+];
+
+/// Options for [scan_dump].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// Glob patterns for the files to check like `"*.numatb"`.
+    /// Files are read as [MeshEx] for ".numshexb", [Adj] for ".adjb", and [SsbhFile] otherwise.
+    pub patterns: Vec<String>,
+    /// Write each successfully read file and compare with the original bytes.
+    pub check_round_trip: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            patterns: DEFAULT_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            check_round_trip: true,
+        }
+    }
+}
+
+/// The results of reading and optionally writing a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The error message if the file could not be read or written.
+    pub error: Option<String>,
+    /// The major and minor version for SSBH files.
+    pub version: Option<(u16, u16)>,
+    /// `Some(true)` if writing the file produced identical bytes
+    /// or [None] if the round trip was not checked.
+    pub round_trip: Option<bool>,
+    /// The byte offset of the first difference if [round_trip](#structfield.round_trip) is `Some(false)`.
+    pub first_mismatch: Option<u64>,
+    /// The time spent reading the file from disk and parsing.
+    pub read_time: Duration,
+    /// The time spent writing the file if the round trip was checked.
+    pub write_time: Option<Duration>,
+}
+
+/// Recursively reads all files in `root` matching the patterns in `options` in parallel.
+/// The reports are sorted by path.
+///
+/// Returns an error if `root` can't be accessed or the patterns are invalid.
+/// Errors for individual files or folders while scanning are included in the reports.
+pub fn scan_dump<P: AsRef<Path>>(
+    root: P,
+    options: &ScanOptions,
+) -> std::io::Result<Vec<FileReport>> {
+    let root = root.as_ref();
+    std::fs::metadata(root)?;
+
+    let walker = globwalk::GlobWalkerBuilder::from_patterns(root, &options.patterns).build()?;
+
+    let mut reports: Vec<_> = walker
+        .par_bridge()
+        .filter_map(|entry| match entry {
+            Ok(entry) => entry
+                .file_type()
+                .is_file()
+                .then(|| scan_file(entry.path(), options.check_round_trip)),
+            Err(e) => Some(error_report(e.path().unwrap_or(root), &e, Duration::ZERO)),
+        })
+        .collect();
+
+    reports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(reports)
+}
+
+/// Reads and optionally writes the file at `path` using the format from its extension.
+pub fn scan_file<P: AsRef<Path>>(path: P, check_round_trip: bool) -> FileReport {
+    let path = path.as_ref();
+    let start = Instant::now();
+
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => return error_report(path, e, start.elapsed()),
+    };
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("numshexb") => check_file(
+            path,
+            &bytes,
+            start,
+            check_round_trip,
+            |b| MeshEx::read(&mut Cursor::new(b)),
+            |_| None,
+            MeshEx::write,
+        ),
+        Some("adjb") => check_file(
+            path,
+            &bytes,
+            start,
+            check_round_trip,
+            |b| Adj::read(&mut Cursor::new(b)),
+            |_| None,
+            Adj::write,
+        ),
+        _ => check_file(
+            path,
+            &bytes,
+            start,
+            check_round_trip,
+            |b| SsbhFile::read(&mut Cursor::new(b)),
+            |f| Some(f.data.major_minor_version()),
+            SsbhFile::write,
+        ),
+    }
+}
+
+fn error_report<E: Display>(path: &Path, e: E, read_time: Duration) -> FileReport {
+    FileReport {
+        path: path.to_owned(),
+        error: Some(e.to_string()),
+        version: None,
+        round_trip: None,
+        first_mismatch: None,
+        read_time,
+        write_time: None,
+    }
+}
+
+fn check_file<T, E: Display>(
+    path: &Path,
+    bytes: &[u8],
+    start: Instant,
+    check_round_trip: bool,
+    read: impl Fn(&[u8]) -> Result<T, E>,
+    version: impl Fn(&T) -> Option<(u16, u16)>,
+    write: impl Fn(&T, &mut Cursor<Vec<u8>>) -> std::io::Result<()>,
+) -> FileReport {
+    let value = match read(bytes) {
+        Ok(value) => value,
+        Err(e) => return error_report(path, e, start.elapsed()),
+    };
+    let read_time = start.elapsed();
+
+    let mut report = FileReport {
+        path: path.to_owned(),
+        error: None,
+        version: version(&value),
+        round_trip: None,
+        first_mismatch: None,
+        read_time,
+        write_time: None,
+    };
+
+    if check_round_trip {
+        let start = Instant::now();
+        let mut writer = Cursor::new(Vec::new());
+        let result = write(&value, &mut writer);
+        report.write_time = Some(start.elapsed());
+
+        match result {
+            Ok(()) => {
+                let output = writer.into_inner();
+                report.round_trip = Some(output == bytes);
+                report.first_mismatch = first_mismatch(bytes, &output);
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+    }
+
+    report
+}
+
+fn first_mismatch(a: &[u8], b: &[u8]) -> Option<u64> {
+    if a == b {
+        None
+    } else {
+        // Files with different lengths may have a common prefix.
+        let index = a
+            .iter()
+            .zip(b)
+            .position(|(a, b)| a != b)
+            .unwrap_or(a.len().min(b.len()));
+        Some(index as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{formats::hlpb::Hlpb, SsbhArray};
+
+    #[test]
+    fn scan_folder() {
+        let root = std::env::temp_dir().join(format!("ssbh_lib_scan_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("a")).unwrap();
+
+        Hlpb::V11 {
+            aim_constraints: SsbhArray::new(),
+            orient_constraints: SsbhArray::new(),
+            constraint_indices: SsbhArray::new(),
+            constraint_types: SsbhArray::new(),
+        }
+        .write_to_file(root.join("a").join("model.nuhlpb"))
+        .unwrap();
+        std::fs::write(root.join("model.numatb"), [0u8; 4]).unwrap();
+        std::fs::write(root.join("model.txt"), [0u8; 4]).unwrap();
+
+        let reports = scan_dump(&root, &ScanOptions::default()).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(2, reports.len());
+
+        let hlpb = &reports[0];
+        assert_eq!(root.join("a").join("model.nuhlpb"), hlpb.path);
+        assert_eq!(None, hlpb.error);
+        assert_eq!(Some((1, 1)), hlpb.version);
+        assert_eq!(Some(true), hlpb.round_trip);
+        assert_eq!(None, hlpb.first_mismatch);
+        assert!(hlpb.write_time.is_some());
+
+        let matl = &reports[1];
+        assert_eq!(root.join("model.numatb"), matl.path);
+        assert!(matl.error.is_some());
+        assert_eq!(None, matl.version);
+        assert_eq!(None, matl.round_trip);
+    }
+
+    #[test]
+    fn scan_missing_folder() {
+        let root =
+            std::env::temp_dir().join(format!("ssbh_lib_scan_missing_{}", std::process::id()));
+        let result = scan_dump(&root, &ScanOptions::default());
+        assert_eq!(std::io::ErrorKind::NotFound, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn scan_invalid_pattern() {
+        let options = ScanOptions {
+            patterns: vec!["*.{numatb".to_string()],
+            check_round_trip: false,
+        };
+        assert!(scan_dump(std::env::temp_dir(), &options).is_err());
+    }
+
+    #[test]
+    fn first_mismatch_offsets() {
+        assert_eq!(None, first_mismatch(&[1, 2], &[1, 2]));
+        assert_eq!(Some(1), first_mismatch(&[1, 2], &[1, 3]));
+        assert_eq!(Some(2), first_mismatch(&[1, 2], &[1, 2, 3]));
+    }
+}
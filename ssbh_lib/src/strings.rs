@@ -7,9 +7,20 @@ use std::{io::Read, str::FromStr};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// An N-byte aligned [CString] with position determined by a relative offset.
+///
+/// Strings are compared and hashed by their bytes, so null strings are ordered before all other strings.
+/**
+```rust
+use ssbh_lib::SsbhString;
+use std::collections::BTreeSet;
+
+let names: BTreeSet<SsbhString> = ["b", "a", "b"].into_iter().map(SsbhString::from).collect();
+assert_eq!(vec!["a", "b"], names.iter().filter_map(|n| n.to_str()).collect::<Vec<_>>());
+```
+ */
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, PartialEq, Eq, Clone)]
+#[derive(Debug, BinRead, SsbhWrite, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct SsbhStringN<const N: usize>(RelPtr64<CString<N>>);
 
 /// A 4-byte aligned [CString] with position determined by a relative offset.
@@ -24,7 +35,7 @@ pub type CString1 = CString<1>;
 /// A null terminated string with a specified alignment.
 /// The empty string is represented as `N` null bytes.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct CString<const N: usize>(
     // Don't make this public to prevent inserting null bytes.
     #[cfg_attr(
@@ -155,6 +166,38 @@ impl<const N: usize> crate::SsbhWrite for CString<N> {
     }
 }
 
+impl<const N: usize> ssbh_write::SsbhWriteAligned for SsbhStringN<N> {
+    fn ssbh_write_aligned<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+        alignment: u64,
+    ) -> std::io::Result<()> {
+        // The data pointer must point past the containing struct.
+        let current_pos = writer.stream_position()?;
+        if *data_ptr < current_pos + self.size_in_bytes() {
+            *data_ptr = current_pos + self.size_in_bytes();
+        }
+
+        // Empty strings use the field's alignment like SsbhStringN with N = alignment.
+        crate::write_rel_ptr_aligned_specialized(
+            writer,
+            &*self.0,
+            data_ptr,
+            alignment,
+            crate::offset_log::AlignmentSource::Field,
+            |s: &CString<N>, writer, _| {
+                if s.0.is_empty() {
+                    writer.write_all(&vec![0u8; alignment as usize])
+                } else {
+                    writer.write_all(&s.0)?;
+                    writer.write_all(&[0u8])
+                }
+            },
+        )
+    }
+}
+
 impl<const N: usize> SsbhStringN<N> {
     /// Creates the string by reading from `bytes` until the first null byte.
     pub fn from_bytes(bytes: &[u8]) -> Self {
@@ -209,6 +252,34 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn ssbh_string_ordering() {
+        let mut names = vec![
+            SsbhString::from("b"),
+            SsbhString::from(""),
+            SsbhStringN::<4>(RelPtr64::null()),
+            SsbhString::from("ab"),
+        ];
+        names.sort();
+        assert_eq!(
+            vec![
+                SsbhStringN::<4>(RelPtr64::null()),
+                SsbhString::from(""),
+                SsbhString::from("ab"),
+                SsbhString::from("b"),
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn ssbh_string_hash_map_key() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(SsbhString8::from("a"), 1);
+        assert_eq!(Some(&1), map.get(&SsbhString8::from("a")));
+        assert_eq!(None, map.get(&SsbhString8::from("A")));
+    }
+
     #[test]
     fn read_ssbh_string() {
         let mut reader = Cursor::new(hex!(
@@ -399,4 +470,44 @@ mod tests {
         // The data pointer should be aligned to 8.
         assert_eq!(24, data_ptr);
     }
+
+    #[test]
+    fn ssbh_write_aligned_string() {
+        use ssbh_write::SsbhWriteAligned;
+
+        let value = SsbhString::from("BlendState0");
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 5;
+        value
+            .ssbh_write_aligned(&mut writer, &mut data_ptr, 8)
+            .unwrap();
+
+        // This should match SsbhString8.
+        assert_eq!(
+            writer.into_inner(),
+            hex!("08000000 00000000 426C656E 64537461 74653000")
+        );
+        assert_eq!(24, data_ptr);
+    }
+
+    #[test]
+    fn ssbh_write_aligned_string_empty() {
+        use ssbh_write::SsbhWriteAligned;
+
+        let value = SsbhString::from("");
+
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+        value
+            .ssbh_write_aligned(&mut writer, &mut data_ptr, 8)
+            .unwrap();
+
+        // This should match SsbhString8.
+        assert_eq!(
+            writer.into_inner(),
+            hex!("08000000 00000000 00000000 00000000")
+        );
+        assert_eq!(16, data_ptr);
+    }
 }
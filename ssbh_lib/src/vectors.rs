@@ -1,4 +1,5 @@
 use binrw::BinRead;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -326,9 +327,12 @@ impl From<Vector4> for [f32; 4] {
 }
 
 /// 4 contiguous floats for encoding RGBA data.
+///
+/// The channels are always stored in RGBA order.
+/// Prefer the named fields or the conversions to and from `[f32; 4]` over indexing.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
-#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq)]
+#[derive(Debug, BinRead, SsbhWrite, Clone, Copy, PartialEq, Default)]
 pub struct Color4f {
     pub r: f32,
     pub g: f32,
@@ -336,6 +340,60 @@ pub struct Color4f {
     pub a: f32,
 }
 
+impl Color4f {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Color4f {
+        Color4f { r, g, b, a }
+    }
+
+    /// Converts the color channels to an array in RGBA order.
+    /// # Examples
+    /**
+    ```rust
+    # use ssbh_lib::Color4f;
+    assert_eq!([1.0, 0.5, 0.25, 1.0], Color4f::new(1.0, 0.5, 0.25, 1.0).to_array());
+    ```
+     */
+    pub fn to_array(&self) -> [f32; 4] {
+        (*self).into()
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for Color4f {
+    fn from(v: (f32, f32, f32, f32)) -> Self {
+        Self::new(v.0, v.1, v.2, v.3)
+    }
+}
+
+impl From<Color4f> for (f32, f32, f32, f32) {
+    fn from(v: Color4f) -> Self {
+        (v.r, v.g, v.b, v.a)
+    }
+}
+
+impl From<[f32; 4]> for Color4f {
+    fn from(v: [f32; 4]) -> Self {
+        Self::new(v[0], v[1], v[2], v[3])
+    }
+}
+
+impl From<Color4f> for [f32; 4] {
+    fn from(v: Color4f) -> Self {
+        [v.r, v.g, v.b, v.a]
+    }
+}
+
+impl From<Vector4> for Color4f {
+    fn from(v: Vector4) -> Self {
+        Self::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Color4f> for Vector4 {
+    fn from(v: Color4f) -> Self {
+        Self::new(v.r, v.g, v.b, v.a)
+    }
+}
+
 /// A column-major 4x4 matrix of contiguous floats.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -426,6 +484,138 @@ impl Matrix4x4 {
     }
 }
 
+// Component-wise arithmetic for vector types.
+macro_rules! vector_ops_impl {
+    ($ty:ident, $($field:ident),*) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)*
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)*
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),* }
+            }
+        }
+
+        impl Mul<f32> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: f32) -> Self {
+                Self { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl Mul<$ty> for f32 {
+            type Output = $ty;
+
+            fn mul(self, rhs: $ty) -> $ty {
+                rhs * self
+            }
+        }
+
+        impl MulAssign<f32> for $ty {
+            fn mul_assign(&mut self, rhs: f32) {
+                $(self.$field *= rhs;)*
+            }
+        }
+
+        impl Div<f32> for $ty {
+            type Output = Self;
+
+            fn div(self, rhs: f32) -> Self {
+                Self { $($field: self.$field / rhs),* }
+            }
+        }
+
+        impl $ty {
+            /// Returns `true` if the absolute difference of all components is at most `epsilon`.
+            pub fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+                $((self.$field - other.$field).abs() <= epsilon)&&*
+            }
+        }
+    };
+}
+
+vector_ops_impl!(Vector3, x, y, z);
+vector_ops_impl!(Vector4, x, y, z, w);
+
+// Component-wise arithmetic and comparisons for matrix types.
+macro_rules! matrix_ops_impl {
+    ($ty:ident, $array:ty, $($col:ident),*) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($col: self.$col + rhs.$col),* }
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($col: self.$col - rhs.$col),* }
+            }
+        }
+
+        impl Mul<f32> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: f32) -> Self {
+                Self { $($col: self.$col * rhs),* }
+            }
+        }
+
+        impl $ty {
+            /// Returns `true` if the absolute difference of all elements is at most `epsilon`.
+            pub fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+                $(self.$col.abs_diff_eq(&other.$col, epsilon))&&*
+            }
+        }
+
+        impl From<$array> for $ty {
+            fn from(cols: $array) -> Self {
+                Self::from_cols_array(&cols)
+            }
+        }
+
+        impl From<$ty> for $array {
+            fn from(m: $ty) -> Self {
+                m.to_cols_array()
+            }
+        }
+    };
+}
+
+matrix_ops_impl!(Matrix3x3, [[f32; 3]; 3], col1, col2, col3);
+matrix_ops_impl!(Matrix4x4, [[f32; 4]; 4], col1, col2, col3, col4);
+
 #[cfg(test)]
 mod tests {
     use binrw::io::Cursor;
@@ -446,6 +636,31 @@ mod tests {
         assert_eq!(Vector3::new(1.0, 2.0, 3.0), [1.0, 2.0, 3.0].into());
     }
 
+    #[test]
+    fn color4f_conversions() {
+        let color = Color4f::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!((1.0, 2.0, 3.0, 4.0), color.into());
+        assert_eq!([1.0, 2.0, 3.0, 4.0], <[f32; 4]>::from(color));
+        assert_eq!(Vector4::new(1.0, 2.0, 3.0, 4.0), color.into());
+        assert_eq!(color, (1.0, 2.0, 3.0, 4.0).into());
+        assert_eq!(color, [1.0, 2.0, 3.0, 4.0].into());
+        assert_eq!(color, Vector4::new(1.0, 2.0, 3.0, 4.0).into());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn color4f_serde() {
+        let json = serde_json::to_value(Color4f::new(1.0, 0.5, 0.25, 0.0)).unwrap();
+        assert_eq!(
+            serde_json::json!({"r": 1.0, "g": 0.5, "b": 0.25, "a": 0.0}),
+            json
+        );
+        assert_eq!(
+            Color4f::new(1.0, 0.5, 0.25, 0.0),
+            serde_json::from_value(json).unwrap()
+        );
+    }
+
     #[test]
     fn vector4_conversions() {
         assert_eq!(
@@ -466,6 +681,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn vector3_arithmetic() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(4.0, 5.0, 6.0);
+        assert_eq!(Vector3::new(5.0, 7.0, 9.0), a + b);
+        assert_eq!(Vector3::new(3.0, 3.0, 3.0), b - a);
+        assert_eq!(Vector3::new(-1.0, -2.0, -3.0), -a);
+        assert_eq!(Vector3::new(2.0, 4.0, 6.0), a * 2.0);
+        assert_eq!(Vector3::new(2.0, 4.0, 6.0), 2.0 * a);
+        assert_eq!(Vector3::new(0.5, 1.0, 1.5), a / 2.0);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 2.0;
+        assert_eq!(Vector3::new(8.0, 10.0, 12.0), c);
+    }
+
+    #[test]
+    fn vector4_arithmetic() {
+        let a = Vector4::new(1.0, 2.0, 3.0, 4.0);
+        let b = Vector4::new(5.0, 6.0, 7.0, 8.0);
+        assert_eq!(Vector4::new(6.0, 8.0, 10.0, 12.0), a + b);
+        assert_eq!(Vector4::new(4.0, 4.0, 4.0, 4.0), b - a);
+        assert_eq!(Vector4::new(-1.0, -2.0, -3.0, -4.0), -a);
+        assert_eq!(Vector4::new(2.0, 4.0, 6.0, 8.0), a * 2.0);
+        assert_eq!(Vector4::new(0.5, 1.0, 1.5, 2.0), a / 2.0);
+    }
+
+    #[test]
+    fn vector_abs_diff_eq() {
+        let a = Vector4::new(1.0, 2.0, 3.0, 4.0);
+        assert!(a.abs_diff_eq(&Vector4::new(1.0, 2.0, 3.0, 4.0001), 0.001));
+        assert!(!a.abs_diff_eq(&Vector4::new(1.0, 2.0, 3.0, 4.1), 0.001));
+        assert!(Vector3::ZERO.abs_diff_eq(&Vector3::new(0.0, -0.0005, 0.0), 0.001));
+    }
+
+    #[test]
+    fn matrix_arithmetic() {
+        let m = Matrix4x4::identity();
+        assert_eq!(
+            Matrix4x4::from_cols_array(&[
+                [2.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 2.0, 0.0],
+                [0.0, 0.0, 0.0, 2.0]
+            ]),
+            m + m
+        );
+        assert_eq!(m * 2.0, m + m);
+        assert_eq!(
+            Matrix3x3::default(),
+            Matrix3x3::identity() - Matrix3x3::identity()
+        );
+        assert!(m.abs_diff_eq(&(m * 1.0001), 0.001));
+        assert!(!m.abs_diff_eq(&(m * 2.0), 0.001));
+    }
+
+    #[test]
+    fn matrix_conversions() {
+        let cols = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        assert_eq!(Matrix3x3::from_cols_array(&cols), cols.into());
+        assert_eq!(cols, <[[f32; 3]; 3]>::from(Matrix3x3::from(cols)));
+
+        let cols = [[1.0, 0.0, 0.0, 0.0]; 4];
+        assert_eq!(Matrix4x4::from_cols_array(&cols), cols.into());
+        assert_eq!(cols, <[[f32; 4]; 4]>::from(Matrix4x4::from(cols)));
+    }
+
     #[test]
     fn read_vector3() {
         let mut reader = Cursor::new(hex!("0000803F 000000C0 0000003F"));
@@ -1,5 +1,28 @@
+//! Helpers for editing part of a file's JSON representation using a
+//! [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) like `"/Mesh/V10/objects/3/attributes"`.
+//! Pointers start from the root of the JSON, so SSBH files include the format and version keys.
+use serde_json::Value;
+
+/// Returns the sub-tree of `value` at `pointer`.
+pub fn select<'a>(value: &'a Value, pointer: &str) -> Result<&'a Value, String> {
+    value
+        .pointer(pointer)
+        .ok_or_else(|| format!("JSON pointer {pointer:?} does not exist"))
+}
+
+/// Replaces the existing sub-tree of `value` at `pointer` with `new_value`.
+pub fn replace(value: &mut Value, pointer: &str, new_value: Value) -> Result<(), String> {
+    let old_value = value
+        .pointer_mut(pointer)
+        .ok_or_else(|| format!("JSON pointer {pointer:?} does not exist"))?;
+    *old_value = new_value;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use serde_json::json;
     use ssbh_lib::{CString, Ptr64, RelPtr64, SsbhArray, SsbhByteBuffer, SsbhString, SsbhString8};
 
     #[test]
@@ -147,4 +170,39 @@ mod tests {
         let v: SsbhString8 = serde_json::from_str("\"\"").unwrap();
         assert_eq!("", v.to_str().unwrap());
     }
+
+    #[test]
+    fn select_pointer() {
+        let value = json!({"Mesh": {"V10": {"objects": [{"name": "a"}, {"name": "b"}]}}});
+        assert_eq!(
+            &json!({"name": "b"}),
+            select(&value, "/Mesh/V10/objects/1").unwrap()
+        );
+        assert_eq!(&value, select(&value, "").unwrap());
+    }
+
+    #[test]
+    fn select_pointer_missing() {
+        let value = json!({"Mesh": {"V10": {"objects": []}}});
+        assert_eq!(
+            Err("JSON pointer \"/Mesh/V10/objects/0\" does not exist".to_string()),
+            select(&value, "/Mesh/V10/objects/0")
+        );
+    }
+
+    #[test]
+    fn replace_pointer() {
+        let mut value = json!({"Mesh": {"V10": {"objects": [{"name": "a"}, {"name": "b"}]}}});
+        replace(&mut value, "/Mesh/V10/objects/0/name", json!("c")).unwrap();
+        assert_eq!(
+            json!({"Mesh": {"V10": {"objects": [{"name": "c"}, {"name": "b"}]}}}),
+            value
+        );
+    }
+
+    #[test]
+    fn replace_pointer_missing() {
+        let mut value = json!({"Mesh": {"V10": {"objects": []}}});
+        assert!(replace(&mut value, "/Mesh/V10/objects/0", json!({})).is_err());
+    }
 }
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use clap::Parser;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use ssbh_lib::prelude::*;
 
 /// Convert SSBH, Meshex, and Adjb files to and from JSON.
@@ -15,10 +15,25 @@ struct Cli {
     /// The output JSON or binary file path.
     /// Set as <input>.json or inferred from the JSON data if not specified.
     output: Option<String>,
+    /// Only export the JSON at the given JSON pointer like "/Mesh/V10/objects/3/attributes"
+    #[arg(long)]
+    select: Option<String>,
+    /// The JSON file used to replace the JSON at the --select pointer for the binary input.
+    /// The input file is modified if no output is specified.
+    #[arg(long, requires = "select")]
+    replace: Option<String>,
+    /// Export the frame buffers, states, and render passes and their references for an Nrpd file.
+    /// Uses Graphviz DOT if the output has the .dot extension and JSON otherwise.
+    #[arg(long, conflicts_with_all = ["select", "replace"])]
+    graph: bool,
 }
 
-fn read_data_write_json<T, E, P, F>(input_path: P, output_path: Option<String>, read_t: F)
-where
+fn read_data_write_json<T, E, P, F>(
+    input_path: P,
+    output_path: Option<String>,
+    select: Option<&str>,
+    read_t: F,
+) where
     T: Serialize,
     P: AsRef<Path> + ToString,
     F: Fn(P) -> Result<T, E>,
@@ -33,12 +48,97 @@ where
     match read_t(input_path) {
         Ok(adjb) => {
             eprintln!("Parse: {:?}", parse_start_time.elapsed());
-            write_json(output_path, adjb);
+            match select {
+                Some(pointer) => {
+                    let value = serde_json::to_value(adjb).unwrap();
+                    match ssbh_lib_json::select(&value, pointer) {
+                        Ok(selected) => write_json(output_path, selected),
+                        Err(error) => eprintln!("{error}"),
+                    }
+                }
+                None => write_json(output_path, adjb),
+            }
         }
         Err(error) => eprintln!("{error:?}"),
     };
 }
 
+fn read_data_replace_json<T, E, F, W>(
+    input_path: &str,
+    output_path: Option<String>,
+    pointer: &str,
+    replace_path: &str,
+    read_t: F,
+    write_t: W,
+) where
+    T: Serialize + DeserializeOwned,
+    F: Fn(PathBuf) -> Result<T, E>,
+    W: Fn(&T, PathBuf) -> std::io::Result<()>,
+    E: std::fmt::Debug,
+{
+    // Edit the input file in place if no output is specified.
+    let output_path = PathBuf::from(output_path.as_deref().unwrap_or(input_path));
+
+    let parse_start_time = Instant::now();
+    let data = match read_t(PathBuf::from(input_path)) {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("{error:?}");
+            return;
+        }
+    };
+    eprintln!("Parse: {:?}", parse_start_time.elapsed());
+
+    let json = std::fs::read_to_string(replace_path).expect("Failed to read file.");
+    let new_value: serde_json::Value = match serde_json::from_str(&json) {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("{error}");
+            return;
+        }
+    };
+
+    let mut value = serde_json::to_value(data).unwrap();
+    if let Err(error) = ssbh_lib_json::replace(&mut value, pointer, new_value) {
+        eprintln!("{error}");
+        return;
+    }
+
+    match serde_json::from_value::<T>(value) {
+        Ok(data) => write_data(data, output_path, write_t),
+        Err(error) => eprintln!("{error}"),
+    }
+}
+
+fn read_nrpd_write_graph(input_path: &str, output_path: Option<String>) {
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(input_path.to_string() + ".graph.json"));
+
+    let parse_start_time = Instant::now();
+    let graph = match SsbhFile::from_file(input_path) {
+        Ok(SsbhFile {
+            data: Ssbh::Nrpd(nrpd),
+            ..
+        }) => nrpd.data.reference_graph(),
+        Ok(_) => {
+            eprintln!("The input for --graph must be an Nrpd file.");
+            return;
+        }
+        Err(error) => {
+            eprintln!("{error:?}");
+            return;
+        }
+    };
+    eprintln!("Parse: {:?}", parse_start_time.elapsed());
+
+    if output_path.extension().and_then(|e| e.to_str()) == Some("dot") {
+        std::fs::write(output_path, graph.to_dot()).expect("unable to write");
+    } else {
+        write_json(output_path, graph);
+    }
+}
+
 fn write_json<T: Sized + Serialize, P: AsRef<Path>>(output_path: P, object: T) {
     let json = serde_json::to_string_pretty(&object).unwrap();
 
@@ -93,12 +193,53 @@ fn write_data<T, P: AsRef<Path>, F: Fn(&T, P) -> std::io::Result<()>>(
 fn main() {
     let cli = Cli::parse();
 
+    let extension = Path::new(&cli.input).extension().unwrap().to_str().unwrap();
+
+    if let (Some(pointer), Some(replace)) = (&cli.select, &cli.replace) {
+        // Only the binary formats can be edited in place.
+        match extension {
+            "adjb" => read_data_replace_json(
+                &cli.input,
+                cli.output,
+                pointer,
+                replace,
+                Adj::from_file,
+                Adj::write_to_file,
+            ),
+            "numshexb" => read_data_replace_json(
+                &cli.input,
+                cli.output,
+                pointer,
+                replace,
+                MeshEx::from_file,
+                MeshEx::write_to_file,
+            ),
+            "json" => eprintln!("The input for --replace must be a binary file."),
+            _ => read_data_replace_json(
+                &cli.input,
+                cli.output,
+                pointer,
+                replace,
+                SsbhFile::from_file,
+                SsbhFile::write_to_file,
+            ),
+        }
+        return;
+    }
+
+    if cli.graph {
+        read_nrpd_write_graph(&cli.input, cli.output);
+        return;
+    }
+
+    let select = cli.select.as_deref();
+
     // Try parsing one of the supported formats.
-    match Path::new(&cli.input).extension().unwrap().to_str().unwrap() {
-        "adjb" => read_data_write_json(cli.input, cli.output, Adj::from_file),
-        "numshexb" => read_data_write_json(cli.input, cli.output, MeshEx::from_file),
+    match extension {
+        "adjb" => read_data_write_json(cli.input, cli.output, select, Adj::from_file),
+        "numshexb" => read_data_write_json(cli.input, cli.output, select, MeshEx::from_file),
         "json" => read_json_write_data(cli.input, cli.output),
         // Assume anything else is an SSBH file.
-        _ => read_data_write_json(cli.input, cli.output, SsbhFile::from_file),
+        _ => read_data_write_json(cli.input, cli.output, select, SsbhFile::from_file),
     };
 }
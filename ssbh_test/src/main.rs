@@ -1,5 +1,6 @@
 use clap::Parser;
-use rayon::prelude::*;
+use ssbh_lib::offset_log::{record_offset_writes, OffsetWrite};
+use ssbh_lib::scan::{scan_dump, FileReport, ScanOptions};
 use std::{io::Cursor, path::Path};
 
 /// Test read/write for all files recursively in a game dump.
@@ -8,6 +9,10 @@ use std::{io::Cursor, path::Path};
 struct Cli {
     /// The root folder of the game dump
     root_folder: String,
+
+    /// Print the offset write closest to the first mismatched byte for files that aren't 1:1
+    #[arg(long)]
+    offset_log: bool,
 }
 
 fn main() {
@@ -16,84 +21,71 @@ fn main() {
     let folder = Path::new(&cli.root_folder);
     let start = std::time::Instant::now();
 
-    let patterns = ["*.{numatb,numdlb,numshb,nusktb,nurpdb,nufxlb,nuanmb,nuhlpb,nushdb}"];
-    globwalk::GlobWalkerBuilder::from_patterns(folder, &patterns)
-        .build()
-        .unwrap()
-        .filter_map(|p| p.ok())
-        .par_bridge()
-        .for_each(|path| {
-            check_read_write_ssbh(path.path());
-        });
-
-    globwalk::GlobWalkerBuilder::from_patterns(folder, &["*.numshexb"])
-        .build()
-        .unwrap()
-        .filter_map(|p| p.ok())
-        .par_bridge()
-        .for_each(|path| {
-            check_read_write_meshex(path.path());
-        });
+    let reports = match scan_dump(folder, &ScanOptions::default()) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("Error scanning {folder:?}: {e}");
+            std::process::exit(1);
+        }
+    };
 
-    globwalk::GlobWalkerBuilder::from_patterns(folder, &["*.adjb"])
-        .build()
-        .unwrap()
-        .filter_map(|p| p.ok())
-        .par_bridge()
-        .for_each(|path| {
-            check_read_write_adj(path.path());
-        });
+    for report in reports {
+        print_report(&report, cli.offset_log);
+    }
 
     println!("Finished in {:?}", start.elapsed());
 }
 
-fn check_read_write_ssbh(path: &Path) {
-    let before = std::fs::read(path).unwrap();
-    match ssbh_lib::SsbhFile::read(&mut Cursor::new(&before)) {
-        Ok(ssbh) => {
-            // Check any supported file for 1:1 read/write.
-            let mut writer = Cursor::new(Vec::new());
-            ssbh.write(&mut writer).unwrap();
-            if before != writer.into_inner() {
-                println!("Read/write not 1:1 for {path:?}");
+fn print_report(report: &FileReport, offset_log: bool) {
+    let path = &report.path;
+    if report.error.is_some() {
+        println!("Error reading {path:?}");
+    } else if report.round_trip == Some(false) {
+        println!("Read/write not 1:1 for {path:?}");
+
+        if offset_log {
+            if let Some(index) = report.first_mismatch {
+                print_closest_offset_write(path, index);
             }
         }
-        _ => {
-            println!("Error reading {path:?}");
-        }
     }
 }
 
-fn check_read_write_meshex(path: &Path) {
+fn print_closest_offset_write(path: &Path, index: u64) {
     let before = std::fs::read(path).unwrap();
-    match ssbh_lib::formats::meshex::MeshEx::read(&mut Cursor::new(&before)) {
-        Ok(data) => {
-            // Check any supported file for 1:1 read/write.
-            let mut writer = Cursor::new(Vec::new());
-            data.write(&mut writer).unwrap();
-            if before != writer.into_inner() {
-                println!("Read/write not 1:1 for {path:?}");
-            }
+    let mut writer = Cursor::new(Vec::new());
+
+    // Write the file again to find the offsets for the written data.
+    let writes = match path.extension().and_then(|e| e.to_str()) {
+        Some("numshexb") => {
+            let data = ssbh_lib::formats::meshex::MeshEx::read(&mut Cursor::new(&before)).unwrap();
+            record_writes(|| data.write(&mut writer))
+        }
+        Some("adjb") => {
+            let data = ssbh_lib::formats::adj::Adj::read(&mut Cursor::new(&before)).unwrap();
+            record_writes(|| data.write(&mut writer))
         }
         _ => {
-            println!("Error reading {path:?}");
+            let data = ssbh_lib::SsbhFile::read(&mut Cursor::new(&before)).unwrap();
+            record_writes(|| data.write(&mut writer))
         }
+    };
+
+    println!("First mismatch at byte {index} of {}", before.len());
+
+    // The first difference is usually caused by the data for the closest preceding offset.
+    match writes
+        .iter()
+        .filter(|w| w.data_position <= index)
+        .max_by_key(|w| w.data_position)
+    {
+        Some(write) => println!("Closest offset write: {write:?}"),
+        None => println!("No offset writes before mismatch"),
     }
 }
 
-fn check_read_write_adj(path: &Path) {
-    let before = std::fs::read(path).unwrap();
-    match ssbh_lib::formats::adj::Adj::read(&mut Cursor::new(&before)) {
-        Ok(data) => {
-            // Check any supported file for 1:1 read/write.
-            let mut writer = Cursor::new(Vec::new());
-            data.write(&mut writer).unwrap();
-            if before != writer.into_inner() {
-                println!("Read/write not 1:1 for {path:?}");
-            }
-        }
-        _ => {
-            println!("Error reading {path:?}");
-        }
-    }
+fn record_writes<F: FnOnce() -> std::io::Result<()>>(f: F) -> Vec<OffsetWrite> {
+    let (result, writes) = record_offset_writes(f);
+    result.unwrap();
+    writes
 }
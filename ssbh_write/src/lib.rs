@@ -56,6 +56,18 @@ pub trait SsbhWrite: Sized {
         Ok(())
     }
 
+    /// Writes the byte representation of `self` to a `writer` that does not implement [Seek].
+    ///
+    /// This is not a streaming write.
+    /// Writing relative offsets requires seeking backwards,
+    /// so the entire output is first written to an in-memory buffer and then passed to `writer` all at once.
+    /// Memory usage is proportional to the size of the output.
+    fn write_to_stream<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        self.write(&mut buffer)?;
+        writer.write_all(buffer.get_ref())
+    }
+
     /// The offset in bytes between successive elements in an array of this type.
     /// This should include any alignment or padding.
     fn size_in_bytes(&self) -> u64 {
@@ -70,6 +82,24 @@ pub trait SsbhWrite: Sized {
     }
 }
 
+/// A trait for types with data behind an offset that can be written using a different alignment
+/// than the [alignment_in_bytes](SsbhWrite::alignment_in_bytes) of the pointed to type.
+///
+/// Fields using the `data_alignment` attribute for the derive macro like `#[ssbhwrite(data_alignment = 8)]`
+/// are written using this trait.
+/// The derive macro rejects alignments that aren't a power of two like `data_alignment = 0`.
+/// This allows a single type to represent offsets that require different alignments in different formats.
+pub trait SsbhWriteAligned: SsbhWrite {
+    /// Writes the byte representation of `self` to `writer` like [ssbh_write](SsbhWrite::ssbh_write)
+    /// with the data behind the offset aligned to `alignment` bytes.
+    fn ssbh_write_aligned<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+        alignment: u64,
+    ) -> std::io::Result<()>;
+}
+
 impl SsbhWrite for () {
     fn ssbh_write<W: std::io::Write + std::io::Seek>(
         &self,
@@ -100,6 +130,30 @@ impl<T: SsbhWrite, const N: usize> SsbhWrite for [T; N] {
     fn size_in_bytes(&self) -> u64 {
         self.as_slice().size_in_bytes()
     }
+
+    fn alignment_in_bytes() -> u64 {
+        // Use the underlying type's alignment.
+        T::alignment_in_bytes()
+    }
+}
+
+impl<T: SsbhWrite> SsbhWrite for Box<T> {
+    fn ssbh_write<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        self.as_ref().ssbh_write(writer, data_ptr)
+    }
+
+    fn size_in_bytes(&self) -> u64 {
+        self.as_ref().size_in_bytes()
+    }
+
+    fn alignment_in_bytes() -> u64 {
+        // Use the underlying type's alignment.
+        T::alignment_in_bytes()
+    }
 }
 
 impl<T: SsbhWrite> SsbhWrite for &[T] {
@@ -290,12 +344,65 @@ impl<T: SsbhWrite> SsbhWrite for Vec<T> {
     }
 }
 
-// TODO: Implement tuples.
+// Tuples are written like structs with the elements as fields.
+macro_rules! ssbh_write_tuple_impl {
+    ($(($($t:ident $i:tt),+)),*) => {
+        $(
+            impl<$($t: SsbhWrite),+> SsbhWrite for ($($t,)+) {
+                fn ssbh_write<W: std::io::Write + std::io::Seek>(
+                    &self,
+                    writer: &mut W,
+                    data_ptr: &mut u64,
+                ) -> std::io::Result<()> {
+                    // The data pointer must point past the containing tuple.
+                    let current_pos = writer.stream_position()?;
+                    if *data_ptr < current_pos + self.size_in_bytes() {
+                        *data_ptr = current_pos + self.size_in_bytes();
+                    }
+                    $(
+                        self.$i.ssbh_write(writer, data_ptr)?;
+                    )+
+                    Ok(())
+                }
+
+                fn size_in_bytes(&self) -> u64 {
+                    0 $(+ self.$i.size_in_bytes())+
+                }
+
+                fn alignment_in_bytes() -> u64 {
+                    // Use the largest alignment of the elements.
+                    [$($t::alignment_in_bytes()),+].into_iter().max().unwrap_or(1)
+                }
+            }
+        )*
+    };
+}
+
+ssbh_write_tuple_impl!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Cursor;
 
+    #[test]
+    fn write_to_stream_vec() {
+        // Vec<u8> implements Write but not Seek.
+        let mut writer = Vec::new();
+        vec![Some(1u8), None].write_to_stream(&mut writer).unwrap();
+
+        let mut cursor = Cursor::new(Vec::new());
+        vec![Some(1u8), None].write(&mut cursor).unwrap();
+        assert_eq!(cursor.into_inner(), writer);
+    }
+
     #[test]
     fn write_vec_empty() {
         let mut writer = Cursor::new(Vec::new());
@@ -360,4 +467,49 @@ mod tests {
         assert_eq!(0, data_ptr);
         assert_eq!(0, value.size_in_bytes());
     }
+
+    #[test]
+    fn write_array() {
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+
+        let value = [1u16, 2u16];
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(vec![1u8, 0u8, 2u8, 0u8], writer.into_inner());
+        assert_eq!(4, data_ptr);
+        assert_eq!(4, value.size_in_bytes());
+        assert_eq!(2, <[u16; 2] as SsbhWrite>::alignment_in_bytes());
+    }
+
+    #[test]
+    fn write_tuple() {
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+
+        let value = (1u8, 2u32, [3u16; 2]);
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(
+            vec![1u8, 2u8, 0u8, 0u8, 0u8, 3u8, 0u8, 3u8, 0u8],
+            writer.into_inner()
+        );
+        assert_eq!(9, data_ptr);
+        assert_eq!(9, value.size_in_bytes());
+        assert_eq!(4, <(u8, u32, [u16; 2]) as SsbhWrite>::alignment_in_bytes());
+    }
+
+    #[test]
+    fn write_box() {
+        let mut writer = Cursor::new(Vec::new());
+        let mut data_ptr = 0;
+
+        let value = Box::new(Some(1u32));
+        value.ssbh_write(&mut writer, &mut data_ptr).unwrap();
+
+        assert_eq!(vec![1u8, 0u8, 0u8, 0u8], writer.into_inner());
+        assert_eq!(4, data_ptr);
+        assert_eq!(4, value.size_in_bytes());
+        assert_eq!(4, <Box<u32> as SsbhWrite>::alignment_in_bytes());
+    }
 }
@@ -0,0 +1,60 @@
+use std::io::Cursor;
+
+use ssbh_write::SsbhWrite;
+
+trait Tagged {
+    fn tag(&self) -> u8;
+}
+
+#[derive(Debug)]
+struct A(u8);
+
+impl Tagged for A {
+    fn tag(&self) -> u8 {
+        self.0
+    }
+}
+
+impl SsbhWrite for A {
+    fn ssbh_write<W: std::io::Write + std::io::Seek>(
+        &self,
+        writer: &mut W,
+        data_ptr: &mut u64,
+    ) -> std::io::Result<()> {
+        self.tag().ssbh_write(writer, data_ptr)
+    }
+}
+
+#[test]
+fn bound_struct() {
+    #[derive(Debug, SsbhWrite)]
+    #[ssbhwrite(bound = "T: SsbhWrite + Tagged")]
+    struct TestStruct<T> {
+        x: T,
+        y: u8,
+    }
+
+    let value = TestStruct { x: A(1), y: 2 };
+
+    let mut writer = Cursor::new(Vec::new());
+    value.write(&mut writer).unwrap();
+    assert_eq!(vec![1u8, 2u8], writer.into_inner());
+}
+
+#[test]
+fn bound_multiple_parameters() {
+    #[derive(Debug, SsbhWrite)]
+    #[ssbhwrite(bound = "T: SsbhWrite, U: SsbhWrite + std::fmt::Debug")]
+    enum TestEnum<T, U>
+    where
+        T: std::fmt::Debug,
+    {
+        A(T),
+        B { x: U },
+    }
+
+    let mut writer = Cursor::new(Vec::new());
+    TestEnum::<u8, u16>::A(1).write(&mut writer).unwrap();
+    TestEnum::<u8, u16>::B { x: 2 }.write(&mut writer).unwrap();
+    assert_eq!(vec![1u8, 2u8, 0u8], writer.into_inner());
+}
@@ -0,0 +1,81 @@
+use std::io::{Cursor, Seek, Write};
+
+use ssbh_write::{SsbhWrite, SsbhWriteAligned};
+
+// Write the alignment instead of the pointed to data to check the attribute value.
+#[derive(Debug)]
+struct Pointer(u8);
+
+impl SsbhWrite for Pointer {
+    fn ssbh_write<W: Write + Seek>(&self, writer: &mut W, _: &mut u64) -> std::io::Result<()> {
+        writer.write_all(&[self.0, 0])
+    }
+}
+
+impl SsbhWriteAligned for Pointer {
+    fn ssbh_write_aligned<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: &mut u64,
+        alignment: u64,
+    ) -> std::io::Result<()> {
+        writer.write_all(&[self.0, alignment as u8])
+    }
+}
+
+#[test]
+fn data_alignment_struct_field() {
+    #[derive(Debug, SsbhWrite)]
+    struct TestStruct {
+        x: Pointer,
+        #[ssbhwrite(data_alignment = 8)]
+        y: Pointer,
+    }
+
+    let mut writer = Cursor::new(Vec::new());
+    let mut data_ptr = 0;
+
+    TestStruct {
+        x: Pointer(1),
+        y: Pointer(2),
+    }
+    .ssbh_write(&mut writer, &mut data_ptr)
+    .unwrap();
+
+    assert_eq!(vec![1u8, 0u8, 2u8, 8u8], writer.into_inner());
+}
+
+#[test]
+fn data_alignment_enum_named_field() {
+    #[derive(Debug, SsbhWrite)]
+    enum TestEnum {
+        A {
+            #[ssbhwrite(data_alignment = 4)]
+            x: Pointer,
+        },
+    }
+
+    let mut writer = Cursor::new(Vec::new());
+    let mut data_ptr = 0;
+
+    TestEnum::A { x: Pointer(1) }
+        .ssbh_write(&mut writer, &mut data_ptr)
+        .unwrap();
+
+    assert_eq!(vec![1u8, 4u8], writer.into_inner());
+}
+
+#[test]
+fn data_alignment_unnamed_field() {
+    #[derive(Debug, SsbhWrite)]
+    struct TestStruct(#[ssbhwrite(data_alignment = 16)] Pointer);
+
+    let mut writer = Cursor::new(Vec::new());
+    let mut data_ptr = 0;
+
+    TestStruct(Pointer(3))
+        .ssbh_write(&mut writer, &mut data_ptr)
+        .unwrap();
+
+    assert_eq!(vec![3u8, 16u8], writer.into_inner());
+}
@@ -10,8 +10,11 @@ use quote::quote;
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
-    parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Fields, FieldsNamed,
-    FieldsUnnamed, Generics, Ident, LitByteStr, MetaNameValue,
+    parse_macro_input,
+    punctuated::Punctuated,
+    token::Comma,
+    Attribute, Data, DataStruct, DeriveInput, Fields, FieldsNamed, FieldsUnnamed, Generics, Ident,
+    LitByteStr, MetaNameValue, WherePredicate,
 };
 
 #[derive(Default)]
@@ -19,8 +22,10 @@ struct WriteOptions {
     pad_after: Option<usize>,
     align_after: Option<usize>,
     alignment: Option<usize>,
+    data_alignment: Option<usize>,
     repr: Option<Ident>,
     magic: Option<LitByteStr>,
+    bound: Option<Punctuated<WherePredicate, Comma>>,
 }
 
 struct TypeRepr {
@@ -57,6 +62,19 @@ fn get_usize_arg(m: &MetaNameValue) -> Option<usize> {
     }
 }
 
+fn get_bound_arg(m: &MetaNameValue) -> Option<Punctuated<WherePredicate, Comma>> {
+    if let syn::Lit::Str(value) = &m.lit {
+        // ex: #[ssbhwrite(bound = "T: SsbhWrite + Clone")]
+        Some(
+            value
+                .parse_with(Punctuated::parse_terminated)
+                .expect("expected where predicates for bound"),
+        )
+    } else {
+        None
+    }
+}
+
 fn get_byte_string_arg(m: &MetaNameValue) -> Option<LitByteStr> {
     if let syn::Lit::ByteStr(value) = &m.lit {
         Some(value.clone())
@@ -283,7 +301,17 @@ fn get_write_options(attrs: &[Attribute]) -> WriteOptions {
                             "pad_after" => write_options.pad_after = get_usize_arg(&v),
                             "align_after" => write_options.align_after = get_usize_arg(&v),
                             "alignment" => write_options.alignment = get_usize_arg(&v),
+                            "data_alignment" => {
+                                write_options.data_alignment = get_usize_arg(&v);
+                                // Aligning to 0 or other non powers of two would panic when writing.
+                                if let Some(alignment) = write_options.data_alignment {
+                                    if !alignment.is_power_of_two() {
+                                        panic!("data_alignment must be a power of two");
+                                    }
+                                }
+                            }
                             "magic" => write_options.magic = get_byte_string_arg(&v),
+                            "bound" => write_options.bound = get_bound_arg(&v),
                             _ => panic!("Unrecognized attribute"),
                         }
                     }
@@ -301,6 +329,14 @@ fn generate_ssbh_write(
     calculate_size: &TokenStream2,
     write_options: &WriteOptions,
 ) -> TokenStream2 {
+    // Add any user specified bounds to the existing where clause.
+    let mut generics = generics.clone();
+    if let Some(bound) = &write_options.bound {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+    }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // Skip generating code for unspecified parameters.
@@ -350,6 +386,21 @@ fn generate_ssbh_write(
     expanded
 }
 
+fn write_field(field: TokenStream2, field_options: &WriteOptions) -> TokenStream2 {
+    match field_options.data_alignment {
+        // ex: #[ssbhwrite(data_alignment = 8)]
+        Some(alignment) => quote! {
+            {
+                use ssbh_write::SsbhWriteAligned as _;
+                #field.ssbh_write_aligned(writer, data_ptr, #alignment as u64)?;
+            }
+        },
+        None => quote! {
+            #field.ssbh_write(writer, data_ptr)?;
+        },
+    }
+}
+
 fn write_pad_after(write_options: &WriteOptions) -> TokenStream2 {
     match write_options.pad_after {
         Some(num_bytes) => quote! { writer.write_all(&[0u8; #num_bytes])?; },
@@ -362,10 +413,9 @@ fn write_aligned_after(write_options: &WriteOptions) -> TokenStream2 {
         Some(num_bytes) => quote! {
             // Check for divide by 0.
             if #num_bytes > 0 {
-                let round_up = |value, n| ((value + n - 1) / n) * n;
                 // TODO: Is seeking from the end always correct?
-                let current_pos = writer.seek(std::io::SeekFrom::End(0))?;
-                let aligned_pos = round_up(current_pos, #num_bytes as u64);
+                let current_pos: u64 = writer.seek(std::io::SeekFrom::End(0))?;
+                let aligned_pos = current_pos.div_ceil(#num_bytes as u64) * #num_bytes as u64;
                 for _ in 0..(aligned_pos - current_pos) {
                     writer.write_all(&[0u8])?;
                 }
@@ -429,19 +479,16 @@ fn write_named_fields(fields: &FieldsNamed, include_self: bool) -> Vec<TokenStre
             let write_pad_after = write_pad_after(&field_options);
             let write_align_after = write_aligned_after(&field_options);
 
-            if include_self {
-                quote! {
-                    self.#name.ssbh_write(writer, data_ptr)?;
-                    #write_pad_after
-                    #write_align_after
-                }
+            let write_field = if include_self {
+                write_field(quote! { self.#name }, &field_options)
             } else {
-                quote! {
-                    // Assume the same names are used in the match expression.
-                    #name.ssbh_write(writer, data_ptr)?;
-                    #write_pad_after
-                    #write_align_after
-                }
+                // Assume the same names are used in the match expression.
+                write_field(quote! { #name }, &field_options)
+            };
+            quote! {
+                #write_field
+                #write_pad_after
+                #write_align_after
             }
         })
         .collect()
@@ -457,21 +504,18 @@ fn write_unnamed_fields(fields: &FieldsUnnamed, include_self: bool) -> Vec<Token
             let write_pad_after = write_pad_after(&field_options);
             let write_align_after = write_aligned_after(&field_options);
 
-            if include_self {
+            let write_field = if include_self {
                 let name = syn::Index::from(i);
-                quote! {
-                    self.#name.ssbh_write(writer, data_ptr)?;
-                    #write_pad_after
-                    #write_align_after
-                }
+                write_field(quote! { self.#name }, &field_options)
             } else {
                 // Assume the same names are used in the match expression.
                 let name = Ident::new(&format!("v{i}"), Span::call_site());
-                quote! {
-                    #name.ssbh_write(writer, data_ptr)?;
-                    #write_pad_after
-                    #write_align_after
-                }
+                write_field(quote! { #name }, &field_options)
+            };
+            quote! {
+                #write_field
+                #write_pad_after
+                #write_align_after
             }
         })
         .collect()